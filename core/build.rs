@@ -10,6 +10,16 @@ fn main() {
 		feature = "kv-rocksdb",
 		feature = "kv-surrealkv",
 		feature = "kv-surrealcs",
+		feature = "kv-sled",
+		feature = "kv-lmdb",
+		feature = "kv-sqlite",
+		feature = "kv-postgres",
+		feature = "kv-s3",
+		feature = "kv-dynamodb",
+		feature = "kv-redis",
+		feature = "kv-tiered",
+		feature = "kv-opfs",
+		feature = "kv-shard",
 	)) {
 		println!("cargo:rustc-cfg=storage");
 		println!("cargo::rustc-check-cfg=cfg(storage)");
@@ -1,9 +1,11 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::err::Error;
 use crate::key::change;
 use crate::key::debug::Sprintable;
 use crate::kvs::Transaction;
 use crate::vs;
 use crate::vs::Versionstamp;
+use std::collections::HashMap;
 use std::str;
 
 // gc_all_at deletes all change feed entries that become stale at the given timestamp.
@@ -49,23 +51,120 @@ pub async fn gc_ns(tx: &Transaction, ts: u64, ns: &str) -> Result<(), Error> {
 		});
 		// Calculate the maximum changefeed expiration
 		let cf_expiry = db_cf_expiry.max(tb_cf_expiry);
-		// Ignore this database if the expiry is greater
-		if ts < cf_expiry {
-			continue;
+		// Calculate the watermark for tables with a shorter expiration
+		let mut watermarks = HashMap::new();
+		for tb in tbs.as_ref() {
+			// Get the table changefeed expiration
+			let tb_cf_expiry = match &tb.changefeed {
+				Some(cf) if !cf.expiry.is_zero() => db_cf_expiry.max(cf.expiry.as_secs()),
+				_ => continue,
+			};
+			// Ignore this table if the expiry is not shorter
+			if tb_cf_expiry >= cf_expiry || ts < tb_cf_expiry {
+				continue;
+			}
+			// Calculate the watermark versionstamp
+			let watermark_vs = tx
+				.lock()
+				.await
+				.get_versionstamp_from_timestamp(ts - tb_cf_expiry, ns, &db.name)
+				.await?;
+			// If a versionstamp exists, then garbage collect
+			if let Some(watermark_vs) = watermark_vs {
+				watermarks.insert(tb.name.to_raw(), watermark_vs);
+			}
+		}
+		// Garbage collect the whole database once expired
+		if ts >= cf_expiry {
+			// Calculate the watermark expiry window
+			let watermark_ts = ts - cf_expiry;
+			// Calculate the watermark versionstamp
+			let watermark_vs =
+				tx.lock().await.get_versionstamp_from_timestamp(watermark_ts, ns, &db.name).await?;
+			// If a versionstamp exists, then garbage collect
+			if let Some(watermark_vs) = watermark_vs {
+				gc_range(tx, ns, &db.name, watermark_vs).await?;
+			}
 		}
-		// Calculate the watermark expiry window
-		let watermark_ts = ts - cf_expiry;
-		// Calculate the watermark versionstamp
-		let watermark_vs =
-			tx.lock().await.get_versionstamp_from_timestamp(watermark_ts, ns, &db.name).await?;
-		// If a versionstamp exists, then garbage collect
-		if let Some(watermark_vs) = watermark_vs {
-			gc_range(tx, ns, &db.name, watermark_vs).await?;
+		// Garbage collect the tables with a shorter expiry
+		if !watermarks.is_empty() {
+			gc_tables(tx, ns, &db.name, &watermarks).await?;
 		}
 	}
 	Ok(())
 }
 
+// gc_tables deletes the change feed entries for the given tables that are older than each table's watermark.
+#[instrument(level = "trace", target = "surrealdb::core::cfs", skip(tx))]
+pub async fn gc_tables(
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+	watermarks: &HashMap<String, Versionstamp>,
+) -> Result<(), Error> {
+	// Calculate the range covering all of the watermarks
+	let Some(vt) = watermarks.values().max() else {
+		return Ok(());
+	};
+	let beg = change::prefix_ts(ns, db, vs::u64_to_versionstamp(0));
+	let end = change::prefix_ts(ns, db, *vt);
+	// Trace for debugging
+	trace!(
+		"Performing garbage collection on {ns}:{db} tables, between {} and {}",
+		beg.sprint(),
+		end.sprint()
+	);
+	// Delete the expired table entries in grouped batches
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = tx.batch(rng, *NORMAL_FETCH_SIZE, false, None).await?;
+		next = res.next;
+		for (k, _) in res.values.iter() {
+			// Decode the changefeed entry key
+			let cf = change::Cf::decode(k)?;
+			// Check if the entry is older than the table watermark
+			if watermarks.get(cf.tb).is_some_and(|vt| cf.vs < *vt) {
+				tx.del(k.clone()).await?;
+			}
+		}
+	}
+	// Ok all good
+	Ok(())
+}
+
+// size calculates the number of change feed entries, and their size in
+// bytes, which are stored for the given database, or for a single table.
+pub async fn size(
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+	tb: Option<&str>,
+) -> Result<(u64, u64), Error> {
+	// Calculate the range of the database change feed
+	let beg = change::prefix(ns, db);
+	let end = change::suffix(ns, db);
+	// Count the entries in grouped batches
+	let mut entries = 0;
+	let mut bytes = 0;
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let res = tx.batch(rng, *NORMAL_FETCH_SIZE, true, None).await?;
+		next = res.next;
+		for (k, v) in res.values.iter() {
+			// Check the change is for the desired table
+			if let Some(tb) = tb {
+				if change::Cf::decode(k)?.tb != tb {
+					continue;
+				}
+			}
+			entries += 1;
+			bytes += (k.len() + v.len()) as u64;
+		}
+	}
+	// Ok all good
+	Ok((entries, bytes))
+}
+
 // gc_db deletes all change feed entries in the given database that are older than the given watermark.
 #[instrument(level = "trace", target = "surrealdb::core::cfs", skip(tx))]
 pub async fn gc_range(tx: &Transaction, ns: &str, db: &str, vt: Versionstamp) -> Result<(), Error> {
@@ -13,7 +13,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[non_exhaustive]
 pub enum TableMutation {
@@ -31,6 +31,11 @@ pub enum TableMutation {
 	#[revision(start = 2)]
 	/// Delete a record where the ID is stored, and the now-deleted value
 	DelWithOriginal(Thing, Value),
+	#[revision(start = 3)]
+	/// Update a record which already existed, without the changes from the original value.
+	/// Records which did not previously exist are recorded with Set, so that creations
+	/// can be distinguished from updates.
+	Update(Thing, Value),
 }
 
 impl From<DefineTableStatement> for Value {
@@ -93,6 +98,10 @@ impl TableMutation {
 				}
 				h
 			}
+			TableMutation::Update(_thing, v) => {
+				h.insert("update".to_string(), v);
+				h
+			}
 			TableMutation::SetWithDiff(_thing, current, operations) => {
 				h.insert("current".to_string(), current);
 				h.insert(
@@ -162,6 +171,7 @@ impl Display for TableMutation {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			TableMutation::Set(id, v) => write!(f, "SET {} {}", id, v),
+			TableMutation::Update(id, v) => write!(f, "SET {} {}", id, v),
 			TableMutation::SetWithDiff(id, _previous, v) => write!(f, "SET {} {:?}", id, v),
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
 			TableMutation::DelWithOriginal(id, _) => write!(f, "DEL {}", id),
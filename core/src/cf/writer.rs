@@ -95,7 +95,10 @@ impl Writer {
 							)
 						}
 					}
-					false => TableMutation::Set(id, current.into_owned()),
+					false => match previous.as_ref().is_none() {
+						true => TableMutation::Set(id, current.into_owned()),
+						false => TableMutation::Update(id, current.into_owned()),
+					},
 				},
 			);
 		} else {
@@ -33,6 +33,10 @@ pub static MAX_QUERY_PARSING_DEPTH: LazyLock<u32> =
 pub static REGEX_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_REGEX_CACHE_SIZE", usize, 1_000);
 
+/// Specifies the number of prepared statements which can be cached in the engine.
+pub static PREPARED_STATEMENT_CACHE_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PREPARED_STATEMENT_CACHE_SIZE", usize, 1_000);
+
 /// Specifies the number of items which can be cached within a single transaction.
 pub static TRANSACTION_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_TRANSACTION_CACHE_SIZE", usize, 10_000);
@@ -69,6 +73,11 @@ pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
 pub static EXTERNAL_SORTING_BUFFER_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_EXTERNAL_SORTING_BUFFER_LIMIT", usize, 50_000);
 
+#[cfg(storage)]
+/// The number of records which a query can hold in memory when grouping or ordering, before spilling to temporary files (defaults to 1,000,000).
+pub static EXTERNAL_MEMORY_BUDGET: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_EXTERNAL_MEMORY_BUDGET", usize, 1_000_000);
+
 /// Specifies whether GraphQL querying and schema definition is enabled.
 pub static GRAPHQL_ENABLE: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_EXPERIMENTAL_GRAPHQL", bool, false);
@@ -85,6 +94,10 @@ pub static EXPERIMENTAL_BEARER_ACCESS: LazyLock<bool> =
 #[cfg(test)]
 pub static EXPERIMENTAL_BEARER_ACCESS: LazyLock<bool> = LazyLock::new(|| true);
 
+/// Specifies how long (in seconds) a revoked token or session is remembered, when no DURATION is specified.
+pub static REVOCATION_DURATION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_REVOCATION_DURATION", u64, 2592000);
+
 /// Used to limit allocation for builtin functions
 pub static GENERATION_ALLOCATION_LIMIT: LazyLock<usize> = LazyLock::new(|| {
 	let n = std::env::var("SURREAL_GENERATION_ALLOCATION_LIMIT")
@@ -92,3 +105,85 @@ pub static GENERATION_ALLOCATION_LIMIT: LazyLock<usize> = LazyLock::new(|| {
 		.unwrap_or(20);
 	2usize.pow(n)
 });
+
+/// Specifies whether committed mutations are recorded in a changelog for embedded storage engines.
+pub static CHANGELOG_ENABLED: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_CHANGELOG_ENABLED", bool, false);
+
+/// Specifies how long (in seconds) changelog entries are retained for embedded storage engines.
+pub static CHANGELOG_RETENTION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_CHANGELOG_RETENTION", u64, 86400);
+
+/// The size (in bytes) above which values are split into chunks in a separate keyspace.
+pub static BLOB_CHUNK_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_BLOB_CHUNK_SIZE", usize, 1024 * 1024);
+
+/// The URL of an object store in which large values are stored, instead of the datastore (empty = disabled).
+pub static BLOB_STORE: LazyLock<String> = lazy_env_parse!("SURREAL_BLOB_STORE", String);
+
+/// Specifies whether approximate storage usage is tracked for each namespace and database.
+pub static STORAGE_USAGE_ENABLED: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_STORAGE_USAGE_ENABLED", bool, false);
+
+/// The maximum on-disk size (in bytes) of local storage engines, above which writes fail (0 = unlimited).
+pub static STORAGE_QUOTA: LazyLock<u64> = lazy_env_parse!("SURREAL_STORAGE_QUOTA", u64, 0);
+
+/// The maximum number of keys which are removed in a single background maintenance transaction.
+pub static MAINTENANCE_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_MAINTENANCE_BATCH_SIZE", u32, 1000);
+
+/// The delay (in milliseconds) between background maintenance transactions, to limit their impact.
+pub static MAINTENANCE_BATCH_DELAY: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_MAINTENANCE_BATCH_DELAY", u64, 10);
+
+/// Specifies how long (in seconds) superseded versions are retained for versioned storage engines (0 = forever).
+pub static VERSIONED_RETENTION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_VERSIONED_RETENTION", u64, 0);
+
+/// The compression factor of the digests which estimate percentiles in GROUP BY queries (higher = more accurate).
+pub static GROUP_DIGEST_COMPRESSION: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GROUP_DIGEST_COMPRESSION", usize, 200);
+
+/// The number of records which are checked in a single batched read before a bulk INSERT statement is processed.
+pub static INSERT_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_INSERT_BATCH_SIZE", usize, 1000);
+
+/// The maximum number of queued asynchronous events which are processed in a single run of the event queue.
+pub static EVENT_QUEUE_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_EVENT_QUEUE_BATCH_SIZE", u32, 100);
+
+/// The maximum number of attempts to process an asynchronous event before it is moved to the dead-letter table.
+pub static EVENT_QUEUE_MAX_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_EVENT_QUEUE_MAX_ATTEMPTS", u32, 5);
+
+/// The initial delay (in milliseconds) before a failed asynchronous event is retried, doubled after each attempt.
+pub static EVENT_QUEUE_RETRY_DELAY: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_EVENT_QUEUE_RETRY_DELAY", u64, 1000);
+
+/// The table into which asynchronous events are written once they have exhausted all attempts.
+pub static EVENT_DEAD_LETTER_TABLE: LazyLock<String> =
+	lazy_env_parse_or_else!("SURREAL_EVENT_DEAD_LETTER_TABLE", String, |_| "event_dead_letter"
+		.to_string());
+
+/// The maximum number of changesets which are delivered to a changefeed sink in a single run.
+pub static SINK_BATCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_SINK_BATCH_SIZE", u32, 100);
+
+/// The maximum number of queued webhooks which are delivered in a single run.
+pub static WEBHOOK_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_WEBHOOK_BATCH_SIZE", u32, 100);
+
+/// The maximum number of attempts to deliver a webhook before it is marked as failed.
+pub static WEBHOOK_MAX_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_WEBHOOK_MAX_ATTEMPTS", u32, 5);
+
+/// The initial delay (in milliseconds) before a failed webhook is retried, doubled after each attempt.
+pub static WEBHOOK_RETRY_DELAY: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_WEBHOOK_RETRY_DELAY", u64, 1000);
+
+/// The maximum duration (in seconds) of each attempt to deliver a webhook.
+pub static WEBHOOK_TIMEOUT: LazyLock<u64> = lazy_env_parse!("SURREAL_WEBHOOK_TIMEOUT", u64, 10);
+
+/// The table in which the delivery status of each webhook is recorded.
+pub static WEBHOOK_DELIVERY_TABLE: LazyLock<String> =
+	lazy_env_parse_or_else!("SURREAL_WEBHOOK_DELIVERY_TABLE", String, |_| "webhook_delivery"
+		.to_string());
@@ -1,9 +1,10 @@
 use crate::cnf::PROTECTED_PARAM_NAMES;
 use crate::ctx::canceller::Canceller;
+use crate::ctx::deadline::Deadline;
 use crate::ctx::reason::Reason;
 #[cfg(feature = "http")]
 use crate::dbs::capabilities::NetTarget;
-use crate::dbs::{Capabilities, Notification};
+use crate::dbs::{Capabilities, RunningQueries};
 use crate::err::Error;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
@@ -12,7 +13,6 @@ use crate::idx::trees::store::IndexStores;
 use crate::kvs::IndexBuilder;
 use crate::kvs::Transaction;
 use crate::sql::value::Value;
-use async_channel::Sender;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
@@ -49,8 +49,6 @@ pub struct MutableContext {
 	cancelled: Arc<AtomicBool>,
 	// A collection of read only values stored in this context.
 	values: HashMap<Cow<'static, str>, Arc<Value>>,
-	// Stores the notification channel if available
-	notifications: Option<Sender<Notification>>,
 	// An optional query planner
 	query_planner: Option<Arc<QueryPlanner>>,
 	// An optional query executor
@@ -59,6 +57,8 @@ pub struct MutableContext {
 	iteration_stage: Option<IterationStage>,
 	// The index store
 	index_stores: IndexStores,
+	// The queries which are running on this datastore
+	running_queries: Option<RunningQueries>,
 	// The index concurrent builders
 	#[cfg(not(target_arch = "wasm32"))]
 	index_builder: Option<IndexBuilder>,
@@ -111,12 +111,12 @@ impl MutableContext {
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
-			notifications: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
 			capabilities: Arc::new(capabilities),
 			index_stores,
+			running_queries: None,
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: Some(index_builder),
 			#[cfg(storage)]
@@ -136,12 +136,12 @@ impl MutableContext {
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
-			notifications: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
 			capabilities: Arc::new(Capabilities::default()),
 			index_stores: IndexStores::default(),
+			running_queries: None,
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: None,
 			#[cfg(storage)]
@@ -157,12 +157,12 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
-			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
 			capabilities: parent.capabilities.clone(),
 			index_stores: parent.index_stores.clone(),
+			running_queries: parent.running_queries.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: parent.index_builder.clone(),
 			#[cfg(storage)]
@@ -187,12 +187,12 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
-			notifications: parent.notifications.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
 			capabilities: parent.capabilities.clone(),
 			index_stores: parent.index_stores.clone(),
+			running_queries: parent.running_queries.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: parent.index_builder.clone(),
 			#[cfg(storage)]
@@ -209,12 +209,12 @@ impl MutableContext {
 			values: HashMap::default(),
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
-			notifications: from.notifications.clone(),
 			query_planner: from.query_planner.clone(),
 			query_executor: from.query_executor.clone(),
 			iteration_stage: from.iteration_stage.clone(),
 			capabilities: from.capabilities.clone(),
 			index_stores: from.index_stores.clone(),
+			running_queries: from.running_queries.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: from.index_builder.clone(),
 			#[cfg(storage)]
@@ -263,12 +263,6 @@ impl MutableContext {
 		}
 	}
 
-	/// Add the LIVE query notification channel to the context, so that we
-	/// can send notifications to any subscribers.
-	pub fn add_notifications(&mut self, chn: Option<&Sender<Notification>>) {
-		self.notifications = chn.cloned()
-	}
-
 	pub(crate) fn set_query_planner(&mut self, qp: QueryPlanner) {
 		self.query_planner = Some(Arc::new(qp));
 	}
@@ -297,12 +291,9 @@ impl MutableContext {
 		self.deadline.map(|v| v.saturating_duration_since(Instant::now()))
 	}
 
-	pub fn notifications(&self) -> Option<Sender<Notification>> {
-		self.notifications.clone()
-	}
-
-	pub fn has_notifications(&self) -> bool {
-		self.notifications.is_some()
+	/// Bound a stream so that it is aborted when this context times out.
+	pub(crate) fn bound_stream<S>(&self, stream: S) -> Deadline<S> {
+		Deadline::new(stream, self.timeout())
 	}
 
 	pub(crate) fn get_query_planner(&self) -> Option<&QueryPlanner> {
@@ -318,6 +309,15 @@ impl MutableContext {
 	}
 
 	/// Get the index_store for this context/ds
+	pub(crate) fn set_running_queries(&mut self, queries: RunningQueries) {
+		self.running_queries = Some(queries);
+	}
+
+	/// Get the registry of queries which are running on this datastore
+	pub(crate) fn get_running_queries(&self) -> Option<&RunningQueries> {
+		self.running_queries.as_ref()
+	}
+
 	pub(crate) fn get_index_stores(&self) -> &IndexStores {
 		&self.index_stores
 	}
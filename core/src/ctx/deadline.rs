@@ -0,0 +1,104 @@
+use crate::err::Error;
+use futures::Future;
+use futures::Stream;
+use futures::StreamExt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::{sleep, Sleep};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep, Sleep};
+
+/// A stream which is aborted once a deadline passes.
+///
+/// Any pending request to the underlying stream is dropped when the
+/// deadline is reached, so that a slow key-value scan does not hold
+/// up a query which has already exceeded its timeout.
+pub struct Deadline<S> {
+	/// The stream which is being bounded
+	stream: S,
+	/// The timer which fires when the deadline is reached
+	timer: Option<Pin<Box<Sleep>>>,
+	/// Whether the deadline has already been reached
+	expired: bool,
+}
+
+impl<S> Deadline<S> {
+	pub fn new(stream: S, timeout: Option<Duration>) -> Self {
+		Self {
+			stream,
+			timer: Self::timer(timeout),
+			expired: false,
+		}
+	}
+
+	fn timer(timeout: Option<Duration>) -> Option<Pin<Box<Sleep>>> {
+		let timeout = timeout?;
+		// Timers can only be created within an async runtime, so streams which
+		// are polled on the parallel iterator threads rely on per-entry checks
+		#[cfg(not(target_arch = "wasm32"))]
+		tokio::runtime::Handle::try_current().ok()?;
+		Some(Box::pin(sleep(timeout)))
+	}
+}
+
+impl<S, T> Stream for Deadline<S>
+where
+	S: Stream<Item = Result<T, Error>> + Unpin,
+{
+	type Item = Result<T, Error>;
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+		// The stream has already been aborted
+		if self.expired {
+			return Poll::Ready(None);
+		}
+		// Return the next entry if it is ready
+		if let Poll::Ready(v) = self.stream.poll_next_unpin(cx) {
+			return Poll::Ready(v);
+		}
+		// Otherwise abort the stream once the deadline passes
+		match self.timer.as_mut().map(|t| t.as_mut().poll(cx)) {
+			Some(Poll::Ready(())) => {
+				self.expired = true;
+				Poll::Ready(Some(Err(Error::QueryTimedout)))
+			}
+			_ => Poll::Pending,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Deadline;
+	use crate::err::Error;
+	use futures::stream::{iter, pending};
+	use futures::StreamExt;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn deadline_aborts_pending_stream() {
+		let stream = pending::<Result<u32, Error>>();
+		let mut stream = Deadline::new(stream, Some(Duration::from_millis(10)));
+		assert!(matches!(stream.next().await, Some(Err(Error::QueryTimedout))));
+		assert!(stream.next().await.is_none());
+	}
+
+	#[tokio::test]
+	async fn deadline_passes_ready_entries() {
+		let stream = iter(vec![Ok::<_, Error>(1), Ok(2)]);
+		let stream = Deadline::new(stream, Some(Duration::ZERO));
+		let res: Vec<u32> = stream.map(|v| v.unwrap()).collect().await;
+		assert_eq!(res, vec![1, 2]);
+	}
+
+	#[tokio::test]
+	async fn deadline_without_timeout() {
+		let stream = iter(vec![Ok::<_, Error>(1)]);
+		let mut stream = Deadline::new(stream, None);
+		assert!(matches!(stream.next().await, Some(Ok(1))));
+		assert!(stream.next().await.is_none());
+	}
+}
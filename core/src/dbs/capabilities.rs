@@ -125,17 +125,29 @@ impl fmt::Display for NetTarget {
 	}
 }
 
+/// Checks if a host matches an allowed host, where a domain in the
+/// form of `*.example.com` matches any subdomain of `example.com`
+fn host_matches(allowed: &url::Host<String>, host: &url::Host<String>) -> bool {
+	match (allowed, host) {
+		(url::Host::Domain(allowed), url::Host::Domain(host)) => match allowed.strip_prefix('*') {
+			Some(suffix) if suffix.starts_with('.') => host.ends_with(suffix),
+			_ => allowed == host,
+		},
+		(allowed, host) => allowed == host,
+	}
+}
+
 impl Target for NetTarget {
 	fn matches(&self, elem: &Self) -> bool {
 		match self {
 			// If self contains a host and port, the elem must match both the host and port
 			Self::Host(host, Some(port)) => match elem {
-				Self::Host(_host, Some(_port)) => host == _host && port == _port,
+				Self::Host(_host, Some(_port)) => host_matches(host, _host) && port == _port,
 				_ => false,
 			},
 			// If self contains a host but no port, the elem must match the host only
 			Self::Host(host, None) => match elem {
-				Self::Host(_host, _) => host == _host,
+				Self::Host(_host, _) => host_matches(host, _host),
 				_ => false,
 			},
 			// If self is an IPNet, it can match both an IPNet or a Host elem that contains an IPAddr
@@ -593,6 +605,26 @@ mod tests {
 			.unwrap()
 			.matches(&NetTarget::from_str("example.com").unwrap()));
 
+		// Host domain with a wildcard
+		assert!(NetTarget::from_str("*.example.com")
+			.unwrap()
+			.matches(&NetTarget::from_str("www.example.com").unwrap()));
+		assert!(NetTarget::from_str("*.example.com")
+			.unwrap()
+			.matches(&NetTarget::from_str("api.eu.example.com:443").unwrap()));
+		assert!(!NetTarget::from_str("*.example.com")
+			.unwrap()
+			.matches(&NetTarget::from_str("example.com").unwrap()));
+		assert!(!NetTarget::from_str("*.example.com")
+			.unwrap()
+			.matches(&NetTarget::from_str("www.badexample.com").unwrap()));
+		assert!(NetTarget::from_str("*.example.com:443")
+			.unwrap()
+			.matches(&NetTarget::from_str("www.example.com:443").unwrap()));
+		assert!(!NetTarget::from_str("*.example.com:443")
+			.unwrap()
+			.matches(&NetTarget::from_str("www.example.com:80").unwrap()));
+
 		// Host IPv4 with and without port
 		assert!(
 			NetTarget::from_str("127.0.0.1")
@@ -1,5 +1,6 @@
 use crate::ctx::reason::Reason;
 use crate::ctx::Context;
+use crate::ctx::MutableContext;
 use crate::dbs::response::Response;
 use crate::dbs::Force;
 use crate::dbs::Options;
@@ -22,12 +23,8 @@ use reblessive::TreeStack;
 use std::pin::{pin, Pin};
 use std::sync::Arc;
 use std::time::Duration;
-#[cfg(not(target_arch = "wasm32"))]
-use tokio::spawn;
 use tracing::instrument;
 use trice::Instant;
-#[cfg(target_arch = "wasm32")]
-use wasm_bindgen_futures::spawn_local as spawn;
 
 const TARGET: &str = "surrealdb::core::dbs";
 
@@ -178,14 +175,13 @@ impl Executor {
 		match stmt {
 			// These statements don't need a transaction.
 			Statement::Use(stmt) => self.execute_use_statement(stmt).map(|_| Value::None),
+			// These statements can only be used within a transaction block.
+			Statement::Savepoint(_) | Statement::Rollback(_) => {
+				Err(Error::SavepointOutsideTransaction)
+			}
 			stmt => {
 				let writeable = stmt.writeable();
 				let txn = Arc::new(kvs.transaction(writeable.into(), LockType::Optimistic).await?);
-				let receiver = self.ctx.has_notifications().then(|| {
-					let (send, recv) = async_channel::unbounded();
-					self.opt.sender = Some(send);
-					recv
-				});
 
 				match self.execute_transaction_statement(txn.clone(), stmt).await {
 					Ok(value)
@@ -210,26 +206,13 @@ impl Executor {
 							});
 						}
 
+						// any notifications are delivered once committed.
 						if let Err(e) = lock.commit().await {
 							return Err(Error::QueryNotExecutedDetail {
 								message: e.to_string(),
 							});
 						}
 
-						// flush notifications.
-						if let Some(recv) = receiver {
-							self.opt.sender = None;
-							if let Some(sink) = self.ctx.notifications() {
-								spawn(async move {
-									while let Ok(x) = recv.recv().await {
-										if sink.send(x).await.is_err() {
-											break;
-										}
-									}
-								});
-							}
-						}
-
 						Ok(value)
 					}
 					Err(e) => {
@@ -272,16 +255,13 @@ impl Executor {
 			return Ok(());
 		};
 
-		// Create a sender for this transaction only if the context allows for notifications.
-		let receiver = self.ctx.has_notifications().then(|| {
-			let (send, recv) = async_channel::unbounded();
-			self.opt.sender = Some(send);
-			recv
-		});
-
 		let txn = Arc::new(txn);
 		let start_results = self.results.len();
 		let mut skip_remaining = false;
+		// The named savepoints, and the number of results when each was created
+		let mut savepoints: Vec<(String, usize)> = Vec::new();
+		// Whether a statement failed, and the transaction needs to be rolled back to a savepoint
+		let mut failed = false;
 
 		// loop over the statements until we hit a cancel or a commit statement.
 		while let Some(stmt) = stream.next().await {
@@ -329,6 +309,20 @@ impl Executor {
 				continue;
 			}
 
+			// After a failed statement, only a rollback to a savepoint can recover the transaction
+			if failed
+				&& !matches!(
+					stmt,
+					Statement::Cancel(_) | Statement::Commit(_) | Statement::Rollback(_)
+				) {
+				self.results.push(Response {
+					time: Duration::ZERO,
+					result: Err(Error::QueryNotExecuted),
+					query_type: QueryType::Other,
+				});
+				continue;
+			}
+
 			trace!(target: TARGET, statement = %stmt, "Executing statement");
 
 			let query_type = match stmt {
@@ -358,8 +352,6 @@ impl Executor {
 						query_type: QueryType::Other,
 					});
 
-					self.opt.sender = None;
-
 					while let Some(stmt) = stream.next().await {
 						let stmt = stmt?;
 						if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
@@ -385,7 +377,18 @@ impl Executor {
 						res.result = Err(Error::QueryCancelled);
 					}
 
-					self.opt.sender = None;
+					return Ok(());
+				}
+				Statement::Commit(_) if failed => {
+					let _ = txn.cancel().await;
+
+					// a statement failed, and was not rolled back to a savepoint.
+					for res in &mut self.results[start_results..] {
+						if res.result.is_ok() {
+							res.query_type = QueryType::Other;
+							res.result = Err(Error::QueryNotExecuted);
+						}
+					}
 
 					return Ok(());
 				}
@@ -400,22 +403,7 @@ impl Executor {
 					} else if let Err(e) = lock.commit().await {
 						e
 					} else {
-						// Successfully commited. everything is fine.
-
-						// flush notifications.
-						if let Some(recv) = receiver {
-							self.opt.sender = None;
-							if let Some(sink) = self.ctx.notifications() {
-								spawn(async move {
-									while let Ok(x) = recv.recv().await {
-										if sink.send(x).await.is_err() {
-											break;
-										}
-									}
-								});
-							}
-						}
-
+						// Successfully commited, and notifications are delivered.
 						return Ok(());
 					};
 
@@ -427,8 +415,6 @@ impl Executor {
 						});
 					}
 
-					self.opt.sender = None;
-
 					return Ok(());
 				}
 				Statement::Option(stmt) => match self.execute_option_statement(stmt) {
@@ -440,6 +426,47 @@ impl Executor {
 					Err(e) => Err(e),
 				},
 				Statement::Use(stmt) => self.execute_use_statement(stmt).map(|_| Value::None),
+				Statement::Savepoint(stmt) => {
+					txn.lock().await.new_save_point().await;
+					savepoints.push((stmt.name.0, self.results.len() + 1));
+					Ok(Value::None)
+				}
+				Statement::Rollback(stmt) => {
+					match savepoints.iter().rposition(|(name, _)| *name == stmt.name.0) {
+						Some(pos) => {
+							let mut lock = txn.lock().await;
+							// Undo this savepoint, and any savepoints created after it
+							for _ in pos..savepoints.len() {
+								if let Err(e) = lock.rollback_to_save_point().await {
+									let _ = lock.cancel().await;
+									return Err(e);
+								}
+							}
+							// Any cached definitions and permissions may have been undone
+							txn.clear();
+							// The savepoint remains, so that it can be rolled back to again
+							lock.new_save_point().await;
+							savepoints.truncate(pos + 1);
+							// Any results since the savepoint have been undone
+							for res in self.results.iter_mut().skip(savepoints[pos].1) {
+								if res.result.is_ok() {
+									res.query_type = QueryType::Other;
+									res.result = Err(Error::QueryRolledBack {
+										name: stmt.name.0.clone(),
+									});
+								}
+							}
+							failed = false;
+							Ok(Value::None)
+						}
+						None => {
+							failed = true;
+							Err(Error::SavepointNotFound {
+								name: stmt.name.0,
+							})
+						}
+					}
+				}
 				stmt => {
 					skip_remaining = matches!(stmt, Statement::Output(_));
 
@@ -451,6 +478,11 @@ impl Executor {
 							skip_remaining = true;
 							Ok(value)
 						}
+						// The transaction can be recovered by rolling back to a savepoint
+						Err(e) if !savepoints.is_empty() => {
+							failed = true;
+							Err(e)
+						}
 						Err(e) => {
 							for res in &mut self.results[start_results..] {
 								res.query_type = QueryType::Other;
@@ -466,8 +498,6 @@ impl Executor {
 
 							let _ = txn.cancel().await;
 
-							self.opt.sender = None;
-
 							while let Some(stmt) = stream.next().await {
 								let stmt = stmt?;
 								if let Statement::Cancel(_) | Statement::Commit(_) = stmt {
@@ -515,8 +545,6 @@ impl Executor {
 			});
 		}
 
-		self.opt.sender = None;
-
 		Ok(())
 	}
 
@@ -541,6 +569,12 @@ impl Executor {
 	where
 		S: Stream<Item = Result<Statement, Error>>,
 	{
+		// Register the query so that it can be listed and killed
+		let mut ctx = MutableContext::unfreeze(ctx)?;
+		let canceller = ctx.add_cancel();
+		let _running = ctx.get_running_queries().map(|q| q.register(&opt, canceller));
+		let ctx = ctx.freeze();
+		// Process all of the statements
 		let mut this = Executor::new(ctx, opt);
 		let mut stream = pin!(stream);
 
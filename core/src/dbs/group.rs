@@ -1,19 +1,34 @@
+#[cfg(storage)]
+use crate::cnf::EXTERNAL_MEMORY_BUDGET;
+use crate::cnf::GROUP_DIGEST_COMPRESSION;
 use crate::ctx::Context;
 use crate::dbs::plan::Explanation;
+#[cfg(storage)]
+use crate::dbs::store::file::FileCollector;
 use crate::dbs::store::MemoryCollector;
 use crate::dbs::{Options, Statement};
 use crate::err::Error;
+use crate::fnc::util::math::tdigest::TDigest;
 use crate::sql::function::OptimisedAggregate;
 use crate::sql::value::{TryAdd, TryFloatDiv, Value};
-use crate::sql::{Array, Field, Function, Idiom};
+use crate::sql::{Array, Field, Fields, Function, Idiom, Number};
 use reblessive::tree::Stk;
 use std::borrow::Cow;
+#[cfg(storage)]
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{BTreeMap, HashMap};
+#[cfg(storage)]
+use std::hash::{Hash, Hasher};
+use std::mem;
 
 pub(super) struct GroupsCollector {
 	base: Vec<Aggregator>,
 	idioms: Vec<Idiom>,
 	grp: BTreeMap<Array, Vec<Aggregator>>,
+	/// Records for groups which did not fit within the memory budget,
+	/// partitioned by group, so that each partition is aggregated separately
+	#[cfg(storage)]
+	partitions: Vec<FileCollector>,
 }
 
 #[derive(Default)]
@@ -26,11 +41,18 @@ struct Aggregator {
 	math_min: Option<Value>,
 	math_sum: Option<Value>,
 	math_mean: Option<(Value, usize)>,
+	math_digest: Option<TDigest>,
+	math_moments: Option<(usize, f64, f64)>,
 	time_max: Option<Value>,
 	time_min: Option<Value>,
+	search_facets: Option<BTreeMap<String, usize>>,
 }
 
 impl GroupsCollector {
+	/// The number of partitions which spilled records are written to
+	#[cfg(storage)]
+	const PARTITIONS: u64 = 16;
+
 	pub(super) fn new(stm: &Statement<'_>) -> Self {
 		let mut idioms_agr: HashMap<Idiom, Aggregator> = HashMap::new();
 		if let Some(fields) = stm.expr() {
@@ -55,6 +77,8 @@ impl GroupsCollector {
 			base,
 			idioms,
 			grp: Default::default(),
+			#[cfg(storage)]
+			partitions: Vec::new(),
 		}
 	}
 
@@ -76,16 +100,59 @@ impl GroupsCollector {
 				// Set the value at the path
 				arr.push(val);
 			}
+			// Spill to disk if the groups exceed the memory budget
+			#[cfg(storage)]
+			if let Some(temp_dir) = ctx.temporary_directory() {
+				if !self.grp.contains_key(&arr) && self.grp.len() >= *EXTERNAL_MEMORY_BUDGET {
+					return self.spill(temp_dir, arr, obj).await;
+				}
+			}
 			// Add to grouped collection
-			let agr = self
-				.grp
-				.entry(arr)
-				.or_insert_with(|| self.base.iter().map(|a| a.new_instance()).collect());
-			Self::pushes(stk, ctx, opt, agr, &self.idioms, obj).await?
+			Self::aggregate(stk, ctx, opt, &mut self.grp, &self.base, &self.idioms, arr, obj)
+				.await?
 		}
 		Ok(())
 	}
 
+	#[allow(clippy::too_many_arguments)]
+	#[allow(clippy::mutable_key_type)]
+	async fn aggregate(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		grp: &mut BTreeMap<Array, Vec<Aggregator>>,
+		base: &[Aggregator],
+		idioms: &[Idiom],
+		arr: Array,
+		obj: Value,
+	) -> Result<(), Error> {
+		let agr = grp.entry(arr).or_insert_with(|| base.iter().map(|a| a.new_instance()).collect());
+		Self::pushes(stk, ctx, opt, agr, idioms, obj).await
+	}
+
+	/// Write a record to the partition of its group on disk
+	#[cfg(storage)]
+	async fn spill(
+		&mut self,
+		temp_dir: &std::path::Path,
+		arr: Array,
+		obj: Value,
+	) -> Result<(), Error> {
+		// Create the partitions on the first spill
+		if self.partitions.is_empty() {
+			for _ in 0..Self::PARTITIONS {
+				self.partitions.push(FileCollector::new(temp_dir)?);
+			}
+		}
+		// Records of the same group always go to the same partition
+		let mut hasher = DefaultHasher::new();
+		arr.hash(&mut hasher);
+		let idx = (hasher.finish() % Self::PARTITIONS) as usize;
+		// Store the group alongside the record
+		let val = Value::from(vec![Value::from(arr), obj]);
+		self.partitions[idx].push(val).await
+	}
+
 	async fn pushes(
 		stk: &mut Stk,
 		ctx: &Context,
@@ -105,6 +172,7 @@ impl GroupsCollector {
 		self.grp.len()
 	}
 
+	#[allow(clippy::mutable_key_type)]
 	pub(super) async fn output(
 		&mut self,
 		stk: &mut Stk,
@@ -114,48 +182,62 @@ impl GroupsCollector {
 	) -> Result<MemoryCollector, Error> {
 		let mut results = MemoryCollector::default();
 		if let Some(fields) = stm.expr() {
-			// Loop over each grouped collection
-			for aggregator in self.grp.values_mut() {
-				// Create a new value
-				let mut obj = Value::base();
-				// Loop over each group clause
-				for field in fields.other() {
-					// Process the field
-					if let Field::Single {
-						expr,
-						alias,
-					} = field
-					{
-						let idiom = alias
-							.as_ref()
-							.map(Cow::Borrowed)
-							.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
-						if let Some(idioms_pos) =
-							self.idioms.iter().position(|i| i.eq(idiom.as_ref()))
-						{
-							if let Some(agr) = aggregator.get_mut(idioms_pos) {
-								match expr {
-									Value::Function(f) if f.is_aggregate() => {
-										let a = f.get_optimised_aggregate();
-										let x = if matches!(a, OptimisedAggregate::None) {
-											// The aggregation is not optimised, let's compute it with the values
-											let vals = agr.take();
-											f.aggregate(vals)?.compute(stk, ctx, opt, None).await?
-										} else {
-											// The aggregation is optimised, just get the value
-											agr.compute(a)?
-										};
-										obj.set(stk, ctx, opt, idiom.as_ref(), x).await?;
-									}
-									_ => {
-										let x = agr.take().first();
-										obj.set(stk, ctx, opt, idiom.as_ref(), x).await?;
-									}
-								}
+			let grp = mem::take(&mut self.grp);
+			// Aggregate any partitions which were spilled to disk
+			#[cfg(storage)]
+			if !self.partitions.is_empty() {
+				let mut groups = Vec::with_capacity(grp.len());
+				for (arr, mut aggregator) in grp {
+					let obj =
+						Self::output_group(stk, ctx, opt, fields, &self.idioms, &mut aggregator)
+							.await?;
+					groups.push((arr, obj));
+				}
+				for mut partition in mem::take(&mut self.partitions) {
+					// Each partition holds a subset of the groups
+					let mut grp = BTreeMap::new();
+					for val in partition.take_vec().await? {
+						if let Value::Array(mut val) = val {
+							let obj = val.0.pop().unwrap_or_default();
+							if let Some(Value::Array(arr)) = val.0.pop() {
+								Self::aggregate(
+									stk,
+									ctx,
+									opt,
+									&mut grp,
+									&self.base,
+									&self.idioms,
+									arr,
+									obj,
+								)
+								.await?;
 							}
 						}
 					}
+					for (arr, mut aggregator) in grp {
+						let obj = Self::output_group(
+							stk,
+							ctx,
+							opt,
+							fields,
+							&self.idioms,
+							&mut aggregator,
+						)
+						.await?;
+						groups.push((arr, obj));
+					}
 				}
+				// Output the groups in the same order as in memory
+				groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+				for (_, obj) in groups {
+					results.push(obj);
+				}
+				return Ok(results);
+			}
+			// Loop over each grouped collection
+			for (_, mut aggregator) in grp {
+				let obj = Self::output_group(stk, ctx, opt, fields, &self.idioms, &mut aggregator)
+					.await?;
 				// Add the object to the results
 				results.push(obj);
 			}
@@ -163,6 +245,73 @@ impl GroupsCollector {
 		Ok(results)
 	}
 
+	/// Compute the output of a single group
+	async fn output_group(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		fields: &Fields,
+		idioms: &[Idiom],
+		aggregator: &mut [Aggregator],
+	) -> Result<Value, Error> {
+		// Create a new value
+		let mut obj = Value::base();
+		// Loop over each group clause
+		for field in fields.other() {
+			// Process the field
+			if let Field::Single {
+				expr,
+				alias,
+			} = field
+			{
+				let idiom = alias
+					.as_ref()
+					.map(Cow::Borrowed)
+					.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+				if let Some(idioms_pos) = idioms.iter().position(|i| i.eq(idiom.as_ref())) {
+					if let Some(agr) = aggregator.get_mut(idioms_pos) {
+						match expr {
+							Value::Function(f) if f.is_aggregate() => {
+								let a = f.get_optimised_aggregate();
+								let x = match a {
+									OptimisedAggregate::None => {
+										// The aggregation is not optimised, let's compute it with the values
+										let vals = agr.take();
+										f.aggregate(vals)?.compute(stk, ctx, opt, None).await?
+									}
+									OptimisedAggregate::MathPercentile => {
+										// The percentile is computed once for each group
+										let perc = match f.args().get(1) {
+											Some(v) => v.compute(stk, ctx, opt, None).await?,
+											None => Value::None,
+										};
+										let perc = perc.coerce_to_number().map_err(|_| {
+											Error::InvalidArguments {
+												name: String::from("math::percentile"),
+												message: String::from(
+													"The second argument must be a number.",
+												),
+											}
+										})?;
+										agr.percentile(perc)
+									}
+									// The aggregation is optimised, just get the value
+									a => agr.compute(a)?,
+								};
+								obj.set(stk, ctx, opt, idiom.as_ref(), x).await?;
+							}
+							_ => {
+								let x = agr.take().first();
+								obj.set(stk, ctx, opt, idiom.as_ref(), x).await?;
+							}
+						}
+					}
+				}
+			}
+		}
+		Ok(obj)
+	}
+
 	pub(super) fn explain(&self, exp: &mut Explanation) {
 		let mut explain = BTreeMap::new();
 		let idioms: Vec<String> =
@@ -225,6 +374,16 @@ impl Aggregator {
 					self.math_mean = Some((0.into(), 0));
 				}
 			}
+			OptimisedAggregate::MathMedian | OptimisedAggregate::MathPercentile => {
+				if self.math_digest.is_none() {
+					self.math_digest = Some(TDigest::new(*GROUP_DIGEST_COMPRESSION));
+				}
+			}
+			OptimisedAggregate::MathStddev | OptimisedAggregate::MathVariance => {
+				if self.math_moments.is_none() {
+					self.math_moments = Some((0, 0.0, 0.0));
+				}
+			}
 			OptimisedAggregate::TimeMax => {
 				if self.time_max.is_none() {
 					self.time_max = Some(Value::None);
@@ -235,6 +394,11 @@ impl Aggregator {
 					self.time_min = Some(Value::None);
 				}
 			}
+			OptimisedAggregate::SearchFacets => {
+				if self.search_facets.is_none() {
+					self.search_facets = Some(BTreeMap::new());
+				}
+			}
 		}
 	}
 
@@ -248,8 +412,11 @@ impl Aggregator {
 			math_min: self.math_min.as_ref().map(|_| Value::None),
 			math_sum: self.math_sum.as_ref().map(|_| 0.into()),
 			math_mean: self.math_mean.as_ref().map(|_| (0.into(), 0)),
+			math_digest: self.math_digest.as_ref().map(|_| TDigest::new(*GROUP_DIGEST_COMPRESSION)),
+			math_moments: self.math_moments.as_ref().map(|_| (0, 0.0, 0.0)),
 			time_max: self.time_max.as_ref().map(|_| Value::None),
 			time_min: self.time_min.as_ref().map(|_| Value::None),
+			search_facets: self.search_facets.as_ref().map(|_| BTreeMap::new()),
 		}
 	}
 
@@ -268,6 +435,19 @@ impl Aggregator {
 				*c += 1;
 			}
 		}
+		if let Value::Number(n) = &val {
+			if let Some(ref mut d) = self.math_digest {
+				d.push(n.to_float());
+			}
+			if let Some((ref mut c, ref mut mean, ref mut m2)) = self.math_moments {
+				// Welford's algorithm computes the variance in a single pass
+				let x = n.to_float();
+				*c += 1;
+				let delta = x - *mean;
+				*mean += delta / *c as f64;
+				*m2 += delta * (x - *mean);
+			}
+		}
 		if val.is_number() {
 			if let Some(s) = self.math_sum.take() {
 				self.math_sum = Some(s.try_add(val.clone())?);
@@ -307,6 +487,16 @@ impl Aggregator {
 				});
 			}
 		}
+		if let Some(ref mut f) = self.search_facets {
+			// Each record contributes its distinct indexed terms
+			if let Value::Array(a) = &val {
+				for t in a.iter() {
+					if let Value::Strand(t) = t {
+						*f.entry(t.0.clone()).or_default() += 1;
+					}
+				}
+			}
+		}
 		if let Some(ref mut a) = self.array {
 			a.0.push(val);
 		} else if let Some(ref mut v) = self.first_val {
@@ -334,8 +524,45 @@ impl Aggregator {
 					Value::None
 				}
 			}
+			OptimisedAggregate::MathMedian => match self.math_digest.take() {
+				Some(mut d) if !d.is_empty() => d.percentile(50.0).into(),
+				_ => Value::None,
+			},
+			// The percentile argument is computed separately
+			OptimisedAggregate::MathPercentile => Value::None,
+			OptimisedAggregate::MathStddev => {
+				self.variance().map(|v| v.sqrt().into()).unwrap_or(Value::None)
+			}
+			OptimisedAggregate::MathVariance => {
+				self.variance().map(Value::from).unwrap_or(Value::None)
+			}
 			OptimisedAggregate::TimeMax => self.time_max.take().unwrap_or(Value::None),
 			OptimisedAggregate::TimeMin => self.time_min.take().unwrap_or(Value::None),
+			OptimisedAggregate::SearchFacets => self
+				.search_facets
+				.take()
+				.map(|f| {
+					Value::from(
+						f.into_iter().map(|(t, c)| (t, Value::from(c))).collect::<BTreeMap<_, _>>(),
+					)
+				})
+				.unwrap_or(Value::None),
+		})
+	}
+
+	fn percentile(&mut self, perc: Number) -> Value {
+		self.math_digest
+			.take()
+			.map(|mut d| d.percentile(perc.to_float()).into())
+			.unwrap_or(Value::None)
+	}
+
+	/// The sample variance of the values
+	fn variance(&mut self) -> Option<f64> {
+		self.math_moments.take().map(|(c, _, m2)| match c {
+			0 => f64::NAN,
+			1 => 0.0,
+			c => m2 / (c - 1) as f64,
 		})
 	}
 
@@ -373,6 +600,12 @@ impl Aggregator {
 		if self.math_min.is_some() {
 			collections.push("math::min".into());
 		}
+		if self.math_digest.is_some() {
+			collections.push("math::percentile".into());
+		}
+		if self.math_moments.is_some() {
+			collections.push("math::variance".into());
+		}
 		if self.math_sum.is_some() {
 			collections.push("math::sum".into());
 		}
@@ -382,6 +615,9 @@ impl Aggregator {
 		if self.time_min.is_some() {
 			collections.push("time::min".into());
 		}
+		if self.search_facets.is_some() {
+			collections.push("search::facets".into());
+		}
 		collections.into()
 	}
 }
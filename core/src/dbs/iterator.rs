@@ -1,24 +1,30 @@
+use crate::cnf::INSERT_BATCH_SIZE;
 use crate::ctx::Context;
 use crate::ctx::{Canceller, MutableContext};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::distinct::AsyncDistinct;
 use crate::dbs::distinct::SyncDistinct;
-use crate::dbs::plan::Plan;
+use crate::dbs::optimiser::RecordFilter;
+use crate::dbs::plan::{Plan, Pushdown};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::processor::Collected;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::processor::ParallelCollector;
 use crate::dbs::result::Results;
+use crate::dbs::window;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
 use crate::idx::planner::iterators::{IteratorRecord, IteratorRef};
 use crate::idx::planner::IterationStage;
+use crate::key::thing;
 use crate::sql::array::Array;
 use crate::sql::edges::Edges;
+use crate::sql::expression::Expression;
 use crate::sql::mock::Mock;
 use crate::sql::object::Object;
+use crate::sql::operator::Operator;
 use crate::sql::table::Table;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
@@ -37,6 +43,7 @@ use std::future::Future;
 use std::mem;
 use std::sync::Arc;
 use std::thread::available_parallelism;
+use trice::Instant;
 
 const TARGET: &str = "surrealdb::core::dbs";
 
@@ -85,6 +92,14 @@ pub(crate) enum Iterable {
 	/// table, which then fetches the correesponding records
 	/// which are matched within the index.
 	Index(Table, IteratorRef),
+	/// An iterable which needs to iterate over the records
+	/// in a single partition of a partitioned table. When the
+	/// 3rd argument is true, we iterate over keys only.
+	Partition(Table, Value, bool),
+	/// An iterable which fetches a list of specific records
+	/// of a table in batches, used when the WHERE clause
+	/// restricts the records to a list of record ids.
+	Records(Table, Vec<Thing>),
 }
 
 #[derive(Debug)]
@@ -131,6 +146,10 @@ pub(crate) struct Iterator {
 	guaranteed: Option<Iterable>,
 	/// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	/// The number of records which matched the statement
+	matched: usize,
+	/// The predicates on the record id, checked before fetching records
+	pub(super) filter: Option<RecordFilter>,
 }
 
 impl Clone for Iterator {
@@ -144,6 +163,8 @@ impl Clone for Iterator {
 			entries: self.entries.clone(),
 			guaranteed: None,
 			cancel_on_limit: None,
+			matched: 0,
+			filter: self.filter.clone(),
 		}
 	}
 }
@@ -320,15 +341,23 @@ impl Iterator {
 		self.setup_limit(stk, &cancel_ctx, opt, stm).await?;
 		// Process the query START clause
 		self.setup_start(stk, &cancel_ctx, opt, stm).await?;
+		// Prune any table scans to a single partition
+		self.setup_partitions(stk, &cancel_ctx, opt, stm).await?;
 		// Prepare the results with possible optimisations on groups
 		self.results = self.results.prepare(
 			#[cfg(storage)]
 			ctx,
 			stm,
 		)?;
+		// Extract any predicates which can be checked before fetching records
+		self.filter = RecordFilter::new(stm);
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
-		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
+		let pushdown = self.pushdown(ctx, stm);
+		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results, pushdown);
 		if plan.do_iterate {
+			let start = Instant::now();
+			// Check which inserted records exist in batches
+			self.prefetch(&cancel_ctx, opt, stm).await?;
 			// Process prepared values
 			if let Some(qp) = ctx.get_query_planner() {
 				while let Some(s) = qp.next_iteration_stage().await {
@@ -357,19 +386,33 @@ impl Iterator {
 					self.iterate(stk, &cancel_ctx, opt, stm).await?;
 				}
 			}
+			plan.add_stage("Iterate", true, self.matched, start);
 			// Process any SPLIT AT clause
+			let start = Instant::now();
 			self.output_split(stk, ctx, opt, stm).await?;
+			plan.add_stage("Split", stm.split().is_some(), self.results.len(), start);
 			// Process any GROUP BY clause
+			let start = Instant::now();
 			self.output_group(stk, ctx, opt, stm).await?;
+			plan.add_stage("Group", stm.group().is_some(), self.results.len(), start);
+			// Process any window functions
+			let start = Instant::now();
+			let windows = self.output_window(stk, ctx, opt, stm).await?;
+			plan.add_stage("Window", windows, self.results.len(), start);
 			// Process any ORDER BY clause
+			let start = Instant::now();
 			if let Some(orders) = stm.order() {
 				#[cfg(not(target_arch = "wasm32"))]
 				self.results.sort(orders).await?;
 				#[cfg(target_arch = "wasm32")]
 				self.results.sort(orders);
 			}
+			plan.add_stage("Order", stm.order().is_some(), self.results.len(), start);
 			// Process any START & LIMIT clause
+			let start = Instant::now();
 			self.results.start_limit(self.start, self.limit).await?;
+			let limited = self.start.is_some() || self.limit.is_some();
+			plan.add_stage("Limit", limited, self.results.len(), start);
 			// Process any FETCH clause
 			if let Some(e) = &mut plan.explanation {
 				e.add_fetch(self.results.len());
@@ -409,6 +452,34 @@ impl Iterator {
 		Ok(self.limit)
 	}
 
+	/// Check which records of a bulk INSERT statement already exist, with batched
+	/// reads, so that new records can be written without a read for each record.
+	async fn prefetch(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		if !matches!(stm, Statement::Insert(_)) || self.entries.len() < 2 {
+			return Ok(());
+		}
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		let txn = ctx.tx();
+		let mut batch = Vec::with_capacity(*INSERT_BATCH_SIZE);
+		for entry in self.entries.iter() {
+			if let Iterable::Mergeable(v, _) | Iterable::Relatable(_, v, _, _) = entry {
+				batch.push(thing::new(ns, db, &v.tb, &v.id));
+				if batch.len() >= *INSERT_BATCH_SIZE {
+					txn.prefetch(mem::take(&mut batch)).await?;
+				}
+			}
+		}
+		if !batch.is_empty() {
+			txn.prefetch(batch).await?;
+		}
+		Ok(())
+	}
+
 	#[inline]
 	async fn setup_start(
 		&mut self,
@@ -423,13 +494,77 @@ impl Iterator {
 		Ok(())
 	}
 
+	/// Restricts any table iterators to a single partition, when the
+	/// WHERE clause matches the partition expression of the table
+	async fn setup_partitions(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if there is a WHERE clause
+		let Some(cond) = stm.conds() else {
+			return Ok(());
+		};
+		// Get the transaction
+		let txn = ctx.tx();
+		// Loop over the table iterators
+		for entry in self.entries.iter_mut() {
+			if let Iterable::Table(tb, keys_only) = entry {
+				// Check if the table is partitioned
+				let expr = match txn.get_tb(opt.ns()?, opt.db()?, tb).await {
+					Ok(dt) => match &dt.partition {
+						Some(expr) => expr.clone(),
+						None => continue,
+					},
+					Err(Error::TbNotFound {
+						..
+					}) => continue,
+					Err(e) => return Err(e),
+				};
+				// Check if the WHERE clause selects a single partition
+				if let Some(v) = partition_condition(&cond.0, &expr) {
+					let v = v.compute(stk, ctx, opt, None).await?;
+					*entry = Iterable::Partition(tb.clone(), v, *keys_only);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Determine which clauses are pushed down into the iteration of records
+	fn pushdown(&self, ctx: &Context, stm: &Statement<'_>) -> Pushdown {
+		#[cfg(not(target_arch = "wasm32"))]
+		let limit = match self.check_set_start_limit(ctx, stm) {
+			true => self.limit.map(|l| l + self.start.unwrap_or(0)),
+			false => None,
+		};
+		#[cfg(target_arch = "wasm32")]
+		let limit = None;
+		let order = match self.entries.as_slice() {
+			[Iterable::Index(_, irf)] if stm.order().is_some() => {
+				ctx.get_query_planner().is_some_and(|qp| qp.is_order(irf))
+			}
+			_ => false,
+		};
+		Pushdown {
+			limit,
+			order,
+		}
+	}
+
 	/// Check if the iteration can be limited per iterator
 	#[cfg(not(target_arch = "wasm32"))]
-	fn check_set_start_limit(&mut self, ctx: &Context, stm: &Statement<'_>) -> bool {
+	fn check_set_start_limit(&self, ctx: &Context, stm: &Statement<'_>) -> bool {
 		// If there are groups we can't
 		if stm.group().is_some() {
 			return false;
 		}
+		// If there are window functions we can't
+		if stm.expr().is_some_and(|f| f.has_window()) {
+			return false;
+		}
 		// If there is no specified order, we can
 		if stm.order().is_none() {
 			return true;
@@ -521,6 +656,27 @@ impl Iterator {
 		Ok(())
 	}
 
+	async fn output_window(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<bool, Error> {
+		// Process any window functions
+		if !window::check(stm)? {
+			return Ok(false);
+		}
+		// Get the query result
+		let res = self.results.take().await?;
+		// Evaluate the window functions
+		let res = window::output(stk, ctx, opt, stm, res).await?;
+		// Collect the results so they can still be ordered
+		self.results = Results::collect(stm, res);
+		// Everything ok
+		Ok(true)
+	}
+
 	async fn output_fetch(
 		&mut self,
 		stk: &mut Stk,
@@ -611,8 +767,16 @@ impl Iterator {
 				let collecting = Self::collecting(ctx, &opt, &exe, vals, chn);
 				// Create an async closure to process key/values
 				let (chn, docs) = bounded(max_concurrent_tasks);
-				let processing =
-					Self::processing(ctx, &opt, &exe, max_threads, distinct, collected, chn);
+				let processing = Self::processing(
+					ctx,
+					&opt,
+					&exe,
+					max_threads,
+					distinct,
+					self.filter.clone(),
+					collected,
+					chn,
+				);
 				// Create an unbounded channel
 				let (chn, vals) = bounded(max_concurrent_tasks);
 				// Create an async closure for received values
@@ -659,6 +823,7 @@ impl Iterator {
 		exe: &Executor<'a>,
 		max_threads: usize,
 		distinct: Option<AsyncDistinct>,
+		filter: Option<RecordFilter>,
 		collected: Receiver<Collected>,
 		chn: Sender<Processed>,
 	) {
@@ -667,8 +832,13 @@ impl Iterator {
 			let chn = chn.clone();
 			let collected = collected.clone();
 			let distinct = distinct.clone();
+			let filter = filter.clone();
 			let process = async move {
 				while let Ok(coll) = collected.recv().await {
+					// Skip records which can not match the condition
+					if coll.is_filtered(filter.as_ref()) {
+						continue;
+					}
 					let pro = coll.process(opt, &tx).await?;
 					ParallelCollector::process(distinct.as_ref(), pro, &chn).await?;
 				}
@@ -744,6 +914,17 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
+				self.matched += 1;
+				// Abort runaway queries which exceed the row limit
+				if let Some(limit) = opt.row_limit.filter(|_| stm.is_select()) {
+					if self.matched as u64 > limit {
+						self.error = Some(Error::QueryRowLimitExceeded {
+							limit,
+						});
+						self.run.cancel();
+						return;
+					}
+				}
 				if let Err(e) = self.results.push(stk, ctx, opt, stm, v).await {
 					self.error = Some(e);
 					self.run.cancel();
@@ -806,3 +987,34 @@ impl Iterator {
 		});
 	}
 }
+
+/// Finds the value which the partition expression of a table
+/// is compared to for equality within a WHERE clause, so that
+/// only the records in the matching partition are iterated.
+fn partition_condition<'a>(cond: &'a Value, expr: &Value) -> Option<&'a Value> {
+	let Value::Expression(e) = cond else {
+		return None;
+	};
+	match e.as_ref() {
+		Expression::Binary {
+			l,
+			o: Operator::And,
+			r,
+		} => partition_condition(l, expr).or_else(|| partition_condition(r, expr)),
+		Expression::Binary {
+			l,
+			o: Operator::Equal | Operator::Exact,
+			r,
+		} => {
+			let computable = |v: &Value| v.is_static() || matches!(v, Value::Param(_));
+			if l == expr && computable(r) {
+				Some(r)
+			} else if r == expr && computable(l) {
+				Some(l)
+			} else {
+				None
+			}
+		}
+		_ => None,
+	}
+}
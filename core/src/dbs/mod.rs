@@ -12,13 +12,16 @@ mod plan;
 mod processor;
 mod response;
 mod result;
+mod running;
 mod session;
 mod statement;
 mod store;
 mod variables;
+mod window;
 
 pub mod capabilities;
 pub mod node;
+pub(crate) mod optimiser;
 
 pub use self::capabilities::Capabilities;
 pub(crate) use self::executor::*;
@@ -26,6 +29,7 @@ pub(crate) use self::iterator::*;
 pub use self::notification::*;
 pub use self::options::*;
 pub use self::response::*;
+pub(crate) use self::running::*;
 pub use self::session::*;
 pub(crate) use self::statement::*;
 pub(crate) use self::variables::*;
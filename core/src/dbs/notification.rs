@@ -25,7 +25,7 @@ impl Display for Action {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct Notification {
@@ -37,6 +37,10 @@ pub struct Notification {
 	pub record: Value,
 	/// The resulting notification content, usually the altered record content
 	pub result: Value,
+	/// The change feed versionstamp of this change, after which this LIVE query can be resumed
+	#[revision(start = 2)]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub resume: Option<u64>,
 }
 
 impl Display for Notification {
@@ -46,6 +50,7 @@ impl Display for Notification {
 			"action".to_string() => self.action.to_string().into(),
 			"record".to_string() => self.record.clone(),
 			"result".to_string() => self.result.clone(),
+			"resume".to_string(), if let Some(v) = self.resume => v.into(),
 		}
 		.into();
 		write!(f, "{}", obj)
@@ -60,8 +65,14 @@ impl Notification {
 			action,
 			record,
 			result,
+			resume: None,
 		}
 	}
+	/// Set the versionstamp from which the LIVE query can be resumed
+	pub fn with_resume(mut self, resume: Option<u64>) -> Self {
+		self.resume = resume;
+		self
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,287 @@
+//! Logical optimisations which are applied to a statement before it is executed.
+//!
+//! The optimiser rewrites a `SELECT` statement into an equivalent statement
+//! which is cheaper to execute, by flattening subqueries in the `FROM` clause
+//! into the outer statement, and by removing projections which are already
+//! covered by a `*` projection. It also extracts any `WHERE` predicates on the
+//! record id, so that records can be filtered out before they are fetched.
+use crate::dbs::Statement;
+use crate::fnc;
+use crate::sql::statements::SelectStatement;
+use crate::sql::{Cond, Expression, Field, Fields, Id, Operator, Part, Subquery, Thing, Value};
+use std::borrow::Cow;
+
+/// Rewrites a `SELECT` statement into an equivalent, cheaper statement
+pub(crate) fn optimise(stm: &SelectStatement) -> Cow<'_, SelectStatement> {
+	let mut stm = Cow::Borrowed(stm);
+	if let Some(flattened) = flatten(&stm) {
+		stm = Cow::Owned(flattened);
+	}
+	if let Some(fields) = projections(&stm) {
+		stm.to_mut().expr = fields;
+	}
+	stm
+}
+
+/// Flattens `SELECT ... FROM (SELECT * FROM ... WHERE ...) WHERE ...`
+/// into a single statement, so that the outer statement can iterate
+/// over the inner tables directly, and can make use of any indexes.
+fn flatten(stm: &SelectStatement) -> Option<SelectStatement> {
+	// Only a single subquery target can be flattened
+	let [Value::Subquery(subquery)] = stm.what.0.as_slice() else {
+		return None;
+	};
+	let Subquery::Select(inner) = subquery.as_ref() else {
+		return None;
+	};
+	// The outer clauses must not depend on the type of the target
+	if stm.only || stm.with.is_some() || stm.version.is_some() {
+		return None;
+	}
+	// Flatten any nested subqueries first
+	let inner = optimise(inner);
+	// The inner statement must output the records unchanged
+	if inner.expr != Fields::all()
+		|| inner.omit.is_some()
+		|| inner.only
+		|| inner.with.is_some()
+		|| inner.split.is_some()
+		|| inner.group.is_some()
+		|| inner.order.is_some()
+		|| inner.limit.is_some()
+		|| inner.start.is_some()
+		|| inner.fetch.is_some()
+		|| inner.version.is_some()
+		|| inner.timeout.is_some()
+		|| inner.explain.is_some()
+	{
+		return None;
+	}
+	// The inner targets must not depend on the outer document
+	if !inner.what.iter().all(|v| matches!(v, Value::Table(_)) || literal(v)) {
+		return None;
+	}
+	// The inner condition must only depend on the record itself
+	if !inner.cond.as_ref().map_or(true, |c| local(&c.0)) {
+		return None;
+	}
+	// Combine the inner and outer conditions
+	let cond = match (&inner.cond, &stm.cond) {
+		(Some(i), Some(o)) => Some(Cond(Value::Expression(Box::new(Expression::Binary {
+			l: i.0.clone(),
+			o: Operator::And,
+			r: o.0.clone(),
+		})))),
+		(Some(c), None) | (None, Some(c)) => Some(c.clone()),
+		(None, None) => None,
+	};
+	Some(SelectStatement {
+		what: inner.what.clone(),
+		cond,
+		parallel: stm.parallel || inner.parallel,
+		tempfiles: stm.tempfiles || inner.tempfiles,
+		..stm.clone()
+	})
+}
+
+/// Removes any projections which are already output by a `*` projection
+fn projections(stm: &SelectStatement) -> Option<Fields> {
+	// Grouped and omitted fields depend on the individual projections
+	if !stm.expr.is_all() || stm.expr.1 || stm.group.is_some() || stm.omit.is_some() {
+		return None;
+	}
+	let mut fields: Vec<Field> = Vec::with_capacity(stm.expr.0.len());
+	for field in stm.expr.0.iter() {
+		let redundant = match field {
+			// A field which is already projected
+			_ if fields.contains(field) => true,
+			// A plain field without an alias is already output by `*`
+			Field::Single {
+				expr: Value::Idiom(i),
+				alias: None,
+			} => i.iter().all(|p| matches!(p, Part::Field(_))),
+			_ => false,
+		};
+		if !redundant {
+			fields.push(field.clone());
+		}
+	}
+	(fields.len() < stm.expr.0.len()).then_some(Fields(fields, false))
+}
+
+/// Checks whether a value is a literal which needs no computation
+fn literal(v: &Value) -> bool {
+	match v {
+		Value::Thing(t) => matches!(t.id, Id::Number(_) | Id::String(_) | Id::Uuid(_)),
+		Value::Array(a) => a.iter().all(literal),
+		Value::None
+		| Value::Null
+		| Value::Bool(_)
+		| Value::Number(_)
+		| Value::Strand(_)
+		| Value::Uuid(_)
+		| Value::Duration(_)
+		| Value::Datetime(_) => true,
+		_ => false,
+	}
+}
+
+/// Checks whether a condition only depends on the fields of the record
+fn local(v: &Value) -> bool {
+	match v {
+		Value::Idiom(i) => i.iter().all(|p| matches!(p, Part::Field(_))),
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => local(v),
+			Expression::Binary {
+				l,
+				o,
+				r,
+			} => {
+				!matches!(o, Operator::Matches(_) | Operator::Knn(..) | Operator::Ann(..))
+					&& local(l) && local(r)
+			}
+		},
+		v => literal(v),
+	}
+}
+
+/// The `WHERE` predicates on the record id of a `SELECT` statement,
+/// which are checked before a record is fetched from the datastore.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordFilter(Vec<(Operator, Value)>);
+
+impl RecordFilter {
+	/// Extracts the predicates on the record id from the statement condition
+	pub(crate) fn new(stm: &Statement<'_>) -> Option<Self> {
+		let Statement::Select(_) = stm else {
+			return None;
+		};
+		let mut checks = Vec::new();
+		Self::extract(&stm.conds()?.0, &mut checks);
+		(!checks.is_empty()).then_some(Self(checks))
+	}
+
+	fn extract(cond: &Value, checks: &mut Vec<(Operator, Value)>) {
+		let Value::Expression(e) = cond else {
+			return;
+		};
+		let Expression::Binary {
+			l,
+			o,
+			r,
+		} = e.as_ref()
+		else {
+			return;
+		};
+		let is_id = |v: &Value| matches!(v, Value::Idiom(i) if i.is_id());
+		match o {
+			// Every side of a conjunction must match
+			Operator::And => {
+				Self::extract(l, checks);
+				Self::extract(r, checks);
+			}
+			Operator::Equal
+			| Operator::Exact
+			| Operator::NotEqual
+			| Operator::LessThan
+			| Operator::LessThanOrEqual
+			| Operator::MoreThan
+			| Operator::MoreThanOrEqual
+			| Operator::Inside
+			| Operator::NotInside
+			| Operator::Contain
+			| Operator::NotContain => {
+				if is_id(l) && literal(r) {
+					checks.push((o.clone(), r.clone()));
+				} else if is_id(r) && literal(l) {
+					// Reverse the comparison so that the id is on the left
+					let o = match o {
+						Operator::LessThan => Operator::MoreThan,
+						Operator::LessThanOrEqual => Operator::MoreThanOrEqual,
+						Operator::MoreThan => Operator::LessThan,
+						Operator::MoreThanOrEqual => Operator::LessThanOrEqual,
+						Operator::Inside => Operator::Contain,
+						Operator::NotInside => Operator::NotContain,
+						Operator::Contain => Operator::Inside,
+						Operator::NotContain => Operator::NotInside,
+						o => o.clone(),
+					};
+					checks.push((o, l.clone()));
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Checks whether a record with this id can match the statement condition
+	pub(crate) fn matches(&self, rid: &Thing) -> bool {
+		let id = Value::Thing(rid.clone());
+		self.0.iter().all(|(o, v)| {
+			let res = match o {
+				Operator::Equal => fnc::operate::equal(&id, v),
+				Operator::Exact => fnc::operate::exact(&id, v),
+				Operator::NotEqual => fnc::operate::not_equal(&id, v),
+				Operator::LessThan => fnc::operate::less_than(&id, v),
+				Operator::LessThanOrEqual => fnc::operate::less_than_or_equal(&id, v),
+				Operator::MoreThan => fnc::operate::more_than(&id, v),
+				Operator::MoreThanOrEqual => fnc::operate::more_than_or_equal(&id, v),
+				Operator::Inside => fnc::operate::inside(&id, v),
+				Operator::NotInside => fnc::operate::not_inside(&id, v),
+				Operator::Contain => fnc::operate::contain(&id, v),
+				Operator::NotContain => fnc::operate::not_contain(&id, v),
+				_ => Ok(Value::Bool(true)),
+			};
+			// Any errors are left to the full condition to report
+			res.map_or(true, |v| v.is_truthy())
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn;
+
+	fn select(sql: &str) -> SelectStatement {
+		match syn::parse(sql).unwrap().0 .0.remove(0) {
+			crate::sql::Statement::Select(s) => s,
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn flatten_subquery() {
+		let stm =
+			select("SELECT name FROM (SELECT * FROM person WHERE age > 18) WHERE name = 'Tobie'");
+		let res = optimise(&stm);
+		assert_eq!(res.to_string(), "SELECT name FROM person WHERE age > 18 AND name = 'Tobie'");
+		// Subqueries which limit or reference the outer document are not flattened
+		let stm = select("SELECT * FROM (SELECT * FROM person LIMIT 5) WHERE age > 18");
+		assert!(matches!(optimise(&stm), Cow::Borrowed(_)));
+		let stm = select("SELECT * FROM (SELECT * FROM person WHERE age > $parent.age)");
+		assert!(matches!(optimise(&stm), Cow::Borrowed(_)));
+	}
+
+	#[test]
+	fn redundant_projections() {
+		let stm = select("SELECT *, name, name.first, *, ->likes AS likes FROM person");
+		let res = optimise(&stm);
+		assert_eq!(res.to_string(), "SELECT *, ->likes AS likes FROM person");
+		let stm = select("SELECT *, name FROM person GROUP BY name");
+		assert!(matches!(optimise(&stm), Cow::Borrowed(_)));
+	}
+
+	#[test]
+	fn record_filter() {
+		let stm = select("SELECT * FROM person WHERE id INSIDE [person:1, person:2] AND person:1 < id AND age > 18");
+		let filter = RecordFilter::new(&Statement::from(&stm)).unwrap();
+		assert!(filter.matches(&Thing::from(("person", Id::Number(2)))));
+		assert!(!filter.matches(&Thing::from(("person", Id::Number(1)))));
+		assert!(!filter.matches(&Thing::from(("person", Id::Number(3)))));
+		let stm = select("SELECT * FROM person WHERE id = $id OR age > 18");
+		assert!(RecordFilter::new(&Statement::from(&stm)).is_none());
+	}
+}
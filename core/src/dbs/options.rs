@@ -1,11 +1,9 @@
 use crate::cnf::MAX_COMPUTATION_DEPTH;
-use crate::dbs::Notification;
 use crate::err::Error;
 use crate::iam::{Action, Auth, ResourceKind, Role};
 use crate::sql::{
 	statements::define::DefineIndexStatement, statements::define::DefineTableStatement, Base,
 };
-use async_channel::Sender;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -46,10 +44,10 @@ pub struct Options {
 	pub futures: Futures,
 	/// Should we process variable field projections?
 	pub projections: bool,
-	/// The channel over which we send notifications
-	pub sender: Option<Sender<Notification>>,
 	/// Version as nanosecond timestamp passed down to Datastore
 	pub version: Option<u64>,
+	/// The maximum number of rows a SELECT statement can process
+	pub row_limit: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -90,9 +88,9 @@ impl Options {
 			futures: Futures::Disabled,
 			projections: false,
 			auth_enabled: true,
-			sender: None,
 			auth: Arc::new(Auth::default()),
 			version: None,
+			row_limit: None,
 		}
 	}
 
@@ -195,6 +193,12 @@ impl Options {
 		self
 	}
 
+	/// Specify the maximum number of rows a SELECT statement can process
+	pub fn with_row_limit(mut self, limit: Option<u64>) -> Self {
+		self.row_limit = limit;
+		self
+	}
+
 	/// Specify if we are currently importing data
 	pub fn with_import(mut self, import: bool) -> Self {
 		self.set_import(import);
@@ -251,7 +255,6 @@ impl Options {
 	/// Create a new Options object for a subquery
 	pub fn new_with_perms(&self, perms: bool) -> Self {
 		Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -265,7 +268,6 @@ impl Options {
 	/// Create a new Options object for a subquery
 	pub fn new_with_force(&self, force: Force) -> Self {
 		Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -278,7 +280,6 @@ impl Options {
 	/// Create a new Options object for a subquery
 	pub fn new_with_strict(&self, strict: bool) -> Self {
 		Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -292,7 +293,6 @@ impl Options {
 	/// Create a new Options object for a subquery
 	pub fn new_with_import(&self, import: bool) -> Self {
 		Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -306,7 +306,6 @@ impl Options {
 	/// Create a new Options object for a subquery
 	pub fn new_with_futures(&self, futures: bool) -> Self {
 		Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -322,19 +321,6 @@ impl Options {
 		}
 	}
 
-	/// Create a new Options object for a subquery
-	pub fn new_with_sender(&self, sender: Sender<Notification>) -> Self {
-		Self {
-			auth: self.auth.clone(),
-			ns: self.ns.clone(),
-			db: self.db.clone(),
-			force: self.force.clone(),
-			futures: self.futures.clone(),
-			sender: Some(sender),
-			..*self
-		}
-	}
-
 	// Get currently selected base
 	pub fn selected_base(&self) -> Result<Base, Error> {
 		match (self.ns.as_ref(), self.db.as_ref()) {
@@ -354,7 +340,6 @@ impl Options {
 			return Err(Error::ComputationDepthExceeded);
 		}
 		Ok(Self {
-			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
@@ -464,17 +449,22 @@ impl Options {
 				&& self.auth.level().ns().unwrap() == self.ns()?
 				&& self.auth.level().db().unwrap() == self.db()?;
 
+		// Do the custom roles of the actor allow the action on the tables of the selected database?
+		let is_granted =
+			self.auth.is_granted(&action, &ResourceKind::Table.on_db(self.ns()?, self.db()?));
+
 		// Is the actor allowed to do the action on the selected database?
-		let is_allowed = match action {
-			Action::View => {
-				// Today all users have at least View permissions, so if the target database belongs to the user's level, don't check permissions
-				can_view && db_in_actor_level
-			}
-			Action::Edit => {
-				// Editor and Owner roles are allowed to edit, but only if the target database belongs to the user's level
-				can_edit && db_in_actor_level
-			}
-		};
+		let is_allowed = is_granted
+			|| match action {
+				Action::View => {
+					// Today all users have at least View permissions, so if the target database belongs to the user's level, don't check permissions
+					can_view && db_in_actor_level
+				}
+				Action::Edit => {
+					// Editor and Owner roles are allowed to edit, but only if the target database belongs to the user's level
+					can_edit && db_in_actor_level
+				}
+			};
 
 		// Check permissions if the author is not already allowed to do the action
 		Ok(!is_allowed)
@@ -3,25 +3,38 @@ use crate::dbs::result::Results;
 use crate::dbs::{Iterable, Statement};
 use crate::sql::{Object, Value};
 use std::collections::HashMap;
+use trice::Instant;
 
 pub(super) struct Plan {
 	pub(super) do_iterate: bool,
 	pub(super) explanation: Option<Explanation>,
 }
 
+/// The clauses which are pushed down into the iteration of records
+pub(super) struct Pushdown {
+	/// The number of records after which iteration stops, if START and LIMIT are pushed down
+	pub(super) limit: Option<u32>,
+	/// Whether the ORDER BY clause is satisfied by the order of an index
+	pub(super) order: bool,
+}
+
 impl Plan {
 	pub(super) fn new(
 		ctx: &Context,
 		stm: &Statement<'_>,
 		iterables: &Vec<Iterable>,
 		results: &Results,
+		pushdown: Pushdown,
 	) -> Self {
 		let (do_iterate, explanation) = match stm.explain() {
 			None => (true, None),
 			Some(e) => {
-				let mut exp = Explanation::default();
+				let mut exp = Explanation {
+					items: vec![],
+					detailed: e.1,
+				};
 				for i in iterables {
-					exp.add_iter(ctx, i);
+					exp.add_iter(ctx, i, &pushdown);
 				}
 				if let Some(qp) = ctx.get_query_planner() {
 					for reason in qp.fallbacks() {
@@ -29,6 +42,9 @@ impl Plan {
 					}
 				}
 				results.explain(&mut exp);
+				if exp.detailed {
+					exp.add_pushdown(stm, iterables, &pushdown);
+				}
 				(e.0, Some(exp))
 			}
 		};
@@ -37,18 +53,32 @@ impl Plan {
 			explanation,
 		}
 	}
+
+	/// Record the number of rows output by a stage of the query, and the time it took
+	pub(super) fn add_stage(&mut self, stage: &str, applies: bool, rows: usize, start: Instant) {
+		if let Some(exp) = self.explanation.as_mut().filter(|e| e.detailed && applies) {
+			exp.items.push(ExplainItem::new_stage(stage, rows, start));
+		}
+	}
 }
 
-#[derive(Default)]
-pub(super) struct Explanation(Vec<ExplainItem>);
+pub(super) struct Explanation {
+	items: Vec<ExplainItem>,
+	/// Whether the full query plan is reported
+	detailed: bool,
+}
 
 impl Explanation {
-	fn add_iter(&mut self, ctx: &Context, iter: &Iterable) {
-		self.0.push(ExplainItem::new_iter(ctx, iter));
+	fn add_iter(&mut self, ctx: &Context, iter: &Iterable, pushdown: &Pushdown) {
+		let mut item = ExplainItem::new_iter(ctx, iter);
+		if self.detailed {
+			item.details.push(("estimate", ExplainItem::estimate(iter, pushdown)));
+		}
+		self.items.push(item);
 	}
 
 	pub(super) fn add_fetch(&mut self, count: usize) {
-		self.0.push(ExplainItem::new_fetch(count));
+		self.items.push(ExplainItem::new_fetch(count));
 	}
 
 	pub(super) fn add_collector(
@@ -56,14 +86,18 @@ impl Explanation {
 		collector_type: &str,
 		details: Vec<(&'static str, Value)>,
 	) {
-		self.0.push(ExplainItem::new_collector(collector_type, details));
+		self.items.push(ExplainItem::new_collector(collector_type, details));
 	}
 	fn add_fallback(&mut self, reason: String) {
-		self.0.push(ExplainItem::new_fallback(reason));
+		self.items.push(ExplainItem::new_fallback(reason));
+	}
+
+	fn add_pushdown(&mut self, stm: &Statement<'_>, iterables: &[Iterable], pushdown: &Pushdown) {
+		self.items.push(ExplainItem::new_pushdown(stm, iterables, pushdown));
 	}
 
 	pub(super) fn output(self) -> Vec<Value> {
-		self.0.into_iter().map(|e| e.into()).collect()
+		self.items.into_iter().map(|e| e.into()).collect()
 	}
 }
 
@@ -80,6 +114,51 @@ impl ExplainItem {
 		}
 	}
 
+	fn new_stage(stage: &str, rows: usize, start: Instant) -> Self {
+		Self {
+			name: "Stage".into(),
+			details: vec![
+				("stage", stage.into()),
+				("rows", rows.into()),
+				("elapsed", Value::from(crate::sql::Duration::from(start.elapsed()))),
+			],
+		}
+	}
+
+	fn new_pushdown(stm: &Statement<'_>, iterables: &[Iterable], pushdown: &Pushdown) -> Self {
+		let index = iterables.iter().any(|i| matches!(i, Iterable::Index(..)));
+		let mut details = vec![
+			("condition", (stm.conds().is_some() && index).into()),
+			("limit", pushdown.limit.is_some().into()),
+		];
+		if stm.order().is_some() {
+			details.push(("order", pushdown.order.into()));
+		}
+		Self {
+			name: "Pushdown".into(),
+			details,
+		}
+	}
+
+	/// The estimated number of records which an iterable will produce, if known
+	fn estimate(iter: &Iterable, pushdown: &Pushdown) -> Value {
+		match iter {
+			Iterable::Value(_)
+			| Iterable::Thing(_)
+			| Iterable::Defer(_)
+			| Iterable::Mergeable(..)
+			| Iterable::Relatable(..) => 1.into(),
+			Iterable::Records(_, ids) => match pushdown.limit {
+				Some(l) if (l as usize) < ids.len() => l.into(),
+				_ => ids.len().into(),
+			},
+			_ => match pushdown.limit {
+				Some(l) => l.into(),
+				None => Value::None,
+			},
+		}
+	}
+
 	fn new_fallback(reason: String) -> Self {
 		Self {
 			name: "Fallback".into(),
@@ -127,6 +206,19 @@ impl ExplainItem {
 				.into(),
 				details: vec![("table", tb.to_owned().into()), ("range", r.to_owned().into())],
 			},
+			Iterable::Partition(t, pt, keys_only) => Self {
+				name: if *keys_only {
+					"Iterate Partition Keys"
+				} else {
+					"Iterate Partition"
+				}
+				.into(),
+				details: vec![("table", Value::from(t.0.to_owned())), ("partition", pt.to_owned())],
+			},
+			Iterable::Records(t, ids) => Self {
+				name: "Iterate Records".into(),
+				details: vec![("table", Value::from(t.0.to_owned())), ("count", ids.len().into())],
+			},
 			Iterable::Mergeable(t, v) => Self {
 				name: "Iterate Mergeable".into(),
 				details: vec![("thing", Value::Thing(t.to_owned())), ("value", v.to_owned())],
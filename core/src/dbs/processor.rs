@@ -3,11 +3,12 @@ use crate::ctx::{Context, MutableContext};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::distinct::AsyncDistinct;
 use crate::dbs::distinct::SyncDistinct;
+use crate::dbs::optimiser::RecordFilter;
 use crate::dbs::{Iterable, Iterator, Operable, Options, Processed, Statement};
 use crate::err::Error;
 use crate::idx::planner::iterators::{CollectorRecord, IteratorRef};
 use crate::idx::planner::IterationStage;
-use crate::key::{graph, thing};
+use crate::key::{graph, partition, thing};
 use crate::kvs::{Key, Transaction, Val};
 use crate::sql::dir::Dir;
 use crate::sql::id::range::IdRange;
@@ -70,7 +71,10 @@ impl Iterable {
 
 	fn iteration_stage_check(&self, ctx: &Context) -> bool {
 		match self {
-			Iterable::Table(tb, _) | Iterable::Index(tb, _) => {
+			Iterable::Table(tb, _)
+			| Iterable::Partition(tb, _, _)
+			| Iterable::Index(tb, _)
+			| Iterable::Records(tb, _) => {
 				if let Some(IterationStage::BuildKnn) = ctx.get_iteration_stage() {
 					if let Some(qp) = ctx.get_query_planner() {
 						if let Some(exe) = qp.get_query_executor(tb) {
@@ -130,6 +134,27 @@ impl Collected {
 		}
 	}
 
+	/// Checks whether this record can be skipped without being fetched,
+	/// because its record id does not match the statement condition
+	pub(super) fn is_filtered(&self, filter: Option<&RecordFilter>) -> bool {
+		let Some(filter) = filter else {
+			return false;
+		};
+		match self {
+			Self::Edge(key) => match graph::Graph::decode(key) {
+				Ok(gra) => !filter.matches(&Thing::from((gra.ft, gra.fk))),
+				Err(_) => false,
+			},
+			Self::KeyVal(key, _) => match thing::Thing::decode(key) {
+				Ok(key) => !filter.matches(&Thing::from((key.tb, key.id))),
+				Err(_) => false,
+			},
+			Self::Thing(thing) => !filter.matches(thing),
+			Self::IndexItem(r) => !filter.matches(&r.0),
+			_ => false,
+		}
+	}
+
 	async fn process_edge(opt: &Options, txn: &Transaction, key: Key) -> Result<Processed, Error> {
 		// Parse the data from the store
 		let gra: graph::Graph = graph::Graph::decode(&key)?;
@@ -340,6 +365,10 @@ pub(super) struct ConcurrentCollector<'a> {
 
 impl<'a> Collector for ConcurrentCollector<'a> {
 	async fn collect(&mut self, collected: Collected) -> Result<(), Error> {
+		// Skip records which can not match the condition
+		if collected.is_filtered(self.ite.filter.as_ref()) {
+			return Ok(());
+		}
 		let pro = collected.process(self.opt, self.txn).await?;
 		self.ite.process(self.stk, self.ctx, self.opt, self.stm, pro).await;
 		Ok(())
@@ -353,6 +382,10 @@ pub(super) struct ConcurrentDistinctCollector<'a> {
 
 impl<'a> Collector for ConcurrentDistinctCollector<'a> {
 	async fn collect(&mut self, collected: Collected) -> Result<(), Error> {
+		// Skip records which can not match the condition
+		if collected.is_filtered(self.coll.ite.filter.as_ref()) {
+			return Ok(());
+		}
 		let pro = collected.process(self.coll.opt, self.coll.txn).await?;
 		if !self.dis.check_already_processed(&pro) {
 			self.coll
@@ -434,6 +467,14 @@ pub(super) trait Collector {
 						self.collect_table(&ctx, opt, &v).await?
 					}
 				}
+				Iterable::Partition(v, pt, keys_only) => {
+					let ctx = Self::check_query_planner_context(ctx, &v);
+					self.collect_partition(&ctx, opt, &v, &pt, keys_only).await?
+				}
+				Iterable::Records(v, ids) => {
+					let ctx = Self::check_query_planner_context(ctx, &v);
+					self.collect_records(&ctx, opt, &v, ids).await?
+				}
 				Iterable::Index(v, irf) => {
 					if let Some(qp) = ctx.get_query_planner() {
 						if let Some(exe) = qp.get_query_executor(&v.0) {
@@ -476,7 +517,7 @@ pub(super) trait Collector {
 		let beg = thing::prefix(opt.ns()?, opt.db()?, v);
 		let end = thing::suffix(opt.ns()?, opt.db()?, v);
 		// Create a new iterable range
-		let mut stream = txn.stream(beg..end, opt.version);
+		let mut stream = ctx.bound_stream(txn.stream(beg..end, opt.version));
 		// Loop until no more entries
 		while let Some(res) = stream.next().await {
 			// Check if the context is finished
@@ -505,7 +546,7 @@ pub(super) trait Collector {
 		let beg = thing::prefix(opt.ns()?, opt.db()?, v);
 		let end = thing::suffix(opt.ns()?, opt.db()?, v);
 		// Create a new iterable range
-		let mut stream = txn.stream_keys(beg..end);
+		let mut stream = ctx.bound_stream(txn.stream_keys(beg..end));
 		// Loop until no more entries
 		while let Some(res) = stream.next().await {
 			// Check if the context is finished
@@ -521,6 +562,80 @@ pub(super) trait Collector {
 		Ok(())
 	}
 
+	async fn collect_records(
+		&mut self,
+		ctx: &Context,
+		opt: &Options,
+		v: &Table,
+		ids: Vec<Thing>,
+	) -> Result<(), Error> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// Check that the table exists
+		txn.check_ns_db_tb(opt.ns()?, opt.db()?, v, opt.strict).await?;
+		// Fetch the records in batches
+		for ids in ids.chunks(*NORMAL_FETCH_SIZE as usize) {
+			// Prepare the record keys
+			let mut keys: Vec<Key> = Vec::with_capacity(ids.len());
+			for id in ids {
+				keys.push(thing::new(opt.ns()?, opt.db()?, v, &id.id).into());
+			}
+			// Fetch all the records at once
+			let vals = txn.getm(keys.clone()).await?;
+			for (k, v) in keys.into_iter().zip(vals) {
+				// Check if the context is finished
+				if ctx.is_done() {
+					return Ok(());
+				}
+				// Skip any records which do not exist
+				if v.is_empty() {
+					continue;
+				}
+				self.collect(Collected::KeyVal(k, v)).await?;
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	async fn collect_partition(
+		&mut self,
+		ctx: &Context,
+		opt: &Options,
+		v: &Table,
+		pt: &Value,
+		keys_only: bool,
+	) -> Result<(), Error> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// Check that the table exists
+		txn.check_ns_db_tb(opt.ns()?, opt.db()?, v, opt.strict).await?;
+		// Prepare the start and end keys
+		let beg = partition::prefix(opt.ns()?, opt.db()?, v, pt);
+		let end = partition::suffix(opt.ns()?, opt.db()?, v, pt);
+		// Create a new iterable range
+		let mut stream = ctx.bound_stream(txn.stream_keys(beg..end));
+		// Loop until no more entries
+		while let Some(res) = stream.next().await {
+			// Check if the context is finished
+			if ctx.is_done() {
+				break;
+			}
+			// Parse the record id from the partition key
+			let k = res?;
+			let k = partition::Partition::decode(&k)?;
+			// Collect the record
+			if keys_only {
+				let key: Key = thing::new(opt.ns()?, opt.db()?, v, &k.id).into();
+				self.collect(Collected::TableKey(key)).await?;
+			} else {
+				self.collect(Collected::Thing(Thing::from((k.tb, k.id)))).await?;
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
 	async fn range_prepare(
 		txn: &Transaction,
 		opt: &Options,
@@ -564,7 +679,7 @@ pub(super) trait Collector {
 		// Prepare
 		let (beg, end) = Self::range_prepare(&txn, opt, tb, r).await?;
 		// Create a new iterable range
-		let mut stream = txn.stream(beg..end, None);
+		let mut stream = ctx.bound_stream(txn.stream(beg..end, None));
 		// Loop until no more entries
 		while let Some(res) = stream.next().await {
 			// Check if the context is finished
@@ -592,7 +707,7 @@ pub(super) trait Collector {
 		// Prepare
 		let (beg, end) = Self::range_prepare(&txn, opt, tb, r).await?;
 		// Create a new iterable range
-		let mut stream = txn.stream_keys(beg..end);
+		let mut stream = ctx.bound_stream(txn.stream_keys(beg..end));
 		// Loop until no more entries
 		while let Some(res) = stream.next().await {
 			// Check if the context is finished
@@ -683,7 +798,7 @@ pub(super) trait Collector {
 		// Loop over the chosen edge types
 		for (beg, end) in keys.into_iter() {
 			// Create a new iterable range
-			let mut stream = txn.stream(beg..end, None);
+			let mut stream = ctx.bound_stream(txn.stream(beg..end, None));
 			// Loop until no more entries
 			while let Some(res) = stream.next().await {
 				// Check if the context is finished
@@ -1,3 +1,5 @@
+#[cfg(storage)]
+use crate::cnf::EXTERNAL_MEMORY_BUDGET;
 use crate::ctx::Context;
 use crate::dbs::group::GroupsCollector;
 use crate::dbs::plan::Explanation;
@@ -6,6 +8,7 @@ use crate::dbs::store::asynchronous::AsyncMemoryOrdered;
 #[cfg(storage)]
 use crate::dbs::store::file::FileCollector;
 use crate::dbs::store::{MemoryCollector, MemoryOrdered, MemoryRandom};
+use crate::dbs::window;
 use crate::dbs::{Options, Statement};
 use crate::err::Error;
 use crate::sql::order::Ordering;
@@ -30,6 +33,10 @@ impl Results {
 		#[cfg(storage)] ctx: &Context,
 		stm: &Statement<'_>,
 	) -> Result<Self, Error> {
+		// Window functions are evaluated once all results are collected
+		if window::check(stm)? {
+			return Ok(Self::Memory(Default::default()));
+		}
 		if stm.expr().is_some() && stm.group().is_some() {
 			return Ok(Self::Groups(GroupsCollector::new(stm)));
 		}
@@ -40,7 +47,12 @@ impl Results {
 			}
 		}
 		if let Some(ordering) = stm.order() {
-			#[cfg(not(target_arch = "wasm32"))]
+			// Results ordered in parallel can not be spilled to temporary files
+			#[cfg(all(not(target_arch = "wasm32"), storage))]
+			if stm.parallel() && ctx.temporary_directory().is_none() {
+				return Ok(Self::AsyncMemoryOrdered(AsyncMemoryOrdered::new(ordering, None)));
+			}
+			#[cfg(all(not(target_arch = "wasm32"), not(storage)))]
 			if stm.parallel() {
 				return Ok(Self::AsyncMemoryOrdered(AsyncMemoryOrdered::new(ordering, None)));
 			}
@@ -54,6 +66,24 @@ impl Results {
 		Ok(Self::Memory(Default::default()))
 	}
 
+	/// Collect values which have already been processed, so that
+	/// any ORDER BY clause is still applied to them afterwards
+	pub(super) fn collect(stm: &Statement<'_>, values: Vec<Value>) -> Self {
+		match stm.order() {
+			Some(Ordering::Random) => {
+				let mut c = MemoryRandom::new(None);
+				values.into_iter().for_each(|v| c.push(v));
+				Self::MemoryRandom(c)
+			}
+			Some(Ordering::Order(orders)) => {
+				let mut c = MemoryOrdered::new(orders.clone(), None);
+				values.into_iter().for_each(|v| c.push(v));
+				Self::MemoryOrdered(c)
+			}
+			None => values.into(),
+		}
+	}
+
 	pub(super) async fn push(
 		&mut self,
 		stk: &mut Stk,
@@ -85,6 +115,34 @@ impl Results {
 				g.push(stk, ctx, opt, stm, val).await?;
 			}
 		}
+		// Spill to disk if the results exceed the memory budget
+		#[cfg(storage)]
+		self.spill(ctx).await?;
+		Ok(())
+	}
+
+	/// Move the collected results to temporary files, once
+	/// there are more results than can be held in memory
+	#[cfg(storage)]
+	async fn spill(&mut self, ctx: &Context) -> Result<(), Error> {
+		// Results can only be spilled with a temporary directory
+		let Some(temp_dir) = ctx.temporary_directory() else {
+			return Ok(());
+		};
+		// Check if the memory budget has been exceeded
+		if self.len() <= *EXTERNAL_MEMORY_BUDGET {
+			return Ok(());
+		}
+		// Only results which are still to be ordered are spilled
+		let values = match self {
+			Self::MemoryOrdered(c) => c.take_unsorted(),
+			Self::MemoryRandom(c) => c.take_unsorted(),
+			_ => return Ok(()),
+		};
+		// Write the collected results to temporary files
+		let mut file = FileCollector::new(temp_dir)?;
+		file.extend(values).await?;
+		*self = Self::File(Box::new(file));
 		Ok(())
 	}
 
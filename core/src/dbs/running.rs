@@ -0,0 +1,120 @@
+use crate::ctx::Canceller;
+use crate::dbs::Options;
+use crate::sql::{Datetime, Object, Uuid, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// A query which is currently being executed by this node
+#[derive(Clone)]
+struct RunningQuery {
+	/// The namespace selected when the query started
+	ns: Option<String>,
+	/// The database selected when the query started
+	db: Option<String>,
+	/// The id of the actor which is running the query
+	actor: String,
+	/// The time at which the query started
+	started: Datetime,
+	/// Used to cooperatively cancel the query
+	canceller: Canceller,
+}
+
+/// The registry of queries which are currently being executed, so
+/// that they can be listed with `INFO FOR QUERIES` and cancelled
+/// with `KILL QUERY`.
+#[derive(Clone, Default)]
+pub struct RunningQueries(Arc<Mutex<BTreeMap<Uuid, RunningQuery>>>);
+
+/// Removes a query from the registry once it has finished
+pub(crate) struct RunningGuard {
+	id: Uuid,
+	queries: RunningQueries,
+}
+
+impl Drop for RunningGuard {
+	fn drop(&mut self) {
+		self.queries.0.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.id);
+	}
+}
+
+impl RunningQueries {
+	/// Register a new query which can be cancelled with the given canceller
+	pub(crate) fn register(&self, opt: &Options, canceller: Canceller) -> RunningGuard {
+		let id = Uuid::new_v4();
+		let query = RunningQuery {
+			ns: opt.ns().ok().map(String::from),
+			db: opt.db().ok().map(String::from),
+			actor: opt.auth.id().to_owned(),
+			started: Datetime::default(),
+			canceller,
+		};
+		self.0.lock().unwrap_or_else(|e| e.into_inner()).insert(id, query);
+		RunningGuard {
+			id,
+			queries: self.clone(),
+		}
+	}
+
+	/// Cancel a running query, returning whether the query was found
+	pub(crate) fn kill(&self, id: &Uuid) -> bool {
+		match self.0.lock().unwrap_or_else(|e| e.into_inner()).get(id) {
+			Some(query) => {
+				query.canceller.cancel();
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// List the queries which are currently running, oldest first
+	pub(crate) fn list(&self) -> Value {
+		let queries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		let mut queries: Vec<_> =
+			queries.iter().map(|(id, q)| (q.started.clone(), *id, q)).collect();
+		queries.sort_by(|a, b| a.0.cmp(&b.0));
+		queries
+			.into_iter()
+			.map(|(_, id, q)| {
+				Value::from(Object::from(map! {
+					"actor" => Value::from(q.actor.as_str()),
+					"db" => q.db.as_deref().map(Value::from).unwrap_or_default(),
+					"id" => Value::from(id),
+					"ns" => q.ns.as_deref().map(Value::from).unwrap_or_default(),
+					"started" => Value::from(q.started.clone()),
+				}))
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::RunningQueries;
+	use crate::ctx::MutableContext;
+	use crate::dbs::Options;
+	use crate::sql::{Uuid, Value};
+
+	#[test]
+	fn register_kill_and_remove() {
+		let queries = RunningQueries::default();
+		let mut ctx = MutableContext::background();
+		let canceller = ctx.add_cancel();
+		let ctx = ctx.freeze();
+		let guard = queries.register(&Options::default(), canceller);
+		let Value::Array(list) = queries.list() else {
+			panic!("Expected an array of running queries");
+		};
+		assert_eq!(list.len(), 1);
+		let Value::Uuid(id) = list[0].pick(&["id".into()]) else {
+			panic!("Expected a query id");
+		};
+		assert!(!ctx.is_done());
+		assert!(!queries.kill(&Uuid::new_v4()));
+		assert!(queries.kill(&id));
+		assert!(ctx.is_done());
+		drop(guard);
+		assert!(!queries.kill(&id));
+		assert_eq!(queries.list(), Value::from(Vec::<Value>::new()));
+	}
+}
@@ -62,6 +62,27 @@ impl FileCollector {
 		}
 	}
 
+	pub(in crate::dbs) async fn extend(&mut self, values: Vec<Value>) -> Result<(), Error> {
+		if let Some(mut writer) = self.writer.take() {
+			let len = values.len();
+			let f = move || {
+				for value in values {
+					writer.push(value)?;
+				}
+				Ok::<FileWriter, Error>(writer)
+			};
+			#[cfg(not(target_arch = "wasm32"))]
+			let writer = spawn_blocking(f).await.map_err(|e| Error::Internal(format!("{e}")))??;
+			#[cfg(target_arch = "wasm32")]
+			let writer = f()?;
+			self.len += len;
+			self.writer = Some(writer);
+			Ok(())
+		} else {
+			Err(Error::Internal("No FileWriter available.".to_string()))
+		}
+	}
+
 	fn check_reader(&mut self) -> Result<(), Error> {
 		if self.reader.is_none() {
 			if let Some(writer) = self.writer.take() {
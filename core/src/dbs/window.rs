@@ -0,0 +1,319 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Statement};
+use crate::err::Error;
+use crate::sql::{Array, Field, Function, Number, Order, Value, Window};
+use reblessive::tree::Stk;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// The values of a record which are needed to evaluate a window function
+struct Row {
+	/// The values which determine the partition of this record
+	partition: Array,
+	/// The values which determine the order of this record within its partition
+	order: Vec<Value>,
+	/// The computed function arguments for this record
+	args: Vec<Value>,
+}
+
+impl TryFrom<Value> for Row {
+	type Error = Error;
+	fn try_from(val: Value) -> Result<Self, Self::Error> {
+		let val: Vec<Value> = val.try_into()?;
+		let mut val = val.into_iter();
+		match (val.next(), val.next(), val.next()) {
+			(
+				Some(Value::Array(partition)),
+				Some(Value::Array(order)),
+				Some(Value::Array(args)),
+			) => Ok(Self {
+				partition,
+				order: order.0,
+				args: args.0,
+			}),
+			_ => Err(fail!("Expected the computed values of a window function")),
+		}
+	}
+}
+
+/// Check if a statement has any window functions which need to be evaluated
+pub(super) fn check(stm: &Statement<'_>) -> Result<bool, Error> {
+	let Some(fields) = stm.expr() else {
+		return Ok(false);
+	};
+	if !fields.has_window() {
+		return Ok(false);
+	}
+	if stm.group().is_some() {
+		return Err(Error::InvalidFunction {
+			name: "OVER".to_string(),
+			message: String::from("Window functions can not be used with a GROUP BY clause."),
+		});
+	}
+	Ok(true)
+}
+
+/// Evaluate any window functions across all of the results
+pub(super) async fn output(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	stm: &Statement<'_>,
+	mut values: Vec<Value>,
+) -> Result<Vec<Value>, Error> {
+	let Some(fields) = stm.expr() else {
+		return Ok(values);
+	};
+	// Check if this is a single VALUE field expression
+	let single = fields.single().is_some();
+	// Evaluate each window function field in turn
+	for field in fields.other() {
+		let Field::Single {
+			expr: Value::Function(f),
+			alias,
+		} = field
+		else {
+			continue;
+		};
+		let Function::Window(name, args, window) = f.as_ref() else {
+			continue;
+		};
+		let idiom = alias.clone().unwrap_or_else(|| f.to_idiom());
+		// Extract the values which were computed for each record
+		let mut rows = Vec::with_capacity(values.len());
+		for v in values.iter() {
+			rows.push(Row::try_from(match single {
+				true => v.clone(),
+				false => v.pick(&idiom),
+			})?);
+		}
+		// Evaluate the window function for each record
+		let out = evaluate(stk, ctx, opt, name, args, window, &rows).await?;
+		// Replace the computed values with the window function output
+		for (v, x) in values.iter_mut().zip(out) {
+			match single {
+				true => *v = x,
+				false => v.put(&idiom, x),
+			}
+		}
+	}
+	Ok(values)
+}
+
+#[allow(clippy::mutable_key_type)]
+async fn evaluate(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	name: &str,
+	args: &[Value],
+	window: &Window,
+	rows: &[Row],
+) -> Result<Vec<Value>, Error> {
+	// Check the number of function arguments
+	let (min, max) = match name {
+		"row_number" | "rank" | "dense_rank" => (0, 0),
+		"lag" | "lead" => (1, 3),
+		_ => (0, usize::MAX),
+	};
+	if args.len() < min || args.len() > max {
+		return Err(Error::InvalidArguments {
+			name: name.to_owned(),
+			message: match (min, max) {
+				(0, 0) => String::from("The function expects 0 arguments."),
+				(r, t) => format!("The function expects {r} to {t} arguments."),
+			},
+		});
+	}
+	// Split the records into partitions
+	let mut partitions: BTreeMap<&Array, Vec<usize>> = BTreeMap::new();
+	for (i, row) in rows.iter().enumerate() {
+		partitions.entry(&row.partition).or_default().push(i);
+	}
+	// Evaluate the function within each partition
+	let mut out = vec![Value::None; rows.len()];
+	for (_, mut idx) in partitions {
+		// Sort the records within the partition
+		idx.sort_by(|a, b| compare(&window.order, &rows[*a].order, &rows[*b].order));
+		// Records with equal ordering values are peers
+		let mut peers = Vec::new();
+		let mut first = 0;
+		for i in 1..=idx.len() {
+			if i == idx.len()
+				|| compare(&window.order, &rows[idx[i - 1]].order, &rows[idx[i]].order)
+					!= Ordering::Equal
+			{
+				peers.push(first..i);
+				first = i;
+			}
+		}
+		// Running aggregates are extended with each peer group, where possible
+		let mut running = Running::new(name, args);
+		for (group, range) in peers.iter().enumerate() {
+			// Running aggregates include all preceding records and any peers
+			let frame = match name {
+				"row_number" | "rank" | "dense_rank" | "lag" | "lead" => None,
+				_ => {
+					if let Some(acc) = running.as_mut() {
+						let add =
+							|i: &usize| acc.push(rows[*i].args.first().unwrap_or(&Value::None));
+						if !idx[range.clone()].iter().all(add) {
+							running = None;
+						}
+					}
+					match &running {
+						Some(acc) => Some(acc.output()),
+						// Otherwise compute the aggregate over the whole frame
+						None => Some(
+							aggregate(stk, ctx, opt, name, args, rows, &idx[..range.end]).await?,
+						),
+					}
+				}
+			};
+			for pos in range.clone() {
+				let row = &rows[idx[pos]];
+				out[idx[pos]] = match name {
+					"row_number" => Value::from(pos as i64 + 1),
+					"rank" => Value::from(range.start as i64 + 1),
+					"dense_rank" => Value::from(group as i64 + 1),
+					"lag" | "lead" => {
+						let offset = match row.args.get(1) {
+							Some(Value::Number(n)) if n.is_integer() && n.as_int() >= 0 => {
+								n.as_int() as usize
+							}
+							None | Some(Value::None) => 1,
+							Some(_) => {
+								return Err(Error::InvalidArguments {
+									name: name.to_owned(),
+									message: String::from(
+										"The second argument must be a positive integer.",
+									),
+								})
+							}
+						};
+						let target = match name {
+							"lag" => pos.checked_sub(offset),
+							_ => pos.checked_add(offset).filter(|p| *p < idx.len()),
+						};
+						match target {
+							Some(p) => rows[idx[p]].args[0].clone(),
+							None => row.args.get(2).cloned().unwrap_or_default(),
+						}
+					}
+					_ => frame.clone().unwrap_or_default(),
+				};
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// An aggregate function which is computed incrementally over a growing frame
+enum Running {
+	/// Counts all of the records
+	Count(usize),
+	/// Counts the truthy values
+	CountValues(usize),
+	/// Adds the numeric values
+	Sum(Number),
+	/// Adds the numeric values as floats, with the number of values
+	Mean(f64, usize),
+	/// Keeps the smallest numeric value
+	Min(Option<Number>),
+	/// Keeps the largest numeric value
+	Max(Option<Number>),
+}
+
+impl Running {
+	/// Create an accumulator, if the function can be computed incrementally
+	fn new(name: &str, args: &[Value]) -> Option<Self> {
+		match (name, args.len()) {
+			("count", 0) => Some(Self::Count(0)),
+			("count", 1) => Some(Self::CountValues(0)),
+			("math::sum", 1) => Some(Self::Sum(Number::Int(0))),
+			("math::mean", 1) => Some(Self::Mean(0.0, 0)),
+			("math::min", 1) => Some(Self::Min(None)),
+			("math::max", 1) => Some(Self::Max(None)),
+			_ => None,
+		}
+	}
+	/// Add the value of a record, returning false if it can not be added
+	fn push(&mut self, val: &Value) -> bool {
+		match (self, val) {
+			(Self::Count(n), _) => *n += 1,
+			(Self::CountValues(n), v) => *n += v.is_truthy() as usize,
+			(Self::Sum(s), Value::Number(v)) => *s = *s + *v,
+			(Self::Mean(s, n), Value::Number(v)) => {
+				*s += v.to_float();
+				*n += 1;
+			}
+			(Self::Min(m), Value::Number(v)) => {
+				if m.as_ref().map_or(true, |m| v < m) {
+					*m = Some(*v);
+				}
+			}
+			(Self::Max(m), Value::Number(v)) => {
+				if m.as_ref().map_or(true, |m| v >= m) {
+					*m = Some(*v);
+				}
+			}
+			// Other values are handled by the function itself
+			_ => return false,
+		}
+		true
+	}
+	/// The value of the aggregate over the records so far
+	fn output(&self) -> Value {
+		match self {
+			Self::Count(n) | Self::CountValues(n) => Value::from(*n),
+			Self::Sum(s) => Value::from(*s),
+			Self::Mean(s, n) => Value::from(s / *n as f64),
+			Self::Min(m) | Self::Max(m) => m.map(Value::from).unwrap_or_default(),
+		}
+	}
+}
+
+/// Compute an aggregate function over a frame of records
+async fn aggregate(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	name: &str,
+	args: &[Value],
+	rows: &[Row],
+	frame: &[usize],
+) -> Result<Value, Error> {
+	// Counting all records does not need any values
+	if name == "count" && args.is_empty() {
+		return Ok(Value::from(frame.len()));
+	}
+	let vals: Vec<Value> =
+		frame.iter().map(|i| rows[*i].args.first().cloned().unwrap_or_default()).collect();
+	// Counting values only counts the truthy values
+	if name == "count" {
+		return Ok(Value::from(vals.iter().filter(|v| v.is_truthy()).count()));
+	}
+	Function::Normal(name.to_owned(), args.to_vec())
+		.aggregate(Value::from(vals))?
+		.compute(stk, ctx, opt, None)
+		.await
+}
+
+/// Compare the ordering values of two records
+fn compare(orders: &[Order], a: &[Value], b: &[Value]) -> Ordering {
+	for (i, order) in orders.iter().enumerate() {
+		let (Some(x), Some(y)) = (a.get(i), b.get(i)) else {
+			continue;
+		};
+		// Reverse the ordering if DESC
+		let o = match order.direction {
+			true => x.compare(y, &[], order.collate, order.locale(), order.numeric),
+			false => y.compare(x, &[], order.collate, order.locale(), order.numeric),
+		};
+		match o {
+			Some(Ordering::Equal) | None => continue,
+			Some(o) => return o,
+		}
+	}
+	Ordering::Equal
+}
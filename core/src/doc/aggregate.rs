@@ -0,0 +1,352 @@
+use crate::ctx::Context;
+use crate::dbs::Action;
+use crate::dbs::Notification;
+use crate::dbs::Options;
+use crate::dbs::Statement;
+use crate::dbs::Workable;
+use crate::doc::CursorDoc;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::statements::LiveStatement;
+use crate::sql::value::{TryAdd, TryFloatDiv, TrySub};
+use crate::sql::{Array, Field, Function, Idiom, Object, Thing, Value};
+use derive::Store;
+use futures::StreamExt;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The running aggregates of a group of a grouped LIVE query
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct LiveGroup {
+	/// The values of the GROUP BY fields of this group
+	pub group: Array,
+	/// The number of records in this group
+	pub records: i64,
+	/// The running total of each field of the LIVE query
+	pub values: Vec<Accumulator>,
+}
+
+/// The running total of an aggregate, which can be added to and subtracted from
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct Accumulator {
+	/// The number of values which have been added
+	pub count: i64,
+	/// The sum of the values which have been added
+	pub sum: Value,
+}
+
+impl Default for Accumulator {
+	fn default() -> Self {
+		Self {
+			count: 0,
+			sum: Value::from(0),
+		}
+	}
+}
+
+/// How a field of a grouped LIVE query is computed
+enum Column<'a> {
+	/// The value of a GROUP BY field, at the specified position
+	Group(usize, &'a Value),
+	/// The count() function, without arguments
+	Count,
+	/// The count() function, with an argument
+	CountIf(&'a Function),
+	/// The math::sum() function
+	Sum(&'a Value),
+	/// The math::mean() function
+	Mean(&'a Value),
+}
+
+/// The values which a record contributes to its group
+type Contribution = (Array, Vec<Option<Value>>);
+
+impl Document {
+	/// Updates the aggregates of a grouped LIVE query
+	/// with a change to this document. The record is
+	/// removed from the group it belonged to before the
+	/// change, and added to the group it belongs to
+	/// after the change, and a notification is sent for
+	/// each group which changed. The groups are stored
+	/// alongside the LIVE query, so that the aggregates
+	/// do not need to be recomputed from every record.
+	pub(super) async fn lq_aggregate(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		action: &Action,
+	) -> Result<(), Error> {
+		// Process the LIVE query as the user who created it
+		let Some((lqctx, lqopt)) = self.lq_context(ctx, opt, lv, action) else {
+			return Ok(());
+		};
+		// Compute the contribution of the record before and after the change
+		let before = match self.is_new() {
+			true => None,
+			false => self.lq_contribution(stk, &lqctx, &lqopt, lv, &self.initial).await?,
+		};
+		let after = match action {
+			Action::Delete => None,
+			_ => self.lq_contribution(stk, &lqctx, &lqopt, lv, &self.current).await?,
+		};
+		// Check if the aggregates have changed
+		if before == after {
+			return Ok(());
+		}
+		// Get the NS, DB, and table
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		let rid = self.id()?;
+		let tb = &rid.tb;
+		let txn = ctx.tx();
+		// Update the groups which the record left and joined
+		let mut groups: Vec<(bool, LiveGroup)> = Vec::with_capacity(2);
+		let changes = [(before, false), (after, true)];
+		for ((group, values), add) in changes.into_iter().filter_map(|(c, a)| Some((c?, a))) {
+			let pos = match groups.iter().position(|(_, g)| g.group == group) {
+				Some(pos) => pos,
+				None => {
+					let gr = group.to_string();
+					let key = crate::key::table::la::new(ns, db, tb, lv.id.0, &gr);
+					let state: Option<LiveGroup> = txn.get(key, None).await?.map(Into::into);
+					let exists = state.is_some();
+					let state = state.unwrap_or_else(|| LiveGroup {
+						group,
+						records: 0,
+						values: vec![Accumulator::default(); values.len()],
+					});
+					groups.push((exists, state));
+					groups.len() - 1
+				}
+			};
+			groups[pos].1.apply(&values, add)?;
+		}
+		// Store the groups, and notify the LIVE query
+		for (exists, state) in groups {
+			let gr = state.group.to_string();
+			let key = crate::key::table::la::new(ns, db, tb, lv.id.0, &gr);
+			let action = match state.records > 0 {
+				true => {
+					txn.set(key, state.clone(), None).await?;
+					match exists {
+						true => Action::Update,
+						false => Action::Create,
+					}
+				}
+				false => {
+					txn.del(key).await?;
+					Action::Delete
+				}
+			};
+			// Check that the LIVE query belongs to this node
+			if opt.id()? != lv.node.0 || !txn.notifies().await {
+				continue;
+			}
+			// Send the notification once committed
+			let result = state.output(lv)?;
+			let record = Value::Array(state.group);
+			txn.notify(Notification::new(lv.id, action, record, result)).await;
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Aggregates the existing records of a table when
+	/// a grouped LIVE query is created, so that later
+	/// changes can be applied to the aggregates.
+	#[allow(clippy::mutable_key_type)]
+	pub(crate) async fn seed_live_aggregate(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		tb: &str,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		let txn = ctx.tx();
+		// Aggregate each of the table records
+		let mut groups: BTreeMap<Array, LiveGroup> = BTreeMap::new();
+		let beg = crate::key::thing::prefix(ns, db, tb);
+		let end = crate::key::thing::suffix(ns, db, tb);
+		let mut stream = txn.stream(beg..end, None);
+		while let Some(res) = stream.next().await {
+			let (k, v) = res?;
+			let key: crate::key::thing::Thing = (&k).into();
+			let rid = Arc::new(Thing::from((key.tb, key.id)));
+			let mut doc = Document::new(
+				Some(rid),
+				None,
+				None,
+				Arc::new(Value::from(&v)),
+				Workable::Normal,
+				false,
+			);
+			// Process the LIVE query as the user who created it
+			let Some((lqctx, lqopt)) = doc.lq_context(ctx, opt, lv, &Action::Create) else {
+				return Ok(());
+			};
+			let Some((group, values)) =
+				doc.lq_contribution(stk, &lqctx, &lqopt, lv, &doc.current).await?
+			else {
+				continue;
+			};
+			let state = groups.entry(group.clone()).or_insert_with(|| LiveGroup {
+				group,
+				records: 0,
+				values: vec![Accumulator::default(); values.len()],
+			});
+			state.apply(&values, true)?;
+		}
+		// Store each of the groups
+		for (group, state) in groups {
+			let gr = group.to_string();
+			let key = crate::key::table::la::new(ns, db, tb, lv.id.0, &gr);
+			txn.set(key, state, None).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Computes the group which a version of this document
+	/// belongs to, and the values which it contributes to
+	/// each aggregate. Returns [`None`] if the document does
+	/// not match the WHERE clause of the LIVE query, or can
+	/// not be viewed by the user who created the LIVE query.
+	async fn lq_contribution(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		doc: &CursorDoc,
+	) -> Result<Option<Contribution>, Error> {
+		// Check if the record exists
+		if doc.doc.as_ref().is_none() {
+			return Ok(None);
+		}
+		// Check the WHERE clause and PERMISSIONS
+		let lq = Statement::from(lv);
+		match self.lq_check(stk, ctx, opt, &lq, doc).await {
+			Err(Error::Ignore) => return Ok(None),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		match self.lq_allow(stk, ctx, opt, &lq, doc).await {
+			Err(Error::Ignore) => return Ok(None),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		// Ensure futures are run
+		let opt = &opt.new_with_futures(true);
+		// Compute the group and the value of each field
+		let columns = columns(lv);
+		let mut group = Array(vec![Value::None; lv.group.as_ref().map_or(0, |g| g.len())]);
+		let mut values = Vec::with_capacity(columns.len());
+		for (_, column) in columns {
+			let value = match column {
+				Column::Group(i, expr) => {
+					group[i] = expr.compute(stk, ctx, opt, Some(doc)).await?;
+					None
+				}
+				Column::Count => Some(Value::from(1)),
+				Column::CountIf(f) => {
+					let v = f.args()[0].compute(stk, ctx, opt, Some(doc)).await?;
+					let v = f.aggregate(v)?.compute(stk, ctx, opt, None).await?;
+					v.is_truthy().then(|| Value::from(1))
+				}
+				Column::Sum(arg) | Column::Mean(arg) => {
+					let v = arg.compute(stk, ctx, opt, Some(doc)).await?;
+					v.is_number().then_some(v)
+				}
+			};
+			values.push(value);
+		}
+		Ok(Some((group, values)))
+	}
+}
+
+impl LiveGroup {
+	/// Adds or removes the contribution of a record
+	fn apply(&mut self, values: &[Option<Value>], add: bool) -> Result<(), Error> {
+		self.records += if add {
+			1
+		} else {
+			-1
+		};
+		for (acc, value) in self.values.iter_mut().zip(values) {
+			if let Some(v) = value {
+				let sum = std::mem::take(&mut acc.sum);
+				match add {
+					true => {
+						acc.count += 1;
+						acc.sum = sum.try_add(v.clone())?;
+					}
+					false => {
+						acc.count -= 1;
+						acc.sum = sum.try_sub(v.clone())?;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+	/// Computes the fields of the LIVE query for this group
+	fn output(&self, lv: &LiveStatement) -> Result<Value, Error> {
+		let mut out = Value::Object(Object::default());
+		for ((name, column), acc) in columns(lv).into_iter().zip(&self.values) {
+			let value = match column {
+				Column::Group(i, _) => self.group[i].clone(),
+				Column::Count | Column::CountIf(_) => Value::from(acc.count),
+				Column::Sum(_) => acc.sum.clone(),
+				Column::Mean(_) => {
+					acc.sum.clone().try_float_div(acc.count.into()).unwrap_or(f64::NAN.into())
+				}
+			};
+			// Check if this is a single VALUE field expression
+			match lv.expr.single().is_some() {
+				false => out.put(&name, value),
+				true => out = value,
+			}
+		}
+		Ok(out)
+	}
+}
+
+/// Determines how each field of a grouped LIVE query is computed
+fn columns(lv: &LiveStatement) -> Vec<(Idiom, Column<'_>)> {
+	let groups = lv.group.as_deref().map(Vec::as_slice).unwrap_or_default();
+	lv.expr
+		.iter()
+		.filter_map(|field| {
+			let Field::Single {
+				expr,
+				alias,
+			} = field
+			else {
+				return None;
+			};
+			let idiom = expr.to_idiom();
+			let name = alias.clone().unwrap_or_else(|| idiom.clone());
+			let column = match expr {
+				Value::Function(f) => match f.as_ref() {
+					Function::Normal(n, a) if n == "count" && a.is_empty() => Column::Count,
+					Function::Normal(n, _) if n == "count" => Column::CountIf(f),
+					Function::Normal(n, a) if n == "math::sum" => Column::Sum(a.first()?),
+					Function::Normal(n, a) if n == "math::mean" => Column::Mean(a.first()?),
+					_ => Column::Group(groups.iter().position(|g| g.0 == idiom)?, expr),
+				},
+				_ => Column::Group(groups.iter().position(|g| g.0 == idiom)?, expr),
+			};
+			Some((name, column))
+		})
+		.collect()
+}
@@ -353,7 +353,7 @@ impl Document {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
 						// Process the PERMISSION clause
-						if !e.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+						if !Self::permitted(stk, ctx, opt, Some(doc), e).await? {
 							return Err(Error::Ignore);
 						}
 					}
@@ -451,19 +451,11 @@ impl Document {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
 						// Process the PERMISSION clause
-						if !e
-							.compute(
-								stk,
-								ctx,
-								opt,
-								Some(match stm.is_delete() {
-									true => &self.initial,
-									false => &self.current,
-								}),
-							)
-							.await?
-							.is_truthy()
-						{
+						let doc = match stm.is_delete() {
+							true => &self.initial,
+							false => &self.current,
+						};
+						if !Self::permitted(stk, ctx, opt, Some(doc), e).await? {
 							return Err(Error::Ignore);
 						}
 					}
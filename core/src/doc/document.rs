@@ -6,12 +6,12 @@ use crate::err::Error;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
 use crate::idx::planner::iterators::IteratorRecord;
+use crate::kvs::LiveFilter;
 use crate::sql::permission::Permission;
 use crate::sql::statements::define::DefineEventStatement;
 use crate::sql::statements::define::DefineFieldStatement;
 use crate::sql::statements::define::DefineIndexStatement;
 use crate::sql::statements::define::DefineTableStatement;
-use crate::sql::statements::live::LiveStatement;
 use crate::sql::table::Table;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
@@ -327,7 +327,7 @@ impl Document {
 							ctx.add_value("value", val);
 							let ctx = ctx.freeze();
 							// Process the PERMISSION clause
-							if !e.compute(stk, &ctx, opt, Some(full)).await?.is_truthy() {
+							if !Self::permitted(stk, &ctx, opt, Some(full), e).await? {
 								out.cut(k);
 							}
 						}
@@ -435,11 +435,11 @@ impl Document {
 		ctx.tx().all_tb_indexes(opt.ns()?, opt.db()?, &id.tb).await
 	}
 
-	// Get the lives for this document
-	pub async fn lv(&self, ctx: &Context, opt: &Options) -> Result<Arc<[LiveStatement]>, Error> {
+	/// Get the compiled lives for this document
+	pub async fn lv(&self, ctx: &Context, opt: &Options) -> Result<Arc<LiveFilter>, Error> {
 		// Get the record id
 		let id = self.id()?;
-		// Get the table definition
-		ctx.tx().all_tb_lives(opt.ns()?, opt.db()?, &id.tb).await
+		// Get the live query definitions
+		ctx.tx().all_tb_lives_filter(opt.ns()?, opt.db()?, &id.tb).await
 	}
 }
@@ -3,7 +3,9 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::kvs::QueuedEvent;
 use crate::sql::value::Value;
+use crate::sql::Datetime;
 use reblessive::tree::Stk;
 
 impl Document {
@@ -12,6 +14,9 @@ impl Document {
 	/// record belongs to. This functions loops
 	/// through the events and processes them all
 	/// within the currently running transaction.
+	/// Asynchronous events are instead added to
+	/// the event queue within the transaction, and
+	/// are processed once the transaction commits.
 	pub async fn process_table_events(
 		&mut self,
 		stk: &mut Stk,
@@ -56,11 +61,36 @@ impl Document {
 			let ctx = ctx.freeze();
 			// Process conditional clause
 			let val = ev.when.compute(stk, &ctx, opt, Some(doc)).await?;
-			// Execute event if value is truthy
-			if val.is_truthy() {
-				for v in ev.then.iter() {
-					v.compute(stk, &ctx, opt, Some(doc)).await?;
-				}
+			// Check if the event should be executed
+			if !val.is_truthy() {
+				continue;
+			}
+			// Queue the event if it is asynchronous
+			if ev.asynchronous {
+				let entry = QueuedEvent {
+					ns: opt.ns()?.to_owned(),
+					db: opt.db()?.to_owned(),
+					tb: ev.what.to_raw(),
+					ev: ev.name.to_raw(),
+					id: doc.rid.as_deref().cloned(),
+					event: ctx.value("event").cloned().unwrap_or_default(),
+					value: doc.doc.as_ref().clone(),
+					before: ctx.value("before").cloned().unwrap_or_default(),
+					after: ctx.value("after").cloned().unwrap_or_default(),
+					then: ev.then.clone(),
+					queued: Datetime::default(),
+					attempts: 0,
+					retry: Datetime::default(),
+					error: None,
+					seq: 0,
+					auth: Some(opt.auth.as_ref().clone()),
+				};
+				entry.enqueue(&ctx.tx()).await?;
+				continue;
+			}
+			// Otherwise execute the event
+			for v in ev.then.iter() {
+				v.compute(stk, &ctx, opt, Some(doc)).await?;
 			}
 		}
 		// Carry on
@@ -32,8 +32,13 @@ impl Document {
 			let mut keys: Vec<Idiom> = vec![];
 			// Loop through all field statements
 			for fd in self.fd(ctx, opt).await?.iter() {
-				// Is this a schemaless field?
-				match fd.flex || fd.kind.as_ref().is_some_and(|k| k.is_literal_nested()) {
+				// Is this a schemaless field, or a vector whose elements are not defined?
+				match fd.flex
+					|| fd
+						.kind
+						.as_ref()
+						.is_some_and(|k| k.is_literal_nested() || k.is_vector_nested())
+				{
 					false => {
 						// Loop over this field in the document
 						for k in self.current.doc.each(&fd.name).into_iter() {
@@ -129,6 +134,11 @@ impl Document {
 				}
 				None => false,
 			};
+			// Resolve any user-defined types in the TYPE clause
+			let kind = match &fd.kind {
+				Some(v) => Some(v.resolved(ctx, opt).await?),
+				None => None,
+			};
 			// Loop over each field in document
 			for (k, mut val) in self.current.doc.as_ref().walk(&fd.name).into_iter() {
 				// Get the initial value
@@ -171,6 +181,21 @@ impl Document {
 				}
 				// Skip this field?
 				if !skipped {
+					// Check for a COMPUTED clause
+					if let Some(expr) = &fd.computed {
+						// Arc the current value
+						let now = Arc::new(val);
+						// Configure the context
+						let mut ctx = MutableContext::new(ctx);
+						ctx.add_value("before", old.clone());
+						ctx.add_value("input", inp.clone());
+						ctx.add_value("after", now.clone());
+						ctx.add_value("value", now);
+						// Freeze the new context
+						let ctx = ctx.freeze();
+						// Process the COMPUTED clause, ignoring any input value
+						val = expr.compute(stk, &ctx, opt, Some(&self.current)).await?;
+					}
 					// Get the default value
 					let def = match &fd.default {
 						Some(v) => Some(v),
@@ -198,7 +223,7 @@ impl Document {
 						}
 					}
 					// Check for a TYPE clause
-					if let Some(kind) = &fd.kind {
+					if let Some(kind) = kind.as_deref() {
 						// If this is the `id` field, it must be a record
 						let cast = match &fd.name {
 							name if name.is_id() => match kind.to_owned() {
@@ -262,7 +287,7 @@ impl Document {
 						val = expr.compute(stk, &ctx, opt, Some(&self.current)).await?;
 					}
 					// Check for a TYPE clause
-					if let Some(kind) = &fd.kind {
+					if let Some(kind) = kind.as_deref() {
 						// If this is the `id` field, it must be a record
 						let cast = match &fd.name {
 							name if name.is_id() => match kind.to_owned() {
@@ -310,7 +335,7 @@ impl Document {
 					}
 					// Check for a ASSERT clause
 					if let Some(expr) = &fd.assert {
-						match (&val, &fd.kind) {
+						match (&val, kind.as_deref()) {
 							// The field TYPE is optional, and the field
 							// value was not set or a NONE value was
 							// specified, so let's ignore the ASSERT clause
@@ -380,7 +405,7 @@ impl Document {
 							// Freeze the new context
 							let ctx = ctx.freeze();
 							// Process the PERMISSION clause
-							if !e.compute(stk, &ctx, opt, Some(&self.current)).await?.is_truthy() {
+							if !Self::permitted(stk, &ctx, opt, Some(&self.current), e).await? {
 								val = old.as_ref().clone()
 							}
 						}
@@ -389,14 +414,36 @@ impl Document {
 				// Skip this field?
 				if !skipped {
 					// If the field is empty, mark child fields as skippable
-					if val.is_none() && fd.kind.as_ref().is_some_and(Kind::can_be_none) {
+					if val.is_none() && kind.as_deref().is_some_and(Kind::can_be_none) {
 						skip = Some(&fd.name);
 					}
 					// Set the new value of the field, or delete it if empty
-					match val {
+					match &val {
 						Value::None => self.current.doc.to_mut().del(stk, ctx, opt, &k).await?,
-						v => self.current.doc.to_mut().set(stk, ctx, opt, &k, v).await?,
+						v => self.current.doc.to_mut().set(stk, ctx, opt, &k, v.clone()).await?,
 					};
+					// Check for a CHECK clause
+					if let Some(expr) = &fd.check {
+						// Arc the current value
+						let now = Arc::new(val);
+						// Configure the context
+						let mut ctx = MutableContext::new(ctx);
+						ctx.add_value("before", old.clone());
+						ctx.add_value("input", inp.clone());
+						ctx.add_value("after", now.clone());
+						ctx.add_value("value", now.clone());
+						// Freeze the new context
+						let ctx = ctx.freeze();
+						// Process the CHECK clause against the updated document
+						if !expr.compute(stk, &ctx, opt, Some(&self.current)).await?.is_truthy() {
+							return Err(Error::FieldConstraint {
+								thing: rid.to_string(),
+								field: fd.name.clone(),
+								check: expr.to_string(),
+								value: now.to_string(),
+							});
+						}
+					}
 				}
 			}
 		}
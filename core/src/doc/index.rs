@@ -4,6 +4,7 @@ use crate::dbs::{Force, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::spatial::SpatialIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
@@ -94,6 +95,7 @@ impl Document {
 			Index::Search(p) => ic.index_full_text(stk, ctx, p).await?,
 			Index::MTree(p) => ic.index_mtree(stk, ctx, p).await?,
 			Index::Hnsw(p) => ic.index_hnsw(ctx, p).await?,
+			Index::Spatial => ic.index_spatial(ctx).await?,
 		}
 		Ok(())
 	}
@@ -112,10 +114,16 @@ impl Document {
 		if !doc.doc.as_ref().is_some() {
 			return Ok(None);
 		}
+		// A partial index only indexes the records matching its condition
+		if let Some(cond) = &ix.cond {
+			if !cond.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+				return Ok(None);
+			}
+		}
 		let mut o = Vec::with_capacity(ix.cols.len());
 		for i in ix.cols.iter() {
 			let v = i.compute(stk, ctx, opt, Some(doc)).await?;
-			o.push(v);
+			o.push(ix.collate(v));
 		}
 		Ok(Some(o))
 	}
@@ -440,4 +448,18 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_spatial(&mut self, ctx: &Context) -> Result<(), Error> {
+		let tx = ctx.tx();
+		let si = SpatialIndex::new(self.opt.ns()?, self.opt.db()?, self.ix);
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			si.remove_document(&tx, self.rid, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			si.index_document(&tx, self.rid, &n).await?;
+		}
+		Ok(())
+	}
 }
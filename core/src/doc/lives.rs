@@ -1,8 +1,10 @@
+use crate::cf::TableMutation;
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::Action;
 use crate::dbs::Notification;
 use crate::dbs::Options;
 use crate::dbs::Statement;
+use crate::dbs::Workable;
 use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
@@ -11,6 +13,7 @@ use crate::sql::paths::META;
 use crate::sql::paths::RD;
 use crate::sql::paths::TK;
 use crate::sql::permission::Permission;
+use crate::sql::statements::LiveStatement;
 use crate::sql::Value;
 use reblessive::tree::Stk;
 use std::sync::Arc;
@@ -33,174 +36,246 @@ impl Document {
 			return Ok(());
 		}
 
-		// Check if we can send notifications
-		let Some(chn) = opt.sender.as_ref() else {
-			// no channel so nothing to do.
-			return Ok(());
-		};
-
 		// Check if changed
 		if !self.changed() {
 			return Ok(());
 		}
 
-		// Get all live queries for this table
+		// Get the event action
+		let action = if stm.is_delete() {
+			Action::Delete
+		} else if self.is_new() {
+			Action::Create
+		} else {
+			Action::Update
+		};
+		// Get the compiled live queries for this table
 		let lvs = self.lv(ctx, opt).await?;
-		// Loop through all index statements
-		for lv in lvs.iter() {
-			// Create a new statement
-			let lq = Statement::from(lv);
-			// Get the event action
-			let met = if stm.is_delete() {
-				Value::from("DELETE")
-			} else if self.is_new() {
-				Value::from("CREATE")
-			} else {
-				Value::from("UPDATE")
-			};
-			// Get the record if of this docunent
-			let rid = self.id.as_ref().unwrap();
-			// Get the current and initial docs
-			let current = self.current.doc.as_arc();
-			let initial = self.initial.doc.as_arc();
-			// Check if this is a delete statement
-			let doc = match stm.is_delete() {
-				true => &self.initial,
-				false => &self.current,
-			};
-			// Ensure that a session exists on the LIVE query
-			let sess = match lv.session.as_ref() {
-				Some(v) => v,
-				None => continue,
-			};
-			// Ensure that auth info exists on the LIVE query
-			let auth = match lv.auth.clone() {
-				Some(v) => v,
-				None => continue,
-			};
-			// We need to create a new context which we will
-			// use for processing this LIVE query statement.
-			// This ensures that we are using the session
-			// of the user who created the LIVE query.
-			let mut lqctx = MutableContext::background();
-			// Set the current transaction on the new LIVE
-			// query context to prevent unreachable behaviour
-			// and ensure that queries can be executed.
-			lqctx.set_transaction(ctx.tx());
-			// Add the session params to this LIVE query, so
-			// that queries can use these within field
-			// projections and WHERE clauses.
-			lqctx.add_value("access", sess.pick(AC.as_ref()).into());
-			lqctx.add_value("auth", sess.pick(RD.as_ref()).into());
-			lqctx.add_value("token", sess.pick(TK.as_ref()).into());
-			lqctx.add_value("session", sess.clone().into());
-			// Add $before, $after, $value, and $event params
-			// to this LIVE query so the user can use these
-			// within field projections and WHERE clauses.
-			lqctx.add_value("event", met.into());
-			lqctx.add_value("value", current.clone());
-			lqctx.add_value("after", current);
-			lqctx.add_value("before", initial);
-			// We need to create a new options which we will
-			// use for processing this LIVE query statement.
-			// This ensures that we are using the auth data
-			// of the user who created the LIVE query.
-			let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
-			// First of all, let's check to see if the WHERE
-			// clause of the LIVE query is matched by this
-			// document. If it is then we can continue.
-			let lqctx = lqctx.freeze();
-			match self.lq_check(stk, &lqctx, &lqopt, &lq, doc).await {
-				Err(Error::Ignore) => continue,
-				Err(e) => return Err(e),
-				Ok(_) => (),
+		// Maintain the aggregates of any grouped live queries,
+		// even when notifications are sent by another node
+		for lv in lvs.aggregates() {
+			self.lq_aggregate(stk, ctx, opt, lv, &action).await?;
+		}
+		// Check if we can send notifications
+		if !ctx.tx().notifies().await {
+			// no channel so nothing to do.
+			return Ok(());
+		}
+		// Check if this is a delete statement
+		let doc = match action {
+			Action::Delete => &self.initial,
+			_ => &self.current,
+		};
+		// Get the live queries which may match
+		let candidates = lvs.candidates(doc.doc.as_ref());
+		// Check if any live queries may match
+		if candidates.is_empty() {
+			return Ok(());
+		}
+		// Get the versionstamp to resume from
+		let resume = self.lq_resume(ctx, opt).await?;
+		// Loop through the live queries which may match
+		for (lv, matched) in candidates {
+			// Send a notification for this live query
+			self.lq_notify(stk, ctx, opt, lv, matched, &action, resume).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Replays a change from the change feed to a
+	/// LIVE SELECT statement which is being resumed.
+	/// When the change feed does not include the
+	/// original documents, a deleted record is only
+	/// known by its id, so the delete is replayed
+	/// without checking the WHERE clause, and the
+	/// PERMISSIONS are checked against the record id.
+	pub(crate) async fn process_resumed_live(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		change: TableMutation,
+		resume: u64,
+	) -> Result<(), Error> {
+		// Check if we can send notifications
+		if !ctx.tx().notifies().await {
+			// no channel so nothing to do.
+			return Ok(());
+		}
+		// Get the change action and documents
+		let (action, id, initial, current, matched) = match change {
+			TableMutation::Set(id, v) => (Action::Create, id, Value::None, v, false),
+			TableMutation::Update(id, v) | TableMutation::SetWithDiff(id, v, _) => {
+				(Action::Update, id, Value::None, v, false)
+			}
+			TableMutation::Del(id) => {
+				let v = Value::from(map! { "id".to_string() => Value::Thing(id.clone()) });
+				(Action::Delete, id, v, Value::None, true)
 			}
-			// Secondly, let's check to see if any PERMISSIONS
-			// clause for this table allows this document to
-			// be viewed by the user who created this LIVE
-			// query. If it does, then we can continue.
-			match self.lq_allow(stk, &lqctx, &lqopt, &lq, doc).await {
-				Err(Error::Ignore) => continue,
+			TableMutation::DelWithOriginal(id, v) => (Action::Delete, id, v, Value::None, false),
+			_ => return Ok(()),
+		};
+		// Create a document for this change
+		let id = Arc::new(id);
+		let mut doc =
+			Document::new(Some(id.clone()), None, None, Arc::new(initial), Workable::Normal, false);
+		doc.current = CursorDoc::new(Some(id), None, current);
+		// Send a notification for this live query
+		doc.lq_notify(stk, ctx, opt, lv, matched, &action, Some(resume)).await?;
+		// Carry on
+		Ok(())
+	}
+	/// Get the change feed versionstamp from which
+	/// a LIVE query can be resumed after this change,
+	/// if change feeds are enabled for this table.
+	async fn lq_resume(&self, ctx: &Context, opt: &Options) -> Result<Option<u64>, Error> {
+		// Get the namespace
+		let ns = opt.ns()?;
+		// Get the database
+		let db = opt.db()?;
+		// Get the table
+		let tb = self.tb(ctx, opt).await?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the database for the record
+		let dbv = txn.get_db(ns, db).await?;
+		// Check if changefeeds are enabled
+		if dbv.changefeed.is_none() && tb.changefeed.is_none() {
+			return Ok(None);
+		}
+		// Get the versionstamp of this change
+		let vs = txn.lock().await.get_versionstamp_lower_bound(ns, db).await?;
+		// Return the versionstamp
+		Ok(Some(vs))
+	}
+	/// Sends a notification to a LIVE query, if the
+	/// document matches the WHERE clause and can be
+	/// viewed by the user who created the LIVE query.
+	/// The notification is sent once the transaction
+	/// has been committed successfully.
+	#[allow(clippy::too_many_arguments)]
+	async fn lq_notify(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		matched: bool,
+		action: &Action,
+		resume: Option<u64>,
+	) -> Result<(), Error> {
+		// Create a new statement
+		let lq = Statement::from(lv);
+		// Get the record if of this docunent
+		let rid = self.id.clone().unwrap();
+		// Process the LIVE query as the user who created it
+		let Some((lqctx, lqopt)) = self.lq_context(ctx, opt, lv, action) else {
+			return Ok(());
+		};
+		// Check if this is a delete action
+		let doc = match action {
+			Action::Delete => &self.initial,
+			_ => &self.current,
+		};
+		// First of all, let's check to see if the WHERE
+		// clause of the LIVE query is matched by this
+		// document, unless the compiled clause already
+		// matched. If it is then we can continue.
+		if !matched {
+			match self.lq_check(stk, &lqctx, &lqopt, &lq, doc).await {
+				Err(Error::Ignore) => return Ok(()),
 				Err(e) => return Err(e),
 				Ok(_) => (),
 			}
-			// Finally, let's check what type of statement
-			// caused this LIVE query to run, and send the
-			// relevant notification based on the statement.
-			if stm.is_delete() {
-				// Send a DELETE notification
-				if opt.id()? == lv.node.0 {
-					// Ensure futures are run
-					let lqopt: &Options = &lqopt.new_with_futures(true);
-					// Output the full document before any changes were applied
-					let mut result =
-						doc.doc.as_ref().compute(stk, &lqctx, lqopt, Some(doc)).await?;
-					// Remove metadata fields on output
-					result.del(stk, &lqctx, lqopt, &*META).await?;
-					let res = chn
-						.send(Notification {
-							id: lv.id,
-							action: Action::Delete,
-							record: Value::Thing(rid.as_ref().clone()),
-							result,
-						})
-						.await;
-
-					if res.is_err() {
-						// channel was closed, that means a transaction probably failed.
-						// just return as nothing can be send.
-						return Ok(());
-					}
-				} else {
-					// TODO: Send to message broker
-				}
-			} else if self.is_new() {
-				// Send a CREATE notification
-				if opt.id()? == lv.node.0 {
-					let res = chn
-						.send(Notification {
-							id: lv.id,
-							action: Action::Create,
-							record: Value::Thing(rid.as_ref().clone()),
-							result: self.pluck(stk, &lqctx, &lqopt, &lq).await?,
-						})
-						.await;
-
-					if res.is_err() {
-						// channel was closed, that means a transaction probably failed.
-						// just return as nothing can be send.
-						return Ok(());
-					}
-				} else {
-					// TODO: Send to message broker
-				}
-			} else {
-				// Send a UPDATE notification
-				if opt.id()? == lv.node.0 {
-					let res = chn
-						.send(Notification {
-							id: lv.id,
-							action: Action::Update,
-							record: Value::Thing(rid.as_ref().clone()),
-							result: self.pluck(stk, &lqctx, &lqopt, &lq).await?,
-						})
-						.await;
-
-					if res.is_err() {
-						// channel was closed, that means a transaction probably failed.
-						// just return as nothing can be send.
-						return Ok(());
-					}
-				} else {
-					// TODO: Send to message broker
-				}
-			};
 		}
+		// Secondly, let's check to see if any PERMISSIONS
+		// clause for this table allows this document to
+		// be viewed by the user who created this LIVE
+		// query. If it does, then we can continue.
+		match self.lq_allow(stk, &lqctx, &lqopt, &lq, doc).await {
+			Err(Error::Ignore) => return Ok(()),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		// Check that the LIVE query belongs to this node
+		if opt.id()? != lv.node.0 {
+			// TODO: Send to message broker
+			return Ok(());
+		}
+		// Finally, let's check what type of action
+		// caused this LIVE query to run, and send the
+		// relevant notification based on the action.
+		let result = match action {
+			Action::Delete => {
+				// Ensure futures are run
+				let lqopt: &Options = &lqopt.new_with_futures(true);
+				// Output the full document before any changes were applied
+				let mut result = doc.doc.as_ref().compute(stk, &lqctx, lqopt, Some(doc)).await?;
+				// Remove metadata fields on output
+				result.del(stk, &lqctx, lqopt, &*META).await?;
+				result
+			}
+			_ => self.pluck(stk, &lqctx, &lqopt, &lq).await?,
+		};
+		// Send the notification once committed
+		let notification =
+			Notification::new(lv.id, action.clone(), Value::Thing(rid.as_ref().clone()), result);
+		ctx.tx().notify(notification.with_resume(resume)).await;
 		// Carry on
 		Ok(())
 	}
+	/// Creates the context and options with which a
+	/// LIVE query is processed, using the session and
+	/// auth data of the user who created the LIVE query.
+	/// Returns [`None`] if the LIVE query has no session.
+	pub(super) fn lq_context(
+		&mut self,
+		ctx: &Context,
+		opt: &Options,
+		lv: &LiveStatement,
+		action: &Action,
+	) -> Option<(Context, Options)> {
+		// Get the event action
+		let met = Value::from(action.to_string());
+		// Get the current and initial docs
+		let current = self.current.doc.as_arc();
+		let initial = self.initial.doc.as_arc();
+		// Ensure that a session exists on the LIVE query
+		let sess = lv.session.as_ref()?;
+		// Ensure that auth info exists on the LIVE query
+		let auth = lv.auth.clone()?;
+		// We need to create a new context which we will
+		// use for processing this LIVE query statement.
+		// This ensures that we are using the session
+		// of the user who created the LIVE query.
+		let mut lqctx = MutableContext::background();
+		// Set the current transaction on the new LIVE
+		// query context to prevent unreachable behaviour
+		// and ensure that queries can be executed.
+		lqctx.set_transaction(ctx.tx());
+		// Add the session params to this LIVE query, so
+		// that queries can use these within field
+		// projections and WHERE clauses.
+		lqctx.add_value("access", sess.pick(AC.as_ref()).into());
+		lqctx.add_value("auth", sess.pick(RD.as_ref()).into());
+		lqctx.add_value("token", sess.pick(TK.as_ref()).into());
+		lqctx.add_value("session", sess.clone().into());
+		// Add $before, $after, $value, and $event params
+		// to this LIVE query so the user can use these
+		// within field projections and WHERE clauses.
+		lqctx.add_value("event", met.into());
+		lqctx.add_value("value", current.clone());
+		lqctx.add_value("after", current);
+		lqctx.add_value("before", initial);
+		// We need to create a new options which we will
+		// use for processing this LIVE query statement.
+		// This ensures that we are using the auth data
+		// of the user who created the LIVE query.
+		let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
+		Some((lqctx.freeze(), lqopt))
+	}
 	/// Check the WHERE clause for a LIVE query
-	async fn lq_check(
+	pub(super) async fn lq_check(
 		&self,
 		stk: &mut Stk,
 		ctx: &Context,
@@ -220,7 +295,7 @@ impl Document {
 		Ok(())
 	}
 	/// Check any PERRMISSIONS for a LIVE query
-	async fn lq_allow(
+	pub(super) async fn lq_allow(
 		&self,
 		stk: &mut Stk,
 		ctx: &Context,
@@ -240,7 +315,7 @@ impl Document {
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
 					// Process the PERMISSION clause
-					if !e.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+					if !Self::permitted(stk, ctx, opt, Some(doc), e).await? {
 						return Err(Error::Ignore);
 					}
 				}
@@ -20,6 +20,7 @@ mod select; // Processes a SELECT statement for this document
 mod update; // Processes a UPDATE statement for this document
 mod upsert; // Processes a UPSERT statement for this document
 
+mod aggregate; // Maintains the aggregates of any grouped live queries
 mod alter; // Modifies and updates the fields in this document
 mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Checks whether the WHERE clauses matches this document
@@ -28,7 +29,11 @@ mod event; // Processes any table events relevant for this document
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
+mod partition; // Stores the table partition of this document
+mod permission; // Evaluates the PERMISSIONS clauses for this document
 mod pluck; // Pulls the projected expressions from the document
 mod purge; // Deletes this document, and any edges or indexes
+mod reference; // Processes any record references relevant for this document
 mod store; // Writes the document content to the storage engine
 mod table; // Processes any foreign tables relevant for this document
+mod ttl; // Stores the expiry time of this document
@@ -0,0 +1,71 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Statement;
+use crate::doc::CursorDoc;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::value::Value;
+use reblessive::tree::Stk;
+
+impl Document {
+	/// Stores the partition of this record if the table is partitioned
+	pub async fn store_partition_data(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the table definition
+		let tb = self.tb(ctx, opt).await?;
+		// Check if the table is a view
+		if tb.drop {
+			return Ok(());
+		}
+		// Check if the table is partitioned
+		let Some(expr) = &tb.partition else {
+			return Ok(());
+		};
+		// Get the namespace
+		let ns = opt.ns()?;
+		// Get the database
+		let db = opt.db()?;
+		// Get the record id
+		let rid = self.id()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Calculate the old and new partitions
+		let o = Self::partition_value(stk, ctx, opt, expr, &self.initial).await?;
+		let n = Self::partition_value(stk, ctx, opt, expr, &self.current).await?;
+		// Update the partition entries
+		if o != n {
+			if let Some(o) = o {
+				let key = crate::key::partition::new(ns, db, &rid.tb, &o, &rid.id);
+				txn.del(key).await?;
+			}
+			if let Some(n) = n {
+				let key = crate::key::partition::new(ns, db, &rid.tb, &n, &rid.id);
+				txn.set(key, vec![], opt.version).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+
+	async fn partition_value(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		expr: &Value,
+		doc: &CursorDoc,
+	) -> Result<Option<Value>, Error> {
+		if !doc.doc.as_ref().is_some() {
+			return Ok(None);
+		}
+		Ok(Some(expr.compute(stk, ctx, opt, Some(doc)).await?))
+	}
+}
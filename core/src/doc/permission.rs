@@ -0,0 +1,187 @@
+use crate::cnf::PROTECTED_PARAM_NAMES;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::value::Value;
+use crate::sql::{Cast, Expression, Function, Id, Operator, Part};
+use reblessive::tree::Stk;
+use std::sync::Arc;
+
+impl Document {
+	/// Evaluates a `PERMISSIONS` clause expression for
+	/// a document. Any parts of the expression which do
+	/// not depend on the document, such as the fields of
+	/// the `$auth` record, are computed once for each
+	/// session within the transaction, and the resolved
+	/// expression is cached so that only the document
+	/// dependent parts are computed for every record.
+	pub(super) async fn permitted(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		expr: &Value,
+	) -> Result<bool, Error> {
+		// Get the transaction
+		let txn = ctx.tx();
+		// The session determines the auth, token, and access
+		let session = ctx.value("session").unwrap_or(&Value::None);
+		// Get the NS and DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Fetch or resolve the permission expression
+		let expr = match txn.get_resolved_permission(ns, db, session, expr) {
+			Some(v) => v,
+			None => {
+				let val = Arc::new(resolve(stk, ctx, opt, expr).await?);
+				txn.set_resolved_permission(ns, db, session, expr, val.clone());
+				val
+			}
+		};
+		// Process the resolved PERMISSION clause
+		Ok(expr.compute(stk, ctx, opt, doc).await?.is_truthy())
+	}
+}
+
+/// Checks whether a value computes to the same result for
+/// every document within a transaction, depending only on
+/// the session parameters and on literal values.
+fn is_invariant(v: &Value) -> bool {
+	match v {
+		Value::None
+		| Value::Null
+		| Value::Bool(_)
+		| Value::Number(_)
+		| Value::Strand(_)
+		| Value::Duration(_)
+		| Value::Datetime(_)
+		| Value::Uuid(_)
+		| Value::Geometry(_)
+		| Value::Bytes(_)
+		| Value::Regex(_)
+		| Value::Constant(_) => true,
+		Value::Thing(v) => match &v.id {
+			Id::Array(v) => v.iter().all(is_invariant),
+			Id::Object(v) => v.values().all(is_invariant),
+			Id::Generate(_) | Id::Range(_) => false,
+			_ => true,
+		},
+		Value::Array(v) => v.iter().all(is_invariant),
+		Value::Object(v) => v.values().all(is_invariant),
+		Value::Param(v) => PROTECTED_PARAM_NAMES.contains(&v.as_str()),
+		Value::Idiom(v) => match v.split_first() {
+			Some((Part::Start(s), rest)) => {
+				is_invariant(s)
+					&& rest.iter().all(|p| {
+						matches!(
+							p,
+							Part::Field(_)
+								| Part::Index(_) | Part::All
+								| Part::First | Part::Last
+								| Part::Flatten | Part::Optional
+						)
+					})
+			}
+			_ => false,
+		},
+		Value::Cast(v) => is_invariant(&v.1),
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => is_invariant(v),
+			Expression::Binary {
+				l,
+				o,
+				r,
+			} => !is_indexed(o) && is_invariant(l) && is_invariant(r),
+		},
+		_ => false,
+	}
+}
+
+/// Checks whether an operator is evaluated using an index,
+/// in which case its operands must be left untouched.
+fn is_indexed(o: &Operator) -> bool {
+	matches!(o, Operator::Matches(_) | Operator::Knn(_, _) | Operator::Ann(_, _))
+}
+
+/// Computes a value which is the same for every document,
+/// returning nothing if the value depends on the document.
+/// Any errors are left to surface if the value is used.
+async fn compute(stk: &mut Stk, ctx: &Context, opt: &Options, v: &Value) -> Option<Value> {
+	match is_invariant(v) {
+		true => v.compute(stk, ctx, opt, None).await.ok(),
+		false => None,
+	}
+}
+
+/// Computes the invariant parts of a permission expression,
+/// returning an expression in which only the document
+/// dependent parts remain to be computed.
+async fn resolve(stk: &mut Stk, ctx: &Context, opt: &Options, v: &Value) -> Result<Value, Error> {
+	// Compute values which are the same for every document
+	if is_invariant(v) {
+		return Ok(compute(stk, ctx, opt, v).await.unwrap_or_else(|| v.clone()));
+	}
+	// Otherwise resolve any invariant parts of the value
+	match v {
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Unary {
+				o,
+				v,
+			} => Ok(Value::Expression(Box::new(Expression::Unary {
+				o: o.clone(),
+				v: stk.run(|stk| resolve(stk, ctx, opt, v)).await?,
+			}))),
+			Expression::Binary {
+				o,
+				..
+			} if is_indexed(o) => Ok(v.clone()),
+			Expression::Binary {
+				l,
+				o,
+				r,
+			} => {
+				let l = match compute(stk, ctx, opt, l).await {
+					// Short-circuit logical operators which are already decided
+					Some(l) => match o {
+						Operator::Or if l.is_truthy() => return Ok(l),
+						Operator::And if !l.is_truthy() => return Ok(l),
+						_ => l,
+					},
+					None => stk.run(|stk| resolve(stk, ctx, opt, l)).await?,
+				};
+				Ok(Value::Expression(Box::new(Expression::Binary {
+					l,
+					o: o.clone(),
+					r: stk.run(|stk| resolve(stk, ctx, opt, r)).await?,
+				})))
+			}
+		},
+		Value::Array(a) => {
+			let mut out = Vec::with_capacity(a.len());
+			for v in a.iter() {
+				out.push(stk.run(|stk| resolve(stk, ctx, opt, v)).await?);
+			}
+			Ok(Value::from(out))
+		}
+		Value::Cast(c) => Ok(Value::Cast(Box::new(Cast(
+			c.0.clone(),
+			stk.run(|stk| resolve(stk, ctx, opt, &c.1)).await?,
+		)))),
+		Value::Function(f) => match f.as_ref() {
+			Function::Normal(name, args) => {
+				let mut out = Vec::with_capacity(args.len());
+				for v in args.iter() {
+					out.push(stk.run(|stk| resolve(stk, ctx, opt, v)).await?);
+				}
+				Ok(Value::Function(Box::new(Function::Normal(name.clone(), out))))
+			}
+			_ => Ok(v.clone()),
+		},
+		_ => Ok(v.clone()),
+	}
+}
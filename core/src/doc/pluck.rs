@@ -83,22 +83,27 @@ impl Document {
 					ctx.add_value("after", current.doc.as_arc());
 					ctx.add_value("before", initial.doc.as_arc());
 					let ctx = ctx.freeze();
+					// Deleted records are projected from the record before deletion
+					let doc = match stm {
+						Statement::Delete(_) => initial,
+						_ => current,
+					};
 					// Output the specified fields
-					v.compute(stk, &ctx, opt, Some(current), false).await
+					v.compute(stk, &ctx, opt, Some(doc), false).await
 				}
 			},
 			None => match stm {
-				Statement::Live(s) => match s.expr.len() {
-					0 => {
+				Statement::Live(s) => match s.is_diff() {
+					true => {
 						// Process the permitted documents
 						let (initial, current) = match self.reduced(stk, ctx, opt, Both).await? {
 							true => (&self.initial_reduced, &self.current_reduced),
 							false => (&self.initial, &self.current),
 						};
-						// Output a DIFF of any changes applied to the document
-						Ok(initial.doc.as_ref().diff(current.doc.as_ref(), Idiom::default()).into())
+						// Output a JSON Patch of any changes applied to the document
+						Ok(initial.doc.as_ref().json_patch(current.doc.as_ref()).into())
 					}
-					_ => {
+					false => {
 						// Process the permitted documents
 						let current = match self.reduced(stk, ctx, opt, Current).await? {
 							true => &self.current_reduced,
@@ -217,11 +222,7 @@ impl Document {
 								ctx.add_value("value", val);
 								let ctx = ctx.freeze();
 								// Process the PERMISSION clause
-								if !e
-									.compute(stk, &ctx, opt, Some(&self.current))
-									.await?
-									.is_truthy()
-								{
+								if !Self::permitted(stk, &ctx, opt, Some(&self.current), e).await? {
 									out.cut(k);
 								}
 							}
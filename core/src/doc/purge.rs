@@ -3,6 +3,7 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::kvs::Count;
 use crate::sql::dir::Dir;
 use crate::sql::edges::Edges;
 use crate::sql::paths::EDGE;
@@ -38,6 +39,8 @@ impl Document {
 			// Purge the record data
 			let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id);
 			txn.del(key).await?;
+			// Uncount the record
+			txn.record_count(ns, db, &rid.tb, Count::rows(-1));
 			// Purge the record edges
 			match (
 				self.initial.doc.as_ref().pick(&*EDGE),
@@ -0,0 +1,169 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Statement;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::key::r#ref::Ref;
+use crate::sql::data::Data;
+use crate::sql::statements::define::OnDelete;
+use crate::sql::statements::{DeleteStatement, UpdateStatement};
+use crate::sql::value::{Value, Values};
+use crate::sql::{Operator, Part, Thing};
+use reblessive::tree::Stk;
+
+impl Document {
+	/// Stores the references of any record link fields with an ON DELETE clause
+	pub async fn store_record_references(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Check if the table is a view
+		if self.tb(ctx, opt).await?.drop {
+			return Ok(());
+		}
+		// Get the namespace
+		let ns = opt.ns()?;
+		// Get the database
+		let db = opt.db()?;
+		// Get the record id
+		let rid = self.id()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Loop through all field statements
+		for fd in self.fd(ctx, opt).await?.iter() {
+			// Only fields with an ON DELETE clause are tracked
+			if fd.on_delete.is_none() {
+				continue;
+			}
+			// Get the name of the field
+			let ff = fd.name.to_string();
+			// Get the old and new linked records
+			let o = links(self.initial.doc.as_ref().pick(&fd.name));
+			let n = links(self.current.doc.as_ref().pick(&fd.name));
+			// Remove the references which no longer exist
+			for v in o.iter().filter(|v| !n.contains(v)) {
+				let key = crate::key::r#ref::new(ns, db, &v.tb, &v.id, &ff, &rid);
+				txn.del(key).await?;
+			}
+			// Store the references which have been added
+			for v in n.iter().filter(|v| !o.contains(v)) {
+				let key = crate::key::r#ref::new(ns, db, &v.tb, &v.id, &ff, &rid);
+				txn.set(key, vec![], opt.version).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Processes the ON DELETE clauses of any records which reference this record
+	pub async fn process_record_references(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the namespace
+		let ns = opt.ns()?;
+		// Get the database
+		let db = opt.db()?;
+		// Get the record id
+		let rid = self.id()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Fetch the references to this record
+		let beg = crate::key::r#ref::prefix(ns, db, &rid.tb, &rid.id);
+		let end = crate::key::r#ref::suffix(ns, db, &rid.tb, &rid.id);
+		let keys = txn.keys(beg..end, u32::MAX, None).await?;
+		// Loop through all references
+		for key in keys {
+			let r = Ref::decode(&key)?;
+			let by = Thing::from((r.ft, r.fk.clone()));
+			// Get the field which holds the reference
+			let fd = match txn.get_tb_field(ns, db, r.ft, r.ff).await {
+				Ok(fd) => fd,
+				Err(Error::FdNotFound {
+					..
+				}) => {
+					// The field has since been removed
+					txn.del(key).await?;
+					continue;
+				}
+				Err(e) => return Err(e),
+			};
+			match fd.on_delete {
+				// The ON DELETE clause has since been removed
+				None => txn.del(key).await?,
+				// The record can not be deleted while it is referenced
+				Some(OnDelete::Restrict) => {
+					return Err(Error::RecordReferenced {
+						thing: rid.to_string(),
+						by: by.to_string(),
+						field: r.ff.to_owned(),
+					})
+				}
+				// Delete the referencing record
+				Some(OnDelete::Cascade) => {
+					let stm = DeleteStatement {
+						what: Values(vec![Value::from(by)]),
+						..DeleteStatement::default()
+					};
+					stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?;
+				}
+				// Remove the link from the referencing record
+				Some(OnDelete::Unset) => {
+					let mut name = fd.name.clone();
+					if name.last() == Some(&Part::All) {
+						name.0.pop();
+					}
+					let val = txn.get_record(ns, db, &by.tb, &by.id).await?;
+					let data = match val.pick(&name) {
+						Value::Array(_) => Data::SetExpression(vec![(
+							name,
+							Operator::Dec,
+							Value::from(rid.as_ref().clone()),
+						)]),
+						_ => Data::UnsetExpression(vec![name]),
+					};
+					let stm = UpdateStatement {
+						what: Values(vec![Value::from(by)]),
+						data: Some(data),
+						..UpdateStatement::default()
+					};
+					stk.run(|stk| stm.compute(stk, ctx, opt, None)).await?;
+				}
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+}
+
+/// Collects the records which are linked to within a field value
+fn links(val: Value) -> Vec<Thing> {
+	let mut out = Vec::new();
+	collect(val, &mut out);
+	out
+}
+
+fn collect(val: Value, out: &mut Vec<Thing>) {
+	match val {
+		Value::Thing(v) if !out.contains(&v) => out.push(v),
+		Value::Array(v) => {
+			for v in v {
+				collect(v, out);
+			}
+		}
+		_ => (),
+	}
+}
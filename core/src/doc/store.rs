@@ -3,6 +3,7 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::kvs::Count;
 
 impl Document {
 	pub async fn store_record_data(
@@ -85,6 +86,10 @@ impl Document {
 			// Let's update the stored value for the specified key
 			_ => ctx.tx().set(key, self, opt.version).await,
 		}?;
+		// Count the record if it was created
+		if self.is_new() {
+			ctx.tx().record_count(opt.ns()?, opt.db()?, &rid.tb, Count::rows(1)).await;
+		}
 		// Carry on
 		Ok(())
 	}
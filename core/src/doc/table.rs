@@ -121,6 +121,7 @@ impl Document {
 										stk,
 										ctx,
 										opt,
+										tb,
 										group,
 										&self.initial,
 									)
@@ -146,6 +147,7 @@ impl Document {
 										stk,
 										ctx,
 										opt,
+										tb,
 										group,
 										&self.current,
 									)
@@ -168,6 +170,7 @@ impl Document {
 										stk,
 										ctx,
 										opt,
+										tb,
 										group,
 										&self.initial,
 									)
@@ -187,6 +190,7 @@ impl Document {
 										stk,
 										ctx,
 										opt,
+										tb,
 										group,
 										&self.current,
 									)
@@ -284,13 +288,15 @@ impl Document {
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
+		view: &View,
 		group: &Groups,
 		doc: &CursorDoc,
 	) -> Result<Vec<Value>, Error> {
+		let exprs = group.iter().map(|v| Self::group_expr(view, v)).collect::<Vec<_>>();
 		Ok(stk
 			.scope(|scope| {
 				try_join_all(
-					group.iter().map(|v| scope.run(|stk| v.compute(stk, ctx, opt, Some(doc)))),
+					exprs.iter().map(|v| scope.run(|stk| v.compute(stk, ctx, opt, Some(doc)))),
 				)
 			})
 			.await?
@@ -298,6 +304,23 @@ impl Document {
 			.collect::<Vec<_>>())
 	}
 
+	/// Gets the expression which a GROUP BY clause is computed
+	/// from. A group can refer to an aliased field in the view
+	/// projection, such as `time::floor(at, 1d) AS day`, in
+	/// which case the aliased expression is used for the group.
+	fn group_expr(view: &View, group: &Idiom) -> Value {
+		view.expr
+			.other()
+			.find_map(|field| match field {
+				Field::Single {
+					expr,
+					alias: Some(alias),
+				} if alias == group => Some(expr.clone()),
+				_ => None,
+			})
+			.unwrap_or_else(|| Value::Idiom(group.clone()))
+	}
+
 	//
 	async fn full(
 		&self,
@@ -736,13 +759,13 @@ impl Document {
 		let mut iter = fdc.groups.0.iter().enumerate();
 		let cond = if let Some((i, g)) = iter.next() {
 			let mut root = Value::Expression(Box::new(Expression::Binary {
-				l: Value::Idiom(g.0.clone()),
+				l: Self::group_expr(fdc.view, g),
 				o: Operator::Equal,
 				r: fdc.group_ids[i].clone(),
 			}));
 			for (i, g) in iter {
 				let exp = Value::Expression(Box::new(Expression::Binary {
-					l: Value::Idiom(g.0.clone()),
+					l: Self::group_expr(fdc.view, g),
 					o: Operator::Equal,
 					r: fdc.group_ids[i].clone(),
 				}));
@@ -768,7 +791,8 @@ impl Document {
 			expr: Fields(vec![field.clone()], false),
 			cond,
 			what: (&fdc.view.what).into(),
-			group: Some(fdc.groups.clone()),
+			// The condition only matches this one group
+			group: Some(Groups(vec![])),
 			..SelectStatement::default()
 		})));
 		let array_first = Value::Function(Box::new(Function::Normal(
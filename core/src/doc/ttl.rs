@@ -0,0 +1,64 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Statement;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::duration::Duration;
+use crate::sql::value::Value;
+use crate::sql::Datetime;
+
+impl Document {
+	/// Stores the expiry time of this record if the table has a TTL
+	pub async fn store_ttl_data(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the table definition
+		let tb = self.tb(ctx, opt).await?;
+		// Check if the table is a view
+		if tb.drop {
+			return Ok(());
+		}
+		// Check if the table has a TTL
+		let Some(ttl) = &tb.ttl else {
+			return Ok(());
+		};
+		// Get the record id
+		let rid = self.id()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the expiry key for this record
+		let key = crate::key::ttl::new(opt.ns()?, opt.db()?, &rid.tb, &rid.id);
+		// Remove the expiry if the record is deleted
+		if self.current.doc.as_ref().is_none() {
+			txn.del(key).await?;
+			return Ok(());
+		}
+		// Calculate when the record expires
+		match &ttl.field {
+			// The record expires after it was created
+			None => {
+				if self.is_new() {
+					let val = Duration(ttl.duration) + Datetime::default();
+					txn.set(key, Value::from(val), None).await?;
+				}
+			}
+			// The record expires after the datetime in the field
+			Some(field) => match self.current.doc.as_ref().pick(field) {
+				Value::Datetime(v) => {
+					let val = Duration(ttl.duration) + v;
+					txn.set(key, Value::from(val), None).await?;
+				}
+				_ => txn.del(key).await?,
+			},
+		}
+		// Carry on
+		Ok(())
+	}
+}
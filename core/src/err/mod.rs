@@ -78,6 +78,10 @@ pub enum Error {
 	#[error("Couldn't write to a read only transaction")]
 	TxReadonly,
 
+	/// The storage engine is unable to enforce the requested isolation level
+	#[error("The storage engine does not support serializable snapshot isolation")]
+	TxIsolationUnsupported,
+
 	/// The conditional value in the request was not equal
 	#[error("Value being checked was not correct")]
 	TxConditionNotMet,
@@ -98,6 +102,20 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The transaction exceeded its deadline and was rolled back
+	#[error("The transaction exceeded its timeout and was rolled back")]
+	TxTimeout,
+
+	/// The transaction failed due to a transient conflict, and can be retried
+	#[error("Failed to commit transaction due to a read or write conflict. This transaction can be retried")]
+	TxRetryable,
+
+	/// The datastore has reached its configured storage quota
+	#[error("The datastore has exceeded its storage quota of {limit} bytes")]
+	StorageQuotaExceeded {
+		limit: u64,
+	},
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -283,6 +301,12 @@ pub enum Error {
 	#[error("The query was not executed due to a cancelled transaction")]
 	QueryCancelled,
 
+	/// The query was aborted, because it processed too many rows
+	#[error("The query was aborted because it processed more than the maximum of {limit} rows")]
+	QueryRowLimitExceeded {
+		limit: u64,
+	},
+
 	/// The query did not execute, because the transaction has failed
 	#[error("The query was not executed due to a failed transaction")]
 	QueryNotExecuted,
@@ -311,6 +335,28 @@ pub enum Error {
 		value: String,
 	},
 
+	/// A savepoint statement was used outside of a transaction
+	#[error("The SAVEPOINT and ROLLBACK TO statements can only be used within a transaction")]
+	SavepointOutsideTransaction,
+
+	/// The requested savepoint does not exist in the current transaction
+	#[error("The savepoint '{name}' does not exist")]
+	SavepointNotFound {
+		name: String,
+	},
+
+	/// The query was undone by rolling back to an earlier savepoint
+	#[error("The query was rolled back to the savepoint '{name}'")]
+	QueryRolledBack {
+		name: String,
+	},
+
+	/// The requested prepared statement does not exist
+	#[error("The prepared statement '{value}' does not exist")]
+	PsNotFound {
+		value: String,
+	},
+
 	/// The requested namespace login does not exist
 	#[error("The namespace login '{value}' does not exist")]
 	NlNotFound {
@@ -335,6 +381,19 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested sink does not exist
+	#[error("The sink '{value}' does not exist")]
+	SkNotFound {
+		value: String,
+	},
+
+	/// A sink was defined on a table without a changefeed
+	#[error("The sink '{value}' can not be defined, as there is no changefeed on the table '{table}' or its database")]
+	SkNoChangeFeed {
+		value: String,
+		table: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function 'fn::{value}' does not exist")]
 	FcNotFound {
@@ -365,6 +424,18 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested type does not exist
+	#[error("The type '{value}' does not exist")]
+	TyNotFound {
+		value: String,
+	},
+
+	/// The requested type is defined in terms of itself
+	#[error("The type '{value}' can not be defined in terms of itself")]
+	TyRecursive {
+		value: String,
+	},
+
 	/// The requested config does not exist
 	#[error("The config for {value} does not exist")]
 	CgNotFound {
@@ -431,6 +502,27 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role does not exist
+	#[error("The root role '{value}' does not exist")]
+	RoleRootNotFound {
+		value: String,
+	},
+
+	/// The requested namespace role does not exist
+	#[error("The role '{value}' does not exist in the namespace '{ns}'")]
+	RoleNsNotFound {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database role does not exist
+	#[error("The role '{value}' does not exist in the database '{db}'")]
+	RoleDbNotFound {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
 	/// Unable to perform the realtime query
 	#[error("Unable to perform the realtime query")]
 	RealtimeDisabled,
@@ -499,6 +591,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not execute REVOKE statement using the specified value
+	#[error("Can not execute REVOKE statement using value '{value}'")]
+	RevokeStatement {
+		value: String,
+	},
+
 	/// Can not execute INSERT statement using the specified value
 	#[error("Can not execute INSERT statement where property 'in' is '{value}'")]
 	InsertStatementIn {
@@ -523,6 +621,30 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not resume a LIVE statement using the specified value
+	#[error("Can not resume LIVE statement using value '{value}', expected a versionstamp")]
+	LiveResume {
+		value: String,
+	},
+
+	/// Can not resume a LIVE statement without a change feed
+	#[error("Can not resume LIVE statement as there is no change feed on the table '{tb}'")]
+	LiveResumeChangefeed {
+		tb: String,
+	},
+
+	/// Can not maintain the specified field of a grouped LIVE statement
+	#[error("Can not maintain LIVE statement field '{value}' incrementally, expected count(), math::sum(), math::mean(), or a GROUP BY field")]
+	LiveAggregate {
+		value: String,
+	},
+
+	/// Can not use the specified clause in a grouped LIVE statement
+	#[error("Can not use {clause} in a LIVE statement with a GROUP clause")]
+	LiveAggregateClause {
+		clause: String,
+	},
+
 	/// Can not execute KILL statement using the specified id
 	#[error("Can not execute KILL statement using id '{value}'")]
 	KillStatement {
@@ -571,6 +693,14 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The record can not be deleted as it is referenced through an ON DELETE RESTRICT field
+	#[error("Cannot delete record `{thing}` as it is referenced by record `{by}` through field `{field}`")]
+	RecordReferenced {
+		thing: String,
+		by: String,
+		field: String,
+	},
+
 	/// The specified table is not configured for the type of record being added
 	#[error("Found record: `{thing}` which is {}a relation, but expected a {target_type}", if *relation { "not " } else { "" })]
 	TableCheck {
@@ -597,6 +727,15 @@ pub enum Error {
 		check: String,
 	},
 
+	/// The specified field did not satisfy the field CHECK constraint
+	#[error("Found {value} for field `{field}`, with record `{thing}`, but field must satisfy the CHECK constraint: {check}")]
+	FieldConstraint {
+		thing: String,
+		value: String,
+		field: Idiom,
+		check: String,
+	},
+
 	/// The specified value did not conform to the LET type check
 	#[error("Found {value} for param ${name}, but expected a {check}")]
 	SetCheck {
@@ -780,6 +919,10 @@ pub enum Error {
 	#[error("Object Store error: {0}")]
 	ObsError(#[from] ObjectStoreError),
 
+	/// There was an error with the blob store
+	#[error("There was an error with the blob store: {0}")]
+	BlobStore(String),
+
 	/// There was an error with model computation
 	#[error("There was an error with model computation: {0}")]
 	ModelComputation(String),
@@ -908,6 +1051,19 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Changes could not be delivered to a sink
+	#[error("Failed to deliver changes to the sink '{name}': {message}")]
+	SinkFailed {
+		name: String,
+		message: String,
+	},
+
+	/// The requested sink already exists
+	#[error("The sink '{value}' already exists")]
+	SkAlreadyExists {
+		value: String,
+	},
+
 	/// The requested field already exists
 	#[error("The field '{value}' already exists")]
 	FdAlreadyExists {
@@ -944,6 +1100,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested type already exists
+	#[error("The type '{value}' already exists")]
+	TyAlreadyExists {
+		value: String,
+	},
+
 	/// The requested config already exists
 	#[error("The config for {value} already exists")]
 	CgAlreadyExists {
@@ -989,6 +1151,40 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role already exists
+	#[error("The root role '{value}' already exists")]
+	RoleRootAlreadyExists {
+		value: String,
+	},
+
+	/// The requested namespace role already exists
+	#[error("The role '{value}' already exists in the namespace '{ns}'")]
+	RoleNsAlreadyExists {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database role already exists
+	#[error("The role '{value}' already exists in the database '{db}'")]
+	RoleDbAlreadyExists {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The role name is used by one of the predefined roles
+	#[error("The role '{value}' is a predefined role and can not be redefined")]
+	RoleReserved {
+		value: String,
+	},
+
+	/// The role can not be removed while it is still granted
+	#[error("The role '{value}' can not be removed, as it is still granted by '{name}'")]
+	RoleInUse {
+		value: String,
+		name: String,
+	},
+
 	/// A database index entry for the specified table is already building
 	#[error("Database index `{index}` is currently building")]
 	IndexAlreadyBuilding {
@@ -1003,6 +1199,14 @@ pub enum Error {
 	#[error("The session has expired")]
 	ExpiredSession,
 
+	/// The token has been revoked
+	#[error("The token has been revoked")]
+	RevokedToken,
+
+	/// The session has been revoked
+	#[error("The session has been revoked")]
+	RevokedSession,
+
 	/// A node task has failed
 	#[error("A node task has failed: {0}")]
 	NodeAgent(&'static str),
@@ -1207,6 +1411,49 @@ impl From<regex::Error> for Error {
 #[cfg(any(feature = "kv-mem", feature = "kv-surrealkv"))]
 impl From<surrealkv::Error> for Error {
 	fn from(e: surrealkv::Error) -> Error {
+		match e {
+			surrealkv::Error::TransactionReadConflict => Error::TxRetryable,
+			_ => Error::Tx(e.to_string()),
+		}
+	}
+}
+
+#[cfg(feature = "kv-sled")]
+impl From<sled::Error> for Error {
+	fn from(e: sled::Error) -> Error {
+		Error::Tx(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-lmdb")]
+impl From<heed::Error> for Error {
+	fn from(e: heed::Error) -> Error {
+		Error::Tx(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-sqlite")]
+impl From<rusqlite::Error> for Error {
+	fn from(e: rusqlite::Error) -> Error {
+		Error::Tx(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-postgres")]
+impl From<tokio_postgres::Error> for Error {
+	fn from(e: tokio_postgres::Error) -> Error {
+		use tokio_postgres::error::SqlState;
+		match e.code() {
+			Some(&SqlState::T_R_SERIALIZATION_FAILURE) => Error::TxRetryable,
+			Some(&SqlState::T_R_DEADLOCK_DETECTED) => Error::TxRetryable,
+			_ => Error::Tx(e.to_string()),
+		}
+	}
+}
+
+#[cfg(feature = "kv-redis")]
+impl From<redis::RedisError> for Error {
+	fn from(e: redis::RedisError) -> Error {
 		Error::Tx(e.to_string())
 	}
 }
@@ -1214,7 +1461,11 @@ impl From<surrealkv::Error> for Error {
 #[cfg(feature = "kv-rocksdb")]
 impl From<rocksdb::Error> for Error {
 	fn from(e: rocksdb::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e.kind() {
+			rocksdb::ErrorKind::Busy => Error::TxRetryable,
+			rocksdb::ErrorKind::TryAgain => Error::TxRetryable,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
@@ -1236,22 +1487,41 @@ impl From<tikv::Error> for Error {
 			tikv::Error::DuplicateKeyInsertion => Error::TxKeyAlreadyExists,
 			tikv::Error::KeyError(ke) if ke.abort.contains("KeyTooLarge") => Error::TxKeyTooLarge,
 			tikv::Error::RegionError(re) if re.raft_entry_too_large.is_some() => Error::TxTooLarge,
+			e if is_tikv_conflict(&e) => Error::TxRetryable,
 			_ => Error::Tx(e.to_string()),
 		}
 	}
 }
 
+/// Check whether a TiKV error was caused by a conflict with another transaction
+#[cfg(feature = "kv-tikv")]
+fn is_tikv_conflict(e: &tikv::Error) -> bool {
+	match e {
+		tikv::Error::KeyError(ke) => ke.conflict.is_some() || ke.deadlock.is_some(),
+		tikv::Error::MultipleKeyErrors(errs) | tikv::Error::ExtractedErrors(errs) => {
+			errs.iter().any(is_tikv_conflict)
+		}
+		_ => false,
+	}
+}
+
 #[cfg(feature = "kv-fdb")]
 impl From<foundationdb::FdbError> for Error {
 	fn from(e: foundationdb::FdbError) -> Error {
-		Error::Ds(e.to_string())
+		match e.is_retryable() {
+			true => Error::TxRetryable,
+			false => Error::Ds(e.to_string()),
+		}
 	}
 }
 
 #[cfg(feature = "kv-fdb")]
 impl From<foundationdb::TransactionCommitError> for Error {
 	fn from(e: foundationdb::TransactionCommitError) -> Error {
-		Error::Tx(e.to_string())
+		match e.is_retryable() {
+			true => Error::TxRetryable,
+			false => Error::Tx(e.to_string()),
+		}
 	}
 }
 
@@ -1295,12 +1565,18 @@ impl Serialize for Error {
 	}
 }
 impl Error {
+	/// Check if this error is a transient transaction conflict
+	pub fn is_retryable(&self) -> bool {
+		matches!(self, Error::TxRetryable)
+	}
+
 	/// Check if this error is related to schema checks
 	pub fn is_schema_related(&self) -> bool {
 		matches!(
 			self,
 			Error::FieldCheck { .. }
 				| Error::FieldValue { .. }
+				| Error::FieldConstraint { .. }
 				| Error::FieldReadonly { .. }
 				| Error::FieldUndefined { .. }
 		)
@@ -1336,3 +1612,46 @@ impl Error {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(any(feature = "kv-mem", feature = "kv-surrealkv"))]
+	#[test]
+	fn surrealkv_conflicts_are_retryable() {
+		let e = Error::from(surrealkv::Error::TransactionReadConflict);
+		assert!(e.is_retryable());
+		let e = Error::from(surrealkv::Error::TransactionClosed);
+		assert!(!e.is_retryable());
+	}
+
+	#[cfg(feature = "kv-tikv")]
+	#[test]
+	fn tikv_conflicts_are_retryable() {
+		use tikv::proto::kvrpcpb::{KeyError, WriteConflict};
+		let conflict = || KeyError {
+			conflict: Some(WriteConflict::default()),
+			..Default::default()
+		};
+		let e = Error::from(tikv::Error::KeyError(Box::new(conflict())));
+		assert!(e.is_retryable());
+		let e = Error::from(tikv::Error::MultipleKeyErrors(vec![tikv::Error::KeyError(Box::new(
+			conflict(),
+		))]));
+		assert!(e.is_retryable());
+		let e = Error::from(tikv::Error::KeyError(Box::default()));
+		assert!(!e.is_retryable());
+	}
+
+	#[cfg(feature = "kv-fdb")]
+	#[test]
+	fn fdb_conflicts_are_retryable() {
+		// The transaction was not committed due to a conflict
+		let e = Error::from(foundationdb::FdbError::from_code(1020));
+		assert!(e.is_retryable());
+		// The operation is not valid for the transaction
+		let e = Error::from(foundationdb::FdbError::from_code(2000));
+		assert!(!e.is_retryable());
+	}
+}
@@ -441,3 +441,30 @@ impl<A: FromArg, B: FromArg, C: FromArg> FromArgs for (A, Option<B>, Option<C>)
 		Ok((a, b, c))
 	}
 }
+
+// Some functions take 1, 2, 3, or 4 arguments. It is safe to assume that, if an argument is None,
+// all of the following arguments will also be None.
+impl<A: FromArg, B: FromArg, C: FromArg, D: FromArg> FromArgs
+	for (A, Option<B>, Option<C>, Option<D>)
+{
+	fn from_args(name: &str, args: Vec<Value>) -> Result<Self, Error> {
+		let err = || Error::InvalidArguments {
+			name: name.to_owned(),
+			message: String::from("Expected 1, 2, 3, or 4 arguments."),
+		};
+		// Process the function arguments
+		let mut args = args.into_iter();
+
+		let a: A = get_arg(name, 1, &mut args, err)?;
+		let b: Option<B> = get_opt_arg(name, 2, &mut args)?;
+		let c: Option<C> = get_opt_arg(name, 3, &mut args)?;
+		let d: Option<D> = get_opt_arg(name, 4, &mut args)?;
+
+		// Process additional function arguments
+		if args.next().is_some() {
+			// Too many arguments
+			return Err(err());
+		}
+		Ok((a, b, c, d))
+	}
+}
@@ -162,6 +162,16 @@ pub fn boolean_xor((lh, rh): (Array, Array)) -> Result<Value, Error> {
 	Ok(results.into())
 }
 
+pub fn chunk((array, chunk_size): (Array, i64)) -> Result<Value, Error> {
+	if chunk_size < 1 {
+		return Err(Error::InvalidArguments {
+			name: String::from("array::chunk"),
+			message: String::from("The second argument must be an integer greater than 0"),
+		});
+	}
+	clump((array, chunk_size))
+}
+
 pub fn clump((array, clump_size): (Array, i64)) -> Result<Value, Error> {
 	let clump_size = clump_size.max(0) as usize;
 	Ok(array.clump(clump_size)?.into())
@@ -726,6 +736,15 @@ pub fn windows((array, window_size): (Array, i64)) -> Result<Value, Error> {
 	Ok(array.windows(window_size)?.into())
 }
 
+pub fn zip((array, other): (Array, Array)) -> Result<Value, Error> {
+	Ok(array
+		.into_iter()
+		.zip(other)
+		.map(|(a, b)| Value::from(vec![a, b]))
+		.collect::<Vec<_>>()
+		.into())
+}
+
 pub mod sort {
 
 	use crate::err::Error;
@@ -81,6 +81,52 @@ macro_rules! bounded_verify_password {
 	};
 }
 
+pub mod hmac {
+
+	use crate::err::Error;
+	use crate::sql::value::Value;
+	use hmac::{Hmac, Mac};
+	use sha2::Sha256;
+
+	pub fn sha256((key, msg): (String, String)) -> Result<Value, Error> {
+		// HMAC accepts keys of any length
+		let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).unwrap();
+		mac.update(msg.as_bytes());
+		let val = mac.finalize().into_bytes();
+		let val = format!("{val:x}");
+		Ok(val.into())
+	}
+}
+
+pub mod ed25519 {
+
+	use crate::err::Error;
+	use crate::sql::value::Value;
+	use ring::signature::{UnparsedPublicKey, ED25519};
+
+	/// Decodes a key or signature given either as bytes or as a hex encoded string
+	fn decode(val: Value, arg: &str) -> Result<Vec<u8>, Error> {
+		match val {
+			Value::Bytes(v) => Ok(v.into_inner()),
+			Value::Strand(v) => hex::decode(v.as_str()).map_err(|_| Error::InvalidArguments {
+				name: String::from("crypto::ed25519::verify"),
+				message: format!("The {arg} must be a valid hex encoded string."),
+			}),
+			v => Err(Error::InvalidArguments {
+				name: String::from("crypto::ed25519::verify"),
+				message: format!("The {arg} must be bytes or a hex encoded string, but found {v}."),
+			}),
+		}
+	}
+
+	pub fn verify((key, msg, sig): (Value, String, Value)) -> Result<Value, Error> {
+		let key = decode(key, "public key")?;
+		let sig = decode(sig, "signature")?;
+		let key = UnparsedPublicKey::new(&ED25519, key);
+		Ok(key.verify(msg.as_bytes(), &sig).is_ok().into())
+	}
+}
+
 pub mod argon2 {
 
 	use super::COST_ALLOWANCE;
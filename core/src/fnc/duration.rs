@@ -2,6 +2,17 @@ use crate::err::Error;
 use crate::sql::duration::Duration;
 use crate::sql::value::Value;
 
+pub fn clamp((val, min, max): (Duration, Duration, Duration)) -> Result<Value, Error> {
+	if min > max {
+		return Err(Error::InvalidArguments {
+			name: "duration::clamp".to_string(),
+			message: "The minimum duration must not be greater than the maximum duration."
+				.to_string(),
+		});
+	}
+	Ok(val.clamp(min, max).into())
+}
+
 pub fn days((val,): (Duration,)) -> Result<Value, Error> {
 	Ok(val.days().into())
 }
@@ -26,6 +37,28 @@ pub fn nanos((val,): (Duration,)) -> Result<Value, Error> {
 	Ok(val.nanos().into())
 }
 
+/// Rounds a duration to the nearest multiple of another duration, with
+/// durations exactly half way between two multiples being rounded up.
+pub fn round((val, unit): (Duration, Duration)) -> Result<Value, Error> {
+	let unit = unit.as_nanos();
+	if unit == 0 {
+		return Err(Error::InvalidArguments {
+			name: "duration::round".to_string(),
+			message: "The duration to round to must be greater than zero.".to_string(),
+		});
+	}
+	let nanos = val.as_nanos();
+	let floor = nanos - nanos % unit;
+	let nanos = match nanos % unit >= unit - unit / 2 {
+		true => floor.checked_add(unit),
+		false => Some(floor),
+	};
+	match nanos.and_then(Duration::checked_from_nanos) {
+		Some(v) => Ok(v.into()),
+		None => Err(Error::ArithmeticOverflow(format!("duration::round({val}, {unit}ns)"))),
+	}
+}
+
 pub fn secs((val,): (Duration,)) -> Result<Value, Error> {
 	Ok(val.secs().into())
 }
@@ -1,10 +1,12 @@
 use crate::err::Error;
+use crate::fnc::util;
 use crate::sql::geometry::Geometry;
 use crate::sql::value::Value;
 use geo::algorithm::bearing::HaversineBearing;
 use geo::algorithm::centroid::Centroid;
 use geo::algorithm::chamberlain_duquette_area::ChamberlainDuquetteArea;
 use geo::algorithm::haversine_distance::HaversineDistance;
+use geo::algorithm::haversine_length::HaversineLength;
 
 pub fn area((arg,): (Geometry,)) -> Result<Value, Error> {
 	match arg {
@@ -42,13 +44,43 @@ pub fn centroid((arg,): (Geometry,)) -> Result<Value, Error> {
 	Ok(centroid.map(Into::into).unwrap_or(Value::None))
 }
 
-pub fn distance((v, w): (Geometry, Geometry)) -> Result<Value, Error> {
+pub fn buffer((arg, distance): (Geometry, f64)) -> Result<Value, Error> {
+	if !distance.is_finite() || distance <= 0.0 {
+		return Err(Error::InvalidArguments {
+			name: String::from("geo::buffer"),
+			message: String::from(
+				"The second argument must be a distance in metres greater than 0.",
+			),
+		});
+	}
+	let mut polygons = util::geo::buffer(&arg.into(), distance).0;
+	Ok(match polygons.len() {
+		1 => Geometry::Polygon(polygons.remove(0)).into(),
+		_ => Geometry::from(polygons).into(),
+	})
+}
+
+/// Checks whether the first geometry contains the second one
+pub fn contains((v, w): (Geometry, Geometry)) -> Result<Value, Error> {
+	Ok(v.contains(&w).into())
+}
+
+/// Calculates the distance between two points, or the length
+/// of a path when a single line or multiline is specified.
+pub fn distance((v, w): (Geometry, Option<Geometry>)) -> Result<Value, Error> {
 	Ok(match (v, w) {
-		(Geometry::Point(v), Geometry::Point(w)) => v.haversine_distance(&w).into(),
+		(Geometry::Point(v), Some(Geometry::Point(w))) => v.haversine_distance(&w).into(),
+		(Geometry::Line(v), None) => v.haversine_length().into(),
+		(Geometry::MultiLine(v), None) => v.haversine_length().into(),
 		_ => Value::None,
 	})
 }
 
+/// Checks whether two geometries intersect
+pub fn intersects((v, w): (Geometry, Geometry)) -> Result<Value, Error> {
+	Ok(v.intersects(&w).into())
+}
+
 pub mod hash {
 
 	use crate::err::Error;
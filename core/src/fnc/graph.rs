@@ -0,0 +1,94 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::paths::{ID, OUT};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{Dir, Edges, Field, Fields, Number, Part, Table, Tables, Thing, Value, Values};
+use reblessive::tree::Stk;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Finds the shortest path between two records, following the outgoing edges of a table
+///
+/// The path is returned as the records and edges which make it up, in order,
+/// or NONE if there is no path. If a weight field is specified, then the path
+/// with the lowest total edge weight is returned instead of the fewest hops.
+pub async fn shortest_path(
+	(stk, ctx, opt, _): (&mut Stk, &Context, Option<&Options>, Option<&CursorDoc>),
+	(from, to, table, weight): (Thing, Thing, String, Option<String>),
+) -> Result<Value, Error> {
+	let Some(opt) = opt else {
+		return Ok(Value::None);
+	};
+	// The lowest known cost of each record, and the record and edge it was reached by
+	let mut best: HashMap<Thing, (Number, Option<(Thing, Thing)>)> = HashMap::new();
+	let mut queue = BinaryHeap::new();
+	best.insert(from.clone(), (Number::Int(0), None));
+	queue.push(Reverse((Number::Int(0), from)));
+	while let Some(Reverse((cost, node))) = queue.pop() {
+		// Check if the query has been cancelled or timed out
+		if ctx.is_done() {
+			return Err(match ctx.is_timedout() {
+				true => Error::QueryTimedout,
+				false => Error::QueryCancelled,
+			});
+		}
+		// Skip this record if a cheaper path has since been found
+		if best.get(&node).is_some_and(|(c, _)| *c < cost) {
+			continue;
+		}
+		// Walk back along the path once the target has been reached
+		if node == to {
+			let mut path = vec![Value::Thing(node.clone())];
+			let mut cur = node;
+			while let Some((_, Some((prev, edge)))) = best.get(&cur) {
+				path.push(Value::Thing(edge.clone()));
+				path.push(Value::Thing(prev.clone()));
+				cur = prev.clone();
+			}
+			path.reverse();
+			return Ok(path.into());
+		}
+		// Fetch the outgoing edges of this record
+		let stm = SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::from(Edges {
+				from: node.clone(),
+				dir: Dir::Out,
+				what: Tables(vec![Table(table.clone())]),
+			})]),
+			..SelectStatement::default()
+		};
+		let Value::Array(edges) = stk.run(|stk| stm.compute(stk, ctx, opt, None)).await? else {
+			continue;
+		};
+		for edge in edges {
+			let (Value::Thing(id), Value::Thing(out)) =
+				(edge.pick(ID.as_ref()), edge.pick(OUT.as_ref()))
+			else {
+				continue;
+			};
+			let step = match &weight {
+				None => Number::Int(1),
+				Some(field) => match edge.pick(&[Part::from(field.clone())]) {
+					Value::Number(v) if v >= Number::Int(0) => v,
+					_ => {
+						return Err(Error::InvalidArguments {
+							name: String::from("graph::shortest_path"),
+							message: format!(
+								"The weight of edge {id} must be a number which is not negative."
+							),
+						})
+					}
+				},
+			};
+			let next = cost + step;
+			if best.get(&out).map_or(true, |(c, _)| next < *c) {
+				best.insert(out.clone(), (next, Some((node.clone(), id))));
+				queue.push(Reverse((next, out)));
+			}
+		}
+	}
+	Ok(Value::None)
+}
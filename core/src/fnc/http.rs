@@ -1,21 +1,28 @@
 use crate::ctx::Context;
+use crate::dbs::Options;
 use crate::err::Error;
 use crate::sql::value::Value;
 
 #[cfg(not(feature = "http"))]
-pub async fn head(_: &Context, (_, _): (Value, Option<Value>)) -> Result<Value, Error> {
+pub async fn head(
+	_: &Context,
+	(_, _, _): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	Err(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
-pub async fn get(_: &Context, (_, _): (Value, Option<Value>)) -> Result<Value, Error> {
+pub async fn get(
+	_: &Context,
+	(_, _, _): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	Err(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
 pub async fn put(
 	_: &Context,
-	(_, _, _): (Value, Option<Value>, Option<Value>),
+	(_, _, _, _): (Value, Option<Value>, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
 	Err(Error::HttpDisabled)
 }
@@ -23,13 +30,21 @@ pub async fn put(
 #[cfg(not(feature = "http"))]
 pub async fn post(
 	_: &Context,
-	(_, _, _): (Value, Option<Value>, Option<Value>),
+	(_, _, _, _): (Value, Option<Value>, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
 	Err(Error::HttpDisabled)
 }
 
 #[cfg(not(feature = "http"))]
 pub async fn patch(
+	_: &Context,
+	(_, _, _, _): (Value, Option<Value>, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
+	Err(Error::HttpDisabled)
+}
+
+#[cfg(not(feature = "http"))]
+pub async fn delete(
 	_: &Context,
 	(_, _, _): (Value, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
@@ -37,10 +52,23 @@ pub async fn patch(
 }
 
 #[cfg(not(feature = "http"))]
-pub async fn delete(_: &Context, (_, _): (Value, Option<Value>)) -> Result<Value, Error> {
+pub async fn webhook(
+	_: (&Context, &Options),
+	(_, _, _): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	Err(Error::HttpDisabled)
 }
 
+#[cfg(feature = "http")]
+const POLICY_THIRD: &str = "The third argument should be an object with optional `timeout`, `retries`, `backoff` and `max_size` fields.";
+
+#[cfg(feature = "http")]
+const POLICY_FOURTH: &str = "The fourth argument should be an object with optional `timeout`, `retries`, `backoff` and `max_size` fields.";
+
+#[cfg(feature = "http")]
+const WEBHOOK_THIRD: &str =
+	"The third argument should be an object with optional `secret` and `headers` fields.";
+
 #[cfg(feature = "http")]
 fn try_as_uri(fn_name: &str, value: Value) -> Result<crate::sql::Strand, Error> {
 	match value {
@@ -71,52 +99,129 @@ fn try_as_opts(
 }
 
 #[cfg(feature = "http")]
-pub async fn head(ctx: &Context, (uri, opts): (Value, Option<Value>)) -> Result<Value, Error> {
+fn try_as_policy(
+	fn_name: &str,
+	error_message: &str,
+	value: Option<Value>,
+) -> Result<crate::fnc::util::http::Policy, Error> {
+	use crate::fnc::util::http::{Policy, MAX_RETRIES};
+	use crate::sql::Number;
+	let invalid = || Error::InvalidArguments {
+		name: fn_name.to_owned(),
+		message: error_message.to_owned(),
+	};
+	let mut policy = Policy::default();
+	match value {
+		Some(Value::Object(opts)) => {
+			for (k, v) in opts {
+				match (k.as_str(), v) {
+					("timeout", Value::Duration(v)) => policy.timeout = Some(v.0),
+					("backoff", Value::Duration(v)) => policy.backoff = v.0,
+					("retries", Value::Number(Number::Int(v)))
+						if (0..=MAX_RETRIES as i64).contains(&v) =>
+					{
+						policy.retries = v as u32
+					}
+					("max_size", Value::Number(Number::Int(v))) if v >= 0 => {
+						policy.max_size = Some(v as u64)
+					}
+					_ => return Err(invalid()),
+				}
+			}
+			Ok(policy)
+		}
+		None => Ok(policy),
+		Some(_) => Err(invalid()),
+	}
+}
+
+#[cfg(feature = "http")]
+pub async fn head(
+	ctx: &Context,
+	(uri, opts, policy): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	let uri = try_as_uri("http::head", uri)?;
 	let opts = try_as_opts("http::head", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::head(ctx, uri, opts).await
+	let policy = try_as_policy("http::head", POLICY_THIRD, policy)?;
+	crate::fnc::util::http::head(ctx, uri, opts, &policy).await
 }
 
 #[cfg(feature = "http")]
-pub async fn get(ctx: &Context, (uri, opts): (Value, Option<Value>)) -> Result<Value, Error> {
+pub async fn get(
+	ctx: &Context,
+	(uri, opts, policy): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	let uri = try_as_uri("http::get", uri)?;
 	let opts = try_as_opts("http::get", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::get(ctx, uri, opts).await
+	let policy = try_as_policy("http::get", POLICY_THIRD, policy)?;
+	crate::fnc::util::http::get(ctx, uri, opts, &policy).await
 }
 
 #[cfg(feature = "http")]
 pub async fn put(
 	ctx: &Context,
-	(uri, body, opts): (Value, Option<Value>, Option<Value>),
+	(uri, body, opts, policy): (Value, Option<Value>, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
 	let uri = try_as_uri("http::put", uri)?;
 	let opts = try_as_opts("http::put", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::put(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let policy = try_as_policy("http::put", POLICY_FOURTH, policy)?;
+	crate::fnc::util::http::put(ctx, uri, body.unwrap_or(Value::Null), opts, &policy).await
 }
 
 #[cfg(feature = "http")]
 pub async fn post(
 	ctx: &Context,
-	(uri, body, opts): (Value, Option<Value>, Option<Value>),
+	(uri, body, opts, policy): (Value, Option<Value>, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
 	let uri = try_as_uri("http::post", uri)?;
 	let opts = try_as_opts("http::post", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::post(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let policy = try_as_policy("http::post", POLICY_FOURTH, policy)?;
+	crate::fnc::util::http::post(ctx, uri, body.unwrap_or(Value::Null), opts, &policy).await
 }
 
 #[cfg(feature = "http")]
 pub async fn patch(
 	ctx: &Context,
-	(uri, body, opts): (Value, Option<Value>, Option<Value>),
+	(uri, body, opts, policy): (Value, Option<Value>, Option<Value>, Option<Value>),
 ) -> Result<Value, Error> {
 	let uri = try_as_uri("http::patch", uri)?;
 	let opts = try_as_opts("http::patch", "The third argument should be an object.", opts)?;
-	crate::fnc::util::http::patch(ctx, uri, body.unwrap_or(Value::Null), opts).await
+	let policy = try_as_policy("http::patch", POLICY_FOURTH, policy)?;
+	crate::fnc::util::http::patch(ctx, uri, body.unwrap_or(Value::Null), opts, &policy).await
 }
 
 #[cfg(feature = "http")]
-pub async fn delete(ctx: &Context, (uri, opts): (Value, Option<Value>)) -> Result<Value, Error> {
+pub async fn delete(
+	ctx: &Context,
+	(uri, opts, policy): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
 	let uri = try_as_uri("http::delete", uri)?;
 	let opts = try_as_opts("http::delete", "The second argument should be an object.", opts)?;
-	crate::fnc::util::http::delete(ctx, uri, opts).await
+	let policy = try_as_policy("http::delete", POLICY_THIRD, policy)?;
+	crate::fnc::util::http::delete(ctx, uri, opts, &policy).await
+}
+
+#[cfg(feature = "http")]
+pub async fn webhook(
+	(ctx, opt): (&Context, &Options),
+	(uri, body, opts): (Value, Option<Value>, Option<Value>),
+) -> Result<Value, Error> {
+	let uri = try_as_uri("http::webhook", uri)?;
+	let opts = try_as_opts("http::webhook", WEBHOOK_THIRD, opts)?;
+	let mut secret = None;
+	let mut headers = crate::sql::Object::default();
+	for (k, v) in opts.unwrap_or_default() {
+		match (k.as_str(), v) {
+			("secret", Value::Strand(v)) => secret = Some(v.0),
+			("headers", Value::Object(v)) => headers = v,
+			_ => {
+				return Err(Error::InvalidArguments {
+					name: "http::webhook".to_owned(),
+					message: WEBHOOK_THIRD.to_owned(),
+				})
+			}
+		}
+	}
+	crate::fnc::util::http::webhook(ctx, opt, uri, body.unwrap_or(Value::Null), headers, secret)
+		.await
 }
@@ -8,12 +8,14 @@ use crate::fnc::util::math::midhinge::Midhinge;
 use crate::fnc::util::math::mode::Mode;
 use crate::fnc::util::math::nearestrank::Nearestrank;
 use crate::fnc::util::math::percentile::Percentile;
+use crate::fnc::util::math::regression::Deviations;
 use crate::fnc::util::math::spread::Spread;
 use crate::fnc::util::math::top::Top;
 use crate::fnc::util::math::trimean::Trimean;
 use crate::fnc::util::math::variance::Variance;
 use crate::sql::number::{Number, Sort};
 use crate::sql::value::{TryPow, Value};
+use crate::sql::Array;
 
 pub fn abs((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.abs().into())
@@ -61,6 +63,14 @@ pub fn cot((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.cot().into())
 }
 
+pub fn correlation((array,): (Array,)) -> Result<Value, Error> {
+	Ok(Deviations::new("math::correlation", array.0)?.correlation().into())
+}
+
+pub fn covariance((array,): (Array,)) -> Result<Value, Error> {
+	Ok(Deviations::new("math::covariance", array.0)?.covariance().into())
+}
+
 pub fn deg2rad((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.deg2rad().into())
 }
@@ -92,6 +102,14 @@ pub fn lerpangle((from, to, factor): (Number, Number, Number)) -> Result<Value,
 	Ok(factor.lerp_angle(from, to).into())
 }
 
+pub fn linear_regression((array,): (Array,)) -> Result<Value, Error> {
+	let (slope, intercept) = Deviations::new("math::linear_regression", array.0)?.regression();
+	Ok(Value::from(map! {
+		String::from("intercept") => Value::from(intercept),
+		String::from("slope") => Value::from(slope),
+	}))
+}
+
 pub fn ln((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.ln().into())
 }
@@ -16,6 +16,7 @@ pub mod crypto;
 pub mod duration;
 pub mod encoding;
 pub mod geo;
+pub mod graph;
 pub mod http;
 pub mod math;
 pub mod not;
@@ -58,6 +59,7 @@ pub async fn run(
 		|| name.eq("array::map")
 		|| name.eq("array::reduce")
 		|| name.eq("array::some")
+		|| name.eq("graph::shortest_path")
 		|| name.eq("record::exists")
 		|| name.eq("type::field")
 		|| name.eq("type::fields")
@@ -120,6 +122,7 @@ pub fn synchronous(
 		"array::boolean_not" => array::boolean_not,
 		"array::boolean_or" => array::boolean_or,
 		"array::boolean_xor" => array::boolean_xor,
+		"array::chunk" => array::chunk,
 		"array::clump" => array::clump,
 		"array::combine" => array::combine,
 		"array::complement" => array::complement,
@@ -158,6 +161,7 @@ pub fn synchronous(
 		"array::sort::asc" => array::sort::asc,
 		"array::sort::desc" => array::sort::desc,
 		"array::windows" => array::windows,
+		"array::zip" => array::zip,
 		//
 		"bytes::len" => bytes::len,
 		//
@@ -168,13 +172,17 @@ pub fn synchronous(
 		"crypto::sha1" => crypto::sha1,
 		"crypto::sha256" => crypto::sha256,
 		"crypto::sha512" => crypto::sha512,
+		"crypto::ed25519::verify" => crypto::ed25519::verify,
+		"crypto::hmac::sha256" => crypto::hmac::sha256,
 		//
+		"duration::clamp" => duration::clamp,
 		"duration::days" => duration::days,
 		"duration::hours" => duration::hours,
 		"duration::micros" => duration::micros,
 		"duration::millis" => duration::millis,
 		"duration::mins" => duration::mins,
 		"duration::nanos" => duration::nanos,
+		"duration::round" => duration::round,
 		"duration::secs" => duration::secs,
 		"duration::weeks" => duration::weeks,
 		"duration::years" => duration::years,
@@ -192,10 +200,13 @@ pub fn synchronous(
 		//
 		"geo::area" => geo::area,
 		"geo::bearing" => geo::bearing,
+		"geo::buffer" => geo::buffer,
 		"geo::centroid" => geo::centroid,
+		"geo::contains" => geo::contains,
 		"geo::distance" => geo::distance,
 		"geo::hash::decode" => geo::hash::decode,
 		"geo::hash::encode" => geo::hash::encode,
+		"geo::intersects" => geo::intersects,
 		"geo::is::valid" => geo::is::valid,
 		//
 		"math::abs" => math::abs,
@@ -206,7 +217,9 @@ pub fn synchronous(
 		"math::bottom" => math::bottom,
 		"math::ceil" => math::ceil,
 		"math::clamp" => math::clamp,
+		"math::correlation" => math::correlation,
 		"math::cos" => math::cos,
+		"math::covariance" => math::covariance,
 		"math::cot" => math::cot,
 		"math::deg2rad" => math::deg2rad,
 		"math::fixed" => math::fixed,
@@ -214,6 +227,7 @@ pub fn synchronous(
 		"math::interquartile" => math::interquartile,
 		"math::lerp" => math::lerp,
 		"math::lerpangle" => math::lerpangle,
+		"math::linear_regression" => math::linear_regression,
 		"math::ln" => math::ln,
 		"math::log" => math::log,
 		"math::log10" => math::log10,
@@ -250,6 +264,8 @@ pub fn synchronous(
 		"object::from_entries" => object::from_entries,
 		"object::keys" => object::keys,
 		"object::len" => object::len,
+		"object::merge_patch" => object::merge_patch,
+		"object::patch" => object::patch,
 		"object::values" => object::values,
 		//
 		"parse::email::host" => parse::email::host,
@@ -295,8 +311,10 @@ pub fn synchronous(
 		"string::len" => string::len,
 		"string::lowercase" => string::lowercase,
 		"string::matches" => string::matches,
+		"string::matches_captures" => string::matches_captures,
 		"string::repeat" => string::repeat,
 		"string::replace" => string::replace,
+		"string::replace_regex" => string::replace_regex,
 		"string::reverse" => string::reverse,
 		"string::slice" => string::slice,
 		"string::slug" => string::slug,
@@ -337,7 +355,10 @@ pub fn synchronous(
 		"string::similarity::fuzzy" => string::similarity::fuzzy,
 		"string::similarity::jaro" => string::similarity::jaro,
 		"string::similarity::jaro_winkler" => string::similarity::jaro_winkler,
+		"string::similarity::levenshtein" => string::similarity::levenshtein,
+		"string::similarity::ngram" => string::similarity::ngram,
 		"string::similarity::smithwaterman" => string::similarity::smithwaterman,
+		"string::similarity::soundex" => string::similarity::soundex,
 		"string::similarity::sorensen_dice" => string::similarity::sorensen_dice,
 		//
 		"string::semver::compare" => string::semver::compare,
@@ -357,6 +378,7 @@ pub fn synchronous(
 		"time::format" => time::format,
 		"time::group" => time::group,
 		"time::hour" => time::hour,
+		"time::in_timezone" => time::in_timezone,
 		"time::max" => time::max,
 		"time::min" => time::min,
 		"time::minute" => time::minute,
@@ -435,6 +457,8 @@ pub fn synchronous(
 		"vector::scale" => vector::scale,
 		"vector::subtract" => vector::subtract,
 		"vector::distance::chebyshev" => vector::distance::chebyshev,
+		"vector::distance::cosine" => vector::distance::cosine,
+		"vector::distance::dot" => vector::distance::dot,
 		"vector::distance::euclidean" => vector::distance::euclidean,
 		"vector::distance::hamming" => vector::distance::hamming,
 		"vector::distance::knn" => vector::distance::knn((ctx, doc)),
@@ -501,18 +525,23 @@ pub async fn asynchronous(
 		"crypto::scrypt::compare" => (cpu_intensive) crypto::scrypt::cmp.await,
 		"crypto::scrypt::generate" => (cpu_intensive) crypto::scrypt::gen.await,
 		//
+		"graph::shortest_path" => graph::shortest_path((stk, ctx, Some(opt), doc)).await,
+		//
 		"http::head" => http::head(ctx).await,
 		"http::get" => http::get(ctx).await,
 		"http::put" => http::put(ctx).await,
 		"http::post" =>  http::post(ctx).await,
 		"http::patch" => http::patch(ctx).await,
 		"http::delete" => http::delete(ctx).await,
+		"http::webhook" => http::webhook((ctx, opt)).await,
 		//
 		"record::exists" => record::exists((stk, ctx, Some(opt), doc)).await,
 		//
 		"search::analyze" => search::analyze((stk, ctx, Some(opt))).await,
+		"search::facets" => search::facets((ctx, Some(opt), doc)).await,
 		"search::score" => search::score((ctx, doc)).await,
 		"search::highlight" => search::highlight((ctx, doc)).await,
+		"search::fragments" => search::fragments((ctx, doc)).await,
 		"search::offsets" => search::offsets((ctx, doc)).await,
 		//
 		"sleep" => sleep::sleep(ctx).await,
@@ -552,6 +581,7 @@ pub async fn idiom(
 				"boolean_not" => array::boolean_not,
 				"boolean_or" => array::boolean_or,
 				"boolean_xor" => array::boolean_xor,
+				"chunk" => array::chunk,
 				"clump" => array::clump,
 				"combine" => array::combine,
 				"complement" => array::complement,
@@ -599,6 +629,7 @@ pub async fn idiom(
 				"sort_asc" => array::sort::asc,
 				"sort_desc" => array::sort::desc,
 				"windows" => array::windows,
+				"zip" => array::zip,
 				//
 				"vector_add" => vector::add,
 				"vector_angle" => vector::angle,
@@ -612,6 +643,8 @@ pub async fn idiom(
 				"vector_scale" => vector::scale,
 				"vector_subtract" => vector::subtract,
 				"vector_distance_chebyshev" => vector::distance::chebyshev,
+				"vector_distance_cosine" => vector::distance::cosine,
+				"vector_distance_dot" => vector::distance::dot,
 				"vector_distance_euclidean" => vector::distance::euclidean,
 				"vector_distance_hamming" => vector::distance::hamming,
 				"vector_distance_knn" => vector::distance::knn((ctx, doc)),
@@ -639,12 +672,14 @@ pub async fn idiom(
 				args.clone(),
 				"no such method found for the duration type",
 				//
+				"clamp" => duration::clamp,
 				"days" => duration::days,
 				"hours" => duration::hours,
 				"micros" => duration::micros,
 				"millis" => duration::millis,
 				"mins" => duration::mins,
 				"nanos" => duration::nanos,
+				"round" => duration::round,
 				"secs" => duration::secs,
 				"weeks" => duration::weeks,
 				"years" => duration::years,
@@ -658,10 +693,13 @@ pub async fn idiom(
 				//
 				"area" => geo::area,
 				"bearing" => geo::bearing,
+				"buffer" => geo::buffer,
 				"centroid" => geo::centroid,
+				"contains" => geo::contains,
 				"distance" => geo::distance,
 				"hash_decode" => geo::hash::decode,
 				"hash_encode" => geo::hash::encode,
+				"intersects" => geo::intersects,
 				"is_valid" => geo::is::valid,
 			)
 		}
@@ -686,6 +724,8 @@ pub async fn idiom(
 				"entries" => object::entries,
 				"keys" => object::keys,
 				"len" => object::len,
+				"merge_patch" => object::merge_patch,
+				"patch" => object::patch,
 				"values" => object::values,
 			)
 		}
@@ -729,8 +769,10 @@ pub async fn idiom(
 				"len" => string::len,
 				"lowercase" => string::lowercase,
 				"matches" => string::matches,
+				"matches_captures" => string::matches_captures,
 				"repeat" => string::repeat,
 				"replace" => string::replace,
+				"replace_regex" => string::replace_regex,
 				"reverse" => string::reverse,
 				"slice" => string::slice,
 				"slug" => string::slug,
@@ -767,7 +809,10 @@ pub async fn idiom(
 				"similarity_fuzzy" => string::similarity::fuzzy,
 				"similarity_jaro" => string::similarity::jaro,
 				"similarity_jaro_winkler" => string::similarity::jaro_winkler,
+				"similarity_levenshtein" => string::similarity::levenshtein,
+				"similarity_ngram" => string::similarity::ngram,
 				"similarity_smithwaterman" => string::similarity::smithwaterman,
+				"similarity_soundex" => string::similarity::soundex,
 				"similarity_sorensen_dice" => string::similarity::sorensen_dice,
 				"semver_compare" => string::semver::compare,
 				"semver_major" => string::semver::major,
@@ -792,6 +837,7 @@ pub async fn idiom(
 				"floor" => time::floor,
 				"format" => time::format,
 				"group" => time::group,
+				"in_timezone" => time::in_timezone,
 				"hour" => time::hour,
 				"is_leap_year" => time::is::leap_year,
 				"micros" => time::micros,
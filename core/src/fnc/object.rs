@@ -68,3 +68,14 @@ pub fn keys((object,): (Object,)) -> Result<Value, Error> {
 pub fn values((object,): (Object,)) -> Result<Value, Error> {
 	Ok(Value::Array(Array(object.values().map(|v| v.to_owned()).collect())))
 }
+
+pub fn merge_patch((mut value, patch): (Value, Value)) -> Result<Value, Error> {
+	value.merge_patch(patch);
+	Ok(value)
+}
+
+pub fn patch((object, operations): (Object, Array)) -> Result<Value, Error> {
+	let mut value = Value::Object(object);
+	value.patch(Value::Array(operations))?;
+	Ok(value)
+}
@@ -19,6 +19,7 @@ impl_module_def!(
 	"boolean_not" => run,
 	"boolean_or" => run,
 	"boolean_xor" => run,
+	"chunk" => run,
 	"clump" => run,
 	"combine" => run,
 	"complement" => run,
@@ -66,5 +67,6 @@ impl_module_def!(
 	"swap" => run,
 	"transpose" => run,
 	"union" => run,
-	"windows" => run
+	"windows" => run,
+	"zip" => run
 );
@@ -3,6 +3,8 @@ use crate::fnc::script::modules::impl_module_def;
 
 mod argon2;
 mod bcrypt;
+mod ed25519;
+mod hmac;
 mod pbkdf2;
 mod scrypt;
 
@@ -19,6 +21,8 @@ impl_module_def!(
 	"sha512" => run,
 	"argon2" => (argon2::Package),
 	"bcrypt" => (bcrypt::Package),
+	"ed25519" => (ed25519::Package),
+	"hmac" => (hmac::Package),
 	"pbkdf2" => (pbkdf2::Package),
 	"scrypt" => (scrypt::Package)
 );
@@ -9,12 +9,14 @@ pub struct Package;
 impl_module_def!(
 	Package,
 	"duration",
+	"clamp" => run,
 	"days" => run,
 	"hours" => run,
 	"micros" => run,
 	"millis" => run,
 	"mins" => run,
 	"nanos" => run,
+	"round" => run,
 	"secs" => run,
 	"weeks" => run,
 	"years" => run,
@@ -12,8 +12,11 @@ impl_module_def!(
 	"geo",
 	"area" => run,
 	"bearing" => run,
+	"buffer" => run,
 	"centroid" => run,
+	"contains" => run,
 	"distance" => run,
 	"hash" => (hash::Package),
+	"intersects" => run,
 	"is" => (is::Package)
 );
@@ -15,7 +15,9 @@ impl_module_def!(
 	"bottom" => run,
 	"ceil" => run,
 	"clamp" => run,
+	"correlation" => run,
 	"cos" => run,
+	"covariance" => run,
 	"cot" => run,
 	"deg2rad" => run,
 	"fixed" => run,
@@ -23,6 +25,7 @@ impl_module_def!(
 	"interquartile" => run,
 	"lerp" => run,
 	"lerpangle" => run,
+	"linear_regression" => run,
 	"ln" => run,
 	"log" => run,
 	"log2" => run,
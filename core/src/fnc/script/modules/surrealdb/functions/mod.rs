@@ -14,6 +14,7 @@ mod crypto;
 mod duration;
 mod encoding;
 mod geo;
+mod graph;
 mod http;
 mod math;
 mod meta;
@@ -42,6 +43,7 @@ impl_module_def!(
 	"duration" => (duration::Package),
 	"encoding" => (encoding::Package),
 	"geo" => (geo::Package),
+	"graph" => (graph::Package),
 	"http" => (http::Package),
 	"math" => (math::Package),
 	"meta" => (meta::Package),
@@ -11,5 +11,7 @@ impl_module_def!(
 	"from_entries" => run,
 	"keys" => run,
 	"len" => run,
+	"merge_patch" => run,
+	"patch" => run,
 	"values" => run
 );
@@ -9,6 +9,8 @@ impl_module_def!(
 	Package,
 	"search",
 	"analyze" => fut Async,
+	"facets" => fut Async,
+	"fragments" => fut Async,
 	"highlight" => fut Async,
 	"offsets" => fut Async,
 	"score" => fut Async
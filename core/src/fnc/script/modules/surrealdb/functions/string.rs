@@ -23,8 +23,10 @@ impl_module_def!(
 	"len" => run,
 	"lowercase" => run,
 	"matches" => run,
+	"matches_captures" => run,
 	"repeat" => run,
 	"replace" => run,
+	"replace_regex" => run,
 	"reverse" => run,
 	"similarity" => (similarity::Package),
 	"slice" => run,
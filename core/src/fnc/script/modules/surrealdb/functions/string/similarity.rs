@@ -10,6 +10,9 @@ impl_module_def!(
 	"fuzzy" => run,
 	"jaro" => run,
 	"jaro_winkler" => run,
+	"levenshtein" => run,
+	"ngram" => run,
 	"sorensen_dice" => run,
-	"smithwaterman" => run
+	"smithwaterman" => run,
+	"soundex" => run
 );
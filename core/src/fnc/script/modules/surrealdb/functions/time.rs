@@ -16,6 +16,7 @@ impl_module_def!(
 	"format" => run,
 	"group" => run,
 	"hour" => run,
+	"in_timezone" => run,
 	"max" => run,
 	"min" => run,
 	"mins" => run,
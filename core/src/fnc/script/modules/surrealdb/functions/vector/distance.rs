@@ -8,6 +8,8 @@ impl_module_def!(
 	Package,
 	"vector::distance",
 	"chebyshev" => run,
+	"cosine" => run,
+	"dot" => run,
 	"euclidean" => run,
 	"hamming" => run,
 	"knn" => run,
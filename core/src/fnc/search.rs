@@ -5,7 +5,10 @@ use crate::err::Error;
 use crate::fnc::get_execution_context;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::highlighter::HighlightParams;
+use crate::idx::ft::FtIndex;
+use crate::sql::index::Index;
 use crate::sql::Value;
+use crate::syn;
 use reblessive::tree::Stk;
 
 pub async fn analyze(
@@ -42,6 +45,17 @@ pub async fn highlight(
 	Ok(Value::None)
 }
 
+pub async fn fragments(
+	(ctx, doc): (&Context, Option<&CursorDoc>),
+	args: (Value, Value, Value, Option<Value>),
+) -> Result<Value, Error> {
+	if let Some((exe, doc, thg)) = get_execution_context(ctx, doc) {
+		let hlp = HighlightParams::fragments(args)?;
+		return exe.highlight(ctx, thg, hlp, doc.doc.as_ref()).await;
+	}
+	Ok(Value::None)
+}
+
 pub async fn offsets(
 	(ctx, doc): (&Context, Option<&CursorDoc>),
 	(match_ref, partial): (Value, Option<Value>),
@@ -52,3 +66,29 @@ pub async fn offsets(
 	}
 	Ok(Value::None)
 }
+
+pub async fn facets(
+	(ctx, opt, doc): (&Context, Option<&Options>, Option<&CursorDoc>),
+	(field,): (Value,),
+) -> Result<Value, Error> {
+	if let (Some(opt), Some(doc), Value::Strand(field)) = (opt, doc, field) {
+		if let Some(thg) = &doc.rid {
+			let field = syn::idiom(&field)?;
+			let ixs = ctx.tx().all_tb_indexes(opt.ns()?, opt.db()?, &thg.tb).await?;
+			// Find the full-text index on the field
+			for ix in ixs.iter() {
+				if let Index::Search(p) = &ix.index {
+					if ix.cols.first() == Some(&field) {
+						let terms = FtIndex::document_terms(ctx, opt, ix, p, thg).await?;
+						return Ok(terms.into());
+					}
+				}
+			}
+			return Err(Error::InvalidArguments {
+				name: String::from("search::facets"),
+				message: format!("There is no full-text index on the field '{field}'."),
+			});
+		}
+	}
+	Ok(Value::None)
+}
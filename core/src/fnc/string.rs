@@ -3,6 +3,7 @@ use crate::err::Error;
 use crate::fnc::util::string;
 use crate::sql::value::Value;
 use crate::sql::Regex;
+use std::collections::BTreeMap;
 
 /// Returns `true` if a string of this length is too much to allocate.
 fn limit(name: &str, n: usize) -> Result<(), Error> {
@@ -69,6 +70,35 @@ pub fn matches((val, regex): (String, Regex)) -> Result<Value, Error> {
 	Ok(regex.0.is_match(&val).into())
 }
 
+/// Extract the capture groups of the first match of a regex. When the regex
+/// has named capture groups, an object of the named groups is returned,
+/// otherwise an array of all the groups, starting with the whole match.
+pub fn matches_captures((val, regex): (String, Regex)) -> Result<Value, Error> {
+	let Some(caps) = regex.0.captures(&val) else {
+		return Ok(Value::None);
+	};
+	let group = |m: Option<regex::Match>| m.map_or(Value::None, |m| m.as_str().into());
+	if regex.0.capture_names().any(|n| n.is_some()) {
+		let obj: BTreeMap<String, Value> = regex
+			.0
+			.capture_names()
+			.flatten()
+			.map(|n| (n.to_owned(), group(caps.name(n))))
+			.collect();
+		Ok(obj.into())
+	} else {
+		Ok(caps.iter().map(group).collect::<Vec<_>>().into())
+	}
+}
+
+/// Replace every match of a regex, expanding any `$1` or `${name}`
+/// capture group references in the replacement string.
+pub fn replace_regex((val, regex, replace): (String, Regex, String)) -> Result<Value, Error> {
+	let res = regex.0.replace_all(&val, replace.as_str());
+	limit("string::replace_regex", res.len())?;
+	Ok(res.into_owned().into())
+}
+
 pub fn replace((val, search, replace): (String, Value, String)) -> Result<Value, Error> {
 	match search {
 		Value::Strand(search) => {
@@ -330,6 +360,7 @@ pub mod similarity {
 
 	use crate::err::Error;
 	use crate::fnc::util::string::fuzzy::Fuzzy;
+	use crate::fnc::util::string::{ngram, soundex};
 	use crate::sql::Value;
 
 	use strsim;
@@ -350,10 +381,44 @@ pub mod similarity {
 		Ok(strsim::jaro_winkler(&a, &b).into())
 	}
 
+	/// Calculate the Levenshtein similarity between two strings
+	/// via [`strsim::normalized_levenshtein`].
+	pub fn levenshtein((a, b): (String, String)) -> Result<Value, Error> {
+		Ok(strsim::normalized_levenshtein(&a, &b).into())
+	}
+
+	/// Calculate the Jaccard similarity of the character n-grams of two strings,
+	/// using trigrams unless a different n-gram length is specified.
+	pub fn ngram((a, b, n): (String, String, Option<i64>)) -> Result<Value, Error> {
+		let n = match n {
+			None => 3,
+			Some(n) if n > 0 => n as usize,
+			Some(_) => {
+				return Err(Error::InvalidArguments {
+					name: "string::similarity::ngram".into(),
+					message: "The n-gram length must be a positive integer.".into(),
+				})
+			}
+		};
+		Ok(ngram::similarity(&a, &b, n).into())
+	}
+
 	pub fn smithwaterman((a, b): (String, String)) -> Result<Value, Error> {
 		Ok(a.as_str().fuzzy_score(b.as_str()).into())
 	}
 
+	/// Calculate the similarity between the Soundex codes of two strings,
+	/// as the proportion of the code characters which are the same.
+	pub fn soundex((a, b): (String, String)) -> Result<Value, Error> {
+		let a = soundex::soundex(&a);
+		let b = soundex::soundex(&b);
+		if a.is_empty() || b.is_empty() {
+			return Ok(0.0.into());
+		}
+		let same = a.chars().zip(b.chars()).filter(|(a, b)| a == b).count();
+		Ok((same as f64 / a.len() as f64).into())
+	}
+
 	/// Calculate the Sørensen-Dice similarity between two strings
 	/// via [`strsim::sorensen_dice`].
 	pub fn sorensen_dice((a, b): (String, String)) -> Result<Value, Error> {
@@ -476,8 +541,9 @@ pub mod semver {
 
 #[cfg(test)]
 mod tests {
-	use super::{contains, matches, replace, slice};
+	use super::{contains, matches, matches_captures, replace, replace_regex, slice};
 	use crate::sql::Value;
+	use crate::syn::Parse;
 
 	#[test]
 	fn string_slice() {
@@ -554,6 +620,55 @@ mod tests {
 		test("foo bar", "bar", true);
 	}
 
+	#[test]
+	fn string_matches_captures() {
+		fn test(base: &str, regex: &str, expected: &str) {
+			assert_eq!(
+				matches_captures((base.to_string(), regex.parse().unwrap())).unwrap(),
+				Value::parse(expected),
+				"matches_captures({},{})",
+				base,
+				regex
+			);
+		}
+
+		test("bar", "foo", "NONE");
+		test(
+			"GET /index.html 200",
+			r"(\w+) (\S+)( \d+)?",
+			"['GET /index.html 200', 'GET', '/index.html', ' 200']",
+		);
+		test(
+			"GET /index.html",
+			r"(\w+) (\S+)( \d+)?",
+			"['GET /index.html', 'GET', '/index.html', NONE]",
+		);
+		test(
+			"2024-03-01",
+			r"(?<year>\d{4})-(?<month>\d{2})(-(?<day>\d{2}))?",
+			"{ year: '2024', month: '03', day: '01' }",
+		);
+	}
+
+	#[test]
+	fn string_replace_regex() {
+		fn test(base: &str, regex: &str, replacement: &str, expected: &str) {
+			assert_eq!(
+				replace_regex((base.to_string(), regex.parse().unwrap(), replacement.to_string()))
+					.unwrap(),
+				Value::from(expected),
+				"replace_regex({},{},{})",
+				base,
+				regex,
+				replacement
+			);
+		}
+
+		test("foo bar", "o+", "0", "f0 bar");
+		test("2024-03-01", r"(\d+)-(\d+)-(\d+)", "$3/$2/$1", "01/03/2024");
+		test("john smith", r"(?<first>\w+) (?<last>\w+)", "${last}, ${first}", "smith, john");
+	}
+
 	#[test]
 	fn is_alphanum() {
 		let value = super::is::alphanum((String::from("abc123"),)).unwrap();
@@ -3,7 +3,8 @@ use crate::sql::datetime::Datetime;
 use crate::sql::duration::Duration;
 use crate::sql::value::Value;
 use chrono::offset::TimeZone;
-use chrono::{DateTime, Datelike, DurationRound, Local, Timelike, Utc};
+use chrono::{DateTime, Datelike, DurationRound, Local, Locale, Timelike, Utc};
+use chrono_tz::Tz;
 
 pub fn ceil((val, duration): (Datetime, Duration)) -> Result<Value, Error> {
 	match chrono::Duration::from_std(*duration) {
@@ -68,8 +69,44 @@ pub fn floor((val, duration): (Datetime, Duration)) -> Result<Value, Error> {
 	}
 }
 
-pub fn format((val, format): (Datetime, String)) -> Result<Value, Error> {
-	Ok(val.format(&format).to_string().into())
+pub fn format(
+	(val, format, tz, locale): (Datetime, String, Option<String>, Option<String>),
+) -> Result<Value, Error> {
+	// Convert the datetime to the requested timezone
+	let val = match tz {
+		Some(tz) => val.with_timezone(&parse_timezone("time::format", &tz)?),
+		None => val.with_timezone(&Tz::UTC),
+	};
+	// Format the datetime with the requested locale
+	let res = match locale {
+		Some(locale) => {
+			let locale = Locale::try_from(locale.replace('-', "_").as_str()).map_err(|_| {
+				Error::InvalidArguments {
+					name: "time::format".to_string(),
+					message: format!("The locale '{locale}' is not supported."),
+				}
+			})?;
+			val.format_localized(&format, locale).to_string()
+		}
+		None => val.format(&format).to_string(),
+	};
+	Ok(res.into())
+}
+
+/// Converts a datetime to the wall-clock time of an IANA timezone. As
+/// datetimes are always stored in UTC, the result is the UTC datetime
+/// which has the same date and time as the datetime in the timezone.
+pub fn in_timezone((val, tz): (Datetime, String)) -> Result<Value, Error> {
+	let tz = parse_timezone("time::in_timezone", &tz)?;
+	Ok(Datetime::from(val.with_timezone(&tz).naive_local().and_utc()).into())
+}
+
+/// Parses an IANA timezone name from the bundled timezone database
+fn parse_timezone(name: &str, tz: &str) -> Result<Tz, Error> {
+	tz.parse().map_err(|_| Error::InvalidArguments {
+		name: name.to_string(),
+		message: format!("The timezone '{tz}' is not a valid IANA timezone."),
+	})
 }
 
 pub fn group((val, group): (Datetime, String)) -> Result<Value, Error> {
@@ -1,6 +1,10 @@
 use crate::sql::geometry::Geometry;
 use crate::sql::strand::Strand;
-use geo::Point;
+use geo::algorithm::haversine_destination::HaversineDestination;
+use geo::{BooleanOps, ConvexHull, LinesIter, MultiPoint, MultiPolygon, Point, Polygon};
+
+/// The number of vertices used to approximate a circle in a buffer
+const CIRCLE_VERTICES: usize = 32;
 
 static BASE32: &[char] = &[
 	'0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'j', 'k',
@@ -99,3 +103,67 @@ pub fn decode(v: Strand) -> Geometry {
 
 	(x, y).into()
 }
+
+/// Calculates the area within a distance in metres of a geometry, by
+/// joining circles around its points with the hulls of its segments.
+pub fn buffer(g: &geo::Geometry<f64>, distance: f64) -> MultiPolygon<f64> {
+	let mut parts = vec![];
+	buffer_parts(g, distance, &mut parts);
+	parts
+		.into_iter()
+		.fold(MultiPolygon::new(vec![]), |acc, p| acc.union(&MultiPolygon::new(vec![p])))
+}
+
+fn buffer_parts(g: &geo::Geometry<f64>, distance: f64, parts: &mut Vec<Polygon<f64>>) {
+	let circle = |p: Point<f64>| -> Vec<Point<f64>> {
+		(0..CIRCLE_VERTICES)
+			.map(|i| p.haversine_destination(360.0 * i as f64 / CIRCLE_VERTICES as f64, distance))
+			.collect()
+	};
+	match g {
+		geo::Geometry::Point(p) => {
+			parts.push(MultiPoint::new(circle(*p)).convex_hull());
+		}
+		geo::Geometry::MultiPoint(m) => {
+			for p in m.iter() {
+				parts.push(MultiPoint::new(circle(*p)).convex_hull());
+			}
+		}
+		geo::Geometry::GeometryCollection(c) => {
+			for g in c.iter() {
+				buffer_parts(g, distance, parts);
+			}
+		}
+		g => {
+			// Areas are contained within their own buffer
+			let lines: Vec<geo::Line<f64>> = match g {
+				geo::Geometry::Line(l) => l.lines_iter().collect(),
+				geo::Geometry::LineString(l) => l.lines_iter().collect(),
+				geo::Geometry::MultiLineString(m) => m.lines_iter().collect(),
+				geo::Geometry::Polygon(p) => {
+					parts.push(p.clone());
+					p.lines_iter().collect()
+				}
+				geo::Geometry::MultiPolygon(m) => {
+					parts.extend(m.iter().cloned());
+					m.lines_iter().collect()
+				}
+				geo::Geometry::Rect(r) => {
+					parts.push(r.to_polygon());
+					r.lines_iter().collect()
+				}
+				geo::Geometry::Triangle(t) => {
+					parts.push(t.to_polygon());
+					t.lines_iter().collect()
+				}
+				_ => Vec::new(),
+			};
+			// Each segment is buffered by the hull of the circles around its ends
+			for l in lines {
+				let mut points = circle(l.start.into());
+				points.extend(circle(l.end.into()));
+				parts.push(MultiPoint::new(points).convex_hull());
+			}
+		}
+	}
+}
@@ -1,12 +1,45 @@
+use crate::cnf::WEBHOOK_DELIVERY_TABLE;
+use crate::ctx::reason::Reason;
 use crate::ctx::Context;
+use crate::dbs::Options;
 use crate::err::Error;
-use crate::sql::{Bytes, Object, Strand, Value};
+use crate::kvs::QueuedWebhook;
+use crate::sql::{Bytes, Datetime, Id, Object, Strand, Thing, Value};
 use crate::syn;
 
+use futures::StreamExt;
 use reqwest::header::CONTENT_TYPE;
-use reqwest::{Client, RequestBuilder, Response};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::time::Duration;
 use url::Url;
 
+/// The maximum number of times a request can be retried
+pub(crate) const MAX_RETRIES: u32 = 10;
+
+/// Options which control how an outgoing request is sent
+#[derive(Debug)]
+pub struct Policy {
+	/// The maximum duration of each attempt, limited by the query timeout
+	pub timeout: Option<Duration>,
+	/// The number of times a failed request is retried
+	pub retries: u32,
+	/// The delay before the first retry, doubled after each attempt
+	pub backoff: Duration,
+	/// The maximum size of the response body in bytes
+	pub max_size: Option<u64>,
+}
+
+impl Default for Policy {
+	fn default() -> Self {
+		Self {
+			timeout: None,
+			retries: 0,
+			backoff: Duration::from_millis(100),
+			max_size: None,
+		}
+	}
+}
+
 pub(crate) fn uri_is_valid(uri: &str) -> bool {
 	reqwest::Url::parse(uri).is_ok()
 }
@@ -19,22 +52,22 @@ fn encode_body(req: RequestBuilder, body: Value) -> RequestBuilder {
 	}
 }
 
-async fn decode_response(res: Response) -> Result<Value, Error> {
+async fn decode_response(res: Response, policy: &Policy) -> Result<Value, Error> {
 	match res.status() {
 		s if s.is_success() => match res.headers().get(CONTENT_TYPE) {
 			Some(mime) => match mime.to_str() {
 				Ok(v) if v.starts_with("application/json") => {
-					let txt = res.text().await?;
-					let val = syn::json(&txt)?;
+					let body = read_body(res, policy.max_size).await?;
+					let val = syn::json(&String::from_utf8_lossy(&body))?;
 					Ok(val)
 				}
 				Ok(v) if v.starts_with("application/octet-stream") => {
-					let bytes = res.bytes().await?;
-					Ok(Value::Bytes(Bytes(bytes.into())))
+					let body = read_body(res, policy.max_size).await?;
+					Ok(Value::Bytes(Bytes(body)))
 				}
 				Ok(v) if v.starts_with("text") => {
-					let txt = res.text().await?;
-					let val = txt.into();
+					let body = read_body(res, policy.max_size).await?;
+					let val = String::from_utf8_lossy(&body).into_owned().into();
 					Ok(val)
 				}
 				_ => Ok(Value::None),
@@ -45,7 +78,92 @@ async fn decode_response(res: Response) -> Result<Value, Error> {
 	}
 }
 
-pub async fn head(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value, Error> {
+async fn read_body(res: Response, max_size: Option<u64>) -> Result<Vec<u8>, Error> {
+	let too_large = |max: u64| {
+		Error::Http(format!("The response body exceeds the maximum size of {max} bytes"))
+	};
+	match max_size {
+		None => Ok(res.bytes().await?.into()),
+		Some(max) => {
+			// Reject responses which announce a larger body up front
+			if res.content_length().is_some_and(|len| len > max) {
+				return Err(too_large(max));
+			}
+			// Stop reading as soon as the limit is exceeded
+			let mut body = Vec::new();
+			let mut stream = res.bytes_stream();
+			while let Some(chunk) = stream.next().await {
+				let chunk = chunk?;
+				if (body.len() + chunk.len()) as u64 > max {
+					return Err(too_large(max));
+				}
+				body.extend_from_slice(&chunk);
+			}
+			Ok(body)
+		}
+	}
+}
+
+async fn send(ctx: &Context, mut req: RequestBuilder, policy: &Policy) -> Result<Response, Error> {
+	let mut backoff = policy.backoff;
+	let mut attempt = 0;
+	loop {
+		// Requests can never outlive the query timeout
+		let timeout = match (ctx.timeout(), policy.timeout) {
+			(Some(t), Some(d)) => Some(t.min(d)),
+			(t, d) => t.or(d),
+		};
+		// Keep hold of the original request while it can still be retried
+		let (this, retained) = match req.try_clone() {
+			Some(this) if attempt < policy.retries => (this, Some(req)),
+			_ => (req, None),
+		};
+		let this = match timeout {
+			#[cfg(not(target_arch = "wasm32"))]
+			Some(d) => this.timeout(d),
+			_ => this,
+		};
+		let res = this.send().await;
+		// Return the response unless the failure is transient
+		let retry = match &res {
+			Ok(res) => {
+				res.status().is_server_error() || res.status() == StatusCode::TOO_MANY_REQUESTS
+			}
+			#[cfg(not(target_arch = "wasm32"))]
+			Err(e) => e.is_timeout() || e.is_connect(),
+			#[cfg(target_arch = "wasm32")]
+			Err(e) => e.is_timeout(),
+		};
+		req = match retained {
+			Some(req) if retry => req,
+			_ => return Ok(res?),
+		};
+		// Wait before trying again
+		let delay = match ctx.timeout() {
+			Some(t) if t < backoff => t,
+			_ => backoff,
+		};
+		#[cfg(target_arch = "wasm32")]
+		wasmtimer::tokio::sleep(delay).await;
+		#[cfg(not(target_arch = "wasm32"))]
+		tokio::time::sleep(delay).await;
+		// Stop retrying if the query has finished
+		match ctx.done() {
+			Some(Reason::Timedout) => return Err(Error::QueryTimedout),
+			Some(Reason::Canceled) => return Err(Error::QueryCancelled),
+			None => (),
+		}
+		backoff = backoff.saturating_mul(2);
+		attempt += 1;
+	}
+}
+
+pub async fn head(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	policy: &Policy,
+) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
 	ctx.check_allowed_net(&url)?;
@@ -62,11 +180,7 @@ pub async fn head(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result
 		req = req.header(k.as_str(), v.to_raw_string());
 	}
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Check the response status
 	match res.status() {
 		s if s.is_success() => Ok(Value::None),
@@ -74,7 +188,12 @@ pub async fn head(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result
 	}
 }
 
-pub async fn get(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value, Error> {
+pub async fn get(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	policy: &Policy,
+) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
 	ctx.check_allowed_net(&url)?;
@@ -91,13 +210,9 @@ pub async fn get(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<
 		req = req.header(k.as_str(), v.to_raw_string());
 	}
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Receive the response as a value
-	decode_response(res).await
+	decode_response(res, policy).await
 }
 
 pub async fn put(
@@ -105,6 +220,7 @@ pub async fn put(
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	policy: &Policy,
 ) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
@@ -124,13 +240,9 @@ pub async fn put(
 	// Submit the request body
 	req = encode_body(req, body);
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Receive the response as a value
-	decode_response(res).await
+	decode_response(res, policy).await
 }
 
 pub async fn post(
@@ -138,6 +250,7 @@ pub async fn post(
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	policy: &Policy,
 ) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
@@ -157,13 +270,9 @@ pub async fn post(
 	// Submit the request body
 	req = encode_body(req, body);
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Receive the response as a value
-	decode_response(res).await
+	decode_response(res, policy).await
 }
 
 pub async fn patch(
@@ -171,6 +280,7 @@ pub async fn patch(
 	uri: Strand,
 	body: Value,
 	opts: impl Into<Object>,
+	policy: &Policy,
 ) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
@@ -190,16 +300,17 @@ pub async fn patch(
 	// Submit the request body
 	req = encode_body(req, body);
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Receive the response as a value
-	decode_response(res).await
+	decode_response(res, policy).await
 }
 
-pub async fn delete(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Result<Value, Error> {
+pub async fn delete(
+	ctx: &Context,
+	uri: Strand,
+	opts: impl Into<Object>,
+	policy: &Policy,
+) -> Result<Value, Error> {
 	// Check if the URI is valid and allowed
 	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
 	ctx.check_allowed_net(&url)?;
@@ -216,11 +327,37 @@ pub async fn delete(ctx: &Context, uri: Strand, opts: impl Into<Object>) -> Resu
 		req = req.header(k.as_str(), v.to_raw_string());
 	}
 	// Send the request and wait
-	let res = match ctx.timeout() {
-		#[cfg(not(target_arch = "wasm32"))]
-		Some(d) => req.timeout(d).send().await?,
-		_ => req.send().await?,
-	};
+	let res = send(ctx, req, policy).await?;
 	// Receive the response as a value
-	decode_response(res).await
+	decode_response(res, policy).await
+}
+
+pub async fn webhook(
+	ctx: &Context,
+	opt: &Options,
+	uri: Strand,
+	body: Value,
+	headers: Object,
+	secret: Option<String>,
+) -> Result<Value, Error> {
+	// Check if the URI is valid and allowed
+	let url = Url::parse(&uri).map_err(|_| Error::InvalidUrl(uri.to_string()))?;
+	ctx.check_allowed_net(&url)?;
+	// Queue the webhook, so that it is only delivered if the transaction commits
+	let now = Datetime::default();
+	let entry = QueuedWebhook {
+		ns: opt.ns()?.to_owned(),
+		db: opt.db()?.to_owned(),
+		url: url.to_string(),
+		body,
+		headers,
+		secret,
+		queued: now.clone(),
+		attempts: 0,
+		retry: now,
+		error: None,
+	};
+	let id = entry.enqueue(&ctx.tx()).await?;
+	// Return the record in which the delivery status is recorded
+	Ok(Value::Thing(Thing::from((WEBHOOK_DELIVERY_TABLE.as_str(), Id::Uuid(id.into())))))
 }
@@ -11,7 +11,9 @@ pub mod midhinge;
 pub mod mode;
 pub mod nearestrank;
 pub mod percentile;
+pub mod regression;
 pub mod spread;
+pub mod tdigest;
 pub mod top;
 pub mod trimean;
 pub mod variance;
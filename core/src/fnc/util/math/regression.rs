@@ -0,0 +1,103 @@
+use crate::err::Error;
+use crate::sql::value::Value;
+
+/// The sums of squared deviations of a set of paired observations
+pub struct Deviations {
+	/// The number of observations
+	pub n: usize,
+	/// The mean of the x values
+	pub mean_x: f64,
+	/// The mean of the y values
+	pub mean_y: f64,
+	/// The sum of squared deviations of the x values
+	pub sxx: f64,
+	/// The sum of squared deviations of the y values
+	pub syy: f64,
+	/// The sum of the products of the deviations of the x and y values
+	pub sxy: f64,
+}
+
+impl Deviations {
+	/// Parses an array of `[x, y]` observations
+	pub fn new(name: &str, points: Vec<Value>) -> Result<Self, Error> {
+		let mut pairs = Vec::with_capacity(points.len());
+		for point in points {
+			match point {
+				Value::Array(a) if a.len() == 2 => match (&a[0], &a[1]) {
+					(Value::Number(x), Value::Number(y)) => {
+						pairs.push((x.to_float(), y.to_float()))
+					}
+					_ => return Err(Self::invalid(name)),
+				},
+				// Observations with missing values are skipped
+				Value::None | Value::Null => continue,
+				_ => return Err(Self::invalid(name)),
+			}
+		}
+		let n = pairs.len();
+		let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n as f64;
+		let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n as f64;
+		let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+		for (x, y) in pairs {
+			sxx += (x - mean_x).powi(2);
+			syy += (y - mean_y).powi(2);
+			sxy += (x - mean_x) * (y - mean_y);
+		}
+		Ok(Self {
+			n,
+			mean_x,
+			mean_y,
+			sxx,
+			syy,
+			sxy,
+		})
+	}
+
+	fn invalid(name: &str) -> Error {
+		Error::InvalidArguments {
+			name: name.to_owned(),
+			message: String::from("The argument must be an array of [x, y] number pairs."),
+		}
+	}
+
+	/// The sample covariance of the observations
+	pub fn covariance(&self) -> f64 {
+		match self.n {
+			0 | 1 => f64::NAN,
+			n => self.sxy / (n - 1) as f64,
+		}
+	}
+
+	/// The Pearson correlation coefficient of the observations
+	pub fn correlation(&self) -> f64 {
+		self.sxy / (self.sxx * self.syy).sqrt()
+	}
+
+	/// The slope and intercept of the least squares regression line
+	pub fn regression(&self) -> (f64, f64) {
+		let slope = self.sxy / self.sxx;
+		(slope, self.mean_y - slope * self.mean_x)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::Parse;
+
+	#[test]
+	fn deviations() {
+		let Value::Array(points) = Value::parse("[[1, 2], [2, 4], [3, 6], NONE, [4, 8]]") else {
+			unreachable!()
+		};
+		let d = Deviations::new("test", points.0).unwrap();
+		assert_eq!(d.n, 4);
+		assert_eq!(d.covariance(), 10.0 / 3.0);
+		assert_eq!(d.correlation(), 1.0);
+		assert_eq!(d.regression(), (2.0, 0.0));
+		let Value::Array(points) = Value::parse("[[1, 2, 3]]") else {
+			unreachable!()
+		};
+		assert!(Deviations::new("test", points.0).is_err());
+	}
+}
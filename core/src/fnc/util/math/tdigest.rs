@@ -0,0 +1,150 @@
+/// A merging t-digest, which estimates percentiles of a stream of values
+///
+/// Values are buffered, and are only merged into a bounded set of centroids
+/// once the buffer is full, so small sets of values give exact results.
+pub struct TDigest {
+	/// The compression factor, limiting the number of centroids
+	compression: f64,
+	/// The merged centroids, as a mean and a weight, sorted by mean
+	centroids: Vec<(f64, f64)>,
+	/// The values which have not yet been merged into the centroids
+	buffer: Vec<f64>,
+	/// The total number of values in the digest
+	count: f64,
+	/// The smallest value in the digest
+	min: f64,
+	/// The largest value in the digest
+	max: f64,
+}
+
+impl TDigest {
+	pub fn new(compression: usize) -> Self {
+		Self {
+			compression: compression.max(1) as f64,
+			centroids: Vec::new(),
+			buffer: Vec::new(),
+			count: 0.0,
+			min: f64::INFINITY,
+			max: f64::NEG_INFINITY,
+		}
+	}
+
+	/// Check if any values have been added to the digest
+	pub fn is_empty(&self) -> bool {
+		self.count == 0.0
+	}
+
+	/// Add a value to the digest
+	pub fn push(&mut self, val: f64) {
+		if val.is_nan() {
+			return;
+		}
+		self.buffer.push(val);
+		self.count += 1.0;
+		self.min = self.min.min(val);
+		self.max = self.max.max(val);
+		if self.buffer.len() >= self.compression as usize * 5 {
+			self.compress();
+		}
+	}
+
+	/// Gets the N percentile, averaging neighboring values if non-exact
+	pub fn percentile(&mut self, perc: f64) -> f64 {
+		// If an empty set, then return NaN
+		if self.is_empty() {
+			return f64::NAN;
+		}
+		// If an invalid percentile, then return NaN
+		if !(0.0..=100.0).contains(&perc) {
+			return f64::NAN;
+		}
+		// If nothing has been merged, then the result is exact
+		if self.centroids.is_empty() {
+			self.buffer.sort_by(f64::total_cmp);
+			let fract_index = (self.buffer.len() - 1) as f64 * perc / 100.0;
+			let floor = self.buffer[fract_index.floor() as usize];
+			let ceil = self.buffer[fract_index.ceil() as usize];
+			return floor + (ceil - floor) * fract_index.fract();
+		}
+		self.compress();
+		// Find the centroids either side of the target rank
+		let rank = perc / 100.0 * self.count;
+		let mut cumulative = 0.0;
+		let mut prev: Option<(f64, f64)> = None;
+		for &(mean, weight) in self.centroids.iter() {
+			let centre = cumulative + weight / 2.0;
+			if rank < centre {
+				let (m, c) = prev.unwrap_or((self.min, 0.0));
+				return m + (mean - m) * (rank - c) / (centre - c);
+			}
+			prev = Some((mean, centre));
+			cumulative += weight;
+		}
+		// The target rank is beyond the last centroid
+		match prev {
+			Some((m, c)) if self.count > c => m + (self.max - m) * (rank - c) / (self.count - c),
+			_ => self.max,
+		}
+	}
+
+	/// Merge the buffered values into the centroids
+	fn compress(&mut self) {
+		if self.buffer.is_empty() {
+			return;
+		}
+		let mut all = std::mem::take(&mut self.centroids);
+		all.extend(self.buffer.drain(..).map(|v| (v, 1.0)));
+		all.sort_by(|a, b| a.0.total_cmp(&b.0));
+		let mut out = Vec::with_capacity(self.compression as usize);
+		let mut iter = all.into_iter();
+		let Some(mut cur) = iter.next() else {
+			return;
+		};
+		let mut before = 0.0;
+		for (mean, weight) in iter {
+			// Centroids near the tails are kept small, for accurate extreme percentiles
+			let q = (before + (cur.1 + weight) / 2.0) / self.count;
+			let limit = (4.0 * self.count * q * (1.0 - q) / self.compression).max(1.0);
+			if cur.1 + weight <= limit {
+				cur.0 += (mean - cur.0) * weight / (cur.1 + weight);
+				cur.1 += weight;
+			} else {
+				before += cur.1;
+				out.push(cur);
+				cur = (mean, weight);
+			}
+		}
+		out.push(cur);
+		self.centroids = out;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_tdigest_exact() {
+		let mut d = TDigest::new(100);
+		assert!(d.percentile(50.0).is_nan());
+		for v in [4.0, 1.0, 3.0, 2.0] {
+			d.push(v);
+		}
+		assert_eq!(d.percentile(50.0), 2.5);
+		assert_eq!(d.percentile(0.0), 1.0);
+		assert_eq!(d.percentile(100.0), 4.0);
+		assert!(d.percentile(101.0).is_nan());
+	}
+
+	#[test]
+	fn test_tdigest_approximate() {
+		let mut d = TDigest::new(100);
+		for v in 0..100_000 {
+			d.push(((v * 7919) % 100_000) as f64);
+		}
+		assert!(d.centroids.len() < 1000);
+		assert!((d.percentile(50.0) - 50_000.0).abs() < 500.0);
+		assert!((d.percentile(99.0) - 99_000.0).abs() < 100.0);
+		assert!((d.percentile(1.0) - 1_000.0).abs() < 100.0);
+	}
+}
@@ -259,6 +259,18 @@ impl DotProduct for Vec<Number> {
 	}
 }
 
+pub trait DotDistance {
+	/// Negated dot product of two vectors, so that closer vectors have a smaller distance
+	fn dot_distance(&self, other: &Self) -> Result<Number, Error>;
+}
+
+impl DotDistance for Vec<Number> {
+	fn dot_distance(&self, other: &Self) -> Result<Number, Error> {
+		check_same_dimension("vector::distance::dot", self, other)?;
+		Ok(-dot(self, other))
+	}
+}
+
 fn dot(a: &[Number], b: &[Number]) -> Number {
 	a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
 }
@@ -0,0 +1,62 @@
+use icu_collator::{Collator, CollatorOptions, Numeric};
+use icu_locid::Locale;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+	// Collators are expensive to create, so they are cached per locale
+	#[allow(clippy::type_complexity)]
+	static COLLATORS: RefCell<HashMap<(String, bool), Option<Rc<Collator>>>> = RefCell::new(HashMap::new());
+}
+
+/// Create a collator for the specified locale
+fn collator(locale: &str, numeric: bool) -> Option<Collator> {
+	let locale: Locale = locale.parse().ok()?;
+	let mut options = CollatorOptions::new();
+	if numeric {
+		options.numeric = Some(Numeric::On);
+	}
+	Collator::try_new(&(&locale).into(), options).ok()
+}
+
+/// Check if a locale can be used for collation
+pub fn valid(locale: &str) -> bool {
+	collator(locale, false).is_some()
+}
+
+/// Compare two strings using the collation rules of the specified locale
+pub fn compare(a: &str, b: &str, locale: &str, numeric: bool) -> Option<Ordering> {
+	COLLATORS.with(|c| {
+		let collator = c
+			.borrow_mut()
+			.entry((locale.to_owned(), numeric))
+			.or_insert_with(|| collator(locale, numeric).map(Rc::new))
+			.clone();
+		collator.map(|c| c.compare(a, b))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn locale() {
+		assert_eq!(compare("Äpfel", "Bär", "de", false), Some(Ordering::Less));
+		assert_eq!(compare("Äpfel", "Zebra", "sv", false), Some(Ordering::Greater));
+	}
+
+	#[test]
+	fn numeric() {
+		assert_eq!(compare("item10", "item9", "en", false), Some(Ordering::Less));
+		assert_eq!(compare("item10", "item9", "en", true), Some(Ordering::Greater));
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(!valid("not a locale"));
+		assert_eq!(compare("a", "b", "not a locale", false), None);
+	}
+}
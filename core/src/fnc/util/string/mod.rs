@@ -1,2 +1,5 @@
+pub mod collate;
 pub mod fuzzy;
+pub mod ngram;
 pub mod slug;
+pub mod soundex;
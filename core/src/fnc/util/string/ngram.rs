@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+/// Retrieve the set of character n-grams of a lowercased string, which
+/// is padded with a space on each side so that word boundaries count.
+pub fn ngrams(s: &str, n: usize) -> HashSet<String> {
+	let chars: Vec<char> =
+		std::iter::once(' ').chain(s.to_lowercase().chars()).chain(std::iter::once(' ')).collect();
+	if chars.len() <= n {
+		return HashSet::from([chars.into_iter().collect()]);
+	}
+	chars.windows(n).map(|w| w.iter().collect()).collect()
+}
+
+/// Calculate the Jaccard similarity of the character n-grams of two strings
+pub fn similarity(a: &str, b: &str, n: usize) -> f64 {
+	let a = ngrams(a, n);
+	let b = ngrams(b, n);
+	let union = a.union(&b).count();
+	match union {
+		0 => 1.0,
+		union => a.intersection(&b).count() as f64 / union as f64,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ngram_similarity() {
+		assert_eq!(ngrams("ab", 3), HashSet::from([" ab".to_string(), "ab ".to_string()]));
+		assert_eq!(similarity("Text", "text", 3), 1.0);
+		assert_eq!(similarity("abc", "xyz", 3), 0.0);
+		assert_eq!(similarity("night", "nacht", 2), 1.0 / 3.0);
+	}
+}
@@ -0,0 +1,57 @@
+/// The length of a Soundex code
+const LENGTH: usize = 4;
+
+/// Calculate the American Soundex code of a string, ignoring any
+/// characters which are not ASCII letters. An empty string is
+/// returned when the string does not contain any ASCII letters.
+pub fn soundex(s: &str) -> String {
+	let mut code = String::with_capacity(LENGTH);
+	let mut last = None;
+	for c in s.chars().filter(char::is_ascii_alphabetic) {
+		let c = c.to_ascii_uppercase();
+		let digit = match c {
+			'B' | 'F' | 'P' | 'V' => Some('1'),
+			'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+			'D' | 'T' => Some('3'),
+			'L' => Some('4'),
+			'M' | 'N' => Some('5'),
+			'R' => Some('6'),
+			// H and W do not separate letters with the same code
+			'H' | 'W' => continue,
+			// Vowels separate letters with the same code
+			_ => None,
+		};
+		if code.is_empty() {
+			code.push(c);
+		} else if digit.is_some() && digit != last {
+			code.extend(digit);
+			if code.len() == LENGTH {
+				return code;
+			}
+		}
+		last = digit;
+	}
+	if !code.is_empty() {
+		while code.len() < LENGTH {
+			code.push('0');
+		}
+	}
+	code
+}
+
+#[cfg(test)]
+mod tests {
+	use super::soundex;
+
+	#[test]
+	fn soundex_codes() {
+		assert_eq!(soundex("Robert"), "R163");
+		assert_eq!(soundex("Rupert"), "R163");
+		assert_eq!(soundex("Rubin"), "R150");
+		assert_eq!(soundex("Ashcraft"), "A261");
+		assert_eq!(soundex("Tymczak"), "T522");
+		assert_eq!(soundex("Pfister"), "P236");
+		assert_eq!(soundex("Lee"), "L000");
+		assert_eq!(soundex("123"), "");
+	}
+}
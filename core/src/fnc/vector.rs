@@ -55,7 +55,8 @@ pub mod distance {
 	use crate::err::Error;
 	use crate::fnc::get_execution_context;
 	use crate::fnc::util::math::vector::{
-		ChebyshevDistance, EuclideanDistance, HammingDistance, ManhattanDistance, MinkowskiDistance,
+		ChebyshevDistance, CosineDistance, DotDistance, EuclideanDistance, HammingDistance,
+		ManhattanDistance, MinkowskiDistance,
 	};
 	use crate::idx::planner::IterationStage;
 	use crate::sql::{Number, Value};
@@ -64,6 +65,14 @@ pub mod distance {
 		Ok(a.chebyshev_distance(&b)?.into())
 	}
 
+	pub fn cosine((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value, Error> {
+		Ok(a.cosine_distance(&b)?.into())
+	}
+
+	pub fn dot((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value, Error> {
+		Ok(a.dot_distance(&b)?.into())
+	}
+
 	pub fn euclidean((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value, Error> {
 		Ok(a.euclidean_distance(&b)?.into())
 	}
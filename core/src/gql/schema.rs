@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use crate::dbs::Session;
 use crate::kvs::Datastore;
+use crate::sql::index::VectorType;
 use crate::sql::kind::Literal;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::define::config::graphql::TablesConfig;
@@ -675,11 +676,16 @@ fn kind_to_type(kind: Kind, types: &mut Vec<Type>) -> Result<TypeRef, GqlError>
 		}
 		Kind::Set(_, _) => return Err(schema_error("Kind::Set is not yet supported")),
 		Kind::Array(k, _) => TypeRef::List(Box::new(kind_to_type(*k, types)?)),
+		Kind::Vector(t, _) => match t {
+			VectorType::F64 | VectorType::F32 => TypeRef::named_nn_list(TypeRef::FLOAT),
+			_ => TypeRef::named_nn_list(TypeRef::INT),
+		},
 		Kind::Function(_, _) => return Err(schema_error("Kind::Function is not yet supported")),
 		Kind::Range => return Err(schema_error("Kind::Range is not yet supported")),
 		// TODO(raphaeldarley): check if union is of literals and generate enum
 		// generate custom scalar from other literals?
 		Kind::Literal(_) => return Err(schema_error("Kind::Literal is not yet supported")),
+		Kind::Custom(_) => return Err(schema_error("Kind::Custom is not yet supported")),
 	};
 
 	let out = match optional {
@@ -742,9 +748,11 @@ fn filter_from_type(
 		Kind::Either(_) => {}
 		Kind::Set(_, _) => {}
 		Kind::Array(_, _) => {}
+		Kind::Vector(_, _) => {}
 		Kind::Function(_, _) => {}
 		Kind::Range => {}
 		Kind::Literal(_) => {}
+		Kind::Custom(_) => {}
 	};
 	Ok(filter)
 }
@@ -1184,8 +1192,17 @@ fn gql_to_sql_kind(val: &GqlValue, kind: Kind) -> Result<SqlValue, GqlError> {
 			}
 			_ => Err(type_error(kind, val)),
 		},
+		Kind::Vector(_, _) => match val {
+			GqlValue::List(l) => {
+				let list: Vec<SqlValue> =
+					l.iter().map(|v| gql_to_sql_kind(v, Kind::Float)).collect::<Result<_, _>>()?;
+				SqlValue::from(list).coerce_to(&kind).map_err(|_| type_error(kind, val))
+			}
+			_ => Err(type_error(kind, val)),
+		},
 		Kind::Function(_, _) => Err(resolver_error("Sets are not yet supported")),
 		Kind::Range => Err(resolver_error("Ranges are not yet supported")),
 		Kind::Literal(_) => Err(resolver_error("Literals are not yet supported")),
+		Kind::Custom(_) => Err(resolver_error("Custom types are not yet supported")),
 	}
 }
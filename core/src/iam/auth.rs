@@ -1,3 +1,4 @@
+use crate::kvs::Datastore;
 use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -74,6 +75,16 @@ impl Auth {
 		Self::new(Actor::new(rid.to_string(), vec![], (ns, db, ac).into()))
 	}
 
+	/// Authenticates as a user on a level, along with any custom roles of the user
+	pub(crate) async fn for_user(
+		kvs: &Datastore,
+		user: &DefineUserStatement,
+		level: Level,
+	) -> Result<Self, crate::err::Error> {
+		let roles = Role::resolve(kvs, &user.roles, &level).await?;
+		Ok(Self::new(Actor::new(user.name.to_string(), roles, level)))
+	}
+
 	//
 	// Permission checks
 	//
@@ -87,6 +98,11 @@ impl Auth {
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.actor.has_role(role)
 	}
+
+	/// Checks if the custom roles of the current actor allow an action on a given resource
+	pub fn is_granted(&self, action: &Action, res: &Resource) -> bool {
+		self.actor.is_granted(action, res)
+	}
 }
 
 impl std::convert::TryFrom<(&DefineUserStatement, Level)> for Auth {
@@ -7,7 +7,7 @@ use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpres
 use serde::{Deserialize, Serialize};
 
 use super::{Level, Resource, ResourceKind};
-use crate::iam::{Error, Role};
+use crate::iam::{Action, Error, Role};
 use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
 
 //
@@ -61,6 +61,14 @@ impl Actor {
 		self.roles.contains(role)
 	}
 
+	/// Checks if any of the custom roles of the actor allow the action on the resource.
+	pub fn is_granted(&self, action: &Action, res: &Resource) -> bool {
+		self.roles.iter().any(|r| match r {
+			Role::Custom(role) => role.allows(action, res),
+			_ => false,
+		})
+	}
+
 	// Cedar policy helpers
 	pub fn cedar_attrs(&self) -> HashMap<String, RestrictedExpression> {
 		[
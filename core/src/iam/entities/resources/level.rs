@@ -67,6 +67,18 @@ impl Level {
 		}
 	}
 
+	/// Checks if the given level is this level or is nested below it
+	pub fn contains(&self, other: &Level) -> bool {
+		let mut level = Some(other.to_owned());
+		while let Some(l) = level {
+			if &l == self {
+				return true;
+			}
+			level = l.parent();
+		}
+		false
+	}
+
 	fn parent(&self) -> Option<Level> {
 		match self {
 			Level::No => None,
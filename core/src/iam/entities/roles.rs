@@ -1,12 +1,15 @@
+use super::{Action, Level, Resource, ResourceKind};
 use crate::iam::Error;
+use crate::kvs::{Datastore, LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::{DefineRoleStatement, RoleAction, RoleGrant};
 use crate::sql::Ident;
 use cedar_policy::{Entity, EntityTypeName, EntityUid, RestrictedExpression};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-// In the future, we will allow for custom roles. For now, provide predefined roles.
-#[revisioned(revision = 1)]
+// Predefined roles, along with any custom roles defined with DEFINE ROLE.
+#[revisioned(revision = 2)]
 #[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -15,6 +18,8 @@ pub enum Role {
 	Viewer,
 	Editor,
 	Owner,
+	#[revision(start = 2)]
+	Custom(CustomRole),
 }
 
 impl std::fmt::Display for Role {
@@ -23,7 +28,114 @@ impl std::fmt::Display for Role {
 			Self::Viewer => write!(f, "Viewer"),
 			Self::Editor => write!(f, "Editor"),
 			Self::Owner => write!(f, "Owner"),
+			Self::Custom(role) => write!(f, "{}", role.name),
+		}
+	}
+}
+
+/// A user-defined role, as it was defined when the actor authenticated.
+#[revisioned(revision = 1)]
+#[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct CustomRole {
+	name: String,
+	level: Level,
+	grants: Vec<RoleGrant>,
+}
+
+impl CustomRole {
+	pub fn new(def: &DefineRoleStatement, level: Level) -> Self {
+		Self {
+			name: def.name.to_raw(),
+			level,
+			grants: def.grants.clone(),
+		}
+	}
+
+	/// Checks if any of the grants of this role allow the action on the resource
+	pub fn allows(&self, action: &Action, res: &Resource) -> bool {
+		// Users, access methods, grants, and roles can only be viewed, as editing
+		// them would allow granting any role, including more than this role allows
+		if action == &Action::Edit
+			&& matches!(res.kind(), ResourceKind::Actor | ResourceKind::Access)
+		{
+			return false;
+		}
+		self.grants.iter().any(|grant| {
+			let action = match grant.action {
+				RoleAction::View => action == &Action::View,
+				RoleAction::Edit => true,
+			};
+			let kind = grant.kinds.is_empty() || grant.kinds.contains(res.kind());
+			action && kind && self.scope(grant).contains(res.level())
+		})
+	}
+
+	// Grants apply to the level of the role, unless they are limited further
+	fn scope(&self, grant: &RoleGrant) -> Level {
+		match (&self.level, &grant.ns, &grant.db) {
+			(Level::Root, Some(ns), Some(db)) => Level::Database(ns.to_raw(), db.to_raw()),
+			(Level::Root, Some(ns), None) => Level::Namespace(ns.to_raw()),
+			(Level::Namespace(ns), None, Some(db)) => Level::Database(ns.to_owned(), db.to_raw()),
+			(level, _, _) => level.to_owned(),
+		}
+	}
+}
+
+impl Role {
+	/// Resolves the named roles of an actor on a level, including any custom roles defined on that level
+	pub(crate) async fn resolve(
+		kvs: &Datastore,
+		roles: &[Ident],
+		level: &Level,
+	) -> Result<Vec<Role>, crate::err::Error> {
+		// Avoid a transaction if only predefined roles are used
+		if let Ok(roles) = roles.iter().map(Role::try_from).collect::<Result<_, _>>() {
+			return Ok(roles);
+		}
+		let tx = kvs.transaction(Read, Optimistic).await?;
+		let res = Self::resolve_with(&tx, roles, level).await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Resolves the named roles of an actor on a level within an existing transaction
+	pub(crate) async fn resolve_with(
+		tx: &Transaction,
+		roles: &[Ident],
+		level: &Level,
+	) -> Result<Vec<Role>, crate::err::Error> {
+		let mut res = Vec::with_capacity(roles.len());
+		for role in roles {
+			if let Ok(role) = Role::try_from(role) {
+				res.push(role);
+				continue;
+			}
+			// Custom roles are only available to actors on the same level
+			let def = match level {
+				Level::Root => tx.get_root_role(role).await,
+				Level::Namespace(ns) => tx.get_ns_role(ns, role).await,
+				Level::Database(ns, db) => tx.get_db_role(ns, db, role).await,
+				_ => return Err(Error::InvalidRole(role.to_raw()).into()),
+			};
+			match def {
+				Ok(def) => res.push(Role::Custom(CustomRole::new(&def, level.to_owned()))),
+				Err(
+					crate::err::Error::RoleRootNotFound {
+						..
+					}
+					| crate::err::Error::RoleNsNotFound {
+						..
+					}
+					| crate::err::Error::RoleDbNotFound {
+						..
+					},
+				) => return Err(Error::InvalidRole(role.to_raw()).into()),
+				Err(e) => return Err(e),
+			}
 		}
+		Ok(res)
 	}
 }
 
@@ -1,6 +1,7 @@
 use crate::dbs::capabilities::NetTarget;
 use crate::err::Error;
 use crate::kvs::Datastore;
+use crate::sql::access_type::JwtAccessVerifyJwks;
 use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::jwk::{
 	AlgorithmParameters::*, Jwk, JwkSet, KeyAlgorithm, KeyOperations, PublicKeyUse,
@@ -206,10 +207,8 @@ pub(super) async fn config(
 		Ok(dec) => {
 			let mut val = Validation::new(alg);
 
-			// TODO(gguillemas): This keeps the existing behavior as of SurrealDB 2.0.0-alpha.9.
-			// Up to that point, a fork of the "jsonwebtoken" crate in version 8.3.0 was being used.
-			// Now that the audience claim is validated by default, we could allow users to leverage this.
-			// This will most likely involve defining an audience string via "DEFINE ACCESS ... TYPE JWT".
+			// The audience claim is only validated when the access method defines an audience
+			// This keeps the existing behavior as of SurrealDB 2.0.0-alpha.9 for other tokens
 			val.validate_aud = false;
 
 			Ok((dec, val))
@@ -221,6 +220,26 @@ pub(super) async fn config(
 	}
 }
 
+// Restricts a verification configuration to the claims expected by a JWKS access method
+// Identity providers usually sign tokens for many applications with the same keys
+// Validating the issuer and audience prevents tokens meant for other applications from being accepted
+pub(super) fn claims(
+	(dec, mut val): (DecodingKey, Validation),
+	jwks: &JwtAccessVerifyJwks,
+) -> (DecodingKey, Validation) {
+	// Tokens without the expected claims are rejected
+	if let Some(iss) = &jwks.issuer {
+		val.set_issuer(&[iss]);
+		val.required_spec_claims.insert("iss".to_string());
+	}
+	if let Some(aud) = &jwks.audience {
+		val.set_audience(&[aud]);
+		val.required_spec_claims.insert("aud".to_string());
+		val.validate_aud = true;
+	}
+	(dec, val)
+}
+
 // Checks if network access to a remote location is allowed by the datastore capabilities
 // Attempts to find a relevant JWK object inside a JWKS object fetched from the remote location
 async fn find_jwk_from_url(kvs: &Datastore, url: &str, kid: &str) -> Result<Jwk, Error> {
@@ -458,6 +477,23 @@ mod tests {
 		assert!(res.is_ok(), "Failed to validate token the second time: {:?}", res.err());
 	}
 
+	#[test]
+	fn test_claims() {
+		let jwk = &DEFAULT_JWKS.keys[0];
+		let cf = (DecodingKey::from_jwk(jwk).unwrap(), Validation::new(RS256));
+		let jwks = JwtAccessVerifyJwks {
+			url: "https://issuer/.well-known/jwks.json".to_string(),
+			issuer: Some("https://issuer/".to_string()),
+			audience: Some("surrealdb".to_string()),
+		};
+		let (_, val) = claims(cf, &jwks);
+		assert_eq!(val.iss, Some(["https://issuer/".to_string()].into()));
+		assert_eq!(val.aud, Some(["surrealdb".to_string()].into()));
+		assert!(val.validate_aud);
+		assert!(val.required_spec_claims.contains("iss"));
+		assert!(val.required_spec_claims.contains("aud"));
+	}
+
 	#[tokio::test]
 	async fn test_capabilities_default() {
 		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::default());
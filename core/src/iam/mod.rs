@@ -11,6 +11,7 @@ pub mod issue;
 #[cfg(feature = "jwks")]
 pub mod jwks;
 pub mod policies;
+pub mod revoke;
 pub mod signin;
 pub mod signup;
 pub mod token;
@@ -47,6 +48,8 @@ pub fn is_allowed(
 ) -> Result<(), Error> {
 	match policies::is_allowed(actor, action, resource, ctx.unwrap_or(Context::empty())) {
 		(allowed, _) if allowed => Ok(()),
+		// Custom roles can allow actions which the predefined roles do not
+		_ if actor.is_granted(action, resource) => Ok(()),
 		_ => {
 			let err = Error::NotAllowed {
 				actor: actor.to_string(),
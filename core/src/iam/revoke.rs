@@ -0,0 +1,77 @@
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::kvs::{Datastore, Key, LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::RevokeKind;
+use crate::sql::{Datetime, Value};
+
+/// Checks whether a token or session has been revoked on the root,
+/// or on the namespace or database which the credential belongs to.
+pub async fn is_revoked(
+	tx: &Transaction,
+	kind: RevokeKind,
+	id: &str,
+	ns: Option<&str>,
+	db: Option<&str>,
+) -> Result<bool, Error> {
+	let kind = kind.as_str();
+	// Revocations on the root apply to all credentials
+	let mut keys: Vec<Key> = vec![crate::key::root::rv::new(kind, id).into()];
+	if let Some(ns) = ns {
+		keys.push(crate::key::namespace::rv::new(ns, kind, id).into());
+		if let Some(db) = db {
+			keys.push(crate::key::database::rv::new(ns, db, kind, id).into());
+		}
+	}
+	// Expired revocations are ignored until they are swept
+	let now = Value::from(Datetime::default());
+	for key in keys {
+		if let Some(v) = tx.get(key, None).await? {
+			if Value::from(v) > now {
+				return Ok(true);
+			}
+		}
+	}
+	Ok(false)
+}
+
+/// Checks that neither the token nor the session of an
+/// authenticated session have been revoked since signin.
+pub async fn check_session(kvs: &Datastore, sess: &Session) -> Result<(), Error> {
+	// Anonymous sessions have no credentials to revoke
+	if sess.au.is_anon() {
+		return Ok(());
+	}
+	// Get the token identifier, if the session used a token
+	let jti = match &sess.tk {
+		Some(Value::Object(tk)) => match tk.get("jti") {
+			Some(Value::Strand(v)) => Some(v.as_str()),
+			_ => None,
+		},
+		_ => None,
+	};
+	// Check if there is anything to look up
+	if jti.is_none() && sess.id.is_none() {
+		return Ok(());
+	}
+	// Revocations are checked at the level of the authenticated actor
+	let level = sess.au.level();
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let res = async {
+		if let Some(jti) = jti {
+			if is_revoked(&tx, RevokeKind::Token, jti, level.ns(), level.db()).await? {
+				return Err(Error::RevokedToken);
+			}
+		}
+		if let Some(id) = &sess.id {
+			if is_revoked(&tx, RevokeKind::Session, id, level.ns(), level.db()).await? {
+				return Err(Error::RevokedSession);
+			}
+		}
+		Ok(())
+	}
+	.await;
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	res
+}
@@ -285,12 +285,16 @@ pub async fn db_access(
 						id: match &gr.subject {
 							access::Subject::User(user) => Some(user.to_raw()),
 							access::Subject::Record(rid) => Some(rid.to_raw()),
+							// Return opaque error as this code should not be reachable.
+							_ => return Err(Error::InvalidAuth),
 						},
 						roles: match &gr.subject {
 							access::Subject::User(_) => {
 								Some(roles.iter().map(|v| v.to_string()).collect())
 							}
 							access::Subject::Record(_) => Default::default(),
+							// Return opaque error as this code should not be reachable.
+							_ => return Err(Error::InvalidAuth),
 						},
 						..Claims::default()
 					};
@@ -315,11 +319,10 @@ pub async fn db_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						access::Subject::User(user) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-								Level::Database(ns, db),
-							)));
+							let level = Level::Database(ns, db);
+							let roles = Role::resolve(kvs, &roles, &level).await?;
+							session.au =
+								Arc::new(Auth::new(Actor::new(user.to_string(), roles, level)));
 						}
 						access::Subject::Record(rid) => {
 							session.au = Arc::new(Auth::new(Actor::new(
@@ -329,6 +332,8 @@ pub async fn db_access(
 							)));
 							session.rd = Some(Value::from(rid.to_owned()));
 						}
+						// Return opaque error as this code should not be reachable.
+						_ => return Err(Error::InvalidAuth),
 					};
 					// Check the authentication token.
 					match enc {
@@ -377,7 +382,9 @@ pub async fn db_user(
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).try_into()?);
+			session.au = Arc::new(
+				Auth::for_user(kvs, &u, Level::Database(ns.to_owned(), db.to_owned())).await?,
+			);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -504,11 +511,10 @@ pub async fn ns_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						access::Subject::User(user) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-								Level::Namespace(ns),
-							)));
+							let level = Level::Namespace(ns);
+							let roles = Role::resolve(kvs, &roles, &level).await?;
+							session.au =
+								Arc::new(Auth::new(Actor::new(user.to_string(), roles, level)));
 						}
 						// Return opaque error as this code should not be reachable.
 						_ => return Err(Error::InvalidAuth),
@@ -557,7 +563,7 @@ pub async fn ns_user(
 			session.tk = Some((&val).into());
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).try_into()?);
+			session.au = Arc::new(Auth::for_user(kvs, &u, Level::Namespace(ns.to_owned())).await?);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -602,7 +608,7 @@ pub async fn root_user(
 			// Set the authentication on the session
 			session.tk = Some(val.into());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Root).try_into()?);
+			session.au = Arc::new(Auth::for_user(kvs, &u, Level::Root).await?);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -725,11 +731,10 @@ pub async fn root_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						access::Subject::User(user) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-								Level::Root,
-							)));
+							let level = Level::Root;
+							let roles = Role::resolve(kvs, &roles, &level).await?;
+							session.au =
+								Arc::new(Auth::new(Actor::new(user.to_string(), roles, level)));
 						}
 						// Return opaque error as this code should not be reachable.
 						_ => return Err(Error::InvalidAuth),
@@ -754,21 +759,26 @@ pub fn validate_grant_bearer(vars: Object) -> Result<(String, String), Error> {
 		Some(key) => key.to_raw_string(),
 		None => return Err(Error::AccessBearerMissingKey),
 	};
-	if key.len() != access::GRANT_BEARER_LENGTH {
+	validate_grant_key(key, access::GRANT_BEARER_PREFIX, access::GRANT_BEARER_LENGTH)
+}
+
+pub fn validate_grant_key(
+	key: String,
+	expected: &str,
+	length: usize,
+) -> Result<(String, String), Error> {
+	if key.len() != length {
 		return Err(Error::AccessGrantBearerInvalid);
 	}
 	// Retrieve the prefix from the provided key.
-	let prefix: String = key.chars().take(access::GRANT_BEARER_PREFIX.len()).collect();
+	let prefix: String = key.chars().take(expected.len()).collect();
 	// Check the length of the key prefix.
-	if prefix != access::GRANT_BEARER_PREFIX {
+	if prefix != expected {
 		return Err(Error::AccessGrantBearerInvalid);
 	}
 	// Retrieve the key identifier from the provided key.
-	let kid: String = key
-		.chars()
-		.skip(access::GRANT_BEARER_PREFIX.len() + 1)
-		.take(access::GRANT_BEARER_ID_LENGTH)
-		.collect();
+	let kid: String =
+		key.chars().skip(expected.len() + 1).take(access::GRANT_BEARER_ID_LENGTH).collect();
 	// Check the length of the key identifier.
 	if kid.len() != access::GRANT_BEARER_ID_LENGTH {
 		return Err(Error::AccessGrantBearerInvalid);
@@ -1177,6 +1187,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 							Role::Viewer => "VIEWER",
 							Role::Editor => "EDITOR",
 							Role::Owner => "OWNER",
+							Role::Custom(_) => unreachable!("custom roles are not tested here"),
 						})
 						.collect();
 					format!("ROLES {}", roles.join(", "))
@@ -3488,10 +3499,27 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				overwrite: false,
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
+			// Define the user with a custom role which is then removed.
+			ds.execute(&format!("DEFINE ROLE nonexistent ON {}", level.level), &sess, None)
+				.await
+				.unwrap();
 			ds.process(Statement::Define(DefineStatement::User(user)).into(), &sess, None)
 				.await
 				.unwrap();
+			// REMOVE ROLE refuses to remove a granted role, so delete the definition directly.
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			match level.level {
+				"ROOT" => tx.del(crate::key::root::rl::new("nonexistent")).await.unwrap(),
+				"NS" => {
+					tx.del(crate::key::namespace::rl::new("test", "nonexistent")).await.unwrap()
+				}
+				"DB" => tx
+					.del(crate::key::database::rl::new("test", "test", "nonexistent"))
+					.await
+					.unwrap(),
+				_ => panic!("Unsupported level"),
+			}
+			tx.commit().await.unwrap();
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
@@ -1,17 +1,19 @@
-use crate::cnf::INSECURE_FORWARD_ACCESS_ERRORS;
+use crate::cnf::{EXPERIMENTAL_BEARER_ACCESS, INSECURE_FORWARD_ACCESS_ERRORS};
 use crate::dbs::Session;
 use crate::err::Error;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
-use crate::iam::{issue::expiration, token::Claims, Actor, Auth, Level, Role};
-use crate::kvs::{Datastore, LockType::*, TransactionType::*};
-use crate::sql::access_type::{AccessType, Jwt, JwtAccessVerify};
-use crate::sql::{statements::DefineUserStatement, Algorithm, Thing, Value};
+use crate::iam::signin::{validate_grant_key, verify_grant_bearer};
+use crate::iam::{issue::expiration, revoke, token::Claims, Actor, Auth, Level, Role};
+use crate::kvs::{Datastore, LockType::*, Transaction, TransactionType::*};
+use crate::sql::access_type::{AccessType, JwtAccessVerify};
+use crate::sql::statements::{access, AccessGrant, DefineAccessStatement, RevokeKind};
+use crate::sql::{statements::DefineUserStatement, Algorithm, Ident, Thing, Value};
 use crate::syn;
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::Utc;
 use jsonwebtoken::{decode, DecodingKey, Validation};
-use std::str::{self, FromStr};
+use std::str;
 use std::sync::Arc;
 use std::sync::LazyLock;
 
@@ -95,8 +97,9 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au =
-					Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).try_into()?);
+				session.au = Arc::new(
+					Auth::for_user(kvs, &u, Level::Database(ns.to_owned(), db.to_owned())).await?,
+				);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -106,7 +109,8 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).try_into()?);
+				session.au =
+					Arc::new(Auth::for_user(kvs, &u, Level::Namespace(ns.to_owned())).await?);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -116,7 +120,7 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Root).try_into()?);
+				session.au = Arc::new(Auth::for_user(kvs, &u, Level::Root).await?);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -131,6 +135,10 @@ pub async fn basic(
 }
 
 pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<(), Error> {
+	// Check if this is an API key instead of a token
+	if token.starts_with(access::GRANT_KEY_PREFIX) {
+		return key(kvs, session, token).await;
+	}
 	// Log the authentication type
 	trace!("Attempting token authentication");
 	// Decode the token without verifying
@@ -151,6 +159,24 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			return Err(Error::ExpiredToken);
 		}
 	}
+	// Check if the auth token has been revoked
+	if let Some(jti) = &token_data.claims.jti {
+		let claims = &token_data.claims;
+		let tx = kvs.transaction(Read, Optimistic).await?;
+		let revoked = revoke::is_revoked(
+			&tx,
+			RevokeKind::Token,
+			jti,
+			claims.ns.as_deref(),
+			claims.db.as_deref(),
+		)
+		.await;
+		tx.cancel().await?;
+		if revoked? {
+			debug!("Token verification failed due to the 'jti' claim having been revoked");
+			return Err(Error::RevokedToken);
+		}
+	}
 	// Check the token authentication claims
 	match &token_data.claims {
 		// Check if this is record access
@@ -178,7 +204,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 					#[cfg(feature = "jwks")]
 					JwtAccessVerify::Jwks(jwks) => {
 						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
+							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg)
+								.await
+								.map(|cf| jwks::claims(cf, jwks))
 						} else {
 							Err(Error::MissingTokenHeader("kid".to_string()))
 						}
@@ -236,12 +264,14 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			match &de.kind {
 				// If the access type is Jwt or Bearer, this is database access
 				AccessType::Jwt(_) | AccessType::Bearer(_) => {
-					let cf = match &de.kind.jwt().verify {
+					let cf = match &de.kind.jwt().ok_or(Error::AccessMethodMismatch)?.verify {
 						JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
 						#[cfg(feature = "jwks")]
 						JwtAccessVerify::Jwks(jwks) => {
 							if let Some(kid) = token_data.header.kid {
-								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
+								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg)
+									.await
+									.map(|cf| jwks::claims(cf, jwks))
 							} else {
 								Err(Error::MissingTokenHeader("kid".to_string()))
 							}
@@ -260,17 +290,18 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 						sess.or.clone_from(&session.or);
 						authenticate_generic(kvs, &sess, au).await?;
 					}
+					// Get the level of the access method
+					let level = Level::Database(ns.to_string(), db.to_string());
 					// Parse the roles
 					let roles = match &token_data.claims.roles {
 						// If no role is provided, grant the viewer role
 						None => vec![Role::Viewer],
 						// If roles are provided, parse them
-						Some(roles) => roles
-							.iter()
-							.map(|r| -> Result<Role, Error> {
-								Role::from_str(r.as_str()).map_err(Error::IamError)
-							})
-							.collect::<Result<Vec<_>, _>>()?,
+						Some(roles) => {
+							let roles: Vec<Ident> =
+								roles.iter().map(|r| r.as_str().into()).collect();
+							Role::resolve(kvs, &roles, &level).await?
+						}
 					};
 					// Log the success
 					debug!("Authenticated to database `{}` with access method `{}`", db, ac);
@@ -280,11 +311,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 					session.db = Some(db.to_owned());
 					session.ac = Some(ac.to_owned());
 					session.exp = expiration(de.duration.session)?;
-					session.au = Arc::new(Auth::new(Actor::new(
-						de.name.to_string(),
-						roles,
-						Level::Database(ns.to_string(), db.to_string()),
-					)));
+					session.au = Arc::new(Auth::new(Actor::new(de.name.to_string(), roles, level)));
 				}
 				// If the access type is Record, this is record access
 				// Record access without an "id" claim is only possible if there is an AUTHENTICATE clause
@@ -297,7 +324,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 							#[cfg(feature = "jwks")]
 							JwtAccessVerify::Jwks(jwks) => {
 								if let Some(kid) = token_data.header.kid {
-									jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
+									jwks::config(kvs, &kid, &jwks.url, token_data.header.alg)
+										.await
+										.map(|cf| jwks::claims(cf, jwks))
 								} else {
 									Err(Error::MissingTokenHeader("kid".to_string()))
 								}
@@ -332,6 +361,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 					}
 					_ => return Err(Error::AccessMethodMismatch),
 				},
+				// Keys are not tokens, and can not be used to authenticate with a token
+				AccessType::Key(_) => return Err(Error::AccessMethodMismatch),
 			};
 			Ok(())
 		}
@@ -364,11 +395,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Database(ns.to_string(), db.to_string()),
-			)));
+			let level = Level::Database(ns.to_string(), db.to_string());
+			let roles = Role::resolve(kvs, &de.roles, &level).await?;
+			session.au = Arc::new(Auth::new(Actor::new(id.to_string(), roles, level)));
 			Ok(())
 		}
 		// Check if this is namespace access
@@ -387,19 +416,23 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(Error::MissingTokenHeader("kid".to_string()))
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					match &de.kind.jwt().ok_or(Error::AccessMethodMismatch)?.verify {
+						JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
+						#[cfg(feature = "jwks")]
+						JwtAccessVerify::Jwks(jwks) => {
+							if let Some(kid) = token_data.header.kid {
+								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg)
+									.await
+									.map(|cf| jwks::claims(cf, jwks))
+							} else {
+								Err(Error::MissingTokenHeader("kid".to_string()))
+							}
 						}
+						#[cfg(not(feature = "jwks"))]
+						_ => return Err(Error::AccessMethodMismatch),
 					}
-					#[cfg(not(feature = "jwks"))]
-					_ => return Err(Error::AccessMethodMismatch),
-				},
+				}
 				_ => return Err(Error::AccessMethodMismatch),
 			}?;
 			// Verify the token
@@ -413,17 +446,17 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				sess.or.clone_from(&session.or);
 				authenticate_generic(kvs, &sess, au).await?;
 			}
+			// Get the level of the access method
+			let level = Level::Namespace(ns.to_string());
 			// Parse the roles
 			let roles = match &token_data.claims.roles {
 				// If no role is provided, grant the viewer role
 				None => vec![Role::Viewer],
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => {
+					let roles: Vec<Ident> = roles.iter().map(|r| r.as_str().into()).collect();
+					Role::resolve(kvs, &roles, &level).await?
+				}
 			};
 			// Log the success
 			debug!("Authenticated to namespace `{}` with access method `{}`", ns, ac);
@@ -432,11 +465,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.ac = Some(ac.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				de.name.to_string(),
-				roles,
-				Level::Namespace(ns.to_string()),
-			)));
+			session.au = Arc::new(Auth::new(Actor::new(de.name.to_string(), roles, level)));
 			Ok(())
 		}
 		// Check if this is namespace authentication with user credentials
@@ -466,11 +495,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Namespace(ns.to_string()),
-			)));
+			let level = Level::Namespace(ns.to_string());
+			let roles = Role::resolve(kvs, &de.roles, &level).await?;
+			session.au = Arc::new(Auth::new(Actor::new(id.to_string(), roles, level)));
 			Ok(())
 		}
 		// Check if this is root access
@@ -488,19 +515,23 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(Error::MissingTokenHeader("kid".to_string()))
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					match &de.kind.jwt().ok_or(Error::AccessMethodMismatch)?.verify {
+						JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
+						#[cfg(feature = "jwks")]
+						JwtAccessVerify::Jwks(jwks) => {
+							if let Some(kid) = token_data.header.kid {
+								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg)
+									.await
+									.map(|cf| jwks::claims(cf, jwks))
+							} else {
+								Err(Error::MissingTokenHeader("kid".to_string()))
+							}
 						}
+						#[cfg(not(feature = "jwks"))]
+						_ => return Err(Error::AccessMethodMismatch),
 					}
-					#[cfg(not(feature = "jwks"))]
-					_ => return Err(Error::AccessMethodMismatch),
-				},
+				}
 				_ => return Err(Error::AccessMethodMismatch),
 			}?;
 			// Verify the token
@@ -519,12 +550,10 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				// If no role is provided, grant the viewer role
 				None => vec![Role::Viewer],
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => {
+					let roles: Vec<Ident> = roles.iter().map(|r| r.as_str().into()).collect();
+					Role::resolve(kvs, &roles, &Level::Root).await?
+				}
 			};
 			// Log the success
 			debug!("Authenticated to root with access method `{}`", ac);
@@ -560,11 +589,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			// Set the session
 			session.tk = Some(value);
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Root,
-			)));
+			let level = Level::Root;
+			let roles = Role::resolve(kvs, &de.roles, &level).await?;
+			session.au = Arc::new(Auth::new(Actor::new(id.to_string(), roles, level)));
 			Ok(())
 		}
 		// There was an auth error
@@ -572,6 +599,92 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 	}
 }
 
+pub async fn key(kvs: &Datastore, session: &mut Session, key: &str) -> Result<(), Error> {
+	// Log the authentication type
+	trace!("Attempting API key authentication");
+	// Key access relies on the experimental access grant management
+	if !*EXPERIMENTAL_BEARER_ACCESS {
+		// Return opaque error to avoid leaking the existence of the feature.
+		debug!("Error attempting to authenticate with disabled key access feature");
+		return Err(Error::InvalidAuth);
+	}
+	// Extract the key identifier from the provided key
+	let (kid, key) =
+		validate_grant_key(key.to_owned(), access::GRANT_KEY_PREFIX, access::GRANT_KEY_LENGTH)?;
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Find the key access method which issued the key
+	let res = find_key_grant(&tx, session, &kid).await;
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	// Return opaque error to avoid leaking the existence of the key
+	let (level, av, gr) = res?.ok_or(Error::InvalidAuth)?;
+	// Authenticate the key against the stored grant
+	verify_grant_bearer(&gr, key)?;
+	// Parse the roles granted by the access method
+	let roles = match &av.kind {
+		AccessType::Key(at) => Role::resolve(kvs, &at.roles, &level).await?,
+		_ => return Err(Error::InvalidAuth),
+	};
+	// Log the success
+	debug!("Authenticated with key `{}` of access method `{}`", gr.id, av.name);
+	// Set the session
+	session.ac = Some(av.name.to_raw());
+	session.exp = expiration(av.duration.session)?;
+	session.au = Arc::new(Auth::new(Actor::new(gr.subject.id(), roles, level)));
+	Ok(())
+}
+
+// Finds the grant for a key identifier in the key access methods of the selected
+// database, namespace, and root, in that order, as keys are bound to a single level
+async fn find_key_grant(
+	tx: &Transaction,
+	session: &Session,
+	kid: &str,
+) -> Result<Option<(Level, DefineAccessStatement, Arc<AccessGrant>)>, Error> {
+	if let (Some(ns), Some(db)) = (&session.ns, &session.db) {
+		for av in tx.all_db_accesses(ns, db).await?.iter() {
+			if let AccessType::Key(_) = av.kind {
+				match tx.get_db_access_grant(ns, db, &av.name, kid).await {
+					Ok(gr) => {
+						let level = Level::Database(ns.to_owned(), db.to_owned());
+						return Ok(Some((level, av.clone(), gr)));
+					}
+					Err(Error::AccessGrantDbNotFound {
+						..
+					}) => continue,
+					Err(e) => return Err(e),
+				}
+			}
+		}
+	}
+	if let Some(ns) = &session.ns {
+		for av in tx.all_ns_accesses(ns).await?.iter() {
+			if let AccessType::Key(_) = av.kind {
+				match tx.get_ns_access_grant(ns, &av.name, kid).await {
+					Ok(gr) => return Ok(Some((Level::Namespace(ns.to_owned()), av.clone(), gr))),
+					Err(Error::AccessGrantNsNotFound {
+						..
+					}) => continue,
+					Err(e) => return Err(e),
+				}
+			}
+		}
+	}
+	for av in tx.all_root_accesses().await?.iter() {
+		if let AccessType::Key(_) = av.kind {
+			match tx.get_root_access_grant(&av.name, kid).await {
+				Ok(gr) => return Ok(Some((Level::Root, av.clone(), gr))),
+				Err(Error::AccessGrantRootNotFound {
+					..
+				}) => continue,
+				Err(e) => return Err(e),
+			}
+		}
+	}
+	Ok(None)
+}
+
 pub async fn verify_root_creds(
 	ds: &Datastore,
 	user: &str,
@@ -804,6 +917,7 @@ mod tests {
 							Role::Viewer => "VIEWER",
 							Role::Editor => "EDITOR",
 							Role::Owner => "OWNER",
+							Role::Custom(_) => unreachable!("custom roles are not tested here"),
 						})
 						.collect();
 					format!("ROLES {}", roles.join(", "))
@@ -923,10 +1037,27 @@ mod tests {
 				overwrite: false,
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
+			// Define the user with a custom role which is then removed.
+			ds.execute(&format!("DEFINE ROLE nonexistent ON {}", level.level), &sess, None)
+				.await
+				.unwrap();
 			ds.process(Statement::Define(DefineStatement::User(user)).into(), &sess, None)
 				.await
 				.unwrap();
+			// REMOVE ROLE refuses to remove a granted role, so delete the definition directly.
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			match level.level {
+				"ROOT" => tx.del(crate::key::root::rl::new("nonexistent")).await.unwrap(),
+				"NS" => {
+					tx.del(crate::key::namespace::rl::new("test", "nonexistent")).await.unwrap()
+				}
+				"DB" => tx
+					.del(crate::key::database::rl::new("test", "test", "nonexistent"))
+					.await
+					.unwrap(),
+				_ => panic!("Unsupported level"),
+			}
+			tx.commit().await.unwrap();
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
@@ -1373,6 +1504,7 @@ mod tests {
 		use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
 		use jsonwebtoken::jwk::{Jwk, JwkSet};
 		use rand::{distributions::Alphanumeric, Rng};
+		use std::str::FromStr;
 		use wiremock::matchers::{method, path};
 		use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -1511,6 +1643,159 @@ mod tests {
 		}
 	}
 
+	#[cfg(feature = "jwks")]
+	#[tokio::test]
+	async fn test_token_record_jwks_issuer_and_audience() {
+		use crate::dbs::capabilities::{Capabilities, NetTarget, Targets};
+		use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+		use jsonwebtoken::jwk::{Jwk, JwkSet};
+		use rand::{distributions::Alphanumeric, Rng};
+		use std::str::FromStr;
+		use wiremock::matchers::{method, path};
+		use wiremock::{Mock, MockServer, ResponseTemplate};
+
+		// Use unique path to prevent accidental cache reuse
+		fn random_path() -> String {
+			let rng = rand::thread_rng();
+			rng.sample_iter(&Alphanumeric).take(8).map(char::from).collect()
+		}
+
+		// Key identifier used in both JWT and JWT
+		let kid = "test_kid";
+		// Secret used to both sign and verify with HMAC
+		let secret = "jwt_secret";
+
+		// JWKS object with single JWK object providing the HS512 secret used to verify
+		let jwks = JwkSet {
+			keys: vec![Jwk {
+				common: jsonwebtoken::jwk::CommonParameters {
+					public_key_use: None,
+					key_operations: None,
+					key_algorithm: Some(jsonwebtoken::jwk::KeyAlgorithm::HS512),
+					key_id: Some(kid.to_string()),
+					x509_url: None,
+					x509_chain: None,
+					x509_sha1_fingerprint: None,
+					x509_sha256_fingerprint: None,
+				},
+				algorithm: jsonwebtoken::jwk::AlgorithmParameters::OctetKey(
+					jsonwebtoken::jwk::OctetKeyParameters {
+						key_type: jsonwebtoken::jwk::OctetKeyType::Octet,
+						value: STANDARD_NO_PAD.encode(secret),
+					},
+				),
+			}],
+		};
+
+		let jwks_path = format!("{}/jwks.json", random_path());
+		let mock_server = MockServer::start().await;
+		let response = ResponseTemplate::new(200).set_body_json(jwks);
+		Mock::given(method("GET"))
+			.and(path(&jwks_path))
+			.respond_with(response)
+			.mount(&mock_server)
+			.await;
+		let server_url = mock_server.uri();
+
+		// We allow requests to the local server serving the JWKS object
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			format!(
+				r#"
+			DEFINE ACCESS token ON DATABASE TYPE RECORD
+				WITH JWT URL '{server_url}/{jwks_path}'
+				ISSUER 'https://issuer/' AUDIENCE 'surrealdb';
+
+			CREATE user:test;
+			"#
+			)
+			.as_str(),
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Use custom JWT header that includes the key identifier
+		let header_with_kid = jsonwebtoken::Header {
+			kid: Some(kid.to_string()),
+			alg: jsonwebtoken::Algorithm::HS512,
+			..jsonwebtoken::Header::default()
+		};
+
+		// Sign the JWT with the same secret specified in the JWK
+		let key = EncodingKey::from_secret(secret.as_ref());
+		let claims = Claims {
+			iss: Some("https://issuer/".to_string()),
+			iat: Some(Utc::now().timestamp()),
+			nbf: Some(Utc::now().timestamp()),
+			aud: Some(Audience::Single("surrealdb".to_string())),
+			exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			ac: Some("token".to_string()),
+			id: Some("user:test".to_string()),
+			..Claims::default()
+		};
+
+		//
+		// Test with the expected issuer and audience
+		//
+		{
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.au.id(), "user:test");
+		}
+
+		//
+		// Test with a token issued by another issuer
+		//
+		{
+			let mut claims = claims.clone();
+			claims.iss = Some("https://other-issuer/".to_string());
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with a token issued for another audience
+		//
+		{
+			let mut claims = claims.clone();
+			claims.aud = Some(Audience::Single("other-application".to_string()));
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with a token without an audience
+		//
+		{
+			let mut claims = claims.clone();
+			claims.aud = None;
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+	}
+
 	#[test]
 	fn test_verify_pass() {
 		let salt = SaltString::generate(&mut rand::thread_rng());
@@ -1627,6 +1912,131 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_revoked_token() {
+		let secret = "jwt_secret";
+		let key = EncodingKey::from_secret(secret.as_ref());
+		let claims = Claims {
+			iss: Some("surrealdb-test".to_string()),
+			iat: Some(Utc::now().timestamp()),
+			nbf: Some(Utc::now().timestamp()),
+			exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
+			jti: Some("revoked".to_string()),
+			ac: Some("token".to_string()),
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Claims::default()
+		};
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let owner = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			format!("DEFINE ACCESS token ON DATABASE TYPE JWT ALGORITHM HS512 KEY '{secret}' DURATION FOR SESSION 30d, FOR TOKEN 30d")
+				.as_str(),
+			&owner,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Create the token
+		let enc = encode(&HEADER, &claims, &key).unwrap();
+		// Signin with the token before it is revoked
+		let mut sess = Session::default();
+		token(&ds, &mut sess, &enc).await.unwrap();
+		// Revoke the token
+		let res = ds.execute("REVOKE TOKEN 'revoked'", &owner, None).await.unwrap();
+		res.into_iter().for_each(|r| {
+			r.result.unwrap();
+		});
+		// Queries in the existing session are rejected
+		match ds.execute("RETURN 1", &sess, None).await {
+			Err(Error::RevokedToken) => {} // ok
+			Err(err) => panic!("Unexpected error querying with revoked token: {:?}", err),
+			res => panic!("Unexpected success querying with revoked token: {:?}", res),
+		}
+		// Signin with the token after it is revoked
+		let mut sess = Session::default();
+		match token(&ds, &mut sess, &enc).await {
+			Err(Error::RevokedToken) => {} // ok
+			Err(err) => panic!("Unexpected error signing in with revoked token: {:?}", err),
+			res => panic!("Unexpected success signing in with revoked token: {:?}", res),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_key() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let res = ds
+			.execute(
+				r#"
+				DEFINE ACCESS api ON DATABASE TYPE KEY ROLES EDITOR DURATION FOR SESSION 2h;
+				ACCESS api ON DATABASE GRANT FOR KEY ci;
+				"#,
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+		// Get the API key from the grant
+		let grant = res.last().unwrap().result.as_ref().unwrap().clone();
+		let grant = grant.coerce_to_object().unwrap().get("grant").unwrap().clone();
+		let grant = grant.coerce_to_object().unwrap();
+		let key = grant.get("key").unwrap().clone().as_string();
+		assert!(key.starts_with("surreal-key-"));
+
+		// Test with a valid key
+		{
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &key).await;
+			assert!(res.is_ok(), "Failed to authenticate with API key: {:?}", res);
+			assert_eq!(sess.ac, Some("api".to_string()));
+			assert_eq!(sess.au.id(), "ci");
+			assert!(sess.au.is_db());
+			assert_eq!(sess.au.level().ns(), Some("test"));
+			assert_eq!(sess.au.level().db(), Some("test"));
+			assert!(sess.au.has_role(&Role::Editor), "Auth user expected to have Editor role");
+			assert!(!sess.au.has_role(&Role::Owner), "Auth user expected to not have Owner role");
+			assert!(sess.exp.is_some(), "Session expiration is expected to be set");
+		}
+
+		// Test with a key for a different database
+		{
+			let mut sess = Session::default().with_ns("test").with_db("other");
+			let res = token(&ds, &mut sess, &key).await;
+			match res {
+				Err(Error::InvalidAuth) => {} // ok
+				res => panic!("Expected an authentication error, but instead received: {:?}", res),
+			}
+		}
+
+		// Test with an invalid secret
+		{
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let invalid = format!("{}{}", &key[..key.len() - 1], "-");
+			let res = token(&ds, &mut sess, &invalid).await;
+			match res {
+				Err(Error::InvalidAuth) => {} // ok
+				res => panic!("Expected an authentication error, but instead received: {:?}", res),
+			}
+		}
+
+		// Test with a revoked key
+		{
+			let kid = grant.get("id").unwrap().clone().as_string();
+			ds.execute(&format!("ACCESS api ON DATABASE REVOKE GRANT {kid}"), &sess, None)
+				.await
+				.unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &key).await;
+			match res {
+				Err(Error::InvalidAuth) => {} // ok
+				res => panic!("Expected an authentication error, but instead received: {:?}", res),
+			}
+		}
+	}
+
 	#[tokio::test]
 	async fn test_token_authenticate_clause() {
 		#[derive(Debug)]
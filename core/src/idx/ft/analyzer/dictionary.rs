@@ -0,0 +1,80 @@
+use crate::err::Error;
+use ahash::HashSet;
+#[cfg(target_arch = "wasm32")]
+use std::fs::File;
+#[cfg(target_arch = "wasm32")]
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::fs::File;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// A list of words used to segment text written without spaces.
+/// The file contains one word per line. Anything following a tab
+/// (such as a frequency or a part-of-speech tag) is ignored.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct Dictionary {
+	words: Arc<HashSet<String>>,
+	/// The length (in characters) of the longest word
+	max_len: usize,
+}
+
+impl Dictionary {
+	pub(in crate::idx) async fn new(path: &Path) -> Result<Self, Error> {
+		let mut words = HashSet::default();
+		let mut max_len = 0;
+		Self::iterate_file(&mut words, &mut max_len, path).await?;
+		Ok(Self {
+			words: Arc::new(words),
+			max_len,
+		})
+	}
+
+	fn add_line(words: &mut HashSet<String>, max_len: &mut usize, line: String) {
+		let word = line.split('\t').next().unwrap_or_default().trim();
+		if word.is_empty() {
+			return;
+		}
+		*max_len = (*max_len).max(word.chars().count());
+		words.insert(word.to_string());
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	async fn iterate_file(
+		words: &mut HashSet<String>,
+		max_len: &mut usize,
+		path: &Path,
+	) -> Result<(), Error> {
+		let file = File::open(path).await?;
+		let reader = BufReader::new(file);
+		let mut lines = reader.lines();
+		while let Some(line) = lines.next_line().await? {
+			Self::add_line(words, max_len, line);
+		}
+		Ok(())
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	async fn iterate_file(
+		words: &mut HashSet<String>,
+		max_len: &mut usize,
+		path: &Path,
+	) -> Result<(), Error> {
+		let file = File::open(path)?;
+		let reader = BufReader::new(file);
+		for line in reader.lines() {
+			Self::add_line(words, max_len, line?);
+		}
+		Ok(())
+	}
+
+	pub(super) fn max_len(&self) -> usize {
+		self.max_len
+	}
+
+	pub(super) fn contains(&self, word: &str) -> bool {
+		self.words.contains(word)
+	}
+}
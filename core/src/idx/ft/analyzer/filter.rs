@@ -5,6 +5,7 @@ use crate::idx::ft::offsets::Position;
 use crate::idx::trees::store::IndexStores;
 use crate::sql::filter::Filter as SqlFilter;
 use crate::sql::language::Language;
+use ahash::HashSet;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
 
@@ -21,6 +22,7 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	Stopwords(HashSet<String>),
 }
 
 impl Filter {
@@ -54,6 +56,7 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(words) => Filter::Stopwords(words.iter().cloned().collect()),
 		};
 		Ok(f)
 	}
@@ -105,6 +108,16 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::Stopwords(w) => Self::stopword(w, c),
+		}
+	}
+
+	#[inline]
+	fn stopword(w: &HashSet<String>, c: &str) -> FilterResult {
+		if w.contains(c) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
 		}
 	}
 
@@ -206,8 +219,7 @@ mod tests {
 
 	#[tokio::test]
 	async fn test_arabic_stemmer() {
-		let input =
-			"الكلاب تحب الجري في الحديقة، لكن كلبي الصغير يفضل النوم في سريره بدلاً من الجري";
+		let input = "الكلاب تحب الجري في الحديقة، لكن كلبي الصغير يفضل النوم في سريره بدلاً من الجري";
 		let output = vec![
 			"كلاب", "تحب", "الجر", "في", "حديق", "لكن", "كلب", "صغير", "يفضل", "نوم", "في", "سرير",
 			"بدل", "من", "الجر",
@@ -853,6 +865,16 @@ mod tests {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn test_stopwords() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class,dictionary('../tests/data/dictionary-zh.txt') FILTERS lowercase,stopwords('the','of','是','的')",
+			"The students of 北京大学 我们是北京大学的学生",
+			&["students", "北京大学", "我们", "北京大学", "学生"],
+		)
+		.await;
+	}
+
 	#[tokio::test]
 	async fn test_uppercase_tokens() {
 		test_analyzer_tokens(
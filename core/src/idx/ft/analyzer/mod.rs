@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::ft::analyzer::filter::FilteringStage;
-use crate::idx::ft::analyzer::tokenizer::{Tokenizer, Tokens};
+use crate::idx::ft::analyzer::tokenizer::{Segmenter, Tokenizer, Tokens};
 use crate::idx::ft::doclength::DocLength;
 use crate::idx::ft::offsets::{Offset, OffsetRecords};
 use crate::idx::ft::postings::TermFrequency;
@@ -17,6 +17,7 @@ use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+pub(in crate::idx) mod dictionary;
 mod filter;
 pub(in crate::idx) mod mapper;
 mod tokenizer;
@@ -24,11 +25,15 @@ mod tokenizer;
 #[derive(Clone)]
 pub(crate) struct Analyzer {
 	az: Arc<DefineAnalyzerStatement>,
+	segmenters: Arc<Vec<Segmenter>>,
 	filters: Arc<Option<Vec<Filter>>>,
 }
 
 pub(in crate::idx) type TermsList = Vec<Option<(TermId, TermLen)>>;
 
+/// The positions of the terms of a string
+pub(in crate::idx) type TermsPositions = HashMap<String, Vec<u32>>;
+
 pub(in crate::idx) struct TermsSet {
 	set: HashSet<TermId>,
 	has_unknown_terms: bool,
@@ -53,6 +58,7 @@ impl TermsSet {
 impl Analyzer {
 	pub(crate) fn new(ixs: &IndexStores, az: Arc<DefineAnalyzerStatement>) -> Result<Self, Error> {
 		Ok(Self {
+			segmenters: Arc::new(Segmenter::try_from(ixs, &az.tokenizers)?),
 			filters: Arc::new(Filter::try_from(ixs, &az.filters)?),
 			az,
 		})
@@ -125,6 +131,47 @@ impl Analyzer {
 		})
 	}
 
+	/// Extracts the terms of a query string, in their order of appearance
+	pub(in crate::idx) async fn extract_querying_sequence(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		content: String,
+	) -> Result<Vec<String>, Error> {
+		let tokens = self.generate_tokens(stk, ctx, opt, FilteringStage::Querying, content).await?;
+		tokens.list().iter().map(|t| tokens.get_token_string(t).map(str::to_owned)).collect()
+	}
+
+	/// Extracts the positions of the terms of each string of a value
+	pub(in crate::idx) async fn extract_terms_positions(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		content: Value,
+	) -> Result<Vec<TermsPositions>, Error> {
+		let mut tv = Vec::new();
+		self.analyze_value(stk, ctx, opt, content, FilteringStage::Indexing, &mut tv).await?;
+		let mut res = Vec::with_capacity(tv.len());
+		for tokens in tv {
+			let mut positions = TermsPositions::new();
+			let mut pos = 0;
+			let mut last = None;
+			for token in tokens.list() {
+				// The tokens generated from the same term (eg. ngrams) share its position
+				let start = token.get_start();
+				if last.is_some_and(|l| l != start) {
+					pos += 1;
+				}
+				last = Some(start);
+				positions.entry(tokens.get_token_string(token)?.to_owned()).or_default().push(pos);
+			}
+			res.push(positions);
+		}
+		Ok(res)
+	}
+
 	/// This method is used for indexing.
 	/// It will create new term ids for non already existing terms.
 	pub(super) async fn extract_terms_with_frequencies(
@@ -285,9 +332,9 @@ impl Analyzer {
 		}
 
 		let tokens = if let Some(t) = &self.az.tokenizers {
-			Tokenizer::tokenize(t, input)
+			Tokenizer::tokenize(t, &self.segmenters, input)?
 		} else {
-			Tokenizer::tokenize(&[], input)
+			Tokenizer::tokenize(&[], &[], input)?
 		};
 		Filter::apply_filters(tokens, &self.filters, stage)
 	}
@@ -329,6 +376,7 @@ mod tests {
 		let Some(Statement::Define(DefineStatement::Analyzer(az))) = stmt.0 .0.pop() else {
 			panic!()
 		};
+		ctx.get_index_stores().dictionaries().load(&az).await.unwrap();
 		let a = Analyzer::new(ctx.get_index_stores(), Arc::new(az)).unwrap();
 
 		let mut stack = reblessive::TreeStack::new();
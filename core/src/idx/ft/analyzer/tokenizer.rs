@@ -1,7 +1,9 @@
 use crate::err;
 use crate::err::Error;
+use crate::idx::ft::analyzer::dictionary::Dictionary;
 use crate::idx::ft::analyzer::filter::{Filter, FilterResult, Term};
 use crate::idx::ft::offsets::{Offset, Position};
+use crate::idx::trees::store::IndexStores;
 use crate::sql::tokenizer::Tokenizer as SqlTokenizer;
 use crate::sql::Value;
 
@@ -144,6 +146,20 @@ impl Token {
 		}
 	}
 
+	/// The start position of the original term this token comes from
+	pub(super) fn get_start(&self) -> Position {
+		match self {
+			Token::Ref {
+				chars,
+				..
+			} => chars.0,
+			Token::String {
+				chars,
+				..
+			} => chars.0,
+		}
+	}
+
 	pub(super) fn get_char_len(&self) -> u32 {
 		match self {
 			Token::Ref {
@@ -188,7 +204,7 @@ pub(super) struct Tokenizer {
 impl Tokenizer {
 	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
 		Self {
-			splitters: t.iter().map(|t| t.into()).collect(),
+			splitters: t.iter().filter_map(Splitter::new).collect(),
 		}
 	}
 
@@ -206,7 +222,11 @@ impl Tokenizer {
 		res
 	}
 
-	pub(super) fn tokenize(t: &[SqlTokenizer], i: String) -> Tokens {
+	pub(super) fn tokenize(
+		t: &[SqlTokenizer],
+		segmenters: &[Segmenter],
+		i: String,
+	) -> Result<Tokens, Error> {
 		let mut w = Tokenizer::new(t);
 		let mut last_char_pos = 0;
 		let mut last_byte_pos = 0;
@@ -245,9 +265,143 @@ impl Tokenizer {
 				len: current_char_pos - last_char_pos,
 			});
 		}
-		Tokens {
+		for s in segmenters {
+			let mut segmented = Vec::with_capacity(t.len());
+			for tk in t {
+				s.segment(&i, tk, &mut segmented)?;
+			}
+			t = segmented;
+		}
+		Ok(Tokens {
 			i,
 			t,
+		})
+	}
+}
+
+/// Splits the runs of Chinese, Japanese or Korean characters
+/// found in a token into smaller tokens.
+pub(super) enum Segmenter {
+	Ngram(usize),
+	Dictionary(Dictionary),
+}
+
+impl Segmenter {
+	pub(super) fn try_from(
+		ixs: &IndexStores,
+		t: &Option<Vec<SqlTokenizer>>,
+	) -> Result<Vec<Segmenter>, Error> {
+		let mut r = Vec::new();
+		if let Some(t) = t {
+			for t in t {
+				match t {
+					SqlTokenizer::Ngram(n) => r.push(Segmenter::Ngram((*n).max(1) as usize)),
+					SqlTokenizer::Dictionary(path) => {
+						r.push(Segmenter::Dictionary(ixs.dictionaries().get(path)?))
+					}
+					_ => {}
+				}
+			}
+		}
+		Ok(r)
+	}
+
+	fn is_cjk(c: char) -> bool {
+		matches!(c as u32,
+			0x1100..=0x11FF // Hangul Jamo
+			| 0x2E80..=0x2FDF // CJK and Kangxi radicals
+			| 0x3040..=0x30FF // Hiragana and Katakana
+			| 0x3100..=0x312F // Bopomofo
+			| 0x3130..=0x318F // Hangul compatibility Jamo
+			| 0x31F0..=0x31FF // Katakana phonetic extensions
+			| 0x3400..=0x4DBF // CJK unified ideographs extension A
+			| 0x4E00..=0x9FFF // CJK unified ideographs
+			| 0xAC00..=0xD7AF // Hangul syllables
+			| 0xF900..=0xFAFF // CJK compatibility ideographs
+			| 0x20000..=0x2FA1F // CJK unified ideographs extensions B to F
+		)
+	}
+
+	fn segment(&self, i: &str, tk: Token, tks: &mut Vec<Token>) -> Result<(), Error> {
+		let Token::Ref {
+			chars,
+			bytes,
+			..
+		} = tk
+		else {
+			tks.push(tk);
+			return Ok(());
+		};
+		// The char and byte positions of each character of the current run
+		let mut run = Vec::new();
+		let mut cjk = false;
+		let (mut char_pos, mut byte_pos) = (chars.0, bytes.0);
+		for c in tk.get_str(i)?.chars() {
+			let is_cjk = Self::is_cjk(c);
+			if is_cjk != cjk && !run.is_empty() {
+				self.split_run(i, cjk, &run, (char_pos, byte_pos), tks);
+				run.clear();
+			}
+			cjk = is_cjk;
+			run.push((char_pos, byte_pos));
+			char_pos += 1;
+			byte_pos += c.len_utf8() as Position;
+		}
+		if !run.is_empty() {
+			self.split_run(i, cjk, &run, (char_pos, byte_pos), tks);
+		}
+		Ok(())
+	}
+
+	fn split_run(
+		&self,
+		i: &str,
+		cjk: bool,
+		run: &[(Position, Position)],
+		end: (Position, Position),
+		tks: &mut Vec<Token>,
+	) {
+		let pos = |k: usize| run.get(k).copied().unwrap_or(end);
+		let mut push = |s: usize, e: usize| {
+			let (s, e) = (pos(s), pos(e));
+			tks.push(Token::Ref {
+				chars: (s.0, s.0, e.0),
+				bytes: (s.1, e.1),
+				len: e.0 - s.0,
+			});
+		};
+		let len = run.len();
+		if !cjk {
+			push(0, len);
+			return;
+		}
+		match self {
+			Segmenter::Ngram(n) => {
+				if len <= *n {
+					push(0, len);
+				} else {
+					for s in 0..=len - n {
+						push(s, s + n);
+					}
+				}
+			}
+			Segmenter::Dictionary(d) => {
+				// Forward maximum matching: at each position, take the
+				// longest word found in the dictionary, or a single character
+				let mut s = 0;
+				while s < len {
+					let mut e = s + 1;
+					for l in (2..=d.max_len().min(len - s)).rev() {
+						let word = &i[pos(s).1 as usize..pos(s + l).1 as usize];
+						if d.contains(word) {
+							e = s + l;
+							break;
+						}
+					}
+					push(s, e);
+					s = e;
+				}
+			}
 		}
 	}
 }
@@ -257,22 +411,25 @@ struct Splitter {
 	state: u8,
 }
 
-impl From<&SqlTokenizer> for Splitter {
-	fn from(t: &SqlTokenizer) -> Self {
-		Self {
-			t: t.clone(),
-			state: 0,
+impl Splitter {
+	/// Returns a splitter if the tokenizer splits the input character by character
+	fn new(t: &SqlTokenizer) -> Option<Self> {
+		match t {
+			SqlTokenizer::Ngram(_) | SqlTokenizer::Dictionary(_) => None,
+			t => Some(Self {
+				t: t.clone(),
+				state: 0,
+			}),
 		}
 	}
-}
 
-impl Splitter {
 	fn should_split(&mut self, c: char) -> bool {
 		match &self.t {
 			SqlTokenizer::Blank => self.blank_state(c),
 			SqlTokenizer::Camel => self.camel_state(c),
 			SqlTokenizer::Class => self.class_state(c),
 			SqlTokenizer::Punct => self.punct_state(c),
+			_ => false,
 		}
 	}
 
@@ -338,7 +495,8 @@ impl Splitter {
 
 #[cfg(test)]
 mod tests {
-	use crate::idx::ft::analyzer::tests::test_analyzer;
+	use crate::idx::ft::analyzer::tests::{test_analyzer, test_analyzer_tokens};
+	use crate::idx::ft::analyzer::tokenizer::Token;
 
 	#[tokio::test]
 	async fn test_tokenize_blank_class() {
@@ -400,4 +558,50 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_tokenize_ngram() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,ngram(2) FILTERS lowercase",
+			"我爱北京 Hello世界 日本語 한",
+			&["我爱", "爱北", "北京", "hello", "世界", "日本", "本語", "한"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_ngram_offsets() {
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS blank,ngram(2)",
+			"a北京大",
+			&[
+				Token::Ref {
+					chars: (0, 0, 1),
+					bytes: (0, 1),
+					len: 1,
+				},
+				Token::Ref {
+					chars: (1, 1, 3),
+					bytes: (1, 7),
+					len: 2,
+				},
+				Token::Ref {
+					chars: (2, 2, 4),
+					bytes: (4, 10),
+					len: 2,
+				},
+			],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_dictionary() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,dictionary('../tests/data/dictionary-zh.txt')",
+			"我们是北京大学的学生 SurrealDB",
+			&["我们", "是", "北京大学", "的", "学生", "SurrealDB"],
+		)
+		.await;
+	}
 }
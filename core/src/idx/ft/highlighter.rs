@@ -6,11 +6,15 @@ use std::collections::hash_map::Entry as HEntry;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+/// The default number of characters in a fragment returned by `search::fragments`
+const DEFAULT_FRAGMENT_SIZE: usize = 100;
+
 pub(crate) struct HighlightParams {
 	prefix: Value,
 	suffix: Value,
 	match_ref: Value,
 	partial: bool,
+	fragment_size: Option<usize>,
 }
 
 impl TryFrom<(Value, Value, Value, Option<Value>)> for HighlightParams {
@@ -25,11 +29,31 @@ impl TryFrom<(Value, Value, Value, Option<Value>)> for HighlightParams {
 			suffix,
 			match_ref,
 			partial,
+			fragment_size: None,
 		})
 	}
 }
 
 impl HighlightParams {
+	/// The parameters used by `search::fragments`, which returns
+	/// the highlighted passages surrounding the matches, instead
+	/// of the whole content of the field
+	pub(crate) fn fragments(
+		(prefix, suffix, match_ref, size): (Value, Value, Value, Option<Value>),
+	) -> Result<Self, Error> {
+		let size = match size {
+			Some(s) => s.coerce_to_u64()? as usize,
+			None => DEFAULT_FRAGMENT_SIZE,
+		};
+		Ok(Self {
+			prefix,
+			suffix,
+			match_ref,
+			partial: false,
+			fragment_size: Some(size.max(1)),
+		})
+	}
+
 	pub(crate) fn match_ref(&self) -> &Value {
 		&self.match_ref
 	}
@@ -40,6 +64,7 @@ pub(super) struct Highlighter {
 	suffix: Vec<char>,
 	fields: Vec<(Idiom, Value)>,
 	offseter: Offseter,
+	fragment_size: Option<usize>,
 }
 
 impl Highlighter {
@@ -53,6 +78,7 @@ impl Highlighter {
 			prefix,
 			suffix,
 			offseter: Offseter::new(hlp.partial),
+			fragment_size: hlp.fragment_size,
 		}
 	}
 
@@ -78,52 +104,118 @@ impl Highlighter {
 			_ => {}
 		}
 	}
+
+	/// Inserts the prefix and the suffix around each match.
+	/// The positions of the matches are shifted by `offset`,
+	/// the position of the first character of `v` in the original text.
+	fn markup<'a>(
+		&self,
+		mut v: Vec<char>,
+		matches: impl Iterator<Item = (&'a Position, &'a Position)>,
+		offset: usize,
+	) -> Result<String, Error> {
+		let mut l = v.len();
+		let mut d = 0;
+
+		// We use a closure to append the prefix and the suffix
+		let mut append = |s: u32, ix: &Vec<char>| -> Result<(), Error> {
+			let p = (s as usize).saturating_sub(offset) + d;
+			if p > l {
+				return Err(Error::HighlightError(format!("position overflow: {s} - len: {l}")));
+			}
+			v.splice(p..p, ix.clone());
+			let xl = ix.len();
+			d += xl;
+			l += xl;
+			Ok(())
+		};
+
+		for (s, e) in matches {
+			append(*s, &self.prefix)?;
+			append(*e, &self.suffix)?;
+		}
+
+		Ok(v.iter().collect())
+	}
+
+	/// Groups the matches into fragments of about `size` characters,
+	/// and returns the highlighted text of each fragment.
+	fn fragments(
+		&self,
+		v: &[char],
+		m: &BTreeMap<Position, Position>,
+		size: usize,
+	) -> Result<Vec<Value>, Error> {
+		// Group the matches which fit together in a single fragment
+		let mut groups: Vec<BTreeMap<Position, Position>> = vec![];
+		for (s, e) in m {
+			if let Some(g) = groups.last_mut() {
+				if let Some((first, _)) = g.first_key_value() {
+					if ((*e - *first) as usize) <= size {
+						g.insert(*s, *e);
+						continue;
+					}
+				}
+			}
+			groups.push(BTreeMap::from([(*s, *e)]));
+		}
+		let len = v.len();
+		let mut res = Vec::with_capacity(groups.len());
+		for g in groups {
+			let (Some((first, _)), Some(last)) = (g.first_key_value(), g.values().max()) else {
+				continue;
+			};
+			let (first, last) = (*first as usize, (*last as usize).min(len));
+			// Spread the remaining characters on both sides of the matches
+			let pad = size.saturating_sub(last - first) / 2;
+			let mut start = first.saturating_sub(pad);
+			let mut end = (last + pad).min(len);
+			// Avoid cutting words at the edges of the fragment
+			if start > 0 {
+				if let Some(p) = v[start..first].iter().position(|c| c.is_whitespace()) {
+					start += p + 1;
+				}
+			}
+			if end < len {
+				if let Some(p) = v[last..end].iter().rposition(|c| c.is_whitespace()) {
+					end = last + p;
+				}
+			}
+			res.push(self.markup(v[start..end].to_vec(), g.iter(), start)?.into());
+		}
+		Ok(res)
+	}
 }
 
 impl TryFrom<Highlighter> for Value {
 	type Error = Error;
 
-	fn try_from(hl: Highlighter) -> Result<Self, Error> {
+	fn try_from(mut hl: Highlighter) -> Result<Self, Error> {
 		if hl.fields.is_empty() {
 			return Ok(Self::None);
 		}
 		let mut vals = vec![];
-		for (_, f) in hl.fields {
+		for (_, f) in std::mem::take(&mut hl.fields) {
 			Highlighter::extract(f, &mut vals);
 		}
 		let mut res = Vec::with_capacity(vals.len());
 		for (idx, val) in vals.into_iter().enumerate() {
-			if let Some(m) = hl.offseter.offsets.get(&(idx as u32)) {
-				let mut v: Vec<char> = val.chars().collect();
-				let mut l = v.len();
-				let mut d = 0;
-
-				// We use a closure to append the prefix and the suffix
-				let mut append = |s: u32, ix: &Vec<char>| -> Result<(), Error> {
-					let p = (s as usize) + d;
-					if p > l {
-						return Err(Error::HighlightError(format!(
-							"position overflow: {s} - len: {l}"
-						)));
-					}
-					v.splice(p..p, ix.clone());
-					let xl = ix.len();
-					d += xl;
-					l += xl;
-					Ok(())
-				};
-
-				for (s, e) in m {
-					append(*s, &hl.prefix)?;
-					append(*e, &hl.suffix)?;
+			match (hl.offseter.offsets.get(&(idx as u32)), hl.fragment_size) {
+				(Some(m), Some(size)) => {
+					let v: Vec<char> = val.chars().collect();
+					res.extend(hl.fragments(&v, m, size)?);
 				}
-
-				let s: String = v.iter().collect();
-				res.push(Value::from(s));
-			} else {
-				res.push(Value::from(val));
+				(Some(m), None) => {
+					res.push(hl.markup(val.chars().collect(), m.iter(), 0)?.into());
+				}
+				// Fields without any match don't produce any fragment
+				(None, Some(_)) => {}
+				(None, None) => res.push(Value::from(val)),
 			}
 		}
+		if hl.fragment_size.is_some() {
+			return Ok(Value::from(res));
+		}
 		Ok(match res.len() {
 			0 => Value::None,
 			1 => res.remove(0),
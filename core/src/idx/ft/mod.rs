@@ -3,6 +3,7 @@ mod doclength;
 pub(crate) mod highlighter;
 mod offsets;
 mod postings;
+pub(in crate::idx) mod proximity;
 pub(super) mod scorer;
 pub(super) mod termdocs;
 pub(crate) mod terms;
@@ -26,7 +27,7 @@ use crate::kvs::Transaction;
 use crate::kvs::{Key, TransactionType};
 use crate::sql::index::SearchParams;
 use crate::sql::scoring::Scoring;
-use crate::sql::statements::DefineAnalyzerStatement;
+use crate::sql::statements::{DefineAnalyzerStatement, DefineIndexStatement};
 use crate::sql::{Idiom, Object, Thing, Value};
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -109,6 +110,7 @@ impl FtIndex {
 		let ixs = ctx.get_index_stores();
 		let az = tx.get_db_analyzer(opt.ns()?, opt.db()?, az).await?;
 		ixs.mappers().check(&az).await?;
+		ixs.dictionaries().check(&az).await?;
 		Self::with_analyzer(ixs, &tx, az, index_key_base, p, tt).await
 	}
 	async fn with_analyzer(
@@ -473,6 +475,41 @@ impl FtIndex {
 		Ok(Value::None)
 	}
 
+	/// Returns the terms indexed for a document, read from the term list of the document
+	pub(crate) async fn document_terms(
+		ctx: &Context,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+		p: &SearchParams,
+		thg: &Thing,
+	) -> Result<Vec<String>, Error> {
+		let tx = ctx.tx();
+		let ikb = IndexKeyBase::new(opt.ns()?, opt.db()?, ix)?;
+		let doc_ids = DocIds::new(
+			ctx.get_index_stores(),
+			&tx,
+			TransactionType::Read,
+			ikb.clone(),
+			p.doc_ids_order,
+			p.doc_ids_cache,
+		)
+		.await?;
+		let Some(doc_id) = doc_ids.get_doc_id(&tx, thg.into()).await? else {
+			return Ok(vec![]);
+		};
+		let Some(val) = tx.get(ikb.new_bk_key(doc_id), None).await? else {
+			return Ok(vec![]);
+		};
+		let term_ids = RoaringTreemap::deserialize_from(&mut val.as_slice())?;
+		let mut terms = Vec::with_capacity(term_ids.len() as usize);
+		for term_id in term_ids {
+			if let Some(term) = tx.get(ikb.new_bu_key(term_id), None).await? {
+				terms.push(String::from_utf8(term)?);
+			}
+		}
+		Ok(terms)
+	}
+
 	pub(crate) async fn statistics(&self, ctx: &Context) -> Result<FtStatistics, Error> {
 		let txn = ctx.tx();
 		let res = FtStatistics {
@@ -0,0 +1,215 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::idx::ft::analyzer::{Analyzer, TermsPositions};
+use crate::sql::Value;
+use reblessive::tree::Stk;
+
+/// A full-text query string, split into the terms matched as a bag of words,
+/// and the constraints on the positions of these terms.
+/// Eg. `"new york" pizza NEAR/3 cheap`
+pub(in crate::idx) struct QueryString {
+	/// The query string without the quotes and the proximity operators
+	pub(in crate::idx) terms: String,
+	constraints: Vec<Constraint<String>>,
+}
+
+enum Constraint<T> {
+	/// The terms must follow each other
+	Phrase(T),
+	/// The two sequences of terms must be at most `n` positions apart
+	Near(T, T, u32),
+}
+
+enum Item {
+	Text(String, bool),
+	Near(u32),
+}
+
+impl QueryString {
+	pub(in crate::idx) fn parse(qs: &str) -> Self {
+		// Split the query string into words, quoted phrases and proximity operators
+		let mut items = vec![];
+		let mut chars = qs.chars().peekable();
+		while let Some(c) = chars.next() {
+			if c.is_whitespace() {
+				continue;
+			}
+			if c == '"' {
+				let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+				items.push(Item::Text(phrase, true));
+				continue;
+			}
+			let mut word = c.to_string();
+			while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+				word.push(c);
+			}
+			match word.strip_prefix("NEAR/").map(str::parse::<u32>) {
+				Some(Ok(n)) => items.push(Item::Near(n)),
+				_ => items.push(Item::Text(word, false)),
+			}
+		}
+		// Collect the terms and the constraints
+		let mut terms = vec![];
+		let mut constraints = vec![];
+		for (i, item) in items.iter().enumerate() {
+			match item {
+				Item::Text(t, quoted) => {
+					terms.push(t.as_str());
+					if *quoted {
+						constraints.push(Constraint::Phrase(t.to_owned()));
+					}
+				}
+				Item::Near(n) => {
+					if let (Some(Item::Text(a, _)), Some(Item::Text(b, _))) =
+						(i.checked_sub(1).and_then(|i| items.get(i)), items.get(i + 1))
+					{
+						constraints.push(Constraint::Near(a.to_owned(), b.to_owned(), *n));
+					}
+				}
+			}
+		}
+		Self {
+			terms: terms.join(" "),
+			constraints,
+		}
+	}
+
+	/// Analyzes the terms of the constraints, the same way the query terms are analyzed
+	pub(in crate::idx) async fn proximities(
+		self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		az: &Analyzer,
+	) -> Result<Proximities, Error> {
+		let mut res = Vec::with_capacity(self.constraints.len());
+		for c in self.constraints {
+			match c {
+				Constraint::Phrase(t) => {
+					let t = az.extract_querying_sequence(stk, ctx, opt, t).await?;
+					if !t.is_empty() {
+						res.push(Constraint::Phrase(t));
+					}
+				}
+				Constraint::Near(a, b, n) => {
+					let a = az.extract_querying_sequence(stk, ctx, opt, a).await?;
+					let b = az.extract_querying_sequence(stk, ctx, opt, b).await?;
+					if !a.is_empty() && !b.is_empty() {
+						res.push(Constraint::Near(a, b, n));
+					}
+				}
+			}
+		}
+		Ok(Proximities(res))
+	}
+}
+
+/// The phrase and proximity constraints of a query
+pub(in crate::idx) struct Proximities(Vec<Constraint<Vec<String>>>);
+
+impl Proximities {
+	pub(in crate::idx) fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Checks that every constraint is satisfied by at least one string of the value
+	pub(in crate::idx) async fn matches(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		az: &Analyzer,
+		v: Value,
+	) -> Result<bool, Error> {
+		let values = az.extract_terms_positions(stk, ctx, opt, v).await?;
+		Ok(self.0.iter().all(|c| values.iter().any(|p| c.matches(p))))
+	}
+}
+
+impl Constraint<Vec<String>> {
+	fn matches(&self, positions: &TermsPositions) -> bool {
+		match self {
+			Self::Phrase(t) => !occurrences(t, positions).is_empty(),
+			Self::Near(a, b, n) => {
+				let oa = occurrences(a, positions);
+				let ob = occurrences(b, positions);
+				oa.iter().any(|pa| ob.iter().any(|pb| distance(*pa, a.len(), *pb, b.len()) <= *n))
+			}
+		}
+	}
+}
+
+/// Returns the starting positions of the occurrences of a sequence of terms
+fn occurrences(terms: &[String], positions: &TermsPositions) -> Vec<u32> {
+	let Some((first, rest)) = terms.split_first() else {
+		return vec![];
+	};
+	let Some(starts) = positions.get(first) else {
+		return vec![];
+	};
+	starts
+		.iter()
+		.copied()
+		.filter(|s| {
+			rest.iter()
+				.enumerate()
+				.all(|(i, t)| positions.get(t).is_some_and(|p| p.contains(&(s + 1 + i as u32))))
+		})
+		.collect()
+}
+
+/// The number of positions between the end of a sequence of terms and the start of the other
+fn distance(a: u32, a_len: usize, b: u32, b_len: usize) -> u32 {
+	if a <= b {
+		b.saturating_sub(a + a_len as u32 - 1)
+	} else {
+		a.saturating_sub(b + b_len as u32 - 1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{distance, occurrences, Constraint, QueryString};
+	use std::collections::HashMap;
+
+	#[test]
+	fn parse_query_string() {
+		let q = QueryString::parse(r#"  "new york" pizza NEAR/3 cheap NEAR/x "#);
+		assert_eq!(q.terms, "new york pizza cheap NEAR/x");
+		assert!(matches!(
+			q.constraints.as_slice(),
+			[Constraint::Phrase(p), Constraint::Near(a, b, 3)]
+				if p == "new york" && a == "pizza" && b == "cheap"
+		));
+	}
+
+	#[test]
+	fn parse_query_string_without_constraints() {
+		let q = QueryString::parse("hello world");
+		assert_eq!(q.terms, "hello world");
+		assert!(q.constraints.is_empty());
+	}
+
+	#[test]
+	fn find_occurrences() {
+		let positions = HashMap::from([
+			("the".to_string(), vec![0, 4]),
+			("quick".to_string(), vec![1]),
+			("fox".to_string(), vec![2, 5]),
+		]);
+		let t = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+		assert_eq!(occurrences(&t(&["the", "fox"]), &positions), vec![4]);
+		assert_eq!(occurrences(&t(&["quick", "fox"]), &positions), vec![1]);
+		assert!(occurrences(&t(&["fox", "quick"]), &positions).is_empty());
+		assert!(occurrences(&t(&["dog"]), &positions).is_empty());
+	}
+
+	#[test]
+	fn compute_distance() {
+		assert_eq!(distance(0, 1, 1, 1), 1);
+		assert_eq!(distance(3, 1, 0, 1), 3);
+		assert_eq!(distance(0, 2, 4, 1), 3);
+		assert_eq!(distance(2, 1, 0, 3), 0);
+	}
+}
@@ -4,6 +4,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::spatial::SpatialIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
@@ -51,6 +52,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Search(p) => self.index_full_text(stk, p).await,
 			Index::MTree(p) => self.index_mtree(stk, p).await,
 			Index::Hnsw(p) => self.index_hnsw(p).await,
+			Index::Spatial => self.index_spatial().await,
 		}
 	}
 
@@ -199,6 +201,20 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_spatial(&mut self) -> Result<(), Error> {
+		let txn = self.ctx.tx();
+		let si = SpatialIndex::new(self.opt.ns()?, self.opt.db()?, self.ix);
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			si.remove_document(&txn, self.rid, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			si.index_document(&txn, self.rid, &n).await?;
+		}
+		Ok(())
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
@@ -2,6 +2,7 @@ pub mod docids;
 pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
+pub(crate) mod spatial;
 pub mod trees;
 
 use crate::err::Error;
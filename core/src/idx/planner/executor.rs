@@ -5,6 +5,7 @@ use crate::err::Error;
 use crate::idx::docids::DocIds;
 use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
 use crate::idx::ft::highlighter::HighlightParams;
+use crate::idx::ft::proximity::{Proximities, QueryString};
 use crate::idx::ft::scorer::BM25Scorer;
 use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::terms::Terms;
@@ -13,8 +14,8 @@ use crate::idx::planner::checker::{HnswConditionChecker, MTreeConditionChecker};
 use crate::idx::planner::iterators::{
 	IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
 	IndexUnionThingIterator, IteratorRange, IteratorRecord, IteratorRef, KnnIterator,
-	KnnIteratorResult, MatchesThingIterator, MultipleIterators, ThingIterator,
-	UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
+	KnnIteratorResult, MatchesThingIterator, MultipleIterators, SpatialThingIterator,
+	ThingIterator, UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
 	UniqueUnionThingIterator, ValueType,
 };
 use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
@@ -28,7 +29,7 @@ use crate::idx::IndexKeyBase;
 use crate::kvs::{Key, TransactionType};
 use crate::sql::index::{Distance, Index};
 use crate::sql::statements::DefineIndexStatement;
-use crate::sql::{Cond, Expression, Idiom, Number, Object, Table, Thing, Value};
+use crate::sql::{Array, Cond, Expression, Idiom, Number, Object, Table, Thing, Value};
 use num_traits::{FromPrimitive, ToPrimitive};
 use reblessive::tree::Stk;
 use rust_decimal::Decimal;
@@ -325,6 +326,10 @@ impl QueryExecutor {
 
 	/// Returns `true` if the expression is matching the current iterator.
 	pub(crate) fn is_iterator_expression(&self, irf: IteratorRef, exp: &Expression) -> bool {
+		// The phrases and proximities are not checked by the iterator
+		if self.0.exp_entries.get(exp).is_some_and(|ft| !ft.0.proximities.is_empty()) {
+			return false;
+		}
 		match self.0.it_entries.get(irf as usize) {
 			Some(IteratorEntry::Single(Some(e), ..)) => exp.eq(e.as_ref()),
 			Some(IteratorEntry::Range(es, ..)) => es.contains(exp),
@@ -380,6 +385,7 @@ impl QueryExecutor {
 				} => self.new_search_index_iterator(irf, io.clone()).await,
 				Index::MTree(_) => Ok(self.new_mtree_index_knn_iterator(irf)),
 				Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+				Index::Spatial => Ok(Self::new_spatial_index_iterator(opt, irf, ix, io)?),
 			}
 		} else {
 			Ok(None)
@@ -406,6 +412,7 @@ impl QueryExecutor {
 					Some(Self::new_index_equal_iterator(irf, opt, &ix, value)?)
 				}
 			}
+			IndexOperator::Prefix(a) => Some(Self::new_index_prefix_iterator(irf, opt, &ix, a)?),
 			IndexOperator::Union(value) => Some(ThingIterator::IndexUnion(
 				IndexUnionThingIterator::new(irf, opt.ns()?, opt.db()?, &ix, value),
 			)),
@@ -449,6 +456,49 @@ impl QueryExecutor {
 		Ok(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators))))
 	}
 
+	/// Builds the iterators scanning the leading columns of a composite index.
+	/// A numeric value is matched by each of its number variants.
+	fn new_index_prefix_iterator(
+		irf: IteratorRef,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+		a: &Array,
+	) -> Result<ThingIterator, Error> {
+		let mut prefixes = vec![vec![]];
+		for v in a.iter() {
+			let variants = match v {
+				Value::Number(n) => Self::get_equal_number_variants(n),
+				v => vec![v.clone()],
+			};
+			prefixes = prefixes
+				.into_iter()
+				.flat_map(|p: Vec<Value>| {
+					variants.iter().map(move |v| {
+						let mut p = p.clone();
+						p.push(v.clone());
+						p
+					})
+				})
+				.collect();
+		}
+		let mut iterators = VecDeque::with_capacity(prefixes.len());
+		for p in prefixes {
+			iterators.push_back(ThingIterator::IndexEqual(IndexEqualThingIterator::prefix(
+				irf,
+				opt.ns()?,
+				opt.db()?,
+				ix,
+				&Array::from(p),
+			)));
+		}
+		if iterators.len() == 1 {
+			if let Some(it) = iterators.pop_front() {
+				return Ok(it);
+			}
+		}
+		Ok(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators))))
+	}
+
 	/// This function takes a reference to a `Number` enum and a conversion function `float_to_int`.
 	/// It returns a tuple containing the variants of the `Number` as `Option<i64>`, `Option<f64>`, and `Option<Decimal>`.
 	///
@@ -722,6 +772,11 @@ impl QueryExecutor {
 		Ok(None)
 	}
 
+	/// Check if a range is scanned with a single iterator, in index order
+	pub(super) fn is_ordered_range(from: &RangeValue, to: &RangeValue) -> bool {
+		Self::get_ranges_variants(from, to).map_or(true, |r| r.len() == 1)
+	}
+
 	fn get_ranges_variants<'a>(
 		from: &'a RangeValue,
 		to: &'a RangeValue,
@@ -753,6 +808,7 @@ impl QueryExecutor {
 			&ix.what,
 			&ix.name,
 			range,
+			ix.cols.len() > 1,
 		)))
 	}
 
@@ -762,6 +818,10 @@ impl QueryExecutor {
 		ix: &DefineIndexStatement,
 		range: &IteratorRange<'_>,
 	) -> Result<ThingIterator, Error> {
+		if ix.cols.len() > 1 {
+			// The records of a composite unique index are scanned as a standard index
+			return Self::new_index_range_iterator(ir, opt, ix, range);
+		}
 		Ok(ThingIterator::UniqueRange(UniqueRangeThingIterator::new(
 			ir,
 			opt.ns()?,
@@ -818,6 +878,11 @@ impl QueryExecutor {
 					Some(Self::new_unique_equal_iterator(irf, opt, ix, value)?)
 				}
 			}
+			IndexOperator::Prefix(a) => Some(Self::new_index_prefix_iterator(irf, opt, ix, a)?),
+			// The records of a composite unique index are scanned as a standard index
+			IndexOperator::Union(value) if ix.cols.len() > 1 => Some(ThingIterator::IndexUnion(
+				IndexUnionThingIterator::new(irf, opt.ns()?, opt.db()?, ix, value),
+			)),
 			IndexOperator::Union(value) => Some(ThingIterator::UniqueUnion(
 				UniqueUnionThingIterator::new(irf, opt, ix, value)?,
 			)),
@@ -914,6 +979,19 @@ impl QueryExecutor {
 		None
 	}
 
+	fn new_spatial_index_iterator(
+		opt: &Options,
+		irf: IteratorRef,
+		ix: &DefineIndexStatement,
+		io: &IndexOption,
+	) -> Result<Option<ThingIterator>, Error> {
+		if let IndexOperator::Intersects(value) = io.op() {
+			let it = SpatialThingIterator::new(irf, opt.ns()?, opt.db()?, ix, value);
+			return Ok(Some(ThingIterator::Spatial(it)));
+		}
+		Ok(None)
+	}
+
 	async fn build_iterators(
 		&self,
 		opt: &Options,
@@ -945,12 +1023,23 @@ impl QueryExecutor {
 		r: Value,
 	) -> Result<bool, Error> {
 		if let Some(ft) = self.0.exp_entries.get(exp) {
-			if let Some(ix_def) = self.get_index_def(ft.0.index_option.ix_ref()) {
-				if self.0.table.eq(&ix_def.what.0) {
-					return self.matches_with_doc_id(ctx, thg, ft).await;
+			// The value of the indexed idiom, and the value on the other side of the operator
+			let (v, o) = match ft.0.index_option.id_pos() {
+				IdiomPosition::Left => (l, r),
+				IdiomPosition::Right => (r, l),
+				IdiomPosition::None => return Ok(false),
+			};
+			let matches = match self.get_index_def(ft.0.index_option.ix_ref()) {
+				Some(ix_def) if self.0.table.eq(&ix_def.what.0) => {
+					self.matches_with_doc_id(ctx, thg, ft).await?
 				}
+				_ => self.matches_with_value(stk, ctx, opt, ft, o).await?,
+			};
+			// The phrases and proximities are checked on the positions of the terms in the value
+			if !matches || ft.0.proximities.is_empty() {
+				return Ok(matches);
 			}
-			return self.matches_with_value(stk, ctx, opt, ft, l, r).await;
+			return ft.0.proximities.matches(stk, ctx, opt, &ft.0.analyzer, v).await;
 		}
 
 		// If no previous case were successful, we end up with a user error
@@ -997,8 +1086,7 @@ impl QueryExecutor {
 		ctx: &Context,
 		opt: &Options,
 		ft: &FtEntry,
-		l: Value,
-		r: Value,
+		v: Value,
 	) -> Result<bool, Error> {
 		// If the query terms contains terms that are unknown in the index
 		// of if there are no terms in the query
@@ -1006,11 +1094,6 @@ impl QueryExecutor {
 		if !ft.0.query_terms_set.is_matchable() {
 			return Ok(false);
 		}
-		let v = match ft.0.index_option.id_pos() {
-			IdiomPosition::Left => r,
-			IdiomPosition::Right => l,
-			IdiomPosition::None => return Ok(false),
-		};
 		let terms = ft.0.terms.read().await;
 		// Extract the terms set from the record
 		let t = ft.0.analyzer.extract_indexing_terms(stk, ctx, opt, &terms, v).await?;
@@ -1109,6 +1192,7 @@ struct Inner {
 	analyzer: Analyzer,
 	query_terms_set: TermsSet,
 	query_terms_list: TermsList,
+	proximities: Proximities,
 	terms: Arc<RwLock<Terms>>,
 	terms_docs: TermsDocs,
 	scorer: Option<BM25Scorer>,
@@ -1123,8 +1207,10 @@ impl FtEntry {
 		io: IndexOption,
 	) -> Result<Option<Self>, Error> {
 		if let Matches(qs, _) = io.op() {
+			let qs = QueryString::parse(qs);
 			let (terms_list, terms_set) =
-				ft.extract_querying_terms(stk, ctx, opt, qs.to_owned()).await?;
+				ft.extract_querying_terms(stk, ctx, opt, qs.terms.clone()).await?;
+			let proximities = qs.proximities(stk, ctx, opt, &ft.analyzer()).await?;
 			let tx = ctx.tx();
 			let terms_docs = Arc::new(ft.get_terms_docs(&tx, &terms_list).await?);
 			drop(tx);
@@ -1134,6 +1220,7 @@ impl FtEntry {
 				analyzer: ft.analyzer(),
 				query_terms_set: terms_set,
 				query_terms_list: terms_list,
+				proximities,
 				scorer: ft.new_scorer(terms_docs.clone())?,
 				terms: ft.terms(),
 				terms_docs,
@@ -5,6 +5,8 @@ use crate::idx::docids::DocId;
 use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::{FtIndex, HitsIterator};
 use crate::idx::planner::plan::RangeValue;
+use crate::idx::spatial;
+use crate::key::index::gc::Gc;
 use crate::key::index::Index;
 use crate::kvs::Key;
 use crate::kvs::Transaction;
@@ -115,6 +117,7 @@ pub(crate) enum ThingIterator {
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	Matches(MatchesThingIterator),
 	Knn(KnnIterator),
+	Spatial(SpatialThingIterator),
 	Multiples(Box<MultipleIterators>),
 }
 
@@ -134,6 +137,7 @@ impl ThingIterator {
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::Matches(i) => i.next_batch(ctx, txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
+			Self::Spatial(i) => i.next_batch(txn, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::Multiples(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
@@ -176,6 +180,32 @@ impl IndexEqualThingIterator {
 		}
 	}
 
+	/// Scans the records matching the values of the leading columns of a composite index
+	pub(super) fn prefix(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		a: &Array,
+	) -> Self {
+		let (beg, end) = if a.len() < ix.cols.len() {
+			(
+				Index::prefix_ids_composite_beg(ns, db, &ix.what, &ix.name, a),
+				Index::prefix_ids_composite_end(ns, db, &ix.what, &ix.name, a),
+			)
+		} else {
+			(
+				Index::prefix_ids_beg(ns, db, &ix.what, &ix.name, a),
+				Index::prefix_ids_end(ns, db, &ix.what, &ix.name, a),
+			)
+		};
+		Self {
+			irf,
+			beg,
+			end,
+		}
+	}
+
 	async fn next_scan<B: IteratorBatch>(
 		tx: &Transaction,
 		irf: IteratorRef,
@@ -205,6 +235,61 @@ impl IndexEqualThingIterator {
 	}
 }
 
+/// Scans the records of a spatial index stored in the cells covering a geometry,
+/// and in the cells enclosing them
+pub(crate) struct SpatialThingIterator {
+	irf: IteratorRef,
+	ranges: VecDeque<(Vec<u8>, Vec<u8>)>,
+}
+
+impl SpatialThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		v: &Value,
+	) -> Self {
+		let cells = match v {
+			Value::Geometry(g) => spatial::covering(g),
+			_ => vec![],
+		};
+		let mut ranges = VecDeque::new();
+		for c in &cells {
+			ranges.push_back((
+				Gc::prefix_cells_beg(ns, db, &ix.what, &ix.name, c),
+				Gc::prefix_cells_end(ns, db, &ix.what, &ix.name, c),
+			));
+		}
+		for c in spatial::ancestors(&cells) {
+			ranges.push_back((
+				Gc::prefix_cell_beg(ns, db, &ix.what, &ix.name, &c),
+				Gc::prefix_cell_end(ns, db, &ix.what, &ix.name, &c),
+			));
+		}
+		Self {
+			irf,
+			ranges,
+		}
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B, Error> {
+		while let Some((beg, end)) = self.ranges.front_mut() {
+			let records: B =
+				IndexEqualThingIterator::next_scan(tx, self.irf, beg, end, limit).await?;
+			if !records.is_empty() {
+				return Ok(records);
+			}
+			self.ranges.pop_front();
+		}
+		Ok(B::empty())
+	}
+}
+
 struct RangeScan {
 	beg: Vec<u8>,
 	end: Vec<u8>,
@@ -284,57 +369,57 @@ pub(super) enum ValueType {
 }
 
 impl ValueType {
-	fn prefix_beg(&self, ns: &str, db: &str, ix_what: &Ident, ix_name: &Ident) -> Vec<u8> {
+	/// The lowest value of the type, if the type restricts the range
+	fn min(&self) -> Option<Value> {
 		match self {
-			Self::None => Index::prefix_beg(ns, db, ix_what, ix_name),
-			Self::NumberInt => Index::prefix_ids_beg(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Int(i64::MIN))]),
-			),
-			Self::NumberFloat => Index::prefix_ids_beg(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Float(f64::MIN))]),
-			),
-			Self::NumberDecimal => Index::prefix_ids_beg(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Decimal(Decimal::MIN))]),
-			),
-		}
-	}
-
-	fn prefix_end(&self, ns: &str, db: &str, ix_what: &Ident, ix_name: &Ident) -> Vec<u8> {
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MIN).into()),
+			Self::NumberFloat => Some(Number::Float(f64::MIN).into()),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MIN).into()),
+		}
+	}
+
+	/// The highest value of the type, if the type restricts the range
+	fn max(&self) -> Option<Value> {
 		match self {
-			Self::None => Index::prefix_end(ns, db, ix_what, ix_name),
-			Self::NumberInt => Index::prefix_ids_end(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Int(i64::MAX))]),
-			),
-			Self::NumberFloat => Index::prefix_ids_end(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Float(f64::MAX))]),
-			),
-			Self::NumberDecimal => Index::prefix_ids_end(
-				ns,
-				db,
-				ix_what,
-				ix_name,
-				&Array(vec![Value::Number(Number::Decimal(Decimal::MAX))]),
-			),
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MAX).into()),
+			Self::NumberFloat => Some(Number::Float(f64::MAX).into()),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MAX).into()),
+		}
+	}
+
+	fn prefix_beg(
+		&self,
+		ns: &str,
+		db: &str,
+		ix_what: &Ident,
+		ix_name: &Ident,
+		composite: bool,
+	) -> Vec<u8> {
+		match self.min() {
+			None => Index::prefix_beg(ns, db, ix_what, ix_name),
+			Some(v) if composite => {
+				Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &Array(vec![v]))
+			}
+			Some(v) => Index::prefix_ids_beg(ns, db, ix_what, ix_name, &Array(vec![v])),
+		}
+	}
+
+	fn prefix_end(
+		&self,
+		ns: &str,
+		db: &str,
+		ix_what: &Ident,
+		ix_name: &Ident,
+		composite: bool,
+	) -> Vec<u8> {
+		match self.max() {
+			None => Index::prefix_end(ns, db, ix_what, ix_name),
+			Some(v) if composite => {
+				Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &Array(vec![v]))
+			}
+			Some(v) => Index::prefix_ids_end(ns, db, ix_what, ix_name, &Array(vec![v])),
 		}
 	}
 }
@@ -345,6 +430,8 @@ pub(crate) struct IndexRangeThingIterator {
 }
 
 impl IndexRangeThingIterator {
+	/// Scans a range of values of the first column of the index.
+	/// When the index is composite, the range covers every value of the following columns.
 	pub(super) fn new(
 		irf: IteratorRef,
 		ns: &str,
@@ -352,9 +439,12 @@ impl IndexRangeThingIterator {
 		ix_what: &Ident,
 		ix_name: &Ident,
 		range: &IteratorRange<'_>,
+		composite: bool,
 	) -> Self {
-		let beg = Self::compute_beg(ns, db, ix_what, ix_name, &range.from, range.value_type);
-		let end = Self::compute_end(ns, db, ix_what, ix_name, &range.to, range.value_type);
+		let beg =
+			Self::compute_beg(ns, db, ix_what, ix_name, &range.from, range.value_type, composite);
+		let end =
+			Self::compute_end(ns, db, ix_what, ix_name, &range.to, range.value_type, composite);
 		Self {
 			irf,
 			r: RangeScan::new(beg, range.from.inclusive, end, range.to.inclusive),
@@ -377,7 +467,7 @@ impl IndexRangeThingIterator {
 			from: Cow::Borrowed(&full_range),
 			to: Cow::Borrowed(&full_range),
 		};
-		Self::new(irf, ns, db, ix_what, ix_name, &range)
+		Self::new(irf, ns, db, ix_what, ix_name, &range, false)
 	}
 
 	fn compute_beg(
@@ -387,15 +477,17 @@ impl IndexRangeThingIterator {
 		ix_name: &Ident,
 		from: &RangeValue,
 		value_type: ValueType,
+		composite: bool,
 	) -> Vec<u8> {
 		if from.value == Value::None {
-			return value_type.prefix_beg(ns, db, ix_what, ix_name);
+			return value_type.prefix_beg(ns, db, ix_what, ix_name, composite);
 		}
 		let fd = Array::from(from.value.to_owned());
-		if from.inclusive {
-			Index::prefix_ids_beg(ns, db, ix_what, ix_name, &fd)
-		} else {
-			Index::prefix_ids_end(ns, db, ix_what, ix_name, &fd)
+		match (from.inclusive, composite) {
+			(true, false) => Index::prefix_ids_beg(ns, db, ix_what, ix_name, &fd),
+			(false, false) => Index::prefix_ids_end(ns, db, ix_what, ix_name, &fd),
+			(true, true) => Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd),
+			(false, true) => Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd),
 		}
 	}
 
@@ -406,15 +498,17 @@ impl IndexRangeThingIterator {
 		ix_name: &Ident,
 		to: &RangeValue,
 		value_type: ValueType,
+		composite: bool,
 	) -> Vec<u8> {
 		if to.value == Value::None {
-			return value_type.prefix_end(ns, db, ix_what, ix_name);
+			return value_type.prefix_end(ns, db, ix_what, ix_name, composite);
 		}
 		let fd = Array::from(to.value.to_owned());
-		if to.inclusive {
-			Index::prefix_ids_end(ns, db, ix_what, ix_name, &fd)
-		} else {
-			Index::prefix_ids_beg(ns, db, ix_what, ix_name, &fd)
+		match (to.inclusive, composite) {
+			(true, false) => Index::prefix_ids_end(ns, db, ix_what, ix_name, &fd),
+			(false, false) => Index::prefix_ids_beg(ns, db, ix_what, ix_name, &fd),
+			(true, true) => Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd),
+			(false, true) => Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd),
 		}
 	}
 
@@ -457,9 +551,17 @@ impl IndexUnionThingIterator {
 			a.0.iter()
 				.map(|v| {
 					let a = Array::from(v.clone());
-					let beg = Index::prefix_ids_beg(ns, db, &ix.what, &ix.name, &a);
-					let end = Index::prefix_ids_end(ns, db, &ix.what, &ix.name, &a);
-					(beg, end)
+					if ix.cols.len() > 1 {
+						(
+							Index::prefix_ids_composite_beg(ns, db, &ix.what, &ix.name, &a),
+							Index::prefix_ids_composite_end(ns, db, &ix.what, &ix.name, &a),
+						)
+					} else {
+						(
+							Index::prefix_ids_beg(ns, db, &ix.what, &ix.name, &a),
+							Index::prefix_ids_end(ns, db, &ix.what, &ix.name, &a),
+						)
+					}
 				})
 				.collect()
 		} else {
@@ -707,7 +809,7 @@ impl UniqueRangeThingIterator {
 		value_type: ValueType,
 	) -> Vec<u8> {
 		if from.value == Value::None {
-			return value_type.prefix_beg(ns, db, ix_what, ix_name);
+			return value_type.prefix_beg(ns, db, ix_what, ix_name, false);
 		}
 		Index::new(ns, db, ix_what, ix_name, &Array::from(from.value.to_owned()), None)
 			.encode()
@@ -723,7 +825,7 @@ impl UniqueRangeThingIterator {
 		value_type: ValueType,
 	) -> Vec<u8> {
 		if to.value == Value::None {
-			return value_type.prefix_end(ns, db, ix_what, ix_name);
+			return value_type.prefix_end(ns, db, ix_what, ix_name, false);
 		}
 		Index::new(ns, db, ix_what, ix_name, &Array::from(to.value.to_owned()), None)
 			.encode()
@@ -4,6 +4,7 @@ pub(crate) mod iterators;
 pub(in crate::idx) mod knn;
 pub(crate) mod plan;
 pub(in crate::idx) mod rewriter;
+pub(crate) mod statistics;
 pub(in crate::idx) mod tree;
 
 use crate::ctx::Context;
@@ -13,13 +14,19 @@ use crate::idx::planner::executor::{InnerQueryExecutor, IteratorEntry, QueryExec
 use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
 use crate::idx::planner::plan::{Plan, PlanBuilder};
+use crate::idx::planner::statistics::{PlanStatistics, TableStatistics};
 use crate::idx::planner::tree::Tree;
-use crate::sql::statements::SelectStatement;
+use crate::sql::index::Distance;
+use crate::sql::statements::{DefineIndexStatement, SelectStatement};
 use crate::sql::with::With;
-use crate::sql::{order::Ordering, Cond, Fields, Groups, Table};
+use crate::sql::{
+	order::Ordering, Cond, Expression, Field, Fields, Function, Groups, Id, Idiom, Index, Limit,
+	Operator, Start, Subquery, Table, Thing, Value,
+};
 use reblessive::tree::Stk;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{self, AtomicU8};
+use std::sync::Arc;
 
 pub(crate) struct QueryPlannerParams<'a> {
 	fields: &'a Fields,
@@ -27,6 +34,8 @@ pub(crate) struct QueryPlannerParams<'a> {
 	order: Option<&'a Ordering>,
 	cond: Option<&'a Cond>,
 	group: Option<&'a Groups>,
+	limit: Option<&'a Limit>,
+	start: Option<&'a Start>,
 }
 
 impl<'a> QueryPlannerParams<'a> {
@@ -64,6 +73,8 @@ impl<'a> From<&'a SelectStatement> for QueryPlannerParams<'a> {
 			order: stmt.order.as_ref(),
 			cond: stmt.cond.as_ref(),
 			group: stmt.group.as_ref(),
+			limit: stmt.limit.as_ref(),
+			start: stmt.start.as_ref(),
 		}
 	}
 }
@@ -76,6 +87,8 @@ pub(crate) struct QueryPlanner {
 	iteration_workflow: Vec<IterationStage>,
 	iteration_index: AtomicU8,
 	orders: Vec<IteratorRef>,
+	/// The indexes hinted with WITH INDEX which exist on the planned tables
+	hinted_indexes: Option<HashSet<String>>,
 }
 
 impl QueryPlanner {
@@ -87,6 +100,7 @@ impl QueryPlanner {
 			iteration_workflow: Vec::default(),
 			iteration_index: AtomicU8::new(0),
 			orders: vec![],
+			hinted_indexes: None,
 		}
 	}
 
@@ -100,12 +114,35 @@ impl QueryPlanner {
 		it: &mut Iterator,
 	) -> Result<(), Error> {
 		let mut is_table_iterator = false;
+		// Record which of the hinted indexes exist on this table
+		if let Some(With::Index(ixs)) = params.with {
+			let indexes = ctx.tx().all_tb_indexes(opt.ns()?, opt.db()?, &t).await?;
+			let hinted = self.hinted_indexes.get_or_insert_with(HashSet::new);
+			for ix in indexes.iter() {
+				if ixs.contains(&ix.name.0) {
+					hinted.insert(ix.name.0.clone());
+				}
+			}
+		}
+
+		// ORDER BY a vector distance with a LIMIT can be answered by an HNSW index
+		let ann_cond = Self::order_ann_condition(stk, ctx, opt, &t, params).await?;
+		let cond = ann_cond.as_ref().or(params.cond);
+
+		let mut tree = Tree::build(stk, ctx, opt, &t, cond, params.with, params.order).await?;
 
-		let mut tree =
-			Tree::build(stk, ctx, opt, &t, params.cond, params.with, params.order).await?;
+		// Estimate the cardinality of the index options if the table has been analyzed,
+		// unless the indexes to use are given explicitly
+		let statistics = match params.with {
+			Some(With::Index(_)) => None,
+			_ => TableStatistics::get(&ctx.tx(), opt.ns()?, opt.db()?, &t)
+				.await?
+				.map(|s| PlanStatistics::new(s, &tree.index_map.definitions)),
+		};
 
 		let is_knn = !tree.knn_expressions.is_empty();
 		let order = tree.index_map.order_limit.take();
+		let order_ix = order.as_ref().map(|o| o.ix_ref());
 		let mut exe = InnerQueryExecutor::new(
 			stk,
 			ctx,
@@ -117,7 +154,7 @@ impl QueryPlanner {
 			tree.knn_condition,
 		)
 		.await?;
-		match PlanBuilder::build(
+		let plan = PlanBuilder::build(
 			tree.root,
 			params,
 			tree.with_indexes,
@@ -125,7 +162,23 @@ impl QueryPlanner {
 			tree.all_and_groups,
 			tree.all_and,
 			tree.all_expressions_with_index,
-		)? {
+			statistics.as_ref(),
+		)?;
+		// A table scan restricted to a list of record ids can fetch those records directly
+		let plan = match plan {
+			Plan::TableIterator(..)
+				if !is_knn
+					&& opt.version.is_none()
+					&& !matches!(params.with, Some(With::NoIndex)) =>
+			{
+				match Self::record_ids(stk, ctx, opt, &t, params.cond).await? {
+					Some(ids) => Plan::RecordIds(ids),
+					None => plan,
+				}
+			}
+			plan => plan,
+		};
+		match plan {
 			Plan::SingleIndex(exp, io) => {
 				if io.require_distinct() {
 					self.requires_distinct = true;
@@ -152,8 +205,14 @@ impl QueryPlanner {
 				self.add(t.clone(), None, exe, it);
 			}
 			Plan::SingleIndexRange(ixn, rq) => {
+				// A single range scan on the ordering index returns records in order
+				let is_order =
+					order_ix == Some(ixn) && QueryExecutor::is_ordered_range(&rq.from, &rq.to);
 				let ir = exe.add_iterator(IteratorEntry::Range(rq.exps, ixn, rq.from, rq.to));
 				self.add(t.clone(), Some(ir), exe, it);
+				if is_order {
+					self.orders.push(ir);
+				}
 			}
 			Plan::TableIterator(reason, keys_only) => {
 				if let Some(reason) = reason {
@@ -163,6 +222,10 @@ impl QueryPlanner {
 				it.ingest(Iterable::Table(t, keys_only));
 				is_table_iterator = true;
 			}
+			Plan::RecordIds(ids) => {
+				self.add(t.clone(), None, exe, it);
+				it.ingest(Iterable::Records(t, ids));
+			}
 		}
 		if is_knn && is_table_iterator {
 			self.iteration_workflow = vec![IterationStage::CollectKnn, IterationStage::BuildKnn];
@@ -184,6 +247,227 @@ impl QueryPlanner {
 			it.ingest(Iterable::Index(tb, irf));
 		}
 	}
+	/// Extracts the record ids of this table from an `id IN $ids` or `id = $id`
+	/// predicate in the top-level conjunctions of the condition, if any.
+	async fn record_ids(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		t: &Table,
+		cond: Option<&Cond>,
+	) -> Result<Option<Vec<Thing>>, Error> {
+		let Some(cond) = cond else {
+			return Ok(None);
+		};
+		let mut conds = vec![&cond.0];
+		while let Some(v) = conds.pop() {
+			let Value::Expression(e) = v else {
+				continue;
+			};
+			let Expression::Binary {
+				l,
+				o,
+				r,
+			} = e.as_ref()
+			else {
+				continue;
+			};
+			let is_id = |v: &Value| matches!(v, Value::Idiom(i) if i.is_id());
+			let ids = match o {
+				Operator::And => {
+					conds.push(l);
+					conds.push(r);
+					continue;
+				}
+				Operator::Inside | Operator::Equal | Operator::Exact if is_id(l) => r,
+				Operator::Contain | Operator::Equal | Operator::Exact if is_id(r) => l,
+				_ => continue,
+			};
+			// The list must not depend on the current document
+			if !ids.is_static()
+				&& !matches!(ids, Value::Param(p) if !matches!(p.as_str(), "this" | "parent"))
+			{
+				continue;
+			}
+			let ids = match ids.compute(stk, ctx, opt, None).await? {
+				Value::Thing(v) => vec![v],
+				Value::Array(a) => {
+					let mut ids = Vec::with_capacity(a.len());
+					for v in a {
+						match v {
+							Value::Thing(v) => ids.push(v),
+							// Other values never match a record id
+							_ => continue,
+						}
+					}
+					ids
+				}
+				_ => continue,
+			};
+			// Only plain ids of this table can be fetched directly
+			let mut ids: Vec<Thing> = ids
+				.into_iter()
+				.filter(|v| {
+					v.tb == t.0 && matches!(v.id, Id::Number(_) | Id::String(_) | Id::Uuid(_))
+				})
+				.collect();
+			ids.sort();
+			ids.dedup();
+			return Ok(Some(ids));
+		}
+		Ok(None)
+	}
+
+	/// Rewrites `ORDER BY <distance alias> LIMIT k` into an approximate nearest neighbour
+	/// condition (`<|k,ef|>`) when the alias is a `vector::distance::*` function over a field
+	/// indexed by an HNSW index using the same distance. The rewritten condition is only used
+	/// for planning: the records are still checked against the original condition.
+	async fn order_ann_condition(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		t: &Table,
+		params: &QueryPlannerParams<'_>,
+	) -> Result<Option<Cond>, Error> {
+		if matches!(params.with, Some(With::NoIndex)) || opt.version.is_some() {
+			return Ok(None);
+		}
+		if params.group.is_some_and(|g| !g.is_empty()) {
+			return Ok(None);
+		}
+		let (Some(Ordering::Order(orders)), Some(limit)) = (params.order, params.limit) else {
+			return Ok(None);
+		};
+		// The nearest neighbours must be the first ascending ordering
+		let Some(order) = orders.0.first().filter(|o| o.direction) else {
+			return Ok(None);
+		};
+		// Other conditions must all be conjunctions, so they can be used as a KNN filter
+		if let Some(cond) = params.cond {
+			if !Self::is_conjunction(&cond.0) {
+				return Ok(None);
+			}
+		}
+		// Find the field which is aliased by the ordering
+		let Some(expr) = params.fields.0.iter().find_map(|f| match f {
+			Field::Single {
+				expr,
+				alias: Some(alias),
+			} if alias.eq(&order.value) => Some(expr),
+			_ => None,
+		}) else {
+			return Ok(None);
+		};
+		let Value::Function(f) = expr else {
+			return Ok(None);
+		};
+		let Function::Normal(name, args) = f.as_ref() else {
+			return Ok(None);
+		};
+		let dist = match name.as_str() {
+			"vector::distance::chebyshev" => Distance::Chebyshev,
+			"vector::distance::cosine" => Distance::Cosine,
+			"vector::distance::euclidean" => Distance::Euclidean,
+			"vector::distance::hamming" => Distance::Hamming,
+			"vector::distance::manhattan" => Distance::Manhattan,
+			_ => return Ok(None),
+		};
+		let (id, val) = match args.as_slice() {
+			[Value::Idiom(id), v] | [v, Value::Idiom(id)] if v.is_static() || v.is_param() => {
+				(id, v)
+			}
+			_ => return Ok(None),
+		};
+		let Some(ef) = Self::hnsw_ef(ctx, opt, t, params, id, &dist).await? else {
+			return Ok(None);
+		};
+		// The searched vector must not depend on the current document
+		let val = val.compute(stk, ctx, opt, None).await?;
+		if !matches!(&val, Value::Array(a) if a.iter().all(Value::is_number)) {
+			return Ok(None);
+		}
+		let mut k = limit.process(stk, ctx, opt, None).await?;
+		if let Some(start) = params.start {
+			k = k.saturating_add(start.process(stk, ctx, opt, None).await?);
+		}
+		if k == 0 {
+			return Ok(None);
+		}
+		let ann = Value::Expression(Box::new(Expression::Binary {
+			l: Value::Idiom(id.clone()),
+			o: Operator::Ann(k, ef.max(k)),
+			r: val,
+		}));
+		let cond = match params.cond {
+			Some(c) => Value::Expression(Box::new(Expression::Binary {
+				l: ann,
+				o: Operator::And,
+				r: c.0.clone(),
+			})),
+			None => ann,
+		};
+		Ok(Some(Cond(cond)))
+	}
+
+	/// Returns the construction size of the HNSW index on the given field using the given distance
+	async fn hnsw_ef(
+		ctx: &Context,
+		opt: &Options,
+		t: &Table,
+		params: &QueryPlannerParams<'_>,
+		id: &Idiom,
+		dist: &Distance,
+	) -> Result<Option<u32>, Error> {
+		let indexes = usable_indexes(ctx, opt, t, params.cond).await?;
+		for ix in indexes.iter() {
+			if let Some(With::Index(ixs)) = params.with {
+				if !ixs.contains(&ix.name.0) {
+					continue;
+				}
+			}
+			if let Index::Hnsw(p) = &ix.index {
+				if ix.cols.0.len() == 1 && ix.cols.0[0].eq(id) && p.distance.eq(dist) {
+					return Ok(Some(p.ef_construction as u32));
+				}
+			}
+		}
+		Ok(None)
+	}
+
+	fn is_conjunction(v: &Value) -> bool {
+		match v {
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} => Self::is_conjunction(l) && Self::is_conjunction(r),
+				Expression::Binary {
+					o: Operator::Or,
+					..
+				} => false,
+				_ => true,
+			},
+			Value::Subquery(s) => match s.as_ref() {
+				Subquery::Value(v) => Self::is_conjunction(v),
+				_ => true,
+			},
+			_ => true,
+		}
+	}
+
+	/// Checks that every index hinted with WITH INDEX exists on at least one of the planned tables
+	pub(crate) fn check_hinted_indexes(&self, with: Option<&With>) -> Result<(), Error> {
+		if let (Some(With::Index(ixs)), Some(hinted)) = (with, &self.hinted_indexes) {
+			if let Some(ix) = ixs.iter().find(|ix| !hinted.contains(*ix)) {
+				return Err(Error::IxNotFound {
+					value: ix.to_owned(),
+				});
+			}
+		}
+		Ok(())
+	}
+
 	pub(crate) fn has_executors(&self) -> bool {
 		!self.executors.is_empty()
 	}
@@ -230,3 +514,71 @@ pub(crate) enum IterationStage {
 	CollectKnn,
 	BuildKnn,
 }
+
+/// Returns the indexes of the table which can be used by queries.
+/// An index built concurrently is only used once its build is complete,
+/// and a partial index is only used if the condition implies the index condition.
+pub(super) async fn usable_indexes(
+	ctx: &Context,
+	opt: &Options,
+	t: &Table,
+	cond: Option<&Cond>,
+) -> Result<Arc<[DefineIndexStatement]>, Error> {
+	let mut indexes = ctx.tx().all_tb_indexes(opt.ns()?, opt.db()?, t).await?;
+	if indexes.iter().any(|ix| ix.cond.is_some()) {
+		let mut conds = vec![];
+		if let Some(c) = cond {
+			conjunctions(c, &mut conds);
+		}
+		indexes = indexes
+			.iter()
+			.filter(|ix| {
+				ix.cond.as_ref().map_or(true, |ic| {
+					let mut ics = vec![];
+					conjunctions(ic, &mut ics);
+					ics.iter().all(|c| conds.contains(c))
+				})
+			})
+			.cloned()
+			.collect();
+	}
+	#[cfg(not(target_arch = "wasm32"))]
+	if let Some(ib) = ctx.get_index_builder() {
+		let mut usable = Vec::with_capacity(indexes.len());
+		for ix in indexes.iter() {
+			if !ib.is_building(ix).await {
+				usable.push(ix.clone());
+			}
+		}
+		if usable.len() < indexes.len() {
+			return Ok(usable.into());
+		}
+	}
+	Ok(indexes)
+}
+
+/// Collects the operands of the top-level AND operators of a condition
+fn conjunctions<'a>(v: &'a Value, res: &mut Vec<&'a Value>) {
+	match v {
+		Value::Expression(e) => {
+			if let Expression::Binary {
+				l,
+				o: Operator::And,
+				r,
+			} = e.as_ref()
+			{
+				conjunctions(l, res);
+				conjunctions(r, res);
+				return;
+			}
+		}
+		Value::Subquery(s) => {
+			if let Subquery::Value(v) = s.as_ref() {
+				conjunctions(v, res);
+				return;
+			}
+		}
+		_ => {}
+	}
+	res.push(v);
+}
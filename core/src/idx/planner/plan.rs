@@ -1,11 +1,12 @@
 use crate::err::Error;
 use crate::idx::ft::MatchRef;
+use crate::idx::planner::statistics::PlanStatistics;
 use crate::idx::planner::tree::{GroupRef, IdiomCol, IdiomPosition, IndexRef, Node};
 use crate::idx::planner::QueryPlannerParams;
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::with::With;
 use crate::sql::{Array, Expression, Idiom, Number, Object};
-use crate::sql::{Operator, Value};
+use crate::sql::{Operator, Thing, Value};
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::Hash;
@@ -17,6 +18,8 @@ pub(super) struct PlanBuilder {
 	has_indexes: bool,
 	/// List of expressions that are not ranges, backed by an index
 	non_range_indexes: Vec<(Arc<Expression>, IndexOption)>,
+	/// List of equalities on the following columns of composite indexes
+	prefix_parts: Vec<IndexOption>,
 	/// List of indexes allowed in this plan
 	with_indexes: Option<Vec<IndexRef>>,
 	/// Group each possible optimisations local to a SubQuery
@@ -32,10 +35,12 @@ impl PlanBuilder {
 		all_and_groups: HashMap<GroupRef, bool>,
 		all_and: bool,
 		all_expressions_with_index: bool,
+		statistics: Option<&PlanStatistics>,
 	) -> Result<Plan, Error> {
 		let mut b = PlanBuilder {
 			has_indexes: false,
 			non_range_indexes: Default::default(),
+			prefix_parts: Default::default(),
 			groups: Default::default(),
 			with_indexes,
 		};
@@ -56,13 +61,23 @@ impl PlanBuilder {
 
 		// If every boolean operator are AND then we can use the single index plan
 		if all_and {
+			// If the table has been analyzed, we pick the most selective option
+			if let Some(stats) = statistics {
+				if let Some(plan) = b.plan_with_statistics(stats, keys_only) {
+					return Ok(plan);
+				}
+			}
 			// TODO: This is currently pretty arbitrary
 			// We take the "first" range query if one is available
-			if let Some((_, group)) = b.groups.into_iter().next() {
+			if let Some((_, group)) = b.groups.pop_first() {
 				if let Some((ir, rq)) = group.take_first_range() {
 					return Ok(Plan::SingleIndexRange(ir, rq));
 				}
 			}
+			// Otherwise we take the longest prefix of a composite index
+			if let Some((e, i)) = b.take_longest_prefix() {
+				return Ok(Plan::SingleIndex(Some(e), i));
+			}
 			// Otherwise we take the first single index option
 			if let Some((e, i)) = b.non_range_indexes.pop() {
 				return Ok(Plan::SingleIndex(Some(e), i));
@@ -125,7 +140,98 @@ impl PlanBuilder {
 		}
 	}
 
+	/// Picks the index option matching the fewest records according to the table statistics,
+	/// or a table scan if even this option matches too many records.
+	/// Returns `None` if an option can't be estimated, leaving the choice to the heuristics.
+	fn plan_with_statistics(&mut self, stats: &PlanStatistics, keys_only: bool) -> Option<Plan> {
+		let mut candidates = vec![];
+		for (i, (_, io)) in self.non_range_indexes.iter().enumerate() {
+			candidates.push((stats.estimate(io)?, Candidate::Single(i)));
+		}
+		if let Some((gr, group)) = self.groups.iter().next() {
+			for (ir, ri) in &group.ranges {
+				if let Some(rb) = UnionRangeQueryBuilder::new_aggregate(ri.clone()) {
+					let estimate = stats.estimate_range(*ir, &rb.from, &rb.to)?;
+					candidates.push((estimate, Candidate::Range(*gr, *ir)));
+				}
+			}
+		}
+		let (estimate, candidate) = candidates.into_iter().min_by_key(|(e, _)| *e)?;
+		if stats.prefers_table_scan(estimate) {
+			return Some(Self::table_iterator(
+				Some("The table statistics estimate that a table scan is cheaper"),
+				keys_only,
+			));
+		}
+		match candidate {
+			Candidate::Single(i) => {
+				// A prefix of a composite index is at least as selective as its first column
+				if let Some((p, values)) = self.longest_prefix() {
+					if p == i {
+						let (e, io) = self.take_prefix(p, values);
+						return Some(Plan::SingleIndex(Some(e), io));
+					}
+				}
+				let (e, io) = self.non_range_indexes.remove(i);
+				Some(Plan::SingleIndex(Some(e), io))
+			}
+			Candidate::Range(gr, ir) => {
+				let ri = self.groups.remove(&gr)?.ranges.remove(&ir)?;
+				UnionRangeQueryBuilder::new_aggregate(ri).map(|rb| Plan::SingleIndexRange(ir, rb))
+			}
+		}
+	}
+
+	/// Extends the equalities on the first column of composite indexes with the equalities
+	/// on the following columns, and takes the one matching the most columns, if any.
+	fn take_longest_prefix(&mut self) -> Option<(Arc<Expression>, IndexOption)> {
+		let (i, values) = self.longest_prefix()?;
+		Some(self.take_prefix(i, values))
+	}
+
+	/// Finds the equality on the first column of a composite index which can be extended
+	/// with the most equalities on the following columns.
+	fn longest_prefix(&self) -> Option<(usize, Vec<Value>)> {
+		let mut longest: Option<(usize, Vec<Value>)> = None;
+		for (i, (_, io)) in self.non_range_indexes.iter().enumerate() {
+			let (IndexOperator::Equality(v) | IndexOperator::Exactness(v)) = io.op() else {
+				continue;
+			};
+			let mut values = vec![v.as_ref().clone()];
+			while let Some(v) = self.prefix_parts.iter().find_map(|p| match p.op() {
+				IndexOperator::Equality(v)
+					if p.ix_ref() == io.ix_ref() && p.id_col == values.len() =>
+				{
+					Some(v)
+				}
+				_ => None,
+			}) {
+				values.push(v.as_ref().clone());
+			}
+			if values.len() > 1 && longest.as_ref().map_or(true, |(_, l)| values.len() > l.len()) {
+				longest = Some((i, values));
+			}
+		}
+		longest
+	}
+
+	fn take_prefix(&mut self, i: usize, values: Vec<Value>) -> (Arc<Expression>, IndexOption) {
+		let (e, io) = self.non_range_indexes.remove(i);
+		let io = IndexOption::new(
+			io.ix_ref,
+			io.id,
+			io.id_col,
+			io.id_pos,
+			IndexOperator::Prefix(Arc::new(Array::from(values))),
+		);
+		(e, io)
+	}
+
 	fn add_index_option(&mut self, group_ref: GroupRef, exp: Arc<Expression>, io: IndexOption) {
+		if io.id_col > 0 {
+			self.prefix_parts.push(io);
+			return;
+		}
 		if let IndexOperator::RangePart(_, _) = io.op() {
 			let level = self.groups.entry(group_ref).or_default();
 			match level.ranges.entry(io.ix_ref()) {
@@ -143,9 +249,19 @@ impl PlanBuilder {
 	}
 }
 
+/// An index option considered when planning with the table statistics
+enum Candidate {
+	/// The position of an option in the non range indexes
+	Single(usize),
+	/// The ranges of an index in a group
+	Range(GroupRef, IndexRef),
+}
+
 pub(super) enum Plan {
 	/// Table full scan
 	TableIterator(Option<String>, bool),
+	/// Direct lookup of the records whose ids are listed in the condition
+	RecordIds(Vec<Thing>),
 	/// Index scan filtered on records matching a given expression
 	SingleIndex(Option<Arc<Expression>>, IndexOption),
 	/// Union of filtered index scans
@@ -172,12 +288,15 @@ pub(super) struct IndexOption {
 pub(super) enum IndexOperator {
 	Equality(Arc<Value>),
 	Exactness(Arc<Value>),
+	/// Equalities on the leading columns of a composite index
+	Prefix(Arc<Array>),
 	Union(Arc<Value>),
 	Join(Vec<IndexOption>),
 	RangePart(Operator, Arc<Value>),
 	Matches(String, Option<MatchRef>),
 	Knn(Arc<Vec<Number>>, u32),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	Intersects(Arc<Value>),
 	Order,
 }
 
@@ -199,7 +318,7 @@ impl IndexOption {
 	}
 
 	pub(super) fn require_distinct(&self) -> bool {
-		matches!(self.op.as_ref(), IndexOperator::Union(_))
+		matches!(self.op.as_ref(), IndexOperator::Union(_) | IndexOperator::Intersects(_))
 	}
 
 	pub(super) fn ix_ref(&self) -> IndexRef {
@@ -210,6 +329,10 @@ impl IndexOption {
 		self.op.as_ref()
 	}
 
+	pub(super) fn id_col(&self) -> IdiomCol {
+		self.id_col
+	}
+
 	pub(super) fn id_ref(&self) -> &Idiom {
 		&self.id
 	}
@@ -241,6 +364,10 @@ impl IndexOption {
 				e.insert("operator", Value::from(Operator::Exact.to_string()));
 				e.insert("value", Self::reduce_array(v));
 			}
+			IndexOperator::Prefix(a) => {
+				e.insert("operator", Value::from("prefix"));
+				e.insert("value", Value::Array(a.as_ref().clone()));
+			}
 			IndexOperator::Union(v) => {
 				e.insert("operator", Value::from("union"));
 				e.insert("value", v.as_ref().clone());
@@ -274,6 +401,10 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::Intersects(v) => {
+				e.insert("operator", Value::from(Operator::Intersects.to_string()));
+				e.insert("value", v.as_ref().to_owned());
+			}
 			IndexOperator::Order => {
 				e.insert("operator", Value::from("Order"));
 			}
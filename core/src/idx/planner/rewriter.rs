@@ -157,7 +157,7 @@ impl<'a> KnnConditionRewriter<'a> {
 			| Part::Index(_)
 			| Part::Optional => Some(p.clone()),
 			Part::Where(v) => self.eval_value(v).map(Part::Where),
-			Part::Graph(_) => None,
+			Part::Graph(_) | Part::Recurse(_) => None,
 			Part::Value(v) => self.eval_value(v).map(Part::Value),
 			Part::Start(v) => self.eval_value(v).map(Part::Start),
 			Part::Method(n, p) => self.eval_values(p).map(|v| Part::Method(n.clone(), v)),
@@ -233,6 +233,9 @@ impl<'a> KnnConditionRewriter<'a> {
 			Function::Anonymous(p, args) => {
 				self.eval_values(args).map(|args| Function::Anonymous(p.clone(), args))
 			}
+			Function::Window(s, args, w) => {
+				self.eval_values(args).map(|args| Function::Window(s.clone(), args, w.clone()))
+			}
 		}
 	}
 
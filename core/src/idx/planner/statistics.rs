@@ -0,0 +1,263 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::err::Error;
+use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
+use crate::idx::planner::tree::IndexRef;
+use crate::key;
+use crate::kvs::Transaction;
+use crate::sql::index::Index;
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{Object, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// The number of buckets of the value distribution histograms
+const HISTOGRAM_BUCKETS: usize = 32;
+/// The maximum number of values sampled to build a histogram
+const MAX_SAMPLES: usize = 1024;
+
+/// The statistics of a table, as computed by ANALYZE TABLE
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct TableStatistics {
+	/// The number of records in the table
+	count: u64,
+	/// The statistics of the unique and non-unique indexes, by index name
+	indexes: BTreeMap<String, IndexStatistics>,
+}
+
+/// The statistics of a unique or non-unique index
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct IndexStatistics {
+	/// The number of entries in the index
+	entries: u64,
+	/// The number of distinct values of the first column
+	distinct: u64,
+	/// The boundaries of equi-depth buckets of the first column values
+	bounds: Vec<Value>,
+}
+
+impl TableStatistics {
+	/// Computes the statistics of a table by scanning its records and its indexes
+	pub(crate) async fn analyze(
+		ctx: &Context,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Self, Error> {
+		let tx = ctx.tx();
+		// Count the records
+		let beg = key::thing::prefix(ns, db, tb);
+		let end = key::thing::suffix(ns, db, tb);
+		let mut count = 0;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			Self::check_done(ctx)?;
+			let batch = tx.batch(rng, *NORMAL_FETCH_SIZE, false, None).await?;
+			next = batch.next;
+			count += batch.values.len() as u64;
+		}
+		// Collect the statistics of each index
+		let mut indexes = BTreeMap::new();
+		for ix in tx.all_tb_indexes(ns, db, tb).await?.iter() {
+			if matches!(ix.index, Index::Idx | Index::Uniq) {
+				let s = IndexStatistics::analyze(ctx, &tx, ns, db, tb, &ix.name).await?;
+				indexes.insert(ix.name.to_raw(), s);
+			}
+		}
+		Ok(Self {
+			count,
+			indexes,
+		})
+	}
+
+	fn check_done(ctx: &Context) -> Result<(), Error> {
+		if ctx.is_timedout() {
+			return Err(Error::QueryTimedout);
+		}
+		if ctx.is_done() {
+			return Err(Error::QueryCancelled);
+		}
+		Ok(())
+	}
+
+	/// Retrieves the stored statistics of a table, if it has been analyzed
+	pub(crate) async fn get(
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Option<Self>, Error> {
+		let key = key::table::st::new(ns, db, tb);
+		Ok(tx.get(key, None).await?.map(Self::from))
+	}
+
+	/// Stores the statistics of a table
+	pub(crate) async fn set(
+		&self,
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<(), Error> {
+		let key = key::table::st::new(ns, db, tb);
+		tx.set(key, self.clone(), None).await
+	}
+}
+
+impl From<&TableStatistics> for Value {
+	fn from(stats: &TableStatistics) -> Self {
+		let mut indexes = Object::default();
+		for (name, s) in &stats.indexes {
+			let mut res = Object::default();
+			res.insert("entries".to_owned(), Value::from(s.entries));
+			res.insert("distinct".to_owned(), Value::from(s.distinct));
+			indexes.insert(name.to_owned(), Value::from(res));
+		}
+		let mut res = Object::default();
+		res.insert("count".to_owned(), Value::from(stats.count));
+		res.insert("indexes".to_owned(), Value::from(indexes));
+		Value::from(res)
+	}
+}
+
+impl IndexStatistics {
+	async fn analyze(
+		ctx: &Context,
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ix: &str,
+	) -> Result<Self, Error> {
+		let beg = key::index::Index::prefix_beg(ns, db, tb, ix);
+		let end = key::index::Index::prefix_end(ns, db, tb, ix);
+		let mut entries = 0;
+		let mut distinct = 0;
+		let mut last: Option<Value> = None;
+		// Every `stride` entries are sampled, the stride doubling whenever the samples are full
+		let mut samples = Vec::with_capacity(MAX_SAMPLES);
+		let mut stride = 1;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			TableStatistics::check_done(ctx)?;
+			let batch = tx.batch(rng, *NORMAL_FETCH_SIZE, false, None).await?;
+			next = batch.next;
+			for (k, _) in batch.values {
+				let key: key::index::Index = (&k).into();
+				let v = key.fd.first().cloned().unwrap_or_default();
+				// The entries are ordered, so equal values are contiguous
+				if last.as_ref() != Some(&v) {
+					distinct += 1;
+				}
+				if entries % stride == 0 {
+					if samples.len() == MAX_SAMPLES {
+						samples = samples.into_iter().step_by(2).collect();
+						stride *= 2;
+					}
+					if entries % stride == 0 {
+						samples.push(v.clone());
+					}
+				}
+				last = Some(v);
+				entries += 1;
+			}
+		}
+		samples.sort();
+		Ok(Self {
+			entries,
+			distinct,
+			bounds: Self::bounds(samples),
+		})
+	}
+
+	/// Picks the values splitting the samples into buckets of equal size
+	fn bounds(samples: Vec<Value>) -> Vec<Value> {
+		if samples.is_empty() {
+			return vec![];
+		}
+		let last = samples.len() - 1;
+		(0..=HISTOGRAM_BUCKETS).map(|i| samples[i * last / HISTOGRAM_BUCKETS].clone()).collect()
+	}
+
+	/// Estimates the number of entries matching a single value of the first column
+	fn equality(&self) -> u64 {
+		self.entries.div_ceil(self.distinct.max(1))
+	}
+
+	/// Estimates the number of entries between two values of the first column
+	fn range(&self, from: &RangeValue, to: &RangeValue) -> u64 {
+		let buckets = self.bounds.len().saturating_sub(1) as u64;
+		if buckets == 0 {
+			return self.entries;
+		}
+		let overlapping = self
+			.bounds
+			.windows(2)
+			.filter(|b| {
+				(from.value.is_none() || b[1] >= from.value)
+					&& (to.value.is_none() || b[0] <= to.value)
+			})
+			.count() as u64;
+		// A range matches at least as many entries as a single value
+		(self.entries * overlapping / buckets).max(self.equality()).min(self.entries)
+	}
+}
+
+/// The statistics of a table, mapped to the indexes known by a query plan
+pub(super) struct PlanStatistics {
+	count: u64,
+	indexes: HashMap<IndexRef, IndexStatistics>,
+}
+
+impl PlanStatistics {
+	pub(super) fn new(stats: TableStatistics, definitions: &[Arc<DefineIndexStatement>]) -> Self {
+		let mut indexes = HashMap::new();
+		let mut all = stats.indexes;
+		for (ir, ix) in definitions.iter().enumerate() {
+			if let Some(s) = all.remove(ix.name.as_str()) {
+				indexes.insert(ir as IndexRef, s);
+			}
+		}
+		Self {
+			count: stats.count,
+			indexes,
+		}
+	}
+
+	/// Estimates the number of records matched by an index option, if possible
+	pub(super) fn estimate(&self, io: &IndexOption) -> Option<u64> {
+		let s = self.indexes.get(&io.ix_ref())?;
+		match io.op() {
+			IndexOperator::Equality(_) | IndexOperator::Exactness(_) | IndexOperator::Prefix(_) => {
+				Some(s.equality())
+			}
+			IndexOperator::Union(v) => match v.as_ref() {
+				Value::Array(a) => Some(s.equality() * a.len() as u64),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// Estimates the number of records matched by a range on an index, if possible
+	pub(super) fn estimate_range(
+		&self,
+		ir: IndexRef,
+		from: &RangeValue,
+		to: &RangeValue,
+	) -> Option<u64> {
+		self.indexes.get(&ir).map(|s| s.range(from, to))
+	}
+
+	/// Checks if scanning the table is cheaper than fetching the given number of records through an index
+	pub(super) fn prefers_table_scan(&self, estimate: u64) -> bool {
+		estimate.saturating_mul(2) > self.count
+	}
+}
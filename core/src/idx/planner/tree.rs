@@ -6,13 +6,15 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
+use crate::idx::planner::usable_indexes;
+use crate::idx::spatial;
 use crate::kvs::Transaction;
-use crate::sql::index::Index;
+use crate::sql::index::{Collation, Index};
 use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement};
 use crate::sql::{
 	order::{OrderList, Ordering},
-	Array, Cond, Expression, Idiom, Kind, Number, Operator, Order, Part, Subquery, Table, Value,
-	With,
+	Array, Cond, Expression, Function, Geometry, Idiom, Kind, Number, Operator, Order, Part,
+	Subquery, Table, Value, With,
 };
 use reblessive::tree::Stk;
 use std::collections::HashMap;
@@ -41,11 +43,11 @@ impl Tree {
 		ctx: &'a Context,
 		opt: &'a Options,
 		table: &'a Table,
-		cond: Option<&Cond>,
-		with: Option<&With>,
-		order: Option<&Ordering>,
+		cond: Option<&'a Cond>,
+		with: Option<&'a With>,
+		order: Option<&'a Ordering>,
 	) -> Result<Self, Error> {
-		let mut b = TreeBuilder::new(ctx, opt, table, with, order);
+		let mut b = TreeBuilder::new(ctx, opt, table, cond, with, order);
 		if let Some(cond) = cond {
 			b.eval_cond(stk, cond).await?;
 		}
@@ -69,6 +71,7 @@ struct TreeBuilder<'a> {
 	ctx: &'a Context,
 	opt: &'a Options,
 	table: &'a Table,
+	cond: Option<&'a Cond>,
 	with: Option<&'a With>,
 	first_order: Option<&'a Order>,
 	schemas: HashMap<Table, SchemaCache>,
@@ -104,6 +107,7 @@ impl<'a> TreeBuilder<'a> {
 		ctx: &'a Context,
 		opt: &'a Options,
 		table: &'a Table,
+		cond: Option<&'a Cond>,
 		with: Option<&'a With>,
 		orders: Option<&'a Ordering>,
 	) -> Self {
@@ -120,6 +124,7 @@ impl<'a> TreeBuilder<'a> {
 			ctx,
 			opt,
 			table,
+			cond,
 			with,
 			first_order,
 			schemas: Default::default(),
@@ -149,17 +154,30 @@ impl<'a> TreeBuilder<'a> {
 		if self.schemas.contains_key(table) {
 			return Ok(());
 		}
-		let l = SchemaCache::new(self.opt, table, tx).await?;
+		// The condition only applies to the records of the queried table
+		let cond = if table == self.table {
+			self.cond
+		} else {
+			None
+		};
+		let l = SchemaCache::new(self.ctx, self.opt, table, cond, tx).await?;
 		self.schemas.insert(table.clone(), l);
 		Ok(())
 	}
 
 	async fn eval_order(&mut self) -> Result<(), Error> {
 		if let Some(o) = self.first_order {
-			if o.direction {
+			// Index keys are only ordered by their binary representation
+			if o.direction && o.is_binary() {
 				if let Node::IndexedField(id, irf) = self.resolve_idiom(&o.value).await? {
 					for (ix_ref, id_col) in &irf {
-						if *id_col == 0 {
+						// Collations don't preserve the order of the values
+						let collated = self
+							.index_map
+							.definitions
+							.get(*ix_ref as usize)
+							.is_some_and(|ix| !ix.collate.is_empty());
+						if *id_col == 0 && !collated {
 							self.index_map.order_limit = Some(IndexOption::new(
 								*ix_ref,
 								id,
@@ -206,11 +224,14 @@ impl<'a> TreeBuilder<'a> {
 			| Value::Datetime(_)
 			| Value::Param(_)
 			| Value::Null
-			| Value::None
-			| Value::Function(_) => {
+			| Value::None => {
 				self.leaf_nodes_count += 1;
 				Ok(Node::Computable)
 			}
+			Value::Function(f) => match Self::spatial_function_expression(f) {
+				Some(e) => self.eval_expression(stk, group, &e).await,
+				None => self.eval_function(v).await,
+			},
 			Value::Array(a) => self.eval_array(stk, a).await,
 			Value::Subquery(s) => self.eval_subquery(stk, s).await,
 			_ => Ok(Node::Unsupported(format!("Unsupported value: {}", v))),
@@ -261,6 +282,21 @@ impl<'a> TreeBuilder<'a> {
 		Ok(n)
 	}
 
+	/// A function matching the column of an index computed over the same function
+	/// is resolved as an indexed field, otherwise the function is computable.
+	async fn eval_function(&mut self, v: &Value) -> Result<Node, Error> {
+		self.leaf_nodes_count += 1;
+		let i = Idiom(vec![Part::Start(v.clone())]);
+		let n = match self.resolved_idioms.get(&i).cloned() {
+			Some(n) => n,
+			None => self.resolve_idiom(&i).await?,
+		};
+		if let Node::IndexedField(_, _) = n {
+			return Ok(n);
+		}
+		Ok(Node::Computable)
+	}
+
 	async fn resolve_idiom(&mut self, i: &Idiom) -> Result<Node, Error> {
 		let tx = self.ctx.tx();
 		self.lazy_load_schema_resolver(&tx, self.table).await?;
@@ -293,7 +329,19 @@ impl<'a> TreeBuilder<'a> {
 		}
 		let mut irs = Vec::new();
 		for ix in schema.indexes.iter() {
-			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
+			if let Some(idiom_index) =
+				ix.cols.iter().position(|p| p.eq(i) || Self::is_lowercased_column(ix, p, i))
+			{
+				// The columns of a composite index share the same reference
+				if let Some(ixr) = self
+					.index_map
+					.definitions
+					.iter()
+					.position(|d| d.name == ix.name && d.what == ix.what)
+				{
+					irs.push((ixr as IndexRef, idiom_index));
+					continue;
+				}
 				let ixr = self.index_map.definitions.len() as IndexRef;
 				if let Some(With::Index(ixs)) = &self.with {
 					if ixs.contains(&ix.name.0) {
@@ -317,6 +365,20 @@ impl<'a> TreeBuilder<'a> {
 		irs
 	}
 
+	/// Checks if the idiom lowercases a column of a case-insensitive index
+	fn is_lowercased_column(ix: &DefineIndexStatement, col: &Idiom, i: &Idiom) -> bool {
+		if !ix.collate.contains(&Collation::NoCase) {
+			return false;
+		}
+		if let [Part::Start(Value::Function(f))] = i.0.as_slice() {
+			if let Function::Normal(name, args) = f.as_ref() {
+				return name == "string::lowercase"
+					&& matches!(args.as_slice(), [Value::Idiom(a)] if a.eq(col));
+			}
+		}
+		false
+	}
+
 	async fn resolve_record_field(
 		&mut self,
 		tx: &Transaction,
@@ -380,6 +442,9 @@ impl<'a> TreeBuilder<'a> {
 				o,
 				r,
 			} => {
+				if let Some(e) = self.spatial_distance_expression(stk, l, o, r).await {
+					return stk.run(|stk| self.eval_expression(stk, group, &e)).await;
+				}
 				// Did we already compute the same expression?
 				if let Some(re) = self.resolved_expressions.get(e).cloned() {
 					return Ok(re.into());
@@ -436,6 +501,74 @@ impl<'a> TreeBuilder<'a> {
 		}
 	}
 
+	/// Rewrites `geo::contains(a, b)` and `geo::intersects(a, b)` on a field into
+	/// the equivalent operators, so a spatial index can be used to evaluate them
+	fn spatial_function_expression(f: &Function) -> Option<Expression> {
+		let Function::Normal(name, args) = f else {
+			return None;
+		};
+		let o = match name.as_str() {
+			"geo::contains" => Operator::Contain,
+			"geo::intersects" => Operator::Intersects,
+			_ => return None,
+		};
+		match args.as_slice() {
+			[l, r] if matches!(l, Value::Idiom(_)) || matches!(r, Value::Idiom(_)) => {
+				Some(Expression::Binary {
+					l: l.clone(),
+					o,
+					r: r.clone(),
+				})
+			}
+			_ => None,
+		}
+	}
+
+	/// Rewrites `geo::distance(field, point) < distance` into an intersection with the
+	/// bounding box of the points within this distance, so a spatial index can be used
+	async fn spatial_distance_expression(
+		&self,
+		stk: &mut Stk,
+		l: &Value,
+		o: &Operator,
+		r: &Value,
+	) -> Option<Expression> {
+		let (f, d) = match o {
+			Operator::LessThan | Operator::LessThanOrEqual => (l, r),
+			Operator::MoreThan | Operator::MoreThanOrEqual => (r, l),
+			_ => return None,
+		};
+		let Value::Function(f) = f else {
+			return None;
+		};
+		let Function::Normal(name, args) = f.as_ref() else {
+			return None;
+		};
+		if name != "geo::distance" {
+			return None;
+		}
+		let (id, p) = match args.as_slice() {
+			[Value::Idiom(id), p] | [p, Value::Idiom(id)] if !matches!(p, Value::Idiom(_)) => {
+				(id, p)
+			}
+			_ => return None,
+		};
+		let Ok(Value::Geometry(Geometry::Point(p))) =
+			stk.run(|stk| p.compute(stk, self.ctx, self.opt, None)).await
+		else {
+			return None;
+		};
+		let Ok(Value::Number(d)) = stk.run(|stk| d.compute(stk, self.ctx, self.opt, None)).await
+		else {
+			return None;
+		};
+		Some(Expression::Binary {
+			l: Value::Idiom(id.clone()),
+			o: Operator::Intersects,
+			r: Value::Geometry(spatial::radius_bounds(p, d.to_float())),
+		})
+	}
+
 	fn check_boolean_operator(&mut self, gr: GroupRef, op: &Operator) {
 		match op {
 			Operator::Neg | Operator::Or => {
@@ -457,7 +590,8 @@ impl<'a> TreeBuilder<'a> {
 	}
 
 	fn check_leaf_node_with_index(&mut self, io: Option<&IndexOption>) {
-		if let Some(io) = io {
+		// An option on a following column of a composite index can't be scanned on its own
+		if let Some(io) = io.filter(|io| io.id_col() == 0) {
 			if let Some(wi) = &self.with_indexes {
 				if !wi.contains(&io.ix_ref()) {
 					return;
@@ -510,13 +644,18 @@ impl<'a> TreeBuilder<'a> {
 		for (irf, id_col) in irs.iter().filter(|(_, id_col)| 0.eq(id_col)) {
 			if let Some(ix) = self.index_map.definitions.get(*irf as usize) {
 				let op = match &ix.index {
-					Index::Idx => self.eval_index_operator(op, n, p),
-					Index::Uniq => self.eval_index_operator(op, n, p),
+					Index::Idx => {
+						self.eval_index_operator(op, n, p).and_then(|o| Self::collate(ix, o))
+					}
+					Index::Uniq => {
+						self.eval_index_operator(op, n, p).and_then(|o| Self::collate(ix, o))
+					}
 					Index::Search {
 						..
 					} => Self::eval_matches_operator(op, n),
 					Index::MTree(_) => self.eval_mtree_knn(e, op, n)?,
 					Index::Hnsw(_) => self.eval_hnsw_knn(e, op, n)?,
+					Index::Spatial => Self::eval_spatial_operator(op, n),
 				};
 				if let Some(op) = op {
 					let io = IndexOption::new(*irf, id.clone(), *id_col, p, op);
@@ -525,9 +664,35 @@ impl<'a> TreeBuilder<'a> {
 				}
 			}
 		}
+		// An equality on a following column of a composite index can extend the prefix scanned
+		if let (Operator::Equal | Operator::Exact, Some(v)) = (op, n.is_computed()) {
+			for (irf, id_col) in irs.iter().filter(|(_, id_col)| 0.ne(id_col)) {
+				if let Some(ix) = self.index_map.definitions.get(*irf as usize) {
+					if let Index::Idx | Index::Uniq = &ix.index {
+						let op = IndexOperator::Equality(Arc::new(ix.collate(v.as_ref().clone())));
+						return Ok(Some(IndexOption::new(*irf, id.clone(), *id_col, p, op)));
+					}
+				}
+			}
+		}
 		Ok(None)
 	}
 
+	/// Applies the collations of the index to the values looked up by an operator
+	fn collate(ix: &DefineIndexStatement, op: IndexOperator) -> Option<IndexOperator> {
+		if ix.collate.is_empty() {
+			return Some(op);
+		}
+		let c = |v: Arc<Value>| Arc::new(ix.collate(v.as_ref().clone()));
+		match op {
+			IndexOperator::Equality(v) => Some(IndexOperator::Equality(c(v))),
+			IndexOperator::Exactness(v) => Some(IndexOperator::Exactness(c(v))),
+			IndexOperator::Union(v) => Some(IndexOperator::Union(c(v))),
+			// Collations don't preserve the order of the values
+			_ => None,
+		}
+	}
+
 	fn lookup_join_index_ref(&self, irs: &LocalIndexRefs) -> Option<(IndexRef, IdiomCol)> {
 		for (irf, id_col) in irs.iter().filter(|(_, id_col)| 0.eq(id_col)) {
 			if let Some(ix) = self.index_map.definitions.get(*irf as usize) {
@@ -549,6 +714,18 @@ impl<'a> TreeBuilder<'a> {
 		None
 	}
 
+	fn eval_spatial_operator(op: &Operator, n: &Node) -> Option<IndexOperator> {
+		if let Some(v) = n.is_computed() {
+			if let Value::Geometry(_) = v.as_ref() {
+				// Containment in either direction implies an intersection
+				if let Operator::Intersects | Operator::Inside | Operator::Contain = op {
+					return Some(IndexOperator::Intersects(v));
+				}
+			}
+		}
+		None
+	}
+
 	fn eval_mtree_knn(
 		&mut self,
 		exp: &Arc<Expression>,
@@ -664,8 +841,14 @@ struct SchemaCache {
 }
 
 impl SchemaCache {
-	async fn new(opt: &Options, table: &Table, tx: &Transaction) -> Result<Self, Error> {
-		let indexes = tx.all_tb_indexes(opt.ns()?, opt.db()?, table).await?;
+	async fn new(
+		ctx: &Context,
+		opt: &Options,
+		table: &Table,
+		cond: Option<&Cond>,
+		tx: &Transaction,
+	) -> Result<Self, Error> {
+		let indexes = usable_indexes(ctx, opt, table, cond).await?;
 		let fields = tx.all_tb_fields(opt.ns()?, opt.db()?, table, None).await?;
 		Ok(Self {
 			indexes,
@@ -0,0 +1,269 @@
+//! A spatial index storing every geometry in the smallest geohash cell containing
+//! its bounding box. Searching an area scans the cells covering its bounding box,
+//! including every cell nested inside them, and the larger cells enclosing them.
+use crate::err::Error;
+use crate::fnc::util::geo::encode;
+use crate::key::index::gc::Gc;
+use crate::kvs::{Key, Transaction};
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{Geometry, Thing, Value};
+use geo::{coord, BoundingRect, Point, Rect};
+use std::collections::BTreeSet;
+
+/// The length of the geohash cells where points are stored
+const MAX_CELL_LEN: usize = 12;
+/// The maximum number of cells scanned to cover the searched area
+const MAX_COVERING_CELLS: i64 = 16;
+/// The mean radius of the Earth in metres, as used by `geo::distance`
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+const BASE32: &str = "0123456789bcdefghjkmnpqrstuvwxyz";
+
+pub(crate) struct SpatialIndex<'a> {
+	ns: &'a str,
+	db: &'a str,
+	ix: &'a DefineIndexStatement,
+}
+
+impl<'a> SpatialIndex<'a> {
+	pub(crate) fn new(ns: &'a str, db: &'a str, ix: &'a DefineIndexStatement) -> Self {
+		Self {
+			ns,
+			db,
+			ix,
+		}
+	}
+
+	pub(crate) async fn remove_document(
+		&self,
+		tx: &Transaction,
+		rid: &Thing,
+		content: &[Value],
+	) -> Result<(), Error> {
+		for cell in Self::cells(content) {
+			tx.del(self.key(&cell, rid)).await?;
+		}
+		Ok(())
+	}
+
+	pub(crate) async fn index_document(
+		&self,
+		tx: &Transaction,
+		rid: &Thing,
+		content: &[Value],
+	) -> Result<(), Error> {
+		for cell in Self::cells(content) {
+			tx.set(self.key(&cell, rid), rid, None).await?;
+		}
+		Ok(())
+	}
+
+	fn key(&self, cell: &str, rid: &Thing) -> Key {
+		Gc::new(self.ns, self.db, &self.ix.what, &self.ix.name, cell, rid.id.clone()).into()
+	}
+
+	/// The cells of the geometries, or arrays of geometries, of the indexed field
+	fn cells(content: &[Value]) -> BTreeSet<String> {
+		let mut cells = BTreeSet::new();
+		for v in content {
+			match v {
+				Value::Geometry(g) => cells.extend(cell(g)),
+				Value::Array(a) => {
+					for v in a.iter() {
+						if let Value::Geometry(g) = v {
+							cells.extend(cell(g));
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+		cells
+	}
+}
+
+fn bounding_rect(g: &Geometry) -> Option<Rect<f64>> {
+	geo::Geometry::from(g.clone()).bounding_rect()
+}
+
+/// Returns the smallest geohash cell containing the bounding box of the geometry
+pub(crate) fn cell(g: &Geometry) -> Option<String> {
+	let r = bounding_rect(g)?;
+	let min = encode(r.min().into(), MAX_CELL_LEN).0;
+	let max = encode(r.max().into(), MAX_CELL_LEN).0;
+	let len = min.bytes().zip(max.bytes()).take_while(|(a, b)| a == b).count();
+	Some(min[..len].to_string())
+}
+
+/// Returns the geohash cells covering the bounding box of the geometry.
+/// The cells have the same length, and are the smallest ones not exceeding
+/// the maximum number of covering cells.
+pub(crate) fn covering(g: &Geometry) -> Vec<String> {
+	let Some(r) = bounding_rect(g) else {
+		return vec![];
+	};
+	let min = encode(r.min().into(), MAX_CELL_LEN).0;
+	let max = encode(r.max().into(), MAX_CELL_LEN).0;
+	for len in (1..=MAX_CELL_LEN).rev() {
+		let (x0, y0) = position(&min[..len]);
+		let (x1, y1) = position(&max[..len]);
+		if (x1 - x0 + 1) * (y1 - y0 + 1) <= MAX_COVERING_CELLS || len == 1 {
+			let mut cells = Vec::new();
+			for x in x0..=x1 {
+				for y in y0..=y1 {
+					cells.push(hash(x, y, len));
+				}
+			}
+			return cells;
+		}
+	}
+	vec![]
+}
+
+/// Returns every cell enclosing one of the given cells, including the whole world (an empty cell)
+pub(crate) fn ancestors(cells: &[String]) -> BTreeSet<String> {
+	let mut ancestors = BTreeSet::new();
+	for c in cells {
+		for len in 0..c.len() {
+			ancestors.insert(c[..len].to_string());
+		}
+	}
+	ancestors
+}
+
+/// Returns the bounding box of the points within the given distance in metres of a point
+pub(crate) fn radius_bounds(p: Point<f64>, distance: f64) -> Geometry {
+	// A slightly larger angle absorbs floating point rounding
+	let d = distance.max(0.0) / EARTH_RADIUS * (1.0 + 1e-9);
+	let dlat = d.to_degrees();
+	let (min_y, max_y) = ((p.y() - dlat).max(-90.0), (p.y() + dlat).min(90.0));
+	let dlon = (d.sin() / p.y().to_radians().cos()).asin().to_degrees();
+	let (min_x, max_x) = if min_y <= -90.0 || max_y >= 90.0 || dlon.is_nan() {
+		(-180.0, 180.0)
+	} else if p.x() - dlon < -180.0 || p.x() + dlon > 180.0 {
+		// The area crosses the antimeridian
+		(-180.0, 180.0)
+	} else {
+		(p.x() - dlon, p.x() + dlon)
+	};
+	let r = Rect::new(
+		coord! {
+			x: min_x,
+			y: min_y,
+		},
+		coord! {
+			x: max_x,
+			y: max_y,
+		},
+	);
+	Geometry::Polygon(r.to_polygon())
+}
+
+/// The position of a geohash cell in the grid of the cells of the same length
+fn position(hash: &str) -> (i64, i64) {
+	let (mut x, mut y) = (0i64, 0i64);
+	let mut bit = 0;
+	for c in hash.chars() {
+		let v = BASE32.find(c).unwrap_or_default() as i64;
+		for i in (0..5).rev() {
+			let b = (v >> i) & 1;
+			if bit % 2 == 0 {
+				x = (x << 1) | b;
+			} else {
+				y = (y << 1) | b;
+			}
+			bit += 1;
+		}
+	}
+	(x, y)
+}
+
+/// The geohash of the cell at the given position in the grid of the cells of the given length
+fn hash(x: i64, y: i64, len: usize) -> String {
+	let bits = 5 * len;
+	let (mut xb, mut yb) = (bits.div_ceil(2), bits / 2);
+	let mut out = String::with_capacity(len);
+	let mut v = 0;
+	for bit in 0..bits {
+		let b = if bit % 2 == 0 {
+			xb -= 1;
+			(x >> xb) & 1
+		} else {
+			yb -= 1;
+			(y >> yb) & 1
+		};
+		v = (v << 1) | b as usize;
+		if bit % 5 == 4 {
+			out.push(BASE32.as_bytes()[v] as char);
+			v = 0;
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use geo::{line_string, point};
+
+	#[test]
+	fn test_cell_of_point() {
+		let g = Geometry::Point(point! { x: -0.118092, y: 51.509865 });
+		assert_eq!(cell(&g), Some(encode(point! { x: -0.118092, y: 51.509865 }, 12).0));
+	}
+
+	#[test]
+	fn test_cell_of_line() {
+		let g = Geometry::Line(line_string![(x: -0.12, y: 51.50), (x: -0.11, y: 51.51)]);
+		let c = cell(&g).unwrap();
+		assert_eq!(c, "gcp");
+		// The points of the line are stored in nested cells
+		assert!(encode(point! { x: -0.12, y: 51.50 }, 12).starts_with(&c));
+		assert!(encode(point! { x: -0.11, y: 51.51 }, 12).starts_with(&c));
+	}
+
+	#[test]
+	fn test_position_and_hash() {
+		for h in ["0", "z", "gcpvj", "u4pruydqqvj8", "s0000000000b"] {
+			let (x, y) = position(h);
+			assert_eq!(hash(x, y, h.len()), h);
+		}
+	}
+
+	#[test]
+	fn test_covering() {
+		let p = point! { x: -0.118092, y: 51.509865 };
+		// A point is covered by its own cell
+		assert_eq!(covering(&Geometry::Point(p)), vec![encode(p, 12).0]);
+		// An area is covered by a limited number of cells containing its corners
+		let g = radius_bounds(p, 1000.0);
+		let cells = covering(&g);
+		assert!(!cells.is_empty() && cells.len() <= MAX_COVERING_CELLS as usize);
+		let len = cells[0].len();
+		for corner in [point! { x: -0.13, y: 51.505 }, point! { x: -0.107, y: 51.515 }] {
+			assert!(cells.contains(&encode(corner, len).0));
+		}
+	}
+
+	#[test]
+	fn test_ancestors() {
+		let a = ancestors(&["gcpv".to_string(), "gcpy".to_string()]);
+		assert_eq!(a.into_iter().collect::<Vec<_>>(), vec!["", "g", "gc", "gcp"]);
+	}
+
+	#[test]
+	fn test_radius_bounds() {
+		let Geometry::Polygon(p) = radius_bounds(point! { x: 0.0, y: 0.0 }, 111_195.0) else {
+			panic!()
+		};
+		let r = p.bounding_rect().unwrap();
+		assert!((r.max().y - 1.0).abs() < 0.001);
+		assert!((r.max().x - 1.0).abs() < 0.001);
+		// Near the poles every longitude is covered
+		let Geometry::Polygon(p) = radius_bounds(point! { x: 10.0, y: 89.9 }, 50_000.0) else {
+			panic!()
+		};
+		let r = p.bounding_rect().unwrap();
+		assert_eq!((r.min().x, r.max().x), (-180.0, 180.0));
+	}
+}
@@ -0,0 +1,75 @@
+use crate::err::Error;
+use crate::idx::ft::analyzer::dictionary::Dictionary;
+use crate::sql::statements::DefineAnalyzerStatement;
+use crate::sql::Tokenizer;
+use ahash::HashSet;
+use dashmap::DashMap;
+use std::path::Path;
+
+#[derive(Default)]
+pub(crate) struct Dictionaries(DashMap<String, Dictionary>);
+
+impl Dictionaries {
+	/// If any dictionary tokenizer is defined, the dictionary will be loaded in memory.
+	pub(crate) async fn load(&self, az: &DefineAnalyzerStatement) -> Result<(), Error> {
+		if let Some(tokenizers) = &az.tokenizers {
+			for t in tokenizers {
+				if let Tokenizer::Dictionary(path) = t {
+					self.insert(path).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Ensure that if a dictionary tokenizer is defined, that the dictionary is also loaded in memory.
+	/// This method does not reload a dictionary if it is already in memory.
+	pub(crate) async fn check(&self, az: &DefineAnalyzerStatement) -> Result<(), Error> {
+		if let Some(tokenizers) = &az.tokenizers {
+			for t in tokenizers {
+				if let Tokenizer::Dictionary(path) = t {
+					if !self.0.contains_key(path) {
+						self.insert(path).await?;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn insert(&self, path: &str) -> Result<(), Error> {
+		let p = Path::new(path);
+		if !p.exists() || !p.is_file() {
+			return Err(Error::Internal(format!("Invalid dictionary path: {p:?}")));
+		}
+		let dictionary = Dictionary::new(p).await?;
+		self.0.insert(path.to_string(), dictionary);
+		Ok(())
+	}
+
+	pub(in crate::idx) fn get(&self, path: &str) -> Result<Dictionary, Error> {
+		match self.0.get(path) {
+			None => Err(Error::Internal(format!("Dictionary not found for {path}"))),
+			Some(e) => Ok(e.value().clone()),
+		}
+	}
+
+	pub(crate) fn cleanup(&self, azs: &[DefineAnalyzerStatement]) {
+		// Collect every existing dictionary
+		let mut keys: HashSet<String> = self.0.iter().map(|e| e.key().to_string()).collect();
+		// Remove keys that still exist in the definitions
+		for az in azs {
+			if let Some(tokenizers) = &az.tokenizers {
+				for t in tokenizers {
+					if let Tokenizer::Dictionary(path) = t {
+						keys.remove(path);
+					}
+				}
+			}
+		}
+		// Any left key can be removed
+		for key in keys {
+			self.0.remove(&key);
+		}
+	}
+}
@@ -1,4 +1,5 @@
 pub mod cache;
+mod dictionary;
 pub(crate) mod hnsw;
 mod lru;
 mod mapper;
@@ -11,6 +12,7 @@ use crate::idx::trees::bkeys::{FstKeys, TrieKeys};
 use crate::idx::trees::btree::{BTreeNode, BTreeStore};
 use crate::idx::trees::mtree::{MTreeNode, MTreeStore};
 use crate::idx::trees::store::cache::{TreeCache, TreeCaches};
+use crate::idx::trees::store::dictionary::Dictionaries;
 use crate::idx::trees::store::hnsw::{HnswIndexes, SharedHnswIndex};
 use crate::idx::trees::store::mapper::Mappers;
 use crate::idx::trees::store::tree::{TreeRead, TreeWrite};
@@ -222,6 +224,7 @@ struct Inner {
 	mtree_caches: TreeCaches<MTreeNode>,
 	hnsw_indexes: HnswIndexes,
 	mappers: Mappers,
+	dictionaries: Dictionaries,
 }
 impl Default for IndexStores {
 	fn default() -> Self {
@@ -231,6 +234,7 @@ impl Default for IndexStores {
 			mtree_caches: TreeCaches::default(),
 			hnsw_indexes: HnswIndexes::default(),
 			mappers: Mappers::default(),
+			dictionaries: Dictionaries::default(),
 		}))
 	}
 }
@@ -383,4 +387,8 @@ impl IndexStores {
 	pub(crate) fn mappers(&self) -> &Mappers {
 		&self.0.mappers
 	}
+
+	pub(crate) fn dictionaries(&self) -> &Dictionaries {
+		&self.0.dictionaries
+	}
 }
@@ -20,14 +20,24 @@ pub enum Category {
 	AccessRoot,
 	/// crate::key::root::access::gr         /*{ac}!gr{gr}
 	AccessGrant,
+	/// crate::key::root::lk                 /!lk{lk}
+	Lock,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
 	/// crate::key::root::ni                 /!ni
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
 	Namespace,
+	/// crate::key::root::qe                 /!qe{qe}
+	EventQueue,
+	/// crate::key::root::rl                 /!rl{rl}
+	Role,
+	/// crate::key::root::rv                 /!rv{kind}{id}
+	Revocation,
 	/// crate::key::root::us                 /!us{us}
 	User,
+	/// crate::key::root::wh                 /!wh{wh}
+	WebhookQueue,
 	///
 	/// ------------------------------
 	///
@@ -55,6 +65,10 @@ pub enum Category {
 	NamespaceAccessRoot,
 	/// crate::key::namespace::access::gr    /*{ns}*{ac}!gr{gr}
 	NamespaceAccessGrant,
+	/// crate::key::namespace::rl            /*{ns}!rl{rl}
+	NamespaceRole,
+	/// crate::key::namespace::rv            /*{ns}!rv{kind}{id}
+	NamespaceRevocation,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
 	///
@@ -76,10 +90,18 @@ pub enum Category {
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+	DatabaseRole,
+	/// crate::key::database::rv             /*{ns}*{db}!rv{kind}{id}
+	DatabaseRevocation,
+	/// crate::key::database::sz             /*{ns}*{db}!sz{id}
+	DatabaseStorageUsage,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
 	DatabaseTimestamp,
+	/// crate::key::database::ty             /*{ns}*{db}!ty{ty}
+	DatabaseType,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
 	DatabaseUser,
 	/// crate::key::database::vs             /*{ns}*{db}!vs
@@ -91,6 +113,10 @@ pub enum Category {
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::cn                /*{ns}*{db}*{tb}!cn{id}
+	TableCount,
+	/// crate::key::table::eo                /*{ns}*{db}*{tb}!eo{id}
+	TableEventOrder,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
@@ -99,8 +125,16 @@ pub enum Category {
 	TableView, // (ft = foreign table = view)
 	/// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 	IndexDefinition,
+	/// crate::key::table::la                /*{ns}*{db}*{tb}!la{lq}{gr}
+	TableLiveAggregate,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::sk                /*{ns}*{db}*{tb}!sk{sk}
+	TableSink,
+	/// crate::key::table::so                /*{ns}*{db}*{tb}!so{sk}
+	TableSinkOffset,
+	/// crate::key::table::st                /*{ns}*{db}*{tb}!st
+	TableStatistics,
 	///
 	/// ------------------------------
 	///
@@ -157,6 +191,21 @@ pub enum Category {
 	///
 	/// crate::key::graph                    /*{ns}*{db}*{tb}~{id}{eg}{fk}
 	Graph,
+	///
+	/// ------------------------------
+	///
+	/// crate::key::r#ref                    /*{ns}*{db}*{tb}&{id}{ft}{ff}{fk}
+	Ref,
+	///
+	/// ------------------------------
+	///
+	/// crate::key::partition                /*{ns}*{db}*{tb}%{pt}{id}
+	Partition,
+	///
+	/// ------------------------------
+	///
+	/// crate::key::ttl                      /*{ns}*{db}*{tb}^{id}
+	Ttl,
 }
 
 impl Display for Category {
@@ -167,10 +216,15 @@ impl Display for Category {
 			Self::Access => "Access",
 			Self::AccessRoot => "AccessRoot",
 			Self::AccessGrant => "AccessGrant",
+			Self::Lock => "Lock",
 			Self::Node => "Node",
 			Self::NamespaceIdentifier => "NamespaceIdentifier",
 			Self::Namespace => "Namespace",
+			Self::EventQueue => "EventQueue",
+			Self::Role => "Role",
+			Self::Revocation => "Revocation",
 			Self::User => "User",
+			Self::WebhookQueue => "WebhookQueue",
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
 			Self::NamespaceRoot => "NamespaceRoot",
@@ -179,6 +233,8 @@ impl Display for Category {
 			Self::NamespaceAccess => "NamespaceAccess",
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
+			Self::NamespaceRole => "NamespaceRole",
+			Self::NamespaceRevocation => "NamespaceRevocation",
 			Self::NamespaceUser => "NamespaceUser",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
@@ -188,18 +244,28 @@ impl Display for Category {
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseRole => "DatabaseRole",
+			Self::DatabaseRevocation => "DatabaseRevocation",
+			Self::DatabaseStorageUsage => "DatabaseStorageUsage",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
+			Self::DatabaseType => "DatabaseType",
 			Self::DatabaseUser => "DatabaseUser",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
+			Self::TableCount => "TableCount",
+			Self::TableEventOrder => "TableEventOrder",
 			Self::TableEvent => "TableEvent",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
+			Self::TableLiveAggregate => "TableLiveAggregate",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableSink => "TableSink",
+			Self::TableSinkOffset => "TableSinkOffset",
+			Self::TableStatistics => "TableStatistics",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -222,6 +288,9 @@ impl Display for Category {
 			Self::ChangeFeed => "ChangeFeed",
 			Self::Thing => "Thing",
 			Self::Graph => "Graph",
+			Self::Ref => "Ref",
+			Self::Partition => "Partition",
+			Self::Ttl => "Ttl",
 		};
 		write!(f, "{}", name)
 	}
@@ -6,8 +6,12 @@ pub mod cg;
 pub mod fc;
 pub mod ml;
 pub mod pa;
+pub mod rl;
+pub mod rv;
+pub mod sz;
 pub mod tb;
 pub mod ti;
 pub mod ts;
+pub mod ty;
 pub mod us;
 pub mod vs;
@@ -0,0 +1,83 @@
+//! Stores incremental storage usage counters for a database
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Sz stands for Database Size.
+// Each Sz key is suffixed by a unique identifier, so that concurrent
+// transactions can record their usage deltas without conflicting.
+// The value is the change in storage usage recorded by a transaction.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Sz<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, id: Uuid) -> Sz<'a> {
+	Sz::new(ns, db, id)
+}
+
+/// Returns the prefix for the whole database usage counters
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!sz\x00");
+	k
+}
+
+/// Returns the suffix for the whole database usage counters
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!sz\xff");
+	k
+}
+
+impl Categorise for Sz<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseStorageUsage
+	}
+}
+
+impl<'a> Sz<'a> {
+	pub fn new(ns: &'a str, db: &'a str, id: Uuid) -> Self {
+		Sz {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b'z',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Sz::new(
+			"testns",
+			"testdb",
+			Uuid::default(),
+		);
+		let enc = Sz::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!sz\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+		let dec = Sz::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
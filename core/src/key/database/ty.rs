@@ -0,0 +1,76 @@
+//! Stores a DEFINE TYPE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Ty<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ty: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ty: &'a str) -> Ty<'a> {
+	Ty::new(ns, db, ty)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!ty\x00");
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!ty\xff");
+	k
+}
+
+impl Categorise for Ty<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseType
+	}
+}
+
+impl<'a> Ty<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ty: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b't',
+			_e: b'y',
+			ty,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ty::new(
+			"testns",
+			"testdb",
+			"testty",
+		);
+		let enc = Ty::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!tytestty\0");
+
+		let dec = Ty::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,146 @@
+//! Stores the records of a spatial index by geohash cell
+use crate::sql::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Gc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub ix: &'a str,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: &'a str,
+	pub id: Id,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub ix: &'a str,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'c',
+		}
+	}
+}
+
+impl<'a> Gc<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, cell: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'c',
+			cell,
+			id,
+		}
+	}
+
+	fn prefix_cell(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+		let mut k = Prefix::new(ns, db, tb, ix).encode().unwrap();
+		k.extend_from_slice(cell.as_bytes());
+		k
+	}
+
+	/// The beginning of the records stored in any cell starting with the given cell
+	pub fn prefix_cells_beg(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+		Self::prefix_cell(ns, db, tb, ix, cell)
+	}
+
+	/// The end of the records stored in any cell starting with the given cell
+	pub fn prefix_cells_end(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+		let mut end = Self::prefix_cell(ns, db, tb, ix, cell);
+		end.extend_from_slice(&[0xff]);
+		end
+	}
+
+	/// The beginning of the records stored in exactly the given cell
+	pub fn prefix_cell_beg(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+		let mut beg = Self::prefix_cell(ns, db, tb, ix, cell);
+		beg.extend_from_slice(&[0x00]);
+		beg
+	}
+
+	/// The end of the records stored in exactly the given cell
+	pub fn prefix_cell_end(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+		let mut end = Self::prefix_cell(ns, db, tb, ix, cell);
+		end.extend_from_slice(&[0x00, 0xff]);
+		end
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let val = Gc::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testix",
+			"u4pru",
+			Id::String("testid".to_string()),
+		);
+		let enc = Gc::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0*testtb\0+testix\0!gcu4pru\0\0\0\0\x01testid\0",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+
+		let dec = Gc::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let beg = Gc::prefix_cells_beg("testns", "testdb", "testtb", "testix", "u4");
+		assert_eq!(beg, b"/*testns\0*testdb\0*testtb\0+testix\0!gcu4");
+		let end = Gc::prefix_cell_end("testns", "testdb", "testtb", "testix", "u4");
+		assert_eq!(end, b"/*testns\0*testdb\0*testtb\0+testix\0!gcu4\0\xff");
+	}
+}
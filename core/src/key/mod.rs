@@ -5,10 +5,15 @@
 /// crate::key::root::all                /
 /// crate::key::root::ac                 /!ac{ac}
 /// crate::key::root::hb                 /!hb{ts}/{nd}
+/// crate::key::root::lk                 /!lk{lk}
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
+/// crate::key::root::qe                 /!qe{qe}
+/// crate::key::root::rl                 /!rl{rl}
+/// crate::key::root::rv                 /!rv{kind}{id}
 /// crate::key::root::us                 /!us{us}
+/// crate::key::root::wh                 /!wh{wh}
 ///
 /// crate::key::node::all                /${nd}
 /// crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
@@ -21,6 +26,8 @@
 /// crate::key::namespace::db            /*{ns}!db{db}
 /// crate::key::namespace::di            /+{ns id}!di
 /// crate::key::namespace::lg            /*{ns}!lg{lg}
+/// crate::key::namespace::rl            /*{ns}!rl{rl}
+/// crate::key::namespace::rv            /*{ns}!rv{kind}{id}
 /// crate::key::namespace::us            /*{ns}!us{us}
 ///
 /// crate::key::namespace::access::all   /*{ns}&{ac}
@@ -32,9 +39,13 @@
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+/// crate::key::database::rv             /*{ns}*{db}!rv{kind}{id}
+/// crate::key::database::sz             /*{ns}*{db}!sz{id}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::ts             /*{ns}*{db}!ts{ts}
+/// crate::key::database::ty             /*{ns}*{db}!ty{ty}
 /// crate::key::database::us             /*{ns}*{db}!us{us}
 /// crate::key::database::vs             /*{ns}*{db}!vs
 /// crate::key::database::cg             /*{ns}*{db}!cg{ty}
@@ -43,11 +54,17 @@
 /// crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
+/// crate::key::table::cn                /*{ns}*{db}*{tb}!cn{id}
+/// crate::key::table::eo                /*{ns}*{db}*{tb}!eo{id}
 /// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
+/// crate::key::table::la                /*{ns}*{db}*{tb}!la{lq}{gr}
 /// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
+/// crate::key::table::sk                /*{ns}*{db}*{tb}!sk{sk}
+/// crate::key::table::so                /*{ns}*{db}*{tb}!so{sk}
+/// crate::key::table::st                /*{ns}*{db}*{tb}!st
 ///
 /// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 /// crate::key::index::bc                /*{ns}*{db}*{tb}+{ix}!bc{id}
@@ -61,6 +78,7 @@
 /// crate::key::index::bs                /*{ns}*{db}*{tb}+{ix}!bs
 /// crate::key::index::bt                /*{ns}*{db}*{tb}+{ix}!bt{id}
 /// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
+/// crate::key::index::gc                /*{ns}*{db}*{tb}+{ix}!gc{cell}{id}
 /// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 ///
 /// crate::key::change                   /*{ns}*{db}#{ts}
@@ -69,6 +87,12 @@
 ///
 /// crate::key::graph                    /*{ns}*{db}*{tb}~{id}{eg}{ft}{fk}
 ///
+/// crate::key::r#ref                    /*{ns}*{db}*{tb}&{id}{ft}{ff}{fk}
+///
+/// crate::key::partition                /*{ns}*{db}*{tb}%{pt}{id}
+///
+/// crate::key::ttl                      /*{ns}*{db}*{tb}^{id}
+///
 pub(crate) mod category;
 pub(crate) mod change;
 pub(crate) mod database;
@@ -77,7 +101,10 @@ pub(crate) mod graph;
 pub(crate) mod index;
 pub(crate) mod namespace;
 pub(crate) mod node;
+pub(crate) mod partition;
+pub(crate) mod r#ref;
 pub(crate) mod root;
 pub(crate) mod table;
 pub(crate) mod thing;
+pub(crate) mod ttl;
 pub(crate) mod version;
@@ -3,4 +3,6 @@ pub mod access;
 pub mod all;
 pub mod db;
 pub mod di;
+pub mod rl;
+pub mod rv;
 pub mod us;
@@ -0,0 +1,85 @@
+//! Stores a REVOKE TOKEN or REVOKE SESSION ON NAMESPACE revocation
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Rv<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub kind: &'a str,
+	pub id: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, kind: &'a str, id: &'a str) -> Rv<'a> {
+	Rv::new(ns, kind, id)
+}
+
+pub fn prefix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(b"!rv\x00");
+	k
+}
+
+pub fn suffix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(b"!rv\xff");
+	k
+}
+
+impl Categorise for Rv<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceRevocation
+	}
+}
+
+impl<'a> Rv<'a> {
+	pub fn new(ns: &'a str, kind: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'r',
+			_d: b'v',
+			kind,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rv::new(
+			"testns",
+			"token",
+			"testid",
+		);
+		let enc = Rv::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!rvtoken\x00testid\x00");
+		let dec = Rv::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns");
+		assert_eq!(val, b"/*testns\0!rv\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns");
+		assert_eq!(val, b"/*testns\0!rv\xff");
+	}
+}
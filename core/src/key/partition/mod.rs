@@ -0,0 +1,159 @@
+//! Stores the partition membership of a record in a partitioned table
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::id::Id;
+use crate::sql::number::Number;
+use crate::sql::value::Value;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct TablePrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+}
+
+impl<'a> TablePrefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'%',
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub pt: Value,
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, pt: &Value) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'%',
+			pt: pt.to_owned(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Partition<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub pt: Value,
+	pub id: Id,
+}
+
+/// Numbers which are equal in value are stored in the same partition
+fn normalise(pt: &Value) -> Value {
+	match pt {
+		Value::Number(v) => Value::Number(Number::Float(v.to_float())),
+		v => v.to_owned(),
+	}
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, pt: &Value, id: &Id) -> Partition<'a> {
+	Partition::new(ns, db, tb, normalise(pt), id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, pt: &Value) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, &normalise(pt)).encode().unwrap();
+	k.extend_from_slice(&[0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, pt: &Value) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, &normalise(pt)).encode().unwrap();
+	k.extend_from_slice(&[0xff]);
+	k
+}
+
+pub fn table_prefix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = TablePrefix::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(&[0x00]);
+	k
+}
+
+pub fn table_suffix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = TablePrefix::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(&[0xff]);
+	k
+}
+
+impl Categorise for Partition<'_> {
+	fn categorise(&self) -> Category {
+		Category::Partition
+	}
+}
+
+impl<'a> Partition<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, pt: Value, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'%',
+			pt,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Partition::new(
+			"testns",
+			"testdb",
+			"testtb",
+			Value::from("testpt"),
+			"testid".into(),
+		);
+		let enc = Partition::encode(&val).unwrap();
+		assert!(enc.starts_with(b"/*testns\0*testdb\0*testtb\0%"));
+
+		let dec = Partition::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
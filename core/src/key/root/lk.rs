@@ -0,0 +1,66 @@
+//! Stores a distributed advisory lock
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Lk<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub lk: &'a str,
+}
+
+pub fn new(lk: &str) -> Lk<'_> {
+	Lk::new(lk)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!lk\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!lk\xff");
+	k
+}
+
+impl Categorise for Lk<'_> {
+	fn categorise(&self) -> Category {
+		Category::Lock
+	}
+}
+
+impl<'a> Lk<'a> {
+	pub fn new(lk: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'l',
+			_c: b'k',
+			lk,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Lk::new(
+			"testlk",
+		);
+		let enc = Lk::encode(&val).unwrap();
+		assert_eq!(enc, b"/!lktestlk\x00");
+
+		let dec = Lk::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
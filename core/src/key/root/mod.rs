@@ -1,7 +1,12 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod lk;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod qe;
+pub mod rl;
+pub mod rv;
 pub mod us;
+pub mod wh;
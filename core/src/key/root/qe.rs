@@ -0,0 +1,75 @@
+//! Stores an entry in the asynchronous event queue
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Qe {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub qe: Uuid,
+}
+
+pub fn new(qe: Uuid) -> Qe {
+	Qe::new(qe)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!qe\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!qe\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00");
+	k
+}
+
+impl Categorise for Qe {
+	fn categorise(&self) -> Category {
+		Category::EventQueue
+	}
+}
+
+impl Qe {
+	pub fn new(qe: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'q',
+			_c: b'e',
+			qe,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Qe::new(Uuid::default());
+		let enc = Qe::encode(&val).unwrap();
+		let dec = Qe::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!qe\0")
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix();
+		assert_eq!(val, b"/!qe\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00")
+	}
+}
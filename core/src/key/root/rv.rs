@@ -0,0 +1,77 @@
+//! Stores a REVOKE TOKEN or REVOKE SESSION ON ROOT revocation
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Rv<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub kind: &'a str,
+	pub id: &'a str,
+}
+
+pub fn new<'a>(kind: &'a str, id: &'a str) -> Rv<'a> {
+	Rv::new(kind, id)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!rv\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!rv\xff");
+	k
+}
+
+impl Categorise for Rv<'_> {
+	fn categorise(&self) -> Category {
+		Category::Revocation
+	}
+}
+
+impl<'a> Rv<'a> {
+	pub fn new(kind: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'v',
+			kind,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rv::new("token", "testid");
+		let enc = Rv::encode(&val).unwrap();
+		assert_eq!(enc, b"/!rvtoken\x00testid\x00");
+		let dec = Rv::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!rv\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix();
+		assert_eq!(val, b"/!rv\xff");
+	}
+}
@@ -0,0 +1,75 @@
+//! Stores an entry in the webhook delivery queue
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Wh {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub wh: Uuid,
+}
+
+pub fn new(wh: Uuid) -> Wh {
+	Wh::new(wh)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!wh\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!wh\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00");
+	k
+}
+
+impl Categorise for Wh {
+	fn categorise(&self) -> Category {
+		Category::WebhookQueue
+	}
+}
+
+impl Wh {
+	pub fn new(wh: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'w',
+			_c: b'h',
+			wh,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Wh::new(Uuid::default());
+		let enc = Wh::encode(&val).unwrap();
+		let dec = Wh::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!wh\0")
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix();
+		assert_eq!(val, b"/!wh\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00")
+	}
+}
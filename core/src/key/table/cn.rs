@@ -0,0 +1,88 @@
+//! Stores incremental row counters for a table
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Cn stands for Table Count.
+// Each Cn key is suffixed by a unique identifier, so that concurrent
+// transactions can record their row count deltas without conflicting.
+// The value is the change in the number of rows recorded by a transaction.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Cn<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, id: Uuid) -> Cn<'a> {
+	Cn::new(ns, db, tb, id)
+}
+
+/// Returns the prefix for the whole table row counters
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!cn\x00");
+	k
+}
+
+/// Returns the suffix for the whole table row counters
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!cn\xff");
+	k
+}
+
+impl Categorise for Cn<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableCount
+	}
+}
+
+impl<'a> Cn<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, id: Uuid) -> Self {
+		Cn {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'c',
+			_f: b'n',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Cn::new(
+			"testns",
+			"testdb",
+			"testtb",
+			Uuid::default(),
+		);
+		let enc = Cn::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!cn\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+		let dec = Cn::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,72 @@
+//! Stores the order of the asynchronous events queued for a record
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::id::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+// Eo stands for Event Order.
+// Each Eo key tracks how many asynchronous events have been queued and
+// processed for a single record, so that they run in commit order.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Eo<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, id: &Id) -> Eo<'a> {
+	Eo::new(ns, db, tb, id.to_owned())
+}
+
+impl Categorise for Eo<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableEventOrder
+	}
+}
+
+impl<'a> Eo<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, id: Id) -> Self {
+		Eo {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'e',
+			_f: b'o',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Eo::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testid".into(),
+		);
+		let enc = Eo::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!eo\0\0\0\x01testid\0");
+		let dec = Eo::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,94 @@
+//! Stores the state of a group of a grouped LIVE SELECT query
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// La stands for Live Aggregate.
+// Each La key stores the running aggregates of a single group of a live
+// query with a GROUP clause, so that they can be maintained incrementally.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct La<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+	pub gr: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid, gr: &'a str) -> La<'a> {
+	La::new(ns, db, tb, lq, gr)
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, lq: Uuid) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!la");
+	k.extend_from_slice(lq.as_bytes());
+	k
+}
+
+impl Categorise for La<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableLiveAggregate
+	}
+}
+
+impl<'a> La<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, lq: Uuid, gr: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'l',
+			_f: b'a',
+			lq,
+			gr,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = La::new("testns", "testdb", "testtb", live_query_id, "['open']");
+		let enc = La::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!la\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10['open']\x00"
+		);
+
+		let dec = La::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		use super::*;
+		let live_query_id =
+			Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = super::prefix("testns", "testdb", "testtb", live_query_id);
+		assert_eq!(
+			val,
+			b"/*testns\x00*testdb\x00*testtb\x00!la\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		)
+	}
+}
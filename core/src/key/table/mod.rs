@@ -1,6 +1,12 @@
 pub mod all;
+pub mod cn;
+pub mod eo;
 pub mod ev;
 pub mod fd;
 pub mod ft;
 pub mod ix;
+pub mod la;
 pub mod lq;
+pub mod sk;
+pub mod so;
+pub mod st;
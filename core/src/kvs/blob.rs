@@ -0,0 +1,337 @@
+//! Transparent chunked storage for large values.
+//!
+//! Values which are larger than the configured chunk size are split into
+//! multiple chunks, which are stored in a dedicated blob keyspace. The
+//! original key then stores a small manifest record, which identifies the
+//! chunks, and which is replaced with the reassembled value when read.
+//! Each write of a large value uses a new blob identifier, so chunks which
+//! are no longer referenced by their owning key, because the key has been
+//! overwritten or deleted, are removed during garbage collection.
+//!
+//! The blob keys are structured as follows:
+//!
+//! ```text
+//! \xff!bl{id}          the key which owns the blob
+//! \xff!bl{id}{index}   a chunk of the blob value
+//! ```
+//!
+//! When a blob store is configured, large values are instead written as a
+//! single object, named by the blob identifier, to the blob store. The
+//! owner key is still stored in the datastore, so that objects which are
+//! no longer referenced, or which were written by a transaction which was
+//! never committed, can be removed during garbage collection.
+use crate::cnf::{BLOB_CHUNK_SIZE, NORMAL_FETCH_SIZE};
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType::*, TransactionType::*};
+use uuid::Uuid;
+
+/// The prefix of the blob keyspace
+const PREFIX: &[u8] = b"\xff!bl";
+
+/// The marker which identifies a stored value as a blob manifest
+const MAGIC: &[u8] = b"\x00\xff!blob\x00";
+
+/// The marker which identifies a blob manifest for a value in the blob store
+const EXTERNAL: &[u8] = b"\x00\xff!blob\x01";
+
+/// How long an object in the blob store may exist without an owner key
+#[cfg(feature = "opendal")]
+const ORPHAN_AGE: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// The encoded length of a blob manifest
+const MANIFEST_LEN: usize = MAGIC.len() + 16 + 8 + 4;
+
+/// Encode the key which records the owner of a blob
+pub(super) fn owner(id: Uuid) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + 16);
+	k.extend_from_slice(PREFIX);
+	k.extend_from_slice(id.as_bytes());
+	k
+}
+
+/// Encode the key for a chunk of a blob
+///
+/// The chunk index follows a non-zero separator, as the in-memory engine
+/// terminates keys with a zero byte, which would otherwise make the owner
+/// key a prefix of the keys of the first chunks.
+pub(super) fn chunk(id: Uuid, idx: u32) -> Key {
+	let mut k = owner(id);
+	k.push(b'*');
+	k.extend_from_slice(&idx.to_be_bytes());
+	k
+}
+
+/// The path of the object which stores a blob in the blob store
+#[cfg(feature = "opendal")]
+pub(super) fn path(id: Uuid) -> String {
+	id.simple().to_string()
+}
+
+/// Check if a key belongs to the blob keyspace
+pub(super) fn internal(key: &[u8]) -> bool {
+	key.starts_with(PREFIX)
+}
+
+/// Check if a value is large enough to be stored in chunks
+pub(super) fn exceeds(val: &Val) -> bool {
+	*BLOB_CHUNK_SIZE > 0 && val.len() > *BLOB_CHUNK_SIZE
+}
+
+/// The record stored in place of a large value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) struct Manifest {
+	/// The unique identifier of this blob
+	pub id: Uuid,
+	/// The total length of the value
+	pub len: u64,
+	/// The number of chunks in the value
+	pub chunks: u32,
+	/// Whether the value is stored in the blob store
+	pub external: bool,
+}
+
+impl Manifest {
+	/// Encode this manifest into a stored value
+	pub(super) fn encode(&self) -> Val {
+		let mut v = Vec::with_capacity(MANIFEST_LEN);
+		v.extend_from_slice(if self.external {
+			EXTERNAL
+		} else {
+			MAGIC
+		});
+		v.extend_from_slice(self.id.as_bytes());
+		v.extend_from_slice(&self.len.to_be_bytes());
+		v.extend_from_slice(&self.chunks.to_be_bytes());
+		v
+	}
+	/// Decode a stored value, if it is a manifest
+	pub(super) fn decode(v: &[u8]) -> Option<Self> {
+		if v.len() != MANIFEST_LEN {
+			return None;
+		}
+		let (v, external) = match v.strip_prefix(MAGIC) {
+			Some(v) => (v, false),
+			None => (v.strip_prefix(EXTERNAL)?, true),
+		};
+		let (id, v) = v.split_at(16);
+		let (len, chunks) = v.split_at(8);
+		Some(Self {
+			id: Uuid::from_slice(id).ok()?,
+			len: u64::from_be_bytes(len.try_into().ok()?),
+			chunks: u32::from_be_bytes(chunks.try_into().ok()?),
+			external,
+		})
+	}
+	/// The range of keys containing the chunks of this blob
+	pub(super) fn range(&self) -> std::ops::Range<Key> {
+		chunk(self.id, 0)..chunk(self.id, self.chunks)
+	}
+}
+
+/// Split a large value into a manifest, and the chunks to store
+pub(super) fn split(val: &[u8]) -> (Manifest, Vec<(Key, Val)>) {
+	let id = Uuid::now_v7();
+	let chunks: Vec<(Key, Val)> = val
+		.chunks(*BLOB_CHUNK_SIZE)
+		.enumerate()
+		.map(|(i, c)| (chunk(id, i as u32), c.to_vec()))
+		.collect();
+	let manifest = Manifest {
+		id,
+		len: val.len() as u64,
+		chunks: chunks.len() as u32,
+		external: false,
+	};
+	(manifest, chunks)
+}
+
+/// Create a manifest for a large value which is written to the blob store
+#[cfg(feature = "opendal")]
+pub(super) fn external(val: &[u8]) -> Manifest {
+	Manifest {
+		id: Uuid::now_v7(),
+		len: val.len() as u64,
+		chunks: 1,
+		external: true,
+	}
+}
+
+/// Reassemble a large value from its stored chunks
+pub(super) fn join(manifest: &Manifest, chunks: Vec<(Key, Val)>) -> Result<Val, Error> {
+	if chunks.len() != manifest.chunks as usize {
+		return Err(Error::Internal(format!("Missing chunks for blob {}", manifest.id)));
+	}
+	let mut v = Vec::with_capacity(manifest.len as usize);
+	for (_, c) in chunks {
+		v.extend_from_slice(&c);
+	}
+	check(manifest, v)
+}
+
+/// Ensure that a reassembled value has the length recorded in its manifest
+pub(super) fn check(manifest: &Manifest, val: Val) -> Result<Val, Error> {
+	match val.len() as u64 == manifest.len {
+		true => Ok(val),
+		false => Err(Error::Internal(format!("Invalid length for blob {}", manifest.id))),
+	}
+}
+
+impl Datastore {
+	/// Deletes all blob chunks which are no longer referenced by their owning key.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn blob_cleanup(&self) -> Result<(), Error> {
+		// Create a new transaction
+		let mut txn = self.internal_transaction(Write, Optimistic).await?.inner();
+		// Iterate over the blob keyspace
+		let mut end = PREFIX.to_vec();
+		end.push(0xff);
+		let mut next = Some(PREFIX.to_vec()..end);
+		while let Some(rng) = next {
+			let res = catch!(txn, txn.batch(rng, *NORMAL_FETCH_SIZE, true, None).await);
+			next = res.next;
+			for (k, v) in res.values.iter() {
+				// Only check the owner entry of each blob
+				if k.len() != PREFIX.len() + 16 {
+					continue;
+				}
+				let id = match Uuid::from_slice(&k[PREFIX.len()..]) {
+					Ok(id) => id,
+					Err(_) => continue,
+				};
+				// Check whether the owning key still references this blob
+				let current = catch!(txn, txn.get_raw(v.clone()).await);
+				let referenced =
+					current.as_deref().and_then(Manifest::decode).is_some_and(|m| m.id == id);
+				if !referenced {
+					// Delete the blob chunks and owner entry
+					catch!(txn, txn.delr(chunk(id, 0)..chunk(id, u32::MAX)).await);
+					catch!(txn, txn.del(k.clone()).await);
+					// Delete the blob from the blob store
+					#[cfg(feature = "opendal")]
+					if let Some(store) = self.blob_store() {
+						catch!(txn, store.delete(&path(id)).await);
+					}
+				}
+			}
+		}
+		// Delete objects which were written by uncommitted transactions
+		#[cfg(feature = "opendal")]
+		if let Some(store) = self.blob_store() {
+			let now = std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap_or_default()
+				.as_secs();
+			for name in catch!(txn, store.list("/").await) {
+				let Ok(id) = Uuid::try_parse(&name) else {
+					continue;
+				};
+				// Skip objects which may belong to a running transaction
+				let Some((secs, _)) = id.get_timestamp().map(|ts| ts.to_unix()) else {
+					continue;
+				};
+				if now.saturating_sub(secs) < ORPHAN_AGE.as_secs() {
+					continue;
+				}
+				// Delete the object if it has no owner
+				if catch!(txn, txn.get_raw(owner(id)).await).is_none() {
+					catch!(txn, store.delete(&name).await);
+				}
+			}
+		}
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn manifest() {
+		let m = Manifest {
+			id: Uuid::now_v7(),
+			len: 5 << 20,
+			chunks: 5,
+			external: false,
+		};
+		let enc = m.encode();
+		assert_eq!(Manifest::decode(&enc), Some(m));
+		let m = Manifest {
+			external: true,
+			..m
+		};
+		assert_eq!(Manifest::decode(&m.encode()), Some(m));
+		assert_eq!(Manifest::decode(b"some text"), None);
+	}
+
+	#[test]
+	fn split_and_join() {
+		let val: Val = (0..*BLOB_CHUNK_SIZE * 2 + 10).map(|i| i as u8).collect();
+		let (manifest, chunks) = split(&val);
+		assert_eq!(manifest.chunks, 3);
+		assert!(chunks.iter().all(|(k, _)| manifest.range().contains(k)));
+		assert_eq!(join(&manifest, chunks).unwrap(), val);
+	}
+
+	#[cfg(feature = "kv-mem")]
+	#[tokio::test]
+	async fn large_values() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let val: Val = vec![7u8; *BLOB_CHUNK_SIZE * 3 + 1];
+		let rng = || PREFIX.to_vec()..[PREFIX, &[0xff][..]].concat();
+		// Large values are stored in chunks
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set("test", val.clone(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("test", None).await.unwrap(), Some(val));
+		assert_eq!(tx.getr(rng(), None).await.unwrap().len(), 5);
+		tx.cancel().await.unwrap();
+		// Chunks of overwritten values are removed
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set("test", "small", None).await.unwrap();
+		tx.commit().await.unwrap();
+		ds.blob_cleanup().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("test", None).await.unwrap(), Some(b"small".to_vec()));
+		assert!(tx.getr(rng(), None).await.unwrap().is_empty());
+		tx.cancel().await.unwrap();
+	}
+
+	#[cfg(all(feature = "kv-mem", feature = "opendal"))]
+	#[tokio::test]
+	async fn large_values_in_blob_store() {
+		use crate::kvs::{DatastoreBuilder, Engine};
+		let ds = DatastoreBuilder::new(Engine::Memory)
+			.with_blob_store("memory://")
+			.build()
+			.await
+			.unwrap();
+		let store = ds.blob_store().unwrap().clone();
+		let val: Val = vec![7u8; *BLOB_CHUNK_SIZE * 3 + 1];
+		let rng = || PREFIX.to_vec()..[PREFIX, &[0xff][..]].concat();
+		// Large values are written to the blob store
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set("test", val.clone(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("test", None).await.unwrap(), Some(val));
+		assert_eq!(tx.getr(rng(), None).await.unwrap().len(), 1);
+		tx.cancel().await.unwrap();
+		assert_eq!(store.list("/").await.unwrap().len(), 1);
+		// Objects of overwritten values are removed
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set("test", "small", None).await.unwrap();
+		tx.commit().await.unwrap();
+		ds.blob_cleanup().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.getr(rng(), None).await.unwrap().is_empty());
+		tx.cancel().await.unwrap();
+		assert!(store.list("/").await.unwrap().is_empty());
+	}
+}
@@ -0,0 +1,137 @@
+//! Buffered transactions for storage engines without interactive transactions.
+//!
+//! Some storage engines can only apply a set of writes atomically, and do
+//! not support long-running transactions with isolated reads. For these
+//! storage engines, all writes within a transaction are buffered in memory,
+//! and every read first checks the buffered writes, before falling back to
+//! the storage engine. The value of each key which is read from the storage
+//! engine is recorded, so that repeated reads of the same key return the
+//! same value, and so that the transaction can be validated when it is
+//! committed. If any key which was read has since been modified by another
+//! transaction, then the commit fails with a retryable conflict. Keys which
+//! are written without first being read use last-write-wins semantics, and
+//! range scans are not validated on commit.
+use crate::kvs::{Key, Val};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Bound, Range};
+
+/// The buffered reads and writes of a single transaction
+#[derive(Default)]
+pub(super) struct Buffer {
+	/// The latest value written to each key, or None if deleted
+	writes: BTreeMap<Key, Option<Val>>,
+	/// The value of each key when it was first read from the storage engine
+	reads: HashMap<Key, Option<Val>>,
+	/// The buffered writes at each save point
+	save_points: Vec<BTreeMap<Key, Option<Val>>>,
+}
+
+impl Buffer {
+	/// Fetch the value of a key which was already written or read
+	pub(super) fn get(&self, key: &[u8]) -> Option<Option<Val>> {
+		self.writes.get(key).or_else(|| self.reads.get(key)).cloned()
+	}
+	/// Record the value of a key which was read from the storage engine
+	pub(super) fn read(&mut self, key: Key, val: Option<Val>) {
+		self.reads.entry(key).or_insert(val);
+	}
+	/// Buffer a write of a value to a key
+	pub(super) fn set(&mut self, key: Key, val: Val) {
+		self.writes.insert(key, Some(val));
+	}
+	/// Buffer the deletion of a key
+	pub(super) fn del(&mut self, key: Key) {
+		self.writes.insert(key, None);
+	}
+	/// The number of buffered writes within a range of keys
+	pub(super) fn count(&self, rng: Range<&[u8]>) -> usize {
+		self.writes.range::<[u8], _>(bounds(&rng)).count()
+	}
+	/// Overlay the buffered writes onto key-value pairs scanned from the storage engine.
+	///
+	/// The scanned pairs should be fetched with a limit which is increased by
+	/// the number of buffered writes within the range, so that the result is
+	/// complete up to the limit, even when buffered deletes are removed.
+	pub(super) fn merge(
+		&self,
+		rng: Range<&[u8]>,
+		scanned: Vec<(Key, Val)>,
+		limit: usize,
+	) -> Vec<(Key, Val)> {
+		let mut out: BTreeMap<Key, Val> = scanned.into_iter().collect();
+		for (k, v) in self.writes.range::<[u8], _>(bounds(&rng)) {
+			match v {
+				Some(v) => out.insert(k.clone(), v.clone()),
+				None => out.remove(k),
+			};
+		}
+		out.into_iter().take(limit).collect()
+	}
+	/// Check if any writes have been buffered
+	pub(super) fn is_empty(&self) -> bool {
+		self.writes.is_empty()
+	}
+	/// Take the keys which were read, and the writes to apply on commit
+	#[allow(clippy::type_complexity)]
+	pub(super) fn drain(&mut self) -> (HashMap<Key, Option<Val>>, BTreeMap<Key, Option<Val>>) {
+		self.save_points.clear();
+		(std::mem::take(&mut self.reads), std::mem::take(&mut self.writes))
+	}
+	/// Mark the current position, so that later writes can be discarded
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.writes.clone());
+	}
+	/// Discard any writes made since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(writes) = self.save_points.pop() {
+			self.writes = writes;
+		}
+	}
+	/// Keep any writes made since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+}
+
+/// Convert a range of key slices into bounds for an ordered map
+fn bounds<'a>(rng: &Range<&'a [u8]>) -> (Bound<&'a [u8]>, Bound<&'a [u8]>) {
+	(Bound::Included(rng.start), Bound::Excluded(rng.end))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reads_see_writes() {
+		let mut b = Buffer::default();
+		b.read(b"a".to_vec(), Some(b"1".to_vec()));
+		assert_eq!(b.get(b"a"), Some(Some(b"1".to_vec())));
+		b.del(b"a".to_vec());
+		assert_eq!(b.get(b"a"), Some(None));
+		assert_eq!(b.get(b"b"), None);
+	}
+
+	#[test]
+	fn merge_scanned() {
+		let mut b = Buffer::default();
+		b.set(b"b".to_vec(), b"2".to_vec());
+		b.del(b"c".to_vec());
+		b.set(b"z".to_vec(), b"9".to_vec());
+		let rng = b"a".as_slice()..b"y".as_slice();
+		assert_eq!(b.count(rng.clone()), 2);
+		let scanned = vec![(b"a".to_vec(), b"1".to_vec()), (b"c".to_vec(), b"3".to_vec())];
+		let res = b.merge(rng, scanned, 10);
+		assert_eq!(res, vec![(b"a".to_vec(), b"1".to_vec()), (b"b".to_vec(), b"2".to_vec())]);
+	}
+
+	#[test]
+	fn save_points() {
+		let mut b = Buffer::default();
+		b.set(b"a".to_vec(), b"1".to_vec());
+		b.new_save_point();
+		b.set(b"a".to_vec(), b"2".to_vec());
+		b.rollback_to_save_point();
+		assert_eq!(b.get(b"a"), Some(Some(b"1".to_vec())));
+	}
+}
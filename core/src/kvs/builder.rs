@@ -0,0 +1,360 @@
+//! A typed builder for configuring and creating a [`Datastore`].
+//!
+//! The string form accepted by [`Datastore::new`] (for example
+//! `rocksdb://path/to/db`) is parsed into a [`DatastoreBuilder`], so
+//! both construction methods share the same underlying configuration.
+use crate::err::Error;
+use crate::kvs::clock::SizedClock;
+use crate::kvs::Datastore;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The storage engine used by a datastore
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Engine {
+	/// An in-memory datastore
+	#[default]
+	Memory,
+	/// A RocksDB datastore on the local filesystem
+	RocksDb,
+	/// A SurrealKV datastore on the local filesystem
+	SurrealKv,
+	/// A remote SurrealCS datastore
+	SurrealCs,
+	/// An IndxDB datastore in the browser
+	IndxDb,
+	/// A remote TiKV cluster
+	TiKv,
+	/// A remote FoundationDB cluster
+	FoundationDb,
+	/// A sled datastore on the local filesystem
+	Sled,
+	/// An LMDB datastore on the local filesystem
+	Lmdb,
+	/// A SQLite datastore on the local filesystem
+	Sqlite,
+	/// A Postgres database cluster
+	Postgres,
+	/// An experimental datastore in an S3 bucket, for infrequently written data
+	S3,
+	/// A DynamoDB table
+	DynamoDb,
+	/// A Redis server, for ephemeral or cache-style data
+	Redis,
+	/// An in-memory cache in front of a RocksDB datastore on the local filesystem
+	Tiered,
+	/// An OPFS datastore in the browser
+	Opfs,
+	/// A meta engine which routes namespaces to other storage engines
+	Shard,
+	/// A storage engine registered with [`Datastore::register_engine`]
+	External(String),
+}
+
+impl Engine {
+	/// The url scheme which identifies this storage engine
+	pub fn scheme(&self) -> &str {
+		match self {
+			Self::Memory => "memory",
+			Self::RocksDb => "rocksdb",
+			Self::SurrealKv => "surrealkv",
+			Self::SurrealCs => "surrealcs",
+			Self::IndxDb => "indxdb",
+			Self::TiKv => "tikv",
+			Self::FoundationDb => "fdb",
+			Self::Sled => "sled",
+			Self::Lmdb => "lmdb",
+			Self::Sqlite => "sqlite",
+			Self::Postgres => "postgres",
+			Self::S3 => "s3",
+			Self::DynamoDb => "dynamodb",
+			Self::Redis => "redis",
+			Self::Tiered => "tiered",
+			Self::Opfs => "opfs",
+			Self::Shard => "shard",
+			Self::External(name) => name,
+		}
+	}
+}
+
+impl fmt::Display for Engine {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.scheme())
+	}
+}
+
+/// A builder for configuring and creating a [`Datastore`]
+///
+/// ```rust,no_run
+/// use surrealdb_core::kvs::{DatastoreBuilder, Engine};
+/// use surrealdb_core::err::Error;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let ds = DatastoreBuilder::new(Engine::RocksDb)
+///         .with_path("path/to/database.db")
+///         .with_cache_size(512 * 1024 * 1024)
+///         .with_strict_mode(true)
+///         .with_query_timeout(Duration::from_secs(30))
+///         .build()
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub struct DatastoreBuilder {
+	// The storage engine to use
+	pub(super) engine: Engine,
+	// The path or address of the storage engine
+	pub(super) path: String,
+	// The size of the storage engine block cache in bytes
+	pub(super) cache_size: Option<usize>,
+	// Whether write transactions are disallowed
+	pub(super) read_only: bool,
+	// The path of a secondary instance attached to a datastore held by another process
+	pub(super) secondary: Option<String>,
+	// The key used to encrypt the data at rest
+	pub(super) encryption_key: Option<Vec<u8>>,
+	// Whether the datastore runs in strict mode by default
+	pub(super) strict: bool,
+	// Whether storage usage is tracked for each database
+	pub(super) storage_usage: bool,
+	// The maximum on-disk size of the datastore in bytes
+	pub(super) storage_quota: Option<u64>,
+	// The URL of the object store in which large values are stored
+	pub(super) blob_store: Option<String>,
+	// The maximum duration for running a query
+	pub(super) query_timeout: Option<Duration>,
+	// The maximum duration for running a transaction
+	pub(super) transaction_timeout: Option<Duration>,
+	// The maximum number of rows a SELECT statement can process
+	pub(super) query_row_limit: Option<u64>,
+	// The clock used for tracking time
+	pub(super) clock: Option<Arc<SizedClock>>,
+}
+
+impl fmt::Debug for DatastoreBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DatastoreBuilder")
+			.field("engine", &self.engine)
+			.field("path", &self.path)
+			.field("cache_size", &self.cache_size)
+			.field("read_only", &self.read_only)
+			.field("secondary", &self.secondary)
+			.field("encryption_key", &self.encryption_key.as_ref().map(|_| "[REDACTED]"))
+			.field("strict", &self.strict)
+			.field("storage_usage", &self.storage_usage)
+			.field("storage_quota", &self.storage_quota)
+			.field("blob_store", &self.blob_store)
+			.field("query_timeout", &self.query_timeout)
+			.field("transaction_timeout", &self.transaction_timeout)
+			.field("query_row_limit", &self.query_row_limit)
+			.finish()
+	}
+}
+
+impl fmt::Display for DatastoreBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.engine {
+			Engine::Memory => f.write_str("memory"),
+			e => write!(f, "{e}://{}", self.path),
+		}
+	}
+}
+
+impl DatastoreBuilder {
+	/// Create a new builder for the specified storage engine
+	pub fn new(engine: Engine) -> Self {
+		Self {
+			engine,
+			storage_usage: *crate::cnf::STORAGE_USAGE_ENABLED,
+			storage_quota: Some(*crate::cnf::STORAGE_QUOTA).filter(|v| *v > 0),
+			blob_store: Some(crate::cnf::BLOB_STORE.clone()).filter(|v| !v.is_empty()),
+			..Default::default()
+		}
+	}
+
+	/// Parse a datastore connection string into a builder
+	pub fn from_path(path: &str) -> Result<Self, Error> {
+		// The in-memory datastore has no path
+		if path == "memory" {
+			return Ok(Self::new(Engine::Memory));
+		}
+		// Split the storage engine scheme from the path
+		let Some((scheme, rest)) = path.split_once(':') else {
+			return Err(Error::Ds("Unable to load the specified datastore".into()));
+		};
+		let engine = match scheme {
+			"file" => {
+				warn!("file:// is deprecated, please use surrealkv:// or rocksdb://");
+				Engine::RocksDb
+			}
+			"rocksdb" => Engine::RocksDb,
+			"surrealkv" => Engine::SurrealKv,
+			"surrealcs" => Engine::SurrealCs,
+			"indxdb" => Engine::IndxDb,
+			"tikv" => Engine::TiKv,
+			"fdb" => Engine::FoundationDb,
+			"sled" => Engine::Sled,
+			"lmdb" => Engine::Lmdb,
+			"sqlite" => Engine::Sqlite,
+			"postgres" => Engine::Postgres,
+			"s3" => Engine::S3,
+			"dynamodb" => Engine::DynamoDb,
+			"redis" => Engine::Redis,
+			"tiered" => Engine::Tiered,
+			"opfs" => Engine::Opfs,
+			"shard" => Engine::Shard,
+			s if super::external::exists(s) => Engine::External(s.to_owned()),
+			_ => return Err(Error::Ds("Unable to load the specified datastore".into())),
+		};
+		let rest = rest.strip_prefix("//").unwrap_or(rest);
+		Ok(Self::new(engine).with_path(rest))
+	}
+
+	/// Set the path or address of the storage engine
+	pub fn with_path(mut self, path: impl Into<String>) -> Self {
+		self.path = path.into();
+		self
+	}
+
+	/// Set the size of the storage engine block cache in bytes
+	pub fn with_cache_size(mut self, size: usize) -> Self {
+		self.cache_size = Some(size);
+		self
+	}
+
+	/// Specify whether write transactions should be disallowed
+	pub fn with_read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
+	/// Attach as a read-only secondary instance to a datastore held by another process
+	///
+	/// The secondary instance stores its own log files at the specified
+	/// path, and periodically catches up with the commits made by the
+	/// process which holds the datastore open. This is only supported by
+	/// the `rocksdb` storage engine, and implies [`Self::with_read_only`].
+	pub fn with_secondary(mut self, path: impl Into<String>) -> Self {
+		self.secondary = Some(path.into());
+		self
+	}
+
+	/// Set the key used to encrypt the data at rest
+	///
+	/// None of the built-in storage engines support encryption at rest
+	/// yet, so [`Self::build`] returns an error naming the storage engine
+	/// when a key is set, rather than storing the data unencrypted.
+	pub fn with_encryption_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+		self.encryption_key = Some(key.into());
+		self
+	}
+
+	/// Specify whether the datastore should run in strict mode
+	pub fn with_strict_mode(mut self, strict: bool) -> Self {
+		self.strict = strict;
+		self
+	}
+
+	/// Specify whether storage usage should be tracked for each database
+	pub fn with_storage_usage(mut self, enabled: bool) -> Self {
+		self.storage_usage = enabled;
+		self
+	}
+
+	/// Set the maximum on-disk size of the datastore in bytes
+	///
+	/// Once the datastore exceeds this size, any write transaction which
+	/// attempts to store data fails with [`Error::StorageQuotaExceeded`].
+	/// Data can still be deleted, in order to reclaim space.
+	pub fn with_storage_quota(mut self, bytes: u64) -> Self {
+		self.storage_quota = Some(bytes);
+		self
+	}
+
+	/// Set the URL of an object store in which large values are stored
+	///
+	/// Values which are larger than the blob chunk size are written to the
+	/// object store, instead of being split into chunks in the datastore.
+	/// Supported URLs include `s3://bucket/path`, `gcs://bucket/path`,
+	/// `azblob://container/path`, `file:///path`, and `memory://`. This
+	/// requires the `opendal` feature.
+	pub fn with_blob_store(mut self, url: impl Into<String>) -> Self {
+		self.blob_store = Some(url.into());
+		self
+	}
+
+	/// Set a global query timeout for the datastore
+	pub fn with_query_timeout(mut self, duration: Duration) -> Self {
+		self.query_timeout = Some(duration);
+		self
+	}
+
+	/// Set a global transaction timeout for the datastore
+	pub fn with_transaction_timeout(mut self, duration: Duration) -> Self {
+		self.transaction_timeout = Some(duration);
+		self
+	}
+
+	/// Set the maximum number of rows a SELECT statement can process
+	pub fn with_query_row_limit(mut self, limit: u64) -> Self {
+		self.query_row_limit = Some(limit);
+		self
+	}
+
+	/// Set the clock used for tracking time
+	pub(crate) fn with_clock(mut self, clock: Option<Arc<SizedClock>>) -> Self {
+		self.clock = clock;
+		self
+	}
+
+	/// Create the datastore with the specified configuration
+	pub async fn build(self) -> Result<Datastore, Error> {
+		Datastore::new_with_builder(self).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_paths() {
+		let b = DatastoreBuilder::from_path("memory").unwrap();
+		assert_eq!(b.engine, Engine::Memory);
+		let b = DatastoreBuilder::from_path("rocksdb://path/to/db").unwrap();
+		assert_eq!((&b.engine, b.path.as_str()), (&Engine::RocksDb, "path/to/db"));
+		let b = DatastoreBuilder::from_path("file:path/to/db").unwrap();
+		assert_eq!((&b.engine, b.path.as_str()), (&Engine::RocksDb, "path/to/db"));
+		let b = DatastoreBuilder::from_path("tikv://127.0.0.1:2379").unwrap();
+		assert_eq!((&b.engine, b.path.as_str()), (&Engine::TiKv, "127.0.0.1:2379"));
+		assert_eq!(b.to_string(), "tikv://127.0.0.1:2379");
+		assert!(DatastoreBuilder::from_path("unknown://path").is_err());
+		assert!(DatastoreBuilder::from_path("path").is_err());
+	}
+
+	#[tokio::test]
+	async fn encryption_key() {
+		let res = DatastoreBuilder::new(Engine::Memory).with_encryption_key("secret").build().await;
+		match res {
+			Err(Error::Ds(e)) => {
+				assert_eq!(e, "The `memory` storage engine does not support encryption at rest")
+			}
+			_ => panic!("expected encryption at rest to be unsupported"),
+		}
+	}
+
+	#[cfg(feature = "kv-mem")]
+	#[tokio::test]
+	async fn read_only() {
+		use crate::kvs::{LockType::*, TransactionType::*};
+		let ds = DatastoreBuilder::new(Engine::Memory).with_read_only(true).build().await.unwrap();
+		assert!(ds.transaction(Read, Optimistic).await.is_ok());
+		assert!(matches!(ds.transaction(Write, Optimistic).await, Err(Error::TxReadonly)));
+	}
+}
@@ -13,13 +13,19 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::statements::DefineSinkStatement;
 use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::DefineTypeStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
 use crate::sql::Value;
 use quick_cache::Weighter;
 use std::any::Any;
-use std::sync::Arc;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, PoisonError, RwLock};
 
 #[derive(Clone)]
 pub(super) struct EntryWeighter;
@@ -54,6 +60,8 @@ pub(super) enum Entry {
 	Nds(Arc<[Node]>),
 	/// A slice of DefineUserStatement specified at the root.
 	Rus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified at the root.
+	Rrs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineAccessStatement specified at the root.
 	Ras(Arc<[DefineAccessStatement]>),
 	/// A slice of AccessGrant specified at the root.
@@ -62,6 +70,8 @@ pub(super) enum Entry {
 	Nss(Arc<[DefineNamespaceStatement]>),
 	/// A slice of DefineUserStatement specified on a namespace.
 	Nus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a namespace.
+	Nrs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineAccessStatement specified on a namespace.
 	Nas(Arc<[DefineAccessStatement]>),
 	/// A slice of AccessGrant specified at on a namespace.
@@ -76,6 +86,8 @@ pub(super) enum Entry {
 	Dag(Arc<[AccessGrant]>),
 	/// A slice of DefineUserStatement specified on a database.
 	Dus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a database.
+	Drs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[DefineFunctionStatement]>),
 	/// A slice of DefineTableStatement specified on a database.
@@ -88,12 +100,16 @@ pub(super) enum Entry {
 	Pas(Arc<[DefineParamStatement]>),
 	/// A slice of DefineTableStatement specified on a database.
 	Tbs(Arc<[DefineTableStatement]>),
+	/// A slice of DefineTypeStatement specified on a database.
+	Tys(Arc<[DefineTypeStatement]>),
 	/// A slice of DefineEventStatement specified on a table.
 	Evs(Arc<[DefineEventStatement]>),
 	/// A slice of DefineFieldStatement specified on a table.
 	Fds(Arc<[DefineFieldStatement]>),
 	/// A slice of DefineIndexStatement specified on a table.
 	Ixs(Arc<[DefineIndexStatement]>),
+	/// A slice of DefineSinkStatement specified on a table.
+	Sks(Arc<[DefineSinkStatement]>),
 	/// A slice of LiveStatement specified on a table.
 	Lvs(Arc<[LiveStatement]>),
 }
@@ -125,6 +141,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Rus")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Rrs`].
+	pub(super) fn try_into_rrs(self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		match self {
+			Entry::Rrs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Rrs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineAccessStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Ras`].
 	pub(super) fn try_into_ras(self) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -173,6 +197,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Nus")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Nrs`].
+	pub(super) fn try_into_nrs(self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		match self {
+			Entry::Nrs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Nrs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineDatabaseStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Dbs`].
 	pub(super) fn try_into_dbs(self) -> Result<Arc<[DefineDatabaseStatement]>, Error> {
@@ -205,6 +237,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Dus")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Drs`].
+	pub(super) fn try_into_drs(self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		match self {
+			Entry::Drs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Drs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineAnalyzerStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Azs`].
 	pub(super) fn try_into_azs(self) -> Result<Arc<[DefineAnalyzerStatement]>, Error> {
@@ -229,6 +269,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Pas")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineTypeStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Tys`].
+	pub(super) fn try_into_tys(self) -> Result<Arc<[DefineTypeStatement]>, Error> {
+		match self {
+			Entry::Tys(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Tys")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineModelStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Mls`].
 	pub(super) fn try_into_mls(self) -> Result<Arc<[DefineModelStatement]>, Error> {
@@ -285,6 +333,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Fts")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineSinkStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Sks`].
+	pub(super) fn try_into_sks(self) -> Result<Arc<[DefineSinkStatement]>, Error> {
+		match self {
+			Entry::Sks(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Sks")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`LiveStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Lvs`].
 	pub(super) fn try_into_lvs(self) -> Result<Arc<[LiveStatement]>, Error> {
@@ -302,3 +358,70 @@ impl Entry {
 		}
 	}
 }
+
+/// A permission clause which has been resolved for a session
+struct Resolved {
+	ns: String,
+	db: String,
+	session: Value,
+	expr: Value,
+	value: Arc<Value>,
+}
+
+/// A cache of permission clauses, with any parts which do not
+/// depend on the document already computed, keyed by the
+/// namespace, database, and session they were resolved for.
+/// Clauses which can not be compared for equality, such as
+/// those containing NaN, are never found in the cache.
+#[derive(Default)]
+pub(super) struct PermissionCache(RwLock<HashMap<u64, Vec<Resolved>>>);
+
+impl PermissionCache {
+	/// Hashes the parts which identify a resolved permission clause.
+	fn hash(ns: &str, db: &str, session: &Value, expr: &Value) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		ns.hash(&mut hasher);
+		db.hash(&mut hasher);
+		session.hash(&mut hasher);
+		expr.hash(&mut hasher);
+		hasher.finish()
+	}
+	/// Fetches a permission clause resolved for a session.
+	pub(super) fn get(
+		&self,
+		ns: &str,
+		db: &str,
+		session: &Value,
+		expr: &Value,
+	) -> Option<Arc<Value>> {
+		let key = Self::hash(ns, db, session, expr);
+		let map = self.0.read().unwrap_or_else(PoisonError::into_inner);
+		map.get(&key)?
+			.iter()
+			.find(|v| v.ns == ns && v.db == db && &v.session == session && &v.expr == expr)
+			.map(|v| v.value.clone())
+	}
+	/// Stores a permission clause resolved for a session.
+	pub(super) fn insert(
+		&self,
+		ns: &str,
+		db: &str,
+		session: &Value,
+		expr: &Value,
+		value: Arc<Value>,
+	) {
+		let key = Self::hash(ns, db, session, expr);
+		let mut map = self.0.write().unwrap_or_else(PoisonError::into_inner);
+		map.entry(key).or_default().push(Resolved {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			session: session.clone(),
+			expr: expr.clone(),
+			value,
+		});
+	}
+	/// Removes all resolved permission clauses.
+	pub(super) fn clear(&self) {
+		self.0.write().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+}
@@ -13,7 +13,7 @@ impl Datastore {
 		// Store the latest versionstamp
 		let mut vs: Option<Versionstamp> = None;
 		// Create a new transaction
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		// Fetch all namespaces
 		let nss = catch!(txn, txn.all_ns().await);
 		// Loop over all namespaces
@@ -42,7 +42,7 @@ impl Datastore {
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
 	pub(crate) async fn changefeed_cleanup(&self, ts: u64) -> Result<(), Error> {
 		// Create a new transaction
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		// Perform the garbage collection
 		catch!(txn, crate::cf::gc_all_at(&txn, ts).await);
 		// Commit the changes
@@ -0,0 +1,258 @@
+//! A write-ahead changelog of committed mutations for local storage engines.
+//!
+//! Distributed storage engines expose their own commit versionstamps, whereas
+//! embedded storage engines do not. When the changelog is enabled, every
+//! mutation in a transaction is captured along with its previous value, and
+//! is written to a dedicated changelog keyspace when the transaction commits.
+//! Commits which write to the changelog are serialized, so that the changelog
+//! versionstamps are strictly increasing in commit order. Any changefeed
+//! entries in the transaction are keyed by the same commit versionstamp, which
+//! is derived from the commit time, so that `SHOW CHANGES` on an embedded
+//! storage engine returns changes in commit order.
+//!
+//! The changelog keys are structured as follows:
+//!
+//! ```text
+//! \xff!cl{versionstamp}{index}
+//! ```
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::vs::{u64_to_versionstamp, versionstamp_to_u64, Versionstamp};
+use derive::Store;
+use futures::lock::{Mutex, MutexGuard};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The prefix of the changelog keyspace
+const PREFIX: &[u8] = b"\xff!cl";
+
+/// A single committed mutation of a key
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub struct Mutation {
+	/// The key which was modified
+	pub key: Key,
+	/// The value of the key before the transaction
+	pub old: Option<Val>,
+	/// The value of the key after the transaction, or None if deleted
+	pub new: Option<Val>,
+}
+
+/// Encode a changelog key for a mutation within a commit
+pub(super) fn new(vs: Versionstamp, idx: u32) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + 14);
+	k.extend_from_slice(PREFIX);
+	k.extend_from_slice(&vs);
+	k.extend_from_slice(&idx.to_be_bytes());
+	k
+}
+
+/// Decode a changelog key into its versionstamp and index
+pub(super) fn decode(k: &[u8]) -> Result<(Versionstamp, u32), Error> {
+	match k.strip_prefix(PREFIX) {
+		Some(k) if k.len() == 14 => {
+			let mut vs = [0u8; 10];
+			vs.copy_from_slice(&k[..10]);
+			let idx = u32::from_be_bytes([k[10], k[11], k[12], k[13]]);
+			Ok((vs, idx))
+		}
+		_ => Err(Error::Internal("Invalid changelog key".to_string())),
+	}
+}
+
+/// The range of changelog keys committed at, or after, a versionstamp
+pub(super) fn since(vs: Versionstamp) -> Range<Key> {
+	let mut end = PREFIX.to_vec();
+	end.push(0xff);
+	new(vs, 0)..end
+}
+
+/// The range of changelog keys committed before a versionstamp
+pub(super) fn before(vs: Versionstamp) -> Range<Key> {
+	PREFIX.to_vec()..new(vs, 0)
+}
+
+/// The shared commit sequence for a datastore changelog
+#[derive(Default)]
+pub(super) struct Changelog {
+	/// The last versionstamp which was assigned to a commit
+	last: Mutex<u64>,
+}
+
+impl Changelog {
+	/// Acquire the commit lock, serializing changelog commits
+	pub(super) async fn lock(&self) -> MutexGuard<'_, u64> {
+		self.last.lock().await
+	}
+	/// Calculate the next commit versionstamp, which is based on the
+	/// current system time, but is guaranteed to always increase.
+	pub(super) fn next(last: &u64) -> u64 {
+		Self::now().max(last.saturating_add(1))
+	}
+	/// Calculate the commit versionstamp for the current
+	/// system time, which no later commit can precede.
+	pub(super) fn now() -> u64 {
+		SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_nanos() as u64)
+			.unwrap_or_default()
+	}
+}
+
+/// Captures the mutations made within a single transaction
+pub(super) struct Recorder {
+	/// The shared datastore changelog
+	log: Arc<Changelog>,
+	/// The mutations made in this transaction, in order
+	mutations: Vec<Mutation>,
+	/// The changefeed entries which are versioned when committed
+	changes: Vec<(Key, Key, Val)>,
+	/// The number of mutations recorded at each save point
+	save_points: Vec<usize>,
+}
+
+impl Recorder {
+	pub(super) fn new(log: Arc<Changelog>) -> Self {
+		Self {
+			log,
+			mutations: vec![],
+			changes: vec![],
+			save_points: vec![],
+		}
+	}
+	/// Record a mutation of a key
+	pub(super) fn record(&mut self, key: Key, old: Option<Val>, new: Option<Val>) {
+		// Changes to the changelog itself are not recorded
+		if key.starts_with(PREFIX) {
+			return;
+		}
+		self.mutations.push(Mutation {
+			key,
+			old,
+			new,
+		});
+	}
+	/// Mark the current position, so that later mutations can be discarded
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.mutations.len());
+	}
+	/// Discard any mutations recorded since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(len) = self.save_points.pop() {
+			self.mutations.truncate(len);
+		}
+	}
+	/// Keep any mutations recorded since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+	/// Record a changefeed entry, which is keyed by the commit versionstamp
+	pub(super) fn change(&mut self, prefix: Key, suffix: Key, val: Val) {
+		self.changes.push((prefix, suffix, val));
+	}
+	/// Check if any mutations or changefeed entries have been recorded
+	pub(super) fn is_empty(&self) -> bool {
+		self.mutations.is_empty() && self.changes.is_empty()
+	}
+	/// The shared datastore changelog
+	pub(super) fn log(&self) -> Arc<Changelog> {
+		self.log.clone()
+	}
+	/// Take the changelog and changefeed entries to write for the specified commit versionstamp
+	pub(super) fn drain(&mut self, vs: u64) -> Vec<(Key, Val)> {
+		let vs = u64_to_versionstamp(vs);
+		let changes = std::mem::take(&mut self.changes).into_iter().map(|(mut k, s, v)| {
+			k.extend_from_slice(&vs);
+			k.extend_from_slice(&s);
+			(k, v)
+		});
+		std::mem::take(&mut self.mutations)
+			.into_iter()
+			.enumerate()
+			.map(|(i, m)| (new(vs, i as u32), m.into()))
+			.chain(changes)
+			.collect()
+	}
+}
+
+/// The commit versionstamp of the changelog at a timestamp in seconds
+pub(super) fn at(ts: u64) -> Versionstamp {
+	u64_to_versionstamp(ts.saturating_mul(1_000_000_000))
+}
+
+/// Decode a changelog entry into its commit versionstamp and mutation
+pub(super) fn entry(k: &[u8], v: Val) -> Result<(u64, Mutation), Error> {
+	let (vs, _) = decode(k)?;
+	Ok((versionstamp_to_u64(&vs), v.into()))
+}
+
+impl Datastore {
+	/// Deletes all changelog entries that are older than the retention period.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn changelog_cleanup(&self, ts: u64) -> Result<(), Error> {
+		// Check if the changelog is enabled
+		if !self.has_changelog() {
+			return Ok(());
+		}
+		// Calculate the oldest versionstamp to keep
+		let retention = *crate::cnf::CHANGELOG_RETENTION;
+		let vs = at(ts.saturating_sub(retention));
+		// Create a new transaction
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		// Delete all expired changelog entries
+		catch!(txn, txn.delr(before(vs)).await);
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let vs = u64_to_versionstamp(42);
+		let enc = new(vs, 7);
+		let dec = decode(&enc).unwrap();
+		assert_eq!(dec, (vs, 7));
+	}
+
+	#[test]
+	fn ordering() {
+		let a = new(u64_to_versionstamp(1), 9);
+		let b = new(u64_to_versionstamp(2), 0);
+		assert!(a < b);
+		assert!(since(u64_to_versionstamp(2)).contains(&b));
+		assert!(!since(u64_to_versionstamp(2)).contains(&a));
+		assert!(before(u64_to_versionstamp(2)).contains(&a));
+	}
+
+	#[test]
+	fn changes() {
+		let mut rec = Recorder::new(Arc::new(Changelog::default()));
+		assert!(rec.is_empty());
+		rec.change(b"pre".to_vec(), b"suf".to_vec(), b"val".to_vec());
+		assert!(!rec.is_empty());
+		let vs = u64_to_versionstamp(42);
+		let mut key = b"pre".to_vec();
+		key.extend_from_slice(&vs);
+		key.extend_from_slice(b"suf");
+		assert_eq!(rec.drain(42), vec![(key, b"val".to_vec())]);
+		assert!(rec.is_empty());
+	}
+
+	#[test]
+	fn next_is_increasing() {
+		let last = u64::MAX - 1;
+		assert_eq!(Changelog::next(&last), u64::MAX);
+	}
+}
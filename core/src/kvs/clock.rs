@@ -14,7 +14,7 @@ use wasmtimer::std::{SystemTime, UNIX_EPOCH};
 #[non_exhaustive]
 pub enum SizedClock {
 	System(SystemClock),
-	#[cfg(test)]
+	#[cfg(any(test, feature = "kv-testsuite"))]
 	Fake(FakeClock),
 	#[cfg(test)]
 	Inc(IncFakeClock),
@@ -29,7 +29,7 @@ impl SizedClock {
 	pub async fn now(&self) -> Timestamp {
 		match self {
 			SizedClock::System(c) => c.now(),
-			#[cfg(test)]
+			#[cfg(any(test, feature = "kv-testsuite"))]
 			SizedClock::Fake(c) => c.now().await,
 			#[cfg(test)]
 			SizedClock::Inc(c) => c.now().await,
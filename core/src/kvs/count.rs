@@ -0,0 +1,199 @@
+//! Maintained row counts for tables.
+//!
+//! Every record which is created or deleted in a transaction changes the
+//! row count of its table, and the change in the number of rows for each
+//! table is written to a dedicated count keyspace when the transaction
+//! commits. Each commit writes its changes to a unique key, so that
+//! concurrent transactions never conflict on the row counters. The changes
+//! for each table are periodically merged into a single entry during
+//! garbage collection.
+//!
+//! The row counts are only exact for tables which have been counted since
+//! they were defined. Tables which were created before row counts were
+//! maintained have no exact base entry, and are counted by scanning.
+//!
+//! The count keys are structured as follows:
+//!
+//! ```text
+//! /*{ns}*{db}*{tb}!cn{id}
+//! ```
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType::*, TransactionType::*};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Add;
+use uuid::Uuid;
+
+/// The maintained row count of a table
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub struct Count {
+	/// The change in the number of rows
+	pub rows: i64,
+	/// Whether the table has been counted since it was defined
+	pub exact: bool,
+}
+
+impl Count {
+	/// The starting count of a newly defined table
+	pub(crate) fn base() -> Self {
+		Count {
+			rows: 0,
+			exact: true,
+		}
+	}
+	/// A change in the number of rows of a table
+	pub(crate) fn rows(rows: i64) -> Self {
+		Count {
+			rows,
+			exact: false,
+		}
+	}
+	/// The number of rows in the table, if the count is exact
+	pub(crate) fn exact(&self) -> Option<u64> {
+		self.exact.then(|| self.rows.max(0) as u64)
+	}
+}
+
+impl Add for Count {
+	type Output = Count;
+	fn add(self, rhs: Count) -> Self::Output {
+		Count {
+			rows: self.rows.saturating_add(rhs.rows),
+			exact: self.exact || rhs.exact,
+		}
+	}
+}
+
+/// A row count change made within a transaction
+enum Change {
+	/// A change in the number of rows of a table
+	Delta(String, String, String, Count),
+	/// All data, including the row counters, under a prefix was deleted
+	Reset(Key),
+}
+
+/// Tracks the row count changes made within a single transaction
+#[derive(Default)]
+pub(super) struct Counter {
+	/// The changes made in this transaction, in order
+	changes: Vec<Change>,
+	/// The number of changes recorded at each save point
+	save_points: Vec<usize>,
+}
+
+impl Counter {
+	/// Record a change in the row count of a table
+	pub(super) fn record(&mut self, ns: &str, db: &str, tb: &str, count: Count) {
+		self.changes.push(Change::Delta(ns.to_owned(), db.to_owned(), tb.to_owned(), count));
+	}
+	/// Record the deletion of all keys under a prefix
+	pub(super) fn reset(&mut self, prefix: Key) {
+		self.changes.push(Change::Reset(prefix));
+	}
+	/// Mark the current position, so that later changes can be discarded
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.changes.len());
+	}
+	/// Discard any changes recorded since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(len) = self.save_points.pop() {
+			self.changes.truncate(len);
+		}
+	}
+	/// Keep any changes recorded since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+	/// Calculate the total change in row count for each table
+	fn totals(&self) -> BTreeMap<(&str, &str, &str), Count> {
+		let mut totals: BTreeMap<(&str, &str, &str), Count> = BTreeMap::new();
+		for change in self.changes.iter() {
+			match change {
+				Change::Delta(ns, db, tb, count) => {
+					let total = totals.entry((ns, db, tb)).or_default();
+					*total = *total + *count;
+				}
+				Change::Reset(prefix) => {
+					totals.retain(|(ns, db, tb), _| {
+						!crate::key::table::cn::prefix(ns, db, tb).starts_with(prefix)
+					});
+				}
+			}
+		}
+		totals
+	}
+	/// The uncommitted change in row count for a table
+	pub(super) fn pending(&self, ns: &str, db: &str, tb: &str) -> Count {
+		self.totals().remove(&(ns, db, tb)).unwrap_or_default()
+	}
+	/// Take the count entries to write for this transaction
+	pub(super) fn drain(&mut self) -> Vec<(Key, Val)> {
+		let res = self
+			.totals()
+			.into_iter()
+			.filter(|(_, count)| *count != Count::default())
+			.map(|((ns, db, tb), count)| {
+				(crate::key::table::cn::new(ns, db, tb, Uuid::now_v7()).into(), count.into())
+			})
+			.collect();
+		self.changes.clear();
+		res
+	}
+}
+
+impl Datastore {
+	/// Merges all recorded row count changes for a table into a single entry.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn count_compact(&self, ns: &str, db: &str, tb: &str) -> Result<(), Error> {
+		// Create a new transaction
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		// Fetch all row count changes for this table
+		let beg = crate::key::table::cn::prefix(ns, db, tb);
+		let end = crate::key::table::cn::suffix(ns, db, tb);
+		let res = catch!(txn, txn.getr(beg.clone()..end.clone(), None).await);
+		// Check if there is anything to merge
+		if res.len() < 2 {
+			return txn.cancel().await;
+		}
+		// Replace the changes with their total
+		let total = res.into_iter().fold(Count::default(), |acc, (_, v)| acc + Count::from(v));
+		catch!(txn, txn.delr(beg..end).await);
+		let key = crate::key::table::cn::new(ns, db, tb, Uuid::now_v7());
+		catch!(txn, txn.set(key, total, None).await);
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn counter() {
+		let one = Count::rows(1);
+		let mut c = Counter::default();
+		c.record("ns", "db", "a", Count::base());
+		c.record("ns", "db", "a", one);
+		c.record("ns", "db", "b", one);
+		c.new_save_point();
+		c.record("ns", "db", "a", one);
+		c.rollback_to_save_point();
+		c.reset(crate::key::table::all::new("ns", "db", "b").encode().unwrap());
+		assert_eq!(c.pending("ns", "db", "a").exact(), Some(1));
+		assert_eq!(c.pending("ns", "db", "b"), Count::default());
+		let res = c.drain();
+		assert_eq!(res.len(), 1);
+		let count: Count = res[0].1.clone().into();
+		assert_eq!(count.exact(), Some(1));
+		assert!(c.drain().is_empty());
+	}
+}
@@ -1,15 +1,22 @@
+use super::builder::{DatastoreBuilder, Engine};
+use super::changelog::{Changelog, Recorder};
+use super::count::Counter;
 use super::export;
+use super::hooks::Hooks;
 use super::tr::Transactor;
 use super::tx::Transaction;
+use super::usage::Tracker;
+use super::verify::Shadow;
 use super::version::Version;
 use crate::cf;
+use crate::cnf::PREPARED_STATEMENT_CACHE_SIZE;
 use crate::ctx::MutableContext;
-#[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::{MethodTarget, RouteTarget};
 use crate::dbs::node::Timestamp;
 use crate::dbs::{
-	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
+	Attach, Capabilities, Executor, Notification, Options, Response, RunningQueries, Session,
+	Variables,
 };
 use crate::err::Error;
 #[cfg(feature = "jwks")]
@@ -21,14 +28,21 @@ use crate::kvs::clock::SizedClock;
 use crate::kvs::clock::SystemClock;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::kvs::index::IndexBuilder;
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
+use crate::kvs::{
+	Consistency, Isolation, LockType, LockType::*, TransactionType, TransactionType::*,
+};
+#[cfg(feature = "opendal")]
+use crate::obs::dal::BlobStore;
+use crate::sink::Publishers;
 use crate::sql::{statements::DefineUserStatement, Base, Query, Value};
 use crate::syn;
 use crate::syn::parser::{Parser, PartialResult};
 use async_channel::{Receiver, Sender};
 use bytes::Bytes;
 use futures::{Future, Stream};
+use quick_cache::sync::Cache;
 use reblessive::{Stack, TreeStack};
+use std::collections::HashMap;
 use std::fmt;
 #[cfg(storage)]
 use std::path::PathBuf;
@@ -38,6 +52,8 @@ use std::task::{ready, Poll};
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(storage)]
+use tempfile::{Builder, TempDir};
 #[cfg(feature = "jwks")]
 use tokio::sync::RwLock;
 use tracing::instrument;
@@ -54,6 +70,15 @@ const LQ_CHANNEL_SIZE: usize = 100;
 // The role assigned to the initial user created when starting the server with credentials for the first time
 const INITIAL_USER_ROLE: &str = "owner";
 
+// The maximum number of times a conflicting transaction is retried
+const TRANSACTION_RETRY_ATTEMPTS: u32 = 10;
+
+// The initial delay before retrying a conflicting transaction
+const TRANSACTION_RETRY_BACKOFF: Duration = Duration::from_millis(5);
+
+// The maximum delay before retrying a conflicting transaction
+const TRANSACTION_RETRY_MAX_BACKOFF: Duration = Duration::from_millis(1000);
+
 /// The underlying datastore instance which stores the dataset.
 #[allow(dead_code)]
 #[non_exhaustive]
@@ -69,21 +94,32 @@ pub struct Datastore {
 	query_timeout: Option<Duration>,
 	// The maximum duration timeout for running multiple statements in a transaction
 	transaction_timeout: Option<Duration>,
+	// The maximum number of rows which a single SELECT statement can process
+	query_row_limit: Option<u64>,
 	// Capabilities for this datastore
 	capabilities: Capabilities,
 	// Whether this datastore enables live query notifications to subscribers
 	pub(super) notification_channel: Option<(Sender<Notification>, Receiver<Notification>)>,
 	// The index store cache
 	index_stores: IndexStores,
+	// The queries which are currently running on this datastore
+	running_queries: RunningQueries,
 	// The index asynchronous builder
 	#[cfg(not(target_arch = "wasm32"))]
 	index_builder: IndexBuilder,
 	#[cfg(feature = "jwks")]
 	// The JWKS object cache
 	jwks_cache: Arc<RwLock<JwksCache>>,
+	// The connections to the message brokers of changefeed sinks
+	sinks: Arc<Publishers>,
 	#[cfg(storage)]
 	// The temporary directory
 	temporary_directory: Option<Arc<PathBuf>>,
+	#[cfg(storage)]
+	// The temporary area owned by this datastore
+	temporary_area: Option<Arc<TempDir>>,
+	// The prepared statement cache
+	prepared: Arc<Cache<Uuid, Arc<Query>>>,
 }
 
 #[derive(Clone)]
@@ -92,15 +128,43 @@ pub(super) struct TransactionFactory {
 	clock: Arc<SizedClock>,
 	// The inner datastore type
 	flavor: Arc<DatastoreFlavor>,
+	// The write-ahead changelog for local storage engines
+	changelog: Option<Arc<Changelog>>,
+	// Whether write transactions are disallowed
+	read_only: bool,
+	// The channel on which committed live query notifications are delivered
+	notifications: Option<Sender<Notification>>,
+	// Whether storage usage is tracked for each database
+	usage: bool,
+	// The maximum on-disk size of the datastore in bytes
+	quota: Option<u64>,
+	// The object store in which large values are stored
+	#[cfg(feature = "opendal")]
+	blobs: Option<Arc<BlobStore>>,
 }
 
 impl TransactionFactory {
-	#[allow(unreachable_code)]
 	pub async fn transaction(
 		&self,
 		write: TransactionType,
 		lock: LockType,
 	) -> Result<Transaction, Error> {
+		self.isolated(write, lock, None, Consistency::Strong).await
+	}
+
+	/// Create a new transaction, using the storage engine default isolation level if unspecified
+	#[allow(unreachable_code)]
+	pub async fn isolated(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+		isolation: Option<Isolation>,
+		consistency: Consistency,
+	) -> Result<Transaction, Error> {
+		// Disallow write transactions on read-only datastores
+		if self.read_only && matches!(write, Write) {
+			return Err(Error::TxReadonly);
+		}
 		// Specify if the transaction is writeable
 		#[allow(unused_variables)]
 		let write = match write {
@@ -113,9 +177,345 @@ impl TransactionFactory {
 			Pessimistic => true,
 			Optimistic => false,
 		};
+		// Check whether the datastore has exceeded its storage quota
+		let exceeded = match self.quota {
+			Some(limit) if write && self.flavor.size()?.is_some_and(|s| s >= limit) => Some(limit),
+			_ => None,
+		};
+		// Use the default isolation level of the storage engine if unspecified
+		let isolation = isolation.unwrap_or_else(|| self.flavor.default_isolation(write, lock));
+		// Track the keys which are read, if conflicting reads are not detected natively
+		let reads = match isolation {
+			Isolation::SerializableSnapshot if write && !self.flavor.is_serializable() => {
+				// Rewriting the keys which were read only has an effect on
+				// storage engines which detect conflicting writes
+				if !self.flavor.detects_conflicts() {
+					return Err(Error::TxIsolationUnsupported);
+				}
+				Some(HashMap::new())
+			}
+			_ => None,
+		};
 		// Create a new transaction on the datastore
-		#[allow(unused_variables)]
-		let inner = match self.flavor.as_ref() {
+		let inner = self.flavor.transaction(write, lock, isolation, consistency).await?;
+		Ok(Transaction::new(Transactor {
+			inner,
+			stash: super::stash::Stash::default(),
+			cf: cf::Writer::new(),
+			clock: self.clock.clone(),
+			log: self.changelog.clone().map(Recorder::new),
+			usage: self.usage.then(Tracker::default),
+			counts: Counter::default(),
+			hooks: Hooks::new(self.notifications.clone().filter(|_| write)),
+			reads,
+			absent: HashMap::new(),
+			save_points: 0,
+			deadline: None,
+			exceeded,
+			#[cfg(feature = "opendal")]
+			blobs: self.blobs.clone(),
+			verify: cfg!(feature = "kv-verify")
+				.then(|| Shadow::new(!matches!(isolation, Isolation::Optimistic))),
+		}))
+	}
+}
+
+#[allow(clippy::large_enum_variant)]
+pub(super) enum DatastoreFlavor {
+	#[cfg(feature = "kv-mem")]
+	Mem(super::mem::Datastore),
+	#[cfg(feature = "kv-rocksdb")]
+	RocksDB(super::rocksdb::Datastore),
+	#[cfg(feature = "kv-rocksdb")]
+	RocksDBSecondary(super::rocksdb::secondary::Datastore),
+	#[cfg(feature = "kv-indxdb")]
+	IndxDB(super::indxdb::Datastore),
+	#[cfg(feature = "kv-tikv")]
+	TiKV(super::tikv::Datastore),
+	#[cfg(feature = "kv-fdb")]
+	FoundationDB(super::fdb::Datastore),
+	#[cfg(feature = "kv-surrealkv")]
+	SurrealKV(super::surrealkv::Datastore),
+	#[cfg(feature = "kv-surrealcs")]
+	SurrealCS(super::surrealcs::Datastore),
+	#[cfg(feature = "kv-sled")]
+	Sled(super::sled::Datastore),
+	#[cfg(feature = "kv-lmdb")]
+	Lmdb(super::lmdb::Datastore),
+	#[cfg(feature = "kv-sqlite")]
+	Sqlite(super::sqlite::Datastore),
+	#[cfg(feature = "kv-postgres")]
+	Postgres(super::postgres::Datastore),
+	#[cfg(feature = "kv-s3")]
+	S3(super::s3::Datastore),
+	#[cfg(feature = "kv-dynamodb")]
+	DynamoDb(super::dynamodb::Datastore),
+	#[cfg(feature = "kv-redis")]
+	Redis(super::redis::Datastore),
+	#[cfg(feature = "kv-tiered")]
+	Tiered(super::tiered::Datastore),
+	#[cfg(feature = "kv-opfs")]
+	Opfs(super::opfs::Datastore),
+	#[cfg(feature = "kv-shard")]
+	Shard(super::shard::Datastore),
+	External(super::external::Datastore),
+}
+
+impl DatastoreFlavor {
+	/// Open the storage engine which is specified by a typed configuration
+	#[allow(unused_variables)]
+	pub(super) async fn new(builder: &DatastoreBuilder) -> Result<Self, Error> {
+		let path = builder.path.as_str();
+		match &builder.engine {
+			// Initiate a memory datastore
+			Engine::Memory => {
+				#[cfg(feature = "kv-mem")]
+				{
+					info!(target: TARGET, "Starting kvs store in {}", builder);
+					let v = super::mem::Datastore::new().await.map(DatastoreFlavor::Mem);
+					info!(target: TARGET, "Started kvs store in {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-mem"))]
+				return Err(Error::Ds("Cannot connect to the `memory` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a rocksdb datastore
+			Engine::RocksDb => {
+				#[cfg(feature = "kv-rocksdb")]
+				if let Some(secondary) = &builder.secondary {
+					info!(target: TARGET, "Attaching to kvs store at {}", builder);
+					let v = super::rocksdb::secondary::Datastore::new(
+						path,
+						secondary,
+						builder.cache_size,
+					)
+					.await
+					.map(DatastoreFlavor::RocksDBSecondary);
+					info!(target: TARGET, "Attached to kvs store at {}", builder);
+					v
+				} else {
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::rocksdb::Datastore::new(path, builder.cache_size)
+						.await
+						.map(DatastoreFlavor::RocksDB);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-rocksdb"))]
+				return Err(Error::Ds("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a surrealkv datastore
+			Engine::SurrealKv => {
+				#[cfg(feature = "kv-surrealkv")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::surrealkv::Datastore::new(path)
+						.await
+						.map(DatastoreFlavor::SurrealKV);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-surrealkv"))]
+				return Err(Error::Ds("Cannot connect to the `surrealkv` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a surrealcs datastore
+			Engine::SurrealCs => {
+				#[cfg(feature = "kv-surrealcs")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v = super::surrealcs::Datastore::new(path)
+						.await
+						.map(DatastoreFlavor::SurrealCS);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-surrealcs"))]
+				return Err(Error::Ds("Cannot connect to the `surrealcs` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a indxdb datastore
+			Engine::IndxDb => {
+				#[cfg(feature = "kv-indxdb")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::indxdb::Datastore::new(path).await.map(DatastoreFlavor::IndxDB);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-indxdb"))]
+				return Err(Error::Ds("Cannot connect to the `indxdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a tikv datastore
+			Engine::TiKv => {
+				#[cfg(feature = "kv-tikv")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v = super::tikv::Datastore::new(path).await.map(DatastoreFlavor::TiKV);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-tikv"))]
+				return Err(Error::Ds("Cannot connect to the `tikv` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a foundationdb datastore
+			Engine::FoundationDb => {
+				#[cfg(feature = "kv-fdb")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v =
+						super::fdb::Datastore::new(path).await.map(DatastoreFlavor::FoundationDB);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-fdb"))]
+				return Err(Error::Ds("Cannot connect to the `foundationdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a sled datastore
+			Engine::Sled => {
+				#[cfg(feature = "kv-sled")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::sled::Datastore::new(path, builder.cache_size)
+						.await
+						.map(DatastoreFlavor::Sled);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-sled"))]
+				return Err(Error::Ds("Cannot connect to the `sled` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate an LMDB datastore
+			Engine::Lmdb => {
+				#[cfg(feature = "kv-lmdb")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::lmdb::Datastore::new(path).await.map(DatastoreFlavor::Lmdb);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-lmdb"))]
+				return Err(Error::Ds("Cannot connect to the `lmdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a SQLite datastore
+			Engine::Sqlite => {
+				#[cfg(feature = "kv-sqlite")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::sqlite::Datastore::new(path).await.map(DatastoreFlavor::Sqlite);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-sqlite"))]
+				return Err(Error::Ds("Cannot connect to the `sqlite` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a Postgres datastore
+			Engine::Postgres => {
+				#[cfg(feature = "kv-postgres")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v =
+						super::postgres::Datastore::new(path).await.map(DatastoreFlavor::Postgres);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-postgres"))]
+				return Err(Error::Ds("Cannot connect to the `postgres` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate an S3 datastore
+			Engine::S3 => {
+				#[cfg(feature = "kv-s3")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v = super::s3::Datastore::new(path).await.map(DatastoreFlavor::S3);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-s3"))]
+				return Err(Error::Ds("Cannot connect to the `s3` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a DynamoDB datastore
+			Engine::DynamoDb => {
+				#[cfg(feature = "kv-dynamodb")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v =
+						super::dynamodb::Datastore::new(path).await.map(DatastoreFlavor::DynamoDb);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-dynamodb"))]
+				return Err(Error::Ds("Cannot connect to the `dynamodb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a Redis datastore
+			Engine::Redis => {
+				#[cfg(feature = "kv-redis")]
+				{
+					info!(target: TARGET, "Connecting to kvs store at {}", builder);
+					let v = super::redis::Datastore::new(path).await.map(DatastoreFlavor::Redis);
+					info!(target: TARGET, "Connected to kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-redis"))]
+				return Err(Error::Ds("Cannot connect to the `redis` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a tiered datastore
+			Engine::Tiered => {
+				#[cfg(feature = "kv-tiered")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::tiered::Datastore::new(path, builder.cache_size)
+						.await
+						.map(DatastoreFlavor::Tiered);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-tiered"))]
+				return Err(Error::Ds("Cannot connect to the `tiered` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate an OPFS datastore
+			Engine::Opfs => {
+				#[cfg(feature = "kv-opfs")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::opfs::Datastore::new(path).await.map(DatastoreFlavor::Opfs);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-opfs"))]
+				return Err(Error::Ds("Cannot connect to the `opfs` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate a sharded datastore
+			Engine::Shard => {
+				#[cfg(feature = "kv-shard")]
+				{
+					info!(target: TARGET, "Starting kvs store at {}", builder);
+					let v = super::shard::Datastore::new(path).await.map(DatastoreFlavor::Shard);
+					info!(target: TARGET, "Started kvs store at {}", builder);
+					v
+				}
+				#[cfg(not(feature = "kv-shard"))]
+				return Err(Error::Ds("Cannot connect to the `shard` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
+			}
+			// Initiate an externally registered datastore
+			Engine::External(name) => {
+				info!(target: TARGET, "Starting kvs store at {}", builder);
+				let v = super::external::Datastore::new(name, path)
+					.await
+					.map(DatastoreFlavor::External);
+				info!(target: TARGET, "Started kvs store at {}", builder);
+				v
+			}
+		}
+	}
+	/// Start a new transaction on the storage engine
+	#[allow(unused_variables)]
+	pub(super) async fn transaction(
+		&self,
+		write: bool,
+		lock: bool,
+		isolation: Isolation,
+		consistency: Consistency,
+	) -> Result<super::tr::Inner, Error> {
+		let inner = match self {
 			#[cfg(feature = "kv-mem")]
 			DatastoreFlavor::Mem(v) => {
 				let tx = v.transaction(write, lock).await?;
@@ -123,9 +523,14 @@ impl TransactionFactory {
 			}
 			#[cfg(feature = "kv-rocksdb")]
 			DatastoreFlavor::RocksDB(v) => {
-				let tx = v.transaction(write, lock).await?;
+				let tx = v.transaction(write, lock, isolation).await?;
 				super::tr::Inner::RocksDB(tx)
 			}
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDBSecondary(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::RocksDBSecondary(tx)
+			}
 			#[cfg(feature = "kv-indxdb")]
 			DatastoreFlavor::IndxDB(v) => {
 				let tx = v.transaction(write, lock).await?;
@@ -133,12 +538,12 @@ impl TransactionFactory {
 			}
 			#[cfg(feature = "kv-tikv")]
 			DatastoreFlavor::TiKV(v) => {
-				let tx = v.transaction(write, lock).await?;
+				let tx = v.transaction(write, lock, consistency).await?;
 				super::tr::Inner::TiKV(tx)
 			}
 			#[cfg(feature = "kv-fdb")]
 			DatastoreFlavor::FoundationDB(v) => {
-				let tx = v.transaction(write, lock).await?;
+				let tx = v.transaction(write, lock, isolation).await?;
 				super::tr::Inner::FoundationDB(tx)
 			}
 			#[cfg(feature = "kv-surrealkv")]
@@ -151,34 +556,200 @@ impl TransactionFactory {
 				let tx = v.transaction(write, lock).await?;
 				super::tr::Inner::SurrealCS(tx)
 			}
+			#[cfg(feature = "kv-sled")]
+			DatastoreFlavor::Sled(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Sled(tx)
+			}
+			#[cfg(feature = "kv-lmdb")]
+			DatastoreFlavor::Lmdb(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Lmdb(tx)
+			}
+			#[cfg(feature = "kv-sqlite")]
+			DatastoreFlavor::Sqlite(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Sqlite(tx)
+			}
+			#[cfg(feature = "kv-postgres")]
+			DatastoreFlavor::Postgres(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Postgres(tx)
+			}
+			#[cfg(feature = "kv-s3")]
+			DatastoreFlavor::S3(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::S3(tx)
+			}
+			#[cfg(feature = "kv-dynamodb")]
+			DatastoreFlavor::DynamoDb(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::DynamoDb(tx)
+			}
+			#[cfg(feature = "kv-redis")]
+			DatastoreFlavor::Redis(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Redis(tx)
+			}
+			#[cfg(feature = "kv-tiered")]
+			DatastoreFlavor::Tiered(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Tiered(tx)
+			}
+			#[cfg(feature = "kv-opfs")]
+			DatastoreFlavor::Opfs(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Opfs(tx)
+			}
+			#[cfg(feature = "kv-shard")]
+			DatastoreFlavor::Shard(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::Shard(tx)
+			}
+			DatastoreFlavor::External(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tr::Inner::External(tx)
+			}
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		};
-		Ok(Transaction::new(Transactor {
-			inner,
-			stash: super::stash::Stash::default(),
-			cf: cf::Writer::new(),
-			clock: self.clock.clone(),
-		}))
+		Ok(inner)
+	}
+	/// Run any storage engine shutdown tasks
+	pub(super) async fn shutdown(&self) -> Result<(), Error> {
+		match self {
+			#[cfg(feature = "kv-mem")]
+			DatastoreFlavor::Mem(v) => v.shutdown().await,
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(v) => v.shutdown().await,
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDBSecondary(v) => v.shutdown().await,
+			#[cfg(feature = "kv-indxdb")]
+			DatastoreFlavor::IndxDB(v) => v.shutdown().await,
+			#[cfg(feature = "kv-tikv")]
+			DatastoreFlavor::TiKV(v) => v.shutdown().await,
+			#[cfg(feature = "kv-fdb")]
+			DatastoreFlavor::FoundationDB(v) => v.shutdown().await,
+			#[cfg(feature = "kv-surrealkv")]
+			DatastoreFlavor::SurrealKV(v) => v.shutdown().await,
+			#[cfg(feature = "kv-surrealcs")]
+			DatastoreFlavor::SurrealCS(v) => v.shutdown().await,
+			#[cfg(feature = "kv-sled")]
+			DatastoreFlavor::Sled(v) => v.shutdown().await,
+			#[cfg(feature = "kv-lmdb")]
+			DatastoreFlavor::Lmdb(v) => v.shutdown().await,
+			#[cfg(feature = "kv-sqlite")]
+			DatastoreFlavor::Sqlite(v) => v.shutdown().await,
+			#[cfg(feature = "kv-postgres")]
+			DatastoreFlavor::Postgres(v) => v.shutdown().await,
+			#[cfg(feature = "kv-s3")]
+			DatastoreFlavor::S3(v) => v.shutdown().await,
+			#[cfg(feature = "kv-dynamodb")]
+			DatastoreFlavor::DynamoDb(v) => v.shutdown().await,
+			#[cfg(feature = "kv-redis")]
+			DatastoreFlavor::Redis(v) => v.shutdown().await,
+			#[cfg(feature = "kv-tiered")]
+			DatastoreFlavor::Tiered(v) => v.shutdown().await,
+			#[cfg(feature = "kv-opfs")]
+			DatastoreFlavor::Opfs(v) => v.shutdown().await,
+			#[cfg(feature = "kv-shard")]
+			DatastoreFlavor::Shard(v) => v.shutdown().await,
+			DatastoreFlavor::External(v) => v.shutdown().await,
+			#[allow(unreachable_patterns)]
+			_ => unreachable!(),
+		}
+	}
+	/// Catch up with commits made by another process, for secondary instances
+	pub(super) async fn refresh(&self) -> Result<(), Error> {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDBSecondary(v) => v.refresh().await,
+			#[allow(unreachable_patterns)]
+			_ => Ok(()),
+		}
+	}
+	/// Drop the storage of databases which have been removed
+	pub(super) fn sweep_partitions(&self) -> Result<(), Error> {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(v) => v.sweep_partitions(),
+			#[allow(unreachable_patterns)]
+			_ => Ok(()),
+		}
+	}
+	/// The isolation level used when none is specified for a transaction
+	#[allow(unused_variables)]
+	pub(super) fn default_isolation(&self, write: bool, lock: bool) -> Isolation {
+		match self {
+			#[cfg(feature = "kv-fdb")]
+			DatastoreFlavor::FoundationDB(_) if write || lock => Isolation::SerializableSnapshot,
+			_ => Isolation::Snapshot,
+		}
+	}
+	/// Check if this storage engine natively detects conflicting reads when committing
+	fn is_serializable(&self) -> bool {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(_) => true,
+			#[cfg(feature = "kv-indxdb")]
+			DatastoreFlavor::IndxDB(_) => true,
+			#[cfg(feature = "kv-fdb")]
+			DatastoreFlavor::FoundationDB(_) => true,
+			#[cfg(feature = "kv-mem")]
+			DatastoreFlavor::Mem(_) => true,
+			#[cfg(feature = "kv-surrealkv")]
+			DatastoreFlavor::SurrealKV(_) => true,
+			#[allow(unreachable_patterns)]
+			_ => false,
+		}
+	}
+	/// Check if this storage engine detects conflicting writes to the same keys when committing
+	fn detects_conflicts(&self) -> bool {
+		match self {
+			#[cfg(feature = "kv-tikv")]
+			DatastoreFlavor::TiKV(_) => true,
+			#[cfg(feature = "kv-postgres")]
+			DatastoreFlavor::Postgres(_) => true,
+			#[allow(unreachable_patterns)]
+			_ => false,
+		}
+	}
+	/// The approximate on-disk size of the datastore, if known
+	fn size(&self) -> Result<Option<u64>, Error> {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(v) => v.size().map(Some),
+			#[allow(unreachable_patterns)]
+			_ => Ok(None),
+		}
+	}
+	/// Check if this is an embedded storage engine, without its own commit versionstamps
+	pub(super) fn is_local(&self) -> bool {
+		match self {
+			#[cfg(feature = "kv-mem")]
+			DatastoreFlavor::Mem(_) => true,
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDB(_) => true,
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDBSecondary(_) => true,
+			#[cfg(feature = "kv-surrealkv")]
+			DatastoreFlavor::SurrealKV(_) => true,
+			#[cfg(feature = "kv-sled")]
+			DatastoreFlavor::Sled(_) => true,
+			#[cfg(feature = "kv-lmdb")]
+			DatastoreFlavor::Lmdb(_) => true,
+			#[cfg(feature = "kv-sqlite")]
+			DatastoreFlavor::Sqlite(_) => true,
+			#[cfg(feature = "kv-tiered")]
+			DatastoreFlavor::Tiered(_) => true,
+			#[cfg(feature = "kv-opfs")]
+			DatastoreFlavor::Opfs(_) => true,
+			#[cfg(feature = "kv-shard")]
+			DatastoreFlavor::Shard(v) => v.is_local(),
+			#[allow(unreachable_patterns)]
+			_ => false,
+		}
 	}
-}
-
-#[allow(clippy::large_enum_variant)]
-pub(super) enum DatastoreFlavor {
-	#[cfg(feature = "kv-mem")]
-	Mem(super::mem::Datastore),
-	#[cfg(feature = "kv-rocksdb")]
-	RocksDB(super::rocksdb::Datastore),
-	#[cfg(feature = "kv-indxdb")]
-	IndxDB(super::indxdb::Datastore),
-	#[cfg(feature = "kv-tikv")]
-	TiKV(super::tikv::Datastore),
-	#[cfg(feature = "kv-fdb")]
-	FoundationDB(super::fdb::Datastore),
-	#[cfg(feature = "kv-surrealkv")]
-	SurrealKV(super::surrealkv::Datastore),
-	#[cfg(feature = "kv-surrealcs")]
-	SurrealCS(super::surrealcs::Datastore),
 }
 
 impl fmt::Display for Datastore {
@@ -189,6 +760,8 @@ impl fmt::Display for Datastore {
 			DatastoreFlavor::Mem(_) => write!(f, "memory"),
 			#[cfg(feature = "kv-rocksdb")]
 			DatastoreFlavor::RocksDB(_) => write!(f, "rocksdb"),
+			#[cfg(feature = "kv-rocksdb")]
+			DatastoreFlavor::RocksDBSecondary(_) => write!(f, "rocksdb"),
 			#[cfg(feature = "kv-indxdb")]
 			DatastoreFlavor::IndxDB(_) => write!(f, "indxdb"),
 			#[cfg(feature = "kv-tikv")]
@@ -199,6 +772,27 @@ impl fmt::Display for Datastore {
 			DatastoreFlavor::SurrealKV(_) => write!(f, "surrealkv"),
 			#[cfg(feature = "kv-surrealcs")]
 			DatastoreFlavor::SurrealCS(_) => write!(f, "surrealcs"),
+			#[cfg(feature = "kv-sled")]
+			DatastoreFlavor::Sled(_) => write!(f, "sled"),
+			#[cfg(feature = "kv-lmdb")]
+			DatastoreFlavor::Lmdb(_) => write!(f, "lmdb"),
+			#[cfg(feature = "kv-sqlite")]
+			DatastoreFlavor::Sqlite(_) => write!(f, "sqlite"),
+			#[cfg(feature = "kv-postgres")]
+			DatastoreFlavor::Postgres(_) => write!(f, "postgres"),
+			#[cfg(feature = "kv-s3")]
+			DatastoreFlavor::S3(_) => write!(f, "s3"),
+			#[cfg(feature = "kv-dynamodb")]
+			DatastoreFlavor::DynamoDb(_) => write!(f, "dynamodb"),
+			#[cfg(feature = "kv-redis")]
+			DatastoreFlavor::Redis(_) => write!(f, "redis"),
+			#[cfg(feature = "kv-tiered")]
+			DatastoreFlavor::Tiered(_) => write!(f, "tiered"),
+			#[cfg(feature = "kv-opfs")]
+			DatastoreFlavor::Opfs(_) => write!(f, "opfs"),
+			#[cfg(feature = "kv-shard")]
+			DatastoreFlavor::Shard(_) => write!(f, "shard"),
+			DatastoreFlavor::External(v) => write!(f, "{}", v.name()),
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
@@ -257,179 +851,148 @@ impl Datastore {
 			auth_enabled: self.auth_enabled,
 			query_timeout: self.query_timeout,
 			transaction_timeout: self.transaction_timeout,
+			query_row_limit: self.query_row_limit,
 			capabilities: self.capabilities,
 			notification_channel: self.notification_channel,
 			index_stores: Default::default(),
+			running_queries: Default::default(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
 			#[cfg(feature = "jwks")]
 			jwks_cache: Arc::new(Default::default()),
+			sinks: Arc::new(Default::default()),
 			#[cfg(storage)]
 			temporary_directory: self.temporary_directory,
+			#[cfg(storage)]
+			temporary_area: self.temporary_area,
+			prepared: Arc::new(Cache::new(*PREPARED_STATEMENT_CACHE_SIZE)),
 			transaction_factory: self.transaction_factory,
 		}
 	}
 
-	#[allow(unused_variables)]
+	/// Creates a new datastore instance with a specific clock
 	pub async fn new_with_clock(
 		path: &str,
 		clock: Option<Arc<SizedClock>>,
 	) -> Result<Datastore, Error> {
-		// Initiate the desired datastore
-		let (flavor, clock): (Result<DatastoreFlavor, Error>, Arc<SizedClock>) = match path {
-			// Initiate an in-memory datastore
-			"memory" => {
-				#[cfg(feature = "kv-mem")]
-				{
-					info!(target: TARGET, "Starting kvs store in {}", path);
-					let v = super::mem::Datastore::new().await.map(DatastoreFlavor::Mem);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store in {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-mem"))]
-                return Err(Error::Ds("Cannot connect to the `memory` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a File datastore
-			s if s.starts_with("file:") => {
-				#[cfg(feature = "kv-rocksdb")]
-				{
-					info!(target: TARGET, "Starting kvs store at {}", path);
-					warn!("file:// is deprecated, please use surrealkv:// or rocksdb://");
-					let s = s.trim_start_matches("file://");
-					let s = s.trim_start_matches("file:");
-					let v = super::rocksdb::Datastore::new(s).await.map(DatastoreFlavor::RocksDB);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-rocksdb"))]
-                return Err(Error::Ds("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a RocksDB datastore
-			s if s.starts_with("rocksdb:") => {
-				#[cfg(feature = "kv-rocksdb")]
-				{
-					info!(target: TARGET, "Starting kvs store at {}", path);
-					let s = s.trim_start_matches("rocksdb://");
-					let s = s.trim_start_matches("rocksdb:");
-					let v = super::rocksdb::Datastore::new(s).await.map(DatastoreFlavor::RocksDB);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-rocksdb"))]
-                return Err(Error::Ds("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a SurrealKV datastore
-			s if s.starts_with("surrealkv:") => {
-				#[cfg(feature = "kv-surrealkv")]
-				{
-					info!(target: TARGET, "Starting kvs store at {}", path);
-					let s = s.trim_start_matches("surrealkv://");
-					let s = s.trim_start_matches("surrealkv:");
-					let v =
-						super::surrealkv::Datastore::new(s).await.map(DatastoreFlavor::SurrealKV);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-surrealkv"))]
-                return Err(Error::Ds("Cannot connect to the `surrealkv` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a SurrealCS datastore
-			s if s.starts_with("surrealcs:") => {
-				#[cfg(feature = "kv-surrealcs")]
-				{
-					info!(target: TARGET, "Starting kvs store at {}", path);
-					let s = s.trim_start_matches("surrealcs://");
-					let s = s.trim_start_matches("surrealcs:");
-					let v =
-						super::surrealcs::Datastore::new(s).await.map(DatastoreFlavor::SurrealCS);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-surrealcs"))]
-				return Err(Error::Ds("Cannot connect to the `surrealcs` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate an IndxDB database
-			s if s.starts_with("indxdb:") => {
-				#[cfg(feature = "kv-indxdb")]
-				{
-					info!(target: TARGET, "Starting kvs store at {}", path);
-					let s = s.trim_start_matches("indxdb://");
-					let s = s.trim_start_matches("indxdb:");
-					let v = super::indxdb::Datastore::new(s).await.map(DatastoreFlavor::IndxDB);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Started kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-indxdb"))]
-                return Err(Error::Ds("Cannot connect to the `indxdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a TiKV datastore
-			s if s.starts_with("tikv:") => {
-				#[cfg(feature = "kv-tikv")]
-				{
-					info!(target: TARGET, "Connecting to kvs store at {}", path);
-					let s = s.trim_start_matches("tikv://");
-					let s = s.trim_start_matches("tikv:");
-					let v = super::tikv::Datastore::new(s).await.map(DatastoreFlavor::TiKV);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Connected to kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-tikv"))]
-                return Err(Error::Ds("Cannot connect to the `tikv` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// Parse and initiate a FoundationDB datastore
-			s if s.starts_with("fdb:") => {
-				#[cfg(feature = "kv-fdb")]
-				{
-					info!(target: TARGET, "Connecting to kvs store at {}", path);
-					let s = s.trim_start_matches("fdb://");
-					let s = s.trim_start_matches("fdb:");
-					let v = super::fdb::Datastore::new(s).await.map(DatastoreFlavor::FoundationDB);
-					let c = clock.unwrap_or_else(|| Arc::new(SizedClock::system()));
-					info!(target: TARGET, "Connected to kvs store at {}", path);
-					Ok((v, c))
-				}
-				#[cfg(not(feature = "kv-fdb"))]
-                return Err(Error::Ds("Cannot connect to the `foundationdb` storage engine as it is not enabled in this build of SurrealDB".to_owned()));
-			}
-			// The datastore path is not valid
-			_ => {
+		match DatastoreBuilder::from_path(path) {
+			Ok(builder) => builder.with_clock(clock).build().await,
+			Err(e) => {
 				info!(target: TARGET, "Unable to load the specified datastore {}", path);
-				Err(Error::Ds("Unable to load the specified datastore".into()))
+				Err(e)
 			}
-		}?;
+		}
+	}
+
+	/// Create a new datastore from a typed configuration
+	#[allow(unused_variables)]
+	pub(super) async fn new_with_builder(builder: DatastoreBuilder) -> Result<Datastore, Error> {
+		// Encryption at rest is not supported by any storage engine yet
+		if builder.encryption_key.is_some() {
+			return Err(Error::Ds(format!(
+				"The `{}` storage engine does not support encryption at rest",
+				builder.engine
+			)));
+		}
+		// Storing large values in a blob store requires the opendal feature
+		#[cfg(not(feature = "opendal"))]
+		if builder.blob_store.is_some() {
+			return Err(Error::Ds("Cannot use a blob store as the `opendal` feature is not enabled in this build of SurrealDB".to_owned()));
+		}
+		// Only the rocksdb storage engine can attach as a secondary instance
+		if builder.secondary.is_some() && builder.engine != Engine::RocksDb {
+			return Err(Error::Ds(format!(
+				"The `{}` storage engine does not support secondary instances",
+				builder.engine
+			)));
+		}
+		// Only the rocksdb, sled, and tiered storage engines have a configurable cache
+		if builder.cache_size.is_some()
+			&& !matches!(builder.engine, Engine::RocksDb | Engine::Sled | Engine::Tiered)
+		{
+			warn!(
+				target: TARGET,
+				"The `{}` storage engine does not support a cache size, ignoring",
+				builder.engine
+			);
+		}
+		// Only the rocksdb storage engine reports its on-disk size
+		if builder.storage_quota.is_some() && builder.engine != Engine::RocksDb {
+			warn!(
+				target: TARGET,
+				"The `{}` storage engine does not support a storage quota, ignoring",
+				builder.engine
+			);
+		}
+		// Initiate the desired datastore
+		let flavor = DatastoreFlavor::new(&builder).await?;
 		// Set the properties on the datastore
-		flavor.map(|flavor| {
-			let tf = TransactionFactory {
-				clock,
-				flavor: Arc::new(flavor),
-			};
-			Self {
-				id: Uuid::new_v4(),
-				transaction_factory: tf.clone(),
-				strict: false,
-				auth_enabled: false,
-				query_timeout: None,
-				transaction_timeout: None,
-				notification_channel: None,
-				capabilities: Capabilities::default(),
-				index_stores: IndexStores::default(),
-				#[cfg(not(target_arch = "wasm32"))]
-				index_builder: IndexBuilder::new(tf),
-				#[cfg(feature = "jwks")]
-				jwks_cache: Arc::new(RwLock::new(JwksCache::new())),
-				#[cfg(storage)]
-				temporary_directory: None,
-			}
+		let changelog = match flavor.is_local() && *crate::cnf::CHANGELOG_ENABLED {
+			true => Some(Arc::new(Changelog::default())),
+			false => None,
+		};
+		let tf = TransactionFactory {
+			clock: builder.clock.unwrap_or_else(|| Arc::new(SizedClock::system())),
+			flavor: Arc::new(flavor),
+			changelog,
+			read_only: builder.read_only || builder.secondary.is_some(),
+			notifications: None,
+			usage: builder.storage_usage,
+			quota: builder.storage_quota,
+			#[cfg(feature = "opendal")]
+			blobs: match &builder.blob_store {
+				Some(url) => Some(Arc::new(BlobStore::new(url)?)),
+				None => None,
+			},
+		};
+		Ok(Self {
+			id: Uuid::new_v4(),
+			transaction_factory: tf.clone(),
+			strict: builder.strict,
+			auth_enabled: false,
+			query_timeout: builder.query_timeout,
+			transaction_timeout: builder.transaction_timeout,
+			query_row_limit: builder.query_row_limit,
+			notification_channel: None,
+			capabilities: Capabilities::default(),
+			index_stores: IndexStores::default(),
+			running_queries: RunningQueries::default(),
+			#[cfg(not(target_arch = "wasm32"))]
+			index_builder: IndexBuilder::new(tf),
+			#[cfg(feature = "jwks")]
+			jwks_cache: Arc::new(RwLock::new(JwksCache::new())),
+			sinks: Arc::new(Publishers::default()),
+			#[cfg(storage)]
+			temporary_directory: None,
+			#[cfg(storage)]
+			temporary_area: None,
+			prepared: Arc::new(Cache::new(*PREPARED_STATEMENT_CACHE_SIZE)),
 		})
 	}
 
+	/// Register a storage engine which is provided by an external crate
+	///
+	/// Once registered, any datastore which is created with a path using
+	/// the specified url scheme will be opened using the storage engine
+	/// returned by the factory. The factory is passed the remainder of the
+	/// path after the url scheme. The built-in url schemes can not be used.
+	///
+	/// ```rust,ignore
+	/// use surrealdb_core::kvs::{Datastore, KvStore};
+	///
+	/// Datastore::register_engine("myengine", |path| async move {
+	///     Ok(Box::new(MyEngine::open(&path)?) as Box<dyn KvStore>)
+	/// })?;
+	/// let ds = Datastore::new("myengine://path/to/data").await?;
+	/// ```
+	pub fn register_engine<F, Fut>(name: &str, factory: F) -> Result<(), Error>
+	where
+		F: Fn(String) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = Result<Box<dyn super::KvStore>, Error>> + Send + 'static,
+	{
+		super::external::register(name, factory)
+	}
+
 	/// Specify whether this Datastore should run in strict mode
 	pub fn with_node_id(mut self, id: Uuid) -> Self {
 		self.id = id;
@@ -444,7 +1007,9 @@ impl Datastore {
 
 	/// Specify whether this datastore should enable live query notifications
 	pub fn with_notifications(mut self) -> Self {
-		self.notification_channel = Some(async_channel::bounded(LQ_CHANNEL_SIZE));
+		let channel = async_channel::bounded(LQ_CHANNEL_SIZE);
+		self.transaction_factory.notifications = Some(channel.0.clone());
+		self.notification_channel = Some(channel);
 		self
 	}
 
@@ -460,6 +1025,12 @@ impl Datastore {
 		self
 	}
 
+	/// Set the maximum number of rows which a single SELECT statement can process
+	pub fn with_query_row_limit(mut self, limit: Option<u64>) -> Self {
+		self.query_row_limit = limit;
+		self
+	}
+
 	/// Set whether authentication is enabled for this Datastore
 	pub fn with_auth_enabled(mut self, enabled: bool) -> Self {
 		self.auth_enabled = enabled;
@@ -473,9 +1044,24 @@ impl Datastore {
 	}
 
 	#[cfg(storage)]
-	/// Set a temporary directory for ordering of large result sets
+	/// Set a temporary directory for ordering and grouping of large result sets
+	///
+	/// Temporary files are written to a directory owned by this datastore,
+	/// within the specified path, which is removed when the datastore is dropped.
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
-		self.temporary_directory = path.map(Arc::new);
+		self.temporary_area = path.as_ref().and_then(|path| {
+			match Builder::new().prefix("SURREAL-DS-").tempdir_in(path) {
+				Ok(dir) => Some(Arc::new(dir)),
+				Err(e) => {
+					warn!(target: TARGET, "Unable to create a temporary area in {}: {e}", path.display());
+					None
+				}
+			}
+		});
+		self.temporary_directory = match &self.temporary_area {
+			Some(dir) => Some(Arc::new(dir.path().to_path_buf())),
+			None => path.map(Arc::new),
+		};
 		self
 	}
 
@@ -505,7 +1091,6 @@ impl Datastore {
 	}
 
 	/// Does the datastore allow connections to a network target?
-	#[cfg(feature = "jwks")]
 	pub(crate) fn allows_network_target(&self, net_target: &NetTarget) -> bool {
 		self.capabilities.allows_network_target(net_target)
 	}
@@ -515,6 +1100,15 @@ impl Datastore {
 		&self.jwks_cache
 	}
 
+	pub(crate) fn sinks(&self) -> &Arc<Publishers> {
+		&self.sinks
+	}
+
+	/// Check if committed mutations are recorded in the changelog
+	pub(super) fn has_changelog(&self) -> bool {
+		self.transaction_factory.changelog.is_some()
+	}
+
 	pub(super) async fn clock_now(&self) -> Timestamp {
 		self.transaction_factory.clock.now().await
 	}
@@ -535,7 +1129,7 @@ impl Datastore {
 	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn get_version(&self) -> Result<Version, Error> {
 		// Start a new writeable transaction
-		let txn = self.transaction(Write, Pessimistic).await?.enclose();
+		let txn = self.internal_transaction(Write, Pessimistic).await?.enclose();
 		// Create the key where the version is stored
 		let key = crate::key::version::new();
 		// Check if a version is already set in storage
@@ -680,6 +1274,8 @@ impl Datastore {
 		self.changefeed_versionstamp(ts).await?;
 		// Garbage old changefeed data from all databases
 		self.changefeed_cleanup(ts).await?;
+		// Garbage old changelog data
+		self.changelog_cleanup(ts).await?;
 		// Everything ok
 		Ok(())
 	}
@@ -693,6 +1289,8 @@ impl Datastore {
 		self.changefeed_versionstamp(ts).await?;
 		// Garbage old changefeed data from all databases
 		self.changefeed_cleanup(ts).await?;
+		// Garbage old changelog data
+		self.changelog_cleanup(ts).await?;
 		// Everything ok
 		Ok(())
 	}
@@ -704,29 +1302,36 @@ impl Datastore {
 		trace!(target: TARGET, "Running datastore shutdown operations");
 		// Delete this datastore from the cluster
 		self.delete_node(self.id).await?;
-		// Run any storag engine shutdown tasks
-		match self.transaction_factory.flavor.as_ref() {
-			#[cfg(feature = "kv-mem")]
-			DatastoreFlavor::Mem(v) => v.shutdown().await,
-			#[cfg(feature = "kv-rocksdb")]
-			DatastoreFlavor::RocksDB(v) => v.shutdown().await,
-			#[cfg(feature = "kv-indxdb")]
-			DatastoreFlavor::IndxDB(v) => v.shutdown().await,
-			#[cfg(feature = "kv-tikv")]
-			DatastoreFlavor::TiKV(v) => v.shutdown().await,
-			#[cfg(feature = "kv-fdb")]
-			DatastoreFlavor::FoundationDB(v) => v.shutdown().await,
-			#[cfg(feature = "kv-surrealkv")]
-			DatastoreFlavor::SurrealKV(v) => v.shutdown().await,
-			#[cfg(feature = "kv-surrealcs")]
-			DatastoreFlavor::SurrealCS(v) => v.shutdown().await,
-			#[allow(unreachable_patterns)]
-			_ => unreachable!(),
-		}
+		// Run any storage engine shutdown tasks
+		self.transaction_factory.flavor.shutdown().await
+	}
+
+	/// Catch up with the latest commits made by the process which holds the datastore
+	///
+	/// Secondary instances catch up automatically when a transaction starts,
+	/// at most once every `SURREAL_ROCKSDB_SECONDARY_REFRESH_INTERVAL`
+	/// milliseconds. This waits for any running transactions to finish, and
+	/// then catches up immediately. For other datastores this does nothing.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn refresh(&self) -> Result<(), Error> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Refreshing the secondary datastore instance");
+		// Catch up with the primary datastore
+		self.transaction_factory.flavor.refresh().await
+	}
+
+	/// Drop the storage of databases which have been removed
+	pub(super) fn sweep_partitions(&self) -> Result<(), Error> {
+		self.transaction_factory.flavor.sweep_partitions()
 	}
 
 	/// Create a new transaction on this datastore
 	///
+	/// If a transaction timeout has been set on this datastore, then any
+	/// operation on the transaction after the timeout has elapsed will
+	/// return an [`Error::TxTimeout`] error, and the transaction will be
+	/// rolled back, releasing any locks held by the storage engine.
+	///
 	/// ```rust,no_run
 	/// use surrealdb_core::kvs::{Datastore, TransactionType::*, LockType::*};
 	/// use surrealdb_core::err::Error;
@@ -744,10 +1349,172 @@ impl Datastore {
 		&self,
 		write: TransactionType,
 		lock: LockType,
+	) -> Result<Transaction, Error> {
+		let txn = self.transaction_factory.transaction(write, lock).await?;
+		match self.transaction_timeout {
+			Some(timeout) => Ok(txn.with_timeout(timeout)),
+			None => Ok(txn),
+		}
+	}
+
+	/// Create a new transaction for internal background tasks
+	///
+	/// Background tasks, such as node membership updates, garbage
+	/// collection, and changefeed cleanup, are not subject to the
+	/// transaction timeout of the datastore, as they are not started
+	/// by a client, and may legitimately take longer than a query.
+	pub(crate) async fn internal_transaction(
+		&self,
+		write: TransactionType,
+		lock: LockType,
 	) -> Result<Transaction, Error> {
 		self.transaction_factory.transaction(write, lock).await
 	}
 
+	/// Create a new transaction on this datastore with a specific isolation level
+	///
+	/// Transactions created with [`Datastore::transaction`] use the default
+	/// isolation level of the storage engine, which is snapshot isolation
+	/// for most storage engines. Storage engines which can not natively
+	/// detect conflicting reads, but do detect conflicting writes, emulate
+	/// serializable snapshot isolation by rewriting any keys which were read
+	/// when the transaction commits. Requesting serializable snapshot
+	/// isolation for a writeable transaction on a storage engine which can
+	/// do neither returns [`Error::TxIsolationUnsupported`].
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::{Datastore, Isolation, TransactionType::*, LockType::*};
+	/// use surrealdb_core::err::Error;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("file://database.db").await?;
+	///     let tx = ds.transaction_with_isolation(Write, Optimistic, Isolation::SerializableSnapshot).await?;
+	///     tx.cancel().await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn transaction_with_isolation(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+		isolation: Isolation,
+	) -> Result<Transaction, Error> {
+		let txn = self
+			.transaction_factory
+			.isolated(write, lock, Some(isolation), Consistency::Strong)
+			.await?;
+		match self.transaction_timeout {
+			Some(timeout) => Ok(txn.with_timeout(timeout)),
+			None => Ok(txn),
+		}
+	}
+
+	/// Create a new transaction on this datastore with a specific read consistency
+	///
+	/// Transactions created with [`Datastore::transaction`] always observe
+	/// the latest committed data. With [`Consistency::Stale`], storage
+	/// engines which support it (currently TiKV) read from a consistent
+	/// snapshot taken at the specified duration in the past. This avoids
+	/// fetching a new timestamp for each read, and reads never wait on
+	/// locks held by more recent transactions, but reads are still served
+	/// by the leader of each region. Write transactions, and other storage
+	/// engines, ignore the read consistency.
+	///
+	/// ```rust,no_run
+	/// use std::time::Duration;
+	/// use surrealdb_core::kvs::{Consistency, Datastore, TransactionType::*, LockType::*};
+	/// use surrealdb_core::err::Error;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("tikv://127.0.0.1:2379").await?;
+	///     let stale = Consistency::Stale(Duration::from_secs(5));
+	///     let mut tx = ds.transaction_with_consistency(Read, Optimistic, stale).await?;
+	///     tx.cancel().await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn transaction_with_consistency(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+		consistency: Consistency,
+	) -> Result<Transaction, Error> {
+		let txn = self.transaction_factory.isolated(write, lock, None, consistency).await?;
+		match self.transaction_timeout {
+			Some(timeout) => Ok(txn.with_timeout(timeout)),
+			None => Ok(txn),
+		}
+	}
+
+	/// Run a closure within a write transaction, retrying on conflicts
+	///
+	/// The closure is passed a new transaction on each attempt. If the
+	/// closure succeeds, then the transaction is committed. If either the
+	/// closure or the commit fails with a transient conflict error, then
+	/// the transaction is cancelled, and the closure is retried after a
+	/// jittered exponential backoff. Any other error is returned directly.
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     ds.with_transaction(|tx| async move {
+	///         tx.set("key", "val", None).await
+	///     })
+	///     .await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn with_transaction<F, Fut, T>(&self, f: F) -> Result<T, Error>
+	where
+		F: Fn(Arc<Transaction>) -> Fut,
+		Fut: Future<Output = Result<T, Error>>,
+	{
+		let mut attempt = 0;
+		loop {
+			// Create a new transaction
+			let txn = self.transaction(Write, Optimistic).await?.enclose();
+			// Run the closure, and commit on success
+			let res = match f(txn.clone()).await {
+				Ok(v) => txn.commit().await.map(|_| v),
+				Err(e) => {
+					let _ = txn.cancel().await;
+					Err(e)
+				}
+			};
+			// Retry the transaction on conflict errors
+			match res {
+				Err(e) if e.is_retryable() && attempt < TRANSACTION_RETRY_ATTEMPTS => {
+					trace!(target: TARGET, attempt, "Retrying conflicting transaction");
+					attempt += 1;
+					Self::retry_backoff(attempt).await;
+				}
+				res => return res,
+			}
+		}
+	}
+
+	/// Sleep for a jittered exponential backoff before retrying a transaction
+	async fn retry_backoff(attempt: u32) {
+		use rand::Rng;
+		// Calculate the maximum delay for this attempt
+		let max = TRANSACTION_RETRY_BACKOFF
+			.saturating_mul(1 << attempt.min(16))
+			.min(TRANSACTION_RETRY_MAX_BACKOFF);
+		// Pick a random delay up to the maximum
+		let dur = rand::thread_rng().gen_range(Duration::ZERO..=max);
+		// Sleep for the specified time
+		#[cfg(target_arch = "wasm32")]
+		wasmtimer::tokio::sleep(dur).await;
+		#[cfg(not(target_arch = "wasm32"))]
+		tokio::time::sleep(dur).await;
+	}
+
 	/// Parse and execute an SQL query
 	///
 	/// ```rust,no_run
@@ -777,6 +1544,59 @@ impl Datastore {
 		self.process(ast, sess, vars).await
 	}
 
+	/// Parse an SQL query once, so that it can be executed repeatedly
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let id = ds.prepare("SELECT * FROM person WHERE age > $age;")?;
+	///     let res = ds.execute_prepared(id, &ses, None).await?;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub fn prepare(&self, txt: &str) -> Result<Uuid, Error> {
+		// Parse the SQL query text
+		let ast = syn::parse(txt)?;
+		// Store the parsed query
+		let id = Uuid::new_v4();
+		self.prepared.insert(id, Arc::new(ast));
+		// Return the statement identifier
+		Ok(id)
+	}
+
+	/// Execute a query which was previously parsed with [`Datastore::prepare`]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn execute_prepared(
+		&self,
+		id: Uuid,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		// Fetch the parsed query
+		let ast = match self.prepared.get(&id) {
+			Some(ast) => ast,
+			None => {
+				return Err(Error::PsNotFound {
+					value: id.to_string(),
+				})
+			}
+		};
+		// Process the AST
+		self.process(ast.as_ref().clone(), sess, vars).await
+	}
+
+	/// Remove a prepared query which is no longer needed
+	pub fn deallocate(&self, id: Uuid) -> bool {
+		self.prepared.remove(&id).is_some()
+	}
+
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn execute_import<S>(
 		&self,
@@ -791,6 +1611,8 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session or its token has been revoked
+		crate::iam::revoke::check_session(self, sess).await?;
 
 		// Check if anonymous actors can execute queries when auth is enabled
 		// TODO(sgirones): Check this as part of the authorisation layer
@@ -917,6 +1739,8 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session or its token has been revoked
+		crate::iam::revoke::check_session(self, sess).await?;
 		// Check if anonymous actors can execute queries when auth is enabled
 		// TODO(sgirones): Check this as part of the authorisation layer
 		self.check_anon(sess).map_err(|_| IamError::NotAllowed {
@@ -967,6 +1791,8 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session or its token has been revoked
+		crate::iam::revoke::check_session(self, sess).await?;
 		// Check if anonymous actors can compute values when auth is enabled
 		// TODO(sgirones): Check this as part of the authorisation layer
 		self.check_anon(sess).map_err(|_| IamError::NotAllowed {
@@ -987,10 +1813,6 @@ impl Datastore {
 		if let Some(timeout) = self.query_timeout {
 			ctx.add_timeout(timeout)?;
 		}
-		// Setup the notification channel
-		if let Some(channel) = &self.notification_channel {
-			ctx.add_notifications(Some(&channel.0));
-		}
 		// Start an execution context
 		sess.context(&mut ctx);
 		// Store the query variables
@@ -1047,6 +1869,8 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session or its token has been revoked
+		crate::iam::revoke::check_session(self, sess).await?;
 		// Create a new memory stack
 		let mut stack = TreeStack::new();
 		// Create a new query options
@@ -1059,10 +1883,6 @@ impl Datastore {
 		if let Some(timeout) = self.query_timeout {
 			ctx.add_timeout(timeout)?;
 		}
-		// Setup the notification channel
-		if let Some(channel) = &self.notification_channel {
-			ctx.add_notifications(Some(&channel.0));
-		}
 		// Start an execution context
 		sess.context(&mut ctx);
 		// Store the query variables
@@ -1159,6 +1979,8 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session or its token has been revoked
+		crate::iam::revoke::check_session(self, sess).await?;
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
 		// Create a new readonly transaction
@@ -1172,6 +1994,60 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a full database export as SQL, writing it to the blob store
+	#[cfg(feature = "opendal")]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn export_to_blob_store(
+		&self,
+		sess: &Session,
+		path: &str,
+		cfg: export::Config,
+	) -> Result<(), Error> {
+		// Check that a blob store is configured
+		let Some(store) = self.blob_store().cloned() else {
+			return Err(Error::BlobStore("No blob store is configured".to_owned()));
+		};
+		// Export the database into memory
+		let (snd, rcv) = async_channel::bounded(10);
+		let export = self.export_with_config(sess, snd, cfg).await?;
+		let collect = async {
+			let mut out = Vec::new();
+			while let Ok(v) = rcv.recv().await {
+				out.extend_from_slice(&v);
+			}
+			out
+		};
+		let (res, out) = futures::join!(export, collect);
+		res?;
+		// Write the export to the blob store
+		store.write(path, out).await
+	}
+
+	/// Performs a database import from SQL which is stored in the blob store
+	#[cfg(feature = "opendal")]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_from_blob_store(
+		&self,
+		sess: &Session,
+		path: &str,
+	) -> Result<Vec<Response>, Error> {
+		// Check that a blob store is configured
+		let Some(store) = self.blob_store() else {
+			return Err(Error::BlobStore("No blob store is configured".to_owned()));
+		};
+		// Read the export from the blob store
+		let sql = store.read(path).await?;
+		let sql = String::from_utf8(sql).map_err(|e| Error::BlobStore(e.to_string()))?;
+		// Execute the SQL import
+		self.import(&sql, sess).await
+	}
+
+	/// The object store in which large values are stored, if configured
+	#[cfg(feature = "opendal")]
+	pub(super) fn blob_store(&self) -> Option<&Arc<BlobStore>> {
+		self.transaction_factory.blobs.as_ref()
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
@@ -1197,6 +2073,7 @@ impl Datastore {
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled)
+			.with_row_limit(self.query_row_limit)
 	}
 	pub fn setup_ctx(&self) -> Result<MutableContext, Error> {
 		let mut ctx = MutableContext::from_ds(
@@ -1208,10 +2085,8 @@ impl Datastore {
 			#[cfg(storage)]
 			self.temporary_directory.clone(),
 		)?;
-		// Setup the notification channel
-		if let Some(channel) = &self.notification_channel {
-			ctx.add_notifications(Some(&channel.0));
-		}
+		// Setup the running query registry
+		ctx.set_running_queries(self.running_queries.clone());
 		Ok(ctx)
 	}
 
@@ -1283,4 +2158,95 @@ mod test {
 		assert_eq!(res, Value::Number(Number::Int(2)));
 		Ok(())
 	}
+
+	#[tokio::test]
+	pub async fn transaction_timeout_rolls_back() -> Result<(), Error> {
+		let timeout = Duration::from_millis(10);
+		let dbs = Datastore::new("memory").await?.with_transaction_timeout(Some(timeout));
+		// Operations fail once the timeout has elapsed
+		let tx = dbs.transaction(Write, Optimistic).await?;
+		tx.set("key", "val", None).await?;
+		tokio::time::sleep(timeout * 5).await;
+		assert!(matches!(tx.get("key", None).await, Err(Error::TxTimeout)));
+		assert!(matches!(tx.commit().await, Err(Error::TxTimeout)));
+		// The timed out transaction was rolled back
+		let tx = dbs.transaction(Read, Optimistic).await?;
+		assert_eq!(tx.get("key", None).await?, None);
+		tx.cancel().await?;
+		// Internal transactions are not subject to the timeout
+		let tx = dbs.internal_transaction(Write, Optimistic).await?;
+		tokio::time::sleep(timeout * 5).await;
+		tx.set("key", "val", None).await?;
+		tx.commit().await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	pub async fn with_transaction_retries_conflicts() -> Result<(), Error> {
+		use std::sync::atomic::{AtomicU32, Ordering};
+
+		let dbs = Datastore::new("memory").await?;
+		let attempts = AtomicU32::new(0);
+		// Fail with a conflict on the first two attempts
+		let res = dbs
+			.with_transaction(|tx| {
+				let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+				async move {
+					tx.set("key", format!("val{attempt}"), None).await?;
+					match attempt {
+						0 | 1 => Err(Error::TxRetryable),
+						_ => Ok(attempt),
+					}
+				}
+			})
+			.await?;
+		assert_eq!(res, 2);
+		// Only the last attempt should have been committed
+		let tx = dbs.transaction(Read, Optimistic).await?;
+		let val = tx.get("key", None).await?;
+		tx.cancel().await?;
+		assert_eq!(val, Some(b"val2".to_vec()));
+		// Other errors should not be retried
+		let res = dbs.with_transaction(|_| async { Err::<(), _>(Error::TxConditionNotMet) }).await;
+		assert!(matches!(res, Err(Error::TxConditionNotMet)));
+		Ok(())
+	}
+
+	#[tokio::test]
+	pub async fn with_transaction_retries_storage_conflicts() -> Result<(), Error> {
+		use std::sync::atomic::{AtomicU32, Ordering};
+
+		let dbs = Datastore::new("memory").await?;
+		let tx = dbs.transaction(Write, Optimistic).await?;
+		tx.set("counter", "0", None).await?;
+		tx.commit().await?;
+		let attempts = AtomicU32::new(0);
+		// Increment the counter, while another transaction modifies it
+		let res = dbs
+			.with_transaction(|tx| {
+				let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+				let dbs = &dbs;
+				async move {
+					let val = tx.get("counter", None).await?.unwrap();
+					let val: u32 = String::from_utf8(val).unwrap().parse().unwrap();
+					// Commit a conflicting change during the first attempt
+					if attempt == 0 {
+						let other = dbs.transaction(Write, Optimistic).await?;
+						other.set("counter", "10", None).await?;
+						other.commit().await?;
+					}
+					tx.set("counter", (val + 1).to_string(), None).await?;
+					Ok(val + 1)
+				}
+			})
+			.await?;
+		// The conflicting commit was retried with the latest value
+		assert_eq!(attempts.load(Ordering::SeqCst), 2);
+		assert_eq!(res, 11);
+		let tx = dbs.transaction(Read, Optimistic).await?;
+		let val = tx.get("counter", None).await?;
+		tx.cancel().await?;
+		assert_eq!(val, Some(b"11".to_vec()));
+		Ok(())
+	}
 }
@@ -0,0 +1,7 @@
+use std::sync::LazyLock;
+
+pub static DYNAMODB_PARTITION: LazyLock<String> =
+	lazy_env_parse!("SURREAL_DYNAMODB_PARTITION", String, "surrealdb".to_string());
+
+pub static DYNAMODB_CREATE_TABLE: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_DYNAMODB_CREATE_TABLE", bool, true);
@@ -0,0 +1,773 @@
+#![cfg(feature = "kv-dynamodb")]
+
+mod cnf;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::buffer::Buffer;
+use crate::kvs::{Check, Key, Val, Version};
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::{AttributeValue, ConditionCheck, Delete, Put, TransactWriteItem};
+use aws_sdk_dynamodb::Client;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::kvs::dynamodb";
+
+/// The maximum number of items in a single DynamoDB transaction.
+///
+/// Every key which is written, and every other key which was read, counts
+/// towards this limit, as the keys which were read are checked for
+/// concurrent modifications when committing. Transactions which exceed
+/// this limit fail with [`Error::TxTooLarge`] and are never applied.
+const MAX_TRANSACTION_ITEMS: usize = 100;
+
+#[non_exhaustive]
+pub struct Datastore {
+	client: Client,
+	table: Arc<str>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The buffered reads and writes of this transaction
+	buffer: Buffer,
+	/// The underlying DynamoDB client
+	client: Client,
+	/// The table which stores the keyspace
+	table: Arc<str>,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Load the region and credentials from the environment
+		let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+		let client = Client::new(&config);
+		// Create the keyspace table if missing
+		if *cnf::DYNAMODB_CREATE_TABLE {
+			create_table(&client, path).await?;
+		}
+		// Create the datastore
+		Ok(Datastore {
+			client,
+			table: Arc::from(path),
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Nothing to do here
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			buffer: Buffer::default(),
+			client: self.client.clone(),
+			table: self.table.clone(),
+		})
+	}
+}
+
+impl Transaction {
+	/// Fetch a key, checking the buffered writes first
+	async fn fetch(&mut self, key: Key) -> Result<Option<Val>, Error> {
+		if let Some(val) = self.buffer.get(&key) {
+			return Ok(val);
+		}
+		let res = self
+			.client
+			.get_item()
+			.table_name(&*self.table)
+			.key("pk", partition(&key))
+			.key("sk", blob(&key))
+			.projection_expression("val")
+			.consistent_read(true)
+			.send()
+			.await
+			.map_err(sdk)?;
+		let val = res.item().and_then(value);
+		self.buffer.read(key, val.clone());
+		Ok(val)
+	}
+	/// Fetch a range of keys, overlaid with the buffered writes
+	async fn range(&mut self, beg: Key, end: Key, limit: u32) -> Result<Vec<(Key, Val)>, Error> {
+		let rng = beg.as_slice()..end.as_slice();
+		// Fetch enough entries to account for buffered deletes
+		let fetch = limit as usize + self.buffer.count(rng.clone());
+		let mut res = Vec::new();
+		// Ranges which span several partitions scan the whole table
+		let Some(part) = range_partition(&beg, &end) else {
+			let res = match beg < end && fetch > 0 {
+				true => self.scan_table(&beg, &end, fetch).await?,
+				false => res,
+			};
+			return Ok(self.buffer.merge(rng, res, limit as usize));
+		};
+		if beg < end && fetch > 0 {
+			// Key attributes can not be empty, so an empty
+			// start key is replaced with an unbounded start
+			let cond = match beg.is_empty() {
+				true => "pk = :pk AND sk <= :end",
+				false => "pk = :pk AND sk BETWEEN :beg AND :end",
+			};
+			let mut next = None;
+			loop {
+				let mut req = self
+					.client
+					.query()
+					.table_name(&*self.table)
+					.key_condition_expression(cond)
+					.expression_attribute_values(":pk", part.clone())
+					.expression_attribute_values(":end", blob(&end))
+					.consistent_read(true)
+					.limit((fetch + 1 - res.len()).min(i32::MAX as usize) as i32)
+					.set_exclusive_start_key(next);
+				if !beg.is_empty() {
+					req = req.expression_attribute_values(":beg", blob(&beg));
+				}
+				let out = req.send().await.map_err(sdk)?;
+				for item in out.items() {
+					if let (Some(AttributeValue::B(k)), Some(v)) = (item.get("sk"), value(item)) {
+						// The end of the range is exclusive
+						if k.as_ref() != end.as_slice() {
+							res.push((k.as_ref().to_vec(), v));
+						}
+					}
+				}
+				next = out.last_evaluated_key;
+				if next.is_none() || res.len() >= fetch {
+					break;
+				}
+			}
+			res.truncate(fetch);
+		}
+		Ok(self.buffer.merge(rng, res, limit as usize))
+	}
+	/// Fetch a range of keys which spans several partitions
+	async fn scan_table(
+		&self,
+		beg: &Key,
+		end: &Key,
+		fetch: usize,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		// Key attributes can not be empty, so an empty
+		// start key is replaced with an unbounded start
+		let cond = match beg.is_empty() {
+			true => "sk < :end",
+			false => "sk >= :beg AND sk < :end",
+		};
+		let mut res = Vec::new();
+		let mut next = None;
+		loop {
+			let mut req = self
+				.client
+				.scan()
+				.table_name(&*self.table)
+				.filter_expression(cond)
+				.expression_attribute_values(":end", blob(end))
+				.consistent_read(true)
+				.set_exclusive_start_key(next);
+			if !beg.is_empty() {
+				req = req.expression_attribute_values(":beg", blob(beg));
+			}
+			let out = req.send().await.map_err(sdk)?;
+			for item in out.items() {
+				if let (Some(AttributeValue::B(k)), Some(v)) = (item.get("sk"), value(item)) {
+					res.push((k.as_ref().to_vec(), v));
+				}
+			}
+			next = out.last_evaluated_key;
+			if next.is_none() {
+				break;
+			}
+		}
+		// The items in a table scan are not ordered
+		res.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+		res.truncate(fetch);
+		Ok(res)
+	}
+	/// Apply the writes, as long as no key which was read has changed
+	async fn apply(
+		&self,
+		reads: HashMap<Key, Option<Val>>,
+		writes: BTreeMap<Key, Option<Val>>,
+	) -> Result<(), Error> {
+		let mut items = Vec::with_capacity(writes.len());
+		// Write each key, on condition that the value read is unchanged
+		for (k, v) in writes.iter() {
+			let (cond, vals) = condition(reads.get(k));
+			let item = match v {
+				Some(v) => TransactWriteItem::builder().put(
+					Put::builder()
+						.table_name(&*self.table)
+						.item("pk", partition(k))
+						.item("sk", blob(k))
+						.item("val", blob(v))
+						.set_condition_expression(cond)
+						.set_expression_attribute_values(vals)
+						.build()
+						.map_err(sdk)?,
+				),
+				None => TransactWriteItem::builder().delete(
+					Delete::builder()
+						.table_name(&*self.table)
+						.key("pk", partition(k))
+						.key("sk", blob(k))
+						.set_condition_expression(cond)
+						.set_expression_attribute_values(vals)
+						.build()
+						.map_err(sdk)?,
+				),
+			};
+			items.push(item.build());
+		}
+		// Check that the other keys which were read are unchanged
+		for (k, v) in reads.iter().filter(|(k, _)| !writes.contains_key(*k)) {
+			let (cond, vals) = condition(Some(v));
+			let check = ConditionCheck::builder()
+				.table_name(&*self.table)
+				.key("pk", partition(k))
+				.key("sk", blob(k))
+				.set_condition_expression(cond)
+				.set_expression_attribute_values(vals)
+				.build()
+				.map_err(sdk)?;
+			items.push(TransactWriteItem::builder().condition_check(check).build());
+		}
+		// DynamoDB limits the number of items in a transaction
+		if items.len() > MAX_TRANSACTION_ITEMS {
+			return Err(Error::TxTooLarge);
+		}
+		match self.client.transact_write_items().set_transact_items(Some(items)).send().await {
+			Ok(_) => Ok(()),
+			Err(e) => match e.as_service_error() {
+				Some(TransactWriteItemsError::TransactionCanceledException(c))
+					if c.cancellation_reasons().iter().any(|r| {
+						matches!(r.code(), Some("ConditionalCheckFailed" | "TransactionConflict"))
+					}) =>
+				{
+					Err(Error::TxRetryable)
+				}
+				Some(TransactWriteItemsError::TransactionConflictException(_)) => {
+					Err(Error::TxRetryable)
+				}
+				_ => Err(sdk(e)),
+			},
+		}
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Discard the buffered writes
+		self.buffer.drain();
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Check if there is anything to write
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+		// Apply the writes, as long as no key which was read has changed
+		let (reads, writes) = self.buffer.drain();
+		self.apply(reads, writes).await?;
+		// Continue
+		Ok(())
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let res = self.fetch(key.into()).await?.is_some();
+		// Return result
+		Ok(res)
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let res = self.fetch(key.into()).await?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Set the key
+		self.buffer.set(key.into(), val.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		// Set the key if empty
+		match self.fetch(key.clone()).await? {
+			None => self.buffer.set(key, val),
+			_ => return Err(Error::TxKeyAlreadyExists),
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		let chk = chk.map(Into::into);
+		// Set the key if valid
+		match (self.fetch(key.clone()).await?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.set(key, val),
+			(None, None) => self.buffer.set(key, val),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Deletes a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Remove the key
+		self.buffer.del(key.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let chk = chk.map(Into::into);
+		// Delete the key if valid
+		match (self.fetch(key.clone()).await?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.del(key),
+			(None, None) => self.buffer.del(key),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit).await?;
+		// Convert the keys and values
+		let res = res.into_iter().map(|kv| kv.0).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// DynamoDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit).await?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// DynamoDB does not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl Transaction {
+	pub(crate) fn new_save_point(&mut self) {
+		self.buffer.new_save_point();
+	}
+
+	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.rollback_to_save_point();
+		Ok(())
+	}
+
+	pub(crate) fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.release_last_save_point();
+		Ok(())
+	}
+}
+
+/// The partition key value of a key.
+///
+/// The keys of each database are stored in their own partition, which
+/// spreads the load across partitions, while range scans within a database
+/// are still served by a single partition. All other keys share a partition.
+fn partition(key: &[u8]) -> AttributeValue {
+	let part = super::partition::prefix(key).unwrap_or_default();
+	AttributeValue::B(Blob::new([cnf::DYNAMODB_PARTITION.as_bytes(), part].concat()))
+}
+
+/// The partition key value of a range of keys, if all keys in the range share a partition
+fn range_partition(beg: &[u8], end: &[u8]) -> Option<AttributeValue> {
+	// A range within a database must not extend past the database
+	if let Some(part) = super::partition::prefix(beg) {
+		let rng = super::partition::range(part);
+		return (end <= rng.end.as_slice()).then(|| partition(beg));
+	}
+	// Database keys follow a namespace prefix, and then a `*`
+	let nest = match beg.strip_prefix(b"/*").and_then(|k| k.iter().position(|&b| b == 0)) {
+		Some(ns) => {
+			let nest = &beg[..ns + 3];
+			// The range must not extend past the namespace
+			if end > super::partition::range(nest).end.as_slice() {
+				return None;
+			}
+			nest
+		}
+		None => b"/",
+	};
+	// The range must not overlap the keys of any database
+	let dbs = [nest, b"*"].concat()..[nest, b"+"].concat();
+	(beg >= dbs.end.as_slice() || end <= dbs.start.as_slice()).then(|| partition(beg))
+}
+
+/// Convert bytes into a binary attribute value
+fn blob(v: &[u8]) -> AttributeValue {
+	AttributeValue::B(Blob::new(v))
+}
+
+/// Extract the stored value from an item
+fn value(item: &HashMap<String, AttributeValue>) -> Option<Val> {
+	match item.get("val") {
+		Some(AttributeValue::B(v)) => Some(v.as_ref().to_vec()),
+		_ => None,
+	}
+}
+
+/// The condition that a key still has the value which was read
+#[allow(clippy::type_complexity)]
+fn condition(
+	read: Option<&Option<Val>>,
+) -> (Option<String>, Option<HashMap<String, AttributeValue>>) {
+	match read {
+		Some(Some(v)) => {
+			(Some("val = :val".to_owned()), Some(HashMap::from([(":val".to_owned(), blob(v))])))
+		}
+		Some(None) => (Some("attribute_not_exists(sk)".to_owned()), None),
+		None => (None, None),
+	}
+}
+
+/// Convert an AWS SDK error into a transaction error
+fn sdk<E: std::error::Error>(e: E) -> Error {
+	Error::Tx(DisplayErrorContext(e).to_string())
+}
+
+/// Create the keyspace table, and wait for it to become active
+async fn create_table(client: &Client, table: &str) -> Result<(), Error> {
+	use aws_sdk_dynamodb::types::{
+		AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType,
+		TableStatus,
+	};
+	// Check if the table already exists
+	match client.describe_table().table_name(table).send().await {
+		Ok(_) => return Ok(()),
+		Err(e) if e.as_service_error().is_some_and(|e| e.is_resource_not_found_exception()) => (),
+		Err(e) => return Err(sdk(e)),
+	}
+	debug!(target: TARGET, "Creating table: {table}");
+	let attr = |name: &str, kind| {
+		AttributeDefinition::builder().attribute_name(name).attribute_type(kind).build()
+	};
+	let key =
+		|name: &str, kind| KeySchemaElement::builder().attribute_name(name).key_type(kind).build();
+	client
+		.create_table()
+		.table_name(table)
+		.attribute_definitions(attr("pk", ScalarAttributeType::B).map_err(sdk)?)
+		.attribute_definitions(attr("sk", ScalarAttributeType::B).map_err(sdk)?)
+		.key_schema(key("pk", KeyType::Hash).map_err(sdk)?)
+		.key_schema(key("sk", KeyType::Range).map_err(sdk)?)
+		.billing_mode(BillingMode::PayPerRequest)
+		.send()
+		.await
+		.map_err(sdk)?;
+	// Wait for the table to become active
+	loop {
+		let res = client.describe_table().table_name(table).send().await.map_err(sdk)?;
+		if res.table().and_then(|t| t.table_status()) == Some(&TableStatus::Active) {
+			return Ok(());
+		}
+		tokio::time::sleep(Duration::from_millis(500)).await;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keys_are_partitioned_by_database() {
+		let part = |k: &[u8]| match partition(k) {
+			AttributeValue::B(v) => v.into_inner(),
+			_ => unreachable!(),
+		};
+		// Keys in a database share a partition
+		let key = part(b"/*test\0*test\0*person\0*\0\0\0\x01");
+		assert_eq!(key, part(b"/*test\0*test\0!tbperson\0"));
+		// Keys in another database use another partition
+		assert_ne!(key, part(b"/*test\0*other\0!tbperson\0"));
+		// Keys outside of a database share the default partition
+		assert_eq!(part(b"/!nstest\0"), part(b"/*test\0!dbtest\0"));
+		assert_ne!(key, part(b"/!nstest\0"));
+	}
+
+	#[test]
+	fn ranges_within_a_partition() {
+		// Scanning the tables of a database uses the database partition
+		assert!(range_partition(b"/*test\0*test\0!tb\0", b"/*test\0*test\0!tb\xff").is_some());
+		// Scanning the databases of a namespace uses the default partition
+		assert!(range_partition(b"/*test\0!db\0", b"/*test\0!db\xff").is_some());
+		// Scanning the namespaces uses the default partition
+		assert!(range_partition(b"/!ns\0", b"/!ns\xff").is_some());
+	}
+
+	#[test]
+	fn ranges_across_partitions() {
+		// Scanning a whole namespace includes the partitions of its databases
+		assert!(range_partition(b"/*test\0", b"/*test\xff").is_none());
+		// Scanning the whole keyspace includes every partition
+		assert!(range_partition(b"", b"\xff").is_none());
+		// Scanning past the end of a database includes other partitions
+		assert!(range_partition(b"/*test\0*test\0", b"/*test\0*zzzz\0").is_none());
+		// Scanning all namespaces includes the partitions of their databases
+		assert!(range_partition(b"/*", b"/+").is_none());
+	}
+}
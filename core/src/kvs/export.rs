@@ -136,7 +136,7 @@ impl Transaction {
 		cfg: Config,
 		chn: Sender<Vec<u8>>,
 	) -> Result<(), Error> {
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+		// Output ROLES, USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS, TYPES
 		self.export_metadata(&cfg, &chn, ns, db).await?;
 		// Output TABLES
 		self.export_tables(ns, db, &cfg, &chn).await?;
@@ -153,8 +153,10 @@ impl Transaction {
 		// Output OPTIONS
 		self.export_section("OPTION", vec!["OPTION IMPORT"], chn).await?;
 
-		// Output USERS
+		// Output ROLES and USERS
 		if cfg.users {
+			let roles = self.all_db_roles(ns, db).await?;
+			self.export_section("ROLES", roles.to_vec(), chn).await?;
 			let users = self.all_db_users(ns, db).await?;
 			self.export_section("USERS", users.to_vec(), chn).await?;
 		}
@@ -183,6 +185,12 @@ impl Transaction {
 			self.export_section("ANALYZERS", analyzers.to_vec(), chn).await?;
 		}
 
+		// Output TYPES, which are needed by any table fields
+		if cfg.tables.is_any() {
+			let types = self.all_db_types(ns, db).await?;
+			self.export_section("TYPES", types.to_vec(), chn).await?;
+		}
+
 		Ok(())
 	}
 
@@ -265,6 +273,14 @@ impl Transaction {
 		}
 		chn.send(bytes!("")).await?;
 
+		let sinks = self.all_tb_sinks(ns, db, &table.name).await?;
+		if !sinks.is_empty() {
+			for sink in sinks.iter() {
+				chn.send(bytes!(format!("{};", sink))).await?;
+			}
+			chn.send(bytes!("")).await?;
+		}
+
 		Ok(())
 	}
 
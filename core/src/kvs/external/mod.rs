@@ -0,0 +1,735 @@
+//! Storage engines which are provided by external crates.
+//!
+//! A downstream crate can implement the [`KvStore`] and [`KvTransaction`]
+//! traits, and register a factory for its storage engine under a custom
+//! url scheme using [`Datastore::register_engine`]. Any datastore which is
+//! then created with that scheme will use the registered storage engine.
+//!
+//! [`Datastore::register_engine`]: crate::kvs::Datastore::register_engine
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::savepoint::{SaveOperation, SavePointImpl, SavePoints};
+use crate::kvs::Check;
+use crate::kvs::Key;
+use crate::kvs::Val;
+use crate::kvs::Version;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// The url schemes which are reserved for the built-in storage engines
+const RESERVED: &[&str] = &[
+	"memory",
+	"file",
+	"rocksdb",
+	"surrealkv",
+	"surrealcs",
+	"indxdb",
+	"tikv",
+	"fdb",
+	"sled",
+	"lmdb",
+	"sqlite",
+	"postgres",
+	"s3",
+	"dynamodb",
+	"redis",
+	"tiered",
+	"opfs",
+	"shard",
+];
+
+/// A storage engine which can be registered by an external crate
+pub trait KvStore: Send + Sync + 'static {
+	/// Start a new transaction
+	fn transaction(
+		&self,
+		write: bool,
+		lock: bool,
+	) -> BoxFuture<'_, Result<Box<dyn KvTransaction>, Error>>;
+
+	/// Shutdown the storage engine
+	fn shutdown(&self) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async { Ok(()) })
+	}
+}
+
+/// A transaction on a storage engine which is provided by an external crate.
+///
+/// Conditional writes must be atomic with respect to other transactions.
+/// The conformance test suite in the `kv-testsuite` feature can be used
+/// to verify that an implementation behaves as the database expects.
+pub trait KvTransaction: Send + Sync {
+	/// Cancel the transaction, reversing all changes
+	fn cancel(&mut self) -> BoxFuture<'_, Result<(), Error>>;
+
+	/// Commit the transaction, persisting all changes
+	fn commit(&mut self) -> BoxFuture<'_, Result<(), Error>>;
+
+	/// Check if a key exists
+	fn exists(&mut self, key: Key, version: Option<u64>) -> BoxFuture<'_, Result<bool, Error>>;
+
+	/// Fetch a key from the storage engine
+	fn get(&mut self, key: Key, version: Option<u64>) -> BoxFuture<'_, Result<Option<Val>, Error>>;
+
+	/// Insert or update a key
+	fn set(&mut self, key: Key, val: Val, version: Option<u64>)
+		-> BoxFuture<'_, Result<(), Error>>;
+
+	/// Insert a key if it doesn't exist, or return [`Error::TxKeyAlreadyExists`]
+	fn put(&mut self, key: Key, val: Val, version: Option<u64>)
+		-> BoxFuture<'_, Result<(), Error>>;
+
+	/// Update a key if the current value matches, or return [`Error::TxConditionNotMet`]
+	fn putc(&mut self, key: Key, val: Val, chk: Option<Val>) -> BoxFuture<'_, Result<(), Error>>;
+
+	/// Delete a key
+	fn del(&mut self, key: Key) -> BoxFuture<'_, Result<(), Error>>;
+
+	/// Delete a key if the current value matches, or return [`Error::TxConditionNotMet`]
+	fn delc(&mut self, key: Key, chk: Option<Val>) -> BoxFuture<'_, Result<(), Error>>;
+
+	/// Retrieve a range of keys, in order
+	fn keys(
+		&mut self,
+		rng: Range<Key>,
+		limit: u32,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<Vec<Key>, Error>>;
+
+	/// Retrieve a range of key-value pairs, in order
+	fn scan(
+		&mut self,
+		rng: Range<Key>,
+		limit: u32,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<Vec<(Key, Val)>, Error>>;
+}
+
+/// A factory which opens a registered storage engine at a path
+type Factory =
+	Arc<dyn Fn(String) -> BoxFuture<'static, Result<Box<dyn KvStore>, Error>> + Send + Sync>;
+
+/// The storage engines which have been registered by external crates
+static ENGINES: LazyLock<RwLock<HashMap<String, Factory>>> = LazyLock::new(Default::default);
+
+/// Register a storage engine factory under a url scheme
+pub(super) fn register<F, Fut>(name: &str, factory: F) -> Result<(), Error>
+where
+	F: Fn(String) -> Fut + Send + Sync + 'static,
+	Fut: Future<Output = Result<Box<dyn KvStore>, Error>> + Send + 'static,
+{
+	// Built-in storage engines can not be replaced
+	if RESERVED.contains(&name) {
+		return Err(Error::Ds(format!("The `{name}` storage engine is built-in")));
+	}
+	// Register the storage engine factory
+	let factory: Factory = Arc::new(move |path| Box::pin(factory(path)));
+	let mut engines = ENGINES.write().map_err(|e| Error::Ds(e.to_string()))?;
+	engines.insert(name.to_owned(), factory);
+	Ok(())
+}
+
+/// Check if a storage engine has been registered under a url scheme
+pub(super) fn exists(name: &str) -> bool {
+	ENGINES.read().map(|e| e.contains_key(name)).unwrap_or_default()
+}
+
+#[non_exhaustive]
+pub struct Datastore {
+	/// The url scheme of the storage engine
+	name: String,
+	/// The underlying storage engine
+	db: Box<dyn KvStore>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The underlying storage engine transaction
+	inner: Box<dyn KvTransaction>,
+	/// The save point implementation
+	save_points: SavePoints,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database using a registered storage engine
+	pub(crate) async fn new(name: &str, path: &str) -> Result<Datastore, Error> {
+		let factory =
+			ENGINES.read().map_err(|e| Error::Ds(e.to_string()))?.get(name).cloned().ok_or_else(
+				|| Error::Ds(format!("The `{name}` storage engine is not registered")),
+			)?;
+		Ok(Datastore {
+			name: name.to_owned(),
+			db: factory(path.to_owned()).await?,
+		})
+	}
+	/// The url scheme of the storage engine
+	pub(crate) fn name(&self) -> &str {
+		&self.name
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		self.db.shutdown().await
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, lock: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			write,
+			check,
+			inner: self.db.transaction(write, lock).await?,
+			save_points: Default::default(),
+		})
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancel a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Cancel this transaction
+		self.inner.cancel().await
+	}
+
+	/// Commit a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Commit this transaction
+		self.inner.commit().await
+	}
+
+	/// Check if a key exists
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		self.inner.exists(key.into(), version).await
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		self.inner.get(key.into(), version).await
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Prepare the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Set).await?
+		} else {
+			None
+		};
+		// Set the key
+		self.inner.set(key, val.into(), version).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Set the key if empty
+		self.inner.put(key, val.into(), version).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Set the key if the condition matches
+		self.inner.putc(key, val.into(), chk.map(Into::into)).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Delete the key
+		self.inner.del(key).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Delete the key if the condition matches
+		self.inner.delc(key, chk.map(Into::into)).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieve a range of keys from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Scan the keys
+		self.inner.keys(rng.start.into()..rng.end.into(), limit, version).await
+	}
+
+	/// Retrieve a range of keys from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Scan the keys
+		self.inner.scan(rng.start.into()..rng.end.into(), limit, version).await
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// External storage engines do not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl SavePointImpl for Transaction {
+	fn get_save_points(&mut self) -> &mut SavePoints {
+		&mut self.save_points
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::LockType::Optimistic;
+	use crate::kvs::TransactionType::{Read, Write};
+	use std::collections::BTreeMap;
+	use std::sync::Mutex;
+
+	/// A storage engine which keeps all keys in memory
+	#[derive(Clone, Default)]
+	struct MemStore(Arc<Mutex<BTreeMap<Key, Val>>>);
+
+	/// A transaction which applies its changes to the store on commit
+	struct MemTransaction {
+		store: MemStore,
+		data: BTreeMap<Key, Val>,
+	}
+
+	impl KvStore for MemStore {
+		fn transaction(
+			&self,
+			_write: bool,
+			_lock: bool,
+		) -> BoxFuture<'_, Result<Box<dyn KvTransaction>, Error>> {
+			Box::pin(async move {
+				let data = self.0.lock().unwrap().clone();
+				let store = self.clone();
+				Ok(Box::new(MemTransaction {
+					store,
+					data,
+				}) as Box<dyn KvTransaction>)
+			})
+		}
+	}
+
+	impl KvTransaction for MemTransaction {
+		fn cancel(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async { Ok(()) })
+		}
+
+		fn commit(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				*self.store.0.lock().unwrap() = std::mem::take(&mut self.data);
+				Ok(())
+			})
+		}
+
+		fn exists(&mut self, key: Key, _: Option<u64>) -> BoxFuture<'_, Result<bool, Error>> {
+			Box::pin(async move { Ok(self.data.contains_key(&key)) })
+		}
+
+		fn get(&mut self, key: Key, _: Option<u64>) -> BoxFuture<'_, Result<Option<Val>, Error>> {
+			Box::pin(async move { Ok(self.data.get(&key).cloned()) })
+		}
+
+		fn set(&mut self, key: Key, val: Val, _: Option<u64>) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				self.data.insert(key, val);
+				Ok(())
+			})
+		}
+
+		fn put(&mut self, key: Key, val: Val, _: Option<u64>) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				match self.data.contains_key(&key) {
+					true => Err(Error::TxKeyAlreadyExists),
+					false => {
+						self.data.insert(key, val);
+						Ok(())
+					}
+				}
+			})
+		}
+
+		fn putc(
+			&mut self,
+			key: Key,
+			val: Val,
+			chk: Option<Val>,
+		) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				match self.data.get(&key) == chk.as_ref() {
+					true => {
+						self.data.insert(key, val);
+						Ok(())
+					}
+					false => Err(Error::TxConditionNotMet),
+				}
+			})
+		}
+
+		fn del(&mut self, key: Key) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				self.data.remove(&key);
+				Ok(())
+			})
+		}
+
+		fn delc(&mut self, key: Key, chk: Option<Val>) -> BoxFuture<'_, Result<(), Error>> {
+			Box::pin(async move {
+				match self.data.get(&key) == chk.as_ref() {
+					true => {
+						self.data.remove(&key);
+						Ok(())
+					}
+					false => Err(Error::TxConditionNotMet),
+				}
+			})
+		}
+
+		fn keys(
+			&mut self,
+			rng: Range<Key>,
+			limit: u32,
+			_: Option<u64>,
+		) -> BoxFuture<'_, Result<Vec<Key>, Error>> {
+			Box::pin(async move {
+				Ok(self.data.range(rng).take(limit as usize).map(|(k, _)| k.clone()).collect())
+			})
+		}
+
+		fn scan(
+			&mut self,
+			rng: Range<Key>,
+			limit: u32,
+			_: Option<u64>,
+		) -> BoxFuture<'_, Result<Vec<(Key, Val)>, Error>> {
+			Box::pin(async move {
+				Ok(self
+					.data
+					.range(rng)
+					.take(limit as usize)
+					.map(|(k, v)| (k.clone(), v.clone()))
+					.collect())
+			})
+		}
+	}
+
+	#[test]
+	fn reserved_names() {
+		let res = register("rocksdb", |_| async { Err(Error::Ds("unused".to_owned())) });
+		assert!(res.is_err());
+		assert!(!exists("rocksdb"));
+		let res = crate::kvs::Datastore::register_engine("memory", |_| async {
+			Ok(Box::new(MemStore::default()) as Box<dyn KvStore>)
+		});
+		assert!(matches!(res, Err(Error::Ds(_))));
+		assert!(!exists("memory"));
+	}
+
+	#[tokio::test]
+	async fn registered_engine() {
+		assert!(crate::kvs::Datastore::new("testengine://data").await.is_err());
+		register("testengine", |path| async move { Err(Error::Ds(format!("opened {path}"))) })
+			.unwrap();
+		assert!(exists("testengine"));
+		match crate::kvs::Datastore::new("testengine://data").await {
+			Err(Error::Ds(e)) => assert_eq!(e, "opened data"),
+			_ => panic!("expected the registered factory to be called"),
+		}
+	}
+
+	#[tokio::test]
+	async fn registered_engine_transactions() {
+		let store = MemStore::default();
+		let engine = store.clone();
+		crate::kvs::Datastore::register_engine("memtest", move |_| {
+			let engine = engine.clone();
+			async move { Ok(Box::new(engine) as Box<dyn KvStore>) }
+		})
+		.unwrap();
+		let ds = crate::kvs::Datastore::new("memtest://data").await.unwrap();
+		// Committed changes are written to the storage engine
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set("a", "1", None).await.unwrap();
+		tx.set("b", "2", None).await.unwrap();
+		tx.set("c", "3", None).await.unwrap();
+		tx.commit().await.unwrap();
+		assert_eq!(store.0.lock().unwrap().get(b"a".as_slice()), Some(&b"1".to_vec()));
+		// Cancelled changes are discarded
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.del("a").await.unwrap();
+		tx.set("d", "4", None).await.unwrap();
+		assert_eq!(tx.get("a", None).await.unwrap(), None);
+		tx.cancel().await.unwrap();
+		// Committed values can be fetched and scanned
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("a", None).await.unwrap(), Some(b"1".to_vec()));
+		assert_eq!(tx.get("d", None).await.unwrap(), None);
+		let res = tx.scan("a".."c", 10, None).await.unwrap();
+		assert_eq!(res, vec![(b"a".to_vec(), b"1".to_vec()), (b"b".to_vec(), b"2".to_vec())]);
+		tx.cancel().await.unwrap();
+		// Deleted keys are removed from the storage engine
+		let tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.del("b").await.unwrap();
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.get("b", None).await.unwrap(), None);
+		assert_eq!(tx.keys("a".."z", 10, None).await.unwrap(), vec![b"a".to_vec(), b"c".to_vec()]);
+		tx.cancel().await.unwrap();
+	}
+}
@@ -8,3 +8,9 @@ pub static FOUNDATIONDB_TRANSACTION_RETRY_LIMIT: LazyLock<i32> =
 
 pub static FOUNDATIONDB_TRANSACTION_MAX_RETRY_DELAY: LazyLock<i32> =
 	lazy_env_parse!("SURREAL_FOUNDATIONDB_TRANSACTION_MAX_RETRY_DELAY", i32, 500);
+
+pub static FOUNDATIONDB_TENANT: LazyLock<String> =
+	lazy_env_parse!("SURREAL_FOUNDATIONDB_TENANT", String);
+
+pub static FOUNDATIONDB_DIRECTORY: LazyLock<String> =
+	lazy_env_parse!("SURREAL_FOUNDATIONDB_DIRECTORY", String);
@@ -6,13 +6,16 @@ use crate::err::Error;
 use crate::key::debug::Sprintable;
 use crate::kvs::savepoint::{SaveOperation, SavePointImpl, SavePoints, SavePrepare};
 use crate::kvs::Check;
+use crate::kvs::Isolation;
 use crate::kvs::Key;
 use crate::kvs::Val;
 use crate::kvs::Version;
 use crate::vs::Versionstamp;
+use foundationdb::directory::{Directory, DirectoryLayer};
 use foundationdb::options::DatabaseOption;
 use foundationdb::options::MutationType;
 use foundationdb::Database;
+use foundationdb::FdbTenant;
 use foundationdb::RangeOption;
 use foundationdb::Transaction as Tx;
 use futures::StreamExt;
@@ -26,6 +29,10 @@ const TIMESTAMP: [u8; 10] = [0x00; 10];
 #[non_exhaustive]
 pub struct Datastore {
 	db: Database,
+	/// The tenant which isolates this datastore, if specified
+	tenant: Option<FdbTenant>,
+	/// The directory prefix which isolates this datastore, if specified
+	prefix: Arc<[u8]>,
 	// The Database stored above, relies on the
 	// foundationdb network being booted before
 	// the client can be used. The return result
@@ -42,14 +49,16 @@ pub struct Datastore {
 pub struct Transaction {
 	/// Is the transaction complete?
 	done: bool,
-	/// Should this transaction lock?
-	lock: bool,
+	/// Should reads be performed as snapshot reads?
+	snapshot: bool,
 	/// Is the transaction writeable?
 	write: bool,
 	/// Should we check unhandled transactions?
 	check: Check,
 	/// The underlying datastore transaction
 	inner: Option<Tx>,
+	/// The directory prefix of every key
+	prefix: Arc<[u8]>,
 	/// The save point implementation
 	save_points: SavePoints,
 }
@@ -94,6 +103,10 @@ impl Datastore {
 	/// See https://apple.github.io/foundationdb/administration.html
 	/// for more information on cluster connection files.
 	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		Self::open(path, &cnf::FOUNDATIONDB_TENANT, &cnf::FOUNDATIONDB_DIRECTORY).await
+	}
+	/// Open a new database, within a tenant and directory if not empty
+	async fn open(path: &str, tenant: &str, directory: &str) -> Result<Datastore, Error> {
 		// Initialize the FoundationDB Client API
 		static NETWORK: LazyLock<Arc<foundationdb::api::NetworkAutoStop>> =
 			LazyLock::new(|| Arc::new(unsafe { foundationdb::boot() }));
@@ -119,34 +132,94 @@ impl Datastore {
 				.map_err(|e| {
 					Error::Ds(format!("Unable to set transaction max retry delay: {e}"))
 				})?;
+				// Open the tenant if specified
+				let tenant =
+					match tenant {
+						"" => None,
+						name => Some(db.open_tenant(name.as_bytes()).map_err(|e| {
+							Error::Ds(format!("Unable to open tenant `{name}`: {e}"))
+						})?),
+					};
+				// Open the directory if specified
+				let prefix = match directory {
+					"" => Arc::from([]),
+					path => Self::directory(&db, tenant.as_ref(), path).await?,
+				};
 				Ok(Datastore {
 					db,
+					tenant,
+					prefix,
 					_fdbnet,
 				})
 			}
 			Err(e) => Err(Error::Ds(e.to_string())),
 		}
 	}
+	/// Create or open a directory, returning its key prefix
+	///
+	/// The directory is specified as a `/` separated path
+	/// within the FoundationDB directory layer, allowing
+	/// multiple datastores to share a single cluster, or
+	/// a single tenant, without any conflicting keys.
+	async fn directory(
+		db: &Database,
+		tenant: Option<&FdbTenant>,
+		path: &str,
+	) -> Result<Arc<[u8]>, Error> {
+		// Split the directory path
+		let path: Vec<String> =
+			path.split('/').filter(|s| !s.is_empty()).map(str::to_owned).collect();
+		// Create a transaction within the tenant
+		let tx = match tenant {
+			Some(tenant) => tenant.create_trx(),
+			None => db.create_trx(),
+		}
+		.map_err(|e| Error::Tx(e.to_string()))?;
+		// Create or open the directory
+		let dir = DirectoryLayer::default()
+			.create_or_open(&tx, &path, None, None)
+			.await
+			.map_err(|e| Error::Ds(format!("Unable to open directory `{path:?}`: {e:?}")))?;
+		let prefix = dir
+			.bytes()
+			.map_err(|e| Error::Ds(format!("Unable to open directory `{path:?}`: {e:?}")))?;
+		let prefix = Arc::from(prefix);
+		// Commit the directory creation
+		tx.commit().await?;
+		// Return the directory prefix
+		Ok(prefix)
+	}
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
 		// Nothing to do here
 		Ok(())
 	}
 	/// Start a new transaction
-	pub(crate) async fn transaction(&self, write: bool, lock: bool) -> Result<Transaction, Error> {
+	pub(crate) async fn transaction(
+		&self,
+		write: bool,
+		_: bool,
+		isolation: Isolation,
+	) -> Result<Transaction, Error> {
 		// Specify the check level
 		#[cfg(not(debug_assertions))]
 		let check = Check::Warn;
 		#[cfg(debug_assertions)]
 		let check = Check::Panic;
+		// Create a new transaction within the tenant
+		let tx = match &self.tenant {
+			Some(tenant) => tenant.create_trx(),
+			None => self.db.create_trx(),
+		};
 		// Create a new transaction
-		match self.db.create_trx() {
+		match tx {
 			Ok(inner) => Ok(Transaction {
 				done: false,
-				lock,
+				snapshot: !matches!(isolation, Isolation::SerializableSnapshot),
 				check,
 				write,
 				inner: Some(inner),
+				prefix: self.prefix.clone(),
 				save_points: Default::default(),
 			}),
 			Err(e) => Err(Error::Tx(e.to_string())),
@@ -155,18 +228,29 @@ impl Datastore {
 }
 
 impl Transaction {
-	/// Each transaction uses `lock=true` to behave similarly to pessimistic
-	/// locks in the same way that pessimistic transactions work in TiKV.
-	/// Standard transactions in FoundationDB (where `snapshot=false`) behave
-	/// behaves like a TiKV pessimistic transaction, by automatically retrying
-	/// on commit conflicts at the client layer. In FoundationDB we assume
-	/// that `lock=true` is effectively specifying that we should ensure
-	/// transactions are serializable. If the transaction is writeable, we also
-	/// assume that the user never wants to lose serializability, so we go with
-	/// the standard FoundationDB serializable more in that scenario.
+	/// Standard transactions in FoundationDB (where `snapshot=false`) are
+	/// serializable, as every read adds a read conflict range which is
+	/// checked when the transaction commits. Snapshot reads (where
+	/// `snapshot=true`) skip these conflict checks, which gives snapshot
+	/// isolation. Unless a specific isolation level is requested, writeable
+	/// or pessimistic transactions are serializable, as we assume that the
+	/// user never wants to lose serializability in those scenarios.
 	#[inline(always)]
 	fn snapshot(&self) -> bool {
-		!self.write && !self.lock
+		self.snapshot
+	}
+
+	/// Add the directory prefix to a key
+	fn prefixed(&self, key: Key) -> Key {
+		match self.prefix.is_empty() {
+			true => key,
+			false => [&self.prefix[..], &key].concat(),
+		}
+	}
+
+	/// Remove the directory prefix from a key
+	fn unprefixed<'a>(&self, key: &'a [u8]) -> &'a [u8] {
+		key.strip_prefix(&self.prefix[..]).unwrap_or(key)
 	}
 }
 
@@ -241,7 +325,13 @@ impl super::api::Transaction for Transaction {
 			return Err(Error::TxFinished);
 		}
 		// Check the key
-		let res = self.inner.as_ref().unwrap().get(&key.into(), self.snapshot()).await?.is_some();
+		let res = self
+			.inner
+			.as_ref()
+			.unwrap()
+			.get(&self.prefixed(key.into()), self.snapshot())
+			.await?
+			.is_some();
 		// Return result
 		Ok(res)
 	}
@@ -265,7 +355,7 @@ impl super::api::Transaction for Transaction {
 			.inner
 			.as_ref()
 			.unwrap()
-			.get(&key.into(), self.snapshot())
+			.get(&self.prefixed(key.into()), self.snapshot())
 			.await?
 			.map(|v| v.to_vec());
 		// Return result
@@ -300,7 +390,7 @@ impl super::api::Transaction for Transaction {
 			None
 		};
 		// Set the key
-		self.inner.as_ref().unwrap().set(&key, &val.into());
+		self.inner.as_ref().unwrap().set(&self.prefixed(key), &val.into());
 		// Confirm the save point
 		if let Some(prep) = prep {
 			self.save_points.save(prep);
@@ -337,6 +427,8 @@ impl super::api::Transaction for Transaction {
 		} else {
 			None
 		};
+		// Add the directory prefix
+		let key = self.prefixed(key);
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
 		// Get the existing value (if any)
@@ -384,6 +476,8 @@ impl super::api::Transaction for Transaction {
 		} else {
 			None
 		};
+		// Add the directory prefix
+		let key = self.prefixed(key);
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
 		// Get the existing value (if any)
@@ -429,7 +523,7 @@ impl super::api::Transaction for Transaction {
 			None
 		};
 		// Remove the key
-		self.inner.as_ref().unwrap().clear(&key);
+		self.inner.as_ref().unwrap().clear(&self.prefixed(key));
 		// Confirm the save point
 		if let Some(prep) = prep {
 			self.save_points.save(prep);
@@ -462,6 +556,8 @@ impl super::api::Transaction for Transaction {
 		} else {
 			None
 		};
+		// Add the directory prefix
+		let key = self.prefixed(key);
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
 		// Get the existing value (if any)
@@ -500,8 +596,11 @@ impl super::api::Transaction for Transaction {
 		}
 		// TODO: Check if we need savepoint with ranges
 
+		// Add the directory prefix
+		let beg = self.prefixed(rng.start.into());
+		let end = self.prefixed(rng.end.into());
 		// Delete the key range
-		self.inner.as_ref().unwrap().clear_range(&rng.start.into(), &rng.end.into());
+		self.inner.as_ref().unwrap().clear_range(&beg, &end);
 		// Return result
 		Ok(())
 	}
@@ -529,8 +628,8 @@ impl super::api::Transaction for Transaction {
 		let inner = self.inner.as_ref().unwrap();
 		// Convert the range to bytes
 		let rng: Range<Key> = Range {
-			start: rng.start.into(),
-			end: rng.end.into(),
+			start: self.prefixed(rng.start.into()),
+			end: self.prefixed(rng.end.into()),
 		};
 		// Create result set
 		let mut res = vec![];
@@ -544,7 +643,7 @@ impl super::api::Transaction for Transaction {
 		// Scan the keys in the iterator
 		while let Some(val) = req.next().await {
 			for v in val?.into_iter() {
-				res.push(Key::from(v.key()));
+				res.push(Key::from(self.unprefixed(v.key())));
 			}
 		}
 		// Return result
@@ -574,8 +673,8 @@ impl super::api::Transaction for Transaction {
 		let inner = self.inner.as_ref().unwrap();
 		// Convert the range to bytes
 		let rng: Range<Key> = Range {
-			start: rng.start.into(),
-			end: rng.end.into(),
+			start: self.prefixed(rng.start.into()),
+			end: self.prefixed(rng.end.into()),
 		};
 		// Create result set
 		let mut res = vec![];
@@ -589,7 +688,7 @@ impl super::api::Transaction for Transaction {
 		// Scan the keys in the iterator
 		while let Some(val) = req.next().await {
 			for v in val?.into_iter() {
-				res.push((Key::from(v.key()), Val::from(v.value())));
+				res.push((Key::from(self.unprefixed(v.key())), Val::from(v.value())));
 			}
 		}
 		// Return result
@@ -636,7 +735,7 @@ impl super::api::Transaction for Transaction {
 			return Err(Error::TxReadonly);
 		}
 		// Build the key starting with the prefix
-		let mut key: Vec<u8> = prefix.into();
+		let mut key: Vec<u8> = self.prefixed(prefix.into());
 		// Get the position of the timestamp
 		let pos = key.len() as u32;
 		// Append the timestamp placeholder
@@ -677,3 +776,64 @@ impl SavePointImpl for Transaction {
 		&mut self.save_points
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::api::Transaction as _;
+	use foundationdb::tenant::TenantManagement;
+
+	const CLUSTER: &str = "/etc/foundationdb/fdb.cluster";
+
+	#[tokio::test]
+	async fn tenants_are_isolated() {
+		// Create the tenants, which may already exist
+		let ds = Datastore::open(CLUSTER, "", "").await.unwrap();
+		for name in ["surrealdb-test-a", "surrealdb-test-b"] {
+			let _ = TenantManagement::create_tenant(&ds.db, name.as_bytes()).await;
+		}
+		let a = Datastore::open(CLUSTER, "surrealdb-test-a", "").await.unwrap();
+		let b = Datastore::open(CLUSTER, "surrealdb-test-b", "").await.unwrap();
+		// Write the same key in each tenant
+		for (ds, val) in [(&a, "a"), (&b, "b")] {
+			let mut tx =
+				ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+			tx.set("test", val, None).await.unwrap();
+			tx.commit().await.unwrap();
+		}
+		// Each tenant only sees its own value
+		for (ds, val) in [(&a, b"a"), (&b, b"b")] {
+			let mut tx = ds.transaction(false, false, Isolation::Snapshot).await.unwrap();
+			assert_eq!(tx.get("test", None).await.unwrap(), Some(val.to_vec()));
+			tx.cancel().await.unwrap();
+		}
+		// Removing all keys in one tenant leaves the other untouched
+		let mut tx = a.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.delr(vec![]..vec![0xff]).await.unwrap();
+		tx.commit().await.unwrap();
+		let mut tx = b.transaction(false, false, Isolation::Snapshot).await.unwrap();
+		assert_eq!(tx.get("test", None).await.unwrap(), Some(b"b".to_vec()));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn directories_are_isolated() {
+		let a = Datastore::open(CLUSTER, "", "surrealdb/test/a").await.unwrap();
+		let b = Datastore::open(CLUSTER, "", "surrealdb/test/b").await.unwrap();
+		assert_ne!(a.prefix, b.prefix);
+		// Write the same key in each directory
+		for (ds, val) in [(&a, "a"), (&b, "b")] {
+			let mut tx =
+				ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+			tx.set("test", val, None).await.unwrap();
+			tx.commit().await.unwrap();
+		}
+		// Each directory only sees its own keys
+		for (ds, val) in [(&a, b"a"), (&b, b"b")] {
+			let mut tx = ds.transaction(false, false, Isolation::Snapshot).await.unwrap();
+			let res = tx.scan(vec![]..vec![0xff], 10, None).await.unwrap();
+			assert_eq!(res, vec![(b"test".to_vec(), val.to_vec())]);
+			tx.cancel().await.unwrap();
+		}
+	}
+}
@@ -0,0 +1,150 @@
+//! Post-commit hooks for transactions.
+//!
+//! Some side effects of a transaction, such as live query notifications,
+//! must only be observed once the transaction has been committed. These are
+//! recorded on the transaction as it runs, are undone along with any save
+//! point which is rolled back, and are discarded if the transaction is
+//! cancelled or fails to commit. Once the storage engine has committed the
+//! transaction, the hooks are run in the order in which they were recorded.
+//!
+//! The hooks are run by the datastore itself, rather than relying on any
+//! change notifications from the storage engine, so that live queries work
+//! in the same way for every storage engine, including embedded single-node
+//! deployments on local storage engines.
+use crate::dbs::Notification;
+use async_channel::{Sender, TrySendError};
+use std::mem;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::spawn;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_futures::spawn_local as spawn;
+
+/// The hooks which are run when a transaction commits
+#[derive(Default)]
+pub(super) struct Hooks {
+	/// The channel on which live query notifications are delivered
+	sink: Option<Sender<Notification>>,
+	/// The live query notifications recorded in this transaction
+	notifications: Vec<Notification>,
+	/// The number of notifications recorded when each save point was created
+	save_points: Vec<usize>,
+}
+
+impl Hooks {
+	/// Create the hooks for a new transaction
+	pub(super) fn new(sink: Option<Sender<Notification>>) -> Self {
+		Hooks {
+			sink,
+			..Default::default()
+		}
+	}
+	/// Check if live query notifications are delivered on commit
+	pub(super) fn notifies(&self) -> bool {
+		self.sink.is_some()
+	}
+	/// Record a live query notification, to be delivered on commit
+	pub(super) fn notify(&mut self, notification: Notification) {
+		if self.sink.is_some() {
+			self.notifications.push(notification);
+		}
+	}
+	/// Discard all of the hooks recorded in this transaction
+	pub(super) fn clear(&mut self) {
+		self.notifications.clear();
+		self.save_points.clear();
+	}
+	/// Run the hooks once the transaction has been committed
+	pub(super) fn run(&mut self) {
+		// Check if there is anything to deliver
+		let Some(sink) = self.sink.as_ref() else {
+			return;
+		};
+		// Deliver the notifications in order
+		let mut notifications = mem::take(&mut self.notifications).into_iter();
+		for notification in notifications.by_ref() {
+			match sink.try_send(notification) {
+				Ok(_) => continue,
+				// The channel is full, so deliver the rest in the background
+				Err(TrySendError::Full(notification)) => {
+					let sink = sink.clone();
+					spawn(async move {
+						let pending = std::iter::once(notification).chain(notifications);
+						for notification in pending {
+							if sink.send(notification).await.is_err() {
+								break;
+							}
+						}
+					});
+					break;
+				}
+				// The channel is closed, so nothing can be delivered
+				Err(TrySendError::Closed(_)) => break,
+			}
+		}
+	}
+	/// Create a new save point for the recorded hooks
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.notifications.len());
+	}
+	/// Undo the hooks recorded since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(len) = self.save_points.pop() {
+			self.notifications.truncate(len);
+		}
+	}
+	/// Keep the hooks recorded since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Hooks;
+	use crate::dbs::{Action, Notification};
+	use crate::sql::{Uuid, Value};
+
+	fn notification(n: i64) -> Notification {
+		Notification::new(Uuid::default(), Action::Create, Value::from(n), Value::None)
+	}
+
+	#[test]
+	fn notifications_are_delivered_in_order() {
+		let (send, recv) = async_channel::unbounded();
+		let mut hooks = Hooks::new(Some(send));
+		hooks.notify(notification(1));
+		hooks.notify(notification(2));
+		assert!(recv.try_recv().is_err());
+		hooks.run();
+		assert_eq!(recv.try_recv().unwrap().record, Value::from(1));
+		assert_eq!(recv.try_recv().unwrap().record, Value::from(2));
+		assert!(recv.try_recv().is_err());
+	}
+
+	#[test]
+	fn notifications_are_rolled_back_to_save_point() {
+		let (send, recv) = async_channel::unbounded();
+		let mut hooks = Hooks::new(Some(send));
+		hooks.notify(notification(1));
+		hooks.new_save_point();
+		hooks.notify(notification(2));
+		hooks.rollback_to_save_point();
+		hooks.new_save_point();
+		hooks.notify(notification(3));
+		hooks.release_last_save_point();
+		hooks.run();
+		assert_eq!(recv.try_recv().unwrap().record, Value::from(1));
+		assert_eq!(recv.try_recv().unwrap().record, Value::from(3));
+		assert!(recv.try_recv().is_err());
+	}
+
+	#[test]
+	fn notifications_are_discarded_when_cleared() {
+		let (send, recv) = async_channel::unbounded();
+		let mut hooks = Hooks::new(Some(send));
+		hooks.notify(notification(1));
+		hooks.clear();
+		hooks.run();
+		assert!(recv.try_recv().is_err());
+	}
+}
@@ -45,7 +45,7 @@ impl BuildingStatus {
 		matches!(self, Self::Error(_))
 	}
 
-	fn is_built(&self) -> bool {
+	pub(crate) fn is_built(&self) -> bool {
 		matches!(self, Self::Built)
 	}
 }
@@ -97,29 +97,42 @@ impl IndexBuilder {
 		ix: Arc<DefineIndexStatement>,
 	) -> Result<(), Error> {
 		match self.indexes.entry(ix) {
-			Entry::Occupied(e) => {
+			Entry::Occupied(mut e) => {
 				// If the building is currently running we return error
 				if !e.get().1.is_finished() {
 					return Err(Error::IndexAlreadyBuilding {
 						index: e.key().name.to_string(),
 					});
 				}
+				// Otherwise the index is built again
+				let ib = self.start_building(ctx, opt, e.key().clone())?;
+				e.insert(ib);
 			}
 			Entry::Vacant(e) => {
 				// No index is currently building, we can start building it
-				let building = Arc::new(Building::new(ctx, self.tf.clone(), opt, e.key().clone())?);
-				let b = building.clone();
-				let jh = task::spawn(async move {
-					if let Err(err) = b.compute().await {
-						b.set_status(BuildingStatus::Error(err.into())).await;
-					}
-				});
-				e.insert((building, jh));
+				let ib = self.start_building(ctx, opt, e.key().clone())?;
+				e.insert(ib);
 			}
 		}
 		Ok(())
 	}
 
+	fn start_building(
+		&self,
+		ctx: &Context,
+		opt: Options,
+		ix: Arc<DefineIndexStatement>,
+	) -> Result<IndexBuilding, Error> {
+		let building = Arc::new(Building::new(ctx, self.tf.clone(), opt, ix)?);
+		let b = building.clone();
+		let jh = task::spawn(async move {
+			if let Err(err) = b.compute().await {
+				b.set_status(BuildingStatus::Error(err.into())).await;
+			}
+		});
+		Ok((building, jh))
+	}
+
 	pub(crate) async fn consume(
 		&self,
 		ctx: &Context,
@@ -135,6 +148,12 @@ impl IndexBuilder {
 		Ok(ConsumeResult::Ignored(old_values, new_values))
 	}
 
+	/// Checks if the index is being built, or if its build failed.
+	/// Such an index is incomplete, and can't be used by queries.
+	pub(crate) async fn is_building(&self, ix: &DefineIndexStatement) -> bool {
+		self.get_status(ix).await.is_some_and(|s| !s.is_built())
+	}
+
 	pub(crate) async fn get_status(&self, ix: &DefineIndexStatement) -> Option<BuildingStatus> {
 		if let Some(a) = self.indexes.get(ix) {
 			Some(a.value().0.status.lock().await.clone())
@@ -291,11 +310,19 @@ impl Building {
 	}
 
 	async fn compute(&self) -> Result<(), Error> {
+		let ns = self.opt.ns()?;
+		let db = self.opt.db()?;
+		// Remove any data of a previous build of the index
+		{
+			let ctx = self.new_write_tx_ctx().await?;
+			let tx = ctx.tx();
+			let key = crate::key::index::all::new(ns, db, &self.tb, &self.ix.name);
+			catch!(tx, tx.delp(key).await);
+			tx.commit().await?;
+		}
 		// Set the initial status
 		self.set_status(BuildingStatus::InitialIndexing(0)).await;
 		// First iteration, we index every keys
-		let ns = self.opt.ns()?;
-		let db = self.opt.db()?;
 		let beg = thing::prefix(ns, db, &self.tb);
 		let end = thing::suffix(ns, db, &self.tb);
 		let mut next = Some(beg..end);
@@ -1,6 +1,11 @@
+use crate::fnc::operate;
+use crate::sql::statements::LiveStatement;
+use crate::sql::{Cond, Expression, Id, Idiom, Operator, Part, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store)]
@@ -17,3 +22,310 @@ pub struct Live {
 	/// The table in which this LIVE query exists
 	pub tb: String,
 }
+
+/// The WHERE clauses of the live queries on a table, compiled into predicates
+/// which can be checked against a document without evaluating the full clause
+/// in the context of each subscriber.
+pub(crate) struct LiveFilter {
+	/// The live queries the filter was compiled from
+	lvs: Arc<[LiveStatement]>,
+	/// The compiled WHERE clause of each live query
+	compiled: Vec<Compiled>,
+	/// The live queries requiring a field to equal a value, by field and value
+	equalities: HashMap<Idiom, HashMap<Value, Vec<usize>>>,
+	/// The live queries which can not be looked up by value
+	others: Vec<usize>,
+	/// The live queries which maintain the aggregates of each group
+	aggregates: Vec<usize>,
+}
+
+struct Compiled {
+	/// The conditions which must all be true for the WHERE clause to match
+	predicates: Vec<Predicate>,
+	/// Whether the predicates are equivalent to the whole WHERE clause
+	complete: bool,
+}
+
+/// A comparison between a field of the document and a literal value
+struct Predicate {
+	idiom: Idiom,
+	operator: Operator,
+	value: Value,
+	/// Whether the value is on the left side of the operator
+	reversed: bool,
+}
+
+impl LiveFilter {
+	pub(crate) fn new(lvs: Arc<[LiveStatement]>) -> Self {
+		let mut compiled = Vec::with_capacity(lvs.len());
+		let mut equalities: HashMap<Idiom, HashMap<Value, Vec<usize>>> = HashMap::new();
+		let mut others = vec![];
+		let mut aggregates = vec![];
+		for (i, lv) in lvs.iter().enumerate() {
+			// Grouped live queries are checked before and after each change
+			if lv.is_aggregate() {
+				aggregates.push(i);
+				compiled.push(Compiled {
+					predicates: vec![],
+					complete: false,
+				});
+				continue;
+			}
+			let c = match &lv.cond {
+				Some(cond) => Compiled::new(cond),
+				None => Compiled {
+					predicates: vec![],
+					complete: true,
+				},
+			};
+			// Index the live query on its first equality with a hashable value
+			match c.predicates.iter().find(|p| p.is_lookup()) {
+				Some(p) => equalities
+					.entry(p.idiom.clone())
+					.or_default()
+					.entry(p.value.clone())
+					.or_default()
+					.push(i),
+				None => others.push(i),
+			}
+			compiled.push(c);
+		}
+		Self {
+			lvs,
+			compiled,
+			equalities,
+			others,
+			aggregates,
+		}
+	}
+
+	/// Returns the live queries which maintain the aggregates of each group, in order of definition.
+	pub(crate) fn aggregates(&self) -> impl Iterator<Item = &LiveStatement> {
+		self.aggregates.iter().map(|&i| &self.lvs[i])
+	}
+
+	/// Returns the live queries which may match a document, in order of definition.
+	/// Each live query is returned with a flag indicating whether its WHERE clause
+	/// is known to match, or whether it still needs to be evaluated.
+	pub(crate) fn candidates(&self, doc: &Value) -> Vec<(&LiveStatement, bool)> {
+		let mut res = self.others.clone();
+		for (idiom, values) in &self.equalities {
+			match Predicate::pick(doc, idiom) {
+				Some(v) if Predicate::is_hashable(&v) => {
+					if let Some(l) = values.get(&v) {
+						res.extend(l);
+					}
+				}
+				// The value can not be looked up, so all the live queries are checked
+				_ => res.extend(values.values().flatten()),
+			}
+		}
+		res.sort_unstable();
+		res.into_iter()
+			.filter_map(|i| self.compiled[i].check(doc).map(|m| (&self.lvs[i], m)))
+			.collect()
+	}
+}
+
+impl Compiled {
+	fn new(cond: &Cond) -> Self {
+		let mut predicates = vec![];
+		let complete = Self::compile(&cond.0, &mut predicates);
+		Self {
+			predicates,
+			complete,
+		}
+	}
+
+	/// Collects the predicates of a conjunction, returning false if some part could not be compiled
+	fn compile(v: &Value, predicates: &mut Vec<Predicate>) -> bool {
+		let Value::Expression(e) = v else {
+			return false;
+		};
+		let Expression::Binary {
+			l,
+			o,
+			r,
+		} = e.as_ref()
+		else {
+			return false;
+		};
+		if *o == Operator::And {
+			let l = Self::compile(l, predicates);
+			let r = Self::compile(r, predicates);
+			return l && r;
+		}
+		if !Predicate::is_supported(o) {
+			return false;
+		}
+		let (idiom, value, reversed) = match (l, r) {
+			(Value::Idiom(i), v) if Predicate::is_literal(v) => (i, v, false),
+			(v, Value::Idiom(i)) if Predicate::is_literal(v) => (i, v, true),
+			_ => return false,
+		};
+		if !idiom.iter().all(|p| matches!(p, Part::Field(_))) {
+			return false;
+		}
+		predicates.push(Predicate {
+			idiom: idiom.clone(),
+			operator: o.clone(),
+			value: value.clone(),
+			reversed,
+		});
+		true
+	}
+
+	/// Returns None if the document does not match, otherwise whether the match is certain
+	fn check(&self, doc: &Value) -> Option<bool> {
+		let mut certain = self.complete;
+		for p in &self.predicates {
+			match p.check(doc) {
+				Some(false) => return None,
+				Some(true) => {}
+				None => certain = false,
+			}
+		}
+		Some(certain)
+	}
+}
+
+impl Predicate {
+	fn is_supported(o: &Operator) -> bool {
+		matches!(
+			o,
+			Operator::Equal
+				| Operator::Exact
+				| Operator::NotEqual
+				| Operator::LessThan
+				| Operator::LessThanOrEqual
+				| Operator::MoreThan
+				| Operator::MoreThanOrEqual
+				| Operator::Contain
+				| Operator::Inside
+		)
+	}
+
+	/// Checks that a value does not need to be computed
+	fn is_literal(v: &Value) -> bool {
+		match v {
+			Value::Thing(t) => matches!(t.id, Id::Number(_) | Id::String(_)),
+			Value::Array(a) => a.iter().all(Self::is_literal),
+			Value::None | Value::Null | Value::Bool(_) | Value::Number(_) => true,
+			Value::Strand(_) | Value::Uuid(_) | Value::Datetime(_) | Value::Duration(_) => true,
+			_ => false,
+		}
+	}
+
+	/// Checks that equal values are also equal once hashed
+	fn is_hashable(v: &Value) -> bool {
+		matches!(v, Value::Bool(_) | Value::Strand(_) | Value::Thing(_))
+	}
+
+	fn is_lookup(&self) -> bool {
+		matches!(self.operator, Operator::Equal | Operator::Exact) && Self::is_hashable(&self.value)
+	}
+
+	/// Picks the value of a field, if it does not need to be computed
+	fn pick(doc: &Value, idiom: &Idiom) -> Option<Value> {
+		let mut v = doc;
+		for p in idiom.iter() {
+			let Part::Field(f) = p else {
+				return None;
+			};
+			match v {
+				Value::Object(o) => match o.get(f.as_str()) {
+					Some(w) => v = w,
+					None => return Some(Value::None),
+				},
+				Value::None => return Some(Value::None),
+				// Records would be fetched, and arrays would be mapped
+				_ => return None,
+			}
+		}
+		match v {
+			Value::Future(_) => None,
+			v => Some(v.clone()),
+		}
+	}
+
+	/// Returns None if the predicate can not be checked without evaluating the WHERE clause
+	fn check(&self, doc: &Value) -> Option<bool> {
+		let v = Self::pick(doc, &self.idiom)?;
+		let (l, r) = match self.reversed {
+			false => (&v, &self.value),
+			true => (&self.value, &v),
+		};
+		let res = match self.operator {
+			Operator::Equal => operate::equal(l, r),
+			Operator::Exact => operate::exact(l, r),
+			Operator::NotEqual => operate::not_equal(l, r),
+			Operator::LessThan => operate::less_than(l, r),
+			Operator::LessThanOrEqual => operate::less_than_or_equal(l, r),
+			Operator::MoreThan => operate::more_than(l, r),
+			Operator::MoreThanOrEqual => operate::more_than_or_equal(l, r),
+			Operator::Contain => operate::contain(l, r),
+			Operator::Inside => operate::inside(l, r),
+			_ => return None,
+		};
+		res.ok().map(|v| v.is_truthy())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::LiveFilter;
+	use crate::sql::statements::LiveStatement;
+	use crate::sql::{Cond, Fields, Value};
+	use crate::syn;
+
+	fn filter(conds: &[&str]) -> LiveFilter {
+		let lvs: Vec<LiveStatement> = conds
+			.iter()
+			.map(|c| {
+				let cond = (!c.is_empty()).then(|| Cond(syn::value(c).unwrap()));
+				LiveStatement::from_source_parts(Fields::all(), Value::None, cond, None, None, None)
+			})
+			.collect();
+		LiveFilter::new(lvs.into())
+	}
+
+	fn candidates(f: &LiveFilter, doc: &str) -> Vec<(usize, bool)> {
+		let doc = syn::value(doc).unwrap();
+		let lvs = f.candidates(&doc);
+		lvs.into_iter()
+			.map(|(lv, m)| (f.lvs.iter().position(|l| l.id == lv.id).unwrap(), m))
+			.collect()
+	}
+
+	#[test]
+	fn live_filter_equalities() {
+		let f =
+			filter(&["status = 'open'", "status = 'closed'", "", "status = 'open' AND age > 18"]);
+		assert_eq!(
+			candidates(&f, "{ status: 'open', age: 20 }"),
+			vec![(0, true), (2, true), (3, true)]
+		);
+		assert_eq!(candidates(&f, "{ status: 'open', age: 10 }"), vec![(0, true), (2, true)]);
+		assert_eq!(candidates(&f, "{ status: 'closed' }"), vec![(1, true), (2, true)]);
+		assert_eq!(candidates(&f, "{ age: 20 }"), vec![(2, true)]);
+	}
+
+	#[test]
+	fn live_filter_partially_compiled() {
+		let f = filter(&[
+			"address.city = 'London' AND string::len(name) > 3",
+			"18 < age OR age = NONE",
+		]);
+		assert_eq!(candidates(&f, "{ address: { city: 'London' } }"), vec![(0, false), (1, false)]);
+		assert_eq!(candidates(&f, "{ address: { city: 'Paris' } }"), vec![(1, false)]);
+		// Records linked from the document would need to be fetched
+		assert_eq!(candidates(&f, "{ address: address:1 }"), vec![(0, false), (1, false)]);
+	}
+
+	#[test]
+	fn live_filter_reversed() {
+		let f = filter(&["18 < age", "'admin' INSIDE roles"]);
+		assert_eq!(candidates(&f, "{ age: 20, roles: ['user'] }"), vec![(0, true)]);
+		assert_eq!(candidates(&f, "{ age: 10, roles: ['admin'] }"), vec![(1, true)]);
+	}
+}
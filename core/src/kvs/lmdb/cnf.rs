@@ -0,0 +1,6 @@
+use std::sync::LazyLock;
+
+pub static LMDB_MAP_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_LMDB_MAP_SIZE", usize, 64 * 1024 * 1024 * 1024);
+
+pub static LMDB_MAX_READERS: LazyLock<u32> = lazy_env_parse!("SURREAL_LMDB_MAX_READERS", u32, 126);
@@ -0,0 +1,488 @@
+#![cfg(feature = "kv-lmdb")]
+
+mod cnf;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::buffer::Buffer;
+use crate::kvs::{Check, Key, Val, Version};
+use heed::types::Bytes;
+use heed::{Database, Env, EnvOpenOptions};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::ops::{Bound, Range};
+
+const TARGET: &str = "surrealdb::core::kvs::lmdb";
+
+#[non_exhaustive]
+pub struct Datastore {
+	env: Env,
+	db: Database<Bytes, Bytes>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The buffered reads and writes of this transaction
+	buffer: Buffer,
+	/// The underlying datastore environment
+	env: Env,
+	/// The underlying datastore database
+	db: Database<Bytes, Bytes>,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Ensure the database directory exists
+		std::fs::create_dir_all(path).map_err(|e| Error::Ds(e.to_string()))?;
+		// Configure custom options
+		let mut opts = EnvOpenOptions::new();
+		// Set the maximum size of the memory map
+		debug!(target: TARGET, "Memory map size: {}", *cnf::LMDB_MAP_SIZE);
+		opts.map_size(*cnf::LMDB_MAP_SIZE);
+		// Set the maximum number of concurrent readers
+		debug!(target: TARGET, "Maximum readers: {}", *cnf::LMDB_MAX_READERS);
+		opts.max_readers(*cnf::LMDB_MAX_READERS);
+		// Open the environment. This is safe as long
+		// as the database files are not modified or
+		// opened again by this process concurrently.
+		let env = unsafe { opts.open(path)? };
+		// Create the unnamed database if missing
+		let mut txn = env.write_txn()?;
+		let db = env.create_database(&mut txn, None)?;
+		txn.commit()?;
+		// Create the datastore
+		Ok(Datastore {
+			env,
+			db,
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Flush any pending writes to disk
+		self.env.force_sync()?;
+		// Everything ok
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			buffer: Buffer::default(),
+			env: self.env.clone(),
+			db: self.db,
+		})
+	}
+}
+
+impl Transaction {
+	/// Fetch a key, checking the buffered writes first
+	fn fetch(&mut self, key: Key) -> Result<Option<Val>, Error> {
+		if let Some(val) = self.buffer.get(&key) {
+			return Ok(val);
+		}
+		let txn = self.env.read_txn()?;
+		let val = self.db.get(&txn, &key)?.map(<[u8]>::to_vec);
+		self.buffer.read(key, val.clone());
+		Ok(val)
+	}
+	/// Fetch a range of keys, overlaid with the buffered writes
+	fn range(&mut self, beg: Key, end: Key, limit: u32) -> Result<Vec<(Key, Val)>, Error> {
+		let rng = beg.as_slice()..end.as_slice();
+		// Fetch enough entries to account for buffered deletes
+		let fetch = limit as usize + self.buffer.count(rng.clone());
+		let bounds = (Bound::Included(rng.start), Bound::Excluded(rng.end));
+		let txn = self.env.read_txn()?;
+		let mut res = Vec::new();
+		for kv in self.db.range(&txn, &bounds)?.take(fetch) {
+			let (k, v) = kv?;
+			res.push((k.to_vec(), v.to_vec()));
+		}
+		Ok(self.buffer.merge(rng, res, limit as usize))
+	}
+	/// Apply the writes, as long as no key which was read has changed
+	fn apply(
+		&self,
+		reads: HashMap<Key, Option<Val>>,
+		writes: BTreeMap<Key, Option<Val>>,
+	) -> Result<(), Error> {
+		// LMDB only allows a single writer at a time
+		let mut txn = self.env.write_txn()?;
+		for (k, v) in reads.iter() {
+			if self.db.get(&txn, k)? != v.as_deref() {
+				txn.abort();
+				return Err(Error::TxRetryable);
+			}
+		}
+		for (k, v) in writes.iter() {
+			match v {
+				Some(v) => self.db.put(&mut txn, k, v)?,
+				None => {
+					self.db.delete(&mut txn, k)?;
+				}
+			}
+		}
+		txn.commit()?;
+		Ok(())
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Discard the buffered writes
+		self.buffer.drain();
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Check if there is anything to write
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+		// Apply the writes, as long as no key which was read has changed
+		let (reads, writes) = self.buffer.drain();
+		self.apply(reads, writes)?;
+		// Continue
+		Ok(())
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let res = self.fetch(key.into())?.is_some();
+		// Return result
+		Ok(res)
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let res = self.fetch(key.into())?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Set the key
+		self.buffer.set(key.into(), val.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		// Set the key if empty
+		match self.fetch(key.clone())? {
+			None => self.buffer.set(key, val),
+			_ => return Err(Error::TxKeyAlreadyExists),
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		let chk = chk.map(Into::into);
+		// Set the key if valid
+		match (self.fetch(key.clone())?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.set(key, val),
+			(None, None) => self.buffer.set(key, val),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Deletes a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Remove the key
+		self.buffer.del(key.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let chk = chk.map(Into::into);
+		// Delete the key if valid
+		match (self.fetch(key.clone())?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.del(key),
+			(None, None) => self.buffer.del(key),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit)?;
+		// Convert the keys and values
+		let res = res.into_iter().map(|kv| kv.0).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// LMDB does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit)?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// LMDB does not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl Transaction {
+	pub(crate) fn new_save_point(&mut self) {
+		self.buffer.new_save_point();
+	}
+
+	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.rollback_to_save_point();
+		Ok(())
+	}
+
+	pub(crate) fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.release_last_save_point();
+		Ok(())
+	}
+}
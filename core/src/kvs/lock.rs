@@ -0,0 +1,141 @@
+//! Distributed advisory locks built on top of the key-value store.
+//!
+//! A lock is stored as a single key, containing the node which holds the
+//! lock and the time at which the lock expires. Locks are acquired and
+//! released using conditional writes, so that only one node can hold a
+//! lock at any time, even when several processes share a storage engine.
+//! A lock which has expired can be taken over by any other node.
+use crate::dbs::node::Timestamp;
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::{LockType::*, TransactionType::*, Val};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+const TARGET: &str = "surrealdb::core::kvs::lock";
+
+/// The current holder of an advisory lock
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct Lock {
+	/// The node which holds the lock
+	pub owner: Uuid,
+	/// The time at which the lock expires
+	pub expiry: Timestamp,
+}
+
+impl Datastore {
+	/// Acquire or renew a named advisory lock for this node.
+	///
+	/// Returns [`true`] if the lock was acquired, or if it was already held
+	/// by this node, in which case its expiry is extended. Returns [`false`]
+	/// if the lock is currently held by another node, and has not expired.
+	/// Locks are not re-entrant counters: a single call to [`Datastore::unlock`]
+	/// releases the lock, regardless of how many times it was acquired.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::lock", skip(self))]
+	pub async fn lock(&self, name: &str, ttl: Duration) -> Result<bool, Error> {
+		// Log when this method is run
+		trace!(target: TARGET, "Acquiring lock {name}");
+		// Open transaction and fetch the current lock
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		let key = crate::key::root::lk::new(name);
+		let now = self.clock_now().await;
+		let prev = catch!(txn, txn.get(key.clone(), None).await);
+		// Check whether the lock is held by another node
+		if let Some(prev) = prev.clone() {
+			let lock: Lock = prev.into();
+			if lock.owner != self.id() && lock.expiry > now {
+				txn.cancel().await?;
+				return Ok(false);
+			}
+		}
+		// Take the lock, as long as it has not changed
+		let val: Val = Lock {
+			owner: self.id(),
+			expiry: now + ttl,
+		}
+		.into();
+		match run!(txn, txn.putc(key, val, prev).await) {
+			Ok(_) => Ok(true),
+			// Another node changed the lock concurrently
+			Err(Error::TxConditionNotMet) => Ok(false),
+			Err(e) if e.is_retryable() => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Release a named advisory lock held by this node.
+	///
+	/// Returns [`true`] if the lock was held by this node and has been
+	/// released, or [`false`] if the lock was not held by this node.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::lock", skip(self))]
+	pub async fn unlock(&self, name: &str) -> Result<bool, Error> {
+		// Log when this method is run
+		trace!(target: TARGET, "Releasing lock {name}");
+		// Open transaction and fetch the current lock
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		let key = crate::key::root::lk::new(name);
+		let prev = catch!(txn, txn.get(key.clone(), None).await);
+		// Check whether the lock is held by this node
+		let Some(prev) = prev else {
+			txn.cancel().await?;
+			return Ok(false);
+		};
+		let lock: Lock = prev.clone().into();
+		if lock.owner != self.id() {
+			txn.cancel().await?;
+			return Ok(false);
+		}
+		// Release the lock, as long as it has not changed
+		match run!(txn, txn.delc(key, Some(prev)).await) {
+			Ok(_) => Ok(true),
+			// Another node took over the expired lock concurrently
+			Err(Error::TxConditionNotMet) => Ok(false),
+			Err(e) if e.is_retryable() => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn lock_and_unlock() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ttl = Duration::from_secs(60);
+		// The lock can be acquired and renewed
+		assert!(ds.lock("migration", ttl).await.unwrap());
+		assert!(ds.lock("migration", ttl).await.unwrap());
+		// The lock can be released once
+		assert!(ds.unlock("migration").await.unwrap());
+		assert!(!ds.unlock("migration").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn lock_held_by_another_node() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ttl = Duration::from_secs(60);
+		let now = ds.clock_now().await;
+		// Store a lock which is held by another node
+		let other = |expiry| Lock {
+			owner: Uuid::new_v4(),
+			expiry,
+		};
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		txn.set(crate::key::root::lk::new("held"), other(now + ttl), None).await.unwrap();
+		txn.set(crate::key::root::lk::new("expired"), other(now - ttl), None).await.unwrap();
+		txn.commit().await.unwrap();
+		// A lock held by another node can not be acquired or released
+		assert!(!ds.lock("held", ttl).await.unwrap());
+		assert!(!ds.unlock("held").await.unwrap());
+		// An expired lock can be taken over
+		assert!(ds.lock("expired", ttl).await.unwrap());
+		assert!(ds.unlock("expired").await.unwrap());
+	}
+}
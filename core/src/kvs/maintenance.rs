@@ -0,0 +1,273 @@
+//! Periodic background maintenance of expired and superseded data.
+//!
+//! Some data in the key-value store is only logically removed when it
+//! expires or is superseded, and remains in storage until it is swept.
+//! The maintenance task removes expired advisory locks and revocations,
+//! deletes records which have outlived the TTL of their table, prunes
+//! stale entries from the versioned history keyspace, drops the storage
+//! of removed databases, and then runs general garbage collection. Changefeed and changelog entries are
+//! trimmed by the separate changefeed garbage collection task.
+//!
+//! Data is removed in a number of small transactions, each limited to a
+//! configurable number of keys, with a configurable delay between each
+//! transaction, so that maintenance does not compete with foreground
+//! queries for storage engine resources.
+use crate::cnf::{MAINTENANCE_BATCH_DELAY, MAINTENANCE_BATCH_SIZE};
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::kvs::lock::Lock;
+use crate::kvs::Datastore;
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::DeleteStatement;
+use crate::sql::{Datetime, Thing, Value, Values};
+use std::ops::Range;
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::kvs::maintenance";
+
+impl Datastore {
+	/// Run the background task to remove expired and superseded data.
+	///
+	/// This function should be run periodically at an interval.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::maintenance", skip(self))]
+	pub async fn maintenance(&self) -> Result<(), Error> {
+		// Log when this method is run
+		trace!(target: TARGET, "Running background maintenance");
+		// Remove expired advisory locks
+		self.sweep_locks().await?;
+		// Remove revocations which have expired
+		self.sweep_revocations().await?;
+		// Remove records which have expired
+		self.sweep_records().await?;
+		// Remove superseded versions of keys
+		#[cfg(feature = "kv-rocksdb")]
+		self.sweep_versions().await?;
+		// Drop the storage of removed databases
+		self.sweep_partitions()?;
+		// Clean up all other miscellaneous data
+		self.garbage_collect().await?;
+		// Everything ok
+		Ok(())
+	}
+
+	/// Removes all advisory locks which have expired.
+	async fn sweep_locks(&self) -> Result<(), Error> {
+		let now = self.clock_now().await;
+		let beg = crate::key::root::lk::prefix();
+		let end = crate::key::root::lk::suffix();
+		let count = self
+			.sweep(beg..end, |_, v| {
+				let lock: Lock = v.clone().into();
+				lock.expiry <= now
+			})
+			.await?;
+		trace!(target: TARGET, "Removed {count} expired locks");
+		Ok(())
+	}
+
+	/// Removes all token and session revocations which have expired.
+	async fn sweep_revocations(&self) -> Result<(), Error> {
+		let now = Value::from(Datetime::default());
+		// Find the revocation ranges of each level
+		let mut ranges = vec![crate::key::root::rv::prefix()..crate::key::root::rv::suffix()];
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		for ns in catch!(txn, txn.all_ns().await).iter() {
+			let ns = ns.name.as_str();
+			ranges
+				.push(crate::key::namespace::rv::prefix(ns)..crate::key::namespace::rv::suffix(ns));
+			for db in catch!(txn, txn.all_db(ns).await).iter() {
+				let db = db.name.as_str();
+				ranges.push(
+					crate::key::database::rv::prefix(ns, db)
+						..crate::key::database::rv::suffix(ns, db),
+				);
+			}
+		}
+		catch!(txn, txn.cancel().await);
+		// Delete the expired revocations in each range
+		let mut count = 0;
+		for rng in ranges {
+			count += self.sweep(rng, |_, v| Value::from(v) <= now).await?;
+		}
+		trace!(target: TARGET, "Removed {count} expired revocations");
+		Ok(())
+	}
+
+	/// Removes all records which have outlived the TTL of their table.
+	async fn sweep_records(&self) -> Result<(), Error> {
+		let now = Value::from(Datetime::default());
+		// Find all tables which have a TTL
+		let mut tables = Vec::new();
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		for ns in catch!(txn, txn.all_ns().await).iter() {
+			for db in catch!(txn, txn.all_db(&ns.name).await).iter() {
+				for tb in catch!(txn, txn.all_tb(&ns.name, &db.name, None).await).iter() {
+					if tb.ttl.is_some() {
+						tables.push((ns.name.to_raw(), db.name.to_raw(), tb.name.to_raw()));
+					}
+				}
+			}
+		}
+		catch!(txn, txn.cancel().await);
+		// Delete the expired records in each table
+		let mut count = 0;
+		for (ns, db, tb) in tables {
+			let sess = Session::owner().with_ns(&ns).with_db(&db);
+			let mut next = Some(
+				crate::key::ttl::prefix(&ns, &db, &tb)..crate::key::ttl::suffix(&ns, &db, &tb),
+			);
+			while let Some(rng) = next {
+				// Find the expired records in this batch
+				let txn = self.internal_transaction(Read, Optimistic).await?;
+				let res = catch!(txn, txn.batch(rng, *MAINTENANCE_BATCH_SIZE, true, None).await);
+				catch!(txn, txn.cancel().await);
+				next = res.next;
+				let mut what = Vec::new();
+				for (k, v) in res.values.iter() {
+					if Value::from(v) <= now {
+						let key = crate::key::ttl::Ttl::decode(k)?;
+						what.push(Value::from(Thing::from((key.tb, key.id))));
+					}
+				}
+				// Delete the expired records, so that any
+				// indexes, events, and references are processed
+				if !what.is_empty() {
+					count += what.len();
+					let stm = DeleteStatement {
+						what: Values(what),
+						..DeleteStatement::default()
+					};
+					for res in self.process(stm.into(), &sess, None).await? {
+						res.result?;
+					}
+				}
+				// Pause before processing the next batch
+				if next.is_some() && *MAINTENANCE_BATCH_DELAY > 0 {
+					let dur = Duration::from_millis(*MAINTENANCE_BATCH_DELAY);
+					#[cfg(target_arch = "wasm32")]
+					wasmtimer::tokio::sleep(dur).await;
+					#[cfg(not(target_arch = "wasm32"))]
+					tokio::time::sleep(dur).await;
+				}
+			}
+		}
+		trace!(target: TARGET, "Removed {count} expired records");
+		Ok(())
+	}
+
+	/// Removes all versions which are older than the configured retention period.
+	#[cfg(feature = "kv-rocksdb")]
+	async fn sweep_versions(&self) -> Result<(), Error> {
+		use crate::cnf::VERSIONED_RETENTION;
+		use crate::kvs::versioned;
+		// Check if superseded versions are retained forever
+		if *VERSIONED_RETENTION == 0 {
+			return Ok(());
+		}
+		let retention = VERSIONED_RETENTION.saturating_mul(1_000_000_000);
+		let mut pruner = versioned::Pruner::new(versioned::now().saturating_sub(retention));
+		let count = self.sweep(versioned::all(), |k, v| pruner.stale(k, v)).await?;
+		trace!(target: TARGET, "Removed {count} superseded versions");
+		Ok(())
+	}
+
+	/// Deletes the keys in a range which match a predicate, in throttled batches.
+	async fn sweep<F>(&self, rng: Range<Key>, mut stale: F) -> Result<usize, Error>
+	where
+		F: FnMut(&Key, &Val) -> bool,
+	{
+		let mut count = 0;
+		let mut next = Some(rng);
+		while let Some(rng) = next {
+			// Process each batch in a separate transaction
+			let txn = self.internal_transaction(Write, Optimistic).await?;
+			let res = catch!(txn, txn.batch(rng, *MAINTENANCE_BATCH_SIZE, true, None).await);
+			next = res.next;
+			for (k, v) in res.values.iter() {
+				if stale(k, v) {
+					catch!(txn, txn.del(k.clone()).await);
+					count += 1;
+				}
+			}
+			catch!(txn, txn.commit().await);
+			// Pause before processing the next batch
+			if next.is_some() && *MAINTENANCE_BATCH_DELAY > 0 {
+				let dur = Duration::from_millis(*MAINTENANCE_BATCH_DELAY);
+				#[cfg(target_arch = "wasm32")]
+				wasmtimer::tokio::sleep(dur).await;
+				#[cfg(not(target_arch = "wasm32"))]
+				tokio::time::sleep(dur).await;
+			}
+		}
+		Ok(count)
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn expired_locks_are_removed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ttl = Duration::from_secs(60);
+		let now = ds.clock_now().await;
+		// Store one active and one expired lock
+		let lock = |expiry| Lock {
+			owner: ds.id(),
+			expiry,
+		};
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		txn.set(crate::key::root::lk::new("active"), lock(now + ttl), None).await.unwrap();
+		txn.set(crate::key::root::lk::new("expired"), lock(now - ttl), None).await.unwrap();
+		txn.commit().await.unwrap();
+		// Only the expired lock is removed
+		ds.maintenance().await.unwrap();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(txn.exists(crate::key::root::lk::new("active"), None).await.unwrap());
+		assert!(!txn.exists(crate::key::root::lk::new("expired"), None).await.unwrap());
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn expired_revocations_are_removed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Store one active and one expired revocation
+		let sql = "
+			REVOKE TOKEN 'active' ON DATABASE DURATION 1h;
+			REVOKE TOKEN 'expired' ON DATABASE DURATION 0ns;
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// Only the expired revocation is removed
+		ds.maintenance().await.unwrap();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let key = |id| crate::key::database::rv::new("test", "test", "token", id);
+		assert!(txn.exists(key("active"), None).await.unwrap());
+		assert!(!txn.exists(key("expired"), None).await.unwrap());
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn expired_records_are_removed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Store one active and one expired record
+		let sql = "
+			DEFINE TABLE session TTL 1h ON seen;
+			CREATE session:active SET seen = time::now();
+			CREATE session:expired SET seen = time::now() - 2h;
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// Only the expired record is removed
+		ds.maintenance().await.unwrap();
+		let sql = "SELECT VALUE id FROM session";
+		let res = ds.execute(sql, &ses, None).await.unwrap().remove(0).result.unwrap();
+		assert_eq!(res.to_string(), "[session:active]");
+	}
+}
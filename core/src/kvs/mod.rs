@@ -14,45 +14,88 @@
 
 mod api;
 mod batch;
+mod blob;
+mod builder;
 mod cache;
 mod cf;
+mod changelog;
 mod clock;
+mod count;
 mod ds;
 #[doc(hidden)]
 pub mod export;
+mod hooks;
 mod live;
+mod lock;
+mod maintenance;
 mod node;
+mod queue;
 mod scanner;
+mod sink;
 mod stash;
 mod tr;
 mod tx;
+mod usage;
+mod verify;
 mod version;
+mod webhook;
 
+mod dynamodb;
+mod external;
 mod fdb;
 mod indxdb;
 mod kv;
+mod lmdb;
 mod mem;
+mod opfs;
+mod postgres;
+mod redis;
 mod rocksdb;
+mod s3;
+mod shard;
+mod sled;
+mod sqlite;
 mod surrealcs;
 mod surrealkv;
+mod tiered;
 mod tikv;
 
-#[cfg(not(target_arch = "wasm32"))]
-mod index;
 #[cfg(any(
-	feature = "kv-tikv",
-	feature = "kv-fdb",
-	feature = "kv-indxdb",
-	feature = "kv-surrealcs",
+	feature = "kv-sled",
+	feature = "kv-lmdb",
+	feature = "kv-sqlite",
+	feature = "kv-s3",
+	feature = "kv-dynamodb",
+	feature = "kv-redis",
+	feature = "kv-tiered",
+	feature = "kv-opfs"
 ))]
+mod buffer;
+#[cfg(not(target_arch = "wasm32"))]
+mod index;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-dynamodb", feature = "kv-shard"))]
+#[allow(dead_code)] // not all functions are used by every storage engine.
+mod partition;
 mod savepoint;
 #[cfg(test)]
 mod tests;
+#[cfg(all(feature = "kv-testsuite", not(test)))]
+pub mod testsuite;
+#[cfg(feature = "kv-rocksdb")]
+mod versioned;
 
+pub use self::builder::{DatastoreBuilder, Engine};
+pub use self::changelog::Mutation;
+pub use self::count::Count;
 pub use self::ds::*;
+pub use self::external::{KvStore, KvTransaction};
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) use self::index::*;
 pub use self::kv::*;
 pub use self::live::*;
+pub(crate) use self::queue::QueuedEvent;
 pub use self::tr::*;
 pub use self::tx::*;
+pub use self::usage::Usage;
+#[cfg(feature = "http")]
+pub(crate) use self::webhook::QueuedWebhook;
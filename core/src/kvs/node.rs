@@ -23,7 +23,7 @@ impl Datastore {
 		// Log when this method is run
 		trace!(target: TARGET, "Inserting node in the cluster");
 		// Open transaction and set node data
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		let key = crate::key::root::nd::Nd::new(id);
 		let now = self.clock_now().await;
 		let val = Node::new(id, now, false);
@@ -47,7 +47,7 @@ impl Datastore {
 		// Log when this method is run
 		trace!(target: TARGET, "Updating node in the cluster");
 		// Open transaction and set node data
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		let key = crate::key::root::nd::new(id);
 		let now = self.clock_now().await;
 		let val = Node::new(id, now, false);
@@ -66,7 +66,7 @@ impl Datastore {
 		// Log when this method is run
 		trace!(target: TARGET, "Archiving node in the cluster");
 		// Open transaction and set node data
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		let key = crate::key::root::nd::new(id);
 		let val = txn.get_node(id).await?;
 		let val = val.as_ref().archive();
@@ -86,7 +86,7 @@ impl Datastore {
 		trace!(target: TARGET, "Archiving expired nodes in the cluster");
 		// Fetch all of the inactive nodes
 		let inactive = {
-			let txn = self.transaction(Read, Optimistic).await?;
+			let txn = self.internal_transaction(Read, Optimistic).await?;
 			let nds = catch!(txn, txn.all_nodes().await);
 			let now = self.clock_now().await;
 			catch!(txn, txn.cancel().await);
@@ -104,7 +104,7 @@ impl Datastore {
 		// Check if there are inactive nodes
 		if !inactive.is_empty() {
 			// Open a writeable transaction
-			let txn = self.transaction(Write, Optimistic).await?;
+			let txn = self.internal_transaction(Write, Optimistic).await?;
 			// Archive the inactive nodes
 			for nd in inactive.iter() {
 				// Log the live query scanning
@@ -136,7 +136,7 @@ impl Datastore {
 		trace!(target: TARGET, "Cleaning up archived nodes in the cluster");
 		// Fetch all of the archived nodes
 		let archived = {
-			let txn = self.transaction(Read, Optimistic).await?;
+			let txn = self.internal_transaction(Read, Optimistic).await?;
 			let nds = catch!(txn, txn.all_nodes().await);
 			catch!(txn, txn.cancel().await);
 			// Filter the archived nodes
@@ -145,7 +145,7 @@ impl Datastore {
 		// Loop over the archived nodes
 		for id in archived.iter() {
 			// Open a writeable transaction
-			let txn = self.transaction(Write, Optimistic).await?;
+			let txn = self.internal_transaction(Write, Optimistic).await?;
 			{
 				// Log the live query scanning
 				trace!(target: TARGET, id = %id, "Deleting live queries for node");
@@ -167,6 +167,10 @@ impl Datastore {
 							let tlq = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, nlq.lq);
 							// Delete the table live query
 							catch!(txn, txn.del(tlq).await);
+							// Delete any aggregates of the live query
+							let tla =
+								crate::key::table::la::prefix(&val.ns, &val.db, &val.tb, nlq.lq);
+							catch!(txn, txn.delp(tla).await);
 							// Delete the node live query
 							catch!(txn, txn.del(nlq).await);
 						}
@@ -202,16 +206,18 @@ impl Datastore {
 	pub async fn garbage_collect(&self) -> Result<(), Error> {
 		// Log the node deletion
 		trace!(target: TARGET, "Garbage collecting all miscellaneous data");
+		// Remove unreferenced blob chunks
+		self.blob_cleanup().await?;
 		// Fetch archived nodes
 		let archived = {
-			let txn = self.transaction(Read, Optimistic).await?;
+			let txn = self.internal_transaction(Read, Optimistic).await?;
 			let nds = catch!(txn, txn.all_nodes().await);
 			// Filter the archived nodes
 			nds.iter().filter_map(Node::archived).collect::<Vec<_>>()
 		};
 		// Fetch all namespaces
 		let nss = {
-			let txn = self.transaction(Read, Optimistic).await?;
+			let txn = self.internal_transaction(Read, Optimistic).await?;
 			catch!(txn, txn.all_ns().await)
 		};
 		// Loop over all namespaces
@@ -220,24 +226,28 @@ impl Datastore {
 			trace!(target: TARGET, "Garbage collecting data in namespace {}", ns.name);
 			// Fetch all databases
 			let dbs = {
-				let txn = self.transaction(Read, Optimistic).await?;
+				let txn = self.internal_transaction(Read, Optimistic).await?;
 				catch!(txn, txn.all_db(&ns.name).await)
 			};
 			// Loop over all databases
 			for db in dbs.iter() {
 				// Log the namespace
 				trace!(target: TARGET, "Garbage collecting data in database {}/{}", ns.name, db.name);
+				// Merge the storage usage changes
+				self.usage_compact(&ns.name, &db.name).await?;
 				// Fetch all tables
 				let tbs = {
-					let txn = self.transaction(Read, Optimistic).await?;
+					let txn = self.internal_transaction(Read, Optimistic).await?;
 					catch!(txn, txn.all_tb(&ns.name, &db.name, None).await)
 				};
 				// Loop over all tables
 				for tb in tbs.iter() {
 					// Log the namespace
 					trace!(target: TARGET, "Garbage collecting data in table {}/{}/{}", ns.name, db.name, tb.name);
+					// Merge the row count changes
+					self.count_compact(&ns.name, &db.name, &tb.name).await?;
 					// Iterate over the table live queries
-					let txn = self.transaction(Write, Optimistic).await?;
+					let txn = self.internal_transaction(Write, Optimistic).await?;
 					let beg = crate::key::table::lq::prefix(&ns.name, &db.name, &tb.name);
 					let end = crate::key::table::lq::suffix(&ns.name, &db.name, &tb.name);
 					let mut next = Some(beg..end);
@@ -284,7 +294,7 @@ impl Datastore {
 		// Log the node deletion
 		trace!(target: TARGET, "Deleting live queries for a connection");
 		// Fetch expired nodes
-		let txn = self.transaction(Write, Optimistic).await?;
+		let txn = self.internal_transaction(Write, Optimistic).await?;
 		// Loop over the live query unique ids
 		for id in ids.into_iter() {
 			// Get the key for this node live query
@@ -299,6 +309,9 @@ impl Datastore {
 				let tlq = crate::key::table::lq::new(&lq.ns, &lq.db, &lq.tb, id);
 				// Delete the table live query
 				catch!(txn, txn.del(tlq).await);
+				// Delete any aggregates of the live query
+				let tla = crate::key::table::la::prefix(&lq.ns, &lq.db, &lq.tb, id);
+				catch!(txn, txn.delp(tla).await);
 				// Delete the node live query
 				catch!(txn, txn.del(nlq).await);
 			}
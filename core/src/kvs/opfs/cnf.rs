@@ -0,0 +1,4 @@
+use std::sync::LazyLock;
+
+pub static OPFS_COMPACTION_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_OPFS_COMPACTION_SIZE", u64, 16 * 1024 * 1024);
@@ -0,0 +1,538 @@
+#![cfg(feature = "kv-opfs")]
+
+mod cnf;
+mod store;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::buffer::Buffer;
+use crate::kvs::{Check, Key, Val, Version};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::rc::Rc;
+use store::{File, Store};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+	FileSystemDirectoryHandle, FileSystemFileHandle, FileSystemGetDirectoryOptions,
+	FileSystemGetFileOptions, FileSystemReadWriteOptions, FileSystemSyncAccessHandle,
+	WorkerGlobalScope,
+};
+
+#[non_exhaustive]
+pub struct Datastore {
+	store: Rc<RefCell<Store<SyncFile>>>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The buffered reads and writes of this transaction
+	buffer: Buffer,
+	/// The underlying datastore keyspace
+	store: Rc<RefCell<Store<SyncFile>>>,
+}
+
+/// A file in the origin private file system, opened for synchronous access
+struct SyncFile(FileSystemSyncAccessHandle);
+
+impl Drop for SyncFile {
+	fn drop(&mut self) {
+		// Release the exclusive lock on the file
+		self.0.close();
+	}
+}
+
+impl File for SyncFile {
+	fn size(&self) -> Result<u64, Error> {
+		Ok(self.0.get_size().map_err(js)? as u64)
+	}
+	fn read(&self, buf: &mut [u8], at: u64) -> Result<(), Error> {
+		let opts = FileSystemReadWriteOptions::new();
+		opts.set_at(at as f64);
+		match self.0.read_with_u8_array_and_options(buf, &opts).map_err(js)? as usize {
+			n if n == buf.len() => Ok(()),
+			_ => Err(Error::Ds("Unable to read the complete OPFS file".to_owned())),
+		}
+	}
+	fn write(&self, buf: &[u8], at: u64) -> Result<(), Error> {
+		let opts = FileSystemReadWriteOptions::new();
+		opts.set_at(at as f64);
+		match self.0.write_with_u8_array_and_options(buf, &opts).map_err(js)? as usize {
+			n if n == buf.len() => Ok(()),
+			_ => Err(Error::Ds("Unable to write the complete OPFS file".to_owned())),
+		}
+	}
+	fn truncate(&self, size: u64) -> Result<(), Error> {
+		self.0.truncate_with_f64(size as f64).map_err(js)
+	}
+	fn flush(&self) -> Result<(), Error> {
+		self.0.flush().map_err(js)
+	}
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Synchronous file access is only available within a web worker
+		let scope = js_sys::global().dyn_into::<WorkerGlobalScope>().map_err(|_| {
+			Error::Ds("The `opfs` storage engine can only be used within a web worker".to_owned())
+		})?;
+		// Fetch the root of the origin private file system
+		let storage = scope.navigator().storage();
+		let mut dir: FileSystemDirectoryHandle = resolve(storage.get_directory()).await?;
+		// Create the database directory if missing
+		let opts = FileSystemGetDirectoryOptions::new();
+		opts.set_create(true);
+		for name in path.split('/').filter(|s| !s.is_empty()) {
+			dir = resolve(dir.get_directory_handle_with_options(name, &opts)).await?;
+		}
+		// Open the data files, locking them for this worker
+		let files = [open(&dir, "0.log").await?, open(&dir, "1.log").await?];
+		// Load the keyspace into memory
+		Ok(Datastore {
+			store: Rc::new(RefCell::new(Store::open(files)?)),
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Every commit is already flushed
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			buffer: Buffer::default(),
+			store: self.store.clone(),
+		})
+	}
+}
+
+impl Transaction {
+	/// Fetch a key, checking the buffered writes first
+	fn fetch(&mut self, key: Key) -> Result<Option<Val>, Error> {
+		if let Some(val) = self.buffer.get(&key) {
+			return Ok(val);
+		}
+		let val = self.store.borrow().get(&key);
+		self.buffer.read(key, val.clone());
+		Ok(val)
+	}
+	/// Fetch a range of keys, overlaid with the buffered writes
+	fn range(&mut self, beg: Key, end: Key, limit: u32) -> Result<Vec<(Key, Val)>, Error> {
+		let rng = beg.as_slice()..end.as_slice();
+		// Fetch enough entries to account for buffered deletes
+		let fetch = limit as usize + self.buffer.count(rng.clone());
+		let res = self.store.borrow().range(rng.clone(), fetch);
+		Ok(self.buffer.merge(rng, res, limit as usize))
+	}
+	/// Apply the writes, as long as no key which was read has changed
+	fn apply(
+		&self,
+		reads: HashMap<Key, Option<Val>>,
+		writes: BTreeMap<Key, Option<Val>>,
+	) -> Result<(), Error> {
+		let mut store = self.store.borrow_mut();
+		for (k, v) in reads.iter() {
+			if store.get(k) != *v {
+				return Err(Error::TxRetryable);
+			}
+		}
+		store.write(writes)
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Discard the buffered writes
+		self.buffer.drain();
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Check if there is anything to write
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+		// Apply the writes, as long as no key which was read has changed
+		let (reads, writes) = self.buffer.drain();
+		self.apply(reads, writes)?;
+		// Continue
+		Ok(())
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let res = self.fetch(key.into())?.is_some();
+		// Return result
+		Ok(res)
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let res = self.fetch(key.into())?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Set the key
+		self.buffer.set(key.into(), val.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		// Set the key if empty
+		match self.fetch(key.clone())? {
+			None => self.buffer.set(key, val),
+			_ => return Err(Error::TxKeyAlreadyExists),
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		let chk = chk.map(Into::into);
+		// Set the key if valid
+		match (self.fetch(key.clone())?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.set(key, val),
+			(None, None) => self.buffer.set(key, val),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Deletes a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Remove the key
+		self.buffer.del(key.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let chk = chk.map(Into::into);
+		// Delete the key if valid
+		match (self.fetch(key.clone())?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.del(key),
+			(None, None) => self.buffer.del(key),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit)?;
+		// Convert the keys and values
+		let res = res.into_iter().map(|kv| kv.0).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// OPFS does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit)?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// OPFS does not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl Transaction {
+	pub(crate) fn new_save_point(&mut self) {
+		self.buffer.new_save_point();
+	}
+
+	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.rollback_to_save_point();
+		Ok(())
+	}
+
+	pub(crate) fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.release_last_save_point();
+		Ok(())
+	}
+}
+
+/// Resolve a promise into a value of the expected type
+async fn resolve<T: JsCast>(promise: js_sys::Promise) -> Result<T, Error> {
+	Ok(JsFuture::from(promise).await.map_err(js)?.unchecked_into())
+}
+
+/// Open a file within a directory for synchronous access
+async fn open(dir: &FileSystemDirectoryHandle, name: &str) -> Result<SyncFile, Error> {
+	let opts = FileSystemGetFileOptions::new();
+	opts.set_create(true);
+	let file: FileSystemFileHandle = resolve(dir.get_file_handle_with_options(name, &opts)).await?;
+	// The file can only be opened by one worker at a time
+	match JsFuture::from(file.create_sync_access_handle()).await {
+		Ok(handle) => Ok(SyncFile(handle.unchecked_into())),
+		Err(e) => Err(Error::Ds(format!(
+			"Unable to open the OPFS datastore, which may be in use in another tab or worker: {}",
+			message(&e)
+		))),
+	}
+}
+
+/// Convert a JavaScript exception into an error
+fn js(e: JsValue) -> Error {
+	Error::Ds(message(&e))
+}
+
+/// Fetch the message from a JavaScript exception
+fn message(e: &JsValue) -> String {
+	match e.dyn_ref::<js_sys::Error>() {
+		Some(e) => e.message().into(),
+		None => format!("{e:?}"),
+	}
+}
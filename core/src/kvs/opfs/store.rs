@@ -0,0 +1,333 @@
+//! An append-only keyspace stored in a pair of files.
+//!
+//! The whole keyspace is held in memory, and every committed transaction is
+//! appended to the active file as a single checksummed record, before the
+//! file is flushed. When the datastore is opened, the records are replayed
+//! in order, and any incomplete record at the end of the file, from a write
+//! which was interrupted, is discarded.
+//!
+//! Each file begins with a generation number. When the active file grows
+//! larger than the configured size, and is mostly made up of overwritten
+//! or deleted entries, the keyspace is written as a single record to the
+//! inactive file, which is then activated by writing a higher generation
+//! number. The generation number is only written once the keyspace has
+//! been flushed, so an interrupted compaction leaves the previous file in
+//! use when the datastore is next opened.
+use super::cnf;
+use crate::err::Error;
+use crate::kvs::{Key, Val};
+use std::collections::BTreeMap;
+use std::ops::{Bound, Range};
+
+/// The size of the generation number at the start of each file
+const HEADER: u64 = 8;
+
+/// The size of the length and checksum before each record
+const FRAME: usize = 12;
+
+/// A file which supports synchronous reads and writes at any offset
+pub(super) trait File {
+	/// The current size of the file in bytes
+	fn size(&self) -> Result<u64, Error>;
+	/// Read into the buffer from an offset
+	fn read(&self, buf: &mut [u8], at: u64) -> Result<(), Error>;
+	/// Write the buffer at an offset
+	fn write(&self, buf: &[u8], at: u64) -> Result<(), Error>;
+	/// Truncate the file to a size in bytes
+	fn truncate(&self, size: u64) -> Result<(), Error>;
+	/// Persist any written data to storage
+	fn flush(&self) -> Result<(), Error>;
+}
+
+pub(super) struct Store<F: File> {
+	/// The latest value of each key
+	data: BTreeMap<Key, Val>,
+	/// The pair of files storing the keyspace
+	files: [F; 2],
+	/// The index of the file which is appended to
+	active: usize,
+	/// The generation number of the active file
+	generation: u64,
+	/// The end of the last record in the active file
+	end: u64,
+	/// The approximate size of the keyspace in bytes
+	live: u64,
+}
+
+impl<F: File> Store<F> {
+	/// Load the keyspace from the most recent file
+	pub(super) fn open(files: [F; 2]) -> Result<Self, Error> {
+		// Find the file with the highest generation
+		let generations = [generation(&files[0])?, generation(&files[1])?];
+		let active = match generations[1] > generations[0] {
+			true => 1,
+			false => 0,
+		};
+		let mut store = Self {
+			data: BTreeMap::new(),
+			files,
+			active,
+			generation: generations[active],
+			end: HEADER,
+			live: 0,
+		};
+		// A new datastore has no generation yet
+		if store.generation == 0 {
+			store.generation = 1;
+			store.files[active].truncate(0)?;
+			store.files[active].write(&store.generation.to_be_bytes(), 0)?;
+			store.files[active].flush()?;
+			return Ok(store);
+		}
+		// Replay the records in the active file
+		let size = store.files[active].size()?;
+		let mut buf = vec![0; (size - HEADER) as usize];
+		store.files[active].read(&mut buf, HEADER)?;
+		let mut pos = 0;
+		while let Some((writes, len)) = record(&buf[pos..]) {
+			store.load(writes);
+			pos += len;
+		}
+		store.end = HEADER + pos as u64;
+		// Discard any incomplete record
+		if store.end < size {
+			warn!("Discarding {} bytes from an incomplete write", size - store.end);
+			store.files[active].truncate(store.end)?;
+			store.files[active].flush()?;
+		}
+		Ok(store)
+	}
+	/// Fetch the latest value of a key
+	pub(super) fn get(&self, key: &[u8]) -> Option<Val> {
+		self.data.get(key).cloned()
+	}
+	/// Fetch the latest values of a range of keys
+	pub(super) fn range(&self, rng: Range<&[u8]>, limit: usize) -> Vec<(Key, Val)> {
+		let bounds = (Bound::Included(rng.start), Bound::Excluded(rng.end));
+		let iter = self.data.range::<[u8], _>(bounds);
+		iter.take(limit).map(|(k, v)| (k.clone(), v.clone())).collect()
+	}
+	/// Persist and apply committed writes, compacting the files if needed
+	pub(super) fn write(&mut self, writes: BTreeMap<Key, Option<Val>>) -> Result<(), Error> {
+		// Append the writes as a single record
+		let buf = encode(&writes);
+		self.files[self.active].write(&buf, self.end)?;
+		self.files[self.active].flush()?;
+		self.end += buf.len() as u64;
+		// Apply the writes to the keyspace
+		self.load(writes);
+		// Check if the active file should be compacted
+		if self.end > *cnf::OPFS_COMPACTION_SIZE && self.end > self.live * 2 {
+			self.compact()?;
+		}
+		Ok(())
+	}
+	/// Write the keyspace to the inactive file, and then activate it
+	fn compact(&mut self) -> Result<(), Error> {
+		let next = 1 - self.active;
+		let generation = self.generation + 1;
+		let snapshot = self.data.iter().map(|(k, v)| (k.clone(), Some(v.clone()))).collect();
+		let buf = encode(&snapshot);
+		// Write the keyspace before the generation number
+		self.files[next].truncate(0)?;
+		self.files[next].write(&buf, HEADER)?;
+		self.files[next].flush()?;
+		self.files[next].write(&generation.to_be_bytes(), 0)?;
+		self.files[next].flush()?;
+		// The previous file is no longer needed
+		self.files[self.active].truncate(0)?;
+		self.files[self.active].flush()?;
+		self.active = next;
+		self.generation = generation;
+		self.end = HEADER + buf.len() as u64;
+		Ok(())
+	}
+	/// Apply writes to the in-memory keyspace
+	fn load(&mut self, writes: BTreeMap<Key, Option<Val>>) {
+		for (k, v) in writes {
+			let len = k.len() as u64;
+			let old = match v {
+				Some(v) => {
+					self.live += len + v.len() as u64;
+					self.data.insert(k, v)
+				}
+				None => self.data.remove(&k),
+			};
+			if let Some(old) = old {
+				self.live -= len + old.len() as u64;
+			}
+		}
+	}
+}
+
+/// Read the generation number of a file, which is zero if not yet written
+fn generation<F: File>(file: &F) -> Result<u64, Error> {
+	if file.size()? < HEADER {
+		return Ok(0);
+	}
+	let mut buf = [0; HEADER as usize];
+	file.read(&mut buf, 0)?;
+	Ok(u64::from_be_bytes(buf))
+}
+
+/// Encode a set of writes as a checksummed record
+fn encode(writes: &BTreeMap<Key, Option<Val>>) -> Vec<u8> {
+	let mut body = Vec::new();
+	for (k, v) in writes {
+		body.extend_from_slice(&(k.len() as u32).to_be_bytes());
+		body.extend_from_slice(k);
+		match v {
+			Some(v) => {
+				body.push(1);
+				body.extend_from_slice(&(v.len() as u32).to_be_bytes());
+				body.extend_from_slice(v);
+			}
+			None => body.push(0),
+		}
+	}
+	let mut out = Vec::with_capacity(FRAME + body.len());
+	out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+	out.extend_from_slice(&blake3::hash(&body).as_bytes()[..8]);
+	out.extend_from_slice(&body);
+	out
+}
+
+/// Decode the record at the start of a buffer, along with its encoded length
+fn record(buf: &[u8]) -> Option<(BTreeMap<Key, Option<Val>>, usize)> {
+	// Check the record is complete
+	let (len, rest) = buf.split_first_chunk::<4>()?;
+	let len = u32::from_be_bytes(*len) as usize;
+	let (sum, rest) = rest.split_first_chunk::<8>()?;
+	let mut body = rest.get(..len)?;
+	// Check the record is not corrupted
+	if blake3::hash(body).as_bytes()[..8] != sum[..] {
+		return None;
+	}
+	let mut out = BTreeMap::new();
+	while !body.is_empty() {
+		let k = take(&mut body)?.to_vec();
+		let v = match body.split_first()? {
+			(1, rest) => {
+				body = rest;
+				Some(take(&mut body)?.to_vec())
+			}
+			(0, rest) => {
+				body = rest;
+				None
+			}
+			_ => return None,
+		};
+		out.insert(k, v);
+	}
+	Some((out, FRAME + len))
+}
+
+/// Take a length-prefixed slice from the front of a buffer
+fn take<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+	let (len, rest) = buf.split_first_chunk::<4>()?;
+	let len = u32::from_be_bytes(*len) as usize;
+	let v = rest.get(..len)?;
+	*buf = &rest[len..];
+	Some(v)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	#[derive(Clone, Default)]
+	struct Memory(Rc<RefCell<Vec<u8>>>);
+
+	impl File for Memory {
+		fn size(&self) -> Result<u64, Error> {
+			Ok(self.0.borrow().len() as u64)
+		}
+		fn read(&self, buf: &mut [u8], at: u64) -> Result<(), Error> {
+			let at = at as usize;
+			buf.copy_from_slice(&self.0.borrow()[at..at + buf.len()]);
+			Ok(())
+		}
+		fn write(&self, buf: &[u8], at: u64) -> Result<(), Error> {
+			let mut data = self.0.borrow_mut();
+			let at = at as usize;
+			if data.len() < at + buf.len() {
+				data.resize(at + buf.len(), 0);
+			}
+			data[at..at + buf.len()].copy_from_slice(buf);
+			Ok(())
+		}
+		fn truncate(&self, size: u64) -> Result<(), Error> {
+			self.0.borrow_mut().resize(size as usize, 0);
+			Ok(())
+		}
+		fn flush(&self) -> Result<(), Error> {
+			Ok(())
+		}
+	}
+
+	fn writes(entries: &[(&[u8], Option<&[u8]>)]) -> BTreeMap<Key, Option<Val>> {
+		entries.iter().map(|(k, v)| (k.to_vec(), v.map(<[u8]>::to_vec))).collect()
+	}
+
+	#[test]
+	fn replay_records() {
+		let files = [Memory::default(), Memory::default()];
+		let mut store = Store::open(files.clone()).unwrap();
+		store.write(writes(&[(b"a", Some(b"1")), (b"b", Some(b"2"))])).unwrap();
+		store.write(writes(&[(b"a", None), (b"c", Some(b"3"))])).unwrap();
+		// The records are replayed when reopened
+		let store = Store::open(files).unwrap();
+		assert_eq!(store.get(b"a"), None);
+		let res = store.range(b"a".as_slice()..b"z".as_slice(), 10);
+		assert_eq!(res, vec![(b"b".to_vec(), b"2".to_vec()), (b"c".to_vec(), b"3".to_vec())]);
+	}
+
+	#[test]
+	fn incomplete_record() {
+		let files = [Memory::default(), Memory::default()];
+		let mut store = Store::open(files.clone()).unwrap();
+		store.write(writes(&[(b"a", Some(b"1"))])).unwrap();
+		store.write(writes(&[(b"b", Some(b"2"))])).unwrap();
+		// Interrupt the last write part way through
+		let size = files[0].size().unwrap();
+		files[0].truncate(size - 1).unwrap();
+		let store = Store::open(files.clone()).unwrap();
+		assert_eq!(store.get(b"a"), Some(b"1".to_vec()));
+		assert_eq!(store.get(b"b"), None);
+		assert_eq!(files[0].size().unwrap(), store.end);
+	}
+
+	#[test]
+	fn compaction() {
+		let files = [Memory::default(), Memory::default()];
+		let mut store = Store::open(files.clone()).unwrap();
+		// Repeatedly overwrite the same key
+		let val = vec![0; 1024 * 1024];
+		let count = *cnf::OPFS_COMPACTION_SIZE / val.len() as u64 + 2;
+		for i in 0..count {
+			let key = i.to_be_bytes();
+			store.write(writes(&[(b"key", Some(val.as_slice())), (&key, None)])).unwrap();
+		}
+		// The keyspace has moved to the other file
+		assert_eq!(store.active, 1);
+		assert_eq!(files[0].size().unwrap(), 0);
+		// The compacted file is loaded when reopened
+		let store = Store::open(files).unwrap();
+		assert_eq!(store.generation, 2);
+		assert_eq!(store.get(b"key"), Some(val));
+	}
+
+	#[test]
+	fn interrupted_compaction() {
+		let files = [Memory::default(), Memory::default()];
+		let mut store = Store::open(files.clone()).unwrap();
+		store.write(writes(&[(b"a", Some(b"1"))])).unwrap();
+		// A compacted file without a generation number is ignored
+		files[1].write(&encode(&writes(&[(b"a", Some(b"2"))])), HEADER).unwrap();
+		let store = Store::open(files).unwrap();
+		assert_eq!(store.active, 0);
+		assert_eq!(store.get(b"a"), Some(b"1".to_vec()));
+	}
+}
@@ -0,0 +1,100 @@
+//! Logical partitioning of the keyspace by namespace and database.
+//!
+//! Every key which belongs to a database begins with the same prefix,
+//! which is made up of the namespace and database names:
+//!
+//! ```text
+//! /*{ns}\x00*{db}\x00
+//! ```
+//!
+//! Storage engines which are able to store data in separate physical
+//! partitions (such as column families or tables) can use this prefix to
+//! store each database separately. This allows the storage of a removed
+//! database to be released at once, and allows each partition to be configured and
+//! backed up on its own. Keys which do not belong to a database, such as
+//! node, namespace, and database definitions, are not partitioned.
+use crate::kvs::Key;
+use std::ops::Range;
+
+/// The prefix of the partition name for a database
+const NAME: &str = "db:";
+
+/// Get the partition prefix of a key, if the key belongs to a database
+pub(super) fn prefix(key: &[u8]) -> Option<&[u8]> {
+	// Ensure this is a namespace key
+	let rest = key.strip_prefix(b"/*")?;
+	// Find the end of the namespace name
+	let ns = rest.iter().position(|&b| b == 0x00)?;
+	// Ensure this is a database key
+	let rest = rest[ns + 1..].strip_prefix(b"*")?;
+	// Find the end of the database name
+	let db = rest.iter().position(|&b| b == 0x00)?;
+	// Return the namespace and database prefix
+	Some(&key[..2 + ns + 2 + db + 1])
+}
+
+/// Get the range of keys which is covered by a partition prefix
+pub(super) fn range(prefix: &[u8]) -> Range<Key> {
+	let mut end = prefix.to_vec();
+	end.push(0xff);
+	prefix.to_vec()..end
+}
+
+/// Check if a key range intersects with a partition
+pub(super) fn intersects(rng: &Range<Key>, prefix: &[u8]) -> bool {
+	let part = range(prefix);
+	rng.start < part.end && rng.end > part.start
+}
+
+/// Get the name of the physical partition for a partition prefix
+pub(super) fn name(prefix: &[u8]) -> String {
+	let mut out = String::with_capacity(NAME.len() + prefix.len() * 2);
+	out.push_str(NAME);
+	for b in prefix {
+		out.push_str(&format!("{b:02x}"));
+	}
+	out
+}
+
+/// Get the partition prefix from the name of a physical partition
+pub(super) fn parse(name: &str) -> Option<Key> {
+	let hex = name.strip_prefix(NAME)?;
+	if hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len()).step_by(2).map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn database_prefix() {
+		let key = b"/*test\x00*test\x00*person\x00*\x00\x00\x00\x01".to_vec();
+		assert_eq!(prefix(&key), Some(&b"/*test\x00*test\x00"[..]));
+		// The database definition is not partitioned
+		assert_eq!(prefix(b"/*test\x00!dbtest\x00"), None);
+		// The namespace definition is not partitioned
+		assert_eq!(prefix(b"/!nstest\x00"), None);
+		// Versioned history keys are not partitioned
+		assert_eq!(prefix(b"\xff!vk/*test\x00*test\x00"), None);
+	}
+
+	#[test]
+	fn partition_ranges() {
+		let part = b"/*test\x00*test\x00";
+		assert!(intersects(&range(part), part));
+		assert!(intersects(&range(b"/*test\x00"), part));
+		assert!(intersects(&range(b"/*test\x00*test\x00*person\x00"), part));
+		assert!(!intersects(&range(b"/*test\x00*other\x00"), part));
+	}
+
+	#[test]
+	fn name_parse() {
+		let part = b"/*test\x00*test\x00";
+		assert_eq!(parse(&name(part)), Some(part.to_vec()));
+		assert_eq!(parse("default"), None);
+		assert_eq!(parse("db:0"), None);
+	}
+}
@@ -0,0 +1,7 @@
+use std::sync::LazyLock;
+
+pub static POSTGRES_POOL_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_POSTGRES_POOL_SIZE", usize, 16);
+
+pub static POSTGRES_TABLE: LazyLock<String> =
+	lazy_env_parse!("SURREAL_POSTGRES_TABLE", String, "surrealdb".to_string());
@@ -0,0 +1,585 @@
+#![cfg(feature = "kv-postgres")]
+
+mod cnf;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::savepoint::{SaveOperation, SavePointImpl, SavePoints, SavePrepare};
+use crate::kvs::{Check, Key, Val, Version};
+use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, RecyclingMethod};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+use tokio_postgres::NoTls;
+
+const TARGET: &str = "surrealdb::core::kvs::postgres";
+
+/// The SQL statements used to access the keyspace table
+struct Queries {
+	get: String,
+	get_for_update: String,
+	set: String,
+	del: String,
+	scan: String,
+	keys: String,
+}
+
+impl Queries {
+	fn new(table: &str) -> Self {
+		// Quote the table name as an identifier
+		let t = format!("\"{}\"", table.replace('"', "\"\""));
+		Self {
+			get: format!("SELECT val FROM {t} WHERE key = $1"),
+			get_for_update: format!("SELECT val FROM {t} WHERE key = $1 FOR UPDATE"),
+			set: format!(
+				"INSERT INTO {t} (key, val) VALUES ($1, $2) ON CONFLICT (key) DO UPDATE SET val = EXCLUDED.val"
+			),
+			del: format!("DELETE FROM {t} WHERE key = $1"),
+			scan: format!(
+				"SELECT key, val FROM {t} WHERE key >= $1 AND key < $2 ORDER BY key LIMIT $3"
+			),
+			keys: format!("SELECT key FROM {t} WHERE key >= $1 AND key < $2 ORDER BY key LIMIT $3"),
+		}
+	}
+}
+
+#[non_exhaustive]
+pub struct Datastore {
+	pool: Pool,
+	sql: Arc<Queries>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The pooled connection running this transaction
+	inner: Option<Object>,
+	/// The SQL statements used to access the keyspace
+	sql: Arc<Queries>,
+	/// The save point implementation
+	save_points: SavePoints,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done {
+			// Detach the connection from the pool, so that it
+			// is closed, and the open transaction rolled back.
+			if let Some(conn) = self.inner.take() {
+				drop(Object::take(conn));
+			}
+		}
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Parse the connection string
+		let config: tokio_postgres::Config =
+			format!("postgres://{path}").parse().map_err(|e| Error::Ds(format!("{e}")))?;
+		// Configure the connection pool
+		let manager = Manager::from_config(
+			config,
+			NoTls,
+			ManagerConfig {
+				recycling_method: RecyclingMethod::Fast,
+			},
+		);
+		debug!(target: TARGET, "Connection pool size: {}", *cnf::POSTGRES_POOL_SIZE);
+		let pool = Pool::builder(manager)
+			.max_size(*cnf::POSTGRES_POOL_SIZE)
+			.build()
+			.map_err(|e| Error::Ds(e.to_string()))?;
+		// Create the keyspace table if missing
+		debug!(target: TARGET, "Keyspace table: {}", *cnf::POSTGRES_TABLE);
+		let table = format!("\"{}\"", cnf::POSTGRES_TABLE.replace('"', "\"\""));
+		let conn = pool.get().await.map_err(|e| Error::Ds(e.to_string()))?;
+		conn.batch_execute(&format!(
+			"CREATE TABLE IF NOT EXISTS {table} (key BYTEA PRIMARY KEY, val BYTEA NOT NULL)"
+		))
+		.await
+		.map_err(|e| Error::Ds(e.to_string()))?;
+		// Create the datastore
+		Ok(Datastore {
+			pool,
+			sql: Arc::new(Queries::new(&cnf::POSTGRES_TABLE)),
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Close all pooled connections
+		self.pool.close();
+		// Everything ok
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Fetch a connection from the pool
+		let inner = self.pool.get().await.map_err(|e| Error::Tx(e.to_string()))?;
+		// Start a snapshot transaction on the connection
+		inner
+			.batch_execute(match write {
+				true => "BEGIN ISOLATION LEVEL REPEATABLE READ READ WRITE",
+				false => "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY",
+			})
+			.await?;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			inner: Some(inner),
+			sql: self.sql.clone(),
+			save_points: Default::default(),
+		})
+	}
+}
+
+impl Transaction {
+	/// The connection running this transaction
+	fn conn(&self) -> Result<&Object, Error> {
+		self.inner.as_ref().ok_or(Error::TxFinished)
+	}
+	/// Fetch a key, locking the row in writeable transactions
+	async fn fetch(&self, key: &[u8]) -> Result<Option<Val>, Error> {
+		let sql = match self.write {
+			true => &self.sql.get_for_update,
+			false => &self.sql.get,
+		};
+		let row = self.conn()?.query_opt(sql, &[&key]).await?;
+		Ok(row.map(|r| r.get(0)))
+	}
+	/// Write a key, or delete it if there is no value
+	async fn apply(&self, key: &[u8], val: Option<&[u8]>) -> Result<(), Error> {
+		match val {
+			Some(val) => self.conn()?.execute(&self.sql.set, &[&key, &val]).await?,
+			None => self.conn()?.execute(&self.sql.del, &[&key]).await?,
+		};
+		Ok(())
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Cancel this transaction
+		self.conn()?.batch_execute("ROLLBACK").await?;
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Commit this transaction
+		if let Err(err) = self.conn()?.batch_execute("COMMIT").await {
+			if let Err(inner_err) = self.conn()?.batch_execute("ROLLBACK").await {
+				error!("Transaction commit failed {} and rollback failed: {}", err, inner_err);
+			}
+			return Err(err.into());
+		}
+		// Continue
+		Ok(())
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let res = self.fetch(&key.into()).await?.is_some();
+		// Return result
+		Ok(res)
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let res = self.fetch(&key.into()).await?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Prepare the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Set).await?
+		} else {
+			None
+		};
+		// Set the key
+		self.apply(&key, Some(&val.into())).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the key
+		let key = key.into();
+		// Get the val
+		let val = val.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Get the existing value (if any)
+		let key_exists = if let Some(SavePrepare::NewKey(_, sv)) = &prep {
+			sv.get_val().is_some()
+		} else {
+			self.fetch(&key).await?.is_some()
+		};
+		// If the key exists we return an error
+		if key_exists {
+			return Err(Error::TxKeyAlreadyExists);
+		}
+		// Set the key if empty
+		self.apply(&key, Some(&val)).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the key
+		let key = key.into();
+		// Get the val
+		let val = val.into();
+		// Get the check
+		let chk = chk.map(Into::into);
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Get the existing value (if any)
+		let current_val = if let Some(SavePrepare::NewKey(_, sv)) = &prep {
+			sv.get_val().cloned()
+		} else {
+			self.fetch(&key).await?
+		};
+		// Set the key if valid
+		match (current_val, chk) {
+			(Some(v), Some(w)) if v == w => self.apply(&key, Some(&val)).await?,
+			(None, None) => self.apply(&key, Some(&val)).await?,
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Deletes a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Delete the key
+		self.apply(&key, None).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the key
+		let key = key.into();
+		// Get the check
+		let chk = chk.map(Into::into);
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Get the existing value (if any)
+		let current_val = if let Some(SavePrepare::NewKey(_, sv)) = &prep {
+			sv.get_val().cloned()
+		} else {
+			self.fetch(&key).await?
+		};
+		// Delete the key if valid
+		match (current_val, chk) {
+			(Some(v), Some(w)) if v == w => self.apply(&key, None).await?,
+			(None, None) => self.apply(&key, None).await?,
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let beg: Key = rng.start.into();
+		let end: Key = rng.end.into();
+		// Scan the keys
+		let rows = self.conn()?.query(&self.sql.keys, &[&beg, &end, &(limit as i64)]).await?;
+		let res = rows.into_iter().map(|r| r.get(0)).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Postgres does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let beg: Key = rng.start.into();
+		let end: Key = rng.end.into();
+		// Scan the keys
+		let rows = self.conn()?.query(&self.sql.scan, &[&beg, &end, &(limit as i64)]).await?;
+		let res = rows.into_iter().map(|r| (r.get(0), r.get(1))).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Postgres does not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl SavePointImpl for Transaction {
+	fn get_save_points(&mut self) -> &mut SavePoints {
+		&mut self.save_points
+	}
+}
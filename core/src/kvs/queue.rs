@@ -0,0 +1,473 @@
+//! Durable queue of asynchronous table events.
+//!
+//! When a `DEFINE EVENT ... ASYNC` event fires, its WHEN clause is checked
+//! within the triggering transaction, but its THEN clause is not run. The
+//! event is instead written to a queue in the key-value store as part of
+//! the same transaction, so that it is only queued if the write commits.
+//!
+//! The queue is processed periodically by a background task. Each event
+//! is run in its own transaction, which also removes the event from the
+//! queue, so that slow side effects do not extend the write transaction.
+//! An event which fails is retried with an exponential backoff, and once
+//! all attempts are exhausted it is moved to a dead-letter table in the
+//! database of the table on which the event was defined.
+//!
+//! Events for the same record run in the order in which their transactions
+//! committed. Each queued event is given the next position in a per-record
+//! sequence, which is written alongside the record, so that conflicting
+//! writes can not be given the same position. An event only runs once all
+//! of the events before it for the same record have been processed, and an
+//! event whose position has already been processed is discarded, so that
+//! an event is never run twice. Each event is also given a unique id, in
+//! the `$event_id` parameter, which remains the same across retries, and
+//! which can be passed to external systems to deduplicate side effects.
+//!
+//! An event runs with the authentication of the user whose write triggered
+//! it, within the namespace and database of the event, in the same way as
+//! a synchronous event, so that queueing an event does not grant it any
+//! further access.
+use crate::cnf::{
+	EVENT_DEAD_LETTER_TABLE, EVENT_QUEUE_BATCH_SIZE, EVENT_QUEUE_MAX_ATTEMPTS,
+	EVENT_QUEUE_RETRY_DELAY,
+};
+use crate::dbs::Session;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Auth, Role};
+use crate::key::root::qe::Qe;
+use crate::kvs::Datastore;
+use crate::kvs::Key;
+use crate::kvs::Transaction;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::CreateStatement;
+use crate::sql::{Data, Datetime, Object, Subquery, Table, Thing, Uuid, Value, Values};
+use derive::Store;
+use reblessive::TreeStack;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::kvs::queue";
+
+/// The name of the lock which is held while processing the queue
+const LOCK: &str = "event_queue";
+
+/// How long the queue lock is held for, if it is not released
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// An asynchronous table event which is waiting to be processed
+#[revisioned(revision = 3)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct QueuedEvent {
+	/// The namespace in which the event was triggered
+	pub ns: String,
+	/// The database in which the event was triggered
+	pub db: String,
+	/// The table on which the event is defined
+	pub tb: String,
+	/// The name of the event
+	pub ev: String,
+	/// The record which triggered the event
+	pub id: Option<Thing>,
+	/// The type of change which triggered the event
+	pub event: Value,
+	/// The document which is passed to the event
+	pub value: Value,
+	/// The record before the change
+	pub before: Value,
+	/// The record after the change
+	pub after: Value,
+	/// The clauses which are run when the event is processed
+	pub then: Values,
+	/// The time at which the event was queued
+	pub queued: Datetime,
+	/// The number of failed attempts to process the event
+	pub attempts: u32,
+	/// The time after which the event can next be processed
+	pub retry: Datetime,
+	/// The error from the last failed attempt
+	pub error: Option<String>,
+	/// The position of the event in the sequence of events for its record
+	#[revision(start = 2)]
+	pub seq: u64,
+	/// The authentication of the user whose write triggered the event
+	#[revision(start = 3)]
+	pub auth: Option<Auth>,
+}
+
+/// The number of events which have been queued and processed for a record
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct EventOrder {
+	/// The position of the last event which was queued
+	pub queued: u64,
+	/// The position of the last event which was processed
+	pub processed: u64,
+}
+
+impl QueuedEvent {
+	/// Add this event to the queue, within the transaction which triggered it
+	pub(crate) async fn enqueue(mut self, txn: &Transaction) -> Result<(), Error> {
+		// Take the next position in the sequence of events for this record
+		if let Some(key) = self.order_key().map(Key::from) {
+			let mut order: EventOrder =
+				txn.get(key.clone(), None).await?.map(Into::into).unwrap_or_default();
+			order.queued += 1;
+			self.seq = order.queued;
+			txn.set(key, order, None).await?;
+		}
+		// Store the event in the queue
+		let key = crate::key::root::qe::new(uuid::Uuid::now_v7());
+		txn.set(key, self, None).await
+	}
+
+	/// The authentication with which the event is run
+	///
+	/// Events which were queued without an authentication run as the owner of their database.
+	fn auth(&self) -> Auth {
+		match &self.auth {
+			Some(auth) => auth.clone(),
+			None => Auth::for_db(Role::Owner, &self.ns, &self.db),
+		}
+	}
+
+	/// The key which tracks the order of events for the record
+	fn order_key(&self) -> Option<crate::key::table::eo::Eo<'_>> {
+		self.id.as_ref().map(|id| crate::key::table::eo::new(&self.ns, &self.db, &id.tb, &id.id))
+	}
+}
+
+impl Datastore {
+	/// Process any asynchronous table events which are waiting in the queue.
+	///
+	/// This function should be run periodically at an interval.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::queue", skip(self))]
+	pub async fn process_event_queue(&self) -> Result<(), Error> {
+		// Only one node processes the queue at a time
+		if !self.lock(LOCK, LOCK_TTL).await? {
+			return Ok(());
+		}
+		// Process the next batch of events
+		let res = self.process_events().await;
+		// Release the lock for other nodes
+		self.unlock(LOCK).await?;
+		res
+	}
+
+	/// Processes the next batch of queued events, oldest first.
+	async fn process_events(&self) -> Result<(), Error> {
+		let now = Datetime::default();
+		// Fetch the next batch of queued events
+		let beg = crate::key::root::qe::prefix();
+		let end = crate::key::root::qe::suffix();
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		let res = catch!(txn, txn.batch(beg..end, *EVENT_QUEUE_BATCH_SIZE, true, None).await);
+		catch!(txn, txn.cancel().await);
+		// Process each event in turn
+		let mut count = 0;
+		for (key, val) in res.values {
+			let mut entry: QueuedEvent = val.into();
+			// Skip events which are waiting to be retried
+			if entry.retry > now {
+				continue;
+			}
+			// Run the event, and remove it from the queue
+			let err = match self.run_queued(&key, &entry, entry.auth(), &entry.then).await {
+				Ok(true) => {
+					count += 1;
+					continue;
+				}
+				// An earlier event for this record has not yet been processed
+				Ok(false) => continue,
+				// The event conflicted with another transaction, so try again later
+				Err(e) if e.is_retryable() => continue,
+				Err(e) => e,
+			};
+			entry.attempts += 1;
+			entry.error = Some(err.to_string());
+			warn!(
+				target: TARGET,
+				"Failed to process event {} on table {} (attempt {}): {err}",
+				entry.ev,
+				entry.tb,
+				entry.attempts
+			);
+			// Move the event to the dead-letter table
+			if entry.attempts >= *EVENT_QUEUE_MAX_ATTEMPTS {
+				let then = Values(vec![dead_letter(&entry)]);
+				let auth = Auth::for_db(Role::Owner, &entry.ns, &entry.db);
+				self.run_queued(&key, &entry, auth, &then).await?;
+				continue;
+			}
+			// Otherwise schedule the event to be retried
+			let delay = EVENT_QUEUE_RETRY_DELAY.saturating_mul(1 << (entry.attempts - 1).min(16));
+			entry.retry = Datetime(now.0 + chrono::Duration::milliseconds(delay as i64));
+			let txn = self.internal_transaction(Write, Optimistic).await?;
+			catch!(txn, txn.set(key, entry, None).await);
+			catch!(txn, txn.commit().await);
+		}
+		trace!(target: TARGET, "Processed {count} queued events");
+		Ok(())
+	}
+
+	/// Runs some clauses for a queued event, removing the event from the queue in the same transaction.
+	///
+	/// Returns [`false`] if the event can not run yet, because an earlier event for the same record
+	/// has not yet been processed. An event which has already been processed is removed without
+	/// being run again.
+	async fn run_queued(
+		&self,
+		key: &Key,
+		entry: &QueuedEvent,
+		auth: Auth,
+		then: &Values,
+	) -> Result<bool, Error> {
+		// Run the event in its database, without permissions
+		let mut sess = Session::default().with_ns(&entry.ns).with_db(&entry.db);
+		sess.au = Arc::new(auth);
+		let opt = self.setup_options(&sess).new_with_perms(false);
+		// Configure the context
+		let mut ctx = self.setup_ctx()?;
+		sess.context(&mut ctx);
+		ctx.add_value("event", entry.event.clone().into());
+		ctx.add_value("event_id", Value::from(Uuid::from(Qe::decode(key)?.qe)).into());
+		ctx.add_value("value", entry.value.clone().into());
+		ctx.add_value("after", entry.after.clone().into());
+		ctx.add_value("before", entry.before.clone().into());
+		// Start a new transaction
+		let txn = self.internal_transaction(Write, Optimistic).await?.enclose();
+		// Check the position of the event for its record
+		let order = match entry.order_key() {
+			// Events queued without a position are not ordered
+			Some(_) if entry.seq == 0 => None,
+			Some(okey) => {
+				let order: EventOrder = catch!(txn, txn.get(okey.clone(), None).await)
+					.map(Into::into)
+					.unwrap_or_default();
+				// This event has already been processed
+				if entry.seq <= order.processed {
+					catch!(txn, txn.del(key.clone()).await);
+					txn.commit().await?;
+					return Ok(true);
+				}
+				// An earlier event has not yet been processed
+				if entry.seq > order.processed + 1 {
+					txn.cancel().await?;
+					return Ok(false);
+				}
+				Some((okey, order))
+			}
+			None => None,
+		};
+		ctx.set_transaction(txn.clone());
+		let ctx = ctx.freeze();
+		// Process the clauses with the triggering document
+		let doc = CursorDoc::new(entry.id.clone().map(Arc::new), None, entry.value.clone());
+		let mut stack = TreeStack::new();
+		let res = stack
+			.enter(|stk| async {
+				for v in then.iter() {
+					v.compute(stk, &ctx, &opt, Some(&doc)).await?;
+				}
+				Ok::<_, Error>(())
+			})
+			.finish()
+			.await;
+		if let Err(e) = res {
+			txn.cancel().await?;
+			return Err(e);
+		}
+		// Record that this event has been processed
+		if let Some((okey, mut order)) = order {
+			order.processed = entry.seq;
+			match order.processed < order.queued {
+				true => catch!(txn, txn.set(okey, order, None).await),
+				false => catch!(txn, txn.del(okey).await),
+			}
+		}
+		// Remove the event from the queue, and commit
+		catch!(txn, txn.del(key.clone()).await);
+		txn.commit().await?;
+		Ok(true)
+	}
+}
+
+/// Creates a record in the dead-letter table for an event which could not be processed
+fn dead_letter(entry: &QueuedEvent) -> Value {
+	let content = Object::from(map! {
+		"action".to_string() => entry.event.clone(),
+		"after".to_string() => entry.after.clone(),
+		"attempts".to_string() => Value::from(entry.attempts),
+		"before".to_string() => entry.before.clone(),
+		"error".to_string() => entry.error.clone().map(Value::from).unwrap_or_default(),
+		"event".to_string() => Value::from(entry.ev.as_str()),
+		"failed".to_string() => Value::from(Datetime::default()),
+		"queued".to_string() => Value::from(entry.queued.clone()),
+		"record".to_string() => entry.id.clone().map(Value::from).unwrap_or_default(),
+		"table".to_string() => Value::from(entry.tb.as_str()),
+	});
+	Value::Subquery(Box::new(Subquery::Create(CreateStatement {
+		what: Values(vec![Value::Table(Table(EVENT_DEAD_LETTER_TABLE.clone()))]),
+		data: Some(Data::ContentExpression(content.into())),
+		..CreateStatement::default()
+	})))
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+
+	async fn query(ds: &Datastore, sql: &str) -> Value {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute(sql, &ses, None).await.unwrap().pop().unwrap().result.unwrap()
+	}
+
+	#[tokio::test]
+	async fn queued_events_run_after_commit() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE EVENT log ON person ASYNC THEN (CREATE log SET person = $after.id)")
+			.await;
+		query(&ds, "CREATE person:tobie").await;
+		// The event is queued, but has not been run
+		assert_eq!(query(&ds, "SELECT VALUE person FROM log").await.to_string(), "[]");
+		// The event is run, and removed from the queue
+		ds.process_event_queue().await.unwrap();
+		assert_eq!(query(&ds, "SELECT VALUE person FROM log").await.to_string(), "[person:tobie]");
+		ds.process_event_queue().await.unwrap();
+		assert_eq!(query(&ds, "SELECT VALUE person FROM log").await.to_string(), "[person:tobie]");
+	}
+
+	#[tokio::test]
+	async fn queued_events_run_with_the_triggering_authentication() {
+		use crate::iam::Level;
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(true);
+		// A database editor can define an event, but not a root user
+		let level = Level::Database("test".into(), "test".into());
+		let ses = Session::for_level(level, Role::Editor).with_ns("test").with_db("test");
+		let sql = "
+			DEFINE EVENT escalate ON person ASYNC THEN {
+				DEFINE USER evil ON ROOT PASSWORD 'evil' ROLES OWNER
+			};
+			CREATE person:tobie;
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// The event fails, as it runs with the authentication of the editor
+		ds.process_event_queue().await.unwrap();
+		let key = crate::key::root::qe::prefix()..crate::key::root::qe::suffix();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = txn.scan(key, 10, None).await.unwrap();
+		assert!(txn.all_root_users().await.unwrap().is_empty());
+		txn.cancel().await.unwrap();
+		assert_eq!(res.len(), 1);
+		let entry: QueuedEvent = res.into_iter().next().unwrap().1.into();
+		assert_eq!(entry.attempts, 1);
+		let error = entry.error.unwrap();
+		assert!(error.contains("Not enough permissions"), "{error}");
+	}
+
+	#[tokio::test]
+	async fn failed_events_are_retried_then_dead_lettered() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE EVENT fail ON person ASYNC THEN { THROW 'unavailable' }").await;
+		query(&ds, "CREATE person:tobie").await;
+		// The first attempt fails, and the event is scheduled to be retried
+		ds.process_event_queue().await.unwrap();
+		let key = crate::key::root::qe::prefix()..crate::key::root::qe::suffix();
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		let res = txn.scan(key, 10, None).await.unwrap();
+		assert_eq!(res.len(), 1);
+		let (key, val) = res.into_iter().next().unwrap();
+		let mut entry: QueuedEvent = val.into();
+		assert_eq!(entry.attempts, 1);
+		assert!(entry.retry > Datetime::default());
+		// Make the final attempt immediately
+		entry.attempts = *EVENT_QUEUE_MAX_ATTEMPTS - 1;
+		entry.retry = Datetime::default();
+		txn.set(key, entry, None).await.unwrap();
+		txn.commit().await.unwrap();
+		ds.process_event_queue().await.unwrap();
+		// The event is moved to the dead-letter table
+		let sql = "SELECT VALUE [event, record, attempts, error] FROM event_dead_letter";
+		assert_eq!(
+			query(&ds, sql).await.to_string(),
+			format!(
+				"[['fail', person:tobie, {}, 'An error occurred: unavailable']]",
+				*EVENT_QUEUE_MAX_ATTEMPTS
+			)
+		);
+		let key = crate::key::root::qe::prefix()..crate::key::root::qe::suffix();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(txn.scan(key, 10, None).await.unwrap().is_empty());
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn events_for_a_record_run_in_commit_order() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE EVENT log ON person ASYNC THEN (
+			UPSERT journal:tobie SET seen = array::append(seen ?? [], $after.n),
+				ids = array::append(ids ?? [], $event_id)
+		)";
+		query(&ds, sql).await;
+		query(&ds, "CREATE person:tobie SET n = 1").await;
+		query(&ds, "UPDATE person:tobie SET n = 2").await;
+		query(&ds, "UPDATE person:tobie SET n = 3").await;
+		// Delay the first event, as if it were waiting to be retried
+		let key = crate::key::root::qe::prefix()..crate::key::root::qe::suffix();
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		let res = txn.scan(key, 10, None).await.unwrap();
+		let seqs: Vec<u64> = res.iter().map(|(_, v)| QueuedEvent::from(v.clone()).seq).collect();
+		assert_eq!(seqs, vec![1, 2, 3]);
+		let (key, val) = res.into_iter().next().unwrap();
+		let mut entry: QueuedEvent = val.into();
+		entry.retry = Datetime(entry.retry.0 + chrono::Duration::hours(1));
+		txn.set(key.clone(), entry.clone(), None).await.unwrap();
+		txn.commit().await.unwrap();
+		// The later events wait for the first event
+		ds.process_event_queue().await.unwrap();
+		assert_eq!(query(&ds, "SELECT VALUE seen FROM journal").await.to_string(), "[]");
+		// Once the first event runs, the later events follow in order
+		entry.retry = Datetime::default();
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		txn.set(key, entry, None).await.unwrap();
+		txn.commit().await.unwrap();
+		ds.process_event_queue().await.unwrap();
+		assert_eq!(query(&ds, "SELECT VALUE seen FROM journal").await.to_string(), "[[1, 2, 3]]");
+		let sql = "SELECT VALUE array::len(array::distinct(ids)) FROM journal";
+		assert_eq!(query(&ds, sql).await.to_string(), "[3]");
+		// The order of the record is no longer tracked
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let key = crate::key::table::eo::new("test", "test", "person", &"tobie".into());
+		assert!(!txn.exists(key, None).await.unwrap());
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn processed_events_are_not_run_again() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE EVENT log ON person ASYNC THEN (CREATE log SET person = $after.id)")
+			.await;
+		query(&ds, "CREATE person:tobie").await;
+		// Mark the event as processed, as if it were being replayed
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		let key = crate::key::table::eo::new("test", "test", "person", &"tobie".into());
+		let order = EventOrder {
+			queued: 1,
+			processed: 1,
+		};
+		txn.set(key, order, None).await.unwrap();
+		txn.commit().await.unwrap();
+		// The event is removed from the queue without being run
+		ds.process_event_queue().await.unwrap();
+		assert_eq!(query(&ds, "SELECT VALUE person FROM log").await.to_string(), "[]");
+		let key = crate::key::root::qe::prefix()..crate::key::root::qe::suffix();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(txn.scan(key, 10, None).await.unwrap().is_empty());
+		txn.cancel().await.unwrap();
+	}
+}
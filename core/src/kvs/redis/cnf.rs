@@ -0,0 +1,4 @@
+use std::sync::LazyLock;
+
+pub static REDIS_PREFIX: LazyLock<String> =
+	lazy_env_parse!("SURREAL_REDIS_PREFIX", String, "surrealdb".to_string());
@@ -0,0 +1,566 @@
+#![cfg(feature = "kv-redis")]
+
+mod cnf;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::buffer::Buffer;
+use crate::kvs::{Check, Key, Val, Version};
+use redis::aio::MultiplexedConnection;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+const TARGET: &str = "surrealdb::core::kvs::redis";
+
+/// The connections to the Redis server
+struct Connections {
+	/// The client used to open new connections
+	client: redis::Client,
+	/// The shared connection used for reads
+	shared: MultiplexedConnection,
+	/// The idle connections used for committing transactions
+	idle: Mutex<Vec<MultiplexedConnection>>,
+	/// The sorted set which indexes all keys
+	index: Vec<u8>,
+	/// The prefix for each stored value
+	prefix: Vec<u8>,
+}
+
+impl Connections {
+	/// The Redis key which stores the value of a key
+	fn value(&self, key: &[u8]) -> Vec<u8> {
+		[self.prefix.as_slice(), key].concat()
+	}
+	/// Take a connection which is not shared with any other transaction.
+	///
+	/// Keys which are watched belong to the connection, so every
+	/// transaction which uses WATCH needs a dedicated connection.
+	async fn dedicated(&self) -> Result<MultiplexedConnection, Error> {
+		let idle = self.idle.lock().map_err(|e| Error::Tx(e.to_string()))?.pop();
+		match idle {
+			Some(con) => Ok(con),
+			None => Ok(self.client.get_multiplexed_async_connection().await?),
+		}
+	}
+	/// Return a dedicated connection once it is no longer in use
+	fn release(&self, con: MultiplexedConnection) {
+		if let Ok(mut idle) = self.idle.lock() {
+			idle.push(con);
+		}
+	}
+}
+
+#[non_exhaustive]
+pub struct Datastore {
+	db: Arc<Connections>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The buffered reads and writes of this transaction
+	buffer: Buffer,
+	/// The underlying Redis connections
+	db: Arc<Connections>,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Connect to the Redis server
+		let client =
+			redis::Client::open(format!("redis://{path}")).map_err(|e| Error::Ds(e.to_string()))?;
+		let shared = client
+			.get_multiplexed_async_connection()
+			.await
+			.map_err(|e| Error::Ds(e.to_string()))?;
+		// Set the prefix for all stored keys
+		debug!(target: TARGET, "Key prefix: {}", *cnf::REDIS_PREFIX);
+		let index = format!("{}:k", *cnf::REDIS_PREFIX).into_bytes();
+		let prefix = format!("{}:v:", *cnf::REDIS_PREFIX).into_bytes();
+		// Create the datastore
+		Ok(Datastore {
+			db: Arc::new(Connections {
+				client,
+				shared,
+				idle: Mutex::new(Vec::new()),
+				index,
+				prefix,
+			}),
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Nothing to do here
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			buffer: Buffer::default(),
+			db: self.db.clone(),
+		})
+	}
+}
+
+impl Transaction {
+	/// Fetch a key, checking the buffered writes first
+	async fn fetch(&mut self, key: Key) -> Result<Option<Val>, Error> {
+		if let Some(val) = self.buffer.get(&key) {
+			return Ok(val);
+		}
+		let mut con = self.db.shared.clone();
+		let val: Option<Val> =
+			redis::cmd("GET").arg(self.db.value(&key)).query_async(&mut con).await?;
+		self.buffer.read(key, val.clone());
+		Ok(val)
+	}
+	/// Fetch a range of keys, overlaid with the buffered writes
+	async fn range(&mut self, beg: Key, end: Key, limit: u32) -> Result<Vec<(Key, Val)>, Error> {
+		let rng = beg.as_slice()..end.as_slice();
+		// Fetch enough entries to account for buffered deletes
+		let fetch = limit as usize + self.buffer.count(rng.clone());
+		let mut con = self.db.shared.clone();
+		// Fetch the keys in order from the index
+		let keys: Vec<Key> = redis::cmd("ZRANGEBYLEX")
+			.arg(&self.db.index)
+			.arg([b"[", beg.as_slice()].concat())
+			.arg([b"(", end.as_slice()].concat())
+			.arg("LIMIT")
+			.arg(0)
+			.arg(fetch)
+			.query_async(&mut con)
+			.await?;
+		// Fetch the values of the keys
+		let mut res = Vec::with_capacity(keys.len());
+		if !keys.is_empty() {
+			let vals: Vec<Option<Val>> = redis::cmd("MGET")
+				.arg(keys.iter().map(|k| self.db.value(k)).collect::<Vec<_>>())
+				.query_async(&mut con)
+				.await?;
+			// Skip any keys deleted since the index was read
+			for (k, v) in keys.into_iter().zip(vals) {
+				if let Some(v) = v {
+					res.push((k, v));
+				}
+			}
+		}
+		Ok(self.buffer.merge(rng, res, limit as usize))
+	}
+	/// Apply the writes, as long as no key which was read has changed
+	async fn apply(
+		&self,
+		reads: HashMap<Key, Option<Val>>,
+		writes: BTreeMap<Key, Option<Val>>,
+	) -> Result<(), Error> {
+		let mut con = self.db.dedicated().await?;
+		let res = self.exec(&mut con, reads, writes).await;
+		// Reuse the connection unless it is in an unknown state
+		if !matches!(res, Err(Error::Tx(_))) {
+			self.db.release(con);
+		}
+		res
+	}
+	/// Watch the keys which were read, and then apply the writes atomically
+	async fn exec(
+		&self,
+		con: &mut MultiplexedConnection,
+		reads: HashMap<Key, Option<Val>>,
+		writes: BTreeMap<Key, Option<Val>>,
+	) -> Result<(), Error> {
+		if !reads.is_empty() {
+			let keys: Vec<Vec<u8>> = reads.keys().map(|k| self.db.value(k)).collect();
+			// Watch the keys for changes by other transactions
+			redis::cmd("WATCH").arg(&keys).query_async::<()>(con).await?;
+			// Check that the keys have not already changed
+			let vals: Vec<Option<Val>> = redis::cmd("MGET").arg(&keys).query_async(con).await?;
+			if reads.values().zip(vals.iter()).any(|(a, b)| a != b) {
+				redis::cmd("UNWATCH").query_async::<()>(con).await?;
+				return Err(Error::TxRetryable);
+			}
+		}
+		// Apply the writes, and update the index
+		let mut pipe = redis::pipe();
+		pipe.atomic();
+		for (k, v) in writes.iter() {
+			match v {
+				Some(v) => {
+					pipe.set(self.db.value(k), v).ignore();
+					pipe.zadd(&self.db.index, k, 0).ignore();
+				}
+				None => {
+					pipe.del(self.db.value(k)).ignore();
+					pipe.zrem(&self.db.index, k).ignore();
+				}
+			}
+		}
+		// The transaction is aborted if a watched key changed
+		let res: Option<()> = pipe.query_async(con).await?;
+		match res {
+			Some(_) => Ok(()),
+			None => Err(Error::TxRetryable),
+		}
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Discard the buffered writes
+		self.buffer.drain();
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Check if there is anything to write
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+		// Apply the writes, as long as no key which was read has changed
+		let (reads, writes) = self.buffer.drain();
+		self.apply(reads, writes).await?;
+		// Continue
+		Ok(())
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let res = self.fetch(key.into()).await?.is_some();
+		// Return result
+		Ok(res)
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let res = self.fetch(key.into()).await?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Set the key
+		self.buffer.set(key.into(), val.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		// Set the key if empty
+		match self.fetch(key.clone()).await? {
+			None => self.buffer.set(key, val),
+			_ => return Err(Error::TxKeyAlreadyExists),
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		let chk = chk.map(Into::into);
+		// Set the key if valid
+		match (self.fetch(key.clone()).await?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.set(key, val),
+			(None, None) => self.buffer.set(key, val),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Deletes a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Remove the key
+		self.buffer.del(key.into());
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Get the arguments
+		let key = key.into();
+		let chk = chk.map(Into::into);
+		// Delete the key if valid
+		match (self.fetch(key.clone()).await?, chk) {
+			(Some(v), Some(w)) if v == w => self.buffer.del(key),
+			(None, None) => self.buffer.del(key),
+			_ => return Err(Error::TxConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit).await?;
+		// Convert the keys and values
+		let res = res.into_iter().map(|kv| kv.0).collect();
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieves a range of key-value pairs from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Redis does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Retrieve the scan range
+		let res = self.range(rng.start.into(), rng.end.into(), limit).await?;
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Redis does not support versioned queries
+		let _ = (rng, limit);
+		Err(Error::UnsupportedVersionedQueries)
+	}
+}
+
+impl Transaction {
+	pub(crate) fn new_save_point(&mut self) {
+		self.buffer.new_save_point();
+	}
+
+	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.rollback_to_save_point();
+		Ok(())
+	}
+
+	pub(crate) fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.buffer.release_last_save_point();
+		Ok(())
+	}
+}
@@ -44,3 +44,12 @@ pub static ROCKSDB_DELETION_FACTORY_DELETE_COUNT: LazyLock<usize> =
 
 pub static ROCKSDB_DELETION_FACTORY_RATIO: LazyLock<f64> =
 	lazy_env_parse!("SURREAL_ROCKSDB_DELETION_FACTORY_RATIO", f64, 0.5);
+
+pub static ROCKSDB_VERSIONED_KEYS: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_ROCKSDB_VERSIONED_KEYS", bool, false);
+
+pub static ROCKSDB_COLUMN_FAMILIES: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_ROCKSDB_COLUMN_FAMILIES", bool, false);
+
+pub static ROCKSDB_SECONDARY_REFRESH_INTERVAL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_ROCKSDB_SECONDARY_REFRESH_INTERVAL", u64, 1000);
@@ -1,25 +1,71 @@
 #![cfg(feature = "kv-rocksdb")]
 
 mod cnf;
+pub(super) mod secondary;
 
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::err::Error;
 use crate::key::debug::Sprintable;
-use crate::kvs::{Check, Key, Val, Version};
+use crate::kvs::{partition, versioned};
+use crate::kvs::{Check, Isolation, Key, Val, Version};
 use rocksdb::{
-	DBCompactionStyle, DBCompressionType, FlushOptions, LogLevel, OptimisticTransactionDB,
-	OptimisticTransactionOptions, Options, ReadOptions, WriteOptions,
+	BlockBasedOptions, BoundColumnFamily, Cache, DBCompactionStyle, DBCompressionType,
+	FlushOptions, LogLevel, MultiThreaded, OptimisticTransactionDB, OptimisticTransactionOptions,
+	Options, ReadOptions, WriteOptions,
 };
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
 use std::ops::Range;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
 const TARGET: &str = "surrealdb::core::kvs::rocksdb";
 
+/// The underlying database, which can create column families while in use
+type Db = OptimisticTransactionDB<MultiThreaded>;
+
 #[derive(Clone)]
 #[non_exhaustive]
 pub struct Datastore {
-	db: Pin<Arc<OptimisticTransactionDB>>,
+	db: Pin<Arc<Db>>,
+	/// Whether versioned history entries are recorded
+	versioned: bool,
+	/// The column families which store each database, if enabled
+	partitions: Option<Partitions>,
+}
+
+/// The databases which are stored in their own column family
+#[derive(Clone)]
+struct Partitions {
+	/// The options used when creating a column family
+	opts: Arc<Options>,
+	/// The partition prefixes which have a column family
+	list: Arc<RwLock<BTreeSet<Key>>>,
+	/// The open transactions, and the partitions they write to
+	tracker: Arc<Mutex<Tracker>>,
+}
+
+/// Tracks which column families can safely be dropped
+#[derive(Default)]
+struct Tracker {
+	/// The sequence number of the next transaction
+	next: u64,
+	/// The sequence numbers of the open transactions
+	open: BTreeSet<u64>,
+	/// The number of open transactions which write to each partition
+	writers: HashMap<Key, usize>,
+	/// The partitions which were found to be empty, with the next sequence number at the time
+	empty: HashMap<Key, u64>,
+}
+
+/// The column family in which a key is stored
+enum Family<'a> {
+	/// The key is stored in the default column family
+	Default,
+	/// The key is stored in a database column family
+	Partition(Arc<BoundColumnFamily<'a>>),
+	/// The key belongs to a database which has no column family
+	Missing,
 }
 
 #[non_exhaustive]
@@ -31,14 +77,24 @@ pub struct Transaction {
 	/// Should we check unhandled transactions?
 	check: Check,
 	/// The underlying datastore transaction
-	inner: Option<rocksdb::Transaction<'static, OptimisticTransactionDB>>,
+	inner: Option<rocksdb::Transaction<'static, Db>>,
 	/// The read options containing the Snapshot
 	ro: ReadOptions,
+	/// Are the keys which are read checked for conflicts on commit?
+	serializable: bool,
+	/// The mutations to record in the versioned history
+	versions: Option<versioned::Writes>,
+	/// The column families which store each database, if enabled
+	partitions: Option<Partitions>,
+	/// The sequence number of this transaction
+	seq: u64,
+	/// The database partitions which this transaction writes to
+	written: BTreeSet<Key>,
 	// The above, supposedly 'static transaction
 	// actually points here, so we need to ensure
 	// the memory is kept alive. This pointer must
 	// be declared last, so that it is dropped last.
-	_db: Pin<Arc<OptimisticTransactionDB>>,
+	_db: Pin<Arc<Db>>,
 }
 
 impl Drop for Datastore {
@@ -56,6 +112,10 @@ impl Drop for Datastore {
 
 impl Drop for Transaction {
 	fn drop(&mut self) {
+		// Allow the column families to be dropped
+		if let Some(partitions) = &self.partitions {
+			partitions.finish(self.seq, &self.written);
+		}
 		if !self.done && self.write {
 			// Check if already panicking
 			if std::thread::panicking() {
@@ -84,11 +144,131 @@ impl Drop for Transaction {
 	}
 }
 
+impl Partitions {
+	/// The partition prefixes which have a column family
+	fn all(&self) -> Vec<Key> {
+		self.list.read().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+	}
+	/// The partition prefixes which intersect with a key range
+	fn intersecting(&self, rng: &Range<Key>) -> BTreeSet<Key> {
+		let list = self.list.read().unwrap_or_else(PoisonError::into_inner);
+		// Check the range is not empty
+		if rng.start >= rng.end {
+			return BTreeSet::new();
+		}
+		// The partition containing the start of the range
+		let first = partition::prefix(&rng.start).filter(|p| list.contains(*p));
+		// The partitions beginning within the range
+		let rest = list.range(rng.clone()).map(Vec::as_slice);
+		first.into_iter().chain(rest).map(<[u8]>::to_vec).collect()
+	}
+	/// Create the column family for a partition, if it does not exist
+	fn create(&self, db: &Db, prefix: &[u8]) -> Result<(), Error> {
+		let mut list = self.list.write().unwrap_or_else(PoisonError::into_inner);
+		if !list.contains(prefix) {
+			db.create_cf(partition::name(prefix), &self.opts)?;
+			list.insert(prefix.to_vec());
+		}
+		Ok(())
+	}
+	/// Drop the column family for a partition, along with all of its data
+	fn remove(&self, db: &Db, prefix: &[u8]) -> Result<(), Error> {
+		let mut list = self.list.write().unwrap_or_else(PoisonError::into_inner);
+		if list.remove(prefix) {
+			db.drop_cf(&partition::name(prefix))?;
+		}
+		Ok(())
+	}
+	/// Register a new transaction, returning its sequence number
+	fn begin(&self) -> u64 {
+		let mut tracker = self.tracker.lock().unwrap_or_else(PoisonError::into_inner);
+		let seq = tracker.next;
+		tracker.next += 1;
+		tracker.open.insert(seq);
+		seq
+	}
+	/// Register a transaction which writes to a partition, creating its column family if needed
+	fn write(&self, db: &Db, prefix: &[u8]) -> Result<(), Error> {
+		let mut tracker = self.tracker.lock().unwrap_or_else(PoisonError::into_inner);
+		self.create(db, prefix)?;
+		tracker.empty.remove(prefix);
+		*tracker.writers.entry(prefix.to_vec()).or_default() += 1;
+		Ok(())
+	}
+	/// Unregister a transaction which has finished
+	fn finish(&self, seq: u64, written: &BTreeSet<Key>) {
+		let mut tracker = self.tracker.lock().unwrap_or_else(PoisonError::into_inner);
+		tracker.open.remove(&seq);
+		for prefix in written {
+			if let Some(count) = tracker.writers.get_mut(prefix) {
+				*count -= 1;
+				if *count == 0 {
+					tracker.writers.remove(prefix);
+				}
+			}
+		}
+	}
+	/// Drop the column families of partitions which no longer contain any data.
+	///
+	/// Removing a database only deletes its keys within the transaction, so a
+	/// column family is dropped later, once it is found to be empty, and once
+	/// every transaction which was open at that point has finished. This
+	/// ensures that no open transaction can still observe, or write to, the
+	/// data in a column family when it is dropped.
+	fn sweep(&self, db: &Db) -> Result<usize, Error> {
+		let mut tracker = self.tracker.lock().unwrap_or_else(PoisonError::into_inner);
+		let oldest = tracker.open.first().copied().unwrap_or(tracker.next);
+		let mut count = 0;
+		for prefix in self.all() {
+			// Partitions which are being written to are kept
+			if tracker.writers.contains_key(&prefix) {
+				tracker.empty.remove(&prefix);
+				continue;
+			}
+			// Check if the column family contains any keys
+			let empty = match db.cf_handle(&partition::name(&prefix)) {
+				Some(cf) => {
+					let mut iter = db.raw_iterator_cf(&cf);
+					iter.seek_to_first();
+					!iter.valid()
+				}
+				None => false,
+			};
+			if !empty {
+				tracker.empty.remove(&prefix);
+				continue;
+			}
+			// Drop the column family once older transactions have finished
+			match tracker.empty.get(&prefix) {
+				Some(seq) if *seq <= oldest => {
+					tracker.empty.remove(&prefix);
+					self.remove(db, &prefix)?;
+					count += 1;
+				}
+				Some(_) => (),
+				None => {
+					let seq = tracker.next;
+					tracker.empty.insert(prefix, seq);
+				}
+			}
+		}
+		Ok(count)
+	}
+}
+
 impl Datastore {
 	/// Open a new database
-	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+	pub(crate) async fn new(path: &str, cache_size: Option<usize>) -> Result<Datastore, Error> {
 		// Configure custom options
 		let mut opts = Options::default();
+		// Set the size of the block cache
+		if let Some(size) = cache_size {
+			debug!(target: TARGET, "Block cache size: {}", size);
+			let cache = Cache::new_lru_cache(size);
+			let mut block = BlockBasedOptions::default();
+			block.set_block_cache(&cache);
+			opts.set_block_based_table_factory(&block);
+		}
 		// Ensure we use fdatasync
 		opts.set_use_fsync(false);
 		// Create database if missing
@@ -165,21 +345,104 @@ impl Datastore {
 				return Err(Error::Ds(format!("Invalid storage engine log level specified: {l}")));
 			}
 		});
+		// Check if versioned history is enabled
+		debug!(target: TARGET, "Enable versioned keys: {}", *cnf::ROCKSDB_VERSIONED_KEYS);
+		// Check if database column families are enabled
+		debug!(target: TARGET, "Enable database column families: {}", *cnf::ROCKSDB_COLUMN_FAMILIES);
+		// Create the datastore
+		Self::open(opts, path, *cnf::ROCKSDB_VERSIONED_KEYS, *cnf::ROCKSDB_COLUMN_FAMILIES)
+	}
+	/// Open the database, along with any existing column families
+	fn open(
+		opts: Options,
+		path: &str,
+		versioned: bool,
+		partitioned: bool,
+	) -> Result<Datastore, Error> {
+		// A new database has no column families yet
+		let names = Db::list_cf(&opts, path).unwrap_or_default();
+		// Find the databases which are stored in column families
+		let list: BTreeSet<Key> = names.iter().filter_map(|n| partition::parse(n)).collect();
+		// Open the database with all column families
+		let db = match names.is_empty() {
+			true => Db::open(&opts, path)?,
+			false => Db::open_cf(&opts, path, &names)?,
+		};
+		// Existing column families must always be used
+		let partitioned = match (partitioned, list.is_empty()) {
+			(false, false) => {
+				warn!(target: TARGET, "Using database column families, as they already exist in the datastore");
+				true
+			}
+			(true, true) => {
+				// Existing databases are stored in the default column family
+				let mut iter = db.raw_iterator();
+				iter.seek_to_first();
+				if iter.valid() {
+					warn!(target: TARGET, "Database column families can only be enabled on a new datastore");
+				}
+				!iter.valid()
+			}
+			(v, _) => v,
+		};
 		// Create the datastore
 		Ok(Datastore {
-			db: Arc::pin(OptimisticTransactionDB::open(&opts, path)?),
+			db: Arc::pin(db),
+			versioned,
+			partitions: partitioned.then(|| Partitions {
+				opts: Arc::new(opts),
+				list: Arc::new(RwLock::new(list)),
+				tracker: Arc::new(Mutex::new(Tracker::default())),
+			}),
 		})
 	}
+	/// The approximate on-disk size of the database in bytes
+	pub(crate) fn size(&self) -> Result<u64, Error> {
+		let mut size = 0;
+		// Fetch the column families for each database
+		let families: Vec<_> = match &self.partitions {
+			Some(p) => {
+				p.all().iter().filter_map(|p| self.db.cf_handle(&partition::name(p))).collect()
+			}
+			None => vec![],
+		};
+		for prop in [
+			"rocksdb.total-sst-files-size",
+			"rocksdb.total-blob-file-size",
+			"rocksdb.cur-size-all-mem-tables",
+		] {
+			size += self.db.property_int_value(prop)?.unwrap_or_default();
+			for cf in families.iter() {
+				size += self.db.property_int_value_cf(cf, prop)?.unwrap_or_default();
+			}
+		}
+		Ok(size)
+	}
 	/// Shutdown the database
 	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
 		// Nothing to do here
 		Ok(())
 	}
+	/// Drop the column families of databases which have been removed
+	pub(crate) fn sweep_partitions(&self) -> Result<(), Error> {
+		if let Some(partitions) = &self.partitions {
+			let count = partitions.sweep(&self.db)?;
+			trace!(target: TARGET, "Dropped {count} database column families");
+		}
+		Ok(())
+	}
 	/// Start a new transaction
-	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+	pub(crate) async fn transaction(
+		&self,
+		write: bool,
+		_: bool,
+		isolation: Isolation,
+	) -> Result<Transaction, Error> {
+		// Read from a consistent snapshot, unless reading the latest data
+		let snapshot = !matches!(isolation, Isolation::Optimistic);
 		// Set the transaction options
 		let mut to = OptimisticTransactionOptions::default();
-		to.set_snapshot(true);
+		to.set_snapshot(snapshot);
 		// Set the write options
 		let mut wo = WriteOptions::default();
 		wo.set_sync(false);
@@ -192,14 +455,15 @@ impl Datastore {
 		// static, but will cause a crash if the
 		// datastore is dropped prematurely.
 		let inner = unsafe {
-			std::mem::transmute::<
-				rocksdb::Transaction<'_, OptimisticTransactionDB>,
-				rocksdb::Transaction<'static, OptimisticTransactionDB>,
-			>(inner)
+			std::mem::transmute::<rocksdb::Transaction<'_, Db>, rocksdb::Transaction<'static, Db>>(
+				inner,
+			)
 		};
 		// Set the read options
 		let mut ro = ReadOptions::default();
-		ro.set_snapshot(&inner.snapshot());
+		if snapshot {
+			ro.set_snapshot(&inner.snapshot());
+		}
 		ro.set_async_io(true);
 		ro.fill_cache(true);
 		// Specify the check level
@@ -214,6 +478,11 @@ impl Datastore {
 			check,
 			inner: Some(inner),
 			ro,
+			serializable: write && matches!(isolation, Isolation::SerializableSnapshot),
+			versions: self.versioned.then(versioned::Writes::default),
+			seq: self.partitions.as_ref().map_or(0, Partitions::begin),
+			partitions: self.partitions.clone(),
+			written: BTreeSet::new(),
 			_db: self.db.clone(),
 		})
 	}
@@ -266,6 +535,13 @@ impl super::api::Transaction for Transaction {
 		}
 		// Mark this transaction as done
 		self.done = true;
+		// Record the versioned history entries
+		if let Some(versions) = self.versions.as_mut() {
+			let inner = self.inner.as_ref().unwrap();
+			for (k, v) in versions.drain(versioned::now()) {
+				inner.put(k, v)?;
+			}
+		}
 		// Commit this transaction
 		match self.inner.take() {
 			Some(inner) => inner.commit()?,
@@ -281,16 +557,16 @@ impl super::api::Transaction for Transaction {
 	where
 		K: Into<Key> + Sprintable + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
-			return Err(Error::UnsupportedVersionedQueries);
-		}
 		// Check to see if transaction is closed
 		if self.done {
 			return Err(Error::TxFinished);
 		}
+		// Check for a versioned query
+		if let Some(ts) = version {
+			return Ok(self.get_at_version(&key.into(), ts)?.is_some());
+		}
 		// Check the key
-		let res = self.inner.as_ref().unwrap().get_opt(key.into(), &self.ro)?.is_some();
+		let res = self.read(key.into())?.is_some();
 		// Return result
 		Ok(res)
 	}
@@ -301,16 +577,16 @@ impl super::api::Transaction for Transaction {
 	where
 		K: Into<Key> + Sprintable + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
-			return Err(Error::UnsupportedVersionedQueries);
-		}
 		// Check to see if transaction is closed
 		if self.done {
 			return Err(Error::TxFinished);
 		}
+		// Check for a versioned query
+		if let Some(ts) = version {
+			return self.get_at_version(&key.into(), ts);
+		}
 		// Get the key
-		let res = self.inner.as_ref().unwrap().get_opt(key.into(), &self.ro)?;
+		let res = self.read(key.into())?;
 		// Return result
 		Ok(res)
 	}
@@ -322,8 +598,8 @@ impl super::api::Transaction for Transaction {
 		K: Into<Key> + Sprintable + Debug,
 		V: Into<Val> + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
+		// RocksDB only supports versioned writes with versioned keys
+		if version.is_some() && self.versions.is_none() {
 			return Err(Error::UnsupportedVersionedQueries);
 		}
 		// Check to see if transaction is closed
@@ -334,8 +610,16 @@ impl super::api::Transaction for Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
+		// Get the arguments
+		let key = key.into();
+		let val = val.into();
+		// Record the versioned history
+		if let Some(versions) = self.versions.as_mut() {
+			versions.set(key.clone(), val.clone(), version);
+		}
 		// Set the key
-		self.inner.as_ref().unwrap().put(key.into(), val.into())?;
+		self.prepare(&key)?;
+		self.write(&key, Some(&val))?;
 		// Return result
 		Ok(())
 	}
@@ -347,8 +631,8 @@ impl super::api::Transaction for Transaction {
 		K: Into<Key> + Sprintable + Debug,
 		V: Into<Val> + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
+		// RocksDB only supports versioned writes with versioned keys
+		if version.is_some() && self.versions.is_none() {
 			return Err(Error::UnsupportedVersionedQueries);
 		}
 		// Check to see if transaction is closed
@@ -359,16 +643,19 @@ impl super::api::Transaction for Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
-		// Get the transaction
-		let inner = self.inner.as_ref().unwrap();
 		// Get the arguments
 		let key = key.into();
 		let val = val.into();
 		// Set the key if empty
-		match inner.get_opt(&key, &self.ro)? {
-			None => inner.put(key, val)?,
+		self.prepare(&key)?;
+		match self.fetch(&key)? {
+			None => self.write(&key, Some(&val))?,
 			_ => return Err(Error::TxKeyAlreadyExists),
 		};
+		// Record the versioned history
+		if let Some(versions) = self.versions.as_mut() {
+			versions.set(key, val, version);
+		}
 		// Return result
 		Ok(())
 	}
@@ -388,18 +675,21 @@ impl super::api::Transaction for Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
-		// Get the transaction
-		let inner = self.inner.as_ref().unwrap();
 		// Get the arguments
 		let key = key.into();
 		let val = val.into();
 		let chk = chk.map(Into::into);
 		// Set the key if valid
-		match (inner.get_opt(&key, &self.ro)?, chk) {
-			(Some(v), Some(w)) if v == w => inner.put(key, val)?,
-			(None, None) => inner.put(key, val)?,
+		self.prepare(&key)?;
+		match (self.fetch(&key)?, chk) {
+			(Some(v), Some(w)) if v == w => self.write(&key, Some(&val))?,
+			(None, None) => self.write(&key, Some(&val))?,
 			_ => return Err(Error::TxConditionNotMet),
 		};
+		// Record the versioned history
+		if let Some(versions) = self.versions.as_mut() {
+			versions.set(key, val, None);
+		}
 		// Return result
 		Ok(())
 	}
@@ -418,8 +708,14 @@ impl super::api::Transaction for Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
+		// Get the arguments
+		let key = key.into();
+		// Record the versioned history
+		if let Some(versions) = self.versions.as_mut() {
+			versions.del(key.clone());
+		}
 		// Remove the key
-		self.inner.as_ref().unwrap().delete(key.into())?;
+		self.write(&key, None)?;
 		// Return result
 		Ok(())
 	}
@@ -439,17 +735,19 @@ impl super::api::Transaction for Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
-		// Get the transaction
-		let inner = self.inner.as_ref().unwrap();
 		// Get the arguments
 		let key = key.into();
 		let chk = chk.map(Into::into);
 		// Delete the key if valid
-		match (inner.get_opt(&key, &self.ro)?, chk) {
-			(Some(v), Some(w)) if v == w => inner.delete(key)?,
-			(None, None) => inner.delete(key)?,
+		match (self.fetch(&key)?, chk) {
+			(Some(v), Some(w)) if v == w => self.write(&key, None)?,
+			(None, None) => self.write(&key, None)?,
 			_ => return Err(Error::TxConditionNotMet),
 		};
+		// Record the versioned history
+		if let Some(versions) = self.versions.as_mut() {
+			versions.del(key);
+		}
 		// Return result
 		Ok(())
 	}
@@ -465,53 +763,24 @@ impl super::api::Transaction for Transaction {
 	where
 		K: Into<Key> + Sprintable + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
-			return Err(Error::UnsupportedVersionedQueries);
-		}
 		// Check to see if transaction is closed
 		if self.done {
 			return Err(Error::TxFinished);
 		}
-		// Get the transaction
-		let inner = self.inner.as_ref().unwrap();
 		// Convert the range to bytes
 		let rng: Range<Key> = Range {
 			start: rng.start.into(),
 			end: rng.end.into(),
 		};
-		// Create result set
-		let mut res = vec![];
-		// Set the key range
-		let beg = rng.start.as_slice();
-		let end = rng.end.as_slice();
-		// Set the ReadOptions with the snapshot
-		let mut ro = ReadOptions::default();
-		ro.set_snapshot(&inner.snapshot());
-		ro.set_iterate_lower_bound(beg);
-		ro.set_iterate_upper_bound(end);
-		ro.set_async_io(true);
-		ro.fill_cache(true);
-		// Create the iterator
-		let mut iter = inner.raw_iterator_opt(ro);
-		// Seek to the start key
-		iter.seek(&rng.start);
-		// Check the scan limit
-		while res.len() < limit as usize {
-			// Check the key and value
-			if let Some(k) = iter.key() {
-				// Check the range validity
-				if k >= beg && k < end {
-					res.push(k.to_vec());
-					iter.next();
-					continue;
-				}
-			}
-			// Exit
-			break;
+		// Check for a versioned query
+		if let Some(ts) = version {
+			let res = self.scan_at_version(rng, limit, ts)?;
+			return Ok(res.into_iter().map(|(k, _)| k).collect());
 		}
+		// Retrieve the keys in the range
+		let res = self.range(&rng, limit, false);
 		// Return result
-		Ok(res)
+		Ok(res.into_iter().map(|(k, _)| k).collect())
 	}
 
 	/// Retrieve a range of keys from the databases
@@ -525,16 +794,76 @@ impl super::api::Transaction for Transaction {
 	where
 		K: Into<Key> + Sprintable + Debug,
 	{
-		// RocksDB does not support versioned queries.
-		if version.is_some() {
-			return Err(Error::UnsupportedVersionedQueries);
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = Range {
+			start: rng.start.into(),
+			end: rng.end.into(),
+		};
+		// Check for a versioned query
+		if let Some(ts) = version {
+			return self.scan_at_version(rng, limit, ts);
 		}
+		// Retrieve the keys and values in the range
+		let res = self.range(&rng, limit, true);
+		// Return result
+		Ok(res)
+	}
+
+	/// Delete a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn delr<K>(&mut self, rng: Range<K>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
 		// Check to see if transaction is closed
 		if self.done {
 			return Err(Error::TxFinished);
 		}
-		// Get the transaction
-		let inner = self.inner.as_ref().unwrap();
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = Range {
+			start: rng.start.into(),
+			end: rng.end.into(),
+		};
+		// Delete the keys in the range, leaving empty
+		// column families to be dropped in the background
+		let mut next = Some(rng);
+		while let Some(rng) = next {
+			let res = self.batch(rng, *NORMAL_FETCH_SIZE, false, None).await?;
+			next = res.next;
+			for (k, _) in res.values.into_iter() {
+				self.del(k).await?;
+			}
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// RocksDB only supports versioned queries with versioned keys
+		if self.versions.is_none() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
 		// Convert the range to bytes
 		let rng: Range<Key> = Range {
 			start: rng.start.into(),
@@ -542,6 +871,156 @@ impl super::api::Transaction for Transaction {
 		};
 		// Create result set
 		let mut res = vec![];
+		// Iterate over the versioned history
+		let mut iter = self.history_iterator(&rng);
+		// Check the scan limit
+		while res.len() < limit as usize {
+			// Check the key and value
+			if let Some((k, v)) = iter.item() {
+				if let Some((key, ts)) = versioned::decode(k) {
+					let deleted = versioned::decode_value(v).is_none();
+					let val = versioned::decode_value(v).unwrap_or_default();
+					res.push((key, val, ts, deleted));
+				}
+				iter.next();
+				continue;
+			}
+			// Exit
+			break;
+		}
+		// Return result
+		Ok(res)
+	}
+}
+
+impl Transaction {
+	/// Get the column family in which a key is stored
+	fn family(&self, key: &[u8]) -> Family<'_> {
+		// Check if databases are stored separately
+		if self.partitions.is_none() {
+			return Family::Default;
+		}
+		// Check if the key belongs to a database
+		let Some(prefix) = partition::prefix(key) else {
+			return Family::Default;
+		};
+		// Fetch the column family for the database
+		match self._db.cf_handle(&partition::name(prefix)) {
+			Some(cf) => Family::Partition(cf),
+			None => Family::Missing,
+		}
+	}
+
+	/// Get the column families which store a range of keys
+	fn families(&self, rng: &Range<Key>) -> Vec<Family<'_>> {
+		// Check if databases are stored separately
+		let Some(partitions) = &self.partitions else {
+			return vec![Family::Default];
+		};
+		// Check if the range is within a single database
+		if let Some(prefix) = partition::prefix(&rng.start) {
+			if rng.end <= partition::range(prefix).end {
+				return vec![self.family(prefix)];
+			}
+		}
+		// Otherwise scan each intersecting column family
+		let mut out = vec![Family::Default];
+		for prefix in partitions.intersecting(rng) {
+			out.push(self.family(&prefix));
+		}
+		out
+	}
+
+	/// Ensure that the column family for a key exists before writing
+	fn prepare(&mut self, key: &[u8]) -> Result<(), Error> {
+		// Check if the key belongs to a separate database
+		let (Some(partitions), Some(prefix)) = (&self.partitions, partition::prefix(key)) else {
+			return Ok(());
+		};
+		// Keep the column family until this transaction finishes. Column
+		// families are created outside of the transaction, so a column family
+		// which remains empty is dropped again in the background.
+		if !self.written.contains(prefix) {
+			partitions.write(&self._db, prefix)?;
+			self.written.insert(prefix.to_vec());
+		}
+		Ok(())
+	}
+
+	/// Fetch the current value of a key, tracking it for conflicts when serializable
+	fn read(&self, key: Key) -> Result<Option<Val>, Error> {
+		let inner = self.inner.as_ref().unwrap();
+		match (self.family(&key), self.serializable) {
+			(Family::Default, true) => Ok(inner.get_for_update_opt(key, true, &self.ro)?),
+			(Family::Default, false) => Ok(inner.get_opt(key, &self.ro)?),
+			(Family::Partition(cf), true) => {
+				Ok(inner.get_for_update_cf_opt(&cf, key, true, &self.ro)?)
+			}
+			(Family::Partition(cf), false) => Ok(inner.get_cf_opt(&cf, key, &self.ro)?),
+			(Family::Missing, _) => Ok(None),
+		}
+	}
+
+	/// Fetch the current value of a key
+	fn fetch(&self, key: &[u8]) -> Result<Option<Val>, Error> {
+		let inner = self.inner.as_ref().unwrap();
+		match self.family(key) {
+			Family::Default => Ok(inner.get_opt(key, &self.ro)?),
+			Family::Partition(cf) => Ok(inner.get_cf_opt(&cf, key, &self.ro)?),
+			Family::Missing => Ok(None),
+		}
+	}
+
+	/// Set the value of a key, or delete the key if no value is specified
+	fn write(&self, key: &[u8], val: Option<&[u8]>) -> Result<(), Error> {
+		let inner = self.inner.as_ref().unwrap();
+		match (self.family(key), val) {
+			(Family::Default, Some(v)) => inner.put(key, v)?,
+			(Family::Default, None) => inner.delete(key)?,
+			(Family::Partition(cf), Some(v)) => inner.put_cf(&cf, key, v)?,
+			(Family::Partition(cf), None) => inner.delete_cf(&cf, key)?,
+			// A database without a column family contains no keys
+			(Family::Missing, None) => (),
+			(Family::Missing, Some(_)) => {
+				return Err(fail!("Unable to write to a database without a column family"))
+			}
+		};
+		Ok(())
+	}
+
+	/// Retrieve a range of keys, and optionally their values, across all column families
+	fn range(&self, rng: &Range<Key>, limit: u32, values: bool) -> Vec<(Key, Val)> {
+		// Fetch the column families which store the range
+		let families = self.families(rng);
+		// Retrieve the keys from each column family
+		let mut res = vec![];
+		for family in families.iter() {
+			match family {
+				Family::Default => res.extend(self.iterate(None, rng, limit, values)),
+				Family::Partition(cf) => res.extend(self.iterate(Some(cf), rng, limit, values)),
+				Family::Missing => (),
+			}
+		}
+		// Merge the keys from separate column families
+		if families.len() > 1 {
+			res.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+			res.truncate(limit as usize);
+		}
+		res
+	}
+
+	/// Retrieve a range of keys, and optionally their values, from a single column family
+	fn iterate(
+		&self,
+		cf: Option<&Arc<BoundColumnFamily<'_>>>,
+		rng: &Range<Key>,
+		limit: u32,
+		values: bool,
+	) -> Vec<(Key, Val)> {
+		// Get the transaction
+		let inner = self.inner.as_ref().unwrap();
+		// Create result set
+		let mut res = vec![];
 		// Set the key range
 		let beg = rng.start.as_slice();
 		let end = rng.end.as_slice();
@@ -553,16 +1032,23 @@ impl super::api::Transaction for Transaction {
 		ro.set_async_io(true);
 		ro.fill_cache(true);
 		// Create the iterator
-		let mut iter = inner.raw_iterator_opt(ro);
+		let mut iter = match cf {
+			Some(cf) => inner.raw_iterator_cf_opt(cf, ro),
+			None => inner.raw_iterator_opt(ro),
+		};
 		// Seek to the start key
-		iter.seek(&rng.start);
+		iter.seek(beg);
 		// Check the scan limit
 		while res.len() < limit as usize {
 			// Check the key and value
 			if let Some((k, v)) = iter.item() {
 				// Check the range validity
 				if k >= beg && k < end {
-					res.push((k.to_vec(), v.to_vec()));
+					let v = match values {
+						true => v.to_vec(),
+						false => vec![],
+					};
+					res.push((k.to_vec(), v));
 					iter.next();
 					continue;
 				}
@@ -571,29 +1057,91 @@ impl super::api::Transaction for Transaction {
 			break;
 		}
 		// Return result
-		Ok(res)
+		res
 	}
 
-	/// Retrieve all the versions from a range of keys from the databases
-	/// This is a no-op for rocksdb database
-	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
-	async fn scan_all_versions<K>(
-		&mut self,
-		rng: Range<K>,
+	/// Create an iterator over the versioned history of a range of keys
+	fn history_iterator(
+		&self,
+		rng: &Range<Key>,
+	) -> rocksdb::DBRawIteratorWithThreadMode<'_, rocksdb::Transaction<'static, Db>> {
+		// Get the transaction
+		let inner = self.inner.as_ref().unwrap();
+		// Convert the range to the history keyspace
+		let rng = versioned::range(rng);
+		// Set the ReadOptions with the snapshot
+		let mut ro = ReadOptions::default();
+		ro.set_snapshot(&inner.snapshot());
+		ro.set_iterate_lower_bound(rng.start.as_slice());
+		ro.set_iterate_upper_bound(rng.end.as_slice());
+		ro.set_async_io(true);
+		ro.fill_cache(true);
+		// Create the iterator
+		let mut iter = inner.raw_iterator_opt(ro);
+		// Seek to the start key
+		iter.seek(&rng.start);
+		iter
+	}
+
+	/// Fetch the value of a key as it was at a specific version
+	fn get_at_version(&self, key: &[u8], ts: Version) -> Result<Option<Val>, Error> {
+		// RocksDB only supports versioned queries with versioned keys
+		if self.versions.is_none() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Get the transaction
+		let inner = self.inner.as_ref().unwrap();
+		// Set the key range for all versions of this key
+		let beg = versioned::encode(key, ts);
+		let mut end = versioned::encode(key, 0);
+		end.push(0x00);
+		// Set the ReadOptions with the snapshot
+		let mut ro = ReadOptions::default();
+		ro.set_snapshot(&inner.snapshot());
+		ro.set_iterate_lower_bound(beg.as_slice());
+		ro.set_iterate_upper_bound(end.as_slice());
+		// Create the iterator
+		let mut iter = inner.raw_iterator_opt(ro);
+		// Seek to the newest version at or before the timestamp
+		iter.seek(&beg);
+		// Return the visible value
+		Ok(iter.value().and_then(versioned::decode_value))
+	}
+
+	/// Retrieve a range of key-value pairs as they were at a specific version
+	fn scan_at_version(
+		&self,
+		rng: Range<Key>,
 		limit: u32,
-	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
-	where
-		K: Into<Key> + Sprintable + Debug,
-	{
-		// Check to see if transaction is closed
-		if self.done {
-			return Err(Error::TxFinished);
+		ts: Version,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		// RocksDB only supports versioned queries with versioned keys
+		if self.versions.is_none() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Create result set
+		let mut res = vec![];
+		// Resolve the visible versions
+		let mut vis = versioned::Visibility::new(ts);
+		// Iterate over the versioned history
+		let mut iter = self.history_iterator(&rng);
+		// Check the scan limit
+		while res.len() < limit as usize {
+			// Check the key and value
+			if let Some((k, v)) = iter.item() {
+				if let Some(kv) = vis.next(k, v) {
+					res.push(kv);
+				}
+				iter.next();
+				continue;
+			}
+			// Exit
+			break;
 		}
-		Err(Error::UnsupportedVersionedQueries)
+		// Return result
+		Ok(res)
 	}
-}
 
-impl Transaction {
 	pub(crate) fn new_save_point(&mut self) {
 		// Get the transaction
 		let inner = self.inner.as_ref().unwrap();
@@ -614,3 +1162,149 @@ impl Transaction {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::api::Transaction as _;
+	use temp_dir::TempDir;
+
+	async fn open(path: &TempDir) -> Datastore {
+		let mut opts = Options::default();
+		opts.create_if_missing(true);
+		Datastore::open(opts, path.path().to_str().unwrap(), false, true).unwrap()
+	}
+
+	#[tokio::test]
+	async fn databases_in_column_families() {
+		let path = TempDir::new().unwrap();
+		{
+			let ds = open(&path).await;
+			// Write keys inside and outside of each database
+			let mut tx =
+				ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+			tx.set(b"/!nstest".to_vec(), b"ns".to_vec(), None).await.unwrap();
+			tx.set(b"/*test\x00*a\x00*one".to_vec(), b"a1".to_vec(), None).await.unwrap();
+			tx.set(b"/*test\x00*a\x00*two".to_vec(), b"a2".to_vec(), None).await.unwrap();
+			tx.set(b"/*test\x00*b\x00*one".to_vec(), b"b1".to_vec(), None).await.unwrap();
+			tx.set(b"/*test\x00!dba".to_vec(), b"def".to_vec(), None).await.unwrap();
+			tx.commit().await.unwrap();
+			drop(tx);
+			assert_eq!(ds.partitions.as_ref().unwrap().all().len(), 2);
+			// Scans across column families are merged in order
+			let mut tx =
+				ds.transaction(false, false, Isolation::SerializableSnapshot).await.unwrap();
+			let res = tx.keys(b"/".to_vec()..b"0".to_vec(), 10, None).await.unwrap();
+			assert_eq!(
+				res,
+				vec![
+					b"/!nstest".to_vec(),
+					b"/*test\x00!dba".to_vec(),
+					b"/*test\x00*a\x00*one".to_vec(),
+					b"/*test\x00*a\x00*two".to_vec(),
+					b"/*test\x00*b\x00*one".to_vec(),
+				]
+			);
+			let res = tx.keys(b"/".to_vec()..b"0".to_vec(), 3, None).await.unwrap();
+			assert_eq!(res.len(), 3);
+			tx.cancel().await.unwrap();
+			drop(tx);
+			// Removing a database drops its column family in the background
+			let mut tx =
+				ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+			tx.delp(b"/*test\x00*a\x00".to_vec()).await.unwrap();
+			assert_eq!(tx.get(b"/*test\x00*a\x00*one".to_vec(), None).await.unwrap(), None);
+			tx.commit().await.unwrap();
+			drop(tx);
+			assert_eq!(ds.partitions.as_ref().unwrap().all().len(), 2);
+			ds.sweep_partitions().unwrap();
+			ds.sweep_partitions().unwrap();
+			assert_eq!(ds.partitions.as_ref().unwrap().all(), vec![b"/*test\x00*b\x00".to_vec()]);
+			// The remaining data is unaffected
+			let mut tx =
+				ds.transaction(false, false, Isolation::SerializableSnapshot).await.unwrap();
+			let res = tx.keys(b"/".to_vec()..b"0".to_vec(), 10, None).await.unwrap();
+			assert_eq!(res.len(), 3);
+			tx.cancel().await.unwrap();
+		}
+		// Existing column families are opened again
+		let ds = open(&path).await;
+		let mut tx = ds.transaction(false, false, Isolation::SerializableSnapshot).await.unwrap();
+		let res = tx.get(b"/*test\x00*b\x00*one".to_vec(), None).await.unwrap();
+		assert_eq!(res, Some(b"b1".to_vec()));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn database_recreated_after_removal() {
+		let path = TempDir::new().unwrap();
+		let ds = open(&path).await;
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*one".to_vec(), b"1".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Remove and recreate the database in one transaction
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.delp(b"/*test\x00*a\x00".to_vec()).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*two".to_vec(), b"2".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Only the data written after the removal remains
+		let mut tx = ds.transaction(false, false, Isolation::SerializableSnapshot).await.unwrap();
+		let res = tx.scan(b"/*test\x00*a\x00".to_vec()..b"/*test\x00*a\x00\xff".to_vec(), 10, None);
+		assert_eq!(res.await.unwrap(), vec![(b"/*test\x00*a\x00*two".to_vec(), b"2".to_vec())]);
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn removed_database_visible_to_older_transactions() {
+		let path = TempDir::new().unwrap();
+		let ds = open(&path).await;
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*one".to_vec(), b"1".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Start a transaction before the database is removed
+		let mut old = ds.transaction(false, false, Isolation::Snapshot).await.unwrap();
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.delp(b"/*test\x00*a\x00".to_vec()).await.unwrap();
+		tx.commit().await.unwrap();
+		drop(tx);
+		// The column family is kept while the older transaction is open
+		ds.sweep_partitions().unwrap();
+		ds.sweep_partitions().unwrap();
+		assert_eq!(ds.partitions.as_ref().unwrap().all().len(), 1);
+		let res = old.get(b"/*test\x00*a\x00*one".to_vec(), None).await.unwrap();
+		assert_eq!(res, Some(b"1".to_vec()));
+		old.cancel().await.unwrap();
+		drop(old);
+		// The column family is dropped once the older transaction has finished
+		ds.sweep_partitions().unwrap();
+		assert!(ds.partitions.as_ref().unwrap().all().is_empty());
+	}
+
+	#[tokio::test]
+	async fn removed_database_keeps_concurrent_writes() {
+		let path = TempDir::new().unwrap();
+		let ds = open(&path).await;
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*one".to_vec(), b"1".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Write to the database while it is being removed
+		let mut writer = ds.transaction(true, false, Isolation::Snapshot).await.unwrap();
+		writer.set(b"/*test\x00*a\x00*two".to_vec(), b"2".to_vec(), None).await.unwrap();
+		let mut tx = ds.transaction(true, false, Isolation::SerializableSnapshot).await.unwrap();
+		tx.delp(b"/*test\x00*a\x00".to_vec()).await.unwrap();
+		tx.commit().await.unwrap();
+		drop(tx);
+		ds.sweep_partitions().unwrap();
+		ds.sweep_partitions().unwrap();
+		writer.commit().await.unwrap();
+		drop(writer);
+		// The concurrent write is not lost
+		ds.sweep_partitions().unwrap();
+		ds.sweep_partitions().unwrap();
+		let mut tx = ds.transaction(false, false, Isolation::Snapshot).await.unwrap();
+		let res = tx.get(b"/*test\x00*a\x00*two".to_vec(), None).await.unwrap();
+		assert_eq!(res, Some(b"2".to_vec()));
+		assert_eq!(tx.get(b"/*test\x00*a\x00*one".to_vec(), None).await.unwrap(), None);
+		tx.cancel().await.unwrap();
+	}
+}
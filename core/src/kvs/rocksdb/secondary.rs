@@ -0,0 +1,612 @@
+//! A read-only secondary instance of a RocksDB datastore.
+//!
+//! A secondary instance attaches to a datastore which is held open by
+//! another process, and reads the files which are written by the primary
+//! instance. This allows reporting or backup processes to read from an
+//! embedded datastore while the primary process continues to write to it.
+//!
+//! The secondary instance only sees the commits which were made when it
+//! last caught up with the primary instance. It catches up automatically
+//! when a transaction starts, at most once every refresh interval, and
+//! can be refreshed explicitly with [`Datastore::refresh`]. Transactions
+//! always read from the state which was current when they started.
+//!
+//! [`Datastore::refresh`]: crate::kvs::Datastore::refresh
+
+use super::{cnf, Family};
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::savepoint::{SavePointImpl, SavePoints};
+use crate::kvs::{partition, versioned};
+use crate::kvs::{Check, Key, Val, Version};
+use rocksdb::{
+	BlockBasedOptions, BoundColumnFamily, Cache, DBRawIteratorWithThreadMode, DBWithThreadMode,
+	MultiThreaded, Options, ReadOptions,
+};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
+
+const TARGET: &str = "surrealdb::core::kvs::rocksdb";
+
+/// The underlying secondary database instance
+type Db = DBWithThreadMode<MultiThreaded>;
+
+/// The secondary instance, along with its open column families
+struct Handle {
+	/// The secondary database instance
+	db: Db,
+	/// The names of the column families which are open
+	names: Vec<String>,
+	/// Whether databases are stored in their own column families
+	partitioned: bool,
+}
+
+#[non_exhaustive]
+pub struct Datastore {
+	/// The path of the primary database
+	primary: String,
+	/// The path where the secondary instance stores its log files
+	secondary: String,
+	/// The options used when opening the secondary instance
+	opts: Options,
+	/// Whether versioned history entries are recorded
+	versioned: bool,
+	/// The secondary instance, locked exclusively when catching up
+	handle: Arc<RwLock<Handle>>,
+	/// When the secondary instance last caught up with the primary
+	refreshed: Mutex<Instant>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Whether versioned history entries are recorded
+	versioned: bool,
+	/// The secondary instance, which can not catch up while in use
+	handle: OwnedRwLockReadGuard<Handle>,
+	/// The save point implementation
+	save_points: SavePoints,
+}
+
+impl Handle {
+	/// Attach to a primary database, with all of its column families
+	fn attach(opts: &Options, primary: &str, secondary: &str) -> Result<Handle, Error> {
+		let names = Db::list_cf(opts, primary)?;
+		let db = Db::open_cf_as_secondary(opts, primary, secondary, &names)?;
+		let partitioned = names.iter().any(|n| partition::parse(n).is_some());
+		Ok(Handle {
+			db,
+			names,
+			partitioned,
+		})
+	}
+}
+
+impl Datastore {
+	/// Attach to a database which is held open by another process
+	pub(crate) async fn new(
+		primary: &str,
+		secondary: &str,
+		cache_size: Option<usize>,
+	) -> Result<Datastore, Error> {
+		// Configure custom options
+		let mut opts = Options::default();
+		// Set the size of the block cache
+		if let Some(size) = cache_size {
+			debug!(target: TARGET, "Block cache size: {}", size);
+			let cache = Cache::new_lru_cache(size);
+			let mut block = BlockBasedOptions::default();
+			block.set_block_cache(&cache);
+			opts.set_block_based_table_factory(&block);
+		}
+		// Secondary instances must keep all files open
+		opts.set_max_open_files(-1);
+		// Attach to the primary database
+		debug!(target: TARGET, "Attaching to the primary database as a secondary instance");
+		let handle = Handle::attach(&opts, primary, secondary)?;
+		// Create the datastore
+		Ok(Datastore {
+			primary: primary.to_owned(),
+			secondary: secondary.to_owned(),
+			opts,
+			versioned: *cnf::ROCKSDB_VERSIONED_KEYS,
+			handle: Arc::new(RwLock::new(handle)),
+			refreshed: Mutex::new(Instant::now()),
+		})
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		// Nothing to do here
+		Ok(())
+	}
+	/// Catch up with the latest commits on the primary database
+	pub(crate) async fn refresh(&self) -> Result<(), Error> {
+		// Wait for any running transactions to finish
+		let mut handle = self.handle.write().await;
+		self.catch_up(&mut handle)
+	}
+	/// Catch up with the primary database, reopening if column families have changed
+	fn catch_up(&self, handle: &mut Handle) -> Result<(), Error> {
+		let names = Db::list_cf(&self.opts, &self.primary)?;
+		match names == handle.names {
+			true => handle.db.try_catch_up_with_primary()?,
+			false => *handle = Handle::attach(&self.opts, &self.primary, &self.secondary)?,
+		}
+		*self.refreshed.lock().unwrap_or_else(PoisonError::into_inner) = Instant::now();
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Transaction, Error> {
+		// A secondary instance can not be written to
+		if write {
+			return Err(Error::TxReadonly);
+		}
+		// Catch up with the primary database if due, unless other transactions are running
+		let interval = Duration::from_millis(*cnf::ROCKSDB_SECONDARY_REFRESH_INTERVAL);
+		let due =
+			self.refreshed.lock().unwrap_or_else(PoisonError::into_inner).elapsed() >= interval;
+		if due {
+			if let Ok(mut handle) = self.handle.try_write() {
+				self.catch_up(&mut handle)?;
+			}
+		}
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			versioned: self.versioned,
+			handle: self.handle.clone().read_owned().await,
+			save_points: Default::default(),
+		})
+	}
+}
+
+impl super::super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, _: Check) {
+		// Read-only transactions can be safely dropped
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		false
+	}
+
+	/// Cancel a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Continue
+		Ok(())
+	}
+
+	/// Commit a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// A secondary instance can not be written to
+		Err(Error::TxReadonly)
+	}
+
+	/// Check if a key exists
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		Ok(self.get(key, version).await?.is_some())
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check for a versioned query
+		if let Some(ts) = version {
+			return self.get_at_version(&key.into(), ts);
+		}
+		// Get the key
+		self.fetch(&key.into())
+	}
+
+	/// Insert or update a key in the database
+	async fn set<K, V>(&mut self, _: K, _: V, _: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		Err(Error::TxReadonly)
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	async fn put<K, V>(&mut self, _: K, _: V, _: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		Err(Error::TxReadonly)
+	}
+
+	/// Insert a key if the current value matches a condition
+	async fn putc<K, V>(&mut self, _: K, _: V, _: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		Err(Error::TxReadonly)
+	}
+
+	/// Delete a key
+	async fn del<K>(&mut self, _: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		Err(Error::TxReadonly)
+	}
+
+	/// Delete a key if the current value matches a condition
+	async fn delc<K, V>(&mut self, _: K, _: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		Err(Error::TxReadonly)
+	}
+
+	/// Retrieve a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		let res = self.scan(rng, limit, version).await?;
+		Ok(res.into_iter().map(|(k, _)| k).collect())
+	}
+
+	/// Retrieve a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = Range {
+			start: rng.start.into(),
+			end: rng.end.into(),
+		};
+		// Check for a versioned query
+		if let Some(ts) = version {
+			return self.scan_at_version(rng, limit, ts);
+		}
+		// Retrieve the keys and values in the range
+		Ok(self.range(&rng, limit))
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// RocksDB only supports versioned queries with versioned keys
+		if !self.versioned {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = Range {
+			start: rng.start.into(),
+			end: rng.end.into(),
+		};
+		// Create result set
+		let mut res = vec![];
+		// Iterate over the versioned history
+		let mut iter = self.history_iterator(&rng);
+		// Check the scan limit
+		while res.len() < limit as usize {
+			// Check the key and value
+			if let Some((k, v)) = iter.item() {
+				if let Some((key, ts)) = versioned::decode(k) {
+					let deleted = versioned::decode_value(v).is_none();
+					let val = versioned::decode_value(v).unwrap_or_default();
+					res.push((key, val, ts, deleted));
+				}
+				iter.next();
+				continue;
+			}
+			// Exit
+			break;
+		}
+		// Return result
+		Ok(res)
+	}
+}
+
+impl Transaction {
+	/// Get the column family in which a key is stored
+	fn family(&self, key: &[u8]) -> Family<'_> {
+		// Check if databases are stored separately
+		if !self.handle.partitioned {
+			return Family::Default;
+		}
+		// Check if the key belongs to a database
+		let Some(prefix) = partition::prefix(key) else {
+			return Family::Default;
+		};
+		// Fetch the column family for the database
+		match self.handle.db.cf_handle(&partition::name(prefix)) {
+			Some(cf) => Family::Partition(cf),
+			None => Family::Missing,
+		}
+	}
+
+	/// Get the column families which store a range of keys
+	fn families(&self, rng: &Range<Key>) -> Vec<Family<'_>> {
+		// Check if databases are stored separately
+		if !self.handle.partitioned {
+			return vec![Family::Default];
+		}
+		// Check if the range is within a single database
+		if let Some(prefix) = partition::prefix(&rng.start) {
+			if rng.end <= partition::range(prefix).end {
+				return vec![self.family(prefix)];
+			}
+		}
+		// Otherwise scan each intersecting column family
+		let mut out = vec![Family::Default];
+		for prefix in self.handle.names.iter().filter_map(|n| partition::parse(n)) {
+			if partition::intersects(rng, &prefix) {
+				out.push(self.family(&prefix));
+			}
+		}
+		out
+	}
+
+	/// Fetch the current value of a key
+	fn fetch(&self, key: &[u8]) -> Result<Option<Val>, Error> {
+		let db = &self.handle.db;
+		match self.family(key) {
+			Family::Default => Ok(db.get(key)?),
+			Family::Partition(cf) => Ok(db.get_cf(&cf, key)?),
+			Family::Missing => Ok(None),
+		}
+	}
+
+	/// Retrieve a range of key-value pairs across all column families
+	fn range(&self, rng: &Range<Key>, limit: u32) -> Vec<(Key, Val)> {
+		// Fetch the column families which store the range
+		let families = self.families(rng);
+		// Retrieve the keys from each column family
+		let mut res = vec![];
+		for family in families.iter() {
+			match family {
+				Family::Default => res.extend(self.iterate(None, rng, limit)),
+				Family::Partition(cf) => res.extend(self.iterate(Some(cf), rng, limit)),
+				Family::Missing => (),
+			}
+		}
+		// Merge the keys from separate column families
+		if families.len() > 1 {
+			res.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+			res.truncate(limit as usize);
+		}
+		res
+	}
+
+	/// Retrieve a range of key-value pairs from a single column family
+	fn iterate(
+		&self,
+		cf: Option<&Arc<BoundColumnFamily<'_>>>,
+		rng: &Range<Key>,
+		limit: u32,
+	) -> Vec<(Key, Val)> {
+		// Create result set
+		let mut res = vec![];
+		// Set the key range
+		let beg = rng.start.as_slice();
+		let end = rng.end.as_slice();
+		// Set the ReadOptions
+		let mut ro = ReadOptions::default();
+		ro.set_iterate_lower_bound(beg);
+		ro.set_iterate_upper_bound(end);
+		ro.set_async_io(true);
+		ro.fill_cache(true);
+		// Create the iterator
+		let mut iter = match cf {
+			Some(cf) => self.handle.db.raw_iterator_cf_opt(cf, ro),
+			None => self.handle.db.raw_iterator_opt(ro),
+		};
+		// Seek to the start key
+		iter.seek(beg);
+		// Check the scan limit
+		while res.len() < limit as usize {
+			// Check the key and value
+			if let Some((k, v)) = iter.item() {
+				// Check the range validity
+				if k >= beg && k < end {
+					res.push((k.to_vec(), v.to_vec()));
+					iter.next();
+					continue;
+				}
+			}
+			// Exit
+			break;
+		}
+		// Return result
+		res
+	}
+
+	/// Create an iterator over the versioned history of a range of keys
+	fn history_iterator(&self, rng: &Range<Key>) -> DBRawIteratorWithThreadMode<'_, Db> {
+		// Convert the range to the history keyspace
+		let rng = versioned::range(rng);
+		// Set the ReadOptions
+		let mut ro = ReadOptions::default();
+		ro.set_iterate_lower_bound(rng.start.as_slice());
+		ro.set_iterate_upper_bound(rng.end.as_slice());
+		ro.set_async_io(true);
+		ro.fill_cache(true);
+		// Create the iterator
+		let mut iter = self.handle.db.raw_iterator_opt(ro);
+		// Seek to the start key
+		iter.seek(&rng.start);
+		iter
+	}
+
+	/// Fetch the value of a key as it was at a specific version
+	fn get_at_version(&self, key: &[u8], ts: Version) -> Result<Option<Val>, Error> {
+		// RocksDB only supports versioned queries with versioned keys
+		if !self.versioned {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Set the key range for all versions of this key
+		let beg = versioned::encode(key, ts);
+		let mut end = versioned::encode(key, 0);
+		end.push(0x00);
+		// Set the ReadOptions
+		let mut ro = ReadOptions::default();
+		ro.set_iterate_lower_bound(beg.as_slice());
+		ro.set_iterate_upper_bound(end.as_slice());
+		// Create the iterator
+		let mut iter = self.handle.db.raw_iterator_opt(ro);
+		// Seek to the newest version at or before the timestamp
+		iter.seek(&beg);
+		// Return the visible value
+		Ok(iter.value().and_then(versioned::decode_value))
+	}
+
+	/// Retrieve a range of key-value pairs as they were at a specific version
+	fn scan_at_version(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		ts: Version,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		// RocksDB only supports versioned queries with versioned keys
+		if !self.versioned {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Create result set
+		let mut res = vec![];
+		// Resolve the visible versions
+		let mut vis = versioned::Visibility::new(ts);
+		// Iterate over the versioned history
+		let mut iter = self.history_iterator(&rng);
+		// Check the scan limit
+		while res.len() < limit as usize {
+			// Check the key and value
+			if let Some((k, v)) = iter.item() {
+				if let Some(kv) = vis.next(k, v) {
+					res.push(kv);
+				}
+				iter.next();
+				continue;
+			}
+			// Exit
+			break;
+		}
+		// Return result
+		Ok(res)
+	}
+}
+
+impl SavePointImpl for Transaction {
+	fn get_save_points(&mut self) -> &mut SavePoints {
+		&mut self.save_points
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::api::Transaction as _;
+	use crate::kvs::Isolation;
+	use temp_dir::TempDir;
+
+	#[tokio::test]
+	async fn secondary_catches_up_with_primary() {
+		let primary = TempDir::new().unwrap();
+		let secondary = TempDir::new().unwrap();
+		let mut opts = Options::default();
+		opts.create_if_missing(true);
+		let path = primary.path().to_str().unwrap();
+		let ds = super::super::Datastore::open(opts, path, false, true).unwrap();
+		// Write some data on the primary
+		let mut tx = ds.transaction(true, false, Isolation::Snapshot).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*one".to_vec(), b"a1".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Attach a secondary instance
+		let sd = Datastore::new(path, secondary.path().to_str().unwrap(), None).await.unwrap();
+		let mut tx = sd.transaction(false, false).await.unwrap();
+		let res = tx.get(b"/*test\x00*a\x00*one".to_vec(), None).await.unwrap();
+		assert_eq!(res, Some(b"a1".to_vec()));
+		// The secondary instance can not be written to
+		assert!(matches!(
+			tx.set(b"key".to_vec(), b"val".to_vec(), None).await,
+			Err(Error::TxReadonly)
+		));
+		tx.cancel().await.unwrap();
+		assert!(matches!(sd.transaction(true, false).await, Err(Error::TxReadonly)));
+		// Write to the primary, including a new database
+		let mut tx = ds.transaction(true, false, Isolation::Snapshot).await.unwrap();
+		tx.set(b"/*test\x00*a\x00*two".to_vec(), b"a2".to_vec(), None).await.unwrap();
+		tx.set(b"/*test\x00*b\x00*one".to_vec(), b"b1".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// The new commits are visible once refreshed
+		sd.refresh().await.unwrap();
+		let mut tx = sd.transaction(false, false).await.unwrap();
+		let res = tx.keys(b"/".to_vec()..b"0".to_vec(), 10, None).await.unwrap();
+		assert_eq!(
+			res,
+			vec![
+				b"/*test\x00*a\x00*one".to_vec(),
+				b"/*test\x00*a\x00*two".to_vec(),
+				b"/*test\x00*b\x00*one".to_vec(),
+			]
+		);
+		tx.cancel().await.unwrap();
+	}
+}
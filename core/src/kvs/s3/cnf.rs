@@ -0,0 +1,6 @@
+use std::sync::LazyLock;
+
+pub static S3_WRITE_BUFFER_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_S3_WRITE_BUFFER_SIZE", usize, 4 * 1024 * 1024);
+
+pub static S3_MAX_SEGMENTS: LazyLock<usize> = lazy_env_parse!("SURREAL_S3_MAX_SEGMENTS", usize, 16);
@@ -0,0 +1,248 @@
+//! A log-structured keyspace stored as immutable objects.
+//!
+//! Committed writes are first applied to an in-memory write buffer. When
+//! the write buffer exceeds the configured size, or when the datastore is
+//! shut down, it is written to object storage as a new immutable segment,
+//! and the manifest object, which lists the live segments in order, is
+//! replaced. When the number of segments exceeds the configured maximum,
+//! all segments are merged into a single segment, dropping deleted keys.
+//!
+//! Writes which are still in the write buffer are not durable, and are
+//! lost if the process exits without shutting down the datastore.
+use super::cnf;
+use crate::err::Error;
+use crate::kvs::{Key, Val};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use std::collections::BTreeMap;
+use std::ops::{Bound, Range};
+use std::sync::Arc;
+
+/// The latest value of each key, or None if deleted
+type Entries = BTreeMap<Key, Option<Val>>;
+
+pub(super) struct Tree {
+	/// The object store containing the segments
+	store: Arc<dyn ObjectStore>,
+	/// The path under which all objects are stored
+	prefix: Path,
+	/// The live segments, ordered from oldest to newest
+	segments: Vec<(u64, Arc<Entries>)>,
+	/// The committed writes which have not yet been flushed
+	buffer: Entries,
+	/// The approximate size of the write buffer in bytes
+	size: usize,
+}
+
+impl Tree {
+	/// Load the live segments from object storage
+	pub(super) async fn open(store: Arc<dyn ObjectStore>, prefix: Path) -> Result<Self, Error> {
+		let mut tree = Self {
+			store,
+			prefix,
+			segments: Vec::new(),
+			buffer: Entries::new(),
+			size: 0,
+		};
+		let ids = match tree.store.get(&tree.manifest()).await {
+			Ok(res) => decode_manifest(&res.bytes().await?)?,
+			Err(object_store::Error::NotFound {
+				..
+			}) => Vec::new(),
+			Err(e) => return Err(e.into()),
+		};
+		for id in ids {
+			let bytes = tree.store.get(&tree.segment(id)).await?.bytes().await?;
+			tree.segments.push((id, Arc::new(decode(&bytes)?)));
+		}
+		Ok(tree)
+	}
+	/// The path of the manifest object
+	fn manifest(&self) -> Path {
+		self.prefix.child("MANIFEST")
+	}
+	/// The path of a segment object
+	fn segment(&self, id: u64) -> Path {
+		self.prefix.child("segments").child(format!("{id:020}.seg"))
+	}
+	/// The identifier for the next segment
+	fn next(&self) -> u64 {
+		self.segments.last().map_or(0, |(id, _)| id + 1)
+	}
+	/// Fetch the latest value of a key
+	pub(super) fn get(&self, key: &[u8]) -> Option<Val> {
+		if let Some(v) = self.buffer.get(key) {
+			return v.clone();
+		}
+		for (_, seg) in self.segments.iter().rev() {
+			if let Some(v) = seg.get(key) {
+				return v.clone();
+			}
+		}
+		None
+	}
+	/// Fetch the latest values of a range of keys
+	pub(super) fn range(&self, rng: Range<&[u8]>, limit: usize) -> Vec<(Key, Val)> {
+		let bounds = (Bound::Included(rng.start), Bound::Excluded(rng.end));
+		// Overlay each layer, from oldest to newest
+		let mut out = Entries::new();
+		let layers = self.segments.iter().map(|(_, s)| s.as_ref()).chain([&self.buffer]);
+		for layer in layers {
+			for (k, v) in layer.range::<[u8], _>(bounds) {
+				out.insert(k.clone(), v.clone());
+			}
+		}
+		out.into_iter().filter_map(|(k, v)| Some((k, v?))).take(limit).collect()
+	}
+	/// Apply committed writes, flushing the write buffer if full
+	pub(super) async fn write(&mut self, writes: Entries) -> Result<(), Error> {
+		for (k, v) in writes {
+			self.size += k.len() + v.as_ref().map_or(0, Vec::len);
+			self.buffer.insert(k, v);
+		}
+		if self.size >= *cnf::S3_WRITE_BUFFER_SIZE {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+	/// Write the write buffer to object storage as a new segment
+	pub(super) async fn flush(&mut self) -> Result<(), Error> {
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+		let id = self.next();
+		self.store.put(&self.segment(id), PutPayload::from(encode(&self.buffer))).await?;
+		self.segments.push((id, Arc::new(std::mem::take(&mut self.buffer))));
+		self.size = 0;
+		match self.segments.len() > *cnf::S3_MAX_SEGMENTS {
+			true => self.compact().await,
+			false => self.save().await,
+		}
+	}
+	/// Merge all live segments into a single segment
+	async fn compact(&mut self) -> Result<(), Error> {
+		let id = self.next();
+		let mut entries = Entries::new();
+		for (_, seg) in self.segments.iter() {
+			for (k, v) in seg.iter() {
+				entries.insert(k.clone(), v.clone());
+			}
+		}
+		// Deleted keys are not needed once all segments are merged
+		entries.retain(|_, v| v.is_some());
+		self.store.put(&self.segment(id), PutPayload::from(encode(&entries))).await?;
+		let old = std::mem::replace(&mut self.segments, vec![(id, Arc::new(entries))]);
+		self.save().await?;
+		// Remove the merged segments once no longer referenced
+		for (id, _) in old {
+			self.store.delete(&self.segment(id)).await?;
+		}
+		Ok(())
+	}
+	/// Replace the manifest with the current list of live segments
+	async fn save(&self) -> Result<(), Error> {
+		let ids: Vec<String> = self.segments.iter().map(|(id, _)| id.to_string()).collect();
+		self.store.put(&self.manifest(), PutPayload::from(ids.join("\n").into_bytes())).await?;
+		Ok(())
+	}
+}
+
+/// Encode the entries of a segment
+fn encode(entries: &Entries) -> Vec<u8> {
+	let mut out = Vec::new();
+	for (k, v) in entries {
+		out.extend_from_slice(&(k.len() as u32).to_be_bytes());
+		out.extend_from_slice(k);
+		match v {
+			Some(v) => {
+				out.push(1);
+				out.extend_from_slice(&(v.len() as u32).to_be_bytes());
+				out.extend_from_slice(v);
+			}
+			None => out.push(0),
+		}
+	}
+	out
+}
+
+/// Decode the entries of a segment
+fn decode(mut buf: &[u8]) -> Result<Entries, Error> {
+	let mut out = Entries::new();
+	while !buf.is_empty() {
+		let k = take(&mut buf)?.to_vec();
+		let v = match buf.split_first() {
+			Some((1, rest)) => {
+				buf = rest;
+				Some(take(&mut buf)?.to_vec())
+			}
+			Some((0, rest)) => {
+				buf = rest;
+				None
+			}
+			_ => return Err(corrupted()),
+		};
+		out.insert(k, v);
+	}
+	Ok(out)
+}
+
+/// Decode the list of live segments
+fn decode_manifest(buf: &[u8]) -> Result<Vec<u64>, Error> {
+	let text = std::str::from_utf8(buf).map_err(|_| corrupted())?;
+	text.lines().map(|l| l.parse().map_err(|_| corrupted())).collect()
+}
+
+/// Take a length-prefixed slice from the front of a buffer
+fn take<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], Error> {
+	let (len, rest) = buf.split_first_chunk::<4>().ok_or_else(corrupted)?;
+	let len = u32::from_be_bytes(*len) as usize;
+	if rest.len() < len {
+		return Err(corrupted());
+	}
+	let (v, rest) = rest.split_at(len);
+	*buf = rest;
+	Ok(v)
+}
+
+fn corrupted() -> Error {
+	Error::Ds("The object storage contains a corrupted segment".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use object_store::memory::InMemory;
+
+	#[test]
+	fn encoding() {
+		let mut entries = Entries::new();
+		entries.insert(b"a".to_vec(), Some(b"1".to_vec()));
+		entries.insert(b"b".to_vec(), None);
+		assert_eq!(decode(&encode(&entries)).unwrap(), entries);
+		assert!(decode(&[0, 0, 0, 9, 1]).is_err());
+	}
+
+	#[tokio::test]
+	async fn flush_and_compact() {
+		let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+		let mut tree = Tree::open(store.clone(), Path::from("test")).await.unwrap();
+		// Write a number of segments, deleting every other key
+		for i in 0..=*cnf::S3_MAX_SEGMENTS as u8 {
+			let mut writes = Entries::new();
+			writes.insert(vec![i], Some(vec![i]));
+			if i > 0 && i % 2 == 0 {
+				writes.insert(vec![i - 1], None);
+			}
+			tree.write(writes).await.unwrap();
+			tree.flush().await.unwrap();
+		}
+		// The segments have been merged into one
+		assert_eq!(tree.segments.len(), 1);
+		assert_eq!(tree.get(&[2]), Some(vec![2]));
+		assert_eq!(tree.get(&[1]), None);
+		// The merged segment is loaded when reopened
+		let tree = Tree::open(store, Path::from("test")).await.unwrap();
+		let res = tree.range(&[0][..]..&[4][..], 10);
+		assert_eq!(res, vec![(vec![0], vec![0]), (vec![2], vec![2])]);
+	}
+}
@@ -0,0 +1,661 @@
+#![cfg(feature = "kv-shard")]
+//! A sharded datastore, routing keys to several other storage engines.
+//!
+//! The shards are defined in a JSON configuration file, which is passed
+//! as the path of the datastore, for example `shard:///etc/shards.json`:
+//!
+//! ```json
+//! {
+//!     "shards": {
+//!         "main": "rocksdb:///data/main.db",
+//!         "disk1": "rocksdb:///disk1/data.db",
+//!         "disk2": "surrealkv:///disk2/data.db"
+//!     },
+//!     "default": "main",
+//!     "namespaces": { "archive": "disk1" },
+//!     "databases": ["disk1", "disk2"]
+//! }
+//! ```
+//!
+//! Each namespace which is listed in `namespaces` is stored entirely on
+//! the specified shard. Each database of any other namespace is stored
+//! entirely on one of the `databases` shards, chosen by hashing the
+//! namespace and database names, and all remaining keys are stored on the
+//! `default` shard. The list of database shards must not be changed once
+//! data has been written, as databases would then be routed to a
+//! different shard.
+//!
+//! Each transaction starts a transaction on a shard when the shard is
+//! first used. As all of the data, indexes, and definitions within a
+//! database are stored on one shard, a transaction within a database only
+//! uses a single shard. Transactions which also change keys outside of
+//! the database, such as when a namespace is first defined, or which span
+//! several databases, commit the changes to each shard in turn, starting
+//! with the default shard. Such commits are not atomic across the shards,
+//! and reads across several shards do not observe a single consistent
+//! snapshot.
+
+mod router;
+
+use crate::err::Error;
+use crate::key::debug::Sprintable;
+use crate::kvs::api::Transaction as _;
+use crate::kvs::builder::{DatastoreBuilder, Engine};
+use crate::kvs::ds::DatastoreFlavor;
+use crate::kvs::savepoint::{SaveOperation, SavePointImpl, SavePoints};
+use crate::kvs::tr::{expand_inner, Inner};
+use crate::kvs::{Check, Consistency, Key, Val, Version};
+use router::{Config, Router};
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+
+const TARGET: &str = "surrealdb::core::kvs::shard";
+
+/// The underlying storage engines shared by all transactions
+struct Shards {
+	/// Determines which shard stores each key
+	router: Router,
+	/// The underlying storage engine of each shard
+	stores: Vec<DatastoreFlavor>,
+}
+
+#[non_exhaustive]
+pub struct Datastore {
+	shards: Arc<Shards>,
+}
+
+#[non_exhaustive]
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: bool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// Is the transaction pessimistic?
+	lock: bool,
+	/// Should we check unhandled transactions?
+	check: Check,
+	/// The transaction on each shard, once the shard has been used
+	txs: Vec<Option<Inner>>,
+	/// The underlying storage engines
+	shards: Arc<Shards>,
+	/// The save point implementation
+	save_points: SavePoints,
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		if !self.done && self.write {
+			// Check if already panicking
+			if std::thread::panicking() {
+				return;
+			}
+			// Handle the behaviour
+			match self.check {
+				Check::None => {
+					trace!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Warn => {
+					warn!("A transaction was dropped without being committed or cancelled");
+				}
+				Check::Panic => {
+					#[cfg(debug_assertions)]
+					{
+						let backtrace = std::backtrace::Backtrace::force_capture();
+						if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+							println!("{}", backtrace);
+						}
+					}
+					panic!("A transaction was dropped without being committed or cancelled");
+				}
+			}
+		}
+	}
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore, Error> {
+		// Load the shard configuration
+		let cfg = std::fs::read_to_string(path)
+			.map_err(|e| Error::Ds(format!("Unable to read the shard configuration: {e}")))?;
+		let cfg: Config = serde_json::from_str(&cfg)
+			.map_err(|e| Error::Ds(format!("Invalid shard configuration: {e}")))?;
+		let router = Router::new(&cfg)?;
+		// Open the storage engine of each shard
+		let mut stores = Vec::with_capacity(cfg.shards.len());
+		for (name, path) in cfg.shards.iter() {
+			let builder = DatastoreBuilder::from_path(path)?;
+			// Sharded datastores can not be nested
+			if builder.engine == Engine::Shard {
+				return Err(Error::Ds(format!(
+					"The `{name}` shard can not be a sharded datastore"
+				)));
+			}
+			debug!(target: TARGET, "Opening the `{name}` shard at {builder}");
+			stores.push(Box::pin(DatastoreFlavor::new(&builder)).await?);
+		}
+		// Create the datastore
+		Ok(Datastore {
+			shards: Arc::new(Shards {
+				router,
+				stores,
+			}),
+		})
+	}
+	/// Check if every shard is an embedded storage engine
+	pub(crate) fn is_local(&self) -> bool {
+		self.shards.stores.iter().all(DatastoreFlavor::is_local)
+	}
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<(), Error> {
+		for store in self.shards.stores.iter() {
+			Box::pin(store.shutdown()).await?;
+		}
+		Ok(())
+	}
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, lock: bool) -> Result<Transaction, Error> {
+		// Specify the check level
+		#[cfg(not(debug_assertions))]
+		let check = Check::Warn;
+		#[cfg(debug_assertions)]
+		let check = Check::Panic;
+		// Create a new transaction
+		Ok(Transaction {
+			done: false,
+			write,
+			lock,
+			check,
+			txs: self.shards.stores.iter().map(|_| None).collect(),
+			shards: self.shards.clone(),
+			save_points: Default::default(),
+		})
+	}
+}
+
+impl Transaction {
+	/// Get the transaction on a shard, starting it when first used
+	async fn shard(&mut self, idx: usize) -> Result<&mut Inner, Error> {
+		let tx = match self.txs[idx].take() {
+			Some(tx) => tx,
+			None => {
+				let store = &self.shards.stores[idx];
+				let isolation = store.default_isolation(self.write, self.lock);
+				let mut tx = store
+					.transaction(self.write, self.lock, isolation, Consistency::Strong)
+					.await?;
+				// Unfinished transactions are reported by this transaction
+				expand_inner!(&mut tx, v => { v.check_level(Check::None) });
+				tx
+			}
+		};
+		Ok(self.txs[idx].insert(tx))
+	}
+	/// Get the transaction on the shard which stores a key
+	async fn route(&mut self, key: &[u8]) -> Result<&mut Inner, Error> {
+		let idx = self.shards.router.route(key);
+		self.shard(idx).await
+	}
+}
+
+impl super::api::Transaction for Transaction {
+	/// Behaviour if unclosed
+	fn check_level(&mut self, check: Check) {
+		self.check = check;
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancel a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Cancel the transaction on every shard
+		let mut res = Ok(());
+		for tx in self.txs.iter_mut().flatten() {
+			if let Err(e) = ops::cancel(tx).await {
+				res = res.and(Err(e));
+			}
+		}
+		res
+	}
+
+	/// Commit a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&mut self) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Mark this transaction as done
+		self.done = true;
+		// Commit the transaction on each shard in turn, starting with the
+		// default shard, and cancelling the remaining shards on any failure
+		let default = self.shards.router.default();
+		let order = std::iter::once(default).chain((0..self.txs.len()).filter(|&i| i != default));
+		let mut res = Ok(());
+		for idx in order {
+			let Some(tx) = self.txs[idx].as_mut() else {
+				continue;
+			};
+			res = match res {
+				Ok(()) => ops::commit(tx).await,
+				Err(e) => {
+					let _ = ops::cancel(tx).await;
+					Err(e)
+				}
+			};
+		}
+		res
+	}
+
+	/// Check if a key exists
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists<K>(&mut self, key: K, version: Option<u64>) -> Result<bool, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check the key
+		let key = key.into();
+		ops::exists(self.route(&key).await?, key, version).await
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get<K>(&mut self, key: K, version: Option<u64>) -> Result<Option<Val>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the key
+		let key = key.into();
+		ops::get(self.route(&key).await?, key, version).await
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Prepare the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Set).await?
+		} else {
+			None
+		};
+		// Set the key
+		ops::set(self.route(&key).await?, key, val.into(), version).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put<K, V>(&mut self, key: K, val: V, version: Option<u64>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, version, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Set the key if empty
+		ops::put(self.route(&key).await?, key, val.into(), version).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc<K, V>(&mut self, key: K, val: V, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Put).await?
+		} else {
+			None
+		};
+		// Set the key if the condition matches
+		ops::putc(self.route(&key).await?, key, val.into(), chk.map(Into::into)).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Delete the key
+		ops::del(self.route(&key).await?, key).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc<K, V>(&mut self, key: K, chk: Option<V>) -> Result<(), Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+		V: Into<Val> + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Extract the key
+		let key = key.into();
+		// Hydrate the savepoint if any
+		let prep = if self.save_points.is_some() {
+			self.save_point_prepare(&key, None, SaveOperation::Del).await?
+		} else {
+			None
+		};
+		// Delete the key if the condition matches
+		ops::delc(self.route(&key).await?, key, chk.map(Into::into)).await?;
+		// Confirm the save point
+		if let Some(prep) = prep {
+			self.save_points.save(prep);
+		}
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieve a range of keys from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = rng.start.into()..rng.end.into();
+		// Scan the keys on each shard
+		let mut res = Vec::new();
+		for idx in self.shards.router.shards(&rng) {
+			let tx = self.shard(idx).await?;
+			res.extend(ops::keys(tx, rng.clone(), limit, version).await?);
+		}
+		// Merge the keys from each shard
+		res.sort_unstable();
+		res.truncate(limit as usize);
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve a range of keys from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = rng.start.into()..rng.end.into();
+		// Scan the keys on each shard
+		let mut res = Vec::new();
+		for idx in self.shards.router.shards(&rng) {
+			let tx = self.shard(idx).await?;
+			res.extend(ops::scan(tx, rng.clone(), limit, version).await?);
+		}
+		// Merge the keys from each shard
+		res.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+		res.truncate(limit as usize);
+		// Return result
+		Ok(res)
+	}
+
+	/// Retrieve all the versions from a range of keys from the databases
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan_all_versions<K>(
+		&mut self,
+		rng: Range<K>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val, Version, bool)>, Error>
+	where
+		K: Into<Key> + Sprintable + Debug,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Convert the range to bytes
+		let rng: Range<Key> = rng.start.into()..rng.end.into();
+		// Scan the versions on each shard
+		let mut res = Vec::new();
+		for idx in self.shards.router.shards(&rng) {
+			let tx = self.shard(idx).await?;
+			res.extend(ops::scan_all_versions(tx, rng.clone(), limit).await?);
+		}
+		// Merge the keys from each shard, keeping
+		// the versions of each key in their order
+		res.sort_by(|a, b| a.0.cmp(&b.0));
+		res.truncate(limit as usize);
+		// Return result
+		Ok(res)
+	}
+}
+
+impl SavePointImpl for Transaction {
+	fn get_save_points(&mut self) -> &mut SavePoints {
+		&mut self.save_points
+	}
+}
+
+/// Operations on the transaction of a single shard.
+///
+/// A shard transaction could itself be a sharded transaction, so these
+/// return boxed futures to avoid an infinitely sized future type.
+mod ops {
+	use crate::err::Error;
+	use crate::kvs::api::Transaction;
+	use crate::kvs::tr::{expand_inner, Inner};
+	use crate::kvs::{Key, Val, Version};
+	use futures::future::BoxFuture;
+	use std::ops::Range;
+
+	pub(super) fn cancel(tx: &mut Inner) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.cancel().await }) })
+	}
+
+	pub(super) fn commit(tx: &mut Inner) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.commit().await }) })
+	}
+
+	pub(super) fn exists(
+		tx: &mut Inner,
+		key: Key,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<bool, Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.exists(key, version).await }) })
+	}
+
+	pub(super) fn get(
+		tx: &mut Inner,
+		key: Key,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<Option<Val>, Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.get(key, version).await }) })
+	}
+
+	pub(super) fn set(
+		tx: &mut Inner,
+		key: Key,
+		val: Val,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.set(key, val, version).await }) })
+	}
+
+	pub(super) fn put(
+		tx: &mut Inner,
+		key: Key,
+		val: Val,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.put(key, val, version).await }) })
+	}
+
+	pub(super) fn putc(
+		tx: &mut Inner,
+		key: Key,
+		val: Val,
+		chk: Option<Val>,
+	) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.putc(key, val, chk).await }) })
+	}
+
+	pub(super) fn del(tx: &mut Inner, key: Key) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.del(key).await }) })
+	}
+
+	pub(super) fn delc(
+		tx: &mut Inner,
+		key: Key,
+		chk: Option<Val>,
+	) -> BoxFuture<'_, Result<(), Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.delc(key, chk).await }) })
+	}
+
+	pub(super) fn keys(
+		tx: &mut Inner,
+		rng: Range<Key>,
+		limit: u32,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<Vec<Key>, Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.keys(rng, limit, version).await }) })
+	}
+
+	pub(super) fn scan(
+		tx: &mut Inner,
+		rng: Range<Key>,
+		limit: u32,
+		version: Option<u64>,
+	) -> BoxFuture<'_, Result<Vec<(Key, Val)>, Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.scan(rng, limit, version).await }) })
+	}
+
+	pub(super) fn scan_all_versions(
+		tx: &mut Inner,
+		rng: Range<Key>,
+		limit: u32,
+	) -> BoxFuture<'_, Result<Vec<(Key, Val, Version, bool)>, Error>> {
+		Box::pin(async move { expand_inner!(tx, v => { v.scan_all_versions(rng, limit).await }) })
+	}
+}
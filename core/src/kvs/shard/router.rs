@@ -0,0 +1,207 @@
+use crate::err::Error;
+use crate::kvs::{partition, Key};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+/// The configuration of a sharded datastore
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+	/// The connection string of each shard, by shard name
+	pub(super) shards: BTreeMap<String, String>,
+	/// The shard which stores any keys which are not otherwise routed
+	pub(super) default: String,
+	/// The shard which stores each namespace, by namespace name
+	#[serde(default)]
+	pub(super) namespaces: BTreeMap<String, String>,
+	/// The shards across which the databases of any other namespaces are distributed
+	#[serde(default)]
+	pub(super) databases: Vec<String>,
+}
+
+/// Determines which shard stores each key
+#[derive(Debug)]
+pub(super) struct Router {
+	/// The number of shards
+	count: usize,
+	/// The shard which stores any keys which are not otherwise routed
+	default: usize,
+	/// The shard which stores each namespace
+	namespaces: HashMap<Vec<u8>, usize>,
+	/// The shards across which databases are distributed
+	databases: Vec<usize>,
+}
+
+impl Router {
+	/// Create a new router from a shard configuration
+	pub(super) fn new(cfg: &Config) -> Result<Router, Error> {
+		// Shards are numbered by the order of their names
+		let names: Vec<&String> = cfg.shards.keys().collect();
+		let index = |name: &String| match names.binary_search(&name) {
+			Ok(idx) => Ok(idx),
+			Err(_) => Err(Error::Ds(format!("The `{name}` shard is not defined"))),
+		};
+		Ok(Router {
+			count: names.len(),
+			default: index(&cfg.default)?,
+			namespaces: cfg
+				.namespaces
+				.iter()
+				.map(|(ns, name)| Ok((ns.as_bytes().to_vec(), index(name)?)))
+				.collect::<Result<_, Error>>()?,
+			databases: cfg.databases.iter().map(index).collect::<Result<_, Error>>()?,
+		})
+	}
+	/// Get the shard which stores any keys which are not otherwise routed
+	pub(super) fn default(&self) -> usize {
+		self.default
+	}
+	/// Get the shard which stores a key
+	pub(super) fn route(&self, key: &[u8]) -> usize {
+		// Keys outside of a namespace are stored on the default shard
+		let Some(ns) = namespace(key) else {
+			return self.default;
+		};
+		// Check if this namespace is stored on a specific shard
+		if let Some(idx) = self.namespaces.get(ns) {
+			return *idx;
+		}
+		// Otherwise distribute databases across the database shards
+		match database(key) {
+			Some((ns, db)) if !self.databases.is_empty() => {
+				let mut hash = blake3::Hasher::new();
+				hash.update(ns);
+				hash.update(&[0x00]);
+				hash.update(db);
+				let mut num = [0; 8];
+				num.copy_from_slice(&hash.finalize().as_bytes()[..8]);
+				let num = u64::from_be_bytes(num) % self.databases.len() as u64;
+				self.databases[num as usize]
+			}
+			_ => self.default,
+		}
+	}
+	/// Get the shards which may store keys in a range, in order
+	pub(super) fn shards(&self, rng: &Range<Key>) -> Vec<usize> {
+		// Ranges outside of all namespaces are stored on the default shard
+		if rng.end.as_slice() <= b"/*".as_slice() || rng.start.as_slice() >= b"/+".as_slice() {
+			return vec![self.default];
+		}
+		// Ranges within a single database are stored on its shard
+		if let Some(prefix) = partition::prefix(&rng.start) {
+			if rng.end <= partition::range(prefix).end {
+				return vec![self.route(&rng.start)];
+			}
+		}
+		// Ranges within a single namespace are stored on its shards
+		if let Some(ns) = namespace(&rng.start) {
+			let mut end = rng.start[..ns.len() + 3].to_vec();
+			end.push(0xff);
+			if rng.end <= end {
+				if let Some(idx) = self.namespaces.get(ns) {
+					return vec![*idx];
+				}
+				let mut out = self.databases.clone();
+				out.push(self.default);
+				out.sort_unstable();
+				out.dedup();
+				return out;
+			}
+		}
+		// Otherwise the range may span every shard
+		(0..self.count).collect()
+	}
+}
+
+/// Get the namespace name of a key, if the key belongs to a namespace
+fn namespace(key: &[u8]) -> Option<&[u8]> {
+	let rest = key.strip_prefix(b"/*")?;
+	let end = rest.iter().position(|&b| b == 0x00)?;
+	Some(&rest[..end])
+}
+
+/// Get the namespace and database names of a key, if the key belongs to or defines a database
+fn database(key: &[u8]) -> Option<(&[u8], &[u8])> {
+	let ns = namespace(key)?;
+	let rest = &key[ns.len() + 3..];
+	// Keys within a database, and the definition of the database
+	let rest = rest.strip_prefix(b"*").or_else(|| rest.strip_prefix(b"!db"))?;
+	let end = rest.iter().position(|&b| b == 0x00)?;
+	Some((ns, &rest[..end]))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn router(json: &str) -> Router {
+		Router::new(&serde_json::from_str(json).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn unknown_shard() {
+		let cfg = r#"{ "shards": { "a": "memory" }, "default": "b" }"#;
+		assert!(Router::new(&serde_json::from_str(cfg).unwrap()).is_err());
+		let cfg = r#"{ "shards": { "a": "memory" }, "default": "a", "databases": ["b"] }"#;
+		assert!(Router::new(&serde_json::from_str(cfg).unwrap()).is_err());
+	}
+
+	#[test]
+	fn route_namespaces() {
+		let r = router(
+			r#"{
+				"shards": { "a": "memory", "b": "memory" },
+				"default": "a",
+				"namespaces": { "other": "b" }
+			}"#,
+		);
+		assert_eq!(r.route(b"/!ndtest"), 0);
+		assert_eq!(r.route(b"/!nsother\x00"), 0);
+		assert_eq!(r.route(b"/*test\x00*test\x00*person\x00*\x00\x00\x00\x01a\x00"), 0);
+		assert_eq!(r.route(b"/*other\x00*test\x00*person\x00*\x00\x00\x00\x01a\x00"), 1);
+		assert_eq!(r.route(b"/*other\x00!dbtest\x00"), 1);
+		assert_eq!(r.shards(&(b"/*other\x00".to_vec()..b"/*other\x00\xff".to_vec())), vec![1]);
+		assert_eq!(r.shards(&(b"/*test\x00".to_vec()..b"/*test\x00\xff".to_vec())), vec![0]);
+		assert_eq!(r.shards(&(b"/!ns".to_vec()..b"/!ns\xff".to_vec())), vec![0]);
+		assert_eq!(r.shards(&(b"/*".to_vec()..b"/*\xff".to_vec())), vec![0, 1]);
+	}
+
+	#[test]
+	fn route_databases() {
+		let r = router(
+			r#"{
+				"shards": { "a": "memory", "b": "memory", "c": "memory" },
+				"default": "a",
+				"namespaces": { "other": "c" },
+				"databases": ["b", "c"]
+			}"#,
+		);
+		let key = |db: u8| {
+			let mut key = b"/*test\x00*".to_vec();
+			key.extend_from_slice(&[b'a' + db % 26, b'a' + db / 26, 0x00]);
+			key
+		};
+		// Databases are distributed across the database shards
+		let used: Vec<usize> = (0..32).map(|db| r.route(&key(db))).collect();
+		assert!(used.contains(&1) && used.contains(&2) && !used.contains(&0));
+		// All keys of a database are stored on the same shard
+		let shard = r.route(b"/*test\x00*test\x00*person\x00*\x00\x00\x00\x01a\x00");
+		assert_eq!(r.route(b"/*test\x00*test\x00*person\x00+index\x00"), shard);
+		assert_eq!(r.route(b"/*test\x00*test\x00!tbperson\x00"), shard);
+		assert_eq!(r.route(b"/*test\x00!dbtest\x00"), shard);
+		// Other namespace keys are stored on the default shard
+		assert_eq!(r.route(b"/*test\x00!ustest\x00"), 0);
+		// Ranges within a database are stored on a single shard
+		let rng = b"/*test\x00*test\x00*person\x00*".to_vec()
+			..b"/*test\x00*test\x00*person\x00*\xff".to_vec();
+		assert_eq!(r.shards(&rng), vec![shard]);
+		let rng = b"/*test\x00*test\x00".to_vec()..b"/*test\x00*test\x00\xff".to_vec();
+		assert_eq!(r.shards(&rng), vec![shard]);
+		// Ranges across databases may span the default shard and the database shards
+		let rng = b"/*test\x00!db".to_vec()..b"/*test\x00!db\xff".to_vec();
+		assert_eq!(r.shards(&rng), vec![0, 1, 2]);
+		let rng = b"/*other\x00".to_vec()..b"/*other\x00\xff".to_vec();
+		assert_eq!(r.shards(&rng), vec![2]);
+	}
+}
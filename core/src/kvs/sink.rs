@@ -0,0 +1,242 @@
+//! Delivery of table changefeeds to sinks.
+//!
+//! The changes committed to the changefeed of a table are delivered to each
+//! `DEFINE SINK` on the table by a background task, which runs periodically.
+//! Each sink keeps track of the versionstamp from which changes have not yet
+//! been delivered, which is stored in the key-value store alongside the sink
+//! definition, and which starts from the point at which the sink was defined.
+//!
+//! The position is only moved forward once the message broker has accepted
+//! the changes, so that changes are delivered at least once. A delivery which
+//! fails is retried from the same position the next time the task runs, so a
+//! consumer may receive a change more than once, but will never miss one, as
+//! long as the sink catches up before the changes expire from the changefeed.
+use crate::cf;
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+use crate::cnf::SINK_BATCH_SIZE;
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::Transaction;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sink::Message;
+use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::DefineSinkStatement;
+use crate::sql::Value;
+use crate::vs::conv::versionstamp_to_u64;
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::kvs::sink";
+
+/// The name of the lock which is held while delivering changes to sinks
+const LOCK: &str = "sinks";
+
+/// How long the sink lock is held for, if it is not released
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+impl Datastore {
+	/// Deliver any new changefeed entries to the sinks defined on each table.
+	///
+	/// This function should be run periodically at an interval.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::sink", skip(self))]
+	pub async fn process_sinks(&self) -> Result<(), Error> {
+		// Only one node delivers changes at a time
+		if !self.lock(LOCK, LOCK_TTL).await? {
+			return Ok(());
+		}
+		// Deliver the changes to each sink
+		let res = self.deliver_sinks().await;
+		// Release the lock for other nodes
+		self.unlock(LOCK).await?;
+		res
+	}
+
+	/// Delivers the next batch of changes to every sink.
+	async fn deliver_sinks(&self) -> Result<(), Error> {
+		// Fetch all of the sink definitions
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		let sinks = catch!(txn, all_sinks(&txn).await);
+		catch!(txn, txn.cancel().await);
+		// A sink which fails does not hold up any other sink
+		for (ns, db, sk) in sinks {
+			if let Err(e) = self.deliver_sink(&ns, &db, &sk).await {
+				warn!(target: TARGET, "Failed to deliver changes on table {}: {e}", sk.what);
+			}
+		}
+		Ok(())
+	}
+
+	/// Delivers the next batch of changes to a sink, and records the position reached.
+	async fn deliver_sink(
+		&self,
+		ns: &str,
+		db: &str,
+		sk: &DefineSinkStatement,
+	) -> Result<(), Error> {
+		// Read the changes from the last delivered position
+		let key = crate::key::table::so::new(ns, db, &sk.what, &sk.name);
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		let offset = catch!(txn, txn.get(key.clone(), None).await);
+		let since = ShowSince::Versionstamp(offset.map(decode).unwrap_or_default());
+		let res = cf::read(&txn, ns, db, Some(&sk.what), since, Some(*SINK_BATCH_SIZE)).await;
+		let changes = catch!(txn, res);
+		catch!(txn, txn.cancel().await);
+		// Check if there is anything to deliver
+		let Some(ChangeSet(last, _)) = changes.last() else {
+			return Ok(());
+		};
+		let next = versionstamp_to_u64(last) + 1;
+		// Deliver the changes before recording the position
+		let messages = messages(ns, db, sk, changes);
+		if !messages.is_empty() {
+			self.sinks().publish(self, sk, &messages).await?;
+		}
+		trace!(target: TARGET, "Delivered {} changes to sink {}", messages.len(), sk.name);
+		// Record the position, unless the sink was removed in the meantime
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		let exists = crate::key::table::sk::new(ns, db, &sk.what, &sk.name);
+		if catch!(txn, txn.exists(exists, None).await) {
+			catch!(txn, txn.set(key, next.to_be_bytes().to_vec(), None).await);
+		}
+		txn.commit().await
+	}
+}
+
+/// Fetches the sinks defined on every table, along with their namespace and database
+async fn all_sinks(txn: &Transaction) -> Result<Vec<(String, String, DefineSinkStatement)>, Error> {
+	let mut out = Vec::new();
+	for ns in txn.all_ns().await?.iter() {
+		let ns = ns.name.as_str();
+		for db in txn.all_db(ns).await?.iter() {
+			let db = db.name.as_str();
+			for tb in txn.all_tb(ns, db, None).await?.iter() {
+				for sk in txn.all_tb_sinks(ns, db, &tb.name).await?.iter() {
+					out.push((ns.to_owned(), db.to_owned(), sk.clone()));
+				}
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Decodes the stored position of a sink
+fn decode(val: Vec<u8>) -> u64 {
+	val.as_slice().try_into().map(u64::from_be_bytes).unwrap_or_default()
+}
+
+/// Converts the record changes in a batch of changesets into messages for a sink
+fn messages(ns: &str, db: &str, sk: &DefineSinkStatement, changes: Vec<ChangeSet>) -> Vec<Message> {
+	let mut out = Vec::new();
+	for ChangeSet(vs, DatabaseMutation(tables)) in changes {
+		let vs = versionstamp_to_u64(&vs);
+		for TableMutations(tb, mutations) in tables {
+			for (i, mutation) in mutations.into_iter().enumerate() {
+				let id = match &mutation {
+					TableMutation::Set(id, _)
+					| TableMutation::Update(id, _)
+					| TableMutation::SetWithDiff(id, _, _)
+					| TableMutation::Del(id)
+					| TableMutation::DelWithOriginal(id, _) => id.clone(),
+					// Table definitions are not record changes
+					TableMutation::Def(_) => continue,
+				};
+				let key = id.to_string();
+				let change = Value::from(map! {
+					"versionstamp".to_string() => Value::from(vs),
+					"table".to_string() => Value::from(tb.as_str()),
+					"record".to_string() => Value::Thing(id),
+					"change".to_string() => mutation.into_value(),
+				});
+				out.push(Message {
+					id: format!("{ns}/{db}/{tb}/{}/{vs}/{i}", sk.name.as_str()),
+					key,
+					payload: change.into_json().to_string().into_bytes(),
+				});
+			}
+		}
+	}
+	out
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+
+	async fn query(ds: &Datastore, sql: &str) -> Result<Value, Error> {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute(sql, &ses, None).await.unwrap().pop().unwrap().result
+	}
+
+	async fn sink(ds: &Datastore) -> DefineSinkStatement {
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let sk = txn.get_tb_sink("test", "test", "person", "out").await.unwrap();
+		txn.cancel().await.unwrap();
+		sk.as_ref().clone()
+	}
+
+	async fn offset(ds: &Datastore) -> Option<u64> {
+		let key = crate::key::table::so::new("test", "test", "person", "out");
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let val = txn.get(key, None).await.unwrap();
+		txn.cancel().await.unwrap();
+		val.map(decode)
+	}
+
+	fn records(messages: Vec<Message>) -> Vec<String> {
+		messages.into_iter().map(|m| m.key).collect()
+	}
+
+	#[tokio::test]
+	async fn changes_are_delivered_from_definition() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE TABLE person CHANGEFEED 1h").await.unwrap();
+		query(&ds, "CREATE person:one").await.unwrap();
+		let sql = "DEFINE SINK out FOR TABLE person TYPE KAFKA URL 'localhost:9092' TOPIC 'people'";
+		query(&ds, sql).await.unwrap();
+		query(&ds, "CREATE person:two").await.unwrap();
+		let publisher = ds.sinks().memory(&sink(&ds).await).await;
+		// Only the changes after the sink was defined are delivered
+		let start = offset(&ds).await.unwrap();
+		ds.process_sinks().await.unwrap();
+		assert_eq!(records(publisher.published()), vec!["person:two"]);
+		assert!(offset(&ds).await.unwrap() > start);
+		// Delivered changes are not delivered again
+		ds.process_sinks().await.unwrap();
+		assert_eq!(records(publisher.published()), vec!["person:two"]);
+		query(&ds, "DELETE person:one").await.unwrap();
+		ds.process_sinks().await.unwrap();
+		assert_eq!(records(publisher.published()), vec!["person:two", "person:one"]);
+	}
+
+	#[tokio::test]
+	async fn failed_deliveries_are_retried() {
+		let ds = Datastore::new("memory").await.unwrap();
+		query(&ds, "DEFINE TABLE person CHANGEFEED 1h").await.unwrap();
+		let sql =
+			"DEFINE SINK out FOR TABLE person TYPE NATS URL 'nats://localhost:4222' TOPIC 'people'";
+		query(&ds, sql).await.unwrap();
+		query(&ds, "CREATE person:one").await.unwrap();
+		// Network access is not allowed, so the delivery fails
+		let start = offset(&ds).await;
+		ds.process_sinks().await.unwrap();
+		assert_eq!(offset(&ds).await, start);
+		// The changes are delivered once the broker is reachable
+		let publisher = ds.sinks().memory(&sink(&ds).await).await;
+		ds.process_sinks().await.unwrap();
+		assert_eq!(records(publisher.published()), vec!["person:one"]);
+		assert_ne!(offset(&ds).await, start);
+	}
+
+	#[tokio::test]
+	async fn sinks_require_a_changefeed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE SINK out FOR TABLE person TYPE KAFKA URL 'localhost:9092' TOPIC 'people'";
+		assert!(matches!(query(&ds, sql).await, Err(Error::SkNoChangeFeed { .. })));
+		// Removing a sink removes its position
+		query(&ds, "DEFINE TABLE person CHANGEFEED 1h").await.unwrap();
+		query(&ds, sql).await.unwrap();
+		assert!(offset(&ds).await.is_some());
+		query(&ds, "REMOVE SINK out FOR TABLE person").await.unwrap();
+		assert!(offset(&ds).await.is_none());
+	}
+}
@@ -0,0 +1,6 @@
+use std::sync::LazyLock;
+
+pub static SLED_FLUSH_EVERY_MS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SLED_FLUSH_EVERY_MS", u64, 500);
+
+pub static SLED_SYNC_DATA: LazyLock<bool> = lazy_env_parse!("SURREAL_SLED_SYNC_DATA", bool, false);
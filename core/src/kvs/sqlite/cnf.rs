@@ -0,0 +1,7 @@
+use std::sync::LazyLock;
+
+pub static SQLITE_SYNCHRONOUS: LazyLock<String> =
+	lazy_env_parse!("SURREAL_SQLITE_SYNCHRONOUS", String, "NORMAL".to_string());
+
+pub static SQLITE_BUSY_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SQLITE_BUSY_TIMEOUT", u64, 5000);
@@ -0,0 +1,34 @@
+#[tokio::test]
+#[serial]
+async fn serializable_snapshot_prevents_write_skew() {
+	use crate::kvs::Isolation;
+	// Create a new datastore
+	let node_id = Uuid::parse_str("4f5a2bd1-1c8d-4a52-9d6d-0f7b2f3e8c41").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Insert the initial keys
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("a", "1", None).await.unwrap();
+	tx.set("b", "1", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Create a serializable transaction which reads one key
+	let mut tx1 = ds
+		.transaction_with_isolation(Write, Optimistic, Isolation::SerializableSnapshot)
+		.await
+		.unwrap()
+		.inner();
+	let val = tx1.get("a", None).await.unwrap().unwrap();
+	assert_eq!(val, b"1");
+	// Modify the key which was read in another transaction
+	let mut tx2 = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx2.set("a", "2", None).await.unwrap();
+	tx2.commit().await.unwrap();
+	// Write to a different key based on the stale read
+	tx1.set("b", "2", None).await.unwrap();
+	assert!(tx1.commit().await.is_err());
+	// Check that the second key was not updated
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("b", None).await.unwrap().unwrap();
+	assert_eq!(val, b"1");
+	tx.cancel().await.unwrap();
+}
@@ -1,5 +1,5 @@
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn multireader() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("b7afc077-2123-476f-bee0-43d7504f1e0a").unwrap();
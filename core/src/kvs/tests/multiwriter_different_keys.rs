@@ -1,5 +1,5 @@
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn multiwriter_different_keys() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("7f0153b0-79cf-4922-85ef-61e390970514").unwrap();
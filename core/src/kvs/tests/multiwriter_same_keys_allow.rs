@@ -1,5 +1,5 @@
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn multiwriter_same_keys_allow() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("a19cf00d-f95b-42c6-95e5-7b310162d570").unwrap();
@@ -1,5 +1,5 @@
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn multiwriter_same_keys_conflict() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("96ebbb5c-8040-497a-9459-838e4931aca7").unwrap();
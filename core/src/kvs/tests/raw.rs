@@ -1,13 +1,13 @@
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn initialise() {
 	let mut tx = new_tx(Write, Optimistic).await.inner();
 	assert!(tx.put("test", "ok", None).await.is_ok());
 	tx.commit().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn exists() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("463a5008-ee1d-43db-9662-5e752b6ea3f9").unwrap();
@@ -26,8 +26,8 @@ async fn exists() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn get() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("477e2895-8c98-4606-a827-0add82eb466b").unwrap();
@@ -46,8 +46,8 @@ async fn get() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn set() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("32b80d8b-dd16-4f6f-a687-1192f6cfc6f1").unwrap();
@@ -73,8 +73,8 @@ async fn set() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn put() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("80149655-db34-451c-8711-6fa662a44b70").unwrap();
@@ -100,8 +100,39 @@ async fn put() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
+async fn put_prefetched() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("3c2e3d1a-4f0b-4b8e-9d7a-2f6c1e5b8a90").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	assert!(tx.put("test", "one", None).await.is_ok());
+	tx.commit().await.unwrap();
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.prefetch(vec!["test", "temp", "tmp"]).await.unwrap();
+	assert!(tx.put("test", "two", None).await.is_err());
+	assert!(tx.put("temp", "two", None).await.is_ok());
+	assert!(tx.put("temp", "three", None).await.is_err());
+	tx.set("tmp", "two", None).await.unwrap();
+	assert!(tx.put("tmp", "three", None).await.is_err());
+	tx.commit().await.unwrap();
+	// Create a readonly transaction
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	let val = tx.get("temp", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"two")));
+	let val = tx.get("tmp", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"two")));
+	tx.cancel().await.unwrap();
+}
+
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn putc() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("705bb520-bc2b-4d52-8e64-d1214397e408").unwrap();
@@ -134,10 +165,22 @@ async fn putc() {
 	let val = tx.get("test", None).await.unwrap();
 	assert!(matches!(val.as_deref(), Some(b"two")));
 	tx.cancel().await.unwrap();
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	assert!(tx.putc("none", "one", None).await.is_ok());
+	assert!(tx.putc("test", "one", None).await.is_err());
+	tx.commit().await.unwrap();
+	// Create a readonly transaction
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("none", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	let val = tx.get("test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"two")));
+	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn del() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("e0acb360-9187-401f-8192-f870b09e2c9e").unwrap();
@@ -158,8 +201,8 @@ async fn del() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn delc() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("0985488e-cf2f-417a-bd10-7f4aa9c99c15").unwrap();
@@ -189,8 +232,8 @@ async fn delc() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn keys() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("83b81cc2-9609-4533-bede-c170ab9f7bbe").unwrap();
@@ -230,8 +273,8 @@ async fn keys() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn scan() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("83b81cc2-9609-4533-bede-c170ab9f7bbe").unwrap();
@@ -280,8 +323,8 @@ async fn scan() {
 	tx.cancel().await.unwrap();
 }
 
-#[tokio::test]
-#[serial]
+#[cfg_attr(test, tokio::test)]
+#[cfg_attr(test, serial)]
 async fn batch() {
 	// Create a new datastore
 	let node_id = Uuid::parse_str("6572a13c-a7a0-4e19-be62-18acb4e854f5").unwrap();
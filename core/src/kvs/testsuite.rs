@@ -0,0 +1,106 @@
+//! A conformance test suite for key-value storage engines.
+//!
+//! This suite runs the same storage tests which are run against each of
+//! the built-in storage engines, verifying the transaction semantics which
+//! the rest of the database depends on, such as snapshot isolation, conflict
+//! detection, and the behaviour of conditional writes and deletes. Storage
+//! engine authors can run the suite against their implementation using
+//! [`run`], which panics if the storage engine does not behave as expected.
+//!
+//! Each test opens a new datastore at a path which is returned by the given
+//! function, and expects the datastore to be empty, so remote storage engines
+//! should return a path to a new, or a cleared, keyspace each time.
+//!
+//! ```rust,no_run
+//! use surrealdb_core::kvs::testsuite::{self, WriteConflicts};
+//!
+//! #[tokio::test]
+//! async fn conformance() {
+//!     let path = || format!("rocksdb:/tmp/{}", uuid::Uuid::new_v4());
+//!     testsuite::run(path, WriteConflicts::Detect).await;
+//! }
+//! ```
+use crate::dbs::node::Timestamp;
+use crate::dbs::Session;
+use crate::kvs::clock::{FakeClock, SizedClock};
+use crate::kvs::Datastore;
+use crate::kvs::LockType;
+use crate::kvs::LockType::*;
+use crate::kvs::Transaction;
+use crate::kvs::TransactionType;
+use crate::kvs::TransactionType::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+type ClockType = Arc<SizedClock>;
+
+type PathFn = Arc<dyn Fn() -> String + Send + Sync>;
+
+tokio::task_local! {
+	// The function which returns the path of each new datastore
+	static PATH: PathFn;
+}
+
+/// How a storage engine handles concurrent writes to the same key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WriteConflicts {
+	/// Only the first transaction to commit succeeds
+	Detect,
+	/// Every transaction commits, and the last commit wins
+	LastWriteWins,
+}
+
+/// Run every test in the suite, opening a new datastore at a new path for each
+pub async fn run<F>(path: F, conflicts: WriteConflicts)
+where
+	F: Fn() -> String + Send + Sync + 'static,
+{
+	PATH.scope(Arc::new(path), async {
+		initialise().await;
+		exists().await;
+		get().await;
+		set().await;
+		put().await;
+		put_prefetched().await;
+		putc().await;
+		del().await;
+		delc().await;
+		keys().await;
+		scan().await;
+		batch().await;
+		snapshot().await;
+		multireader().await;
+		multiwriter_different_keys().await;
+		match conflicts {
+			WriteConflicts::Detect => multiwriter_same_keys_conflict().await,
+			WriteConflicts::LastWriteWins => multiwriter_same_keys_allow().await,
+		}
+		timestamp_to_versionstamp().await;
+		writing_ts_again_results_in_following_ts().await;
+	})
+	.await
+}
+
+async fn new_ds(id: Uuid, clock: ClockType) -> (Datastore, ()) {
+	// Fetch the path for the new datastore
+	let path = PATH.with(|path| path());
+	// Setup the datastore
+	let ds = Datastore::new_with_clock(&path, Some(clock)).await.unwrap().with_node_id(id);
+	// Return the datastore
+	(ds, ())
+}
+
+async fn new_tx(write: TransactionType, lock: LockType) -> Transaction {
+	let nodeid = Uuid::new_v4();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	new_ds(nodeid, clock).await.0.transaction(write, lock).await.unwrap()
+}
+
+include!("tests/raw.rs");
+include!("tests/snapshot.rs");
+include!("tests/multireader.rs");
+include!("tests/multiwriter_different_keys.rs");
+include!("tests/multiwriter_same_keys_allow.rs");
+include!("tests/multiwriter_same_keys_conflict.rs");
+include!("tests/timestamp_to_versionstamp.rs");
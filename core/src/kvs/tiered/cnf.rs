@@ -0,0 +1,7 @@
+use std::sync::LazyLock;
+
+pub static TIERED_CACHE_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_TIERED_CACHE_SIZE", u64, 256 * 1024 * 1024);
+
+pub static TIERED_CACHE_MISSING: LazyLock<bool> =
+	lazy_env_parse!("SURREAL_TIERED_CACHE_MISSING", bool, true);
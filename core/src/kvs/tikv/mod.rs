@@ -4,6 +4,7 @@ use crate::err::Error;
 use crate::key::debug::Sprintable;
 use crate::kvs::savepoint::{SaveOperation, SavePointImpl, SavePoints, SavePrepare};
 use crate::kvs::Check;
+use crate::kvs::Consistency;
 use crate::kvs::Key;
 use crate::kvs::Val;
 use crate::kvs::Version;
@@ -30,7 +31,7 @@ pub struct Transaction {
 	/// Should we check unhandled transactions?
 	check: Check,
 	/// The underlying datastore transaction
-	inner: tikv::Transaction,
+	inner: Inner,
 	// The above, supposedly 'static transaction
 	// actually points here, so we need to ensure
 	// the memory is kept alive. This pointer must
@@ -40,6 +41,56 @@ pub struct Transaction {
 	save_points: SavePoints,
 }
 
+/// The underlying transaction, or a read-only snapshot
+enum Inner {
+	/// A transaction which reads the latest committed data
+	Transaction(tikv::Transaction),
+	/// A read-only snapshot of the data at an earlier timestamp
+	Snapshot(tikv::Snapshot),
+}
+
+impl Inner {
+	/// Get the underlying transaction, if this is not a snapshot
+	fn transaction(&mut self) -> Result<&mut tikv::Transaction, Error> {
+		match self {
+			Inner::Transaction(tx) => Ok(tx),
+			Inner::Snapshot(_) => Err(Error::TxReadonly),
+		}
+	}
+	/// Check if a key exists
+	async fn key_exists(&mut self, key: Key) -> Result<bool, tikv::Error> {
+		match self {
+			Inner::Transaction(tx) => tx.key_exists(key).await,
+			Inner::Snapshot(tx) => tx.key_exists(key).await,
+		}
+	}
+	/// Fetch a key
+	async fn get(&mut self, key: Key) -> Result<Option<Val>, tikv::Error> {
+		match self {
+			Inner::Transaction(tx) => tx.get(key).await,
+			Inner::Snapshot(tx) => tx.get(key).await,
+		}
+	}
+	/// Retrieve a range of keys
+	async fn scan_keys(&mut self, rng: Range<Key>, limit: u32) -> Result<Vec<Key>, tikv::Error> {
+		match self {
+			Inner::Transaction(tx) => Ok(tx.scan_keys(rng, limit).await?.map(Key::from).collect()),
+			Inner::Snapshot(tx) => Ok(tx.scan_keys(rng, limit).await?.map(Key::from).collect()),
+		}
+	}
+	/// Retrieve a range of key-value pairs
+	async fn scan(&mut self, rng: Range<Key>, limit: u32) -> Result<Vec<(Key, Val)>, tikv::Error> {
+		match self {
+			Inner::Transaction(tx) => {
+				Ok(tx.scan(rng, limit).await?.map(|kv| (Key::from(kv.0), kv.1)).collect())
+			}
+			Inner::Snapshot(tx) => {
+				Ok(tx.scan(rng, limit).await?.map(|kv| (Key::from(kv.0), kv.1)).collect())
+			}
+		}
+	}
+}
+
 impl Drop for Transaction {
 	fn drop(&mut self) {
 		if !self.done && self.write {
@@ -86,7 +137,12 @@ impl Datastore {
 		Ok(())
 	}
 	/// Start a new transaction
-	pub(crate) async fn transaction(&self, write: bool, lock: bool) -> Result<Transaction, Error> {
+	pub(crate) async fn transaction(
+		&self,
+		write: bool,
+		lock: bool,
+		consistency: Consistency,
+	) -> Result<Transaction, Error> {
 		// Set whether this should be an optimistic or pessimistic transaction
 		let mut opt = if lock {
 			TransactionOptions::new_pessimistic()
@@ -108,18 +164,36 @@ impl Datastore {
 		let check = Check::Warn;
 		#[cfg(debug_assertions)]
 		let check = Check::Panic;
+		// Create a new transaction or snapshot
+		let inner = match consistency {
+			// Read-only transactions can read from an earlier snapshot
+			Consistency::Stale(staleness) if !write => {
+				// Fetch the current timestamp from the placement driver
+				let now = match self.db.current_timestamp().await {
+					Ok(ts) => ts.version(),
+					Err(e) => return Err(Error::Tx(e.to_string())),
+				};
+				// The physical part of the timestamp is in milliseconds
+				let ms = u64::try_from(staleness.as_millis()).unwrap_or(u64::MAX);
+				let ver = now.saturating_sub(ms.saturating_mul(1 << 18));
+				// Read from a snapshot at the earlier timestamp
+				Inner::Snapshot(self.db.snapshot(tikv::Timestamp::from_version(ver), opt))
+			}
+			// Otherwise read the latest committed data
+			_ => match self.db.begin_with_options(opt).await {
+				Ok(inner) => Inner::Transaction(inner),
+				Err(e) => return Err(Error::Tx(e.to_string())),
+			},
+		};
 		// Create a new transaction
-		match self.db.begin_with_options(opt).await {
-			Ok(inner) => Ok(Transaction {
-				done: false,
-				check,
-				write,
-				inner,
-				db: self.db.clone(),
-				save_points: Default::default(),
-			}),
-			Err(e) => Err(Error::Tx(e.to_string())),
-		}
+		Ok(Transaction {
+			done: false,
+			check,
+			write,
+			inner,
+			db: self.db.clone(),
+			save_points: Default::default(),
+		})
 	}
 }
 
@@ -150,7 +224,7 @@ impl super::api::Transaction for Transaction {
 		self.done = true;
 		// Cancel this transaction
 		if self.write {
-			self.inner.rollback().await?;
+			self.inner.transaction()?.rollback().await?;
 		}
 		// Continue
 		Ok(())
@@ -170,8 +244,8 @@ impl super::api::Transaction for Transaction {
 		// Mark this transaction as done
 		self.done = true;
 		// Commit this transaction
-		if let Err(err) = self.inner.commit().await {
-			if let Err(inner_err) = self.inner.rollback().await {
+		if let Err(err) = self.inner.transaction()?.commit().await {
+			if let Err(inner_err) = self.inner.transaction()?.rollback().await {
 				error!("Transaction commit failed {} and rollback failed: {}", err, inner_err);
 			}
 			return Err(err.into());
@@ -248,7 +322,7 @@ impl super::api::Transaction for Transaction {
 			None
 		};
 		// Set the key
-		self.inner.put(key, val.into()).await?;
+		self.inner.transaction()?.put(key, val.into()).await?;
 		// Confirm the save point
 		if let Some(prep) = prep {
 			self.save_points.save(prep);
@@ -297,7 +371,7 @@ impl super::api::Transaction for Transaction {
 			return Err(Error::TxKeyAlreadyExists);
 		}
 		// Set the key if empty
-		self.inner.put(key, val).await?;
+		self.inner.transaction()?.put(key, val).await?;
 		// Confirm the save point
 		if let Some(prep) = prep {
 			self.save_points.save(prep);
@@ -341,8 +415,8 @@ impl super::api::Transaction for Transaction {
 		};
 		// Delete the key
 		match (current_val, chk) {
-			(Some(v), Some(w)) if v == w => self.inner.put(key, val).await?,
-			(None, None) => self.inner.put(key, val).await?,
+			(Some(v), Some(w)) if v == w => self.inner.transaction()?.put(key, val).await?,
+			(None, None) => self.inner.transaction()?.put(key, val).await?,
 			_ => return Err(Error::TxConditionNotMet),
 		};
 		// Confirm the save point
@@ -376,7 +450,7 @@ impl super::api::Transaction for Transaction {
 			None
 		};
 		// Delete the key
-		self.inner.delete(key).await?;
+		self.inner.transaction()?.delete(key).await?;
 		// Confirm the save point
 		if let Some(prep) = prep {
 			self.save_points.save(prep);
@@ -418,8 +492,8 @@ impl super::api::Transaction for Transaction {
 		};
 		// Delete the key
 		match (current_val, chk) {
-			(Some(v), Some(w)) if v == w => self.inner.delete(key).await?,
-			(None, None) => self.inner.delete(key).await?,
+			(Some(v), Some(w)) if v == w => self.inner.transaction()?.delete(key).await?,
+			(None, None) => self.inner.transaction()?.delete(key).await?,
 			_ => return Err(Error::TxConditionNotMet),
 		};
 		// Confirm the save point
@@ -477,7 +551,7 @@ impl super::api::Transaction for Transaction {
 			end: rng.end.into(),
 		};
 		// Scan the keys
-		let res = self.inner.scan_keys(rng, limit).await?.map(Key::from).collect();
+		let res = self.inner.scan_keys(rng, limit).await?;
 		// Return result
 		Ok(res)
 	}
@@ -507,7 +581,7 @@ impl super::api::Transaction for Transaction {
 			end: rng.end.into(),
 		};
 		// Scan the keys
-		let res = self.inner.scan(rng, limit).await?.map(|kv| (Key::from(kv.0), kv.1)).collect();
+		let res = self.inner.scan(rng, limit).await?;
 		// Return result
 		Ok(res)
 	}
@@ -525,7 +599,7 @@ impl super::api::Transaction for Transaction {
 		// Calculate the version key
 		let key = key.into();
 		// Get the transaction version
-		let ver = self.inner.current_timestamp().await?.version();
+		let ver = self.inner.transaction()?.current_timestamp().await?.version();
 		// Calculate the previous version value
 		if let Some(prev) = self.get(key.as_slice(), None).await? {
 			let res: Result<[u8; 10], Error> = match prev.as_slice().try_into() {
@@ -4,28 +4,34 @@ use super::Key;
 use super::Val;
 use crate::cf;
 use crate::dbs::node::Timestamp;
+use crate::dbs::Notification;
 use crate::doc::CursorValue;
 use crate::err::Error;
 use crate::idg::u32::U32;
 use crate::key::debug::Sprintable;
 use crate::kvs::batch::Batch;
+use crate::kvs::blob;
+use crate::kvs::changelog::{self, Changelog, Recorder};
 use crate::kvs::clock::SizedClock;
-#[cfg(any(
-	feature = "kv-tikv",
-	feature = "kv-fdb",
-	feature = "kv-indxdb",
-	feature = "kv-surrealcs",
-))]
+use crate::kvs::count::{Count, Counter};
+use crate::kvs::hooks::Hooks;
 use crate::kvs::savepoint::SavePointImpl;
 use crate::kvs::stash::Stash;
+use crate::kvs::usage::Tracker;
+use crate::kvs::verify::Shadow;
+#[cfg(feature = "opendal")]
+use crate::obs::dal::BlobStore;
 use crate::sql;
 use crate::sql::thing::Thing;
 use crate::vs::Versionstamp;
 use sql::statements::DefineTableStatement;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
+use trice::Instant;
 
 const TARGET: &str = "surrealdb::core::kvs::tr";
 
@@ -70,6 +76,43 @@ impl From<bool> for LockType {
 	}
 }
 
+/// Specifies the isolation level of a transaction.
+///
+/// A storage engine may provide a stronger isolation level than the
+/// one requested, but never a weaker one. Creating a transaction with
+/// an isolation level which the storage engine can not enforce fails.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Isolation {
+	/// Each read observes the latest committed data, and conflicting
+	/// writes to the same keys are detected when committing.
+	Optimistic,
+	/// All reads observe a consistent snapshot of the data, taken when the
+	/// transaction starts, and conflicting writes to the same keys are
+	/// detected when committing.
+	Snapshot,
+	/// All reads observe a consistent snapshot of the data, and any keys
+	/// which were read are also checked for concurrent modifications when
+	/// committing, preventing write skew anomalies.
+	SerializableSnapshot,
+}
+
+/// Specifies the read consistency of a read-only transaction.
+///
+/// Write transactions always observe the latest committed data, and
+/// storage engines which can not serve stale reads ignore this setting.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Consistency {
+	/// All reads observe the latest committed data.
+	#[default]
+	Strong,
+	/// All reads observe a consistent snapshot of the data as it was at
+	/// the specified duration in the past, so reads do not conflict with,
+	/// or wait for, writes which were committed more recently.
+	Stale(Duration),
+}
+
 /// A set of undoable updates and requests against a dataset.
 #[allow(dead_code)]
 #[non_exhaustive]
@@ -78,6 +121,18 @@ pub struct Transactor {
 	pub(super) stash: Stash,
 	pub(super) cf: cf::Writer,
 	pub(super) clock: Arc<SizedClock>,
+	pub(super) log: Option<Recorder>,
+	pub(super) usage: Option<Tracker>,
+	pub(super) counts: Counter,
+	pub(super) hooks: Hooks,
+	pub(super) deadline: Option<Instant>,
+	pub(super) reads: Option<HashMap<Key, Val>>,
+	pub(super) absent: HashMap<Key, usize>,
+	pub(super) save_points: usize,
+	pub(super) exceeded: Option<u64>,
+	pub(super) verify: Option<Shadow>,
+	#[cfg(feature = "opendal")]
+	pub(super) blobs: Option<Arc<BlobStore>>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -86,6 +141,8 @@ pub(super) enum Inner {
 	Mem(super::mem::Transaction),
 	#[cfg(feature = "kv-rocksdb")]
 	RocksDB(super::rocksdb::Transaction),
+	#[cfg(feature = "kv-rocksdb")]
+	RocksDBSecondary(super::rocksdb::secondary::Transaction),
 	#[cfg(feature = "kv-indxdb")]
 	IndxDB(super::indxdb::Transaction),
 	#[cfg(feature = "kv-tikv")]
@@ -96,6 +153,27 @@ pub(super) enum Inner {
 	SurrealKV(super::surrealkv::Transaction),
 	#[cfg(feature = "kv-surrealcs")]
 	SurrealCS(super::surrealcs::Transaction),
+	#[cfg(feature = "kv-sled")]
+	Sled(super::sled::Transaction),
+	#[cfg(feature = "kv-lmdb")]
+	Lmdb(super::lmdb::Transaction),
+	#[cfg(feature = "kv-sqlite")]
+	Sqlite(super::sqlite::Transaction),
+	#[cfg(feature = "kv-postgres")]
+	Postgres(super::postgres::Transaction),
+	#[cfg(feature = "kv-s3")]
+	S3(super::s3::Transaction),
+	#[cfg(feature = "kv-dynamodb")]
+	DynamoDb(super::dynamodb::Transaction),
+	#[cfg(feature = "kv-redis")]
+	Redis(super::redis::Transaction),
+	#[cfg(feature = "kv-tiered")]
+	Tiered(super::tiered::Transaction),
+	#[cfg(feature = "kv-opfs")]
+	Opfs(super::opfs::Transaction),
+	#[cfg(feature = "kv-shard")]
+	Shard(super::shard::Transaction),
+	External(super::external::Transaction),
 }
 
 impl fmt::Display for Transactor {
@@ -106,6 +184,8 @@ impl fmt::Display for Transactor {
 			Inner::Mem(_) => write!(f, "memory"),
 			#[cfg(feature = "kv-rocksdb")]
 			Inner::RocksDB(_) => write!(f, "rocksdb"),
+			#[cfg(feature = "kv-rocksdb")]
+			Inner::RocksDBSecondary(_) => write!(f, "rocksdb"),
 			#[cfg(feature = "kv-indxdb")]
 			Inner::IndxDB(_) => write!(f, "indxdb"),
 			#[cfg(feature = "kv-tikv")]
@@ -116,6 +196,27 @@ impl fmt::Display for Transactor {
 			Inner::SurrealKV(_) => write!(f, "surrealkv"),
 			#[cfg(feature = "kv-surrealcs")]
 			Inner::SurrealCS(_) => write!(f, "surrealcs"),
+			#[cfg(feature = "kv-sled")]
+			Inner::Sled(_) => write!(f, "sled"),
+			#[cfg(feature = "kv-lmdb")]
+			Inner::Lmdb(_) => write!(f, "lmdb"),
+			#[cfg(feature = "kv-sqlite")]
+			Inner::Sqlite(_) => write!(f, "sqlite"),
+			#[cfg(feature = "kv-postgres")]
+			Inner::Postgres(_) => write!(f, "postgres"),
+			#[cfg(feature = "kv-s3")]
+			Inner::S3(_) => write!(f, "s3"),
+			#[cfg(feature = "kv-dynamodb")]
+			Inner::DynamoDb(_) => write!(f, "dynamodb"),
+			#[cfg(feature = "kv-redis")]
+			Inner::Redis(_) => write!(f, "redis"),
+			#[cfg(feature = "kv-tiered")]
+			Inner::Tiered(_) => write!(f, "tiered"),
+			#[cfg(feature = "kv-opfs")]
+			Inner::Opfs(_) => write!(f, "opfs"),
+			#[cfg(feature = "kv-shard")]
+			Inner::Shard(_) => write!(f, "shard"),
+			Inner::External(_) => write!(f, "external"),
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
@@ -129,6 +230,8 @@ macro_rules! expand_inner {
 			Inner::Mem($arm) => $b,
 			#[cfg(feature = "kv-rocksdb")]
 			Inner::RocksDB($arm) => $b,
+			#[cfg(feature = "kv-rocksdb")]
+			Inner::RocksDBSecondary($arm) => $b,
 			#[cfg(feature = "kv-indxdb")]
 			Inner::IndxDB($arm) => $b,
 			#[cfg(feature = "kv-tikv")]
@@ -139,12 +242,36 @@ macro_rules! expand_inner {
 			Inner::SurrealKV($arm) => $b,
 			#[cfg(feature = "kv-surrealcs")]
 			Inner::SurrealCS($arm) => $b,
+			#[cfg(feature = "kv-sled")]
+			Inner::Sled($arm) => $b,
+			#[cfg(feature = "kv-lmdb")]
+			Inner::Lmdb($arm) => $b,
+			#[cfg(feature = "kv-sqlite")]
+			Inner::Sqlite($arm) => $b,
+			#[cfg(feature = "kv-postgres")]
+			Inner::Postgres($arm) => $b,
+			#[cfg(feature = "kv-s3")]
+			Inner::S3($arm) => $b,
+			#[cfg(feature = "kv-dynamodb")]
+			Inner::DynamoDb($arm) => $b,
+			#[cfg(feature = "kv-redis")]
+			Inner::Redis($arm) => $b,
+			#[cfg(feature = "kv-tiered")]
+			Inner::Tiered($arm) => $b,
+			#[cfg(feature = "kv-opfs")]
+			Inner::Opfs($arm) => $b,
+			#[cfg(feature = "kv-shard")]
+			Inner::Shard($arm) => $b,
+			Inner::External($arm) => $b,
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
 	};
 }
 
+#[cfg(feature = "kv-shard")]
+pub(super) use expand_inner;
+
 impl Transactor {
 	// Allow unused_variables when no storage is enabled as none of the values are used then.
 	#![cfg_attr(
@@ -155,6 +282,16 @@ impl Transactor {
 			feature = "kv-tikv",
 			feature = "kv-fdb",
 			feature = "kv-surrealkv",
+			feature = "kv-sled",
+			feature = "kv-lmdb",
+			feature = "kv-sqlite",
+			feature = "kv-postgres",
+			feature = "kv-s3",
+			feature = "kv-dynamodb",
+			feature = "kv-redis",
+			feature = "kv-tiered",
+			feature = "kv-opfs",
+			feature = "kv-shard",
 		)),
 		allow(unused_variables)
 	)]
@@ -193,6 +330,7 @@ impl Transactor {
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
 	pub async fn cancel(&mut self) -> Result<(), Error> {
 		trace!(target: TARGET, "Cancel");
+		self.hooks.clear();
 		expand_inner!(&mut self.inner, v => { v.cancel().await })
 	}
 
@@ -202,7 +340,46 @@ impl Transactor {
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
 	pub async fn commit(&mut self) -> Result<(), Error> {
 		trace!(target: TARGET, "Commit");
-		expand_inner!(&mut self.inner, v => { v.commit().await })
+		self.check_deadline().await?;
+		// Rewrite any keys which were read, so that concurrent changes conflict
+		if let Some(reads) = self.reads.take() {
+			for (k, v) in reads {
+				expand_inner!(&mut self.inner, i => { i.set(k, v, None).await })?;
+			}
+		}
+		// Write any recorded storage usage changes
+		if let Some(usage) = self.usage.as_mut() {
+			for (k, v) in usage.drain() {
+				expand_inner!(&mut self.inner, i => { i.set(k, v, None).await })?;
+			}
+		}
+		// Write any recorded row count changes
+		for (k, v) in self.counts.drain() {
+			expand_inner!(&mut self.inner, i => { i.set(k, v, None).await })?;
+		}
+		// Write any recorded mutations to the changelog
+		if let Some(rec) = self.log.as_mut().filter(|r| !r.is_empty()) {
+			// Serialize commits which write to the changelog
+			let log = rec.log();
+			let mut last = log.lock().await;
+			// Calculate the versionstamp for this commit
+			let vs = Changelog::next(&last);
+			// Write the changelog entries
+			for (k, v) in rec.drain(vs) {
+				expand_inner!(&mut self.inner, i => { i.set(k, v, None).await })?;
+			}
+			// Commit the transaction
+			expand_inner!(&mut self.inner, v => { v.commit().await })?;
+			// Store the committed versionstamp
+			*last = vs;
+			// Run the post-commit hooks
+			self.hooks.run();
+			return Ok(());
+		}
+		expand_inner!(&mut self.inner, v => { v.commit().await })?;
+		// Run the post-commit hooks
+		self.hooks.run();
+		Ok(())
 	}
 
 	/// Check if a key exists in the datastore.
@@ -213,7 +390,15 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), version = version, "Exists");
-		expand_inner!(&mut self.inner, v => { v.exists(key, version).await })
+		self.check_deadline().await?;
+		if self.reads.is_some() && version.is_none() {
+			return Ok(self.get(key, None).await?.is_some());
+		}
+		let res = expand_inner!(&mut self.inner, v => { v.exists(key.clone(), version).await })?;
+		if let (Some(shadow), None) = (self.verify.as_mut(), version) {
+			shadow.exists(&key, res);
+		}
+		Ok(res)
 	}
 
 	/// Fetch a key from the datastore.
@@ -224,7 +409,18 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), version = version, "Get");
-		expand_inner!(&mut self.inner, v => { v.get(key, version).await })
+		self.check_deadline().await?;
+		if version.is_some() || (self.reads.is_none() && self.verify.is_none()) {
+			let res = expand_inner!(&mut self.inner, v => { v.get(key, version).await })?;
+			return self.load(res).await;
+		}
+		let res = expand_inner!(&mut self.inner, v => { v.get(key.clone(), None).await })?;
+		self.observe(key.clone(), res.as_ref());
+		let val = self.load(res).await?;
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.read(&key, val.as_ref());
+		}
+		Ok(val)
 	}
 
 	/// Fetch many keys from the datastore.
@@ -235,7 +431,34 @@ impl Transactor {
 	{
 		let keys = keys.into_iter().map(Into::into).collect::<Vec<Key>>();
 		trace!(target: TARGET, keys = keys.sprint(), "GetM");
-		expand_inner!(&mut self.inner, v => { v.getm(keys).await })
+		self.check_deadline().await?;
+		let res = expand_inner!(&mut self.inner, v => { v.getm(keys).await })?;
+		let mut out = Vec::with_capacity(res.len());
+		for val in res {
+			out.push(self.load_value(val).await?);
+		}
+		Ok(out)
+	}
+
+	/// Check whether a set of keys exist in the datastore, with a single batched read.
+	///
+	/// Keys which do not exist are remembered, so that a subsequent [`put`](Self::put)
+	/// of any of these keys can be written without checking the datastore again.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
+	pub async fn prefetch<K>(&mut self, keys: Vec<K>) -> Result<(), Error>
+	where
+		K: Into<Key> + Debug,
+	{
+		let keys = keys.into_iter().map(Into::into).collect::<Vec<Key>>();
+		trace!(target: TARGET, keys = keys.sprint(), "Prefetch");
+		self.check_deadline().await?;
+		let res = expand_inner!(&mut self.inner, v => { v.getm(keys.clone()).await })?;
+		for (key, val) in keys.into_iter().zip(res) {
+			if val.is_empty() {
+				self.absent.insert(key, self.save_points);
+			}
+		}
+		Ok(())
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -254,7 +477,13 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "GetR");
-		expand_inner!(&mut self.inner, v => { v.getr(beg..end, version).await })
+		self.check_deadline().await?;
+		let res = expand_inner!(&mut self.inner, v => { v.getr(beg.clone()..end.clone(), version).await })?;
+		let res = self.load_all(res).await?;
+		if let (Some(shadow), None) = (self.verify.as_mut(), version) {
+			shadow.range(beg.as_slice()..end.as_slice(), &res, true);
+		}
+		Ok(res)
 	}
 
 	/// Retrieve a specific prefixed range of keys from the datastore.
@@ -267,7 +496,9 @@ impl Transactor {
 	{
 		let key: Key = key.into();
 		trace!(target: TARGET, key = key.sprint(), "GetP");
-		expand_inner!(&mut self.inner, v => { v.getp(key).await })
+		self.check_deadline().await?;
+		let res = expand_inner!(&mut self.inner, v => { v.getp(key).await })?;
+		self.load_all(res).await
 	}
 
 	/// Insert or update a key in the datastore.
@@ -279,7 +510,19 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), version = version, "Set");
-		expand_inner!(&mut self.inner, v => { v.set(key, val, version).await })
+		self.check_deadline().await?;
+		self.check_quota(&key)?;
+		self.unobserve(&key);
+		let val: Val = val.into();
+		if !self.tracking() {
+			let val = self.store(&key, val).await?;
+			return expand_inner!(&mut self.inner, v => { v.set(key, val, version).await });
+		}
+		let old = self.previous(&key).await?;
+		let stored = self.store(&key, val.clone()).await?;
+		expand_inner!(&mut self.inner, v => { v.set(key.clone(), stored, version).await })?;
+		self.record(key, old, Some(val));
+		Ok(())
 	}
 
 	/// Insert a key if it doesn't exist in the datastore.
@@ -291,7 +534,30 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), version = version, "Put");
-		expand_inner!(&mut self.inner, v => { v.put(key, val, version).await })
+		self.check_deadline().await?;
+		self.check_quota(&key)?;
+		// Keys which are known not to exist don't need to be checked
+		let absent = self.absent.remove(&key).is_some();
+		self.unobserve(&key);
+		let val: Val = val.into();
+		if !self.tracking() {
+			let val = self.store(&key, val).await?;
+			return match absent {
+				true => expand_inner!(&mut self.inner, v => { v.set(key, val, version).await }),
+				false => expand_inner!(&mut self.inner, v => { v.put(key, val, version).await }),
+			};
+		}
+		let stored = self.store(&key, val.clone()).await?;
+		match absent {
+			true => {
+				expand_inner!(&mut self.inner, v => { v.set(key.clone(), stored, version).await })
+			}
+			false => {
+				expand_inner!(&mut self.inner, v => { v.put(key.clone(), stored, version).await })
+			}
+		}?;
+		self.record(key, None, Some(val));
+		Ok(())
 	}
 
 	/// Update a key in the datastore if the current value matches a condition.
@@ -303,7 +569,28 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), "PutC");
-		expand_inner!(&mut self.inner, v => { v.putc(key, val, chk).await })
+		self.check_deadline().await?;
+		self.check_quota(&key)?;
+		self.unobserve(&key);
+		let val: Val = val.into();
+		let chk: Option<Val> = chk.map(Into::into);
+		// Large values are compared once they have been reassembled
+		if blob::exceeds(&val) || chk.as_ref().is_some_and(blob::exceeds) {
+			let cur = expand_inner!(&mut self.inner, v => { v.get(key.clone(), None).await })?;
+			if self.load(cur).await? != chk {
+				return Err(Error::TxConditionNotMet);
+			}
+			let stored = self.store(&key, val.clone()).await?;
+			expand_inner!(&mut self.inner, v => { v.set(key.clone(), stored, None).await })?;
+			self.record(key, chk, Some(val));
+			return Ok(());
+		}
+		if !self.tracking() {
+			return expand_inner!(&mut self.inner, v => { v.putc(key, val, chk).await });
+		}
+		expand_inner!(&mut self.inner, v => { v.putc(key.clone(), val.clone(), chk.clone()).await })?;
+		self.record(key, chk, Some(val));
+		Ok(())
 	}
 
 	/// Delete a key from the datastore.
@@ -314,7 +601,15 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), "Del");
-		expand_inner!(&mut self.inner, v => { v.del(key).await })
+		self.check_deadline().await?;
+		self.unobserve(&key);
+		if !self.tracking() {
+			return expand_inner!(&mut self.inner, v => { v.del(key).await });
+		}
+		let old = self.previous(&key).await?;
+		expand_inner!(&mut self.inner, v => { v.del(key.clone()).await })?;
+		self.record(key, old, None);
+		Ok(())
 	}
 
 	/// Delete a key from the datastore if the current value matches a condition.
@@ -326,7 +621,25 @@ impl Transactor {
 	{
 		let key = key.into();
 		trace!(target: TARGET, key = key.sprint(), "DelC");
-		expand_inner!(&mut self.inner, v => { v.delc(key, chk).await })
+		self.check_deadline().await?;
+		self.unobserve(&key);
+		let chk: Option<Val> = chk.map(Into::into);
+		// Large values are compared once they have been reassembled
+		if chk.as_ref().is_some_and(blob::exceeds) {
+			let cur = expand_inner!(&mut self.inner, v => { v.get(key.clone(), None).await })?;
+			if self.load(cur).await? != chk {
+				return Err(Error::TxConditionNotMet);
+			}
+			expand_inner!(&mut self.inner, v => { v.del(key.clone()).await })?;
+			self.record(key, chk, None);
+			return Ok(());
+		}
+		if !self.tracking() {
+			return expand_inner!(&mut self.inner, v => { v.delc(key, chk).await });
+		}
+		expand_inner!(&mut self.inner, v => { v.delc(key.clone(), chk.clone()).await })?;
+		self.record(key, chk, None);
+		Ok(())
 	}
 
 	/// Delete a range of keys from the datastore.
@@ -341,6 +654,17 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), "DelR");
+		self.check_deadline().await?;
+		if let Some(reads) = self.reads.as_mut() {
+			reads.retain(|k, _| !rng.contains(&k.as_slice()));
+		}
+		self.absent.retain(|k, _| !rng.contains(&k.as_slice()));
+		if self.tracking() {
+			let vals = expand_inner!(&mut self.inner, v => { v.getr(beg.clone()..end.clone(), None).await })?;
+			for (k, old) in self.load_all(vals).await? {
+				self.record(k, Some(old), None);
+			}
+		}
 		expand_inner!(&mut self.inner, v => { v.delr(beg..end).await })
 	}
 
@@ -354,6 +678,21 @@ impl Transactor {
 	{
 		let key: Key = key.into();
 		trace!(target: TARGET, key = key.sprint(), "DelP");
+		self.check_deadline().await?;
+		if let Some(reads) = self.reads.as_mut() {
+			reads.retain(|k, _| !k.starts_with(&key));
+		}
+		self.absent.retain(|k, _| !k.starts_with(&key));
+		if self.tracking() {
+			let vals = expand_inner!(&mut self.inner, v => { v.getp(key.clone()).await })?;
+			for (k, old) in self.load_all(vals).await? {
+				self.record(k, Some(old), None);
+			}
+		}
+		if let Some(usage) = self.usage.as_mut() {
+			usage.reset(key.clone());
+		}
+		self.counts.reset(key.clone());
 		expand_inner!(&mut self.inner, v => { v.delp(key).await })
 	}
 
@@ -374,6 +713,7 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), limit = limit, version = version, "Keys");
+		self.check_deadline().await?;
 		if beg > end {
 			return Ok(vec![]);
 		}
@@ -397,10 +737,16 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), limit = limit, version = version, "Scan");
+		self.check_deadline().await?;
 		if beg > end {
 			return Ok(vec![]);
 		}
-		expand_inner!(&mut self.inner, v => { v.scan(beg..end, limit, version).await })
+		let res = expand_inner!(&mut self.inner, v => { v.scan(beg.clone()..end.clone(), limit, version).await })?;
+		let res = self.load_all(res).await?;
+		if let (Some(shadow), None) = (self.verify.as_mut(), version) {
+			shadow.range(beg.as_slice()..end.as_slice(), &res, res.len() < limit as usize);
+		}
+		Ok(res)
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -421,7 +767,10 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), values = values, version = version, "Batch");
-		expand_inner!(&mut self.inner, v => { v.batch(beg..end, batch, values, version).await })
+		self.check_deadline().await?;
+		let mut res = expand_inner!(&mut self.inner, v => { v.batch(beg..end, batch, values, version).await })?;
+		res.values = self.load_all(res.values).await?;
+		Ok(res)
 	}
 
 	/// Obtain a new change timestamp for a key
@@ -434,6 +783,7 @@ impl Transactor {
 		K: Into<Key> + Debug,
 	{
 		let key = key.into();
+		self.check_deadline().await?;
 		expand_inner!(&mut self.inner, v => { v.get_timestamp(key).await })
 	}
 
@@ -452,9 +802,236 @@ impl Transactor {
 		let ts_key = ts_key.into();
 		let prefix = prefix.into();
 		let suffix = suffix.into();
+		self.check_deadline().await?;
+		self.check_quota(&ts_key)?;
 		expand_inner!(&mut self.inner, v => { v.set_versionstamp(ts_key, prefix, suffix, val).await })
 	}
 
+	// --------------------------------------------------
+	// Deadline methods
+	// --------------------------------------------------
+
+	/// Set a deadline for this transaction.
+	///
+	/// Once the deadline has passed, any further operation on this
+	/// transaction will return an [`Error::TxTimeout`] error, and the
+	/// transaction will be rolled back, releasing any held locks.
+	pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+		self.deadline = Instant::now().checked_add(timeout);
+	}
+
+	/// Check if the deadline for this transaction has passed.
+	async fn check_deadline(&mut self) -> Result<(), Error> {
+		match self.deadline {
+			Some(deadline) if deadline <= Instant::now() => {
+				// Roll back the transaction if it is still open
+				if !expand_inner!(&self.inner, v => { v.closed() }) {
+					trace!(target: TARGET, "Rolling back transaction after deadline");
+					expand_inner!(&mut self.inner, v => { v.cancel().await })?;
+				}
+				Err(Error::TxTimeout)
+			}
+			_ => Ok(()),
+		}
+	}
+
+	// --------------------------------------------------
+	// Quota methods
+	// --------------------------------------------------
+
+	/// Check if data can be written, when the storage quota has been exceeded.
+	///
+	/// Deletions are always allowed, so that space can be reclaimed, and
+	/// cluster metadata can always be written, so that this node remains
+	/// registered in the cluster while the datastore is full.
+	fn check_quota(&self, key: &[u8]) -> Result<(), Error> {
+		match self.exceeded {
+			Some(limit) if !key.starts_with(b"/!") => Err(Error::StorageQuotaExceeded {
+				limit,
+			}),
+			_ => Ok(()),
+		}
+	}
+
+	// --------------------------------------------------
+	// Blob methods
+	// --------------------------------------------------
+
+	/// Store the chunks of a large value, returning the value to store in its place.
+	async fn store(&mut self, key: &Key, val: Val) -> Result<Val, Error> {
+		if !blob::exceeds(&val) {
+			return Ok(val);
+		}
+		#[cfg(feature = "opendal")]
+		if let Some(blobs) = &self.blobs {
+			let manifest = blob::external(&val);
+			blobs.write(&blob::path(manifest.id), val).await?;
+			let owner = blob::owner(manifest.id);
+			expand_inner!(&mut self.inner, i => { i.set(owner, key.clone(), None).await })?;
+			return Ok(manifest.encode());
+		}
+		let (manifest, chunks) = blob::split(&val);
+		for (k, v) in chunks {
+			expand_inner!(&mut self.inner, i => { i.set(k, v, None).await })?;
+		}
+		let owner = blob::owner(manifest.id);
+		expand_inner!(&mut self.inner, i => { i.set(owner, key.clone(), None).await })?;
+		Ok(manifest.encode())
+	}
+
+	/// Reassemble a stored value, if it is a large value stored in chunks.
+	async fn load_value(&mut self, val: Val) -> Result<Val, Error> {
+		match blob::Manifest::decode(&val) {
+			#[cfg(feature = "opendal")]
+			Some(manifest) if manifest.external => match &self.blobs {
+				Some(blobs) => blob::check(&manifest, blobs.read(&blob::path(manifest.id)).await?),
+				None => Err(Error::BlobStore(format!(
+					"Blob {} is stored in a blob store which is not configured",
+					manifest.id
+				))),
+			},
+			#[cfg(not(feature = "opendal"))]
+			Some(manifest) if manifest.external => Err(Error::BlobStore(format!(
+				"Blob {} is stored in a blob store which is not configured",
+				manifest.id
+			))),
+			Some(manifest) => {
+				let rng = manifest.range();
+				let res = expand_inner!(&mut self.inner, i => { i.getr(rng, None).await })?;
+				blob::join(&manifest, res)
+			}
+			None => Ok(val),
+		}
+	}
+
+	/// Reassemble an optional stored value.
+	async fn load(&mut self, val: Option<Val>) -> Result<Option<Val>, Error> {
+		match val {
+			Some(val) => Ok(Some(self.load_value(val).await?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Reassemble any large values in a set of key-value pairs.
+	async fn load_all(&mut self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>, Error> {
+		if !res.iter().any(|(k, v)| !blob::internal(k) && blob::Manifest::decode(v).is_some()) {
+			return Ok(res);
+		}
+		let mut out = Vec::with_capacity(res.len());
+		for (k, v) in res {
+			match blob::internal(&k) {
+				true => out.push((k, v)),
+				false => out.push((k, self.load_value(v).await?)),
+			}
+		}
+		Ok(out)
+	}
+
+	/// Fetch the stored value of a key, without reassembling large values.
+	pub(super) async fn get_raw(&mut self, key: Key) -> Result<Option<Val>, Error> {
+		expand_inner!(&mut self.inner, v => { v.get(key, None).await })
+	}
+
+	// --------------------------------------------------
+	// Isolation methods
+	// --------------------------------------------------
+
+	/// Track a key which was read in a serializable transaction.
+	///
+	/// Storage engines without native serializable isolation only detect
+	/// conflicting writes. Rewriting the keys which were read, with their
+	/// unchanged values, when committing turns a concurrent modification
+	/// of those keys into a write conflict. Keys which do not exist can not
+	/// be tracked in this way, so range reads and missing keys are not
+	/// protected against concurrent modification.
+	fn observe(&mut self, key: Key, val: Option<&Val>) {
+		if let (Some(reads), Some(val)) = (self.reads.as_mut(), val) {
+			reads.entry(key).or_insert_with(|| val.clone());
+		}
+	}
+
+	/// Stop tracking a key which is being modified by this transaction.
+	fn unobserve(&mut self, key: &Key) {
+		if let Some(reads) = self.reads.as_mut() {
+			reads.remove(key);
+		}
+		self.absent.remove(key);
+	}
+
+	// --------------------------------------------------
+	// Hook methods
+	// --------------------------------------------------
+
+	/// Check if live query notifications are delivered when this transaction commits.
+	pub(crate) fn notifies(&self) -> bool {
+		self.hooks.notifies()
+	}
+
+	/// Record a live query notification, which is delivered once this transaction commits.
+	pub(crate) fn notify(&mut self, notification: Notification) {
+		self.hooks.notify(notification)
+	}
+
+	// --------------------------------------------------
+	// Changelog methods
+	// --------------------------------------------------
+
+	/// Check if mutations are captured for the changelog, storage usage, or verification.
+	fn tracking(&self) -> bool {
+		self.log.is_some() || self.usage.is_some() || self.verify.is_some()
+	}
+
+	/// Fetch the current value of a key, if mutations are being captured.
+	async fn previous(&mut self, key: &Key) -> Result<Option<Val>, Error> {
+		if !self.tracking() {
+			return Ok(None);
+		}
+		let res = expand_inner!(&mut self.inner, v => { v.get(key.clone(), None).await })?;
+		let val = self.load(res).await?;
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.read(key, val.as_ref());
+		}
+		Ok(val)
+	}
+
+	/// Record a mutation in the changelog, storage usage, and verification model, if enabled.
+	fn record(&mut self, key: Key, old: Option<Val>, new: Option<Val>) {
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.write(&key, new.as_ref());
+		}
+		if let Some(usage) = self.usage.as_mut() {
+			usage.record(&key, old.as_ref(), new.as_ref());
+		}
+		if let Some(log) = self.log.as_mut() {
+			log.record(key, old, new);
+		}
+	}
+
+	// --------------------------------------------------
+	// Row count methods
+	// --------------------------------------------------
+
+	/// Record a change in the number of rows in a table.
+	pub(crate) fn record_count(&mut self, ns: &str, db: &str, tb: &str, count: Count) {
+		self.counts.record(ns, db, tb, count);
+	}
+
+	/// Retrieve the number of rows in a table, if it is maintained exactly.
+	pub(crate) async fn get_count(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Option<u64>, Error> {
+		let beg = crate::key::table::cn::prefix(ns, db, tb);
+		let end = crate::key::table::cn::suffix(ns, db, tb);
+		let res = self.getr(beg..end, None).await?;
+		let total = res
+			.into_iter()
+			.fold(self.counts.pending(ns, db, tb), |acc, (_, v)| acc + Count::from(v));
+		Ok(total.exact())
+	}
+
 	// --------------------------------------------------
 	// Additional methods
 	// --------------------------------------------------
@@ -597,10 +1174,16 @@ impl Transactor {
 	//
 	// Lastly, you should set lock=true if you want the changefeed to be correctly ordered for
 	// non-FDB backends.
+	//
+	// When the changelog is enabled, the changefeed entries are instead versioned with the
+	// changelog commit versionstamp, so that they are ordered by commit without conflicting.
 	pub(crate) async fn complete_changes(&mut self, _lock: bool) -> Result<(), Error> {
 		let changes = self.cf.get();
 		for (tskey, prefix, suffix, v) in changes {
-			self.set_versionstamped(tskey, prefix, suffix, v).await?
+			match self.log.as_mut() {
+				Some(log) => log.change(prefix, suffix, v),
+				None => self.set_versionstamped(tskey, prefix, suffix, v).await?,
+			}
 		}
 		Ok(())
 	}
@@ -613,6 +1196,10 @@ impl Transactor {
 		ns: &str,
 		db: &str,
 	) -> Result<Versionstamp, Error> {
+		// The changelog versionstamps are derived from the commit time
+		if self.log.is_some() {
+			return Ok(changelog::at(ts));
+		}
 		// This also works as an advisory lock on the ts keys so that there is
 		// on other concurrent transactions that can write to the ts_key or the keys after it.
 		let key = crate::key::database::vs::new(ns, db);
@@ -653,12 +1240,41 @@ impl Transactor {
 		Ok(vst)
 	}
 
+	// get_versionstamp_lower_bound returns a versionstamp which the changes in this transaction,
+	// and in any transaction which commits afterwards, are versioned at or after. Reading the
+	// change feed from this versionstamp may also return changes from concurrent transactions.
+	pub(crate) async fn get_versionstamp_lower_bound(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<u64, Error> {
+		// The changelog versionstamps are derived from the commit time
+		if self.log.is_some() {
+			return Ok(Changelog::now());
+		}
+		// The next commit is versioned after the latest versionstamp
+		let key = crate::key::database::vs::new(ns, db);
+		match self.get(key, None).await? {
+			Some(v) => {
+				let vs: Versionstamp = v.as_slice().try_into().map_err(|_| {
+					Error::Internal("Invalid versionstamp for the database".to_string())
+				})?;
+				Ok(crate::vs::conv::versionstamp_to_u64(&vs).saturating_add(1))
+			}
+			None => Ok(1),
+		}
+	}
+
 	pub(crate) async fn get_versionstamp_from_timestamp(
 		&mut self,
 		ts: u64,
 		ns: &str,
 		db: &str,
 	) -> Result<Option<Versionstamp>, Error> {
+		// The changelog versionstamps are derived from the commit time
+		if self.log.is_some() {
+			return Ok(Some(changelog::at(ts)));
+		}
 		let start = crate::key::database::ts::prefix(ns, db);
 		let ts_key = crate::key::database::ts::new(ns, db, ts + 1);
 		let end = ts_key.encode()?;
@@ -677,14 +1293,52 @@ impl Transactor {
 	}
 
 	pub(crate) async fn new_save_point(&mut self) {
+		self.save_points += 1;
+		if let Some(log) = self.log.as_mut() {
+			log.new_save_point();
+		}
+		if let Some(usage) = self.usage.as_mut() {
+			usage.new_save_point();
+		}
+		self.counts.new_save_point();
+		self.hooks.new_save_point();
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.new_save_point();
+		}
 		expand_inner!(&mut self.inner, v => { v.new_save_point() })
 	}
 
 	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		// Keys which were checked after the save point may exist again once it is rolled back
+		self.absent.retain(|_, d| *d < self.save_points);
+		self.save_points = self.save_points.saturating_sub(1);
+		if let Some(log) = self.log.as_mut() {
+			log.rollback_to_save_point();
+		}
+		if let Some(usage) = self.usage.as_mut() {
+			usage.rollback_to_save_point();
+		}
+		self.counts.rollback_to_save_point();
+		self.hooks.rollback_to_save_point();
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.rollback_to_save_point();
+		}
 		expand_inner!(&mut self.inner, v => { v.rollback_to_save_point().await })
 	}
 
 	pub(crate) async fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.save_points = self.save_points.saturating_sub(1);
+		if let Some(log) = self.log.as_mut() {
+			log.release_last_save_point();
+		}
+		if let Some(usage) = self.usage.as_mut() {
+			usage.release_last_save_point();
+		}
+		self.counts.release_last_save_point();
+		self.hooks.release_last_save_point();
+		if let Some(shadow) = self.verify.as_mut() {
+			shadow.release_last_save_point();
+		}
 		expand_inner!(&mut self.inner, v => { v.release_last_save_point() })
 	}
 
@@ -700,6 +1354,7 @@ impl Transactor {
 		let end: Key = rng.end.into();
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), "BatchVersions");
+		self.check_deadline().await?;
 		expand_inner!(&mut self.inner, v => { v.batch_versions(beg..end, batch).await })
 	}
 }
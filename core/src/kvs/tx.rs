@@ -6,11 +6,18 @@ use super::Val;
 use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::cnf::TRANSACTION_CACHE_SIZE;
 use crate::dbs::node::Node;
+use crate::dbs::Notification;
 use crate::err::Error;
 use crate::kvs::cache::Entry;
 use crate::kvs::cache::EntryWeighter;
+use crate::kvs::cache::PermissionCache;
+use crate::kvs::changelog;
 use crate::kvs::scanner::Scanner;
+use crate::kvs::Count;
+use crate::kvs::LiveFilter;
+use crate::kvs::Mutation;
 use crate::kvs::Transactor;
+use crate::kvs::Usage;
 use crate::sql::statements::define::DefineConfigStatement;
 use crate::sql::statements::AccessGrant;
 use crate::sql::statements::DefineAccessStatement;
@@ -23,7 +30,10 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::statements::DefineSinkStatement;
 use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::DefineTypeStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
 use crate::sql::Id;
@@ -36,6 +46,7 @@ use quick_cache::sync::Cache;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[non_exhaustive]
@@ -44,6 +55,8 @@ pub struct Transaction {
 	tx: Mutex<Transactor>,
 	/// The query cache for this store
 	cache: Cache<Key, Entry, EntryWeighter>,
+	/// The resolved permission clauses, cleared on any write
+	perms: PermissionCache,
 }
 
 impl Transaction {
@@ -56,6 +69,7 @@ impl Transaction {
 				*TRANSACTION_CACHE_SIZE as u64,
 				EntryWeighter,
 			),
+			perms: PermissionCache::default(),
 		}
 	}
 
@@ -64,6 +78,12 @@ impl Transaction {
 		self.tx.into_inner()
 	}
 
+	/// Set a deadline after which this transaction is rolled back
+	pub fn with_timeout(mut self, timeout: Duration) -> Transaction {
+		self.tx.get_mut().set_timeout(timeout);
+		self
+	}
+
 	/// Enclose this transaction in an [`Arc`]
 	pub fn enclose(self) -> Arc<Transaction> {
 		Arc::new(self)
@@ -100,6 +120,16 @@ impl Transaction {
 		self.lock().await.commit().await
 	}
 
+	/// Check if live query notifications are delivered when this transaction commits.
+	pub(crate) async fn notifies(&self) -> bool {
+		self.lock().await.notifies()
+	}
+
+	/// Record a live query notification, which is delivered once this transaction commits.
+	pub(crate) async fn notify(&self, notification: Notification) {
+		self.lock().await.notify(notification)
+	}
+
 	/// Check if a key exists in the datastore.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
 	pub async fn exists<K>(&self, key: K, version: Option<u64>) -> Result<bool, Error>
@@ -159,6 +189,7 @@ impl Transaction {
 	where
 		K: Into<Key> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.del(key).await
 	}
 
@@ -169,6 +200,7 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.delc(key, chk).await
 	}
 
@@ -180,6 +212,7 @@ impl Transaction {
 	where
 		K: Into<Key> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.delr(rng).await
 	}
 
@@ -191,6 +224,7 @@ impl Transaction {
 	where
 		K: Into<Key> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.delp(key).await
 	}
 
@@ -201,6 +235,7 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.set(key, val, version).await
 	}
 
@@ -211,6 +246,7 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.put(key, val, version).await
 	}
 
@@ -221,9 +257,19 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		self.perms.clear();
 		self.lock().await.putc(key, val, chk).await
 	}
 
+	/// Check whether a set of keys exist in the datastore, with a single batched read.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn prefetch<K>(&self, keys: Vec<K>) -> Result<(), Error>
+	where
+		K: Into<Key> + Debug,
+	{
+		self.lock().await.prefetch(keys).await
+	}
+
 	/// Retrieve a specific range of keys from the datastore.
 	///
 	/// This function fetches the full range of keys, in a single request to the underlying datastore.
@@ -312,6 +358,41 @@ impl Transaction {
 		)
 	}
 
+	/// Retrieve the changelog entries committed at, or after, a versionstamp.
+	///
+	/// This function is only populated for embedded storage engines with the changelog enabled.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn changelog(&self, since: u64, limit: u32) -> Result<Vec<(u64, Mutation)>, Error> {
+		let rng = changelog::since(crate::vs::u64_to_versionstamp(since));
+		let res = self.scan(rng, limit, None).await?;
+		res.into_iter().map(|(k, v)| changelog::entry(&k, v)).collect()
+	}
+
+	/// Retrieve the approximate storage usage of a database.
+	///
+	/// This function is only populated when storage usage tracking is enabled.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_usage(&self, ns: &str, db: &str) -> Result<Usage, Error> {
+		let beg = crate::key::database::sz::prefix(ns, db);
+		let end = crate::key::database::sz::suffix(ns, db);
+		let res = self.getr(beg..end, None).await?;
+		Ok(res.into_iter().fold(Usage::default(), |acc, (_, v)| acc + Usage::from(v)))
+	}
+
+	/// Record a change in the number of rows in a table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn record_count(&self, ns: &str, db: &str, tb: &str, count: Count) {
+		self.lock().await.record_count(ns, db, tb, count)
+	}
+
+	/// Retrieve the number of rows in a table.
+	///
+	/// This function returns `None` if the table was defined before row counts were maintained.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_count(&self, ns: &str, db: &str, tb: &str) -> Result<Option<u64>, Error> {
+		self.lock().await.get_count(ns, db, tb).await
+	}
+
 	// --------------------------------------------------
 	// Rollback methods
 	// --------------------------------------------------
@@ -376,6 +457,25 @@ impl Transaction {
 		.try_into_rus()
 	}
 
+	/// Retrieve all ROOT level roles in a datastore.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_root_roles(&self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::root::rl::prefix();
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::root::rl::suffix();
+				let val = self.getr(key..end, None).await?;
+				let val = val.convert().into();
+				let val = Entry::Rrs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_rrs()
+	}
+
 	/// Retrieve all ROOT level accesses in a datastore.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_root_accesses(&self) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -452,6 +552,25 @@ impl Transaction {
 		.try_into_nus()
 	}
 
+	/// Retrieve all namespace role definitions for a specific namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_ns_roles(&self, ns: &str) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::namespace::rl::prefix(ns);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::namespace::rl::suffix(ns);
+				let val = self.getr(key..end, None).await?;
+				let val = val.convert().into();
+				let val = Entry::Nrs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_nrs()
+	}
+
 	/// Retrieve all namespace access definitions for a specific namespace.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_ns_accesses(&self, ns: &str) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -536,6 +655,29 @@ impl Transaction {
 		.try_into_dus()
 	}
 
+	/// Retrieve all database role definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_roles(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::database::rl::prefix(ns, db);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::database::rl::suffix(ns, db);
+				let val = self.getr(key..end, None).await?;
+				let val = val.convert().into();
+				let val = Entry::Drs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_drs()
+	}
+
 	/// Retrieve all database access definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_accesses(
@@ -652,6 +794,29 @@ impl Transaction {
 		.try_into_pas()
 	}
 
+	/// Retrieve all type definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_types(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineTypeStatement]>, Error> {
+		let key = crate::key::database::ty::prefix(ns, db);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::database::ty::suffix(ns, db);
+				let val = self.getr(key..end, None).await?;
+				let val = val.convert().into();
+				let val = Entry::Tys(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_tys()
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_models(
@@ -843,6 +1008,53 @@ impl Transaction {
 		.try_into_lvs()
 	}
 
+	/// Retrieve all sink definitions for a specific table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_tb_sinks(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefineSinkStatement]>, Error> {
+		let key = crate::key::table::sk::prefix(ns, db, tb);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::table::sk::suffix(ns, db, tb);
+				let val = self.getr(key..end, None).await?;
+				let val = val.convert().into();
+				let val = Entry::Sks(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_sks()
+	}
+
+	/// Retrieve the compiled WHERE clauses of all live queries for a specific table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub(crate) async fn all_tb_lives_filter(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<LiveFilter>, Error> {
+		// The filter is cached under the end of the live query range
+		let key = crate::key::table::lq::suffix(ns, db, tb);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let lvs = self.all_tb_lives(ns, db, tb).await?;
+				let val = Entry::Any(Arc::new(LiveFilter::new(lvs)));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific node in the cluster.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_node(&self, id: Uuid) -> Result<Arc<Node>, Error> {
@@ -883,6 +1095,26 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a specific root role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_root_role(&self, rl: &str) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::root::rl::new(rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleRootNotFound {
+					value: rl.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific root access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_root_access(&self, ra: &str) -> Result<Arc<DefineAccessStatement>, Error> {
@@ -970,6 +1202,27 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a specific namespace role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_ns_role(&self, ns: &str, rl: &str) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::namespace::rl::new(ns, rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleNsNotFound {
+					value: rl.to_owned(),
+					ns: ns.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific namespace access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_ns_access(
@@ -1070,6 +1323,33 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a specific role definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_role(
+		&self,
+		ns: &str,
+		db: &str,
+		rl: &str,
+	) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::database::rl::new(ns, db, rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleDbNotFound {
+					value: rl.to_owned(),
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific database access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_access(
@@ -1228,6 +1508,31 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a specific type definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_type(
+		&self,
+		ns: &str,
+		db: &str,
+		ty: &str,
+	) -> Result<Arc<DefineTypeStatement>, Error> {
+		let key = crate::key::database::ty::new(ns, db, ty).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::TyNotFound {
+					value: ty.to_owned(),
+				})?;
+				let val: DefineTypeStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific config definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_config(
@@ -1304,6 +1609,32 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a sink for a table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_tb_sink(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		sk: &str,
+	) -> Result<Arc<DefineSinkStatement>, Error> {
+		let key = crate::key::table::sk::new(ns, db, tb, sk).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::SkNotFound {
+					value: sk.to_owned(),
+				})?;
+				let val: DefineSinkStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a field for a table.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_tb_field(
@@ -1510,7 +1841,31 @@ impl Transaction {
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	#[inline(always)]
 	pub fn clear(&self) {
-		self.cache.clear()
+		self.cache.clear();
+		self.perms.clear();
+	}
+
+	/// Fetch a permission clause which has been resolved for a session.
+	pub(crate) fn get_resolved_permission(
+		&self,
+		ns: &str,
+		db: &str,
+		session: &Value,
+		expr: &Value,
+	) -> Option<Arc<Value>> {
+		self.perms.get(ns, db, session, expr)
+	}
+
+	/// Store a permission clause which has been resolved for a session.
+	pub(crate) fn set_resolved_permission(
+		&self,
+		ns: &str,
+		db: &str,
+		session: &Value,
+		expr: &Value,
+		val: Arc<Value>,
+	) {
+		self.perms.insert(ns, db, session, expr, val)
 	}
 
 	// --------------------------------------------------
@@ -1676,6 +2031,7 @@ impl Transaction {
 						};
 						let val = {
 							self.put(&key, &val, None).await?;
+							self.record_count(ns, db, tb, Count::base()).await;
 							Entry::Any(Arc::new(val))
 						};
 						let _ = cache.insert(val.clone());
@@ -0,0 +1,241 @@
+//! Approximate storage usage metrics for namespaces and databases.
+//!
+//! When storage usage tracking is enabled, every mutation in a transaction
+//! is attributed to the database which contains the modified key, and the
+//! change in the number of keys and bytes for each database is written to
+//! a dedicated usage keyspace when the transaction commits. Each commit
+//! writes its changes to a unique key, so that concurrent transactions
+//! never conflict on the usage counters. The changes for each database are
+//! periodically merged into a single entry during garbage collection.
+//!
+//! The usage keys are structured as follows:
+//!
+//! ```text
+//! /*{ns}*{db}!sz{id}
+//! ```
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::Value;
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Add;
+use uuid::Uuid;
+
+/// The approximate storage used by a database
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub struct Usage {
+	/// The number of keys stored
+	pub keys: i64,
+	/// The number of bytes stored, including keys and values
+	pub bytes: i64,
+}
+
+impl Usage {
+	/// Check if this usage records no change
+	fn is_zero(&self) -> bool {
+		self.keys == 0 && self.bytes == 0
+	}
+}
+
+impl Add for Usage {
+	type Output = Usage;
+	fn add(self, rhs: Usage) -> Self::Output {
+		Usage {
+			keys: self.keys.saturating_add(rhs.keys),
+			bytes: self.bytes.saturating_add(rhs.bytes),
+		}
+	}
+}
+
+impl InfoStructure for Usage {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"bytes".to_string() => self.bytes.into(),
+			"keys".to_string() => self.keys.into(),
+		})
+	}
+}
+
+/// Extract the namespace and database which contain a key
+fn database(key: &[u8]) -> Option<(&str, &str)> {
+	let rest = key.strip_prefix(b"/*")?;
+	let end = rest.iter().position(|&c| c == 0)?;
+	let (ns, rest) = (&rest[..end], rest[end + 1..].strip_prefix(b"*")?);
+	let end = rest.iter().position(|&c| c == 0)?;
+	let (db, rest) = (&rest[..end], &rest[end + 1..]);
+	// Changes to the usage counters are not tracked
+	if rest.starts_with(b"!sz") {
+		return None;
+	}
+	Some((std::str::from_utf8(ns).ok()?, std::str::from_utf8(db).ok()?))
+}
+
+/// A storage usage change made within a transaction
+enum Change {
+	/// A change in the usage of a database
+	Delta(String, String, Usage),
+	/// All data, including the usage counters, under a prefix was deleted
+	Reset(Key),
+}
+
+/// Tracks the storage usage changes made within a single transaction
+#[derive(Default)]
+pub(super) struct Tracker {
+	/// The changes made in this transaction, in order
+	changes: Vec<Change>,
+	/// The number of changes recorded at each save point
+	save_points: Vec<usize>,
+}
+
+impl Tracker {
+	/// Record a mutation of a key
+	pub(super) fn record(&mut self, key: &[u8], old: Option<&Val>, new: Option<&Val>) {
+		let Some((ns, db)) = database(key) else {
+			return;
+		};
+		let size = |v: Option<&Val>| v.map_or(0, |v| (key.len() + v.len()) as i64);
+		let usage = Usage {
+			keys: new.is_some() as i64 - old.is_some() as i64,
+			bytes: size(new) - size(old),
+		};
+		if !usage.is_zero() {
+			self.changes.push(Change::Delta(ns.to_owned(), db.to_owned(), usage));
+		}
+	}
+	/// Record the deletion of all keys under a prefix
+	pub(super) fn reset(&mut self, prefix: Key) {
+		self.changes.push(Change::Reset(prefix));
+	}
+	/// Mark the current position, so that later changes can be discarded
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.changes.len());
+	}
+	/// Discard any changes recorded since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(len) = self.save_points.pop() {
+			self.changes.truncate(len);
+		}
+	}
+	/// Keep any changes recorded since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+	/// Take the usage entries to write for this transaction
+	pub(super) fn drain(&mut self) -> Vec<(Key, Val)> {
+		let mut totals: BTreeMap<(String, String), Usage> = BTreeMap::new();
+		for change in std::mem::take(&mut self.changes) {
+			match change {
+				Change::Delta(ns, db, usage) => {
+					let total = totals.entry((ns, db)).or_default();
+					*total = *total + usage;
+				}
+				Change::Reset(prefix) => {
+					totals.retain(|(ns, db), _| {
+						!crate::key::database::sz::prefix(ns, db).starts_with(&prefix)
+					});
+				}
+			}
+		}
+		totals
+			.into_iter()
+			.filter(|(_, usage)| !usage.is_zero())
+			.map(|((ns, db), usage)| {
+				(crate::key::database::sz::new(&ns, &db, Uuid::now_v7()).into(), usage.into())
+			})
+			.collect()
+	}
+}
+
+impl Datastore {
+	/// Merges all recorded storage usage changes for a database into a single entry.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn usage_compact(&self, ns: &str, db: &str) -> Result<(), Error> {
+		// Create a new transaction
+		let txn = self.internal_transaction(Write, Optimistic).await?;
+		// Fetch all usage changes for this database
+		let beg = crate::key::database::sz::prefix(ns, db);
+		let end = crate::key::database::sz::suffix(ns, db);
+		let res = catch!(txn, txn.getr(beg.clone()..end.clone(), None).await);
+		// Check if there is anything to merge
+		if res.len() < 2 {
+			return txn.cancel().await;
+		}
+		// Replace the changes with their total
+		let total = res.into_iter().fold(Usage::default(), |acc, (_, v)| acc + Usage::from(v));
+		catch!(txn, txn.delr(beg..end).await);
+		let key = crate::key::database::sz::new(ns, db, Uuid::now_v7());
+		catch!(txn, txn.set(key, total, None).await);
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn database_of_key() {
+		assert_eq!(database(b"/*ns\0*db\0*tb\0*\0\0\0\x01"), Some(("ns", "db")));
+		assert_eq!(database(b"/*ns\0*db\0!tbperson\0"), Some(("ns", "db")));
+		assert_eq!(database(b"/*ns\0!dbdb\0"), None);
+		assert_eq!(database(b"/!nsns\0"), None);
+		assert_eq!(database(&crate::key::database::sz::prefix("ns", "db")), None);
+	}
+
+	#[test]
+	fn tracker() {
+		let mut t = Tracker::default();
+		let val = vec![0u8; 10];
+		t.record(b"/*ns\0*a\0k", None, Some(&val));
+		t.record(b"/*ns\0*b\0k", None, Some(&val));
+		t.new_save_point();
+		t.record(b"/*ns\0*a\0j", None, Some(&val));
+		t.rollback_to_save_point();
+		t.reset(b"/*ns\0*b\0".to_vec());
+		let res = t.drain();
+		assert_eq!(res.len(), 1);
+		let usage: Usage = res[0].1.clone().into();
+		assert_eq!(
+			usage,
+			Usage {
+				keys: 1,
+				bytes: 19,
+			}
+		);
+	}
+
+	#[cfg(feature = "kv-mem")]
+	#[tokio::test]
+	async fn info_for_ns() {
+		use crate::dbs::Session;
+		use crate::kvs::{DatastoreBuilder, Engine};
+		use crate::sql::Part;
+		let ds =
+			DatastoreBuilder::new(Engine::Memory).with_storage_usage(true).build().await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "CREATE person:one SET name = 'Tobie'; CREATE person:two SET name = 'Jaime'";
+		ds.execute(sql, &ses, None).await.unwrap();
+		// Merge the usage changes from each transaction
+		ds.garbage_collect().await.unwrap();
+		let mut res = ds.execute("INFO FOR NS", &ses, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		let keys = val.pick(&[Part::from("usage"), Part::from("databases"), Part::from("test")]);
+		let keys = keys.pick(&[Part::from("keys")]);
+		assert!(keys.is_positive());
+		// Removing the database removes its usage
+		ds.execute("REMOVE DATABASE test", &ses, None).await.unwrap();
+		let mut res = ds.execute("INFO FOR NS", &ses, None).await.unwrap();
+		let val = res.remove(0).result.unwrap();
+		assert!(val.pick(&[Part::from("usage")]).is_none());
+	}
+}
@@ -0,0 +1,163 @@
+//! Verification of transaction semantics against an in-memory model.
+//!
+//! When the `kv-verify` feature is enabled, every transaction keeps a
+//! shadow model of the keys which it has written, and of the keys which
+//! it has read. Each read from the storage engine is then checked against
+//! the model, so that a read which does not reflect a prior write in the
+//! same transaction, or a repeated read which does not return the same
+//! value from a consistent snapshot, is detected as soon as it happens.
+//! This is intended for testing storage engine implementations, and any
+//! violation results in a panic.
+use crate::key::debug::Sprintable;
+use crate::kvs::{Key, Val};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Bound, Range};
+
+/// A shadow model of the reads and writes within a single transaction
+pub(super) struct Shadow {
+	/// Whether reads must be consistent with a snapshot
+	repeatable: bool,
+	/// The latest value written to each key in this transaction
+	writes: BTreeMap<Key, Option<Val>>,
+	/// The value first read for each key not written in this transaction
+	reads: HashMap<Key, Option<Val>>,
+	/// The writes made at each save point
+	save_points: Vec<BTreeMap<Key, Option<Val>>>,
+}
+
+impl Shadow {
+	pub(super) fn new(repeatable: bool) -> Self {
+		Self {
+			repeatable,
+			writes: BTreeMap::new(),
+			reads: HashMap::new(),
+			save_points: Vec::new(),
+		}
+	}
+	/// Record that a key was set to a value, or deleted
+	pub(super) fn write(&mut self, key: &[u8], val: Option<&Val>) {
+		self.writes.insert(key.to_vec(), val.cloned());
+	}
+	/// Check the value read for a key
+	pub(super) fn read(&mut self, key: &[u8], val: Option<&Val>) {
+		// A key written in this transaction must return the written value
+		if let Some(written) = self.writes.get(key) {
+			assert_eq!(
+				written.as_ref(),
+				val,
+				"Read of key {} does not reflect a prior write in the same transaction",
+				key.sprint()
+			);
+			return;
+		}
+		// A key read again from a snapshot must return the same value
+		if self.repeatable {
+			let first = self.reads.entry(key.to_vec()).or_insert_with(|| val.cloned());
+			assert_eq!(
+				first.as_ref(),
+				val,
+				"Repeated read of key {} returned a different value within the same transaction",
+				key.sprint()
+			);
+		}
+	}
+	/// Check whether a key was found to exist
+	pub(super) fn exists(&mut self, key: &[u8], exists: bool) {
+		let expected = match self.writes.get(key) {
+			Some(written) => written.is_some(),
+			None => match self.reads.get(key) {
+				Some(first) if self.repeatable => first.is_some(),
+				_ => return,
+			},
+		};
+		assert_eq!(
+			expected,
+			exists,
+			"Existence of key {} is inconsistent with the same transaction",
+			key.sprint()
+		);
+	}
+	/// Check the key-value pairs read from a range of keys
+	pub(super) fn range(&mut self, rng: Range<&[u8]>, res: &[(Key, Val)], complete: bool) {
+		for (k, v) in res {
+			self.read(k, Some(v));
+		}
+		// A complete range must contain every key written in this transaction
+		if complete {
+			let rng = (Bound::Included(rng.start), Bound::Excluded(rng.end));
+			for (k, v) in self.writes.range::<[u8], _>(rng) {
+				if v.is_some() {
+					assert!(
+						res.iter().any(|(r, _)| r == k),
+						"Range read is missing key {} which was written in the same transaction",
+						k.sprint()
+					);
+				}
+			}
+		}
+	}
+	/// Mark the current position, so that later writes can be discarded
+	pub(super) fn new_save_point(&mut self) {
+		self.save_points.push(self.writes.clone());
+	}
+	/// Discard any writes made since the last save point
+	pub(super) fn rollback_to_save_point(&mut self) {
+		if let Some(writes) = self.save_points.pop() {
+			self.writes = writes;
+		}
+	}
+	/// Keep any writes made since the last save point
+	pub(super) fn release_last_save_point(&mut self) {
+		self.save_points.pop();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reads_reflect_writes() {
+		let mut s = Shadow::new(true);
+		s.write(b"a", Some(&b"1".to_vec()));
+		s.write(b"b", None);
+		s.read(b"a", Some(&b"1".to_vec()));
+		s.read(b"b", None);
+		s.exists(b"a", true);
+		s.range(b"a".as_slice()..b"z".as_slice(), &[(b"a".to_vec(), b"1".to_vec())], true);
+	}
+
+	#[test]
+	#[should_panic]
+	fn stale_read_is_detected() {
+		let mut s = Shadow::new(false);
+		s.write(b"a", Some(&b"1".to_vec()));
+		s.read(b"a", Some(&b"0".to_vec()));
+	}
+
+	#[test]
+	#[should_panic]
+	fn missing_range_key_is_detected() {
+		let mut s = Shadow::new(false);
+		s.write(b"a", Some(&b"1".to_vec()));
+		s.range(b"a".as_slice()..b"z".as_slice(), &[], true);
+	}
+
+	#[test]
+	#[should_panic]
+	fn non_repeatable_read_is_detected() {
+		let mut s = Shadow::new(true);
+		s.read(b"a", Some(&b"1".to_vec()));
+		s.read(b"a", Some(&b"2".to_vec()));
+	}
+
+	#[test]
+	fn save_points() {
+		let mut s = Shadow::new(true);
+		s.write(b"a", Some(&b"1".to_vec()));
+		s.new_save_point();
+		s.write(b"a", Some(&b"2".to_vec()));
+		s.rollback_to_save_point();
+		s.read(b"a", Some(&b"1".to_vec()));
+	}
+}
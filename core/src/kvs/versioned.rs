@@ -0,0 +1,299 @@
+//! Versioned key encoding for storage engines without native MVCC support.
+//!
+//! When enabled, every committed mutation is additionally recorded in a
+//! dedicated history keyspace, alongside the latest value which remains
+//! stored under its original key. Each history entry is keyed by the
+//! original key, followed by the commit timestamp, so that a read which
+//! is pinned to an earlier version can find the newest entry which was
+//! committed at, or before, the requested version.
+//!
+//! The history keys are structured as follows:
+//!
+//! ```text
+//! \xff!vk{escaped key}\x00\x00{u64::MAX - ts}
+//! ```
+//!
+//! Any `\x00` byte within the original key is escaped as `\x00\xff`, and
+//! the key is terminated with `\x00\x00`. This ensures that the ordering
+//! of the original keys is preserved, and that no encoded key is a prefix
+//! of another encoded key. The timestamp is inverted so that the newest
+//! version of a key is always the first entry in a forward range scan.
+use crate::kvs::{Key, Val, Version};
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The prefix of the versioned history keyspace
+const PREFIX: &[u8] = b"\xff!vk";
+
+/// The value marker for a live value
+const LIVE: u8 = 0x00;
+
+/// The value marker for a deleted value
+const TOMBSTONE: u8 = 0x01;
+
+/// Encode a key at a specific version into the history keyspace
+pub(super) fn encode(key: &[u8], ts: Version) -> Key {
+	let mut k = prefix(key);
+	k.extend_from_slice(&(u64::MAX - ts).to_be_bytes());
+	k
+}
+
+/// Decode a history key into the original key and its version
+pub(super) fn decode(enc: &[u8]) -> Option<(Key, Version)> {
+	// Ensure this is a history key
+	let enc = enc.strip_prefix(PREFIX)?;
+	// Ensure there is space for the terminator and timestamp
+	if enc.len() < 10 {
+		return None;
+	}
+	let (esc, ts) = enc.split_at(enc.len() - 8);
+	// Decode the inverted version timestamp
+	let ts = u64::MAX - u64::from_be_bytes(ts.try_into().ok()?);
+	// Unescape the original key
+	let esc = esc.strip_suffix(b"\x00\x00")?;
+	let mut key = Vec::with_capacity(esc.len());
+	let mut iter = esc.iter();
+	while let Some(b) = iter.next() {
+		match b {
+			0x00 => match iter.next() {
+				Some(0xff) => key.push(0x00),
+				_ => return None,
+			},
+			b => key.push(*b),
+		}
+	}
+	Some((key, ts))
+}
+
+/// The prefix under which all versions of a key are stored
+pub(super) fn prefix(key: &[u8]) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + key.len() + 10);
+	k.extend_from_slice(PREFIX);
+	for b in key {
+		match b {
+			0x00 => k.extend_from_slice(b"\x00\xff"),
+			b => k.push(*b),
+		}
+	}
+	k.extend_from_slice(b"\x00\x00");
+	k
+}
+
+/// The range containing the entire history keyspace
+pub(super) fn all() -> Range<Key> {
+	let mut end = PREFIX.to_vec();
+	end.push(0xff);
+	PREFIX.to_vec()..end
+}
+
+/// Convert a range of keys into a range over the history keyspace
+pub(super) fn range(rng: &Range<Key>) -> Range<Key> {
+	prefix(&rng.start)..prefix(&rng.end)
+}
+
+/// Encode a value, or a deletion, for storage in the history keyspace
+pub(super) fn encode_value(val: Option<&[u8]>) -> Val {
+	match val {
+		Some(v) => {
+			let mut out = Vec::with_capacity(v.len() + 1);
+			out.push(LIVE);
+			out.extend_from_slice(v);
+			out
+		}
+		None => vec![TOMBSTONE],
+	}
+}
+
+/// Decode a value from the history keyspace, returning None if deleted
+pub(super) fn decode_value(val: &[u8]) -> Option<Val> {
+	match val.split_first() {
+		Some((&LIVE, v)) => Some(v.to_vec()),
+		_ => None,
+	}
+}
+
+/// Get the current timestamp used for committing a new version
+pub(super) fn now() -> Version {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or_default()
+}
+
+/// Tracks the mutations made in a transaction, so that the
+/// corresponding history entries can be written on commit.
+#[derive(Default)]
+pub(super) struct Writes {
+	/// The latest mutation for each key, and an optional explicit version
+	entries: BTreeMap<Key, (Option<Val>, Option<Version>)>,
+}
+
+impl Writes {
+	/// Record that a key was set to a value
+	pub(super) fn set(&mut self, key: Key, val: Val, version: Option<Version>) {
+		// Changes to the history keyspace itself are not versioned
+		if !key.starts_with(PREFIX) {
+			self.entries.insert(key, (Some(val), version));
+		}
+	}
+	/// Record that a key was deleted
+	pub(super) fn del(&mut self, key: Key) {
+		// Changes to the history keyspace itself are not versioned
+		if !key.starts_with(PREFIX) {
+			self.entries.insert(key, (None, None));
+		}
+	}
+	/// Take the history entries to write, at the specified commit timestamp
+	pub(super) fn drain(&mut self, ts: Version) -> Vec<(Key, Val)> {
+		std::mem::take(&mut self.entries)
+			.into_iter()
+			.map(|(k, (v, ver))| (encode(&k, ver.unwrap_or(ts)), encode_value(v.as_deref())))
+			.collect()
+	}
+}
+
+/// Resolves which history entries are visible at a specific version.
+///
+/// Entries must be fed in history keyspace order. For each original key,
+/// the first entry with a version at or before the read version is the
+/// visible one. If that entry is a tombstone, the key is not visible.
+pub(super) struct Visibility {
+	/// The version at which the read is pinned
+	version: Version,
+	/// The last original key which was resolved
+	last: Option<Key>,
+}
+
+impl Visibility {
+	pub(super) fn new(version: Version) -> Self {
+		Self {
+			version,
+			last: None,
+		}
+	}
+	/// Process the next history entry, returning the visible key-value pair if any
+	pub(super) fn next(&mut self, enc: &[u8], val: &[u8]) -> Option<(Key, Val)> {
+		let (key, ts) = decode(enc)?;
+		// Skip versions committed after the read version
+		if ts > self.version {
+			return None;
+		}
+		// Skip older versions of a key which was already resolved
+		if self.last.as_ref() == Some(&key) {
+			return None;
+		}
+		self.last = Some(key.clone());
+		decode_value(val).map(|v| (key, v))
+	}
+}
+
+/// Determines which history entries are no longer needed.
+///
+/// Entries must be fed in history keyspace order. For each original key,
+/// all versions newer than the horizon are retained, along with the newest
+/// version at or before the horizon, so that reads pinned to any version
+/// after the horizon are unaffected. Older versions are stale, as is the
+/// newest version at or before the horizon when it records a deletion.
+pub(super) struct Pruner {
+	/// The oldest version which must remain readable
+	horizon: Version,
+	/// The last original key which was resolved at the horizon
+	last: Option<Key>,
+}
+
+impl Pruner {
+	pub(super) fn new(horizon: Version) -> Self {
+		Self {
+			horizon,
+			last: None,
+		}
+	}
+	/// Process the next history entry, returning whether it can be deleted
+	pub(super) fn stale(&mut self, enc: &[u8], val: &[u8]) -> bool {
+		let Some((key, ts)) = decode(enc) else {
+			return false;
+		};
+		// Keep versions committed after the horizon
+		if ts > self.horizon {
+			return false;
+		}
+		// Remove older versions of a key which was already resolved
+		if self.last.as_ref() == Some(&key) {
+			return true;
+		}
+		self.last = Some(key);
+		// Keep the version visible at the horizon, unless it is a deletion
+		decode_value(val).is_none()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encode_decode() {
+		let key = b"/*test\x00*test\x00*person".to_vec();
+		let enc = encode(&key, 1234);
+		let dec = decode(&enc).unwrap();
+		assert_eq!(dec, (key, 1234));
+	}
+
+	#[test]
+	fn ordering_is_preserved() {
+		let a = encode(b"a", 0);
+		let b = encode(b"a\x00", u64::MAX);
+		let c = encode(b"a\x01", 0);
+		assert!(a < b);
+		assert!(b < c);
+	}
+
+	#[test]
+	fn newest_version_first() {
+		let old = encode(b"key", 10);
+		let new = encode(b"key", 20);
+		assert!(new < old);
+		assert!(prefix(b"key") < new);
+	}
+
+	#[test]
+	fn visibility() {
+		let entries = [
+			(encode(b"a", 30), encode_value(Some(b"a3"))),
+			(encode(b"a", 20), encode_value(None)),
+			(encode(b"a", 10), encode_value(Some(b"a1"))),
+			(encode(b"b", 15), encode_value(Some(b"b1"))),
+		];
+		let visible = |version| {
+			let mut vis = Visibility::new(version);
+			entries.iter().filter_map(|(k, v)| vis.next(k, v)).collect::<Vec<_>>()
+		};
+		assert_eq!(visible(5), vec![]);
+		assert_eq!(visible(10), vec![(b"a".to_vec(), b"a1".to_vec())]);
+		assert_eq!(visible(20), vec![(b"b".to_vec(), b"b1".to_vec())]);
+		assert_eq!(
+			visible(40),
+			vec![(b"a".to_vec(), b"a3".to_vec()), (b"b".to_vec(), b"b1".to_vec())]
+		);
+	}
+
+	#[test]
+	fn pruning() {
+		let entries = [
+			(encode(b"a", 30), encode_value(Some(b"a3"))),
+			(encode(b"a", 20), encode_value(Some(b"a2"))),
+			(encode(b"a", 10), encode_value(Some(b"a1"))),
+			(encode(b"b", 15), encode_value(None)),
+			(encode(b"b", 5), encode_value(Some(b"b1"))),
+		];
+		let mut pruner = Pruner::new(25);
+		let stale = entries.iter().map(|(k, v)| pruner.stale(k, v)).collect::<Vec<_>>();
+		assert_eq!(stale, vec![false, false, true, true, true]);
+	}
+
+	#[test]
+	fn history_is_not_versioned() {
+		let mut writes = Writes::default();
+		writes.set(b"a".to_vec(), b"a1".to_vec(), None);
+		writes.del(encode(b"a", 10));
+		assert_eq!(writes.drain(20).len(), 1);
+	}
+}
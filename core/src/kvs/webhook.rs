@@ -0,0 +1,395 @@
+//! Durable queue of outgoing webhooks.
+//!
+//! The `http::webhook()` function does not send a request when it is called.
+//! The webhook is instead written to a queue in the key-value store as part of
+//! the calling transaction, so that it is only delivered if the transaction
+//! commits, and so that a slow or unavailable endpoint does not hold up the
+//! transaction. This makes it safe to call from the THEN clause of an event.
+//!
+//! The queue is processed periodically by a background task, which POSTs the
+//! body of each webhook as JSON. Each request carries the id of the webhook,
+//! which remains the same across retries so that receivers can deduplicate
+//! deliveries, and the time at which the request was sent. When a secret is
+//! given, the request is signed with an HMAC-SHA256 of the timestamp and the
+//! body, so that receivers can verify where the request came from.
+//!
+//! A webhook which fails is retried with an exponential backoff, until all
+//! attempts are exhausted. The outcome of each attempt is recorded in the
+//! delivery status table of the database from which the webhook was sent,
+//! in the record which is returned by `http::webhook()`.
+use crate::cnf::{
+	WEBHOOK_BATCH_SIZE, WEBHOOK_DELIVERY_TABLE, WEBHOOK_MAX_ATTEMPTS, WEBHOOK_RETRY_DELAY,
+};
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::key::root::wh::Wh;
+use crate::kvs::Datastore;
+use crate::kvs::Key;
+use crate::kvs::Transaction;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::UpsertStatement;
+use crate::sql::{Data, Datetime, Id, Object, Subquery, Thing, Value, Values};
+use derive::Store;
+use reblessive::TreeStack;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const TARGET: &str = "surrealdb::core::kvs::webhook";
+
+/// The name of the lock which is held while delivering webhooks
+const LOCK: &str = "webhooks";
+
+/// How long the webhook lock is held for, if it is not released
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// A webhook which is waiting to be delivered
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct QueuedWebhook {
+	/// The namespace from which the webhook was sent
+	pub ns: String,
+	/// The database from which the webhook was sent
+	pub db: String,
+	/// The url to which the webhook is delivered
+	pub url: String,
+	/// The body of the webhook, sent as JSON
+	pub body: Value,
+	/// Additional headers which are sent with the webhook
+	pub headers: Object,
+	/// The secret with which the webhook is signed
+	pub secret: Option<String>,
+	/// The time at which the webhook was queued
+	pub queued: Datetime,
+	/// The number of failed attempts to deliver the webhook
+	pub attempts: u32,
+	/// The time after which the webhook can next be delivered
+	pub retry: Datetime,
+	/// The error from the last failed attempt
+	pub error: Option<String>,
+}
+
+impl QueuedWebhook {
+	/// Add this webhook to the queue, within the transaction which sent it
+	#[cfg_attr(not(feature = "http"), allow(dead_code))]
+	pub(crate) async fn enqueue(self, txn: &Transaction) -> Result<uuid::Uuid, Error> {
+		let id = uuid::Uuid::now_v7();
+		txn.set(crate::key::root::wh::new(id), self, None).await?;
+		Ok(id)
+	}
+}
+
+impl Datastore {
+	/// Deliver any webhooks which are waiting in the queue.
+	///
+	/// This function should be run periodically at an interval.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::webhook", skip(self))]
+	pub async fn process_webhooks(&self) -> Result<(), Error> {
+		// Only one node delivers webhooks at a time
+		if !self.lock(LOCK, LOCK_TTL).await? {
+			return Ok(());
+		}
+		// Deliver the next batch of webhooks
+		let res = self.deliver_webhooks().await;
+		// Release the lock for other nodes
+		self.unlock(LOCK).await?;
+		res
+	}
+
+	/// Delivers the next batch of queued webhooks, oldest first.
+	async fn deliver_webhooks(&self) -> Result<(), Error> {
+		let now = Datetime::default();
+		// Fetch the next batch of queued webhooks
+		let beg = crate::key::root::wh::prefix();
+		let end = crate::key::root::wh::suffix();
+		let txn = self.internal_transaction(Read, Optimistic).await?;
+		let res = catch!(txn, txn.batch(beg..end, *WEBHOOK_BATCH_SIZE, true, None).await);
+		catch!(txn, txn.cancel().await);
+		// Deliver each webhook in turn
+		let mut count = 0;
+		for (key, val) in res.values {
+			let mut entry: QueuedWebhook = val.into();
+			// Skip webhooks which are waiting to be retried
+			if entry.retry > now {
+				continue;
+			}
+			let id = Wh::decode(&key)?.wh;
+			// Send the webhook, and check the response
+			let (response, err) = match self.send_webhook(id, &entry).await {
+				Ok(code) if (200..300).contains(&code) => (Some(code), None),
+				Ok(code) => {
+					(Some(code), Some(format!("The endpoint responded with status {code}")))
+				}
+				Err(e) => (None, Some(e.to_string())),
+			};
+			entry.attempts += 1;
+			let status = match err {
+				None => {
+					count += 1;
+					"delivered"
+				}
+				Some(err) => {
+					warn!(
+						target: TARGET,
+						"Failed to deliver webhook {id} to {} (attempt {}): {err}",
+						entry.url,
+						entry.attempts
+					);
+					entry.error = Some(err);
+					match entry.attempts >= *WEBHOOK_MAX_ATTEMPTS {
+						true => "failed",
+						false => {
+							let delay = WEBHOOK_RETRY_DELAY
+								.saturating_mul(1 << (entry.attempts - 1).min(16));
+							let delay = chrono::Duration::milliseconds(delay as i64);
+							entry.retry = Datetime(Datetime::default().0 + delay);
+							"retrying"
+						}
+					}
+				}
+			};
+			// Record the outcome, and update the queue
+			self.record_webhook(&key, id, &entry, status, response).await?;
+		}
+		trace!(target: TARGET, "Delivered {count} queued webhooks");
+		Ok(())
+	}
+
+	/// Sends a webhook, returning the status code of the response.
+	#[cfg(feature = "http")]
+	async fn send_webhook(&self, id: uuid::Uuid, entry: &QueuedWebhook) -> Result<u16, Error> {
+		use crate::dbs::capabilities::NetTarget;
+		use hmac::{Hmac, Mac};
+		use reqwest::header::CONTENT_TYPE;
+		use sha2::Sha256;
+		// Check that the url is still allowed
+		let url = url::Url::parse(&entry.url).map_err(|_| Error::InvalidUrl(entry.url.clone()))?;
+		let target = match url.host() {
+			Some(host) => NetTarget::Host(host.to_owned(), url.port_or_known_default()),
+			None => return Err(Error::InvalidUrl(entry.url.clone())),
+		};
+		if !self.allows_network_target(&target) {
+			warn!("Capabilities denied outgoing network connection attempt, target: '{target}'");
+			return Err(Error::NetTargetNotAllowed(target.to_string()));
+		}
+		// Encode the body, and sign it with the time at which it is sent
+		let body = entry.body.clone().into_json().to_string();
+		let timestamp = Datetime::default().0.timestamp().to_string();
+		// Build the request
+		let cli = reqwest::Client::builder().build()?;
+		let mut req = cli.post(url);
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			let timeout = Duration::from_secs(*crate::cnf::WEBHOOK_TIMEOUT);
+			req = req.timeout(timeout).header("User-Agent", "SurrealDB");
+		}
+		for (k, v) in entry.headers.iter() {
+			req = req.header(k.as_str(), v.to_raw_string());
+		}
+		req = req
+			.header(CONTENT_TYPE, "application/json")
+			.header("X-Surreal-Webhook-Id", id.to_string())
+			.header("X-Surreal-Webhook-Timestamp", &timestamp);
+		if let Some(secret) = &entry.secret {
+			// HMAC accepts keys of any length
+			let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+			mac.update(timestamp.as_bytes());
+			mac.update(b".");
+			mac.update(body.as_bytes());
+			let signature = hex::encode(mac.finalize().into_bytes());
+			req = req.header("X-Surreal-Webhook-Signature", format!("sha256={signature}"));
+		}
+		// Send the request and wait
+		let res = req.body(body).send().await?;
+		Ok(res.status().as_u16())
+	}
+
+	/// Sends a webhook, returning the status code of the response.
+	#[cfg(not(feature = "http"))]
+	async fn send_webhook(&self, _: uuid::Uuid, _: &QueuedWebhook) -> Result<u16, Error> {
+		Err(Error::HttpDisabled)
+	}
+
+	/// Records the outcome of an attempt to deliver a webhook, updating the queue in the same transaction.
+	async fn record_webhook(
+		&self,
+		key: &Key,
+		id: uuid::Uuid,
+		entry: &QueuedWebhook,
+		status: &str,
+		response: Option<u16>,
+	) -> Result<(), Error> {
+		// Write the status as the owner of the database, without permissions
+		let sess = Session::owner().with_ns(&entry.ns).with_db(&entry.db);
+		let opt = self.setup_options(&sess).new_with_perms(false);
+		// Configure the context
+		let mut ctx = self.setup_ctx()?;
+		sess.context(&mut ctx);
+		// Start a new transaction
+		let txn = self.internal_transaction(Write, Optimistic).await?.enclose();
+		ctx.set_transaction(txn.clone());
+		let ctx = ctx.freeze();
+		// Record the delivery status
+		let then = delivery_status(id, entry, status, response);
+		let mut stack = TreeStack::new();
+		let res = stack.enter(|stk| then.compute(stk, &ctx, &opt, None)).finish().await;
+		if let Err(e) = res {
+			txn.cancel().await?;
+			return Err(e);
+		}
+		// Remove the webhook from the queue, unless it is retried
+		match status {
+			"retrying" => catch!(txn, txn.set(key.clone(), entry.clone(), None).await),
+			_ => catch!(txn, txn.del(key.clone()).await),
+		}
+		txn.commit().await
+	}
+}
+
+/// Creates or updates the record in the delivery status table for a webhook
+fn delivery_status(
+	id: uuid::Uuid,
+	entry: &QueuedWebhook,
+	status: &str,
+	response: Option<u16>,
+) -> Value {
+	let retry = match status {
+		"retrying" => Value::from(entry.retry.clone()),
+		_ => Value::None,
+	};
+	let content = Object::from(map! {
+		"attempts".to_string() => Value::from(entry.attempts),
+		"error".to_string() => entry.error.clone().map(Value::from).unwrap_or_default(),
+		"queued".to_string() => Value::from(entry.queued.clone()),
+		"response".to_string() => response.map(Value::from).unwrap_or_default(),
+		"retry".to_string() => retry,
+		"status".to_string() => Value::from(status),
+		"updated".to_string() => Value::from(Datetime::default()),
+		"url".to_string() => Value::from(entry.url.as_str()),
+	});
+	let rid = Thing::from((WEBHOOK_DELIVERY_TABLE.as_str(), Id::Uuid(id.into())));
+	Value::Subquery(Box::new(Subquery::Upsert(UpsertStatement {
+		what: Values(vec![Value::Thing(rid)]),
+		data: Some(Data::ContentExpression(content.into())),
+		..UpsertStatement::default()
+	})))
+}
+
+#[cfg(all(test, feature = "kv-mem", feature = "http"))]
+mod tests {
+	use super::*;
+	use crate::dbs::Capabilities;
+	use hmac::{Hmac, Mac};
+	use sha2::Sha256;
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, MockServer, ResponseTemplate};
+
+	async fn query(ds: &Datastore, sql: &str) -> Value {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute(sql, &ses, None).await.unwrap().pop().unwrap().result.unwrap()
+	}
+
+	async fn queued(ds: &Datastore) -> Vec<(Key, QueuedWebhook)> {
+		let key = crate::key::root::wh::prefix()..crate::key::root::wh::suffix();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = txn.scan(key, 10, None).await.unwrap();
+		txn.cancel().await.unwrap();
+		res.into_iter().map(|(k, v)| (k, v.into())).collect()
+	}
+
+	async fn server(status: u16) -> MockServer {
+		let server = MockServer::start().await;
+		Mock::given(method("POST"))
+			.and(path("/hook"))
+			.respond_with(ResponseTemplate::new(status))
+			.mount(&server)
+			.await;
+		server
+	}
+
+	#[tokio::test]
+	async fn webhooks_are_signed_and_delivered_after_commit() {
+		let server = server(200).await;
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+		let sql = format!(
+			"DEFINE EVENT hook ON person THEN http::webhook('{}/hook', {{ id: $after.id }}, {{ secret: 'secret' }})",
+			server.uri()
+		);
+		query(&ds, &sql).await;
+		query(&ds, "CREATE person:tobie").await;
+		// The webhook is queued, but has not been sent
+		assert_eq!(queued(&ds).await.len(), 1);
+		assert!(server.received_requests().await.unwrap().is_empty());
+		// The webhook is sent, and removed from the queue
+		ds.process_webhooks().await.unwrap();
+		assert!(queued(&ds).await.is_empty());
+		let requests = server.received_requests().await.unwrap();
+		assert_eq!(requests.len(), 1);
+		let req = &requests[0];
+		assert_eq!(String::from_utf8_lossy(&req.body), r#"{"id":"person:tobie"}"#);
+		// The request is signed with the timestamp and the body
+		let header = |name: &str| req.headers.get(name).unwrap().to_str().unwrap().to_owned();
+		let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+		mac.update(format!("{}.", header("x-surreal-webhook-timestamp")).as_bytes());
+		mac.update(&req.body);
+		let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+		assert_eq!(header("x-surreal-webhook-signature"), signature);
+		// The delivery is recorded
+		let sql = "SELECT VALUE [<string> meta::id(id), status, attempts, response] FROM webhook_delivery";
+		let id = header("x-surreal-webhook-id");
+		assert_eq!(query(&ds, sql).await.to_string(), format!("[['{id}', 'delivered', 1, 200]]"));
+	}
+
+	#[tokio::test]
+	async fn failed_webhooks_are_retried_then_marked_as_failed() {
+		let server = server(503).await;
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+		let sql = format!("RETURN http::webhook('{}/hook', {{ ok: true }})", server.uri());
+		let rid = query(&ds, &sql).await;
+		// The first attempt fails, and the webhook is scheduled to be retried
+		ds.process_webhooks().await.unwrap();
+		let sql = "SELECT VALUE id FROM webhook_delivery";
+		assert_eq!(query(&ds, sql).await, Value::from(vec![rid]));
+		let sql = "SELECT VALUE [status, attempts, response, error] FROM webhook_delivery";
+		assert_eq!(
+			query(&ds, sql).await.to_string(),
+			"[['retrying', 1, 503, 'The endpoint responded with status 503']]"
+		);
+		let (key, mut entry) = queued(&ds).await.pop().unwrap();
+		assert_eq!(entry.attempts, 1);
+		assert!(entry.retry > Datetime::default());
+		// Waiting webhooks are not sent again
+		ds.process_webhooks().await.unwrap();
+		assert_eq!(server.received_requests().await.unwrap().len(), 1);
+		// Make the final attempt immediately
+		entry.attempts = *WEBHOOK_MAX_ATTEMPTS - 1;
+		entry.retry = Datetime::default();
+		let txn = ds.transaction(Write, Optimistic).await.unwrap();
+		txn.set(key, entry, None).await.unwrap();
+		txn.commit().await.unwrap();
+		ds.process_webhooks().await.unwrap();
+		// The webhook is marked as failed, and removed from the queue
+		let sql = "SELECT VALUE [status, attempts] FROM webhook_delivery";
+		assert_eq!(
+			query(&ds, sql).await.to_string(),
+			format!("[['failed', {}]]", *WEBHOOK_MAX_ATTEMPTS)
+		);
+		assert!(queued(&ds).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn webhooks_are_not_queued_when_cancelled() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::all());
+		let sql = "BEGIN; RETURN http::webhook('http://localhost/hook'); CANCEL;";
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute(sql, &ses, None).await.unwrap();
+		assert!(queued(&ds).await.is_empty());
+		// Network access is checked when the webhook is queued
+		let ds = Datastore::new("memory").await.unwrap();
+		let sql = "RETURN http::webhook('http://localhost/hook')";
+		let res = ds.execute(sql, &ses, None).await.unwrap().pop().unwrap().result;
+		assert!(matches!(res, Err(Error::NetTargetNotAllowed(_))));
+		assert!(queued(&ds).await.is_empty());
+	}
+}
@@ -10,6 +10,7 @@ pub mod ctx;
 mod doc;
 mod exe;
 mod fnc;
+mod sink;
 mod vs;
 
 pub mod sql;
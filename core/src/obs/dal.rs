@@ -0,0 +1,120 @@
+//! A generic object storage layer using the [OpenDAL](https://docs.rs/opendal/latest/opendal/)
+//! crate. This allows large values, exports, and backups to be stored in local file storage, or
+//! in cloud storage such as S3, GCS, or Azure Blob Storage.
+//!
+//! A blob store is configured using a URL. The scheme selects the storage service, the host
+//! specifies the bucket or container, the path specifies the root directory within it, and any
+//! query parameters are passed through as options to the storage service:
+//!
+//! ```text
+//! s3://bucket/path?region=eu-west-2&endpoint=http://localhost:9000
+//! gcs://bucket/path?credential_path=/etc/gcs.json
+//! azblob://container/path?account_name=name&account_key=key
+//! file:///path/to/directory
+//! memory://
+//! ```
+use crate::err::Error;
+use opendal::{Operator, Scheme};
+use url::Url;
+
+/// An object store which is accessed through OpenDAL
+#[derive(Clone, Debug)]
+pub(crate) struct BlobStore {
+	op: Operator,
+}
+
+impl BlobStore {
+	/// Create a blob store from a URL
+	pub(crate) fn new(url: &str) -> Result<Self, Error> {
+		// Parse the blob store URL
+		let url = Url::parse(url)
+			.map_err(|e| Error::BlobStore(format!("Invalid blob store url `{url}`: {e}")))?;
+		// Determine the storage service
+		let (scheme, bucket) = match url.scheme() {
+			"s3" => (Scheme::S3, Some("bucket")),
+			"gcs" | "gs" => (Scheme::Gcs, Some("bucket")),
+			"azblob" => (Scheme::Azblob, Some("container")),
+			"file" => (Scheme::Fs, None),
+			"memory" => (Scheme::Memory, None),
+			s => return Err(Error::BlobStore(format!("The `{s}` blob store is not supported"))),
+		};
+		// Pass any query parameters through as options
+		let mut opts: Vec<(String, String)> =
+			url.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+		// Specify the bucket or container
+		if let Some(key) = bucket {
+			match url.host_str() {
+				Some(host) if !host.is_empty() => opts.push((key.to_owned(), host.to_owned())),
+				_ => return Err(Error::BlobStore(format!("No {key} specified in blob store url"))),
+			}
+		}
+		// Specify the root directory
+		let root = match url.path() {
+			"" => "/",
+			path => path,
+		};
+		opts.push(("root".to_owned(), root.to_owned()));
+		// Create the storage service
+		let op = Operator::via_iter(scheme, opts).map_err(|e| Error::BlobStore(e.to_string()))?;
+		Ok(Self {
+			op,
+		})
+	}
+	/// Read the contents of an object
+	pub(crate) async fn read(&self, path: &str) -> Result<Vec<u8>, Error> {
+		match self.op.read(path).await {
+			Ok(v) => Ok(v.to_vec()),
+			Err(e) => Err(Error::BlobStore(e.to_string())),
+		}
+	}
+	/// Write the contents of an object, replacing any existing object
+	pub(crate) async fn write(&self, path: &str, data: Vec<u8>) -> Result<(), Error> {
+		match self.op.write(path, data).await {
+			Ok(_) => Ok(()),
+			Err(e) => Err(Error::BlobStore(e.to_string())),
+		}
+	}
+	/// Delete an object, if it exists
+	pub(crate) async fn delete(&self, path: &str) -> Result<(), Error> {
+		self.op.delete(path).await.map_err(|e| Error::BlobStore(e.to_string()))
+	}
+	/// List the names of the objects in a directory
+	pub(crate) async fn list(&self, path: &str) -> Result<Vec<String>, Error> {
+		match self.op.list(path).await {
+			Ok(v) => Ok(v
+				.into_iter()
+				.filter(|e| e.metadata().is_file())
+				.map(|e| e.name().to_owned())
+				.collect()),
+			Err(e) => Err(Error::BlobStore(e.to_string())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_urls() {
+		assert!(BlobStore::new("memory://").is_ok());
+		assert!(BlobStore::new("s3://bucket/path?region=eu-west-2").is_ok());
+		assert!(BlobStore::new("s3:///path").is_err());
+		assert!(BlobStore::new("ftp://host/path").is_err());
+		assert!(BlobStore::new("path").is_err());
+	}
+
+	#[tokio::test]
+	async fn read_write_delete() {
+		let store = BlobStore::new("memory://").unwrap();
+		store.write("one", b"1".to_vec()).await.unwrap();
+		store.write("two", b"2".to_vec()).await.unwrap();
+		assert_eq!(store.read("one").await.unwrap(), b"1".to_vec());
+		let mut list = store.list("/").await.unwrap();
+		list.sort();
+		assert_eq!(list, vec!["one".to_owned(), "two".to_owned()]);
+		store.delete("one").await.unwrap();
+		assert!(store.read("one").await.is_err());
+		assert_eq!(store.list("/").await.unwrap(), vec!["two".to_owned()]);
+	}
+}
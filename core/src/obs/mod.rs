@@ -17,6 +17,9 @@ use std::sync::Arc;
 use std::sync::LazyLock;
 use url::Url;
 
+#[cfg(feature = "opendal")]
+pub(crate) mod dal;
+
 fn initialize_store(env_var: &str, default_dir: &str) -> Arc<dyn ObjectStore> {
 	match std::env::var(env_var) {
 		Ok(url) => {
@@ -11,6 +11,10 @@ pub struct EngineOptions {
 	pub node_membership_check_interval: Duration,
 	pub node_membership_cleanup_interval: Duration,
 	pub changefeed_gc_interval: Duration,
+	pub maintenance_interval: Duration,
+	pub event_queue_interval: Duration,
+	pub sink_interval: Duration,
+	pub webhook_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -20,6 +24,10 @@ impl Default for EngineOptions {
 			node_membership_check_interval: Duration::from_secs(15),
 			node_membership_cleanup_interval: Duration::from_secs(300),
 			changefeed_gc_interval: Duration::from_secs(10),
+			maintenance_interval: Duration::from_secs(60),
+			event_queue_interval: Duration::from_secs(1),
+			sink_interval: Duration::from_secs(1),
+			webhook_interval: Duration::from_secs(1),
 		}
 	}
 }
@@ -41,4 +49,20 @@ impl EngineOptions {
 		self.changefeed_gc_interval = interval;
 		self
 	}
+	pub fn with_maintenance_interval(mut self, interval: Duration) -> Self {
+		self.maintenance_interval = interval;
+		self
+	}
+	pub fn with_event_queue_interval(mut self, interval: Duration) -> Self {
+		self.event_queue_interval = interval;
+		self
+	}
+	pub fn with_sink_interval(mut self, interval: Duration) -> Self {
+		self.sink_interval = interval;
+		self
+	}
+	pub fn with_webhook_interval(mut self, interval: Duration) -> Self {
+		self.webhook_interval = interval;
+		self
+	}
 }
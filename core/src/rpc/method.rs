@@ -23,6 +23,8 @@ pub enum Method {
 	Delete,
 	Version,
 	Query,
+	Prepare,
+	Execute,
 	Relate,
 	Run,
 	GraphQL,
@@ -56,6 +58,8 @@ impl Method {
 			"delete" => Self::Delete,
 			"version" => Self::Version,
 			"query" => Self::Query,
+			"prepare" => Self::Prepare,
+			"execute" => Self::Execute,
 			"relate" => Self::Relate,
 			"run" => Self::Run,
 			"graphql" => Self::GraphQL,
@@ -90,6 +94,8 @@ impl Method {
 			Self::Delete => "delete",
 			Self::Version => "version",
 			Self::Query => "query",
+			Self::Prepare => "prepare",
+			Self::Execute => "execute",
 			Self::Relate => "relate",
 			Self::Run => "run",
 			Self::GraphQL => "graphql",
@@ -123,6 +129,8 @@ impl Method {
 				| Method::Delete
 				| Method::Version
 				| Method::Query
+				| Method::Prepare
+				| Method::Execute
 				| Method::Relate
 				| Method::Run
 				| Method::GraphQL
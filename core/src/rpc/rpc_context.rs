@@ -16,7 +16,7 @@ use crate::{
 			CreateStatement, DeleteStatement, InsertStatement, KillStatement, LiveStatement,
 			RelateStatement, SelectStatement, UpdateStatement, UpsertStatement,
 		},
-		Array, Fields, Function, Model, Output, Query, Strand, Value,
+		Array, Fields, Function, Model, Output, Query, Statement, Strand, Value,
 	},
 };
 
@@ -38,6 +38,12 @@ pub trait RpcContext {
 	fn handle_kill(&self, _lqid: &Uuid) -> impl std::future::Future<Output = ()> + Send {
 		async { unimplemented!("handle functions must be redefined if LQ_SUPPORT = true") }
 	}
+	fn check_live_quota(
+		&self,
+		_count: usize,
+	) -> impl std::future::Future<Output = Result<(), RpcError>> + Send {
+		async { Ok(()) }
+	}
 
 	#[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 	const GQL_SUPPORT: bool = false;
@@ -79,6 +85,8 @@ pub trait RpcContext {
 			Method::Delete => self.delete(params).await,
 			Method::Version => self.version(params).await,
 			Method::Query => self.query(params).await,
+			Method::Prepare => self.prepare(params).await,
+			Method::Execute => self.execute_prepared(params).await,
 			Method::Relate => self.relate(params).await,
 			Method::Run => self.run(params).await,
 			Method::GraphQL => self.graphql(params).await,
@@ -110,6 +118,8 @@ pub trait RpcContext {
 			Method::Delete => self.delete(params).await,
 			Method::Version => self.version(params).await,
 			Method::Query => self.query(params).await,
+			Method::Prepare => self.prepare(params).await,
+			Method::Execute => self.execute_prepared(params).await,
 			Method::Relate => self.relate(params).await,
 			Method::Run => self.run(params).await,
 			Method::GraphQL => self.graphql(params).await,
@@ -269,6 +279,7 @@ pub trait RpcContext {
 		// Specify the SQL query string
 		let sql = KillStatement {
 			id,
+			query: false,
 		}
 		.into();
 		// Specify the query parameters
@@ -684,6 +695,51 @@ pub trait RpcContext {
 		self.query_inner(query, vars).await.map(Into::into)
 	}
 
+	async fn prepare(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok(Value::Strand(query)) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse and store the specified query
+		let id = self.kvs().prepare(&query)?;
+		// Return the prepared statement identifier
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn execute_prepared(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok((id, vars)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse the prepared statement identifier
+		let id = match id {
+			Value::Uuid(v) => v.0,
+			Value::Strand(v) => match Uuid::parse_str(v.as_str()) {
+				Ok(v) => v,
+				_ => return Err(RpcError::InvalidParams),
+			},
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// Specify the query variables
+		let vars = match vars {
+			Value::Object(mut v) => Some(mrg! {v.0, &self.vars()}),
+			Value::None | Value::Null => Some(self.vars().clone()),
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// If no live query handler force realtime off
+		if !Self::LQ_SUPPORT && self.session().rt {
+			return Err(RpcError::BadLQConfig);
+		}
+		// Execute the prepared statement
+		let res = self.kvs().execute_prepared(id, self.session(), vars).await?;
+		// Post-process hooks for web layer
+		for response in &res {
+			self.handle_live_query_results(response).await;
+		}
+		// Return the result to the client
+		Ok(res.into())
+	}
+
 	// ------------------------------
 	// Methods for running functions
 	// ------------------------------
@@ -860,22 +916,72 @@ pub trait RpcContext {
 		if !Self::LQ_SUPPORT && self.session().rt {
 			return Err(RpcError::BadLQConfig);
 		}
-		// Execute the query on the database
-		let res = match query {
-			Value::Query(sql) => self.kvs().process(sql, self.session(), vars).await?,
-			Value::Strand(sql) => self.kvs().execute(&sql, self.session(), vars).await?,
+		// Parse the query if necessary
+		let sql = match query {
+			Value::Query(sql) => sql,
+			Value::Strand(sql) => crate::syn::parse(&sql)?,
 			_ => return Err(fail!("Unexpected query type: {query:?}").into()),
 		};
+		// Register any live queries before execution, so
+		// that notifications which are sent as soon as the
+		// query is committed, such as changes replayed when
+		// resuming a live query, are not missed.
+		let lives = self.handle_live_query_statements(&sql).await?;
+		// Execute the query on the database
+		let res = match self.kvs().process(sql, self.session(), vars).await {
+			Ok(res) => res,
+			Err(e) => {
+				// Unregister any live queries
+				for lqid in lives {
+					self.handle_kill(&lqid).await;
+				}
+				return Err(e.into());
+			}
+		};
 
 		// Post-process hooks for web layer
 		for response in &res {
 			// This error should be unreachable because we shouldn't proceed if there's no handler
 			self.handle_live_query_results(response).await;
 		}
+		// Unregister any live queries which failed
+		for lqid in lives {
+			let started = res.iter().any(|r| match (&r.query_type, &r.result) {
+				(QueryType::Live, Ok(Value::Uuid(id))) => id.0 == lqid,
+				_ => false,
+			});
+			if !started {
+				self.handle_kill(&lqid).await;
+			}
+		}
 		// Return the result to the client
 		Ok(res)
 	}
 
+	async fn handle_live_query_statements(&self, sql: &Query) -> Result<Vec<Uuid>, RpcError> {
+		// Check if live queries are supported
+		if !Self::LQ_SUPPORT || !self.session().rt {
+			return Ok(Vec::new());
+		}
+		// Get the ids of any live queries
+		let lives: Vec<Uuid> = sql
+			.iter()
+			.filter_map(|stm| match stm {
+				Statement::Live(lv) => Some(lv.id.0),
+				_ => None,
+			})
+			.collect();
+		// Check that the live queries are within the quota
+		if !lives.is_empty() {
+			self.check_live_quota(lives.len()).await?;
+		}
+		// Register the ids of the live queries
+		for lqid in lives.iter() {
+			self.handle_live(lqid).await;
+		}
+		Ok(lives)
+	}
+
 	async fn handle_live_query_results(&self, res: &Response) {
 		match &res.query_type {
 			QueryType::Live => {
@@ -21,6 +21,8 @@ pub enum RpcError {
 	LqNotSuported,
 	#[error("RT is enabled for the session, but LQ is not supported by the context")]
 	BadLQConfig,
+	#[error("The maximum of {0} live queries has been reached")]
+	LqLimitExceeded(usize),
 	#[error("A GraphQL request was made, but GraphQL is not supported by the context")]
 	BadGQLConfig,
 	#[error("Error: {0}")]
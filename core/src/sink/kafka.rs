@@ -0,0 +1,48 @@
+//! Delivery of changes to a Kafka topic.
+use super::{brokers, Message};
+use chrono::Utc;
+use rskafka::client::partition::{Compression, PartitionClient, UnknownTopicHandling};
+use rskafka::client::ClientBuilder;
+use rskafka::record::Record;
+use std::collections::BTreeMap;
+
+/// The partition of the topic which changes are written to, so that they are kept in order
+const PARTITION: i32 = 0;
+
+/// A connection to the partition of a Kafka topic
+pub(crate) struct Publisher {
+	client: PartitionClient,
+}
+
+impl Publisher {
+	/// Connects to a Kafka cluster, given a comma-separated list of bootstrap brokers
+	pub(super) async fn connect(url: &str, topic: &str) -> Result<Self, String> {
+		let brokers = brokers(url).map(str::to_owned).collect();
+		let client = ClientBuilder::new(brokers).build().await.map_err(|e| e.to_string())?;
+		let client = client
+			.partition_client(topic, PARTITION, UnknownTopicHandling::Retry)
+			.await
+			.map_err(|e| e.to_string())?;
+		Ok(Self {
+			client,
+		})
+	}
+
+	/// Produces a batch of records, returning once they have been acknowledged by the broker
+	pub(super) async fn publish(&self, messages: &[Message]) -> Result<(), String> {
+		let records = messages
+			.iter()
+			.map(|m| Record {
+				key: Some(m.key.clone().into_bytes()),
+				value: Some(m.payload.clone()),
+				headers: BTreeMap::from([("id".to_owned(), m.id.clone().into_bytes())]),
+				timestamp: Utc::now(),
+			})
+			.collect();
+		self.client
+			.produce(records, Compression::NoCompression)
+			.await
+			.map_err(|e| e.to_string())?;
+		Ok(())
+	}
+}
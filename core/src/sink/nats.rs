@@ -0,0 +1,41 @@
+//! Delivery of changes to a NATS JetStream subject.
+use super::Message;
+use async_nats::jetstream::{self, Context};
+use async_nats::HeaderMap;
+
+/// A connection to a NATS server with JetStream enabled
+pub(crate) struct Publisher {
+	context: Context,
+	subject: String,
+}
+
+impl Publisher {
+	/// Connects to a NATS server, given a comma-separated list of server urls
+	pub(super) async fn connect(url: &str, subject: &str) -> Result<Self, String> {
+		let client = async_nats::connect(url).await.map_err(|e| e.to_string())?;
+		Ok(Self {
+			context: jetstream::new(client),
+			subject: subject.to_owned(),
+		})
+	}
+
+	/// Publishes a batch of messages, returning once they have been acknowledged by the stream
+	pub(super) async fn publish(&self, messages: &[Message]) -> Result<(), String> {
+		let mut acks = Vec::with_capacity(messages.len());
+		for m in messages {
+			// JetStream discards messages with an id which it has already stored
+			let mut headers = HeaderMap::new();
+			headers.insert("Nats-Msg-Id", m.id.as_str());
+			let ack = self
+				.context
+				.publish_with_headers(self.subject.clone(), headers, m.payload.clone().into())
+				.await
+				.map_err(|e| e.to_string())?;
+			acks.push(ack);
+		}
+		for ack in acks {
+			ack.await.map_err(|e| e.to_string())?;
+		}
+		Ok(())
+	}
+}
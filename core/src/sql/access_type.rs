@@ -1,7 +1,7 @@
 use super::Value;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::DefineAccessStatement;
-use crate::sql::{escape::quote_str, Algorithm};
+use crate::sql::{escape::quote_str, fmt::Fmt, Algorithm, Ident};
 use revision::revisioned;
 use revision::Error as RevisionError;
 use serde::{Deserialize, Serialize};
@@ -9,7 +9,7 @@ use std::fmt;
 use std::fmt::Display;
 
 /// The type of access methods available
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -20,6 +20,8 @@ pub enum AccessType {
 	#[doc(hidden)]
 	#[revision(start = 2)]
 	Bearer(BearerAccess),
+	#[revision(start = 3)]
+	Key(KeyAccess),
 }
 
 // Allows retrieving the JWT configuration for any access type.
@@ -36,12 +38,14 @@ impl Default for AccessType {
 	}
 }
 
-impl Jwt for AccessType {
-	fn jwt(&self) -> &JwtAccess {
+impl AccessType {
+	/// Returns the JWT configuration of the access method, if it uses JWT
+	pub fn jwt(&self) -> Option<&JwtAccess> {
 		match self {
-			AccessType::Record(at) => at.jwt(),
-			AccessType::Jwt(at) => at.jwt(),
-			AccessType::Bearer(at) => at.jwt(),
+			AccessType::Record(at) => Some(at.jwt()),
+			AccessType::Jwt(at) => Some(at.jwt()),
+			AccessType::Bearer(at) => Some(at.jwt()),
+			AccessType::Key(_) => None,
 		}
 	}
 }
@@ -68,6 +72,9 @@ impl Display for AccessType {
 					write!(f, " FOR RECORD")?;
 				}
 			}
+			AccessType::Key(ac) => {
+				write!(f, "KEY {ac}")?;
+			}
 		}
 		Ok(())
 	}
@@ -94,6 +101,10 @@ impl InfoStructure for AccessType {
 			}.into(),
 					"jwt".to_string() => ac.jwt.structure(),
 				}),
+			AccessType::Key(ac) => Value::from(map! {
+				"kind".to_string() => "KEY".into(),
+				"roles".to_string() => ac.roles.into_iter().map(Ident::structure).collect(),
+			}),
 		}
 	}
 }
@@ -108,7 +119,7 @@ impl AccessType {
 		match self {
 			// The grants for JWT and record access methods are JWT
 			AccessType::Jwt(_) | AccessType::Record(_) => false,
-			AccessType::Bearer(_) => true,
+			AccessType::Bearer(_) | AccessType::Key(_) => true,
 		}
 	}
 	/// Returns whether or not the access method can issue tokens
@@ -117,6 +128,8 @@ impl AccessType {
 		match self {
 			// The JWT access method can only issue tokens if an issuer is set
 			AccessType::Jwt(jwt) => jwt.issue.is_some(),
+			// The key access method authenticates with the keys themselves
+			AccessType::Key(_) => false,
 			_ => true,
 		}
 	}
@@ -165,7 +178,7 @@ impl Display for JwtAccess {
 				write!(f, "ALGORITHM {} KEY {}", v.alg, quote_str(&v.key))?;
 			}
 			JwtAccessVerify::Jwks(ref v) => {
-				write!(f, "URL {}", quote_str(&v.url),)?;
+				write!(f, "{v}")?;
 			}
 		}
 		if let Some(iss) = &self.issue {
@@ -179,9 +192,7 @@ impl InfoStructure for JwtAccess {
 	fn structure(self) -> Value {
 		Value::from(map! {
 			"verify".to_string() => match self.verify {
-				JwtAccessVerify::Jwks(v) => Value::from(map!{
-					"url".to_string() => v.url.into(),
-				}),
+				JwtAccessVerify::Jwks(v) => v.structure(),
 				JwtAccessVerify::Key(v) => Value::from(map!{
 					"alg".to_string() => v.alg.structure(),
 					"key".to_string() => v.key.into(),
@@ -260,9 +271,7 @@ impl Default for JwtAccessVerify {
 impl InfoStructure for JwtAccessVerify {
 	fn structure(self) -> Value {
 		match self {
-			JwtAccessVerify::Jwks(v) => Value::from(map! {
-				"url".to_string() => v.url.into(),
-			}),
+			JwtAccessVerify::Jwks(v) => v.structure(),
 			JwtAccessVerify::Key(v) => Value::from(map! {
 				"alg".to_string() => v.alg.structure(),
 				"key".to_string() => v.key.into(),
@@ -290,11 +299,40 @@ impl Default for JwtAccessVerifyKey {
 	}
 }
 
-#[revisioned(revision = 1)]
-#[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[revisioned(revision = 2)]
+#[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct JwtAccessVerifyJwks {
 	pub url: String,
+	// The expected "iss" claim of verified tokens
+	#[revision(start = 2)]
+	pub issuer: Option<String>,
+	// The expected "aud" claim of verified tokens
+	#[revision(start = 2)]
+	pub audience: Option<String>,
+}
+
+impl Display for JwtAccessVerifyJwks {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "URL {}", quote_str(&self.url))?;
+		if let Some(ref v) = self.issuer {
+			write!(f, " ISSUER {}", quote_str(v))?;
+		}
+		if let Some(ref v) = self.audience {
+			write!(f, " AUDIENCE {}", quote_str(v))?;
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for JwtAccessVerifyJwks {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"url".to_string() => self.url.into(),
+			"issuer".to_string(), if let Some(v) = self.issuer => v.into(),
+			"audience".to_string(), if let Some(v) = self.audience => v.into(),
+		})
+	}
 }
 
 #[revisioned(revision = 3)]
@@ -365,6 +403,35 @@ impl Jwt for BearerAccess {
 	}
 }
 
+#[revisioned(revision = 1)]
+#[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct KeyAccess {
+	// The roles granted to requests authenticated with a key
+	pub roles: Vec<Ident>,
+}
+
+impl Default for KeyAccess {
+	fn default() -> Self {
+		Self {
+			// Keys are read-only by default
+			roles: vec!["Viewer".into()],
+		}
+	}
+}
+
+impl Display for KeyAccess {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"ROLES {}",
+			Fmt::comma_separated(
+				&self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<String>>()
+			),
+		)
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -49,8 +49,10 @@ impl Cast {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
+		// Resolve any user-defined types
+		let kind = self.0.resolved(ctx, opt).await?;
 		// Compute the value to be cast and convert it
-		stk.run(|stk| self.1.compute(stk, ctx, opt, doc)).await?.convert_to(&self.0)
+		stk.run(|stk| self.1.compute(stk, ctx, opt, doc)).await?.convert_to(&kind)
 	}
 }
 
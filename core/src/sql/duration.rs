@@ -13,7 +13,8 @@ use std::ops::Deref;
 use std::str::FromStr;
 use std::time;
 
-use super::value::{TryAdd, TrySub};
+use super::value::{TryAdd, TryDiv, TryMul, TryRem, TrySub};
+use super::Number;
 
 pub(crate) static SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
 pub(crate) static SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
@@ -94,6 +95,11 @@ impl Duration {
 	pub fn new(secs: u64, nanos: u32) -> Duration {
 		time::Duration::new(secs, nanos).into()
 	}
+	/// Create a duration from a total number of nanoseconds, if it is in range
+	pub(crate) fn checked_from_nanos(nanos: u128) -> Option<Duration> {
+		let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+		Some(Self::new(secs, (nanos % 1_000_000_000) as u32))
+	}
 	/// Convert the Duration to a raw String
 	pub fn to_raw(&self) -> String {
 		self.to_string()
@@ -362,6 +368,61 @@ impl TrySub<Datetime> for Duration {
 	}
 }
 
+impl TryMul<Number> for Duration {
+	type Output = Self;
+	fn try_mul(self, other: Number) -> Result<Self, Error> {
+		let err = || Error::ArithmeticOverflow(format!("{self} * {other}"));
+		match other {
+			// Integers are multiplied exactly, as nanoseconds
+			Number::Int(v) => {
+				let v = u128::try_from(v).map_err(|_| err())?;
+				self.0.as_nanos().checked_mul(v).and_then(Self::checked_from_nanos).ok_or_else(err)
+			}
+			_ => time::Duration::try_from_secs_f64(self.0.as_secs_f64() * other.to_float())
+				.map(Duration::from)
+				.map_err(|_| err()),
+		}
+	}
+}
+
+impl TryDiv<Number> for Duration {
+	type Output = Self;
+	fn try_div(self, other: Number) -> Result<Self, Error> {
+		let err = || Error::TryDiv(self.to_string(), other.to_string());
+		match other {
+			// Integers are divided exactly, as nanoseconds
+			Number::Int(v) => {
+				let v = u128::try_from(v).map_err(|_| err())?;
+				self.0.as_nanos().checked_div(v).and_then(Self::checked_from_nanos).ok_or_else(err)
+			}
+			_ => time::Duration::try_from_secs_f64(self.0.as_secs_f64() / other.to_float())
+				.map(Duration::from)
+				.map_err(|_| err()),
+		}
+	}
+}
+
+impl TryDiv for Duration {
+	type Output = Number;
+	fn try_div(self, other: Self) -> Result<Number, Error> {
+		if other.0.is_zero() {
+			return Err(Error::TryDiv(self.to_string(), other.to_string()));
+		}
+		Ok(Number::Float(self.0.as_secs_f64() / other.0.as_secs_f64()))
+	}
+}
+
+impl TryRem for Duration {
+	type Output = Self;
+	fn try_rem(self, other: Self) -> Result<Self, Error> {
+		self.0
+			.as_nanos()
+			.checked_rem(other.0.as_nanos())
+			.and_then(Self::checked_from_nanos)
+			.ok_or_else(|| Error::TryRem(self.to_string(), other.to_string()))
+	}
+}
+
 impl Sum<Self> for Duration {
 	fn sum<I>(iter: I) -> Duration
 	where
@@ -2,11 +2,17 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Explain(pub bool);
+pub struct Explain(
+	/// Whether the statement is executed, reporting the actual results
+	pub bool,
+	/// Whether the full query plan is reported, as requested by `EXPLAIN SELECT ...`
+	#[revision(start = 2)]
+	pub bool,
+);
 
 impl fmt::Display for Explain {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::{fmt::Fmt, Function, Idiom, Part, Value};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -42,6 +42,16 @@ impl Fields {
 		}
 	}
 
+	/// Check if any of the fields is a window function
+	pub(crate) fn has_window(&self) -> bool {
+		self.0.iter().any(|v| {
+			matches!(v, Field::Single {
+				expr: Value::Function(f),
+				..
+			} if f.is_window())
+		})
+	}
+
 	/// Check if the fields are only about counting
 	pub(crate) fn is_count_all_only(&self) -> bool {
 		let mut is_count_only = false;
@@ -142,6 +152,10 @@ impl Fields {
 							let x = match f.args().len() {
 								// If no function arguments, then compute the result
 								0 => f.compute(stk, ctx, opt, Some(doc)).await?,
+								// If the function needs the record, then compute the result
+								_ if f.is_computed_per_record() => {
+									f.compute(stk, ctx, opt, Some(doc)).await?
+								}
 								// If arguments, then pass the first value through
 								_ => f.args()[0].compute(stk, ctx, opt, Some(doc)).await?,
 							};
@@ -151,6 +165,19 @@ impl Fields {
 								true => out = x,
 							}
 						}
+						// This expression is a window function
+						Value::Function(f) if f.is_window() => {
+							let Function::Window(n, a, w) = f.as_ref() else {
+								unreachable!()
+							};
+							// The window is evaluated once all records are collected
+							let x = w.compute(stk, ctx, opt, Some(doc), n, a).await?;
+							// Check if this is a single VALUE field expression
+							match self.single().is_some() {
+								false => out.set(stk, ctx, opt, name.as_ref(), x).await?,
+								true => out = x,
+							}
+						}
 						// This expression is a multi-output graph traversal
 						Value::Idiom(v) if v.is_multi_yield() => {
 							// Store the different output yields here
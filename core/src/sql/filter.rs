@@ -1,10 +1,11 @@
+use crate::sql::escape::quote_str;
 use crate::sql::language::Language;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +17,8 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	#[revision(start = 2)]
+	Stopwords(Vec<String>),
 }
 
 impl Display for Filter {
@@ -28,6 +31,10 @@ impl Display for Filter {
 			Self::Snowball(lang) => write!(f, "SNOWBALL({lang})"),
 			Self::Uppercase => f.write_str("UPPERCASE"),
 			Self::Mapper(path) => write!(f, "MAPPER({path})"),
+			Self::Stopwords(words) => {
+				let words: Vec<String> = words.iter().map(|w| quote_str(w)).collect();
+				write!(f, "STOPWORDS({})", words.join(","))
+			}
 		}
 	}
 }
@@ -8,6 +8,7 @@ use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
 use crate::sql::script::Script;
 use crate::sql::value::Value;
+use crate::sql::window::Window;
 use crate::sql::Permission;
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
@@ -20,7 +21,7 @@ use super::Kind;
 
 pub(crate) const TOKEN: &str = "$surrealdb::private::sql::Function";
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[serde(rename = "$surrealdb::private::sql::Function")]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -30,6 +31,8 @@ pub enum Function {
 	Custom(String, Vec<Value>),
 	Script(Script, Vec<Value>),
 	Anonymous(Value, Vec<Value>),
+	#[revision(start = 2)]
+	Window(String, Vec<Value>, Window),
 	// Add new variants here
 }
 
@@ -41,8 +44,13 @@ pub(crate) enum OptimisedAggregate {
 	MathMin,
 	MathSum,
 	MathMean,
+	MathMedian,
+	MathPercentile,
+	MathStddev,
+	MathVariance,
 	TimeMax,
 	TimeMin,
+	SearchFacets,
 }
 
 impl PartialOrd for Function {
@@ -58,6 +66,7 @@ impl Function {
 		match self {
 			Self::Normal(n, _) => Some(n.as_str()),
 			Self::Custom(n, _) => Some(n.as_str()),
+			Self::Window(n, _, _) => Some(n.as_str()),
 			_ => None,
 		}
 	}
@@ -66,6 +75,7 @@ impl Function {
 		match self {
 			Self::Normal(_, a) => a,
 			Self::Custom(_, a) => a,
+			Self::Window(_, a, _) => a,
 			_ => &[],
 		}
 	}
@@ -76,6 +86,7 @@ impl Function {
 			Self::Script(_, _) => "function".to_string().into(),
 			Self::Normal(f, _) => f.to_owned().into(),
 			Self::Custom(f, _) => format!("fn::{f}").into(),
+			Self::Window(f, _, _) => f.to_owned().into(),
 		}
 	}
 	/// Convert this function to an aggregate
@@ -113,6 +124,11 @@ impl Function {
 		}
 	}
 
+	/// Check if this function is a window function
+	pub fn is_window(&self) -> bool {
+		matches!(self, Self::Window(_, _, _))
+	}
+
 	/// Check if this function is a closure function
 	pub fn is_inline(&self) -> bool {
 		matches!(self, Self::Anonymous(_, _))
@@ -141,7 +157,10 @@ impl Function {
 			Self::Normal(f, _) if f == "array::last" => true,
 			Self::Normal(f, _) if f == "count" => true,
 			Self::Normal(f, _) if f == "math::bottom" => true,
+			Self::Normal(f, _) if f == "math::correlation" => true,
+			Self::Normal(f, _) if f == "math::covariance" => true,
 			Self::Normal(f, _) if f == "math::interquartile" => true,
+			Self::Normal(f, _) if f == "math::linear_regression" => true,
 			Self::Normal(f, _) if f == "math::max" => true,
 			Self::Normal(f, _) if f == "math::mean" => true,
 			Self::Normal(f, _) if f == "math::median" => true,
@@ -157,11 +176,16 @@ impl Function {
 			Self::Normal(f, _) if f == "math::top" => true,
 			Self::Normal(f, _) if f == "math::trimean" => true,
 			Self::Normal(f, _) if f == "math::variance" => true,
+			Self::Normal(f, _) if f == "search::facets" => true,
 			Self::Normal(f, _) if f == "time::max" => true,
 			Self::Normal(f, _) if f == "time::min" => true,
 			_ => false,
 		}
 	}
+	/// Check if this grouping function is computed for each record, before being aggregated
+	pub(crate) fn is_computed_per_record(&self) -> bool {
+		matches!(self, Self::Normal(f, _) if f == "search::facets")
+	}
 	pub(crate) fn get_optimised_aggregate(&self) -> OptimisedAggregate {
 		match self {
 			Self::Normal(f, v) if f == "count" => {
@@ -173,8 +197,13 @@ impl Function {
 			}
 			Self::Normal(f, _) if f == "math::max" => OptimisedAggregate::MathMax,
 			Self::Normal(f, _) if f == "math::mean" => OptimisedAggregate::MathMean,
+			Self::Normal(f, _) if f == "math::median" => OptimisedAggregate::MathMedian,
 			Self::Normal(f, _) if f == "math::min" => OptimisedAggregate::MathMin,
+			Self::Normal(f, _) if f == "math::percentile" => OptimisedAggregate::MathPercentile,
+			Self::Normal(f, _) if f == "math::stddev" => OptimisedAggregate::MathStddev,
 			Self::Normal(f, _) if f == "math::sum" => OptimisedAggregate::MathSum,
+			Self::Normal(f, _) if f == "math::variance" => OptimisedAggregate::MathVariance,
+			Self::Normal(f, _) if f == "search::facets" => OptimisedAggregate::SearchFacets,
 			Self::Normal(f, _) if f == "time::max" => OptimisedAggregate::TimeMax,
 			Self::Normal(f, _) if f == "time::min" => OptimisedAggregate::TimeMin,
 			_ => OptimisedAggregate::None,
@@ -301,11 +330,33 @@ impl Function {
 						)
 					})
 					.await?;
+				// Resolve any user-defined argument types
+				let mut kinds = Vec::with_capacity(val.args.len());
+				for (_, kind) in val.args.iter() {
+					kinds.push(kind.resolved(ctx, opt).await?);
+				}
 				// Duplicate context
 				let mut ctx = MutableContext::new_isolated(ctx);
-				// Process the function arguments
-				for (val, (name, kind)) in a.into_iter().zip(&val.args) {
-					ctx.add_value(name.to_raw(), val.coerce_to(kind)?.into());
+				// Process the function arguments, setting any omitted optional arguments to NONE
+				let mut a = a.into_iter();
+				for ((name, _), kind) in val.args.iter().zip(kinds) {
+					let arg = a.next().unwrap_or_default();
+					let arg = match arg.coerce_to(&kind) {
+						Ok(arg) => arg,
+						Err(Error::CoerceTo {
+							from,
+							..
+						}) => {
+							return Err(Error::InvalidArguments {
+								name: format!("fn::{}", val.name),
+								message: format!(
+									"Expected a value of type '{kind}' for argument ${name}, but found {from}."
+								),
+							})
+						}
+						Err(e) => return Err(e),
+					};
+					ctx.add_value(name.to_raw(), arg.into());
 				}
 				let ctx = ctx.freeze();
 				// Run the custom function
@@ -318,12 +369,18 @@ impl Function {
 
 				if let Some(ref returns) = val.returns {
 					result
-						.coerce_to(returns)
+						.coerce_to(&*returns.resolved(&ctx, opt).await?)
 						.map_err(|e| e.function_check_from_coerce(val.name.to_string()))
 				} else {
 					Ok(result)
 				}
 			}
+			Self::Window(s, _, _) => Err(Error::InvalidFunction {
+				name: s.to_owned(),
+				message: String::from(
+					"Window functions can only be used as a field in a SELECT statement.",
+				),
+			}),
 			#[allow(unused_variables)]
 			Self::Script(s, x) => {
 				#[cfg(feature = "scripting")]
@@ -359,6 +416,7 @@ impl fmt::Display for Function {
 			Self::Custom(s, e) => write!(f, "fn::{s}({})", Fmt::comma_separated(e)),
 			Self::Script(s, e) => write!(f, "function({}) {{{s}}}", Fmt::comma_separated(e)),
 			Self::Anonymous(p, e) => write!(f, "{p}({})", Fmt::comma_separated(e)),
+			Self::Window(s, e, w) => write!(f, "{s}({}) {w}", Fmt::comma_separated(e)),
 		}
 	}
 }
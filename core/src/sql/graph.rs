@@ -53,6 +53,7 @@ impl Graph {
 				.map(|x| Order {
 					value: x.order,
 					collate: x.collate,
+					locale: None,
 					numeric: x.numeric,
 					direction: x.direction,
 				})
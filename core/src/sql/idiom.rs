@@ -116,7 +116,9 @@ impl Idiom {
 	pub(crate) fn simplify(&self) -> Idiom {
 		self.0
 			.iter()
-			.filter(|&p| matches!(p, Part::Field(_) | Part::Start(_) | Part::Graph(_)))
+			.filter(|&p| {
+				matches!(p, Part::Field(_) | Part::Start(_) | Part::Graph(_) | Part::Recurse(_))
+			})
 			.cloned()
 			.collect::<Vec<_>>()
 			.into()
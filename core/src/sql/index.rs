@@ -11,8 +11,9 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use unicode_normalization::UnicodeNormalization;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +30,9 @@ pub enum Index {
 	/// HNSW index for distance based metrics
 	#[revision(start = 2)]
 	Hnsw(HnswParams),
+	/// Geohash cell index for geometries
+	#[revision(start = 3)]
+	Spatial,
 }
 
 #[revisioned(revision = 2)]
@@ -236,11 +240,47 @@ impl Display for VectorType {
 	}
 }
 
+/// A normalisation applied to the strings of an index,
+/// both when the values are indexed and when they are looked up
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Collation {
+	/// Strings are compared regardless of their case
+	NoCase,
+	/// Strings are compared in their unicode compatibility normalization form (NFKC)
+	Nfkc,
+}
+
+impl Collation {
+	pub(crate) fn apply(&self, v: Value) -> Value {
+		match v {
+			Value::Strand(s) => match self {
+				Self::NoCase => s.0.to_lowercase().into(),
+				Self::Nfkc => s.0.nfkc().collect::<String>().into(),
+			},
+			Value::Array(a) => Value::Array(a.into_iter().map(|v| self.apply(v)).collect()),
+			v => v,
+		}
+	}
+}
+
+impl Display for Collation {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NoCase => f.write_str("NOCASE"),
+			Self::Nfkc => f.write_str("NFKC"),
+		}
+	}
+}
+
 impl Display for Index {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Self::Idx => Ok(()),
 			Self::Uniq => f.write_str("UNIQUE"),
+			Self::Spatial => f.write_str("SPATIAL"),
 			Self::Search(p) => {
 				write!(
 					f,
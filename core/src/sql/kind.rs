@@ -1,16 +1,22 @@
 use super::escape::escape_key;
 use super::{Duration, Number, Strand};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::sql::index::VectorType;
 use crate::sql::statements::info::InfoStructure;
+use crate::sql::statements::DefineTypeStatement;
 use crate::sql::{
 	fmt::{is_pretty, pretty_indent, Fmt, Pretty},
 	Table, Value,
 };
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter, Write};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -38,6 +44,11 @@ pub enum Kind {
 	Function(Option<Vec<Kind>>, Option<Box<Kind>>),
 	Range,
 	Literal(Literal),
+	#[revision(start = 2)]
+	Custom(String),
+	/// An array of numbers of the given type, with exactly the given dimension
+	#[revision(start = 3)]
+	Vector(VectorType, u64),
 }
 
 impl Default for Kind {
@@ -84,7 +95,77 @@ impl Kind {
 			return x.iter().any(|x| x.is_literal_nested());
 		}
 
-		false
+		// A user-defined type may be a literal
+		matches!(self, Kind::Custom(_))
+	}
+
+	/// Returns true if this type is a vector, or contains a vector
+	pub(crate) fn is_vector_nested(&self) -> bool {
+		match self {
+			Kind::Vector(_, _) => true,
+			Kind::Option(x) => x.is_vector_nested(),
+			Kind::Either(x) => x.iter().any(|x| x.is_vector_nested()),
+			_ => false,
+		}
+	}
+
+	/// Returns true if this type is, or contains, a user-defined type
+	pub(crate) fn has_custom(&self) -> bool {
+		match self {
+			Kind::Custom(_) => true,
+			Kind::Option(k) | Kind::Set(k, _) | Kind::Array(k, _) => k.has_custom(),
+			Kind::Either(k) => k.iter().any(Kind::has_custom),
+			Kind::Literal(l) => l.has_custom(),
+			_ => false,
+		}
+	}
+
+	/// Returns this type with any user-defined types replaced by their definitions
+	pub(crate) async fn resolved(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Cow<'_, Kind>, Error> {
+		if !self.has_custom() {
+			return Ok(Cow::Borrowed(self));
+		}
+		let types = ctx.tx().all_db_types(opt.ns()?, opt.db()?).await?;
+		Ok(Cow::Owned(self.resolve(&types, &mut Vec::new())?))
+	}
+
+	/// Replaces any user-defined types with their definitions, keeping
+	/// track of the types being resolved so that cycles can be detected
+	pub(crate) fn resolve(
+		&self,
+		types: &[DefineTypeStatement],
+		seen: &mut Vec<String>,
+	) -> Result<Kind, Error> {
+		Ok(match self {
+			Kind::Custom(name) => {
+				if seen.contains(name) {
+					return Err(Error::TyRecursive {
+						value: name.to_owned(),
+					});
+				}
+				let Some(ty) = types.iter().find(|t| t.name.0 == *name) else {
+					return Err(Error::TyNotFound {
+						value: name.to_owned(),
+					});
+				};
+				seen.push(name.to_owned());
+				let kind = ty.kind.resolve(types, seen)?;
+				seen.pop();
+				kind
+			}
+			Kind::Option(k) => Kind::Option(Box::new(k.resolve(types, seen)?)),
+			Kind::Set(k, l) => Kind::Set(Box::new(k.resolve(types, seen)?), *l),
+			Kind::Array(k, l) => Kind::Array(Box::new(k.resolve(types, seen)?), *l),
+			Kind::Either(k) => {
+				Kind::Either(k.iter().map(|k| k.resolve(types, seen)).collect::<Result<_, _>>()?)
+			}
+			Kind::Literal(l) => Kind::Literal(l.resolve(types, seen)?),
+			k => k.to_owned(),
+		})
 	}
 
 	/// Returns Some if this type can be converted into a discriminated object, None otherwise
@@ -170,7 +251,10 @@ impl Kind {
 				| Kind::Geometry(_)
 				| Kind::Function(_, _)
 				| Kind::Range
-				| Kind::Literal(_) => return None,
+				| Kind::Literal(_)
+				| Kind::Custom(_)
+				// The elements of a vector are checked with the vector itself
+				| Kind::Vector(_, _) => return None,
 				Kind::Option(x) => {
 					this = x;
 				}
@@ -236,6 +320,8 @@ impl Display for Kind {
 			Kind::Either(k) => write!(f, "{}", Fmt::verbar_separated(k)),
 			Kind::Range => f.write_str("range"),
 			Kind::Literal(l) => write!(f, "{}", l),
+			Kind::Custom(n) => f.write_str(n),
+			Kind::Vector(t, d) => write!(f, "vector<{}, {d}>", t.to_string().to_lowercase()),
 		}
 	}
 }
@@ -279,6 +365,40 @@ impl Literal {
 		}
 	}
 
+	/// Returns true if this literal contains a user-defined type
+	fn has_custom(&self) -> bool {
+		match self {
+			Self::Array(a) => a.iter().any(Kind::has_custom),
+			Self::Object(o) => o.values().any(Kind::has_custom),
+			Self::DiscriminatedObject(_, d) => d.iter().any(|o| o.values().any(Kind::has_custom)),
+			_ => false,
+		}
+	}
+
+	/// Replaces any user-defined types within this literal with their definitions
+	fn resolve(
+		&self,
+		types: &[DefineTypeStatement],
+		seen: &mut Vec<String>,
+	) -> Result<Literal, Error> {
+		let object = |o: &BTreeMap<String, Kind>, seen: &mut Vec<String>| {
+			o.iter()
+				.map(|(k, v)| Ok((k.to_owned(), v.resolve(types, seen)?)))
+				.collect::<Result<BTreeMap<_, _>, Error>>()
+		};
+		Ok(match self {
+			Self::Array(a) => {
+				Self::Array(a.iter().map(|k| k.resolve(types, seen)).collect::<Result<_, _>>()?)
+			}
+			Self::Object(o) => Self::Object(object(o, seen)?),
+			Self::DiscriminatedObject(k, d) => Self::DiscriminatedObject(
+				k.to_owned(),
+				d.iter().map(|o| object(o, seen)).collect::<Result<_, _>>()?,
+			),
+			l => l.to_owned(),
+		})
+	}
+
 	pub fn validate_value(&self, value: &Value) -> bool {
 		match self {
 			Self::String(v) => match value {
@@ -65,11 +65,13 @@ pub(crate) mod table_type;
 pub(crate) mod thing;
 pub(crate) mod timeout;
 pub(crate) mod tokenizer;
+pub(crate) mod ttl;
 pub(crate) mod user;
 pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 #[doc(hidden)]
@@ -149,6 +151,7 @@ pub use self::table_type::{Relation, TableType};
 pub use self::thing::Thing;
 pub use self::timeout::Timeout;
 pub use self::tokenizer::Tokenizer;
+pub use self::ttl::Ttl;
 pub use self::uuid::Uuid;
 #[doc(hidden)]
 pub use self::value::serde::from_value;
@@ -157,6 +160,7 @@ pub use self::value::Value;
 pub use self::value::Values;
 pub use self::version::Version;
 pub use self::view::View;
+pub use self::window::Window;
 pub use self::with::With;
 
 // module reexporting parsing function to prevent a breaking change.
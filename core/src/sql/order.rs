@@ -1,5 +1,6 @@
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
+use crate::sql::Strand;
 use crate::sql::Value;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -40,8 +41,8 @@ impl OrderList {
 		for order in &self.0 {
 			// Reverse the ordering if DESC
 			let o = match order.direction {
-				true => a.compare(b, &order.value.0, order.collate, order.numeric),
-				false => b.compare(a, &order.value.0, order.collate, order.numeric),
+				true => a.compare(b, &order.value.0, order.collate, order.locale(), order.numeric),
+				false => b.compare(a, &order.value.0, order.collate, order.locale(), order.numeric),
 			};
 			//
 			match o {
@@ -55,7 +56,7 @@ impl OrderList {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -63,16 +64,33 @@ pub struct Order {
 	/// The value to order by
 	pub value: Idiom,
 	pub collate: bool,
+	/// The locale whose collation rules are used, if specified
+	#[revision(start = 2)]
+	pub locale: Option<Strand>,
 	pub numeric: bool,
 	/// true if the direction is ascending
 	pub direction: bool,
 }
 
+impl Order {
+	/// The locale whose collation rules are used, if specified
+	pub(crate) fn locale(&self) -> Option<&str> {
+		self.locale.as_ref().map(|v| v.as_str())
+	}
+	/// Check if the values are ordered by their binary representation
+	pub(crate) fn is_binary(&self) -> bool {
+		!self.collate && !self.numeric
+	}
+}
+
 impl fmt::Display for Order {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", self.value)?;
 		if self.collate {
 			write!(f, " COLLATE")?;
+			if let Some(ref v) = self.locale {
+				write!(f, " {v}")?;
+			}
 		}
 		if self.numeric {
 			write!(f, " NUMERIC")?;
@@ -1,13 +1,20 @@
+use crate::cnf::MAX_COMPUTATION_DEPTH;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
 use crate::sql::{fmt::Fmt, strand::no_nul_bytes, Graph, Ident, Idiom, Number, Value};
+use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
 use std::str;
 
 use super::fmt::{is_pretty, pretty_indent};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -26,6 +33,8 @@ pub enum Part {
 	#[revision(start = 2)]
 	Destructure(Vec<DestructurePart>),
 	Optional,
+	#[revision(start = 3)]
+	Recurse(Recurse),
 }
 
 impl From<i32> for Part {
@@ -87,6 +96,7 @@ impl Part {
 			Part::Where(v) => v.writeable(),
 			Part::Value(v) => v.writeable(),
 			Part::Method(_, v) => v.iter().any(Value::writeable),
+			Part::Recurse(v) => v.path.iter().any(Part::writeable),
 			_ => false,
 		}
 	}
@@ -130,6 +140,7 @@ impl fmt::Display for Part {
 				}
 			}
 			Part::Optional => write!(f, "?"),
+			Part::Recurse(v) => write!(f, "{v}"),
 		}
 	}
 }
@@ -235,3 +246,103 @@ impl fmt::Display for DestructurePart {
 		}
 	}
 }
+
+// ------------------------------
+
+/// A graph traversal which is repeated between a minimum and maximum depth
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Recurse {
+	/// The minimum number of hops before records are output
+	pub min: u32,
+	/// The maximum number of hops to traverse
+	pub max: u32,
+	/// The edge and record traversal which makes up a single hop
+	pub path: Vec<Part>,
+}
+
+impl Recurse {
+	/// Walk the graph breadth-first from the specified records
+	///
+	/// Each record is only visited once, so cycles in the graph are not
+	/// followed, and records are output at the depth they are first reached.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		val: &Value,
+	) -> Result<Value, Error> {
+		// Limit the traversal depth
+		if self.max > *MAX_COMPUTATION_DEPTH {
+			return Err(Error::ComputationDepthExceeded);
+		}
+		// Collect the records to start from
+		let start = match val {
+			Value::Array(v) => v.iter().collect(),
+			v => vec![v],
+		};
+		let mut seen = HashSet::new();
+		let mut frontier = Vec::new();
+		for v in start {
+			let rid = match v {
+				Value::Thing(v) => v.clone(),
+				Value::Object(v) => match v.rid() {
+					Some(v) => v,
+					None => continue,
+				},
+				_ => continue,
+			};
+			if seen.insert(rid.clone()) {
+				frontier.push(Value::Thing(rid));
+			}
+		}
+		// A minimum depth of zero includes the starting records
+		let mut out = match self.min {
+			0 => frontier.clone(),
+			_ => Vec::new(),
+		};
+		// Traverse one hop at a time
+		for depth in 1..=self.max {
+			if frontier.is_empty() || ctx.is_done() {
+				break;
+			}
+			let next = Value::from(std::mem::take(&mut frontier));
+			let next = stk.run(|stk| next.get(stk, ctx, opt, doc, &self.path)).await?.flatten();
+			let Value::Array(next) = next else {
+				break;
+			};
+			for v in next {
+				if let Value::Thing(v) = v {
+					if seen.insert(v.clone()) {
+						if depth >= self.min {
+							out.push(Value::Thing(v.clone()));
+						}
+						frontier.push(Value::Thing(v));
+					}
+				}
+			}
+		}
+		Ok(out.into())
+	}
+}
+
+impl fmt::Display for Recurse {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut path = self.path.iter();
+		if let Some(p) = path.next() {
+			write!(f, "{p}")?;
+		}
+		match self.min == self.max {
+			true => write!(f, "{{{}}}", self.max)?,
+			false => write!(f, "{{{}..{}}}", self.min, self.max)?,
+		}
+		for p in path {
+			write!(f, "{p}")?;
+		}
+		Ok(())
+	}
+}
@@ -11,8 +11,9 @@ use crate::sql::{
 		CommitStatement, ContinueStatement, CreateStatement, DefineStatement, DeleteStatement,
 		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
 		LiveStatement, OptionStatement, OutputStatement, RelateStatement, RemoveStatement,
-		SelectStatement, SetStatement, ShowStatement, SleepStatement, ThrowStatement,
-		UpdateStatement, UpsertStatement, UseStatement,
+		RevokeStatement, RollbackStatement, SavepointStatement, SelectStatement, SetStatement,
+		ShowStatement, SleepStatement, ThrowStatement, UpdateStatement, UpsertStatement,
+		UseStatement,
 	},
 	value::Value,
 };
@@ -55,7 +56,7 @@ impl Display for Statements {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -97,6 +98,12 @@ pub enum Statement {
 	#[doc(hidden)]
 	#[revision(start = 5)]
 	Access(AccessStatement),
+	#[revision(start = 6)]
+	Savepoint(SavepointStatement),
+	#[revision(start = 6)]
+	Rollback(RollbackStatement),
+	#[revision(start = 7)]
+	Revoke(RevokeStatement),
 }
 
 impl Statement {
@@ -106,7 +113,7 @@ impl Statement {
 			Self::Value(v) => v.writeable(),
 			Self::Access(_) => true,
 			Self::Alter(_) => true,
-			Self::Analyze(_) => false,
+			Self::Analyze(v) => v.writeable(),
 			Self::Break(_) => false,
 			Self::Continue(_) => false,
 			Self::Create(v) => v.writeable(),
@@ -123,6 +130,7 @@ impl Statement {
 			Self::Rebuild(_) => true,
 			Self::Relate(v) => v.writeable(),
 			Self::Remove(_) => true,
+			Self::Revoke(_) => true,
 			Self::Select(v) => v.writeable(),
 			Self::Set(v) => v.writeable(),
 			Self::Show(_) => false,
@@ -177,6 +185,7 @@ impl Statement {
 			Self::Relate(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Rebuild(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Remove(v) => v.compute(ctx, opt, doc).await,
+			Self::Revoke(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Select(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Set(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Show(v) => v.compute(ctx, opt, doc).await,
@@ -221,6 +230,9 @@ impl Display for Statement {
 			Self::Rebuild(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
+			Self::Revoke(v) => write!(Pretty::from(f), "{v}"),
+			Self::Rollback(v) => write!(Pretty::from(f), "{v}"),
+			Self::Savepoint(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
 			Self::Set(v) => write!(Pretty::from(f), "{v}"),
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
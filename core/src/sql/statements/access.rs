@@ -16,6 +16,7 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 
 pub static GRANT_BEARER_PREFIX: &str = "surreal-bearer";
+pub static GRANT_KEY_PREFIX: &str = "surreal-key";
 // Keys and their identifiers are generated randomly from a 62-character pool.
 pub static GRANT_BEARER_CHARACTER_POOL: &[u8] =
 	b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
@@ -29,6 +30,9 @@ pub static GRANT_BEARER_KEY_LENGTH: usize = 24;
 // Total bearer key length.
 pub static GRANT_BEARER_LENGTH: usize =
 	GRANT_BEARER_PREFIX.len() + 1 + GRANT_BEARER_ID_LENGTH + 1 + GRANT_BEARER_KEY_LENGTH;
+// Total API key length.
+pub static GRANT_KEY_LENGTH: usize =
+	GRANT_KEY_PREFIX.len() + 1 + GRANT_BEARER_ID_LENGTH + 1 + GRANT_BEARER_KEY_LENGTH;
 
 // TODO(gguillemas): Document once bearer access is no longer experimental.
 #[doc(hidden)]
@@ -169,6 +173,7 @@ impl From<AccessGrant> for Object {
 		match grant.subject {
 			Subject::Record(id) => sub.insert("record".to_owned(), Value::from(id)),
 			Subject::User(name) => sub.insert("user".to_owned(), Value::from(name.to_raw())),
+			Subject::Key(name) => sub.insert("key".to_owned(), Value::from(name.to_raw())),
 		};
 		res.insert("subject".to_owned(), Value::from(sub));
 
@@ -198,13 +203,15 @@ impl From<AccessGrant> for Object {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum Subject {
 	Record(Thing),
 	User(Ident),
+	#[revision(start = 2)]
+	Key(Ident),
 }
 
 impl Subject {
@@ -213,6 +220,7 @@ impl Subject {
 		match self {
 			Subject::Record(id) => id.to_raw(),
 			Subject::User(name) => name.to_raw(),
+			Subject::Key(name) => name.to_raw(),
 		}
 	}
 }
@@ -269,6 +277,15 @@ pub struct GrantBearer {
 impl GrantBearer {
 	#[doc(hidden)]
 	pub fn new() -> Self {
+		Self::with_prefix(GRANT_BEARER_PREFIX)
+	}
+
+	/// Generates a new API key for a key access method.
+	pub fn new_key() -> Self {
+		Self::with_prefix(GRANT_KEY_PREFIX)
+	}
+
+	fn with_prefix(prefix: &str) -> Self {
 		let id = format!(
 			"{}{}",
 			// The pool for the first character of the key identifier excludes digits.
@@ -278,7 +295,7 @@ impl GrantBearer {
 		let secret = random_string(GRANT_BEARER_KEY_LENGTH, GRANT_BEARER_CHARACTER_POOL);
 		Self {
 			id: id.clone().into(),
-			key: format!("{GRANT_BEARER_PREFIX}-{id}-{secret}").into(),
+			key: format!("{prefix}-{id}-{secret}").into(),
 		}
 	}
 }
@@ -323,13 +340,17 @@ async fn compute_grant(
 		}
 	};
 	// Verify the access type.
-	match &ac.kind {
-		AccessType::Jwt(_) => Err(Error::FeatureNotYetImplemented {
-			feature: format!("Grants for JWT on {base}"),
-		}),
-		AccessType::Record(_) => Err(Error::FeatureNotYetImplemented {
-			feature: format!("Grants for record on {base}"),
-		}),
+	let grant = match &ac.kind {
+		AccessType::Jwt(_) => {
+			return Err(Error::FeatureNotYetImplemented {
+				feature: format!("Grants for JWT on {base}"),
+			})
+		}
+		AccessType::Record(_) => {
+			return Err(Error::FeatureNotYetImplemented {
+				feature: format!("Grants for record on {base}"),
+			})
+		}
 		AccessType::Bearer(at) => {
 			match &stmt.subject {
 				Subject::User(user) => {
@@ -358,72 +379,81 @@ async fn compute_grant(
 					}
 					// A grant can be created for a record that does not exist yet.
 				}
+				// API keys can only be created by key access methods.
+				Subject::Key(_) => return Err(Error::AccessGrantInvalidSubject),
 			};
 			// Create a new bearer key.
-			let grant = GrantBearer::new();
-			let gr = AccessGrant {
-				ac: ac.name.clone(),
-				// Unique grant identifier.
-				// In the case of bearer grants, the key identifier.
-				id: grant.id.clone(),
-				// Current time.
-				creation: Datetime::default(),
-				// Current time plus grant duration. Only if set.
-				expiration: ac.duration.grant.map(|d| d + Datetime::default()),
-				// The grant is initially not revoked.
-				revocation: None,
-				// Subject associated with the grant.
-				subject: stmt.subject.to_owned(),
-				// The contents of the grant.
-				grant: Grant::Bearer(grant),
-			};
-
-			// Create the grant.
-			// On the very unlikely event of a collision, "put" will return an error.
-			let res = match base {
-				Base::Root => {
-					let key = crate::key::root::access::gr::new(&gr.ac, &gr.id);
-					txn.put(key, &gr, None).await
-				}
-				Base::Ns => {
-					let key = crate::key::namespace::access::gr::new(opt.ns()?, &gr.ac, &gr.id);
-					txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
-					txn.put(key, &gr, None).await
-				}
-				Base::Db => {
-					let key =
-						crate::key::database::access::gr::new(opt.ns()?, opt.db()?, &gr.ac, &gr.id);
-					txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
-					txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
-					txn.put(key, &gr, None).await
-				}
-				_ => {
-					return Err(Error::Unimplemented(
-						"Managing access methods outside of root, namespace and database levels"
-							.to_string(),
-					))
-				}
-			};
-
-			// Check if a collision was found in order to log a specific error on the server.
-			// For an access method with a billion grants, this chance is of only one in 295 billion.
-			if let Err(Error::TxKeyAlreadyExists) = res {
-				error!("A collision was found when attempting to create a new grant. Purging inactive grants is advised")
+			GrantBearer::new()
+		}
+		AccessType::Key(_) => {
+			// Grant subject must be a named key.
+			if !matches!(&stmt.subject, Subject::Key(_)) {
+				return Err(Error::AccessGrantInvalidSubject);
 			}
-			res?;
-
-			info!(
-				"Access method '{}' was used to create grant '{}' of type '{}' for '{}' by '{}'",
-				gr.ac,
-				gr.id,
-				gr.grant.variant(),
-				gr.subject.id(),
-				opt.auth.id()
-			);
+			// Create a new API key.
+			GrantBearer::new_key()
+		}
+	};
+	let gr = AccessGrant {
+		ac: ac.name.clone(),
+		// Unique grant identifier.
+		// In the case of bearer grants, the key identifier.
+		id: grant.id.clone(),
+		// Current time.
+		creation: Datetime::default(),
+		// Current time plus grant duration. Only if set.
+		expiration: ac.duration.grant.map(|d| d + Datetime::default()),
+		// The grant is initially not revoked.
+		revocation: None,
+		// Subject associated with the grant.
+		subject: stmt.subject.to_owned(),
+		// The contents of the grant.
+		grant: Grant::Bearer(grant),
+	};
 
-			Ok(Value::Object(gr.into()))
+	// Create the grant.
+	// On the very unlikely event of a collision, "put" will return an error.
+	let res = match base {
+		Base::Root => {
+			let key = crate::key::root::access::gr::new(&gr.ac, &gr.id);
+			txn.put(key, &gr, None).await
+		}
+		Base::Ns => {
+			let key = crate::key::namespace::access::gr::new(opt.ns()?, &gr.ac, &gr.id);
+			txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+			txn.put(key, &gr, None).await
+		}
+		Base::Db => {
+			let key = crate::key::database::access::gr::new(opt.ns()?, opt.db()?, &gr.ac, &gr.id);
+			txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+			txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+			txn.put(key, &gr, None).await
+		}
+		_ => {
+			return Err(Error::Unimplemented(
+				"Managing access methods outside of root, namespace and database levels"
+					.to_string(),
+			))
 		}
+	};
+
+	// Check if a collision was found in order to log a specific error on the server.
+	// For an access method with a billion grants, this chance is of only one in 295 billion.
+	if let Err(Error::TxKeyAlreadyExists) = res {
+		error!("A collision was found when attempting to create a new grant. Purging inactive grants is advised")
 	}
+	res?;
+
+	info!(
+		"Access method '{}' was used to create grant '{}' of type '{}' for '{}' by '{}'",
+		gr.ac,
+		gr.id,
+		gr.grant.variant(),
+		gr.subject.id(),
+		opt.auth.id()
+	);
+
+	Ok(Value::Object(gr.into()))
 }
 
 async fn compute_show(
@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::idx::ft::FtIndex;
+use crate::idx::planner::statistics::TableStatistics;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::kvs::TransactionType;
@@ -17,15 +18,22 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum AnalyzeStatement {
 	Idx(Ident, Ident),
+	#[revision(start = 2)]
+	Table(Ident),
 }
 
 impl AnalyzeStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		matches!(self, Self::Table(_))
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -69,6 +77,19 @@ impl AnalyzeStatement {
 				// Return the result object
 				Ok(value)
 			}
+			AnalyzeStatement::Table(tb) => {
+				// Allowed to run?
+				opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+				// Check the table exists
+				let (ns, db) = (opt.ns()?, opt.db()?);
+				let tx = ctx.tx();
+				tx.get_tb(ns, db, tb).await?;
+				// Compute and store the statistics used by the query planner
+				let stats = TableStatistics::analyze(ctx, ns, db, tb).await?;
+				stats.set(&tx, ns, db, tb).await?;
+				// Return the result object
+				Ok(Value::from(&stats))
+			}
 		}
 	}
 }
@@ -77,6 +98,7 @@ impl Display for AnalyzeStatement {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Self::Idx(tb, idx) => write!(f, "ANALYZE INDEX {idx} ON {tb}"),
+			Self::Table(tb) => write!(f, "ANALYZE TABLE {tb}"),
 		}
 	}
 }
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, Level, ResourceKind, Role};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{access::AccessDuration, AccessType, Base, Ident, Strand, Value};
 use derive::Store;
@@ -52,6 +52,8 @@ impl DefineAccessStatement {
 				ac.jwt = ac.jwt.redacted();
 				AccessType::Bearer(ac)
 			}
+			// No secrets in key access methods
+			AccessType::Key(ac) => AccessType::Key(ac),
 		};
 		das
 	}
@@ -82,6 +84,10 @@ impl DefineAccessStatement {
 						});
 					}
 				}
+				// Check that the roles granted by API keys exist
+				if let AccessType::Key(ac) = &self.kind {
+					Role::resolve_with(&txn, &ac.roles, &Level::Root).await?;
+				}
 				// Process the statement
 				let key = crate::key::root::ac::new(&self.name);
 				txn.set(
@@ -114,6 +120,10 @@ impl DefineAccessStatement {
 						});
 					}
 				}
+				// Check that the roles granted by API keys exist
+				if let AccessType::Key(ac) = &self.kind {
+					Role::resolve_with(&txn, &ac.roles, &(opt.ns()?,).into()).await?;
+				}
 				// Process the statement
 				let key = crate::key::namespace::ac::new(opt.ns()?, &self.name);
 				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
@@ -148,6 +158,10 @@ impl DefineAccessStatement {
 						});
 					}
 				}
+				// Check that the roles granted by API keys exist
+				if let AccessType::Key(ac) = &self.kind {
+					Role::resolve_with(&txn, &ac.roles, &(opt.ns()?, opt.db()?).into()).await?;
+				}
 				// Process the statement
 				let key = crate::key::database::ac::new(opt.ns()?, opt.db()?, &self.name);
 				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
@@ -60,6 +60,7 @@ impl DefineAnalyzerStatement {
 			..self.clone()
 		};
 		ctx.get_index_stores().mappers().load(&az).await?;
+		ctx.get_index_stores().dictionaries().load(&az).await?;
 		txn.set(key, az, None).await?;
 		// Clear the cache
 		txn.clear();
@@ -10,7 +10,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -24,6 +24,9 @@ pub struct DefineEventStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub overwrite: bool,
+	/// Whether the event is queued and processed after the transaction commits
+	#[revision(start = 4)]
+	pub asynchronous: bool,
 }
 
 impl DefineEventStatement {
@@ -81,6 +84,9 @@ impl Display for DefineEventStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {} ON {} WHEN {} THEN {}", self.name, self.what, self.when, self.then)?;
+		if self.asynchronous {
+			write!(f, " ASYNC")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -95,6 +101,7 @@ impl InfoStructure for DefineEventStatement {
 			"what".to_string() => self.what.structure(),
 			"when".to_string() => self.when.structure(),
 			"then".to_string() => self.then.structure(),
+			"async".to_string(), if self.asynchronous => true.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -14,7 +14,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,6 +34,36 @@ pub struct DefineFieldStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 4)]
 	pub overwrite: bool,
+	#[revision(start = 5)]
+	pub check: Option<Value>,
+	#[revision(start = 6)]
+	pub on_delete: Option<OnDelete>,
+	#[revision(start = 7)]
+	pub computed: Option<Value>,
+}
+
+/// What happens to a record when a record it links to is deleted
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum OnDelete {
+	/// The linked record can not be deleted while it is referenced
+	Restrict,
+	/// The referencing record is deleted along with the linked record
+	Cascade,
+	/// The link is removed from the referencing record
+	Unset,
+}
+
+impl Display for OnDelete {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Restrict => f.write_str("RESTRICT"),
+			Self::Cascade => f.write_str("CASCADE"),
+			Self::Unset => f.write_str("SET NONE"),
+		}
+	}
 }
 
 impl DefineFieldStatement {
@@ -223,9 +253,18 @@ impl Display for DefineFieldStatement {
 		if let Some(ref v) = self.value {
 			write!(f, " VALUE {v}")?
 		}
+		if let Some(ref v) = self.computed {
+			write!(f, " COMPUTED {v}")?
+		}
 		if let Some(ref v) = self.assert {
 			write!(f, " ASSERT {v}")?
 		}
+		if let Some(ref v) = self.check {
+			write!(f, " CHECK {v}")?
+		}
+		if let Some(ref v) = self.on_delete {
+			write!(f, " ON DELETE {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -252,7 +291,10 @@ impl InfoStructure for DefineFieldStatement {
 			"flex".to_string() => self.flex.into(),
 			"kind".to_string(), if let Some(v) = self.kind => v.structure(),
 			"value".to_string(), if let Some(v) = self.value => v.structure(),
+			"computed".to_string(), if let Some(v) = self.computed => v.structure(),
 			"assert".to_string(), if let Some(v) = self.assert => v.structure(),
+			"check".to_string(), if let Some(v) = self.check => v.structure(),
+			"on_delete".to_string(), if let Some(v) = self.on_delete => v.to_string().into(),
 			"default".to_string(), if let Some(v) = self.default => v.structure(),
 			"readonly".to_string() => self.readonly.into(),
 			"permissions".to_string() => self.permissions.structure(),
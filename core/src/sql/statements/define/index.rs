@@ -3,9 +3,11 @@ use crate::dbs::{Force, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::sql::fmt::Fmt;
+use crate::sql::index::Collation;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::UpdateStatement;
-use crate::sql::{Base, Ident, Idioms, Index, Output, Part, Strand, Value, Values};
+use crate::sql::{Base, Cond, Ident, Idioms, Index, Output, Part, Strand, Value, Values};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -13,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +31,12 @@ pub struct DefineIndexStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub concurrently: bool,
+	/// Only the records matching this condition are indexed
+	#[revision(start = 5)]
+	pub cond: Option<Cond>,
+	/// The normalisations applied to the indexed strings
+	#[revision(start = 6)]
+	pub collate: Vec<Collation>,
 }
 
 impl DefineIndexStatement {
@@ -92,18 +100,35 @@ impl DefineIndexStatement {
 		.await?;
 		// Clear the cache
 		txn.clear();
-		#[cfg(not(target_arch = "wasm32"))]
-		if self.concurrently {
-			self.async_index(ctx, opt)?;
-		} else {
-			self.sync_index(stk, ctx, opt, doc).await?;
-		}
-		#[cfg(target_arch = "wasm32")]
-		self.sync_index(stk, ctx, opt, doc).await?;
+		// Build the index
+		self.build(stk, ctx, opt, doc, self.concurrently).await?;
 		// Ok all good
 		Ok(Value::None)
 	}
 
+	/// Applies the collations of this index to a value
+	pub(crate) fn collate(&self, v: Value) -> Value {
+		self.collate.iter().fold(v, |v, c| c.apply(v))
+	}
+
+	/// Builds the index from the records of the table, either within the
+	/// current transaction, or concurrently using the background index builder
+	#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+	pub(crate) async fn build(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		concurrently: bool,
+	) -> Result<(), Error> {
+		#[cfg(not(target_arch = "wasm32"))]
+		if concurrently {
+			return self.async_index(ctx, opt);
+		}
+		self.sync_index(stk, ctx, opt, doc).await
+	}
+
 	async fn sync_index(
 		&self,
 		stk: &mut Stk,
@@ -143,9 +168,15 @@ impl Display for DefineIndexStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {} ON {} FIELDS {}", self.name, self.what, self.cols)?;
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
+		if !self.collate.is_empty() {
+			write!(f, " COLLATE {}", Fmt::comma_separated(&self.collate))?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -163,6 +194,7 @@ impl InfoStructure for DefineIndexStatement {
 			"what".to_string() => self.what.structure(),
 			"cols".to_string() => self.cols.structure(),
 			"index".to_string() => self.index.structure(),
+			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -10,7 +10,10 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
+mod sink;
 mod table;
+mod r#type;
 mod user;
 
 pub use access::DefineAccessStatement;
@@ -18,12 +21,15 @@ pub use analyzer::DefineAnalyzerStatement;
 pub use config::DefineConfigStatement;
 pub use database::DefineDatabaseStatement;
 pub use event::DefineEventStatement;
-pub use field::DefineFieldStatement;
+pub use field::{DefineFieldStatement, OnDelete};
 pub use function::DefineFunctionStatement;
 pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use r#type::DefineTypeStatement;
+pub use role::{DefineRoleStatement, RoleAction, RoleGrant};
+pub use sink::{DefineSinkStatement, SinkKind};
 pub use table::DefineTableStatement;
 pub use user::DefineUserStatement;
 
@@ -43,7 +49,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -74,6 +80,12 @@ pub enum DefineStatement {
 	#[revision(start = 2)]
 	Access(DefineAccessStatement),
 	Config(DefineConfigStatement),
+	#[revision(start = 3)]
+	Type(DefineTypeStatement),
+	#[revision(start = 4)]
+	Sink(DefineSinkStatement),
+	#[revision(start = 5)]
+	Role(DefineRoleStatement),
 }
 
 // Revision implementations
@@ -120,6 +132,9 @@ impl DefineStatement {
 			Self::Model(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Access(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Config(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Type(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Sink(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Role(ref v) => v.compute(ctx, opt, doc).await,
 		}
 	}
 }
@@ -140,6 +155,9 @@ impl Display for DefineStatement {
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Access(v) => Display::fmt(v, f),
 			Self::Config(v) => Display::fmt(v, f),
+			Self::Type(v) => Display::fmt(v, f),
+			Self::Sink(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
 		}
 	}
 }
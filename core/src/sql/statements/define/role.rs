@@ -0,0 +1,262 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind, Role};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{fmt::Fmt, Base, Ident, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineRoleStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub grants: Vec<RoleGrant>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+/// An action which a role allows on a set of resources
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RoleGrant {
+	pub action: RoleAction,
+	// The resources the action is allowed on, or any resource when empty
+	pub kinds: Vec<ResourceKind>,
+	// The namespace the grant is limited to, for roles defined on the root
+	pub ns: Option<Ident>,
+	// The database the grant is limited to, for roles defined on the root or a namespace
+	pub db: Option<Ident>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum RoleAction {
+	#[default]
+	View,
+	// Editing a resource also allows viewing it
+	Edit,
+}
+
+impl Display for RoleAction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::View => f.write_str("VIEW"),
+			Self::Edit => f.write_str("EDIT"),
+		}
+	}
+}
+
+/// The statement keyword for a kind of resource which can be granted in a role
+pub(crate) fn kind_keyword(kind: &ResourceKind) -> &'static str {
+	match kind {
+		ResourceKind::Namespace => "NAMESPACE",
+		ResourceKind::Database => "DATABASE",
+		ResourceKind::Table => "TABLE",
+		ResourceKind::Field => "FIELD",
+		ResourceKind::Index => "INDEX",
+		ResourceKind::Event => "EVENT",
+		ResourceKind::Function => "FUNCTION",
+		ResourceKind::Parameter => "PARAM",
+		ResourceKind::Analyzer => "ANALYZER",
+		ResourceKind::Model => "MODEL",
+		ResourceKind::Access => "ACCESS",
+		ResourceKind::Type => "TYPE",
+		ResourceKind::Sink => "SINK",
+		ResourceKind::Actor => "USER",
+		_ => "ANY",
+	}
+}
+
+impl Display for RoleGrant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "GRANT {}", self.action)?;
+		if !self.kinds.is_empty() {
+			write!(f, " ON {}", Fmt::comma_separated(self.kinds.iter().map(kind_keyword)))?;
+		}
+		match (&self.ns, &self.db) {
+			(Some(ns), Some(db)) => write!(f, " IN NAMESPACE {ns} DATABASE {db}")?,
+			(Some(ns), None) => write!(f, " IN NAMESPACE {ns}")?,
+			(None, Some(db)) => write!(f, " IN DATABASE {db}")?,
+			(None, None) => {}
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for RoleGrant {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"action".to_string() => self.action.to_string().into(),
+			"on".to_string() => self.kinds.iter().map(|k| Value::from(kind_keyword(k))).collect::<Vec<_>>().into(),
+			"ns".to_string(), if let Some(v) = self.ns => v.structure(),
+			"db".to_string(), if let Some(v) = self.db => v.structure(),
+		})
+	}
+}
+
+impl DefineRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Predefined roles can not be redefined
+		if Role::from_str(&self.name).is_ok() {
+			return Err(Error::RoleReserved {
+				value: self.name.to_string(),
+			});
+		}
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_root_role(&self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleRootAlreadyExists {
+							value: self.name.to_string(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::root::rl::new(&self.name);
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_ns_role(opt.ns()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleNsAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::namespace::rl::new(opt.ns()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_db_role(opt.ns()?, opt.db()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleDbAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+							db: opt.db()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::database::rl::new(opt.ns()?, opt.db()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			// Other levels are not supported
+			_ => Err(Error::InvalidLevel(self.base.to_string())),
+		}
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for grant in self.grants.iter() {
+			write!(f, " {grant}")?;
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineRoleStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"base".to_string() => self.base.structure(),
+			"grants".to_string() => self.grants.into_iter().map(InfoStructure::structure).collect::<Vec<_>>().into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
@@ -0,0 +1,138 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineSinkStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub kind: SinkKind,
+	pub url: Strand,
+	pub topic: Strand,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+/// The message broker which changes are delivered to
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum SinkKind {
+	#[default]
+	Kafka,
+	Nats,
+}
+
+impl Display for SinkKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Kafka => f.write_str("KAFKA"),
+			Self::Nats => f.write_str("NATS"),
+		}
+	}
+}
+
+impl DefineSinkStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Sink, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		if txn.get_tb_sink(opt.ns()?, opt.db()?, &self.what, &self.name).await.is_ok() {
+			if self.if_not_exists {
+				return Ok(Value::None);
+			} else if !self.overwrite {
+				return Err(Error::SkAlreadyExists {
+					value: self.name.to_string(),
+				});
+			}
+		}
+		// Process the statement
+		let key = crate::key::table::sk::new(opt.ns()?, opt.db()?, &self.what, &self.name);
+		txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+		let db = txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+		let tb = txn.get_or_add_tb(opt.ns()?, opt.db()?, &self.what, opt.strict).await?;
+		// Changes can only be delivered from a changefeed
+		if db.changefeed.is_none() && tb.changefeed.is_none() {
+			return Err(Error::SkNoChangeFeed {
+				value: self.name.to_string(),
+				table: self.what.to_string(),
+			});
+		}
+		txn.set(
+			key,
+			DefineSinkStatement {
+				// Don't persist the `IF NOT EXISTS` clause to schema
+				if_not_exists: false,
+				overwrite: false,
+				..self.clone()
+			},
+			None,
+		)
+		.await?;
+		// Deliver the changes committed from now on
+		let key = crate::key::table::so::new(opt.ns()?, opt.db()?, &self.what, &self.name);
+		if !txn.exists(key.clone(), None).await? {
+			let vs = txn.lock().await.get_versionstamp_lower_bound(opt.ns()?, opt.db()?).await?;
+			txn.set(key, vs.to_be_bytes().to_vec(), None).await?;
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineSinkStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE SINK")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(
+			f,
+			" {} FOR TABLE {} TYPE {} URL {} TOPIC {}",
+			self.name, self.what, self.kind, self.url, self.topic
+		)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineSinkStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"what".to_string() => self.what.structure(),
+			"kind".to_string() => self.kind.to_string().into(),
+			"url".to_string() => self.url.into(),
+			"topic".to_string() => self.topic.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
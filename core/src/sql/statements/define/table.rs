@@ -4,7 +4,7 @@ use crate::dbs::{Force, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::kvs::Transaction;
+use crate::kvs::{Count, Transaction};
 use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::paths::{IN, OUT};
 use crate::sql::statements::info::InfoStructure;
@@ -12,15 +12,16 @@ use crate::sql::{
 	changefeed::ChangeFeed, statements::UpdateStatement, Base, Ident, Output, Permissions, Strand,
 	Value, Values, View,
 };
-use crate::sql::{Idiom, Kind, TableType};
+use crate::sql::{Datetime, Duration, Idiom, Kind, TableType, Thing, Ttl};
 use derive::Store;
+use futures::StreamExt;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -39,6 +40,10 @@ pub struct DefineTableStatement {
 	pub kind: TableType,
 	#[revision(start = 4)]
 	pub overwrite: bool,
+	#[revision(start = 5)]
+	pub partition: Option<Value>,
+	#[revision(start = 6)]
+	pub ttl: Option<Ttl>,
 }
 
 impl DefineTableStatement {
@@ -54,7 +59,8 @@ impl DefineTableStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb(opt.ns()?, opt.db()?, &self.name).await.is_ok() {
+		let prev = txn.get_tb(opt.ns()?, opt.db()?, &self.name).await.ok();
+		if prev.is_some() {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite {
@@ -79,6 +85,10 @@ impl DefineTableStatement {
 			..self.clone()
 		};
 		txn.set(key, &dt, None).await?;
+		// Start counting the rows of a new table
+		if prev.is_none() {
+			txn.record_count(opt.ns()?, opt.db()?, &self.name, Count::base()).await;
+		}
 		// Add table relational fields
 		self.add_in_out_fields(&txn, opt).await?;
 		// Clear the cache
@@ -87,6 +97,14 @@ impl DefineTableStatement {
 		if dt.changefeed.is_some() {
 			txn.lock().await.record_table_change(opt.ns()?, opt.db()?, &self.name, &dt);
 		}
+		// Check if the table partitioning has changed
+		if prev.as_ref().is_some_and(|v| v.partition != self.partition) {
+			self.rebuild_partitions(stk, ctx, opt).await?;
+		}
+		// Check if the table TTL has changed
+		if prev.as_ref().is_some_and(|v| v.ttl != self.ttl) {
+			self.rebuild_ttl(ctx, opt).await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
 			// Remove the table data
@@ -131,6 +149,75 @@ impl DefineTableStatement {
 	pub fn allows_normal(&self) -> bool {
 		matches!(self.kind, TableType::Normal | TableType::Any)
 	}
+	/// Used to recompute the partition of existing table records
+	async fn rebuild_partitions(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<(), Error> {
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Remove the existing partition entries
+		let beg = crate::key::partition::table_prefix(opt.ns()?, opt.db()?, &self.name);
+		let end = crate::key::partition::table_suffix(opt.ns()?, opt.db()?, &self.name);
+		txn.delr(beg..end).await?;
+		// Check if the table is partitioned
+		let Some(expr) = &self.partition else {
+			return Ok(());
+		};
+		// Loop over all of the table records
+		let beg = crate::key::thing::prefix(opt.ns()?, opt.db()?, &self.name);
+		let end = crate::key::thing::suffix(opt.ns()?, opt.db()?, &self.name);
+		let mut stream = txn.stream(beg..end, None);
+		while let Some(res) = stream.next().await {
+			let (k, v) = res?;
+			let key: crate::key::thing::Thing = (&k).into();
+			let rid = Thing::from((key.tb, key.id));
+			// Compute the partition of the record
+			let doc = CursorDoc::new(Some(rid.clone().into()), None, Value::from(&v));
+			let pt = expr.compute(stk, ctx, opt, Some(&doc)).await?;
+			// Store the partition entry
+			let key = crate::key::partition::new(opt.ns()?, opt.db()?, &self.name, &pt, &rid.id);
+			txn.set(key, vec![], None).await?;
+		}
+		Ok(())
+	}
+	/// Used to recompute the expiry time of existing table records
+	async fn rebuild_ttl(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Remove the existing expiry entries
+		let beg = crate::key::ttl::prefix(opt.ns()?, opt.db()?, &self.name);
+		let end = crate::key::ttl::suffix(opt.ns()?, opt.db()?, &self.name);
+		txn.delr(beg..end).await?;
+		// Check if the table has a TTL
+		let Some(ttl) = &self.ttl else {
+			return Ok(());
+		};
+		// Existing records expire from now
+		let now = Datetime::default();
+		// Loop over all of the table records
+		let beg = crate::key::thing::prefix(opt.ns()?, opt.db()?, &self.name);
+		let end = crate::key::thing::suffix(opt.ns()?, opt.db()?, &self.name);
+		let mut stream = txn.stream(beg..end, None);
+		while let Some(res) = stream.next().await {
+			let (k, v) = res?;
+			let key: crate::key::thing::Thing = (&k).into();
+			// Calculate when the record expires
+			let val = match &ttl.field {
+				None => Duration(ttl.duration) + now.clone(),
+				Some(field) => match Value::from(&v).pick(field) {
+					Value::Datetime(v) => Duration(ttl.duration) + v,
+					_ => continue,
+				},
+			};
+			// Store the expiry entry
+			let key = crate::key::ttl::new(opt.ns()?, opt.db()?, &self.name, &key.id);
+			txn.set(key, Value::from(val), None).await?;
+		}
+		Ok(())
+	}
 	/// Used to add relational fields to existing table records
 	pub async fn add_in_out_fields(&self, txn: &Transaction, opt: &Options) -> Result<(), Error> {
 		// Add table relational fields
@@ -219,6 +306,9 @@ impl Display for DefineTableStatement {
 		} else {
 			" SCHEMALESS"
 		})?;
+		if let Some(ref v) = self.partition {
+			write!(f, " PARTITION BY {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -228,6 +318,9 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.ttl {
+			write!(f, " {v}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -248,6 +341,8 @@ impl InfoStructure for DefineTableStatement {
 			"kind".to_string() => self.kind.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"partition".to_string(), if let Some(v) = self.partition => v.structure(),
+			"ttl".to_string(), if let Some(v) = self.ttl => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, Level, ResourceKind, Role};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
 	escape::quote_str, fmt::Fmt, user::UserDuration, Base, Duration, Ident, Strand, Value,
@@ -123,6 +123,8 @@ impl DefineUserStatement {
 						});
 					}
 				}
+				// Check that the roles of the user exist
+				Role::resolve_with(&txn, &self.roles, &Level::Root).await?;
 				// Process the statement
 				let key = crate::key::root::us::new(&self.name);
 				txn.set(
@@ -155,6 +157,8 @@ impl DefineUserStatement {
 						});
 					}
 				}
+				// Check that the roles of the user exist
+				Role::resolve_with(&txn, &self.roles, &(opt.ns()?,).into()).await?;
 				// Process the statement
 				let key = crate::key::namespace::us::new(opt.ns()?, &self.name);
 				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
@@ -189,6 +193,8 @@ impl DefineUserStatement {
 						});
 					}
 				}
+				// Check that the roles of the user exist
+				Role::resolve_with(&txn, &self.roles, &(opt.ns()?, opt.db()?).into()).await?;
 				// Process the statement
 				let key = crate::key::database::us::new(opt.ns()?, opt.db()?, &self.name);
 				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
+use crate::kvs::{Transaction, Usage};
 use crate::sql::{Base, Ident, Object, Value, Version};
 use derive::Store;
 use revision::revisioned;
@@ -11,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -36,6 +37,9 @@ pub enum InfoStatement {
 	#[revision(start = 3)]
 	#[revision(override(revision = 3, discriminant = 10))]
 	Index(Ident, Ident, bool),
+
+	#[revision(start = 6)]
+	Queries(bool),
 }
 
 impl InfoStatement {
@@ -52,12 +56,15 @@ impl InfoStatement {
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the custom roles
+				let roles = txn.all_root_roles().await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_root_accesses().await?.iter().map(|v| v.redacted()).collect()),
 						"namespaces".to_string() => process(txn.all_ns().await?),
 						"nodes".to_string() => process(txn.all_nodes().await?),
+						"roles".to_string(), if !roles.is_empty() => process(roles.clone()),
 						"users".to_string() => process(txn.all_root_users().await?),
 					}),
 					false => Value::from(map! {
@@ -82,6 +89,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"roles".to_string(), if !roles.is_empty() => {
+							let mut out = Object::default();
+							for v in roles.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_root_users().await?.iter() {
@@ -99,11 +113,17 @@ impl InfoStatement {
 				let ns = opt.ns()?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the storage usage, if tracked
+				let usage = ns_usage(&txn, ns).await?;
+				// Get the custom roles
+				let roles = txn.all_ns_roles(ns).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_ns_accesses(ns).await?.iter().map(|v| v.redacted()).collect()),
 						"databases".to_string() => process(txn.all_db(ns).await?),
+						"usage".to_string(), if let Some(usage) = usage => usage,
+						"roles".to_string(), if !roles.is_empty() => process(roles.clone()),
 						"users".to_string() => process(txn.all_ns_users(ns).await?),
 					}),
 					false => Value::from(map! {
@@ -121,6 +141,14 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"usage".to_string(), if let Some(usage) = usage => usage,
+						"roles".to_string(), if !roles.is_empty() => {
+							let mut out = Object::default();
+							for v in roles.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_ns_users(ns).await?.iter() {
@@ -141,6 +169,8 @@ impl InfoStatement {
 				let version = version.as_ref().map(|v| v.to_u64());
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the custom roles
+				let roles = txn.all_db_roles(ns, db).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
@@ -150,6 +180,8 @@ impl InfoStatement {
 						"models".to_string() => process(txn.all_db_models(ns, db).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db).await?),
 						"tables".to_string() => process(txn.all_tb(ns, db, version).await?),
+						"types".to_string() => process(txn.all_db_types(ns, db).await?),
+						"roles".to_string(), if !roles.is_empty() => process(roles.clone()),
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
 					}),
@@ -196,6 +228,20 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"types".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_types(ns, db).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"roles".to_string(), if !roles.is_empty() => {
+							let mut out = Object::default();
+							for v in roles.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_users(ns, db).await?.iter() {
@@ -224,7 +270,7 @@ impl InfoStatement {
 				// Get the transaction
 				let txn = ctx.tx();
 				// Create the result set
-				Ok(match structured {
+				let mut res = match structured {
 					true => Value::from(map! {
 						"events".to_string() => process(txn.all_tb_events(ns, db, tb).await?),
 						"fields".to_string() => process(txn.all_tb_fields(ns, db, tb, version).await?),
@@ -269,7 +315,65 @@ impl InfoStatement {
 							out.into()
 						},
 					}),
-				})
+				};
+				// Add the sinks which deliver the table changefeed
+				let sinks = txn.all_tb_sinks(ns, db, tb).await?;
+				if !sinks.is_empty() {
+					let sinks = match structured {
+						true => process(sinks),
+						false => {
+							let mut out = Object::default();
+							for v in sinks.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						}
+					};
+					if let Value::Object(o) = &mut res {
+						o.insert("sinks".to_string(), sinks);
+					}
+				}
+				// Add the progress of the indexes being built
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(ib) = ctx.get_index_builder() {
+					let mut building = Object::default();
+					for ix in txn.all_tb_indexes(ns, db, tb).await?.iter() {
+						if let Some(status) = ib.get_status(ix).await.filter(|s| !s.is_built()) {
+							building.insert(ix.name.to_raw(), status.into());
+						}
+					}
+					if !building.is_empty() {
+						if let Value::Object(o) = &mut res {
+							o.insert("building".to_string(), building.into());
+						}
+					}
+				}
+				// Add the size of the table changefeed
+				if version.is_none() {
+					let changefeed = match txn.get_tb(ns, db, tb).await {
+						Ok(v) => {
+							v.changefeed.is_some() || txn.get_db(ns, db).await?.changefeed.is_some()
+						}
+						Err(Error::TbNotFound {
+							..
+						}) => false,
+						Err(e) => return Err(e),
+					};
+					if changefeed {
+						let (entries, size) =
+							crate::cf::size(&txn, ns, db, Some(tb.as_str())).await?;
+						if let Value::Object(o) = &mut res {
+							o.insert(
+								"changefeed".to_string(),
+								Value::from(map! {
+									"entries".to_string() => entries.into(),
+									"size".to_string() => size.into(),
+								}),
+							);
+						}
+					}
+				}
+				Ok(res)
 			}
 			InfoStatement::User(user, base, structured) => {
 				// Get the base type
@@ -310,6 +414,15 @@ impl InfoStatement {
 				}
 				Ok(Object::default().into())
 			}
+			InfoStatement::Queries(_) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+				// Output the queries running on this node
+				Ok(match ctx.get_running_queries() {
+					Some(queries) => queries.list(),
+					None => Value::from(Vec::<Value>::new()),
+				})
+			}
 		}
 	}
 }
@@ -348,6 +461,8 @@ impl fmt::Display for InfoStatement {
 			},
 			Self::Index(ref i, ref t, false) => write!(f, "INFO FOR INDEX {i} ON {t}"),
 			Self::Index(ref i, ref t, true) => write!(f, "INFO FOR INDEX {i} ON {t} STRUCTURE"),
+			Self::Queries(false) => f.write_str("INFO FOR QUERIES"),
+			Self::Queries(true) => f.write_str("INFO FOR QUERIES STRUCTURE"),
 		}
 	}
 }
@@ -365,6 +480,7 @@ impl InfoStatement {
 			InfoStatement::Tb(t, _, v) => InfoStatement::Tb(t, true, v),
 			InfoStatement::User(u, b, _) => InfoStatement::User(u, b, true),
 			InfoStatement::Index(i, t, _) => InfoStatement::Index(i, t, true),
+			InfoStatement::Queries(_) => InfoStatement::Queries(true),
 		}
 	}
 
@@ -377,6 +493,27 @@ impl InfoStatement {
 	}
 }
 
+/// Fetch the storage usage of a namespace and its databases, if tracked
+async fn ns_usage(txn: &Transaction, ns: &str) -> Result<Option<Value>, Error> {
+	let mut total = Usage::default();
+	let mut dbs = Object::default();
+	for db in txn.all_db(ns).await?.iter() {
+		let usage = txn.get_db_usage(ns, &db.name).await?;
+		if usage != Usage::default() {
+			total = total + usage;
+			dbs.insert(db.name.to_raw(), usage.structure());
+		}
+	}
+	if dbs.is_empty() {
+		return Ok(None);
+	}
+	Ok(Some(Value::from(map! {
+		"bytes".to_string() => total.bytes.into(),
+		"databases".to_string() => dbs.into(),
+		"keys".to_string() => total.keys.into(),
+	})))
+}
+
 fn process<T>(a: Arc<[T]>) -> Value
 where
 	T: InfoStructure + Clone,
@@ -2,15 +2,16 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
 use crate::kvs::Live;
-use crate::sql::Value;
+use crate::sql::{Base, Value};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -18,6 +19,9 @@ pub struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
 	pub id: Value,
+	// Whether this kills a running query instead of a live query
+	#[revision(start = 2)]
+	pub query: bool,
 }
 
 impl KillStatement {
@@ -29,6 +33,10 @@ impl KillStatement {
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
+		// Are we killing a running query?
+		if self.query {
+			return self.kill_query(stk, ctx, opt).await;
+		}
 		// Is realtime enabled?
 		opt.realtime()?;
 		// Valid options?
@@ -63,6 +71,9 @@ impl KillStatement {
 				// Delete the table live query
 				let key = crate::key::table::lq::new(&val.ns, &val.db, &val.tb, lid);
 				txn.del(key).await?;
+				// Delete any aggregates of the live query
+				let key = crate::key::table::la::prefix(&val.ns, &val.db, &val.tb, lid);
+				txn.delp(key).await?;
 			}
 			None => {
 				return Err(Error::KillStatement {
@@ -73,10 +84,41 @@ impl KillStatement {
 		// Return the query id
 		Ok(Value::None)
 	}
+
+	/// Cancel a query which is currently running on this node
+	async fn kill_query(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root)?;
+		// Resolve the running query id
+		let id = match self.id.compute(stk, ctx, opt, None).await?.convert_to_uuid() {
+			Err(_) => {
+				return Err(Error::KillStatement {
+					value: self.id.to_string(),
+				})
+			}
+			Ok(id) => id,
+		};
+		// Cancel the query if it is still running
+		match ctx.get_running_queries().map(|q| q.kill(&id)) {
+			Some(true) => Ok(Value::None),
+			_ => Err(Error::KillStatement {
+				value: self.id.to_string(),
+			}),
+		}
+	}
 }
 
 impl fmt::Display for KillStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "KILL {}", self.id)
+		if self.query {
+			write!(f, "KILL QUERY {}", self.id)
+		} else {
+			write!(f, "KILL {}", self.id)
+		}
 	}
 }
@@ -1,18 +1,21 @@
+use crate::cf::{self, ChangeSet, DatabaseMutation, TableMutations};
 use crate::ctx::Context;
 use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::iam::Auth;
 use crate::kvs::Live;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Cond, Fetchs, Fields, Uuid, Value};
+use crate::sql::statements::show::ShowSince;
+use crate::sql::{Cond, Fetchs, Field, Fields, Function, Groups, Uuid, Value};
+use crate::vs;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -35,6 +38,17 @@ pub struct LiveStatement {
 	// This is optional as it is only set by the database
 	// runtime when storing the live query to storage.
 	pub(crate) session: Option<Value>,
+	// When a live query is resumed, any changes which
+	// were missed after the resume versionstamp, which
+	// is the last one seen by the client, are replayed
+	// from the change feed for the table.
+	#[revision(start = 2)]
+	pub resume: Option<Value>,
+	// When a live query has a GROUP clause, the
+	// aggregates of each group are maintained as
+	// records change, and are sent as notifications.
+	#[revision(start = 3)]
+	pub group: Option<Groups>,
 }
 
 impl LiveStatement {
@@ -53,7 +67,9 @@ impl LiveStatement {
 		expr: Fields,
 		what: Value,
 		cond: Option<Cond>,
+		group: Option<Groups>,
 		fetch: Option<Fetchs>,
+		resume: Option<Value>,
 	) -> Self {
 		LiveStatement {
 			id: Uuid::new_v4(),
@@ -61,11 +77,68 @@ impl LiveStatement {
 			expr,
 			what,
 			cond,
+			group,
 			fetch,
+			resume,
 			..Default::default()
 		}
 	}
 
+	/// Checks if this live query sends JSON Patch changes instead of records
+	pub(crate) fn is_diff(&self) -> bool {
+		self.expr.is_empty()
+	}
+
+	/// Checks if this live query sends the aggregates of each group instead of records
+	pub(crate) fn is_aggregate(&self) -> bool {
+		self.group.is_some()
+	}
+
+	/// Checks that the aggregates of a grouped live query can be maintained incrementally
+	fn check_aggregate(&self) -> Result<(), Error> {
+		let Some(group) = &self.group else {
+			return Ok(());
+		};
+		// The output of each change can not be computed for a group
+		let clause = if self.is_diff() {
+			Some("DIFF")
+		} else if self.fetch.is_some() {
+			Some("FETCH")
+		} else if self.resume.is_some() {
+			Some("RESUME")
+		} else {
+			None
+		};
+		if let Some(clause) = clause {
+			return Err(Error::LiveAggregateClause {
+				clause: clause.to_owned(),
+			});
+		}
+		// Each field must be an invertible aggregate, or a grouped field
+		for field in self.expr.iter() {
+			let ok = match field {
+				Field::Single {
+					expr: Value::Function(f),
+					..
+				} => matches!(
+					f.as_ref(),
+					Function::Normal(n, _) if matches!(n.as_str(), "count" | "math::sum" | "math::mean")
+				),
+				Field::Single {
+					expr: Value::Idiom(i),
+					..
+				} => group.iter().any(|g| g.0 == *i),
+				_ => false,
+			};
+			if !ok {
+				return Err(Error::LiveAggregate {
+					value: field.to_string(),
+				});
+			}
+		}
+		Ok(())
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -78,6 +151,8 @@ impl LiveStatement {
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
+		// Check any aggregates can be maintained
+		self.check_aggregate()?;
 		// Get the Node ID
 		let nid = opt.id()?;
 		// Check that auth has been set
@@ -88,12 +163,27 @@ impl LiveStatement {
 			// Use the current session authentication
 			// for when we store the LIVE Statement
 			session: ctx.value("session").cloned(),
+			// The resume versionstamp is only used
+			// when the LIVE Statement is created
+			resume: None,
 			// Clone the rest of the original fields
 			// from the LIVE statement to the new one
 			..self.clone()
 		};
 		// Get the id
 		let id = stm.id.0;
+		// Process the resume versionstamp
+		let resume = match &self.resume {
+			Some(v) => match v.compute(stk, ctx, opt, doc).await? {
+				Value::Number(v) if v.is_int() && v.to_int() >= 0 => Some(v.to_int() as u64),
+				v => {
+					return Err(Error::LiveResume {
+						value: v.to_string(),
+					})
+				}
+			},
+			None => None,
+		};
 		// Process the live query table
 		match stm.what.compute(stk, ctx, opt, doc).await? {
 			Value::Table(tb) => {
@@ -119,7 +209,17 @@ impl LiveStatement {
 				txn.put(key, lq, None).await?;
 				// Insert the table live query
 				let key = crate::key::table::lq::new(ns, db, &tb, id);
-				txn.put(key, stm, None).await?;
+				txn.put(key, stm.clone(), None).await?;
+				// Drop the transaction lock
+				drop(txn);
+				// Aggregate the records which already exist
+				if stm.is_aggregate() {
+					Document::seed_live_aggregate(stk, ctx, opt, &stm, &tb).await?;
+				}
+				// Replay any changes since the resume versionstamp
+				if let Some(since) = resume {
+					Self::replay(stk, ctx, opt, &stm, &tb, since).await?;
+				}
 			}
 			v => {
 				return Err(Error::LiveStatement {
@@ -130,17 +230,78 @@ impl LiveStatement {
 		// Return the query id
 		Ok(id.into())
 	}
+
+	/// Replay the changes to a table from the change feed,
+	/// after the specified versionstamp, which is the last
+	/// one seen by the client, sending a notification for
+	/// each change which matches this LIVE query, as if
+	/// the LIVE query had not stopped.
+	async fn replay(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &LiveStatement,
+		tb: &str,
+		since: u64,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Ensure that change feeds are enabled
+		let dbv = txn.get_db(ns, db).await?;
+		let tbv = txn.get_tb(ns, db, tb).await?;
+		if dbv.changefeed.is_none() && tbv.changefeed.is_none() {
+			return Err(Error::LiveResumeChangefeed {
+				tb: tb.to_string(),
+			});
+		}
+		// Read the change feed in batches, after the last seen change
+		let mut start = since.saturating_add(1);
+		loop {
+			let changes =
+				cf::read(&txn, ns, db, Some(tb), ShowSince::Versionstamp(start), Some(1000))
+					.await?;
+			// Check if there are any further changes
+			let Some(ChangeSet(last, _)) = changes.last() else {
+				break;
+			};
+			// Continue from after the last change
+			start = vs::versionstamp_to_u64(last).saturating_add(1);
+			// Replay each of the changes
+			for ChangeSet(v, DatabaseMutation(tbs)) in changes {
+				let v = vs::versionstamp_to_u64(&v);
+				for TableMutations(_, muts) in tbs {
+					for change in muts {
+						Document::process_resumed_live(stk, ctx, opt, stm, change, v).await?;
+					}
+				}
+			}
+		}
+		// Carry on
+		Ok(())
+	}
 }
 
 impl fmt::Display for LiveStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "LIVE SELECT {} FROM {}", self.expr, self.what)?;
+		match self.is_diff() {
+			true => write!(f, "LIVE SELECT DIFF FROM {}", self.what)?,
+			false => write!(f, "LIVE SELECT {} FROM {}", self.expr, self.what)?,
+		}
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.group {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.resume {
+			write!(f, " RESUME {v}")?
+		}
 		Ok(())
 	}
 }
@@ -151,7 +312,9 @@ impl InfoStructure for LiveStatement {
 			"expr".to_string() => self.expr.structure(),
 			"what".to_string() => self.what.structure(),
 			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
+			"group".to_string(), if let Some(v) = self.group => Value::from(v.to_string()),
 			"fetch".to_string(), if let Some(v) = self.fetch => v.structure(),
+			"resume".to_string(), if let Some(v) = self.resume => v.structure(),
 		})
 	}
 }
@@ -276,4 +439,175 @@ mod tests {
 		assert_eq!(table_occurrences[0].name.0, tb);
 		tx.cancel().await.unwrap();
 	}
+
+	#[tokio::test]
+	async fn test_live_query_diff_sends_json_patches() {
+		let dbs = new_ds().await.unwrap().with_notifications();
+		let (ns, db, tb) = ("test", "test", "person");
+		let ses = Session::owner().with_ns(ns).with_db(db).with_rt(true);
+
+		// Initiate a live query statement
+		let lq_stmt = format!("LIVE SELECT DIFF FROM {}", tb);
+		let live_query_response = &mut dbs.execute(&lq_stmt, &ses, None).await.unwrap();
+		let live_id = live_query_response.remove(0).result.unwrap();
+		let live_id = match live_id {
+			Value::Uuid(id) => id,
+			_ => panic!("expected uuid"),
+		};
+
+		// Create and update a record
+		let stmt = format!(
+			"CREATE {tb}:test SET name = 'Tobie', tags = ['a', 'b', 'c'];
+			UPDATE {tb}:test SET name = 'Jaime', tags = ['a'];"
+		);
+		dbs.execute(&stmt, &ses, None).await.unwrap();
+
+		// Validate notifications
+		let notifications = dbs.notifications().expect("expected notifications");
+		let notification = notifications.recv().await.unwrap();
+		assert_eq!(
+			notification,
+			Notification::new(
+				live_id,
+				Action::Create,
+				Value::Thing(Thing::from((tb, "test"))),
+				Value::parse(&format!(
+					"[{{ op: 'replace', path: '/', value: {{ id: {tb}:test, name: 'Tobie', tags: ['a', 'b', 'c'] }} }}]"
+				)),
+			)
+		);
+		let notification = notifications.recv().await.unwrap();
+		assert_eq!(
+			notification,
+			Notification::new(
+				live_id,
+				Action::Update,
+				Value::Thing(Thing::from((tb, "test"))),
+				Value::parse(
+					"[
+						{ op: 'replace', path: '/name', value: 'Jaime' },
+						{ op: 'remove', path: '/tags/2' },
+						{ op: 'remove', path: '/tags/1' },
+					]"
+				),
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn test_live_query_notifications_are_sent_on_commit() {
+		let dbs = new_ds().await.unwrap().with_notifications();
+		let (ns, db, tb) = ("test", "test", "person");
+		let ses = Session::owner().with_ns(ns).with_db(db).with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		// Initiate a live query statement
+		let lq_stmt = format!("LIVE SELECT * FROM {tb}");
+		let res = &mut dbs.execute(&lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid");
+		};
+
+		// Changes which are rolled back are not notified
+		let stmt = format!(
+			"BEGIN;
+			CREATE {tb}:one;
+			SAVEPOINT before;
+			CREATE {tb}:two;
+			ROLLBACK TO before;
+			CREATE {tb}:three;
+			COMMIT;"
+		);
+		dbs.execute(&stmt, &ses, None).await.unwrap();
+		for id in ["one", "three"] {
+			let notification = notifications.recv().await.unwrap();
+			assert_eq!(notification.id, live_id);
+			assert_eq!(notification.record, Value::Thing(Thing::from((tb, id))));
+		}
+		assert!(notifications.try_recv().is_err());
+
+		// Changes made outside of a query are notified
+		let val = Value::parse(&format!("(CREATE {tb}:four)"));
+		dbs.compute(val, &ses, None).await.unwrap();
+		let notification = notifications.recv().await.unwrap();
+		assert_eq!(notification.id, live_id);
+		assert_eq!(notification.action, Action::Create);
+		assert_eq!(notification.record, Value::Thing(Thing::from((tb, "four"))));
+	}
+
+	#[tokio::test]
+	async fn test_live_query_resume_replays_changes() {
+		let dbs = new_ds().await.unwrap().with_notifications();
+		let (ns, db, tb) = ("test", "test", "person");
+		let ses = Session::owner().with_ns(ns).with_db(db).with_rt(true);
+		let notifications = dbs.notifications().expect("expected notifications");
+
+		// Define a table with a change feed
+		let stmt = format!("DEFINE TABLE {tb} CHANGEFEED 1h");
+		dbs.execute(&stmt, &ses, None).await.unwrap();
+
+		// Initiate a live query statement
+		let lq_stmt = format!("LIVE SELECT * FROM {tb} WHERE active = true");
+		let res = &mut dbs.execute(&lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid");
+		};
+
+		// Create a record, and get the resume token
+		let stmt = format!("CREATE {tb}:one SET active = true");
+		dbs.execute(&stmt, &ses, None).await.unwrap();
+		let notification = notifications.recv().await.unwrap();
+		assert_eq!(notification.id, live_id);
+		let resume = notification.resume.expect("expected a resume token");
+
+		// Stop the live query, and make some changes
+		dbs.execute(&format!("KILL {live_id}"), &ses, None).await.unwrap();
+		let stmt = format!(
+			"CREATE {tb}:two SET active = true;
+			CREATE {tb}:three SET active = false;
+			UPDATE {tb}:one SET seen = true;
+			DELETE {tb}:one;"
+		);
+		dbs.execute(&stmt, &ses, None).await.unwrap();
+
+		// Resume the live query
+		let lq_stmt = format!("LIVE SELECT * FROM {tb} WHERE active = true RESUME {resume}");
+		let res = &mut dbs.execute(&lq_stmt, &ses, None).await.unwrap();
+		let Value::Uuid(live_id) = res.remove(0).result.unwrap() else {
+			panic!("expected uuid");
+		};
+
+		// Validate the replayed notifications
+		for (id, action) in
+			[("two", Action::Create), ("one", Action::Update), ("one", Action::Delete)]
+		{
+			let notification = notifications.recv().await.unwrap();
+			assert_eq!(notification.id, live_id);
+			assert_eq!(notification.action, action);
+			assert_eq!(notification.record, Value::Thing(Thing::from((tb, id))));
+			assert!(notification.resume.is_some_and(|v| v > resume));
+		}
+		assert!(notifications.try_recv().is_err());
+	}
+
+	#[tokio::test]
+	async fn test_live_query_resume_requires_changefeed() {
+		let dbs = new_ds().await.unwrap().with_notifications();
+		let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+		let res = &mut dbs.execute("LIVE SELECT * FROM person RESUME 1", &ses, None).await.unwrap();
+		assert!(matches!(
+			res.remove(0).result,
+			Err(crate::err::Error::LiveResumeChangefeed { .. })
+		));
+	}
+
+	#[test]
+	fn test_live_query_diff_display() {
+		let sql = "LIVE SELECT DIFF FROM person WHERE age > 18";
+		let query = crate::syn::parse(sql).unwrap();
+		assert_eq!(query.0[0].to_string(), sql);
+		let sql = "LIVE SELECT * FROM person RESUME 1234";
+		let query = crate::syn::parse(sql).unwrap();
+		assert_eq!(query.0[0].to_string(), sql);
+	}
 }
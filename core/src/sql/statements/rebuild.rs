@@ -4,7 +4,6 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::ident::Ident;
-use crate::sql::statements::RemoveIndexStatement;
 use crate::sql::value::Value;
 use crate::sql::Base;
 use derive::Store;
@@ -49,7 +48,7 @@ impl Display for RebuildStatement {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -57,6 +56,8 @@ pub struct RebuildIndexStatement {
 	pub name: Ident,
 	pub what: Ident,
 	pub if_exists: bool,
+	#[revision(start = 2)]
+	pub concurrently: bool,
 }
 
 impl RebuildIndexStatement {
@@ -71,18 +72,24 @@ impl RebuildIndexStatement {
 		let future = async {
 			// Allowed to run?
 			opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+			// Get the transaction
+			let txn = ctx.tx();
 			// Get the index definition
-			let ix = ctx.tx().get_tb_index(opt.ns()?, opt.db()?, &self.what, &self.name).await?;
-			// Create the remove statement
-			let stm = RemoveIndexStatement {
-				name: self.name.clone(),
-				what: self.what.clone(),
-				if_exists: false,
-			};
-			// Execute the delete statement
-			stm.compute(ctx, opt).await?;
+			let ix = txn.get_tb_index(opt.ns()?, opt.db()?, &self.what, &self.name).await?;
+			// Clear the index store cache
+			ctx.get_index_stores()
+				.index_removed(&txn, opt.ns()?, opt.db()?, &self.what, &self.name)
+				.await?;
+			// A concurrent build removes the index data itself, once it has started
+			let concurrently =
+				!cfg!(target_arch = "wasm32") && (self.concurrently || ix.concurrently);
+			if !concurrently {
+				// Remove the index data, keeping the definition
+				let key = crate::key::index::all::new(opt.ns()?, opt.db()?, &self.what, &self.name);
+				txn.delp(key).await?;
+			}
 			// Rebuild the index
-			ix.compute(stk, ctx, opt, doc).await?;
+			ix.build(stk, ctx, opt, doc, concurrently).await?;
 			// Ok all good
 			Ok(Value::None)
 		}
@@ -103,6 +110,9 @@ impl Display for RebuildIndexStatement {
 			write!(f, " IF EXISTS")?
 		}
 		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.concurrently {
+			write!(f, " CONCURRENTLY")?
+		}
 		Ok(())
 	}
 }
@@ -33,9 +33,10 @@ impl RemoveAnalyzerStatement {
 			txn.del(key).await?;
 			// Clear the cache
 			txn.clear();
-			// Cleanup in-memory mappers if not used anymore
+			// Cleanup in-memory mappers and dictionaries if not used anymore
 			let azs = txn.all_db_analyzers(ns, db).await?;
 			ctx.get_index_stores().mappers().cleanup(&azs);
+			ctx.get_index_stores().dictionaries().cleanup(&azs);
 			// TODO Check that the analyzer is not used in any schema
 			// Ok all good
 			Ok(Value::None)
@@ -8,7 +8,10 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
+mod sink;
 mod table;
+mod r#type;
 mod user;
 
 pub use access::RemoveAccessStatement;
@@ -21,6 +24,9 @@ pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use r#type::RemoveTypeStatement;
+pub use role::RemoveRoleStatement;
+pub use sink::RemoveSinkStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
 
@@ -34,7 +40,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -51,6 +57,12 @@ pub enum RemoveStatement {
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
 	Model(RemoveModelStatement),
+	#[revision(start = 2)]
+	Type(RemoveTypeStatement),
+	#[revision(start = 3)]
+	Sink(RemoveSinkStatement),
+	#[revision(start = 4)]
+	Role(RemoveRoleStatement),
 }
 
 impl RemoveStatement {
@@ -78,6 +90,9 @@ impl RemoveStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt).await,
 			Self::User(ref v) => v.compute(ctx, opt).await,
 			Self::Model(ref v) => v.compute(ctx, opt).await,
+			Self::Type(ref v) => v.compute(ctx, opt).await,
+			Self::Sink(ref v) => v.compute(ctx, opt).await,
+			Self::Role(ref v) => v.compute(ctx, opt).await,
 		}
 	}
 }
@@ -97,6 +112,9 @@ impl Display for RemoveStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Type(v) => Display::fmt(v, f),
+			Self::Sink(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -0,0 +1,126 @@
+use crate::cnf::REVOCATION_DURATION;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Datetime, Duration, Value};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RevokeStatement {
+	pub kind: RevokeKind,
+	// The token `jti` claim or the session id to revoke
+	pub id: Value,
+	pub base: Option<Base>,
+	// How long the revocation is kept, which should outlive the credential
+	pub duration: Option<Duration>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum RevokeKind {
+	#[default]
+	Token,
+	Session,
+}
+
+impl RevokeKind {
+	/// The name under which revocations of this kind are stored
+	pub(crate) fn as_str(&self) -> &'static str {
+		match self {
+			Self::Token => "token",
+			Self::Session => "session",
+		}
+	}
+}
+
+impl Display for RevokeKind {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Token => f.write_str("TOKEN"),
+			Self::Session => f.write_str("SESSION"),
+		}
+	}
+}
+
+impl RevokeStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		let base = match &self.base {
+			Some(base) => base.clone(),
+			None => opt.selected_base()?,
+		};
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &base)?;
+		// Compute the identifier to revoke
+		let id = match self.id.compute(stk, ctx, opt, doc).await? {
+			Value::Strand(v) if !v.is_empty() => v.0,
+			Value::Uuid(v) => v.to_raw(),
+			v => {
+				return Err(Error::RevokeStatement {
+					value: v.to_string(),
+				})
+			}
+		};
+		// Calculate when the revocation can be forgotten
+		let duration = match self.duration {
+			Some(v) => v,
+			None => Duration::from_secs(*REVOCATION_DURATION),
+		};
+		let expiry = duration + Datetime::default();
+		// Get the transaction
+		let txn = ctx.tx();
+		// Store the revocation
+		let kind = self.kind.as_str();
+		match base {
+			Base::Root => {
+				let key = crate::key::root::rv::new(kind, &id);
+				txn.set(key, Value::from(expiry), None).await?;
+			}
+			Base::Ns => {
+				let key = crate::key::namespace::rv::new(opt.ns()?, kind, &id);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(key, Value::from(expiry), None).await?;
+			}
+			Base::Db => {
+				let key = crate::key::database::rv::new(opt.ns()?, opt.db()?, kind, &id);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+				txn.set(key, Value::from(expiry), None).await?;
+			}
+			// Other levels are not supported
+			_ => return Err(Error::InvalidLevel(base.to_string())),
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for RevokeStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REVOKE {} {}", self.kind, self.id)?;
+		if let Some(ref v) = self.base {
+			write!(f, " ON {v}")?;
+		}
+		if let Some(ref v) = self.duration {
+			write!(f, " DURATION {v}")?;
+		}
+		Ok(())
+	}
+}
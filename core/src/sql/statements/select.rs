@@ -1,17 +1,19 @@
 use crate::ctx::{Context, MutableContext};
-use crate::dbs::{Iterable, Iterator, Options, Statement};
+use crate::dbs::{optimiser, Iterable, Iterator, Options, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::Action;
 use crate::idx::planner::{QueryPlanner, QueryPlannerParams};
 use crate::sql::{
 	order::{OldOrders, Order, OrderList, Ordering},
-	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Splits, Start, Timeout, Value,
-	Values, Version, With,
+	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Permission, Splits, Start,
+	Timeout, Value, Values, Version, With,
 };
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use std::sync::Arc;
 
@@ -67,6 +69,7 @@ impl SelectStatement {
 				.map(|x| Order {
 					value: x.order,
 					collate: x.collate,
+					locale: None,
 					numeric: x.numeric,
 					direction: x.direction,
 				})
@@ -104,6 +107,10 @@ impl SelectStatement {
 	) -> Result<Value, Error> {
 		// Valid options?
 		opt.valid_for_db()?;
+		// Rewrite the statement into a cheaper equivalent
+		if let Cow::Owned(stm) = optimiser::optimise(self) {
+			return stk.run(|stk| stm.compute(stk, ctx, opt, doc)).await;
+		}
 		// Assign the statement
 		let stm = Statement::from(self);
 		// Create a new iterator
@@ -135,6 +142,12 @@ impl SelectStatement {
 		let mut planner = QueryPlanner::new();
 		let params: QueryPlannerParams<'_> = self.into();
 		let keys = params.is_keys_only();
+		// Check if the maintained row count can be used
+		if keys {
+			if let Some(v) = self.fast_count(&ctx, &opt).await? {
+				return Ok(v);
+			}
+		}
 		// Loop over the select targets
 		for w in self.what.0.iter() {
 			let v = w.compute(stk, &ctx, &opt, doc).await?;
@@ -183,6 +196,8 @@ impl SelectStatement {
 				v => i.ingest(Iterable::Value(v)),
 			};
 		}
+		// Check that any index hints refer to existing indexes
+		planner.check_hinted_indexes(self.with.as_ref())?;
 		// Create a new context
 		let mut ctx = MutableContext::new(&ctx);
 		// Add query executors if any
@@ -213,8 +228,66 @@ impl SelectStatement {
 	}
 }
 
+impl SelectStatement {
+	/// Answer `SELECT count() FROM table GROUP ALL` from the maintained row count of the table
+	async fn fast_count(&self, ctx: &Context, opt: &Options) -> Result<Option<Value>, Error> {
+		// Only a plain count of a single table can be answered
+		let [Value::Table(tb)] = self.what.0.as_slice() else {
+			return Ok(None);
+		};
+		if !self.group.as_ref().is_some_and(|g| g.is_empty())
+			|| self.only
+			|| self.omit.is_some()
+			|| self.split.is_some()
+			|| self.limit.is_some()
+			|| self.start.is_some()
+			|| self.fetch.is_some()
+			|| self.version.is_some()
+			|| self.explain.is_some()
+		{
+			return Ok(None);
+		}
+		// Get the transaction
+		let txn = ctx.tx();
+		// Check that the table exists
+		let dt = match txn.get_tb(opt.ns()?, opt.db()?, tb).await {
+			Ok(dt) => dt,
+			Err(Error::TbNotFound {
+				..
+			}) => return Ok(None),
+			Err(e) => return Err(e),
+		};
+		// Records which are filtered by permissions must be counted individually
+		if opt.check_perms(Action::View)? && !matches!(dt.permissions.select, Permission::Full) {
+			return Ok(None);
+		}
+		// Get the maintained row count of the table
+		let count = match txn.get_count(opt.ns()?, opt.db()?, tb).await? {
+			// An empty table has no groups
+			Some(0) | None => return Ok(None),
+			Some(count) => Value::from(count),
+		};
+		// Output the count for each field
+		let mut obj = Value::base();
+		for field in self.expr.iter() {
+			if let Field::Single {
+				expr,
+				alias,
+			} = field
+			{
+				let idiom = alias.clone().unwrap_or_else(|| expr.to_idiom());
+				obj.put(&idiom, count.clone());
+			}
+		}
+		Ok(Some(Value::from(vec![obj])))
+	}
+}
+
 impl fmt::Display for SelectStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(v) = self.explain.as_ref().filter(|v| v.1) {
+			write!(f, "{v} ")?
+		}
 		write!(f, "SELECT {}", self.expr)?;
 		if let Some(ref v) = self.omit {
 			write!(f, " OMIT {v}")?
@@ -257,7 +330,7 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
-		if let Some(ref v) = self.explain {
+		if let Some(v) = self.explain.as_ref().filter(|v| !v.1) {
 			write!(f, " {v}")?
 		}
 		Ok(())
@@ -44,6 +44,14 @@ pub struct ShowStatement {
 }
 
 impl ShowStatement {
+	#[doc(hidden)]
+	pub fn new(table: Option<Table>, since: ShowSince, limit: Option<u32>) -> Self {
+		ShowStatement {
+			table,
+			since,
+			limit,
+		}
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
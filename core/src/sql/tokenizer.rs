@@ -1,9 +1,10 @@
+use crate::sql::escape::quote_str;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -12,15 +13,23 @@ pub enum Tokenizer {
 	Camel,
 	Class,
 	Punct,
+	/// Splits runs of Chinese, Japanese or Korean characters into overlapping n-grams
+	#[revision(start = 2)]
+	Ngram(u16),
+	/// Splits runs of Chinese, Japanese or Korean characters into the longest words found in a dictionary file
+	#[revision(start = 2)]
+	Dictionary(String),
 }
 
 impl Display for Tokenizer {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str(match self {
-			Self::Blank => "BLANK",
-			Self::Camel => "CAMEL",
-			Self::Class => "CLASS",
-			Self::Punct => "PUNCT",
-		})
+		match self {
+			Self::Blank => f.write_str("BLANK"),
+			Self::Camel => f.write_str("CAMEL"),
+			Self::Class => f.write_str("CLASS"),
+			Self::Punct => f.write_str("PUNCT"),
+			Self::Ngram(n) => write!(f, "NGRAM({n})"),
+			Self::Dictionary(path) => write!(f, "DICTIONARY({})", quote_str(path)),
+		}
 	}
 }
@@ -0,0 +1,38 @@
+use crate::sql::duration::Duration;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Idiom, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::time;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Ttl {
+	/// How long a record lives for before it expires
+	pub duration: time::Duration,
+	/// The datetime field which the record expires from,
+	/// otherwise the record expires from when it was created
+	pub field: Option<Idiom>,
+}
+
+impl Display for Ttl {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TTL {}", Duration(self.duration))?;
+		if let Some(ref v) = self.field {
+			write!(f, " ON {v}")?;
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for Ttl {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"duration".to_string() => Duration(self.duration).structure(),
+			"field".to_string(), if let Some(v) = self.field => v.structure(),
+		})
+	}
+}
@@ -9,6 +9,7 @@ impl Value {
 		other: &Self,
 		path: &[Part],
 		collate: bool,
+		locale: Option<&str>,
 		numeric: bool,
 	) -> Option<Ordering> {
 		match path.first() {
@@ -17,7 +18,7 @@ impl Value {
 				// Current path part is an object
 				(Value::Object(a), Value::Object(b)) => match p {
 					Part::Field(f) => match (a.get(f.as_str()), b.get(f.as_str())) {
-						(Some(a), Some(b)) => a.compare(b, path.next(), collate, numeric),
+						(Some(a), Some(b)) => a.compare(b, path.next(), collate, locale, numeric),
 						(Some(_), None) => Some(Ordering::Greater),
 						(None, Some(_)) => Some(Ordering::Less),
 						(_, _) => Some(Ordering::Equal),
@@ -28,7 +29,7 @@ impl Value {
 				(Value::Array(a), Value::Array(b)) => match p {
 					Part::All => {
 						for (a, b) in a.iter().zip(b.iter()) {
-							match a.compare(b, path.next(), collate, numeric) {
+							match a.compare(b, path.next(), collate, locale, numeric) {
 								Some(Ordering::Equal) => continue,
 								None => continue,
 								o => return o,
@@ -41,26 +42,26 @@ impl Value {
 						}
 					}
 					Part::First => match (a.first(), b.first()) {
-						(Some(a), Some(b)) => a.compare(b, path.next(), collate, numeric),
+						(Some(a), Some(b)) => a.compare(b, path.next(), collate, locale, numeric),
 						(Some(_), None) => Some(Ordering::Greater),
 						(None, Some(_)) => Some(Ordering::Less),
 						(_, _) => Some(Ordering::Equal),
 					},
 					Part::Last => match (a.last(), b.last()) {
-						(Some(a), Some(b)) => a.compare(b, path.next(), collate, numeric),
+						(Some(a), Some(b)) => a.compare(b, path.next(), collate, locale, numeric),
 						(Some(_), None) => Some(Ordering::Greater),
 						(None, Some(_)) => Some(Ordering::Less),
 						(_, _) => Some(Ordering::Equal),
 					},
 					Part::Index(i) => match (a.get(i.to_usize()), b.get(i.to_usize())) {
-						(Some(a), Some(b)) => a.compare(b, path.next(), collate, numeric),
+						(Some(a), Some(b)) => a.compare(b, path.next(), collate, locale, numeric),
 						(Some(_), None) => Some(Ordering::Greater),
 						(None, Some(_)) => Some(Ordering::Less),
 						(_, _) => Some(Ordering::Equal),
 					},
 					_ => {
 						for (a, b) in a.iter().zip(b.iter()) {
-							match a.compare(b, path, collate, numeric) {
+							match a.compare(b, path, collate, locale, numeric) {
 								Some(Ordering::Equal) => continue,
 								None => continue,
 								o => return o,
@@ -74,13 +75,14 @@ impl Value {
 					}
 				},
 				// Ignore everything else
-				(a, b) => a.compare(b, path.next(), collate, numeric),
+				(a, b) => a.compare(b, path.next(), collate, locale, numeric),
 			},
 			// No more parts so get the value
-			None => match (collate, numeric) {
-				(true, true) => self.natural_lexical_cmp(other),
-				(true, false) => self.lexical_cmp(other),
-				(false, true) => self.natural_cmp(other),
+			None => match (collate, locale, numeric) {
+				(true, Some(locale), numeric) => self.collated_cmp(other, locale, numeric),
+				(true, None, true) => self.natural_lexical_cmp(other),
+				(true, None, false) => self.lexical_cmp(other),
+				(false, _, true) => self.natural_cmp(other),
 				_ => self.partial_cmp(other),
 			},
 		}
@@ -99,7 +101,18 @@ mod tests {
 		let idi = Idiom::default();
 		let one = Value::parse("{ test: { other: null, something: 456 } }");
 		let two = Value::parse("{ test: { other: null, something: 123 } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
+		assert_eq!(res, Some(Ordering::Greater));
+	}
+
+	#[test]
+	fn compare_locale() {
+		let idi = Idiom::parse("test");
+		let one = Value::parse("{ test: 'Äpfel' }");
+		let two = Value::parse("{ test: 'Zebra' }");
+		let res = one.compare(&two, &idi, true, Some("de"), false);
+		assert_eq!(res, Some(Ordering::Less));
+		let res = one.compare(&two, &idi, true, Some("sv"), false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -108,7 +121,7 @@ mod tests {
 		let idi = Idiom::parse("test.something");
 		let one = Value::parse("{ test: { other: null, something: 456 } }");
 		let two = Value::parse("{ test: { other: null, something: 123 } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -117,7 +130,7 @@ mod tests {
 		let idi = Idiom::parse("test.something");
 		let one = Value::parse("{ test: { other: null } }");
 		let two = Value::parse("{ test: { other: null, something: 123 } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -126,7 +139,7 @@ mod tests {
 		let idi = Idiom::parse("test.something");
 		let one = Value::parse("{ test: { other: null, something: 456 } }");
 		let two = Value::parse("{ test: { other: null } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -135,7 +148,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [4, 5, 6] } }");
 		let two = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -144,7 +157,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [1, 2, 3, 4, 5, 6] } }");
 		let two = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -153,7 +166,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
 		let two = Value::parse("{ test: { other: null, something: [1, 2, 3, 4, 5, 6] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -162,7 +175,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: null } }");
 		let two = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -171,7 +184,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [4, 5, 6] } }");
 		let two = Value::parse("{ test: { other: null, something: null } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -180,7 +193,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [1, null, 3] } }");
 		let two = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Less));
 	}
 
@@ -189,7 +202,7 @@ mod tests {
 		let idi = Idiom::parse("test.something.*");
 		let one = Value::parse("{ test: { other: null, something: [1, 2, 3] } }");
 		let two = Value::parse("{ test: { other: null, something: [1, null, 3] } }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater));
 	}
 
@@ -198,7 +211,7 @@ mod tests {
 		let idi = Idiom::parse("test[$]");
 		let one = Value::parse("{ test: [1,5] }");
 		let two = Value::parse("{ test: [2,4] }");
-		let res = one.compare(&two, &idi, false, false);
+		let res = one.compare(&two, &idi, false, None, false);
 		assert_eq!(res, Some(Ordering::Greater))
 	}
 }
@@ -5,6 +5,16 @@ use std::cmp::min;
 
 impl Value {
 	pub(crate) fn diff(&self, val: &Value, path: Idiom) -> Vec<Operation> {
+		self.patch_ops(val, path, true)
+	}
+
+	/// Computes the RFC 6902 JSON Patch operations transforming this value into another,
+	/// where changed strings are replaced instead of being patched as text
+	pub(crate) fn json_patch(&self, val: &Value) -> Vec<Operation> {
+		self.patch_ops(val, Idiom::default(), false)
+	}
+
+	fn patch_ops(&self, val: &Value, path: Idiom, text: bool) -> Vec<Operation> {
 		let mut ops: Vec<Operation> = vec![];
 		match (self, val) {
 			(Value::Object(a), Value::Object(b)) if a != b => {
@@ -25,7 +35,7 @@ impl Value {
 						}),
 						Some(old) => {
 							let path = path.clone().push(key.clone().into());
-							ops.append(&mut old.diff(val, path))
+							ops.append(&mut old.patch_ops(val, path, text))
 						}
 					}
 				}
@@ -34,7 +44,7 @@ impl Value {
 				let mut n = 0;
 				while n < min(a.len(), b.len()) {
 					let path = path.clone().push(n.into());
-					ops.append(&mut a[n].diff(&b[n], path));
+					ops.append(&mut a[n].patch_ops(&b[n], path, text));
 					n += 1;
 				}
 				while n < b.len() {
@@ -46,16 +56,18 @@ impl Value {
 					}
 					n += 1;
 				}
-				while n < a.len() {
-					if n >= b.len() {
+				// Remove from the end, so that the indexes of the remaining items are unchanged
+				let mut m = a.len();
+				while m > n {
+					m -= 1;
+					if m >= b.len() {
 						ops.push(Operation::Remove {
-							path: path.clone().push(n.into()),
+							path: path.clone().push(m.into()),
 						})
 					}
-					n += 1;
 				}
 			}
-			(Value::Strand(a), Value::Strand(b)) if a != b => ops.push(Operation::Change {
+			(Value::Strand(a), Value::Strand(b)) if text && a != b => ops.push(Operation::Change {
 				path,
 				value: {
 					let dmp = dmp::new();
@@ -129,4 +141,21 @@ mod tests {
 		);
 		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
 	}
+
+	#[test]
+	fn diff_remove_array() {
+		let old = Value::parse("{ test: [1,2,3,4] }");
+		let now = Value::parse("{ test: [1,2] }");
+		let res =
+			Value::parse("[{ op: 'remove', path: '/test/3' }, { op: 'remove', path: '/test/2' }]");
+		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
+	}
+
+	#[test]
+	fn json_patch_replace_text() {
+		let old = Value::parse("{ test: { other: 'test' } }");
+		let now = Value::parse("{ test: { other: 'text' } }");
+		let res = Value::parse("[{ op: 'replace', path: '/test/other', value: 'text' }]");
+		assert_eq!(res.to_operations().unwrap(), old.json_patch(&now));
+	}
 }
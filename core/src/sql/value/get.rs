@@ -36,6 +36,11 @@ impl Value {
 			return Err(Error::ComputationDepthExceeded);
 		}
 		match path.first() {
+			// Recursively traverse the graph from the current records
+			Some(Part::Recurse(r)) => {
+				let v = stk.run(|stk| r.compute(stk, ctx, opt, doc, self)).await?;
+				stk.run(|stk| v.get(stk, ctx, opt, doc, path.next())).await
+			}
 			// Get the current value at the path
 			Some(p) => match self {
 				// Current value at path is a geometry
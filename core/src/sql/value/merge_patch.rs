@@ -0,0 +1,83 @@
+use crate::sql::value::Value;
+use crate::sql::Object;
+
+impl Value {
+	/// Applies a JSON Merge Patch, as defined in RFC 7396
+	pub(crate) fn merge_patch(&mut self, val: Value) {
+		match val {
+			// An object patch is merged field by field
+			Value::Object(patch) => {
+				if !self.is_object() {
+					*self = Value::Object(Object::default());
+				}
+				if let Value::Object(obj) = self {
+					for (k, v) in patch.0.into_iter() {
+						match v {
+							// A null value removes the field
+							Value::Null => {
+								obj.remove(&k);
+							}
+							v => obj.entry(k).or_insert(Value::None).merge_patch(v),
+						}
+					}
+				}
+			}
+			// Any other patch replaces the value
+			val => *self = val,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::syn::Parse;
+
+	#[tokio::test]
+	async fn merge_patch_basic() {
+		let mut res = Value::parse(
+			"{
+				title: 'Goodbye!',
+				author: {
+					givenName: 'John',
+					familyName: 'Doe',
+				},
+				tags: ['example', 'sample'],
+				content: 'This will be unchanged',
+			}",
+		);
+		let mrg = Value::parse(
+			"{
+				title: 'Hello!',
+				phoneNumber: '+01-123-456-7890',
+				author: {
+					familyName: null,
+				},
+				tags: ['example'],
+			}",
+		);
+		let val = Value::parse(
+			"{
+				title: 'Hello!',
+				author: {
+					givenName: 'John',
+				},
+				tags: ['example'],
+				content: 'This will be unchanged',
+				phoneNumber: '+01-123-456-7890',
+			}",
+		);
+		res.merge_patch(mrg);
+		assert_eq!(res, val);
+	}
+
+	#[tokio::test]
+	async fn merge_patch_replace() {
+		let mut res = Value::parse("{ a: { b: 'c' } }");
+		res.merge_patch(Value::parse("{ a: { b: { c: null, d: 1 } } }"));
+		assert_eq!(res, Value::parse("{ a: { b: { d: 1 } } }"));
+		res.merge_patch(Value::parse("['c']"));
+		assert_eq!(res, Value::parse("['c']"));
+	}
+}
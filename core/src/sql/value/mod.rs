@@ -27,6 +27,7 @@ mod increment;
 mod into_json;
 mod last;
 mod merge;
+mod merge_patch;
 mod patch;
 mod pick;
 mod put;
@@ -18,6 +18,14 @@ impl Value {
 							v.insert(g.to_raw(), obj);
 						}
 					},
+					Part::Recurse(r) => match v.get_mut(r.to_string().as_str()) {
+						Some(v) if v.is_some() => v.put(path.next(), val),
+						_ => {
+							let mut obj = Value::base();
+							obj.put(path.next(), val);
+							v.insert(r.to_string(), obj);
+						}
+					},
 					Part::Field(f) => match v.get_mut(f.to_raw().as_str()) {
 						Some(v) if v.is_some() => v.put(path.next(), val),
 						_ => {
@@ -44,9 +44,9 @@ impl Value {
 			}
 
 			match p {
-				Part::Graph(g) => {
+				Part::Graph(_) | Part::Recurse(_) => {
 					match place {
-						Value::Object(obj) => match obj.entry(g.to_raw()) {
+						Value::Object(obj) => match obj.entry(p.to_string()) {
 							Entry::Vacant(x) => {
 								let v = x.insert(Value::None);
 								return Self::assign(stk, ctx, opt, v, val, iter.as_slice()).await;
@@ -250,6 +250,7 @@ impl Value {
 		for p in path.iter().rev() {
 			let name = match p {
 				Part::Graph(x) => x.to_raw(),
+				Part::Recurse(x) => x.to_string(),
 				Part::Field(f) => f.0.clone(),
 				Part::Index(i) => i.to_string(),
 				Part::Value(x) => {
@@ -6,6 +6,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fnc::util::string::fuzzy::Fuzzy;
 use crate::sql::id::range::IdRange;
+use crate::sql::index::VectorType;
 use crate::sql::kind::Literal;
 use crate::sql::range::OldRange;
 use crate::sql::statements::info::InfoStructure;
@@ -1348,6 +1349,7 @@ impl Value {
 				Some(l) => self.coerce_to_array_type_len(t, l).map(Value::from),
 				None => self.coerce_to_array_type(t).map(Value::from),
 			},
+			Kind::Vector(t, d) => self.coerce_to_vector(t, d).map(Value::from),
 			Kind::Record(t) => match t.is_empty() {
 				true => self.coerce_to_record().map(Value::from),
 				false => self.coerce_to_record_type(t).map(Value::from),
@@ -1378,6 +1380,10 @@ impl Value {
 				})
 			}
 			Kind::Literal(lit) => self.coerce_to_literal(lit),
+			// User-defined types must be resolved before use
+			Kind::Custom(name) => Err(Error::TyNotFound {
+				value: name.to_owned(),
+			}),
 		};
 		// Check for any conversion errors
 		match res {
@@ -1867,6 +1873,55 @@ impl Value {
 			})
 	}
 
+	/// Try to coerce this value to a vector of a certain type, and dimension
+	pub(crate) fn coerce_to_vector(self, t: &VectorType, d: &u64) -> Result<Array, Error> {
+		let kind = Kind::Vector(*t, *d);
+		self.coerce_to_array()?
+			.into_iter()
+			.map(|value| {
+				let n = match t {
+					VectorType::F64 | VectorType::F32 => value.coerce_to_float()?,
+					_ => value.coerce_to_int()?,
+				};
+				Self::check_vector_element(n, t)
+			})
+			.collect::<Result<Array, Error>>()
+			.map_err(|e| match e {
+				Error::CoerceTo {
+					from,
+					..
+				} => Error::CoerceTo {
+					from,
+					into: kind.to_string(),
+				},
+				e => e,
+			})
+			.and_then(|v| match v.len() {
+				v if v != *d as usize => Err(Error::LengthInvalid {
+					kind: kind.to_string(),
+					size: v,
+				}),
+				_ => Ok(v),
+			})
+	}
+
+	/// Check that a number can be stored in a vector of a certain type
+	fn check_vector_element(n: Number, t: &VectorType) -> Result<Value, Error> {
+		let valid = match (t, &n) {
+			(VectorType::F32, Number::Float(v)) => !v.is_finite() || (*v as f32).is_finite(),
+			(VectorType::I32, Number::Int(v)) => i32::try_from(*v).is_ok(),
+			(VectorType::I16, Number::Int(v)) => i16::try_from(*v).is_ok(),
+			_ => true,
+		};
+		match valid {
+			true => Ok(n.into()),
+			false => Err(Error::CoerceTo {
+				from: n.into(),
+				into: t.to_string().to_lowercase(),
+			}),
+		}
+	}
+
 	/// Try to coerce this value to an `Array` of a certain type, unique values
 	pub(crate) fn coerce_to_set_type(self, kind: &Kind) -> Result<Array, Error> {
 		self.coerce_to_array()?
@@ -1944,6 +1999,7 @@ impl Value {
 				Some(l) => self.convert_to_array_type_len(t, l).map(Value::from),
 				None => self.convert_to_array_type(t).map(Value::from),
 			},
+			Kind::Vector(t, d) => self.convert_to_vector(t, d).map(Value::from),
 			Kind::Record(t) => match t.is_empty() {
 				true => self.convert_to_record().map(Value::from),
 				false => self.convert_to_record_type(t).map(Value::from),
@@ -1974,6 +2030,10 @@ impl Value {
 				})
 			}
 			Kind::Literal(lit) => self.convert_to_literal(lit),
+			// User-defined types must be resolved before use
+			Kind::Custom(name) => Err(Error::TyNotFound {
+				value: name.to_owned(),
+			}),
 		};
 		// Check for any conversion errors
 		match res {
@@ -2508,6 +2568,42 @@ impl Value {
 			})
 	}
 
+	/// Try to convert this value to a vector of a certain type, and dimension
+	pub(crate) fn convert_to_vector(self, t: &VectorType, d: &u64) -> Result<Array, Error> {
+		let kind = Kind::Vector(*t, *d);
+		self.convert_to_array()?
+			.into_iter()
+			.map(|value| {
+				let n = match t {
+					VectorType::F64 | VectorType::F32 => value.convert_to_float()?,
+					_ => value.convert_to_int()?,
+				};
+				Self::check_vector_element(n, t)
+			})
+			.collect::<Result<Array, Error>>()
+			.map_err(|e| match e {
+				Error::ConvertTo {
+					from,
+					..
+				}
+				| Error::CoerceTo {
+					from,
+					..
+				} => Error::ConvertTo {
+					from,
+					into: kind.to_string(),
+				},
+				e => e,
+			})
+			.and_then(|v| match v.len() {
+				v if v != *d as usize => Err(Error::LengthInvalid {
+					kind: kind.to_string(),
+					size: v,
+				}),
+				_ => Ok(v),
+			})
+	}
+
 	/// Try to convert this value to an `Array` of a certain type, unique values
 	pub(crate) fn convert_to_set_type(self, kind: &Kind) -> Result<Array, Error> {
 		self.convert_to_array()?
@@ -2826,6 +2922,19 @@ impl Value {
 		}
 	}
 
+	/// Compare this Value to another Value using the collation rules of a locale
+	pub fn collated_cmp(&self, other: &Value, locale: &str, numeric: bool) -> Option<Ordering> {
+		match (self, other) {
+			(Value::Strand(a), Value::Strand(b)) => {
+				match crate::fnc::util::string::collate::compare(a, b, locale, numeric) {
+					Some(o) => Some(o),
+					None => self.natural_lexical_cmp(other),
+				}
+			}
+			_ => self.partial_cmp(other),
+		}
+	}
+
 	pub fn can_be_range_bound(&self) -> bool {
 		matches!(
 			self,
@@ -2917,7 +3026,11 @@ impl Value {
 			Value::Array(v) => v.iter().any(Value::writeable),
 			Value::Object(v) => v.iter().any(|(_, v)| v.writeable()),
 			Value::Function(v) => {
-				v.is_custom() || v.is_script() || v.args().iter().any(Value::writeable)
+				v.is_custom()
+					|| v.is_script()
+					// Webhooks are queued within the transaction
+					|| v.name() == Some("http::webhook")
+					|| v.args().iter().any(Value::writeable)
 			}
 			Value::Model(m) => m.args.iter().any(Value::writeable),
 			Value::Subquery(v) => v.writeable(),
@@ -3017,7 +3130,7 @@ impl TrySub for Value {
 
 pub(crate) trait TryMul<Rhs = Self> {
 	type Output;
-	fn try_mul(self, v: Self) -> Result<Self::Output, Error>;
+	fn try_mul(self, v: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl TryMul for Value {
@@ -3025,6 +3138,8 @@ impl TryMul for Value {
 	fn try_mul(self, other: Self) -> Result<Self, Error> {
 		Ok(match (self, other) {
 			(Self::Number(v), Self::Number(w)) => Self::Number(v.try_mul(w)?),
+			(Self::Duration(v), Self::Number(w)) => Self::Duration(v.try_mul(w)?),
+			(Self::Number(v), Self::Duration(w)) => Self::Duration(w.try_mul(v)?),
 			(v, w) => return Err(Error::TryMul(v.to_raw_string(), w.to_raw_string())),
 		})
 	}
@@ -3034,7 +3149,7 @@ impl TryMul for Value {
 
 pub(crate) trait TryDiv<Rhs = Self> {
 	type Output;
-	fn try_div(self, v: Self) -> Result<Self::Output, Error>;
+	fn try_div(self, v: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl TryDiv for Value {
@@ -3042,6 +3157,8 @@ impl TryDiv for Value {
 	fn try_div(self, other: Self) -> Result<Self, Error> {
 		Ok(match (self, other) {
 			(Self::Number(v), Self::Number(w)) => Self::Number(v.try_div(w)?),
+			(Self::Duration(v), Self::Number(w)) => Self::Duration(v.try_div(w)?),
+			(Self::Duration(v), Self::Duration(w)) => Self::Number(v.try_div(w)?),
 			(v, w) => return Err(Error::TryDiv(v.to_raw_string(), w.to_raw_string())),
 		})
 	}
@@ -3051,7 +3168,7 @@ impl TryDiv for Value {
 
 pub(crate) trait TryFloatDiv<Rhs = Self> {
 	type Output;
-	fn try_float_div(self, v: Self) -> Result<Self::Output, Error>;
+	fn try_float_div(self, v: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl TryFloatDiv for Value {
@@ -3059,6 +3176,8 @@ impl TryFloatDiv for Value {
 	fn try_float_div(self, other: Self) -> Result<Self::Output, Error> {
 		Ok(match (self, other) {
 			(Self::Number(v), Self::Number(w)) => Self::Number(v.try_float_div(w)?),
+			(Self::Duration(v), Self::Number(w)) => Self::Duration(v.try_div(w)?),
+			(Self::Duration(v), Self::Duration(w)) => Self::Number(v.try_div(w)?),
 			(v, w) => return Err(Error::TryDiv(v.to_raw_string(), w.to_raw_string())),
 		})
 	}
@@ -3068,7 +3187,7 @@ impl TryFloatDiv for Value {
 
 pub(crate) trait TryRem<Rhs = Self> {
 	type Output;
-	fn try_rem(self, v: Self) -> Result<Self::Output, Error>;
+	fn try_rem(self, v: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl TryRem for Value {
@@ -3076,6 +3195,7 @@ impl TryRem for Value {
 	fn try_rem(self, other: Self) -> Result<Self, Error> {
 		Ok(match (self, other) {
 			(Self::Number(v), Self::Number(w)) => Self::Number(v.try_rem(w)?),
+			(Self::Duration(v), Self::Duration(w)) => Self::Duration(v.try_rem(w)?),
 			(v, w) => return Err(Error::TryRem(v.to_raw_string(), w.to_raw_string())),
 		})
 	}
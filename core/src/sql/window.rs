@@ -0,0 +1,77 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::Fmt;
+use crate::sql::{Idiom, Order, Value};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The names of the functions which can only be used with an `OVER` clause
+pub(crate) const RANKING: &[&str] = &["dense_rank", "lag", "lead", "rank", "row_number"];
+
+/// The `OVER (...)` clause of a window function
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	/// The values which split the results into separate partitions
+	pub partition: Vec<Idiom>,
+	/// The order of the results within each partition
+	pub order: Vec<Order>,
+}
+
+impl Window {
+	/// Compute the values of a record which are needed to evaluate a window function
+	///
+	/// The output is an array of the partition values, the order values,
+	/// and the function arguments, which is then evaluated across all of
+	/// the results once the records have been iterated.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		name: &str,
+		args: &[Value],
+	) -> Result<Value, Error> {
+		let mut partition = Vec::with_capacity(self.partition.len());
+		for v in self.partition.iter() {
+			partition.push(v.compute(stk, ctx, opt, doc).await?);
+		}
+		let mut order = Vec::with_capacity(self.order.len());
+		for v in self.order.iter() {
+			order.push(v.value.compute(stk, ctx, opt, doc).await?);
+		}
+		// Aggregate functions only evaluate the first argument for each record
+		let args = match RANKING.contains(&name) {
+			true => args,
+			false => &args[..args.len().min(1)],
+		};
+		let mut values = Vec::with_capacity(args.len());
+		for v in args.iter() {
+			values.push(stk.run(|stk| v.compute(stk, ctx, opt, doc)).await?);
+		}
+		Ok(Value::from(vec![Value::from(partition), Value::from(order), Value::from(values)]))
+	}
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("OVER (")?;
+		if !self.partition.is_empty() {
+			write!(f, "PARTITION BY {}", Fmt::comma_separated(&self.partition))?;
+			if !self.order.is_empty() {
+				f.write_str(" ")?;
+			}
+		}
+		if !self.order.is_empty() {
+			write!(f, "ORDER BY {}", Fmt::comma_separated(&self.order))?;
+		}
+		f.write_str(")")
+	}
+}
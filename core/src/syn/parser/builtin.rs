@@ -1,6 +1,6 @@
 use super::{ParseResult, Parser};
 use crate::{
-	sql::{Constant, Function, Value},
+	sql::{window::RANKING, Constant, Function, Value},
 	syn::{
 		error::MessageKind,
 		parser::{mac::expected, unexpected, SyntaxError},
@@ -89,6 +89,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("array::boolean_not") => PathKind::Function,
 		UniCase::ascii("array::boolean_or") => PathKind::Function,
 		UniCase::ascii("array::boolean_xor") => PathKind::Function,
+		UniCase::ascii("array::chunk") => PathKind::Function,
 		UniCase::ascii("array::clump") => PathKind::Function,
 		UniCase::ascii("array::combine") => PathKind::Function,
 		UniCase::ascii("array::complement") => PathKind::Function,
@@ -136,6 +137,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("array::transpose") => PathKind::Function,
 		UniCase::ascii("array::union") => PathKind::Function,
 		UniCase::ascii("array::windows") => PathKind::Function,
+		UniCase::ascii("array::zip") => PathKind::Function,
 		UniCase::ascii("array::sort::asc") => PathKind::Function,
 		UniCase::ascii("array::sort::desc") => PathKind::Function,
 		//
@@ -148,6 +150,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("crypto::sha1") => PathKind::Function,
 		UniCase::ascii("crypto::sha256") => PathKind::Function,
 		UniCase::ascii("crypto::sha512") => PathKind::Function,
+		UniCase::ascii("crypto::ed25519::verify") => PathKind::Function,
+		UniCase::ascii("crypto::hmac::sha256") => PathKind::Function,
 		UniCase::ascii("crypto::argon2::compare") => PathKind::Function,
 		UniCase::ascii("crypto::argon2::generate") => PathKind::Function,
 		UniCase::ascii("crypto::bcrypt::compare") => PathKind::Function,
@@ -157,12 +161,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("crypto::scrypt::compare") => PathKind::Function,
 		UniCase::ascii("crypto::scrypt::generate") => PathKind::Function,
 		//
+		UniCase::ascii("duration::clamp") => PathKind::Function,
 		UniCase::ascii("duration::days") => PathKind::Function,
 		UniCase::ascii("duration::hours") => PathKind::Function,
 		UniCase::ascii("duration::micros") => PathKind::Function,
 		UniCase::ascii("duration::millis") => PathKind::Function,
 		UniCase::ascii("duration::mins") => PathKind::Function,
 		UniCase::ascii("duration::nanos") => PathKind::Function,
+		UniCase::ascii("duration::round") => PathKind::Function,
 		UniCase::ascii("duration::secs") => PathKind::Function,
 		UniCase::ascii("duration::weeks") => PathKind::Function,
 		UniCase::ascii("duration::years") => PathKind::Function,
@@ -175,23 +181,34 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("duration::from::secs") => PathKind::Function,
 		UniCase::ascii("duration::from::weeks") => PathKind::Function,
 		//
+		UniCase::ascii("dense_rank") => PathKind::Function,
+		//
 		UniCase::ascii("encoding::base64::decode") => PathKind::Function,
 		UniCase::ascii("encoding::base64::encode") => PathKind::Function,
 		//
 		UniCase::ascii("geo::area") => PathKind::Function,
 		UniCase::ascii("geo::bearing") => PathKind::Function,
+		UniCase::ascii("geo::buffer") => PathKind::Function,
 		UniCase::ascii("geo::centroid") => PathKind::Function,
+		UniCase::ascii("geo::contains") => PathKind::Function,
 		UniCase::ascii("geo::distance") => PathKind::Function,
 		UniCase::ascii("geo::hash::decode") => PathKind::Function,
 		UniCase::ascii("geo::hash::encode") => PathKind::Function,
+		UniCase::ascii("geo::intersects") => PathKind::Function,
 		UniCase::ascii("geo::is::valid") => PathKind::Function,
 		//
+		UniCase::ascii("graph::shortest_path") => PathKind::Function,
+		//
 		UniCase::ascii("http::head") => PathKind::Function,
 		UniCase::ascii("http::get") => PathKind::Function,
 		UniCase::ascii("http::put") => PathKind::Function,
 		UniCase::ascii("http::post") => PathKind::Function,
 		UniCase::ascii("http::patch") => PathKind::Function,
 		UniCase::ascii("http::delete") => PathKind::Function,
+		UniCase::ascii("http::webhook") => PathKind::Function,
+		//
+		UniCase::ascii("lag") => PathKind::Function,
+		UniCase::ascii("lead") => PathKind::Function,
 		//
 		UniCase::ascii("math::abs") => PathKind::Function,
 		UniCase::ascii("math::acos") => PathKind::Function,
@@ -201,7 +218,9 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("math::bottom") => PathKind::Function,
 		UniCase::ascii("math::ceil") => PathKind::Function,
 		UniCase::ascii("math::clamp") => PathKind::Function,
+		UniCase::ascii("math::correlation") => PathKind::Function,
 		UniCase::ascii("math::cos") => PathKind::Function,
+		UniCase::ascii("math::covariance") => PathKind::Function,
 		UniCase::ascii("math::cot") => PathKind::Function,
 		UniCase::ascii("math::deg2rad") => PathKind::Function,
 		UniCase::ascii("math::fixed") => PathKind::Function,
@@ -209,6 +228,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("math::interquartile") => PathKind::Function,
 		UniCase::ascii("math::lerp") => PathKind::Function,
 		UniCase::ascii("math::lerpangle") => PathKind::Function,
+		UniCase::ascii("math::linear_regression") => PathKind::Function,
 		UniCase::ascii("math::ln") => PathKind::Function,
 		UniCase::ascii("math::log") => PathKind::Function,
 		UniCase::ascii("math::log10") => PathKind::Function,
@@ -245,6 +265,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("object::from_entries") => PathKind::Function,
 		UniCase::ascii("object::keys") => PathKind::Function,
 		UniCase::ascii("object::len") => PathKind::Function,
+		UniCase::ascii("object::merge_patch") => PathKind::Function,
+		UniCase::ascii("object::patch") => PathKind::Function,
 		UniCase::ascii("object::matches") => PathKind::Function,
 		UniCase::ascii("object::values") => PathKind::Function,
 		//
@@ -271,14 +293,20 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("rand::uuid::v7") => PathKind::Function,
 		UniCase::ascii("rand::uuid") => PathKind::Function,
 		//
+		UniCase::ascii("rank") => PathKind::Function,
+		//
 		UniCase::ascii("record::exists") => PathKind::Function,
 		UniCase::ascii("record::id") => PathKind::Function,
 		UniCase::ascii("record::table") => PathKind::Function,
 		UniCase::ascii("record::tb") => PathKind::Function,
 		//
+		UniCase::ascii("row_number") => PathKind::Function,
+		//
 		UniCase::ascii("search::analyze") => PathKind::Function,
+		UniCase::ascii("search::facets") => PathKind::Function,
 		UniCase::ascii("search::score") => PathKind::Function,
 		UniCase::ascii("search::highlight") => PathKind::Function,
+		UniCase::ascii("search::fragments") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
 		//
 		UniCase::ascii("session::ac") => PathKind::Function,
@@ -300,6 +328,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("string::lowercase") => PathKind::Function,
 		UniCase::ascii("string::repeat") => PathKind::Function,
 		UniCase::ascii("string::replace") => PathKind::Function,
+		UniCase::ascii("string::replace_regex") => PathKind::Function,
 		UniCase::ascii("string::reverse") => PathKind::Function,
 		UniCase::ascii("string::slice") => PathKind::Function,
 		UniCase::ascii("string::slug") => PathKind::Function,
@@ -350,9 +379,13 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("string::similarity::fuzzy") => PathKind::Function,
 		UniCase::ascii("string::similarity::jaro") => PathKind::Function,
 		UniCase::ascii("string::similarity::jaro_winkler") => PathKind::Function,
+		UniCase::ascii("string::similarity::levenshtein") => PathKind::Function,
+		UniCase::ascii("string::similarity::ngram") => PathKind::Function,
 		UniCase::ascii("string::similarity::smithwaterman") => PathKind::Function,
+		UniCase::ascii("string::similarity::soundex") => PathKind::Function,
 		UniCase::ascii("string::similarity::sorensen_dice") => PathKind::Function,
 		UniCase::ascii("string::matches") => PathKind::Function,
+		UniCase::ascii("string::matches_captures") => PathKind::Function,
 		//
 		UniCase::ascii("time::ceil") => PathKind::Function,
 		UniCase::ascii("time::day") => PathKind::Function,
@@ -360,6 +393,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("time::format") => PathKind::Function,
 		UniCase::ascii("time::group") => PathKind::Function,
 		UniCase::ascii("time::hour") => PathKind::Function,
+		UniCase::ascii("time::in_timezone") => PathKind::Function,
 		UniCase::ascii("time::max") => PathKind::Function,
 		UniCase::ascii("time::micros") => PathKind::Function,
 		UniCase::ascii("time::millis") => PathKind::Function,
@@ -443,6 +477,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("vector::scale") => PathKind::Function,
 		UniCase::ascii("vector::subtract") => PathKind::Function,
 		UniCase::ascii("vector::distance::chebyshev") => PathKind::Function,
+		UniCase::ascii("vector::distance::cosine") => PathKind::Function,
+		UniCase::ascii("vector::distance::dot") => PathKind::Function,
 		UniCase::ascii("vector::distance::euclidean") => PathKind::Function,
 		UniCase::ascii("vector::distance::hamming") => PathKind::Function,
 		UniCase::ascii("vector::distance::knn") => PathKind::Function,
@@ -496,10 +532,29 @@ impl Parser<'_> {
 
 		match PATHS.get_entry(&UniCase::ascii(str)) {
 			Some((_, PathKind::Constant(x))) => Ok(Value::Constant(x.clone())),
-			Some((k, PathKind::Function)) => stk
-				.run(|ctx| self.parse_builtin_function(ctx, k.into_inner().to_owned()))
-				.await
-				.map(|x| Value::Function(Box::new(x))),
+			Some((k, PathKind::Function)) => {
+				let name = k.into_inner();
+				let func = stk.run(|ctx| self.parse_builtin_function(ctx, name.to_owned())).await?;
+				// Check if this is a call to a window function
+				if !self.eat(t!("OVER")) {
+					if RANKING.contains(&name) {
+						return Err(SyntaxError::new(format_args!(
+							"The function `{name}` can only be used with an OVER clause"
+						))
+						.with_span(span, MessageKind::Error));
+					}
+					return Ok(Value::Function(Box::new(func)));
+				}
+				if !RANKING.contains(&name) && !func.is_aggregate() {
+					return Err(SyntaxError::new(format_args!(
+						"The function `{name}` can not be used as a window function"
+					))
+					.with_span(span, MessageKind::Error));
+				}
+				let window = stk.run(|ctx| self.parse_window(ctx)).await?;
+				let args = func.args().to_vec();
+				Ok(Value::Function(Box::new(Function::Window(name.to_owned(), args, window))))
+			}
 			None => {
 				// Generate a suggestion.
 				// don't search further if the levenshtein distance is further then 10.
@@ -1,7 +1,7 @@
 use reblessive::Stk;
 
 use crate::{
-	sql::{Function, Ident, Model, Value},
+	sql::{Function, Ident, Model, Value, Window},
 	syn::{
 		error::syntax_error,
 		parser::mac::{expected, expected_whitespace, unexpected},
@@ -46,6 +46,34 @@ impl Parser<'_> {
 		Ok(args)
 	}
 
+	/// Parse the clause of a window function
+	///
+	/// Expects `OVER` to already be called.
+	pub(super) async fn parse_window(&mut self, ctx: &mut Stk) -> ParseResult<Window> {
+		let start = expected!(self, t!("(")).span;
+		let mut window = Window::default();
+		if self.eat(t!("PARTITION")) {
+			expected!(self, t!("BY"));
+			loop {
+				window.partition.push(self.parse_basic_idiom(ctx).await?);
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		if self.eat(t!("ORDER")) {
+			expected!(self, t!("BY"));
+			loop {
+				window.order.push(self.parse_order(ctx).await?);
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		self.expect_closing_delimiter(t!(")"), start)?;
+		Ok(window)
+	}
+
 	/// Parse a model invocation
 	///
 	/// Expects `ml` to already be called.
@@ -158,6 +186,18 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn function_window() {
+		let sql = "math::sum(amount) OVER (PARTITION BY customer ORDER BY time DESC)";
+		let out = Value::parse(sql);
+		assert_eq!(sql, format!("{}", out));
+		let sql = "row_number() over ()";
+		let out = Value::parse(sql);
+		assert_eq!("row_number() OVER ()", format!("{}", out));
+		assert!(syn::value("row_number()").is_err());
+		assert!(syn::value("string::len(name) OVER ()").is_err());
+	}
+
 	#[test]
 	fn function_simple_together() {
 		let sql = "function() { return 'test'; }";
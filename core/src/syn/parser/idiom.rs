@@ -2,8 +2,8 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		part::DestructurePart, Dir, Edges, Field, Fields, Graph, Ident, Idiom, Part, Table, Tables,
-		Value,
+		part::{DestructurePart, Recurse},
+		Dir, Edges, Field, Fields, Graph, Ident, Idiom, Part, Table, Tables, Value,
 	},
 	syn::{
 		error::bail,
@@ -22,6 +22,15 @@ impl Parser<'_> {
 		peek == t!("<") && matches!(self.peek1().kind, t!("-") | t!("->"))
 	}
 
+	/// Returns if the next tokens make the preceding graph traversal recursive, like `{1..5}`.
+	///
+	/// The `{` must directly follow the traversal, without any whitespace, so that it can not be
+	/// confused with a following block.
+	pub(super) fn peek_starts_recurse(&mut self) -> bool {
+		self.peek_whitespace().kind == t!("{")
+			&& matches!(self.peek_whitespace_token_at(1).kind, TokenKind::Digits | t!(".."))
+	}
+
 	/// Parse fields of a selecting query: `foo, bar` in `SELECT foo, bar FROM baz`.
 	///
 	/// # Parser State
@@ -87,6 +96,10 @@ impl Parser<'_> {
 	) -> ParseResult<Idiom> {
 		let mut res = start;
 		loop {
+			if matches!(res.last(), Some(Part::Graph(_))) && self.peek_starts_recurse() {
+				stk.run(|stk| self.parse_recurse(stk, &mut res)).await?;
+				continue;
+			}
 			match self.peek_kind() {
 				t!("?") => {
 					self.pop_peek();
@@ -149,6 +162,10 @@ impl Parser<'_> {
 	) -> ParseResult<Value> {
 		let mut res = start;
 		loop {
+			if matches!(res.last(), Some(Part::Graph(_))) && self.peek_starts_recurse() {
+				ctx.run(|ctx| self.parse_recurse(ctx, &mut res)).await?;
+				continue;
+			}
 			match self.peek_kind() {
 				t!("?") => {
 					self.pop_peek();
@@ -214,7 +231,11 @@ impl Parser<'_> {
 		let graph = ctx.run(|ctx| self.parse_graph(ctx, dir)).await?;
 		// the production `Thing Graph` is reparsed as an edge if the graph does not contain an
 		// alias or a condition.
-		if res.len() == 1 && graph.alias.is_none() && graph.cond.is_none() {
+		if res.len() == 1
+			&& graph.alias.is_none()
+			&& graph.cond.is_none()
+			&& !self.peek_starts_recurse()
+		{
 			match std::mem::replace(&mut res[0], Part::All) {
 				Part::Value(Value::Thing(t)) | Part::Start(Value::Thing(t)) => {
 					let edge = Edges {
@@ -239,6 +260,58 @@ impl Parser<'_> {
 		Ok(None)
 	}
 
+	/// Parse the depth of a recursive graph traversal, and the record traversal which follows it,
+	/// i.e. `{1..5}->person` in `->knows{1..5}->person`.
+	///
+	/// # Parser state
+	/// Expects the edge traversal to be the last part in `res`, and the `{` to be the next token.
+	async fn parse_recurse(&mut self, ctx: &mut Stk, res: &mut Vec<Part>) -> ParseResult<()> {
+		let start = self.pop_peek().span;
+		let (min, max): (u32, u32) = if self.eat(t!("..")) {
+			(1, self.next_token_value()?)
+		} else {
+			let min = self.next_token_value()?;
+			match self.eat(t!("..")) {
+				true => (min, self.next_token_value()?),
+				false => (min, min),
+			}
+		};
+		self.expect_closing_delimiter(t!("}"), start)?;
+		if max == 0 || min > max {
+			bail!("Invalid recursion depth `{min}..{max}`", @self.last_span() => "The maximum depth must be at least 1, and not less than the minimum depth");
+		}
+		// The edge traversal must be followed by a record traversal
+		let token = self.peek();
+		let dir = match token.kind {
+			t!("->") => {
+				self.pop_peek();
+				Dir::Out
+			}
+			t!("<") => {
+				self.pop_peek();
+				if self.eat_whitespace(t!("-")) {
+					Dir::In
+				} else if self.eat_whitespace(t!("->")) {
+					Dir::Both
+				} else {
+					unexpected!(self, token, "either `<-` `<->` or `->`")
+				}
+			}
+			_ => unexpected!(self, token, "a graph traversal after a recursive edge traversal"),
+		};
+		let node = ctx.run(|ctx| self.parse_graph(ctx, dir)).await?;
+		// The edge traversal is checked before calling this function.
+		let Some(Part::Graph(edge)) = res.pop() else {
+			unreachable!()
+		};
+		res.push(Part::Recurse(Recurse {
+			min,
+			max,
+			path: vec![Part::Graph(edge), Part::Graph(node)],
+		}));
+		Ok(())
+	}
+
 	/// Parse a idiom which can only start with a graph or an identifier.
 	/// Other expressions are not allowed as start of this idiom
 	pub async fn parse_plain_idiom(&mut self, ctx: &mut Stk) -> ParseResult<Idiom> {
@@ -145,6 +145,16 @@ impl Parser<'_> {
 					Ok(Kind::Set(Box::new(Kind::Any), None))
 				}
 			}
+			t!("VECTOR") => {
+				let span = expected!(self, t!("<")).span;
+				let t = self.parse_vector_type()?;
+				expected!(self, t!(","));
+				let dimension = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(">"), span)?;
+				Ok(Kind::Vector(t, dimension))
+			}
+			// Any other name refers to a user-defined type
+			TokenKind::Identifier => Ok(Kind::Custom(self.lexer.string.take().unwrap())),
 			_ => unexpected!(self, next, "a kind name"),
 		}
 	}
@@ -231,6 +241,7 @@ mod tests {
 	use reblessive::Stack;
 
 	use super::*;
+	use crate::sql::index::VectorType;
 	use crate::sql::table::Table;
 
 	fn kind(i: &str) -> ParseResult<Kind> {
@@ -542,4 +553,25 @@ mod tests {
 			])
 		);
 	}
+
+	#[test]
+	fn kind_custom() {
+		let sql = "option<array<address>>";
+		let res = kind(sql);
+		let out = res.unwrap();
+		assert_eq!("option<array<address>>", format!("{}", out));
+		assert_eq!(
+			out,
+			Kind::Option(Box::new(Kind::Array(Box::new(Kind::Custom("address".to_owned())), None)))
+		);
+	}
+
+	#[test]
+	fn kind_vector() {
+		let sql = "vector<f32, 768>";
+		let res = kind(sql);
+		let out = res.unwrap();
+		assert_eq!("vector<f32, 768>", format!("{}", out));
+		assert_eq!(out, Kind::Vector(VectorType::F32, 768));
+	}
 }
@@ -334,7 +334,11 @@ impl Parser<'_> {
 		};
 
 		// Parse the rest of the idiom if it is being continued.
-		if self.peek_continues_idiom() {
+		let recurse = match &value {
+			Value::Idiom(Idiom(x)) => matches!(x.last(), Some(Part::Graph(_))),
+			_ => false,
+		} && self.peek_starts_recurse();
+		if recurse || self.peek_continues_idiom() {
 			let value = match value {
 				Value::Idiom(Idiom(x)) => self.parse_remaining_value_idiom(ctx, x).await,
 				Value::Table(Table(x)) => {
@@ -1,11 +1,12 @@
 use reblessive::Stk;
 
 use crate::cnf::EXPERIMENTAL_BEARER_ACCESS;
+use crate::iam::ResourceKind;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::index::HnswParams;
 use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig};
 use crate::sql::statements::define::config::ConfigInner;
-use crate::sql::statements::define::DefineConfigStatement;
+use crate::sql::statements::define::{DefineConfigStatement, OnDelete, SinkKind};
 use crate::sql::Value;
 use crate::{
 	sql::{
@@ -17,14 +18,16 @@ use crate::{
 			define::config::graphql, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
+			DefineParamStatement, DefineRoleStatement, DefineSinkStatement, DefineStatement,
+			DefineTableStatement, DefineTypeStatement, DefineUserStatement, RoleAction, RoleGrant,
 		},
 		table_type,
 		tokenizer::Tokenizer,
-		user, AccessType, Ident, Idioms, Index, Kind, Param, Permissions, Scoring, Strand,
-		TableType, Values,
+		user, AccessType, Ident, Idiom, Idioms, Index, Kind, Param, Part, Permissions, Scoring,
+		Strand, TableType, Values,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -60,6 +63,11 @@ impl Parser<'_> {
 			t!("ANALYZER") => self.parse_define_analyzer().map(DefineStatement::Analyzer),
 			t!("ACCESS") => self.parse_define_access(ctx).await.map(DefineStatement::Access),
 			t!("CONFIG") => self.parse_define_config().map(DefineStatement::Config),
+			t!("TYPE") => {
+				ctx.run(|ctx| self.parse_define_type(ctx)).await.map(DefineStatement::Type)
+			}
+			t!("SINK") => self.parse_define_sink().map(DefineStatement::Sink),
+			t!("ROLE") => self.parse_define_role().map(DefineStatement::Role),
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -238,24 +246,7 @@ impl Parser<'_> {
 				}
 				t!("ROLES") => {
 					self.pop_peek();
-					let mut roles = Vec::new();
-					loop {
-						let token = self.peek();
-						let role = self.next_token_value::<Ident>()?;
-						// NOTE(gguillemas): This hardcoded list is a temporary fix in order
-						// to avoid making breaking changes to the DefineUserStatement structure
-						// while still providing parsing feedback to users referencing unexistent roles.
-						// This list should be removed once arbitrary roles can be defined by users.
-						if !matches!(role.to_lowercase().as_str(), "viewer" | "editor" | "owner") {
-							unexpected!(self, token, "an existent role");
-						}
-						roles.push(role);
-
-						if !self.eat(t!(",")) {
-							res.roles = roles;
-							break;
-						}
-					}
+					res.roles = self.parse_roles()?;
 				}
 				t!("DURATION") => {
 					self.pop_peek();
@@ -295,6 +286,107 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_role(&mut self) -> ParseResult<DefineRoleStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefineRoleStatement {
+			name,
+			base,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("GRANT") => {
+					self.pop_peek();
+					let grant = self.parse_role_grant(&res.base)?;
+					res.grants.push(grant);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// Parses the action, resources, and scope allowed by a role.
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `GRANT` keyword
+	fn parse_role_grant(&mut self, base: &Base) -> ParseResult<RoleGrant> {
+		let next = self.next();
+		let mut res = RoleGrant {
+			action: match next.kind {
+				t!("VIEW") => RoleAction::View,
+				t!("EDIT") => RoleAction::Edit,
+				_ => unexpected!(self, next, "either VIEW or EDIT"),
+			},
+			..Default::default()
+		};
+		if self.eat(t!("ON")) {
+			loop {
+				let next = self.next();
+				res.kinds.push(match next.kind {
+					t!("NAMESPACE") => ResourceKind::Namespace,
+					t!("DATABASE") => ResourceKind::Database,
+					t!("TABLE") => ResourceKind::Table,
+					t!("FIELD") => ResourceKind::Field,
+					t!("INDEX") => ResourceKind::Index,
+					t!("EVENT") => ResourceKind::Event,
+					t!("FUNCTION") => ResourceKind::Function,
+					t!("PARAM") => ResourceKind::Parameter,
+					t!("ANALYZER") => ResourceKind::Analyzer,
+					t!("MODEL") => ResourceKind::Model,
+					t!("ACCESS") => ResourceKind::Access,
+					t!("TYPE") => ResourceKind::Type,
+					t!("SINK") => ResourceKind::Sink,
+					t!("USER") => ResourceKind::Actor,
+					_ => unexpected!(self, next, "a resource type"),
+				});
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+		if self.eat(t!("IN")) {
+			let next = self.next();
+			match (next.kind, base) {
+				// Roles on the root can be limited to a namespace or one of its databases
+				(t!("NAMESPACE"), Base::Root) => {
+					res.ns = Some(self.next_token_value()?);
+					if self.eat(t!("DATABASE")) {
+						res.db = Some(self.next_token_value()?);
+					}
+				}
+				// Roles on a namespace can be limited to one of its databases
+				(t!("DATABASE"), Base::Ns) => {
+					res.db = Some(self.next_token_value()?);
+				}
+				(_, Base::Root) => unexpected!(self, next, "NAMESPACE"),
+				(_, Base::Ns) => unexpected!(self, next, "DATABASE"),
+				_ => unexpected!(self, next, "a role on the root or a namespace"),
+			}
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_access(
 		&mut self,
 		stk: &mut Stk,
@@ -404,6 +496,25 @@ impl Parser<'_> {
 							}
 							res.kind = AccessType::Bearer(ac);
 						}
+						t!("KEY") => {
+							// Key access relies on the experimental access grant management
+							if !*EXPERIMENTAL_BEARER_ACCESS {
+								unexpected!(
+									self,
+									peek,
+									"the experimental bearer access feature to be enabled for key access"
+								);
+							}
+
+							self.pop_peek();
+							let mut ac = access_type::KeyAccess {
+								..Default::default()
+							};
+							if self.eat(t!("ROLES")) {
+								ac.roles = self.parse_roles()?;
+							}
+							res.kind = AccessType::Key(ac);
+						}
 						_ => break,
 					}
 				}
@@ -515,11 +626,9 @@ impl Parser<'_> {
 								}
 								TokenKind::Keyword(Keyword::Jwks) => {
 									expected!(self, t!("VALUE"));
-									ac.jwt.verify = access_type::JwtAccessVerify::Jwks(
-										access_type::JwtAccessVerifyJwks {
-											url: self.next_token_value::<Strand>()?.0,
-										},
-									);
+									let url = self.next_token_value::<Strand>()?.0;
+									ac.jwt.verify =
+										access_type::JwtAccessVerify::Jwks(self.parse_jwks(url)?);
 								}
 								_ => unexpected!(self, next, "a token algorithm or 'JWKS'"),
 							}
@@ -558,11 +667,9 @@ impl Parser<'_> {
 								}
 								TokenKind::Keyword(Keyword::Jwks) => {
 									expected!(self, t!("VALUE"));
-									ac.verify = access_type::JwtAccessVerify::Jwks(
-										access_type::JwtAccessVerifyJwks {
-											url: self.next_token_value::<Strand>()?.0,
-										},
-									);
+									let url = self.next_token_value::<Strand>()?.0;
+									ac.verify =
+										access_type::JwtAccessVerify::Jwks(self.parse_jwks(url)?);
 								}
 								_ => unexpected!(self, next, "a token algorithm or 'JWKS'"),
 							}
@@ -669,6 +776,88 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub async fn parse_define_type(&mut self, ctx: &mut Stk) -> ParseResult<DefineTypeStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		let kind = ctx.run(|ctx| self.parse_inner_kind(ctx)).await?;
+
+		let mut res = DefineTypeStatement {
+			name,
+			kind,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		while let t!("COMMENT") = self.peek_kind() {
+			self.pop_peek();
+			res.comment = Some(self.next_token_value()?);
+		}
+		Ok(res)
+	}
+
+	pub fn parse_define_sink(&mut self) -> ParseResult<DefineSinkStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		if !self.eat(t!("ON")) {
+			expected!(self, t!("FOR"));
+		}
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+
+		let mut res = DefineSinkStatement {
+			name,
+			what,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("TYPE") => {
+					self.pop_peek();
+					let next = self.next();
+					res.kind = match next.kind {
+						t!("KAFKA") => SinkKind::Kafka,
+						t!("NATS") => SinkKind::Nats,
+						_ => unexpected!(self, next, "a sink type"),
+					};
+				}
+				t!("URL") => {
+					self.pop_peek();
+					res.url = self.next_token_value()?;
+				}
+				t!("TOPIC") => {
+					self.pop_peek();
+					res.topic = self.next_token_value()?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_table(&mut self, ctx: &mut Stk) -> ParseResult<DefineTableStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -738,6 +927,15 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("TTL") => {
+					self.pop_peek();
+					res.ttl = Some(self.parse_ttl(ctx).await?);
+				}
+				t!("PARTITION") => {
+					self.pop_peek();
+					expected!(self, t!("BY"));
+					res.partition = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -801,6 +999,10 @@ impl Parser<'_> {
 						res.then.0.push(ctx.run(|ctx| self.parse_value_field(ctx)).await?)
 					}
 				}
+				t!("ASYNC") => {
+					self.pop_peek();
+					res.asynchronous = true;
+				}
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = Some(self.next_token_value()?);
@@ -834,6 +1036,7 @@ impl Parser<'_> {
 			..Default::default()
 		};
 
+		let mut computed = None;
 		loop {
 			match self.peek_kind() {
 				// FLEX, FLEXI and FLEXIBLE are all the same token type.
@@ -853,10 +1056,32 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.value = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
 				}
+				t!("COMPUTED") => {
+					computed = Some(self.pop_peek().span);
+					res.computed = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
 				t!("ASSERT") => {
 					self.pop_peek();
 					res.assert = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
 				}
+				t!("CHECK") => {
+					self.pop_peek();
+					res.check = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
+				t!("ON") => {
+					self.pop_peek();
+					expected!(self, t!("DELETE"));
+					let next = self.next();
+					res.on_delete = Some(match next.kind {
+						t!("RESTRICT") => OnDelete::Restrict,
+						t!("CASCADE") => OnDelete::Cascade,
+						t!("SET") => {
+							expected!(self, t!("NONE"));
+							OnDelete::Unset
+						}
+						_ => unexpected!(self, next, "RESTRICT, CASCADE, or SET NONE"),
+					});
+				}
 				t!("DEFAULT") => {
 					self.pop_peek();
 					res.default = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
@@ -873,6 +1098,14 @@ impl Parser<'_> {
 			}
 		}
 
+		// A computed field always derives its own value
+		if let Some(span) = computed {
+			if res.value.is_some() || res.default.is_some() {
+				bail!("A COMPUTED field can not also have a VALUE or DEFAULT clause",
+					@span => "The field is computed here")
+			}
+		}
+
 		Ok(res)
 	}
 
@@ -905,15 +1138,29 @@ impl Parser<'_> {
 				// COLUMNS and FIELDS are the same tokenkind
 				t!("FIELDS") => {
 					self.pop_peek();
-					res.cols = Idioms(vec![self.parse_local_idiom(ctx).await?]);
+					res.cols = Idioms(vec![self.parse_index_column(ctx).await?]);
 					while self.eat(t!(",")) {
-						res.cols.0.push(self.parse_local_idiom(ctx).await?);
+						res.cols.0.push(self.parse_index_column(ctx).await?);
+					}
+				}
+				t!("WHERE") => {
+					res.cond = self.try_parse_condition(ctx).await?;
+				}
+				t!("COLLATE") => {
+					self.pop_peek();
+					res.collate = vec![self.parse_collation()?];
+					while self.eat(t!(",")) {
+						res.collate.push(self.parse_collation()?);
 					}
 				}
 				t!("UNIQUE") => {
 					self.pop_peek();
 					res.index = Index::Uniq;
 				}
+				t!("SPATIAL") => {
+					self.pop_peek();
+					res.index = Index::Spatial;
+				}
 				t!("SEARCH") => {
 					self.pop_peek();
 					let mut analyzer: Option<Ident> = None;
@@ -1139,6 +1386,23 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses a column of an index, either a local idiom,
+	/// or a builtin function computing the indexed value from the record.
+	async fn parse_index_column(&mut self, ctx: &mut Stk) -> ParseResult<Idiom> {
+		let token = self.peek();
+		if !Self::kind_is_identifier(token.kind) || self.peek1().kind != t!("::") {
+			return self.parse_local_idiom(ctx).await;
+		}
+		self.pop_peek();
+		match self.parse_builtin(ctx, token.span).await? {
+			v @ Value::Function(_) => Ok(Idiom(vec![Part::Start(v)])),
+			_ => {
+				let span = token.span.covers(self.last_span());
+				bail!("Expected a function or a field as an index column", @span)
+			}
+		}
+	}
+
 	pub fn parse_define_analyzer(&mut self) -> ParseResult<DefineAnalyzerStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -1206,6 +1470,19 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path.into()))
 							}
+							t!("STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let mut words = Vec::new();
+								loop {
+									let word: Strand = self.next_token_value()?;
+									words.push(word.0);
+									if !self.eat(t!(",")) {
+										break;
+									}
+								}
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Stopwords(words))
+							}
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -1225,6 +1502,18 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							t!("NGRAM") => {
+								let open_span = expected!(self, t!("(")).span;
+								let n = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								Tokenizer::Ngram(n)
+							}
+							t!("DICTIONARY") => {
+								let open_span = expected!(self, t!("(")).span;
+								let path: Strand = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								Tokenizer::Dictionary(path.into())
+							}
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -1449,9 +1738,7 @@ impl Parser<'_> {
 			t!("URL") => {
 				self.pop_peek();
 				let url = self.next_token_value::<Strand>()?.0;
-				res.verify = access_type::JwtAccessVerify::Jwks(access_type::JwtAccessVerifyJwks {
-					url,
-				});
+				res.verify = access_type::JwtAccessVerify::Jwks(self.parse_jwks(url)?);
 			}
 			_ => unexpected!(self, peek, "`ALGORITHM`, or `URL`"),
 		}
@@ -1500,4 +1787,39 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	/// Parses a comma separated list of the roles granted by a user or access method.
+	pub fn parse_roles(&mut self) -> ParseResult<Vec<Ident>> {
+		let mut roles = Vec::new();
+		loop {
+			// Custom roles are checked to exist when the statement is processed
+			roles.push(self.next_token_value::<Ident>()?);
+
+			if !self.eat(t!(",")) {
+				return Ok(roles);
+			}
+		}
+	}
+
+	/// Parses the optional claims which tokens verified with a JWKS URL must contain.
+	pub fn parse_jwks(&mut self, url: String) -> ParseResult<access_type::JwtAccessVerifyJwks> {
+		let mut res = access_type::JwtAccessVerifyJwks {
+			url,
+			..Default::default()
+		};
+		loop {
+			match self.peek_kind() {
+				t!("ISSUER") => {
+					self.pop_peek();
+					res.issuer = Some(self.next_token_value::<Strand>()?.0);
+				}
+				t!("AUDIENCE") => {
+					self.pop_peek();
+					res.audience = Some(self.next_token_value::<Strand>()?.0);
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
 }
@@ -21,7 +21,7 @@ use crate::{
 		statements::{
 			analyze::AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
 			CommitStatement, ContinueStatement, ForeachStatement, InfoStatement, OutputStatement,
-			UseStatement,
+			RevokeKind, RevokeStatement, RollbackStatement, SavepointStatement, UseStatement,
 		},
 		Expression, Operator, Statement, Statements, Value,
 	},
@@ -137,6 +137,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_delete_stmt(ctx)).await.map(Statement::Delete)
 			}
+			t!("EXPLAIN") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_explain_stmt(ctx)).await.map(Statement::Select)
+			}
 			t!("FOR") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_for_stmt(ctx)).await.map(Statement::Foreach)
@@ -181,6 +185,18 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_remove_stmt(ctx)).await.map(Statement::Remove)
 			}
+			t!("REVOKE") if matches!(self.peek1().kind, t!("TOKEN") | t!("SESSION")) => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_revoke_stmt(ctx)).await.map(Statement::Revoke)
+			}
+			t!("ROLLBACK") => {
+				self.pop_peek();
+				self.parse_rollback().map(Statement::Rollback)
+			}
+			t!("SAVEPOINT") => {
+				self.pop_peek();
+				self.parse_savepoint().map(Statement::Savepoint)
+			}
 			t!("SELECT") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_select_stmt(ctx)).await.map(Statement::Select)
@@ -254,6 +270,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_delete_stmt(ctx).await.map(Entry::Delete)
 			}
+			t!("EXPLAIN") => {
+				self.pop_peek();
+				self.parse_explain_stmt(ctx).await.map(Entry::Select)
+			}
 			t!("FOR") => {
 				self.pop_peek();
 				self.parse_for_stmt(ctx).await.map(Entry::Foreach)
@@ -381,7 +401,16 @@ impl Parser<'_> {
 							subject: Subject::Record(rid),
 						}))
 					}
-					_ => unexpected!(self, peek, "either USER or RECORD"),
+					t!("KEY") => {
+						self.pop_peek();
+						let name = self.next_token_value()?;
+						Ok(AccessStatement::Grant(AccessStatementGrant {
+							ac,
+							base,
+							subject: Subject::Key(name),
+						}))
+					}
+					_ => unexpected!(self, peek, "one of USER, RECORD or KEY"),
 				}
 			}
 			t!("SHOW") => {
@@ -492,6 +521,10 @@ impl Parser<'_> {
 
 	/// Parsers a analyze statement.
 	fn parse_analyze(&mut self) -> ParseResult<AnalyzeStatement> {
+		if self.eat(t!("TABLE")) {
+			let table = self.next_token_value()?;
+			return Ok(AnalyzeStatement::Table(table));
+		}
 		expected!(self, t!("INDEX"));
 
 		let index = self.next_token_value()?;
@@ -534,6 +567,52 @@ impl Parser<'_> {
 		Ok(CommitStatement)
 	}
 
+	/// Parsers a savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `SAVEPOINT` to already be consumed.
+	fn parse_savepoint(&mut self) -> ParseResult<SavepointStatement> {
+		let name = self.next_token_value()?;
+		Ok(SavepointStatement {
+			name,
+		})
+	}
+
+	/// Parsers a rollback statement.
+	///
+	/// # Parser State
+	/// Expects `ROLLBACK` to already be consumed.
+	fn parse_rollback(&mut self) -> ParseResult<RollbackStatement> {
+		expected!(self, t!("TO"));
+		self.eat(t!("SAVEPOINT"));
+		let name = self.next_token_value()?;
+		Ok(RollbackStatement {
+			name,
+		})
+	}
+
+	/// Parsers a REVOKE TOKEN or REVOKE SESSION statement.
+	///
+	/// # Parser State
+	/// Expects `REVOKE` to already be consumed.
+	async fn parse_revoke_stmt(&mut self, ctx: &mut Stk) -> ParseResult<RevokeStatement> {
+		let next = self.next();
+		let kind = match next.kind {
+			t!("TOKEN") => RevokeKind::Token,
+			t!("SESSION") => RevokeKind::Session,
+			_ => unexpected!(self, next, "either TOKEN or SESSION"),
+		};
+		let id = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		let base = self.eat(t!("ON")).then(|| self.parse_base(false)).transpose()?;
+		let duration = self.eat(t!("DURATION")).then(|| self.next_token_value()).transpose()?;
+		Ok(RevokeStatement {
+			kind,
+			id,
+			base,
+			duration,
+		})
+	}
+
 	/// Parsers a USE statement.
 	///
 	/// # Parser State
@@ -610,6 +689,7 @@ impl Parser<'_> {
 				let table = self.next_token_value()?;
 				InfoStatement::Index(index, table, false)
 			}
+			t!("QUERIES") => InfoStatement::Queries(false),
 			_ => unexpected!(self, next, "an info target"),
 		};
 
@@ -629,6 +709,7 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `KILL` to already be consumed.
 	pub(super) fn parse_kill_stmt(&mut self) -> ParseResult<KillStatement> {
+		let query = self.eat(t!("QUERY"));
 		let peek = self.peek();
 		let id = match peek.kind {
 			t!("u\"") | t!("u'") | TokenKind::Glued(Glued::Uuid) => {
@@ -639,6 +720,7 @@ impl Parser<'_> {
 		};
 		Ok(KillStatement {
 			id,
+			query,
 		})
 	}
 
@@ -649,6 +731,7 @@ impl Parser<'_> {
 	pub(super) async fn parse_live_stmt(&mut self, stk: &mut Stk) -> ParseResult<LiveStatement> {
 		expected!(self, t!("SELECT"));
 
+		let before = self.peek().span;
 		let expr = match self.peek_kind() {
 			t!("DIFF") => {
 				self.pop_peek();
@@ -656,15 +739,21 @@ impl Parser<'_> {
 			}
 			_ => self.parse_fields(stk).await?,
 		};
+		let fields_span = before.covers(self.last_span());
 		expected!(self, t!("FROM"));
 		let what = match self.peek().kind {
 			t!("$param") => Value::Param(self.next_token_value()?),
 			_ => Value::Table(self.next_token_value()?),
 		};
 		let cond = self.try_parse_condition(stk).await?;
+		let group = self.try_parse_group(stk, &expr, fields_span).await?;
 		let fetch = self.try_parse_fetch(stk).await?;
+		let resume = match self.eat(t!("RESUME")) {
+			true => Some(stk.run(|stk| self.parse_value_field(stk)).await?),
+			false => None,
+		};
 
-		Ok(LiveStatement::from_source_parts(expr, what, cond, fetch))
+		Ok(LiveStatement::from_source_parts(expr, what, cond, group, fetch, resume))
 	}
 
 	/// Parsers a OPTION statement.
@@ -703,11 +792,13 @@ impl Parser<'_> {
 				expected!(self, t!("ON"));
 				self.eat(t!("TABLE"));
 				let what = self.next_token_value()?;
+				let concurrently = self.eat(t!("CONCURRENTLY"));
 
 				RebuildStatement::Index(RebuildIndexStatement {
 					what,
 					name,
 					if_exists,
+					concurrently,
 				})
 			}
 			_ => unexpected!(self, next, "a rebuild statement keyword"),
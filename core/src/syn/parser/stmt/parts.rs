@@ -1,5 +1,7 @@
 //! Contains parsing code for smaller common parts of statements.
 
+use std::collections::BTreeMap;
+
 use reblessive::Stk;
 
 use crate::sql::Fetch;
@@ -7,9 +9,9 @@ use crate::syn::error::bail;
 use crate::{
 	sql::{
 		changefeed::ChangeFeed,
-		index::{Distance, VectorType},
-		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
-		Permission, Permissions, Tables, Timeout, Value, View,
+		index::{Collation, Distance, VectorType},
+		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Object,
+		Output, Permission, Permissions, Tables, Timeout, Ttl, Value, View,
 	},
 	syn::{
 		parser::{
@@ -97,11 +99,64 @@ impl Parser<'_> {
 				self.pop_peek();
 				Output::Before
 			}
-			_ => Output::Fields(self.parse_fields(ctx).await?),
+			t!("{") if self.peek_starts_projection() => {
+				let start = self.pop_peek().span;
+				let expr = self.parse_projection(ctx, start).await?;
+				Output::Fields(Fields(
+					vec![Field::Single {
+						expr,
+						alias: None,
+					}],
+					true,
+				))
+			}
+			_ => {
+				let mut fields = self.parse_fields(ctx).await?;
+				// A single object projection is output as the record itself
+				if let [Field::Single {
+					expr: Value::Object(_),
+					alias: None,
+				}] = fields.0.as_slice()
+				{
+					fields.1 = true;
+				}
+				Output::Fields(fields)
+			}
 		};
 		Ok(Some(res))
 	}
 
+	/// Returns if the next tokens start an object projection, i.e. `{ id, name }`.
+	fn peek_starts_projection(&mut self) -> bool {
+		Self::kind_is_identifier(self.peek_token_at(1).kind)
+			&& matches!(self.peek_token_at(2).kind, t!(",") | t!("}") | t!(":"))
+	}
+
+	/// Parses an object projection, where fields without a value are shorthand
+	/// for the field of the same name, i.e. `{ id, name, at: time::now() }`.
+	///
+	/// # Parser state
+	/// Expects the first `{` to already have been eaten.
+	async fn parse_projection(&mut self, ctx: &mut Stk, start: Span) -> ParseResult<Value> {
+		let mut map = BTreeMap::new();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let key = self.parse_object_key()?;
+			let value = match self.eat(t!(":")) {
+				true => ctx.run(|ctx| self.parse_value_inherit(ctx)).await?,
+				false => Value::Idiom(Idiom::from(key.clone())),
+			};
+			map.insert(key, value);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), start)?;
+				break;
+			}
+		}
+		Ok(Value::Object(Object(map)))
+	}
+
 	/// Parses a statement timeout if the next token is `TIMEOUT`.
 	pub fn try_parse_timeout(&mut self) -> ParseResult<Option<Timeout>> {
 		if !self.eat(t!("TIMEOUT")) {
@@ -425,6 +480,24 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a table TTL production
+	///
+	/// # Parse State
+	/// Expects the parser to have already eaten the `TTL` keyword.
+	pub async fn parse_ttl(&mut self, ctx: &mut Stk) -> ParseResult<Ttl> {
+		let duration = self.next_token_value::<Duration>()?.0;
+		let field = if self.eat(t!("ON")) {
+			Some(self.parse_local_idiom(ctx).await?)
+		} else {
+			None
+		};
+
+		Ok(Ttl {
+			duration,
+			field,
+		})
+	}
+
 	/// Parses a view production
 	///
 	/// # Parse State
@@ -476,6 +549,15 @@ impl Parser<'_> {
 		}
 	}
 
+	pub fn parse_collation(&mut self) -> ParseResult<Collation> {
+		let next = self.next();
+		match next.kind {
+			t!("NOCASE") => Ok(Collation::NoCase),
+			t!("NFKC") => Ok(Collation::Nfkc),
+			_ => unexpected!(self, next, "a collation"),
+		}
+	}
+
 	pub fn parse_vector_type(&mut self) -> ParseResult<VectorType> {
 		let next = self.next();
 		match next.kind {
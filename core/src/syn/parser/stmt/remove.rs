@@ -5,7 +5,8 @@ use crate::{
 		statements::{
 			remove::RemoveAnalyzerStatement, RemoveAccessStatement, RemoveDatabaseStatement,
 			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
-			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
+			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
+			RemoveRoleStatement, RemoveSinkStatement, RemoveStatement, RemoveTypeStatement,
 			RemoveUserStatement,
 		},
 		Param,
@@ -200,6 +201,57 @@ impl Parser<'_> {
 				})
 			}
 			// TODO(raphaeldarley): add Config here
+			t!("TYPE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+
+				RemoveStatement::Type(RemoveTypeStatement {
+					name,
+					if_exists,
+				})
+			}
+			t!("SINK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				if !self.eat(t!("ON")) {
+					expected!(self, t!("FOR"));
+				}
+				self.eat(t!("TABLE"));
+				let what = self.next_token_value()?;
+
+				RemoveStatement::Sink(RemoveSinkStatement {
+					name,
+					what,
+					if_exists,
+				})
+			}
+			t!("ROLE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+
+				RemoveStatement::Role(RemoveRoleStatement {
+					name,
+					base,
+					if_exists,
+				})
+			}
 			_ => unexpected!(self, next, "a remove statement keyword"),
 		};
 		Ok(res)
@@ -1,13 +1,15 @@
 use reblessive::Stk;
 
 use crate::{
+	fnc::util::string::collate,
 	sql::{
 		order::{OrderList, Ordering},
 		statements::SelectStatement,
-		Explain, Field, Fields, Ident, Idioms, Limit, Order, Split, Splits, Start, Values, Version,
-		With,
+		Explain, Field, Fields, Ident, Idioms, Limit, Order, Split, Splits, Start, Strand, Values,
+		Version, With,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -19,6 +21,19 @@ use crate::{
 use super::parts::MissingKind;
 
 impl Parser<'_> {
+	/// Parses an `EXPLAIN [FULL] SELECT ...` statement, which reports the full query plan
+	pub(crate) async fn parse_explain_stmt(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<SelectStatement> {
+		let full = self.eat(t!("FULL"));
+		expected!(self, t!("SELECT"));
+		let mut stmt = self.parse_select_stmt(stk).await?;
+		let full = full || stmt.explain.as_ref().is_some_and(|v| v.0);
+		stmt.explain = Some(Explain(full, true));
+		Ok(stmt)
+	}
+
 	pub(crate) async fn parse_select_stmt(
 		&mut self,
 		stk: &mut Stk,
@@ -62,7 +77,7 @@ impl Parser<'_> {
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
 		let tempfiles = self.eat(t!("TEMPFILES"));
-		let explain = self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL"))));
+		let explain = self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL")), false));
 
 		Ok(SelectStatement {
 			expr,
@@ -190,9 +205,19 @@ impl Parser<'_> {
 		Ok(Some(Ordering::Order(OrderList(orders))))
 	}
 
-	async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
+	pub(crate) async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
 		let start = self.parse_basic_idiom(ctx).await?;
 		let collate = self.eat(t!("COLLATE"));
+		let locale = match self.peek_kind() {
+			t!("'") | t!("\"") if collate => {
+				let locale: Strand = self.next_token_value()?;
+				if !collate::valid(&locale) {
+					bail!("Invalid collation locale `{}`", locale.as_str(), @self.last_span());
+				}
+				Some(locale)
+			}
+			_ => None,
+		};
 		let numeric = self.eat(t!("NUMERIC"));
 		let direction = match self.peek_kind() {
 			t!("ASCENDING") => {
@@ -208,6 +233,7 @@ impl Parser<'_> {
 		Ok(Order {
 			value: start,
 			collate,
+			locale,
 			numeric,
 			direction,
 		})
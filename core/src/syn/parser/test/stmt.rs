@@ -1,14 +1,15 @@
 use crate::{
+	iam::ResourceKind,
 	sql::{
 		access::AccessDuration,
 		access_type::{
 			AccessType, BearerAccess, BearerAccessSubject, JwtAccess, JwtAccessIssue,
-			JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey, RecordAccess,
+			JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey, KeyAccess, RecordAccess,
 		},
 		block::Entry,
 		changefeed::ChangeFeed,
 		filter::Filter,
-		index::{Distance, HnswParams, MTreeParams, SearchParams, VectorType},
+		index::{Collation, Distance, HnswParams, MTreeParams, SearchParams, VectorType},
 		language::Language,
 		order::{OrderList, Ordering},
 		statements::{
@@ -17,28 +18,32 @@ use crate::{
 				AccessStatementShow,
 			},
 			analyze::AnalyzeStatement,
+			define::{OnDelete, RoleAction, RoleGrant, SinkKind},
 			show::{ShowSince, ShowStatement},
 			sleep::SleepStatement,
 			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
 			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
-			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
-			UpsertStatement, UseStatement,
+			DefineParamStatement, DefineRoleStatement, DefineSinkStatement, DefineStatement,
+			DefineTableStatement, DefineTypeStatement, DeleteStatement, ForeachStatement,
+			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+			OutputStatement, RelateStatement, RemoveAccessStatement, RemoveAnalyzerStatement,
+			RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
+			RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
+			RemoveParamStatement, RemoveRoleStatement, RemoveSinkStatement, RemoveStatement,
+			RemoveTableStatement, RemoveTypeStatement, RemoveUserStatement, RevokeKind,
+			RevokeStatement, RollbackStatement, SavepointStatement, SelectStatement, SetStatement,
+			ThrowStatement, UpdateStatement, UpsertStatement, UseStatement,
 		},
 		tokenizer::Tokenizer,
 		user::UserDuration,
 		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Expression, Fetch, Fetchs, Field, Fields, Function, Future, Graph, Group, Groups, Id,
+		Ident, Idiom, Idioms, Index, Kind, Limit, Literal, Number, Object, Operator, Order, Output,
+		Param, Part, Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand,
+		Subquery, Table, TableType, Tables, Thing, Timeout, Ttl, Uuid, Value, Values, Version,
+		With,
 	},
 	syn::parser::mac::test_parse,
 };
@@ -54,7 +59,10 @@ pub fn parse_analyze() {
 	assert_eq!(
 		res,
 		Statement::Analyze(AnalyzeStatement::Idx(Ident("a".to_string()), Ident("b".to_string())))
-	)
+	);
+
+	let res = test_parse!(parse_stmt, r#"ANALYZE TABLE a"#).unwrap();
+	assert_eq!(res, Statement::Analyze(AnalyzeStatement::Table(Ident("a".to_string()))))
 }
 
 #[test]
@@ -87,6 +95,63 @@ pub fn parse_commit() {
 	assert_eq!(res, Statement::Commit(CommitStatement));
 }
 
+#[test]
+pub fn parse_savepoint() {
+	let res = test_parse!(parse_stmt, r#"SAVEPOINT before"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Savepoint(SavepointStatement {
+			name: Ident("before".to_owned()),
+		})
+	);
+}
+
+#[test]
+pub fn parse_rollback() {
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO before"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rollback(RollbackStatement {
+			name: Ident("before".to_owned()),
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO SAVEPOINT before"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rollback(RollbackStatement {
+			name: Ident("before".to_owned()),
+		})
+	);
+}
+
+#[test]
+pub fn parse_revoke() {
+	let res = test_parse!(parse_stmt, r#"REVOKE TOKEN "abc""#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Revoke(RevokeStatement {
+			kind: RevokeKind::Token,
+			id: Value::Strand(Strand("abc".to_string())),
+			base: None,
+			duration: None,
+		})
+	);
+	let res =
+		test_parse!(parse_stmt, r#"REVOKE SESSION $session.id ON DATABASE DURATION 1d"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Revoke(RevokeStatement {
+			kind: RevokeKind::Session,
+			id: Value::from(Idiom(vec![
+				Part::Start(Param::from("session").into()),
+				Part::from("id")
+			])),
+			base: Some(Base::Db),
+			duration: Some(Duration(std::time::Duration::from_secs(86400))),
+		})
+	);
+}
+
 #[test]
 pub fn parse_continue() {
 	let res = test_parse!(parse_stmt, r#"CONTINUE"#).unwrap();
@@ -360,29 +425,33 @@ fn parse_define_user() {
 			res
 		);
 	}
-	// With nonexistent role.
+	// With custom role.
 	{
 		let res = test_parse!(
 			parse_stmt,
 			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES foo"#
-		);
-		assert!(
-			res.is_err(),
-			"Unexpected successful parsing of user with nonexistent role: {:?}",
-			res
-		);
+		)
+		.unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.roles, vec![Ident("foo".to_string())]);
 	}
-	// With existent and nonexistent roles.
+	// With predefined and custom roles.
 	{
 		let res = test_parse!(
 			parse_stmt,
 			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES Viewer, foo"#
-		);
-		assert!(
-			res.is_err(),
-			"Unexpected successful parsing of user with nonexistent role: {:?}",
-			res
-		);
+		)
+		.unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.roles, vec![Ident("Viewer".to_string()), Ident("foo".to_string())]);
 	}
 }
 
@@ -480,6 +549,8 @@ fn parse_define_token_jwks() {
 			kind: AccessType::Jwt(JwtAccess {
 				verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 					url: "http://example.com/.well-known/jwks.json".to_string(),
+					issuer: None,
+					audience: None,
 				}),
 				issue: None,
 			}),
@@ -497,6 +568,33 @@ fn parse_define_token_jwks() {
 	)
 }
 
+#[test]
+fn parse_define_token_jwks_claims() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE TOKEN a ON DATABASE TYPE JWKS VALUE "https://issuer/.well-known/jwks.json" ISSUER "https://issuer/" AUDIENCE "surrealdb""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Access(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.kind,
+		AccessType::Jwt(JwtAccess {
+			verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
+				url: "https://issuer/.well-known/jwks.json".to_string(),
+				issuer: Some("https://issuer/".to_string()),
+				audience: Some("surrealdb".to_string()),
+			}),
+			issue: None,
+		}),
+	);
+	assert_eq!(
+		stmt.to_string(),
+		"DEFINE ACCESS a ON DATABASE TYPE JWT URL 'https://issuer/.well-known/jwks.json' ISSUER 'https://issuer/' AUDIENCE 'surrealdb' DURATION FOR SESSION NONE"
+	);
+}
+
 // TODO(gguillemas): This test is kept in 2.0.0 for backward compatibility. Drop in 3.0.0.
 #[test]
 fn parse_define_token_jwks_on_scope() {
@@ -928,6 +1026,8 @@ fn parse_define_access_jwt_jwks() {
 				kind: AccessType::Jwt(JwtAccess {
 					verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
+						issuer: None,
+						audience: None,
 					}),
 					issue: None,
 				}),
@@ -959,6 +1059,8 @@ fn parse_define_access_jwt_jwks() {
 				kind: AccessType::Jwt(JwtAccess {
 					verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
+						issuer: None,
+						audience: None,
 					}),
 					issue: Some(JwtAccessIssue {
 						alg: Algorithm::Hs384,
@@ -993,6 +1095,8 @@ fn parse_define_access_jwt_jwks() {
 				kind: AccessType::Jwt(JwtAccess {
 					verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
+						issuer: None,
+						audience: None,
 					}),
 					issue: Some(JwtAccessIssue {
 						alg: Algorithm::Hs384,
@@ -1026,6 +1130,8 @@ fn parse_define_access_jwt_jwks() {
 				kind: AccessType::Jwt(JwtAccess {
 					verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
+						issuer: None,
+						audience: None,
 					}),
 					issue: Some(JwtAccessIssue {
 						alg: Algorithm::Ps256,
@@ -1060,6 +1166,8 @@ fn parse_define_access_jwt_jwks() {
 				kind: AccessType::Jwt(JwtAccess {
 					verify: JwtAccessVerify::Jwks(JwtAccessVerifyJwks {
 						url: "http://example.com/.well-known/jwks.json".to_string(),
+						issuer: None,
+						audience: None,
 					}),
 					issue: Some(JwtAccessIssue {
 						alg: Algorithm::Ps256,
@@ -1599,6 +1707,131 @@ fn parse_define_access_bearer() {
 	}
 }
 
+#[test]
+fn parse_define_access_key() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ACCESS a ON DB TYPE KEY ROLES EDITOR DURATION FOR GRANT 30d COMMENT "foo""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Access(DefineAccessStatement {
+			name: Ident("a".to_string()),
+			base: Base::Db,
+			kind: AccessType::Key(KeyAccess {
+				roles: vec![Ident("EDITOR".to_string())],
+			}),
+			authenticate: None,
+			duration: AccessDuration {
+				grant: Some(Duration::from_days(30)),
+				token: Some(Duration::from_hours(1)),
+				session: None,
+			},
+			comment: Some(Strand("foo".to_string())),
+			if_not_exists: false,
+			overwrite: false,
+		})),
+	);
+	// Keys are read-only by default.
+	let res = test_parse!(parse_stmt, r#"DEFINE ACCESS a ON NS TYPE KEY"#).unwrap();
+	let Statement::Define(DefineStatement::Access(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.kind,
+		AccessType::Key(KeyAccess {
+			roles: vec![Ident("Viewer".to_string())],
+		})
+	);
+	// Custom roles can be granted.
+	let res = test_parse!(parse_stmt, r#"DEFINE ACCESS a ON DB TYPE KEY ROLES foo"#).unwrap();
+	let Statement::Define(DefineStatement::Access(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.kind,
+		AccessType::Key(KeyAccess {
+			roles: vec![Ident("foo".to_string())],
+		})
+	);
+}
+
+#[test]
+fn parse_define_role() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ROLE reporter ON DB GRANT VIEW ON TABLE, FIELD GRANT EDIT ON FUNCTION COMMENT "foo""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Role(DefineRoleStatement {
+			name: Ident("reporter".to_string()),
+			base: Base::Db,
+			grants: vec![
+				RoleGrant {
+					action: RoleAction::View,
+					kinds: vec![ResourceKind::Table, ResourceKind::Field],
+					ns: None,
+					db: None,
+				},
+				RoleGrant {
+					action: RoleAction::Edit,
+					kinds: vec![ResourceKind::Function],
+					ns: None,
+					db: None,
+				},
+			],
+			comment: Some(Strand("foo".to_string())),
+			if_not_exists: false,
+			overwrite: false,
+		})),
+	);
+	// Grants on the root can be limited to a namespace and database.
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ROLE IF NOT EXISTS auditor ON ROOT GRANT VIEW IN NAMESPACE a DATABASE b"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Role(stmt)) = res else {
+		panic!()
+	};
+	assert!(stmt.if_not_exists);
+	assert_eq!(
+		stmt.grants,
+		vec![RoleGrant {
+			action: RoleAction::View,
+			kinds: vec![],
+			ns: Some(Ident("a".to_string())),
+			db: Some(Ident("b".to_string())),
+		}]
+	);
+	// Grants on a namespace can be limited to a database.
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE ROLE OVERWRITE auditor ON NS GRANT EDIT IN DATABASE b"#)
+			.unwrap();
+	let Statement::Define(DefineStatement::Role(stmt)) = res else {
+		panic!()
+	};
+	assert!(stmt.overwrite);
+	assert_eq!(
+		stmt.grants,
+		vec![RoleGrant {
+			action: RoleAction::Edit,
+			kinds: vec![],
+			ns: None,
+			db: Some(Ident("b".to_string())),
+		}]
+	);
+	// Grants can not reach outside of the level of the role.
+	test_parse!(parse_stmt, r#"DEFINE ROLE auditor ON NS GRANT VIEW IN NAMESPACE a"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE ROLE auditor ON DB GRANT VIEW IN DATABASE b"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE ROLE auditor ON ROOT GRANT VIEW IN DATABASE b"#).unwrap_err();
+	// Only viewing and editing can be granted.
+	test_parse!(parse_stmt, r#"DEFINE ROLE auditor ON DB GRANT OWN"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_param() {
 	let res =
@@ -1625,6 +1858,54 @@ fn parse_define_param() {
 	);
 }
 
+#[test]
+fn parse_define_type() {
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TYPE address { street: string, geo: option<point> }"#)
+			.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Type(DefineTypeStatement {
+			name: Ident("address".to_string()),
+			kind: Kind::Literal(Literal::Object(
+				[
+					("street".to_string(), Kind::String),
+					("geo".to_string(), Kind::Option(Box::new(Kind::Point))),
+				]
+				.into_iter()
+				.collect()
+			)),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+		}))
+	);
+}
+
+#[test]
+fn parse_define_sink() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SINK orders_out FOR TABLE orders TYPE NATS URL "nats://localhost:4222" TOPIC "orders" COMMENT "test""#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Sink(DefineSinkStatement {
+			name: Ident("orders_out".to_string()),
+			what: Ident("orders".to_string()),
+			kind: SinkKind::Nats,
+			url: Strand("nats://localhost:4222".to_string()),
+			topic: Strand("orders".to_string()),
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: false,
+			overwrite: false,
+		}))
+	);
+}
+
 #[test]
 fn parse_define_table() {
 	let res =
@@ -1670,6 +1951,41 @@ fn parse_define_table() {
 			if_not_exists: false,
 			overwrite: false,
 			kind: TableType::Normal,
+			partition: None,
+			ttl: None,
+		}))
+	);
+}
+
+#[test]
+fn parse_define_table_partition() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE metrics PARTITION BY region"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Table(DefineTableStatement {
+			name: Ident("metrics".to_string()),
+			permissions: Permissions::none(),
+			partition: Some(ident_field("region")),
+			..Default::default()
+		}))
+	);
+}
+
+#[test]
+fn parse_define_table_ttl() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE session TTL 30m ON seen"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Table(DefineTableStatement {
+			name: Ident("session".to_string()),
+			permissions: Permissions::none(),
+			ttl: Some(Ttl {
+				duration: std::time::Duration::from_secs(1800),
+				field: Some(Idiom(vec![Part::Field(Ident("seen".to_string()))])),
+			}),
+			..Default::default()
 		}))
 	);
 }
@@ -1690,8 +2006,29 @@ fn parse_define_event() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			asynchronous: false,
 		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE EVENT event ON TABLE table WHEN true THEN null ASYNC COMMENT "queued""#
 	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Event(DefineEventStatement {
+			name: Ident("event".to_owned()),
+			what: Ident("table".to_owned()),
+			when: Value::Bool(true),
+			then: Values(vec![Value::Null]),
+			comment: Some(Strand("queued".to_owned())),
+			if_not_exists: false,
+			overwrite: false,
+			asynchronous: true,
+		}))
+	);
 }
 
 #[test]
@@ -1700,7 +2037,7 @@ fn parse_define_field() {
 	{
 		let res = test_parse!(
 			parse_stmt,
-			r#"DEFINE FIELD foo.*[*]... ON TABLE bar FLEX TYPE option<number | array<record<foo>,10>> VALUE null ASSERT true DEFAULT false PERMISSIONS FOR UPDATE NONE, FOR CREATE WHERE true"#
+			r#"DEFINE FIELD foo.*[*]... ON TABLE bar FLEX TYPE option<number | array<record<foo>,10>> VALUE null ASSERT true CHECK $value > 0 ON DELETE CASCADE DEFAULT false PERMISSIONS FOR UPDATE NONE, FOR CREATE WHERE true"#
 		).unwrap();
 
 		assert_eq!(
@@ -1731,6 +2068,13 @@ fn parse_define_field() {
 				comment: None,
 				if_not_exists: false,
 				overwrite: false,
+				check: Some(Value::Expression(Box::new(Expression::Binary {
+					l: Value::Param(Param::from("value")),
+					o: Operator::MoreThan,
+					r: Value::Number(Number::Int(0)),
+				}))),
+				on_delete: Some(OnDelete::Cascade),
+				computed: None,
 			}))
 		)
 	}
@@ -1763,9 +2107,43 @@ fn parse_define_field() {
 				comment: None,
 				if_not_exists: false,
 				overwrite: false,
+				check: None,
+				on_delete: None,
+				computed: None,
+			}))
+		)
+	}
+
+	// Computed field
+	{
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE FIELD foo ON TABLE bar COMPUTED baz + 1"#).unwrap();
+
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Field(DefineFieldStatement {
+				name: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+				what: Ident("bar".to_owned()),
+				computed: Some(Value::Expression(Box::new(Expression::Binary {
+					l: Value::Idiom(Idiom(vec![Part::Field(Ident("baz".to_owned()))])),
+					o: Operator::Add,
+					r: Value::Number(Number::Int(1)),
+				}))),
+				..Default::default()
 			}))
 		)
 	}
+
+	// Computed field with a VALUE clause
+	{
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE FIELD foo ON TABLE bar VALUE 1 COMPUTED baz + 1"#);
+		assert!(
+			res.is_err(),
+			"Unexpected successful parsing of computed field with a value clause: {:?}",
+			res
+		);
+	}
 }
 
 #[test]
@@ -1813,7 +2191,9 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			collate: vec![],
 		}))
 	);
 
@@ -1830,7 +2210,56 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			collate: vec![],
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Spatial,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
+			collate: vec![],
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS string::lowercase(a), b UNIQUE"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![
+				Idiom(vec![Part::Start(Value::Function(Box::new(Function::Normal(
+					"string::lowercase".to_owned(),
+					vec![Value::Idiom(Idiom(vec![Part::Field(Ident("a".to_owned()))]))]
+				))))]),
+				Idiom(vec![Part::Field(Ident("b".to_owned()))]),
+			]),
+			index: Index::Uniq,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
+			collate: vec![],
 		}))
 	);
 
@@ -1855,7 +2284,9 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			collate: vec![],
 		}))
 	);
 
@@ -1882,7 +2313,61 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			collate: vec![],
+		}))
+	);
+}
+
+#[test]
+fn parse_define_index_partial() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX active_email ON user FIELDS email WHERE active = true UNIQUE"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("active_email".to_owned()),
+			what: Ident("user".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("email".to_owned()))])]),
+			index: Index::Uniq,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: Some(Cond(Value::Expression(Box::new(Expression::Binary {
+				l: ident_field("active"),
+				o: Operator::Equal,
+				r: Value::Bool(true),
+			})))),
+			collate: vec![],
+		}))
+	);
+}
+
+#[test]
+fn parse_define_index_collate() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX username ON user FIELDS username UNIQUE COLLATE NOCASE, NFKC"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("username".to_owned()),
+			what: Ident("user".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("username".to_owned()))])]),
+			index: Index::Uniq,
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
+			collate: vec![Collation::NoCase, Collation::Nfkc],
 		}))
 	);
 }
@@ -1920,6 +2405,34 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[test]
+fn parse_define_analyzer_cjk() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ANALYZER cjk TOKENIZERS BLANK, NGRAM(2), DICTIONARY('/tmp/words.txt') FILTERS LOWERCASE, STOPWORDS('the', '的')"#
+	).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			name: Ident("cjk".to_owned()),
+			tokenizers: Some(vec![
+				Tokenizer::Blank,
+				Tokenizer::Ngram(2),
+				Tokenizer::Dictionary("/tmp/words.txt".to_owned()),
+			]),
+			filters: Some(vec![
+				Filter::Lowercase,
+				Filter::Stopwords(vec!["the".to_owned(), "的".to_owned()]),
+			]),
+			comment: None,
+			function: None,
+			if_not_exists: false,
+			overwrite: false,
+		})),
+	)
+}
+
 #[test]
 fn parse_delete() {
 	let res = test_parse!(
@@ -1972,6 +2485,37 @@ fn parse_delete_2() {
 	)
 }
 
+#[test]
+fn parse_delete_return_projection() {
+	let res = test_parse!(parse_stmt, r#"DELETE foo RETURN { id, name, at: $now }"#).unwrap();
+	let Statement::Delete(DeleteStatement {
+		output: Some(Output::Fields(fields)),
+		..
+	}) = res
+	else {
+		panic!("expected a DELETE statement with a projection")
+	};
+	assert_eq!(
+		fields,
+		Fields(
+			vec![Field::Single {
+				expr: Value::Object(Object(
+					[
+						("id".to_owned(), Value::Idiom(Idiom::from("id"))),
+						("name".to_owned(), Value::Idiom(Idiom::from("name"))),
+						("at".to_owned(), Value::Param(Param::from("now"))),
+					]
+					.into_iter()
+					.collect()
+				)),
+				alias: None,
+			}],
+			true
+		)
+	);
+	assert_eq!(fields.to_string(), "VALUE { at: $now, id: id, name: name }");
+}
+
 #[test]
 pub fn parse_for() {
 	let res = test_parse!(
@@ -2069,6 +2613,8 @@ fn parse_info() {
 		res,
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false))
 	);
+	let res = test_parse!(parse_stmt, "INFO FOR QUERIES").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Queries(false)));
 }
 
 #[test]
@@ -2142,6 +2688,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				collate: true,
+				locale: None,
 				numeric: true,
 				direction: true,
 			}]))),
@@ -2159,11 +2706,48 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			timeout: None,
 			parallel: false,
 			tempfiles: false,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 	);
 }
 
+#[test]
+fn parse_select_collate_locale() {
+	let res =
+		test_parse!(parse_stmt, "SELECT * FROM a ORDER BY name COLLATE 'de' NUMERIC DESC").unwrap();
+	let Statement::Select(stm) = res else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(
+		stm.order,
+		Some(Ordering::Order(OrderList(vec![Order {
+			value: Idiom(vec![Part::Field(Ident("name".to_owned()))]),
+			collate: true,
+			locale: Some(Strand("de".to_owned())),
+			numeric: true,
+			direction: false,
+		}])))
+	);
+
+	test_parse!(parse_stmt, "SELECT * FROM a ORDER BY name COLLATE 'not a locale'").unwrap_err();
+}
+
+#[test]
+fn parse_explain_select() {
+	let res = test_parse!(parse_stmt, r#"EXPLAIN FULL SELECT * FROM a"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.explain, Some(Explain(true, true)));
+	assert_eq!(stmt.to_string(), "EXPLAIN FULL SELECT * FROM a");
+	let res = test_parse!(parse_stmt, r#"EXPLAIN SELECT * FROM a"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.explain, Some(Explain(false, true)));
+	assert!(test_parse!(parse_stmt, r#"EXPLAIN CREATE a"#).is_err());
+}
+
 #[test]
 fn parse_let() {
 	let res = test_parse!(parse_stmt, r#"LET $param = 1"#).unwrap();
@@ -2421,7 +3005,8 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: Value::Param(Param(Ident("param".to_owned())))
+			id: Value::Param(Param(Ident("param".to_owned()))),
+			query: false,
 		})
 	);
 
@@ -2429,7 +3014,18 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002")))
+			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			query: false,
+		})
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"KILL QUERY u"e72bee20-f49b-11ec-b939-0242ac120002" "#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			query: true,
 		})
 	);
 }
@@ -2470,7 +3066,24 @@ fn parse_live() {
 			]))),
 			Fetch(Value::Idiom(Idiom(vec![Part::Field(Ident("b".to_owned()))]))),
 		])),
+	);
+	assert_eq!(stmt.resume, None);
+
+	let res = test_parse!(parse_stmt, r#"LIVE SELECT * FROM table RESUME 1234"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.resume, Some(Value::Number(Number::Int(1234))));
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT count(), math::sum(amount) FROM orders WHERE status = 'open' GROUP ALL"#
 	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.group, Some(Groups(Vec::new())));
 }
 
 #[test]
@@ -2655,6 +3268,36 @@ fn parse_remove() {
 			if_exists: false,
 		}))
 	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE ROLE IF EXISTS reporter ON NAMESPACE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Role(RemoveRoleStatement {
+			name: Ident("reporter".to_owned()),
+			base: Base::Ns,
+			if_exists: true,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE TYPE IF EXISTS address"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Type(RemoveTypeStatement {
+			name: Ident("address".to_owned()),
+			if_exists: true,
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"REMOVE SINK IF EXISTS orders_out FOR TABLE orders"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Sink(RemoveSinkStatement {
+			name: Ident("orders_out".to_owned()),
+			what: Ident("orders".to_owned()),
+			if_exists: true,
+		}))
+	);
 }
 
 #[test]
@@ -2777,6 +3420,17 @@ fn parse_access_grant() {
 				}),
 			}))
 		);
+	} // Key
+	{
+		let res = test_parse!(parse_stmt, r#"ACCESS a ON DATABASE GRANT FOR KEY b"#).unwrap();
+		assert_eq!(
+			res,
+			Statement::Access(AccessStatement::Grant(AccessStatementGrant {
+				ac: Ident("a".to_string()),
+				base: Some(Base::Db),
+				subject: access::Subject::Key(Ident("b".to_string())),
+			}))
+		);
 	}
 }
 
@@ -285,6 +285,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			kind: TableType::Normal,
+			partition: None,
+			ttl: None,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -294,6 +296,7 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			asynchronous: false,
 		})),
 		Statement::Define(DefineStatement::Field(DefineFieldStatement {
 			name: Idiom(vec![
@@ -321,6 +324,9 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			check: None,
+			on_delete: None,
+			computed: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -349,6 +355,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			collate: vec![],
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -359,6 +367,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			collate: vec![],
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -377,6 +387,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			collate: vec![],
 		})),
 		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
 			name: Ident("ana".to_owned()),
@@ -505,6 +517,7 @@ fn statements() -> Vec<Statement> {
 			order: Some(Ordering::Order(OrderList(vec![Order {
 				value: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				collate: true,
+				locale: None,
 				numeric: true,
 				direction: true,
 			}]))),
@@ -522,7 +535,7 @@ fn statements() -> Vec<Statement> {
 			timeout: None,
 			parallel: false,
 			tempfiles: false,
-			explain: Some(Explain(true)),
+			explain: Some(Explain(true, false)),
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
@@ -604,6 +617,7 @@ fn statements() -> Vec<Statement> {
 		}),
 		Statement::Kill(KillStatement {
 			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			query: false,
 		}),
 		Statement::Output(OutputStatement {
 			what: ident_field("RETRUN"),
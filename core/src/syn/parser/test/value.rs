@@ -258,3 +258,27 @@ fn datetime_error() {
 fn empty_string() {
 	test_parse!(parse_value_field, "").unwrap_err();
 }
+
+#[test]
+fn parse_recursive_graph() {
+	let value = test_parse!(parse_value_field, "a:1->knows{1..5}->person.name").unwrap();
+	let Value::Idiom(x) = value else {
+		panic!("not the right value type");
+	};
+	assert_eq!(x.0.len(), 3);
+	let Part::Recurse(r) = &x.0[1] else {
+		panic!("not a recursive graph traversal");
+	};
+	assert_eq!((r.min, r.max), (1, 5));
+	assert_eq!(r.path.len(), 2);
+	assert_eq!(x.to_string(), "a:1->knows{1..5}->person.name");
+	// An open minimum depth starts from the first hop
+	let value = test_parse!(parse_value_field, "<-knows{..3}<-person").unwrap();
+	assert_eq!(value.to_string(), "<-knows{1..3}<-person");
+	// A single depth is displayed without a range
+	let value = test_parse!(parse_value_field, "->knows{2}->?").unwrap();
+	assert_eq!(value.to_string(), "->knows{2}->?");
+	// The traversal must be followed by a record traversal
+	test_parse!(parse_value_field, "->knows{1..3}.name").unwrap_err();
+	test_parse!(parse_value_field, "->knows{3..1}->person").unwrap_err();
+}
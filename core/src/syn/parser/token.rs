@@ -26,6 +26,9 @@ impl Parser<'_> {
 				| t!("RETURN")
 				| t!("RELATE")
 				| t!("REMOVE")
+				| t!("REVOKE")
+				| t!("ROLLBACK")
+				| t!("SAVEPOINT")
 				| t!("SELECT")
 				| t!("LET") | t!("SHOW")
 				| t!("SLEEP")
@@ -128,8 +131,9 @@ impl Parser<'_> {
 				| t!("FOR") | t!("INFO")
 				| t!("KILL") | t!("LIVE")
 				| t!("OPTION")
-				| t!("LET") | t!("SHOW")
-				| t!("SLEEP")
+				| t!("LET") | t!("ROLLBACK")
+				| t!("SAVEPOINT")
+				| t!("SHOW") | t!("SLEEP")
 				| t!("THROW")
 				| t!("USE")
 		)
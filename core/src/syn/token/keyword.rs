@@ -1,7 +1,7 @@
 macro_rules! keyword {
 	($($name:ident => $value:tt),* $(,)?) => {
 
-		#[repr(u8)]
+		#[repr(u16)]
 		#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 		#[non_exhaustive] pub enum Keyword{
 			$($name,)*
@@ -35,7 +35,9 @@ keyword! {
 	Ascending => "ASCENDING",
 	Ascii => "ASCII",
 	Assert => "ASSERT",
+	Async => "ASYNC",
 	At => "AT",
+	Audience => "AUDIENCE",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
 	Bearer => "BEARER",
@@ -50,9 +52,12 @@ keyword! {
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
 	Capacity => "CAPACITY",
+	Cascade => "CASCADE",
+	Check => "CHECK",
 	Class => "CLASS",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
+	Computed => "COMPUTED",
 	Concurrently => "CONCURRENTLY",
 	Config => "CONFIG",
 	Content => "CONTENT",
@@ -64,6 +69,7 @@ keyword! {
 	Delete => "DELETE",
 	Descending => "DESCENDING",
 	Diff => "DIFF",
+	Dictionary => "DICTIONARY",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
 	DocIdsCache => "DOC_IDS_CACHE",
@@ -74,6 +80,7 @@ keyword! {
 	Duplicate => "DUPLICATE",
 	Efc => "EFC",
 	Edgengram => "EDGENGRAM",
+	Edit => "EDIT",
 	Event => "EVENT",
 	Else => "ELSE",
 	End => "END",
@@ -110,6 +117,7 @@ keyword! {
 	Issuer => "ISSUER",
 	Jwt => "JWT",
 	Jwks => "JWKS",
+	Kafka => "KAFKA",
 	Key => "KEY",
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
@@ -126,8 +134,11 @@ keyword! {
 	MTree => "MTREE",
 	MTreeCache => "MTREE_CACHE",
 	Namespace => "NAMESPACE",
+	Nats => "NATS",
+	Nfkc => "NFKC",
 	Ngram => "NGRAM",
 	No => "NO",
+	NoCase => "NOCASE",
 	NoIndex => "NOINDEX",
 	None => "NONE",
 	Null => "NULL",
@@ -138,9 +149,11 @@ keyword! {
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
+	Over => "OVER",
 	Overwrite => "OVERWRITE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
+	Partition => "PARTITION",
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
@@ -149,6 +162,8 @@ keyword! {
 	PostingsOrder => "POSTINGS_ORDER",
 	Punct => "PUNCT",
 	Purge => "PURGE",
+	Queries => "QUERIES",
+	Query => "QUERY",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
@@ -156,11 +171,16 @@ keyword! {
 	Relation => "RELATION",
 	Remove => "REMOVE",
 	Replace => "REPLACE",
+	Restrict => "RESTRICT",
+	Resume => "RESUME",
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
+	Role => "ROLE",
 	Roles => "ROLES",
+	Rollback => "ROLLBACK",
 	Root => "ROOT",
+	Savepoint => "SAVEPOINT",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -172,10 +192,13 @@ keyword! {
 	Signin => "SIGNIN",
 	Signup => "SIGNUP",
 	Since => "SINCE",
+	Sink => "SINK",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Spatial => "SPATIAL",
 	Split => "SPLIT",
 	Start => "START",
+	Stopwords => "STOPWORDS",
 	Structure => "STRUCTURE",
 	Table => "TABLE",
 	Tables => "TABLES",
@@ -188,8 +211,10 @@ keyword! {
 	Tokenizers => "TOKENIZERS",
 	Token => "TOKEN",
 	To => "TO",
+	Topic => "TOPIC",
 	Transaction => "TRANSACTION",
 	True => "true",
+	Ttl => "TTL",
 	Type => "TYPE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
@@ -202,6 +227,7 @@ keyword! {
 	Value => "VALUE",
 	Values => "VALUES",
 	Version => "VERSION",
+	View => "VIEW",
 	Vs => "VS",
 	When => "WHEN",
 	Where => "WHERE",
@@ -245,6 +271,7 @@ keyword! {
 	String => "STRING",
 	Uuid => "UUID",
 	Ulid => "ULID",
+	Vector => "VECTOR",
 	Rand => "RAND",
 	Feature => "FEATURE",
 	Line => "LINE",
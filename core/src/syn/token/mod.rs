@@ -397,8 +397,10 @@ impl fmt::Display for TokenKind {
 	}
 }
 
-/// An assertion statically checking that the size of Tokenkind remains two bytes
-const _TOKEN_KIND_SIZE_ASSERT: [(); 2] = [(); std::mem::size_of::<TokenKind>()];
+/// An assertion statically checking that the size of Tokenkind remains four bytes
+const _TOKEN_KIND_SIZE_ASSERT: [(); 4] = [(); std::mem::size_of::<TokenKind>()];
+/// An assertion statically checking that the size of Token remains twelve bytes
+const _TOKEN_SIZE_ASSERT: [(); 12] = [(); std::mem::size_of::<Token>()];
 
 impl TokenKind {
 	pub fn has_data(&self) -> bool {
@@ -68,6 +68,13 @@ pub(crate) enum Command {
 		query: Query,
 		variables: CoreObject,
 	},
+	Prepare {
+		query: String,
+	},
+	ExecutePrepared {
+		id: Uuid,
+		variables: CoreObject,
+	},
 	ExportFile {
 		path: PathBuf,
 	},
@@ -298,6 +305,21 @@ impl Command {
 					params: Some(params.into()),
 				}
 			}
+			Command::Prepare {
+				query,
+			} => RouterRequest {
+				id,
+				method: "prepare",
+				params: Some(CoreValue::from(vec![CoreValue::from(query)])),
+			},
+			Command::ExecutePrepared {
+				id: statement,
+				variables,
+			} => RouterRequest {
+				id,
+				method: "execute",
+				params: Some(CoreValue::from(vec![CoreValue::from(statement), variables.into()])),
+			},
 			Command::ExportFile {
 				..
 			}
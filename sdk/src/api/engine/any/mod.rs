@@ -278,6 +278,9 @@ impl Surreal<Any> {
 /// // Instantiate an IndxDB-backed instance
 /// let db = connect("indxdb://DatabaseName").await?;
 ///
+/// // Instantiate an OPFS-backed instance
+/// let db = connect("opfs://DatabaseName").await?;
+///
 /// // Instantiate a TiKV-backed instance
 /// let db = connect("tikv://localhost:2379").await?;
 ///
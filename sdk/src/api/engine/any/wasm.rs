@@ -62,6 +62,20 @@ impl Connection for Any {
 					);
 				}
 
+				EndpointKind::Opfs => {
+					#[cfg(feature = "kv-opfs")]
+					{
+						features.insert(ExtraFeatures::LiveQueries);
+						spawn_local(engine::local::wasm::run_router(address, conn_tx, route_rx));
+						conn_rx.recv().await??;
+					}
+
+					#[cfg(not(feature = "kv-opfs"))]
+					return Err(
+						DbError::Ds("Cannot connect to the `opfs` storage engine as it is not enabled in this build of SurrealDB".to_owned()).into()
+					);
+				}
+
 				EndpointKind::Memory => {
 					#[cfg(feature = "kv-mem")]
 					{
@@ -256,6 +256,46 @@ pub struct RocksDb;
 #[derive(Debug)]
 pub struct IndxDb;
 
+/// OPFS database
+///
+/// The origin private file system can only be accessed synchronously
+/// within a web worker, and the database can only be opened by a single
+/// worker at a time.
+///
+/// # Examples
+///
+/// Instantiating an OPFS-backed instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Opfs;
+///
+/// let db = Surreal::new::<Opfs>("DatabaseName").await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Instantiating an OPFS-backed strict instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::opt::Config;
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Opfs;
+///
+/// let config = Config::default().strict();
+/// let db = Surreal::new::<Opfs>(("DatabaseName", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "kv-opfs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kv-opfs")))]
+#[derive(Debug)]
+pub struct Opfs;
+
 /// TiKV database
 ///
 /// # Examples
@@ -766,6 +806,22 @@ async fn router(
 			let response = process(response);
 			Ok(DbResponse::Query(response))
 		}
+		Command::Prepare {
+			query,
+		} => {
+			let id = kvs.prepare(&query)?;
+			Ok(DbResponse::Other(CoreValue::Uuid(id.into())))
+		}
+		Command::ExecutePrepared {
+			id,
+			mut variables,
+		} => {
+			let mut vars = vars.clone();
+			vars.append(&mut variables.0);
+			let response = kvs.execute_prepared(id, &*session, Some(vars)).await?;
+			let response = process(response);
+			Ok(DbResponse::Query(response))
+		}
 
 		#[cfg(target_arch = "wasm32")]
 		Command::ExportFile {
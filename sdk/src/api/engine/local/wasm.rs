@@ -128,6 +128,18 @@ pub(crate) async fn run_router(
 	if let Some(interval) = address.config.changefeed_gc_interval {
 		opt.changefeed_gc_interval = interval;
 	}
+	if let Some(interval) = address.config.maintenance_interval {
+		opt.maintenance_interval = interval;
+	}
+	if let Some(interval) = address.config.event_queue_interval {
+		opt.event_queue_interval = interval;
+	}
+	if let Some(interval) = address.config.sink_interval {
+		opt.sink_interval = interval;
+	}
+	if let Some(interval) = address.config.webhook_interval {
+		opt.webhook_interval = interval;
+	}
 	let tasks = tasks::init(kvs.clone(), canceller.clone(), &opt);
 
 	let mut notifications = kvs.notifications().map(Box::pin);
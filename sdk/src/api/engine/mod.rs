@@ -7,6 +7,7 @@ pub mod any;
 	feature = "kv-rocksdb",
 	feature = "kv-fdb",
 	feature = "kv-indxdb",
+	feature = "kv-opfs",
 	feature = "kv-surrealkv",
 	feature = "kv-surrealcs",
 ))]
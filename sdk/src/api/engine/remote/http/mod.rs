@@ -306,6 +306,19 @@ async fn router(
 			.expect("query should be valid request");
 			send_request(req, base_url, client, headers, auth).await
 		}
+		Command::ExecutePrepared {
+			id,
+			mut variables,
+		} => {
+			variables.extend(vars.clone());
+			let req = Command::ExecutePrepared {
+				id,
+				variables,
+			}
+			.into_router_request(None)
+			.expect("execute should be valid request");
+			send_request(req, base_url, client, headers, auth).await
+		}
 		Command::Use {
 			namespace,
 			database,
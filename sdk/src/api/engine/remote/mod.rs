@@ -52,6 +52,8 @@ fn duration_from_str(duration: &str) -> Option<std::time::Duration> {
 
 #[cfg(test)]
 mod tests {
+	use crate::api::err::Error;
+	use crate::RecordId;
 	use std::time::Duration;
 
 	#[test]
@@ -76,6 +78,31 @@ mod tests {
 			assert_eq!(duration, parsed, "Duration {string} not parsed correctly");
 		}
 	}
+
+	#[test]
+	fn index_exists_from_message() {
+		let message = "Database index `email` already contains 'a, with record `b`', with record `user:⟨a b⟩`";
+		let Error::IndexExists {
+			index,
+			value,
+			record,
+		} = super::query_error(message.to_owned())
+		else {
+			panic!("Expected an IndexExists error");
+		};
+		assert_eq!(index, "email");
+		assert_eq!(value, "'a, with record `b`'");
+		assert_eq!(record, RecordId::from(("user", "a b")));
+		// The message is preserved
+		let error = super::query_error(message.to_owned());
+		assert_eq!(error.to_string(), message);
+		// The errors of the RPC methods are recognised too
+		let error = super::query_error(format!("There was a problem with the database: {message}"));
+		assert!(matches!(error, Error::IndexExists { .. }));
+		// Other errors are kept as query errors
+		let error = super::query_error("Specify a namespace to use".to_owned());
+		assert!(matches!(error, Error::Query(_)));
+	}
 }
 
 #[revisioned(revision = 1)]
@@ -102,11 +129,32 @@ impl From<Failure> for Error {
 			-32602 => Self::InvalidParams(failure.message),
 			-32603 => Self::InternalError(failure.message),
 			-32700 => Self::ParseError(failure.message),
-			_ => Self::Query(failure.message),
+			_ => query_error(failure.message),
 		}
 	}
 }
 
+/// Converts the message of an error returned by the server, recovering
+/// the details of the errors applications can branch on
+fn query_error(message: String) -> Error {
+	index_exists_error(&message).unwrap_or(Error::Query(message))
+}
+
+fn index_exists_error(message: &str) -> Option<Error> {
+	// RPC methods prefix the message of the database errors
+	let message =
+		message.strip_prefix("There was a problem with the database: ").unwrap_or(message);
+	let rest = message.strip_prefix("Database index `")?;
+	let (index, rest) = rest.split_once("` already contains ")?;
+	let (value, record) = rest.rsplit_once(", with record `")?;
+	let record = record.strip_suffix('`')?.parse().ok()?;
+	Some(Error::IndexExists {
+		index: index.to_owned(),
+		value: value.to_owned(),
+		record,
+	})
+}
+
 impl From<Failure> for crate::Error {
 	fn from(value: Failure) -> Self {
 		let api_err: Error = value.into();
@@ -133,7 +181,7 @@ impl DbResponse {
 						Status::Err => {
 							map.insert(
 								index,
-								(stats, Err(Error::Query(response.result.as_raw_string()).into())),
+								(stats, Err(query_error(response.result.as_raw_string()).into())),
 							);
 						}
 						_ => unreachable!(),
@@ -47,7 +47,11 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task2 = spawn_task_node_membership_check(dbs.clone(), canceller.clone(), opts);
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
-	Tasks(vec![task1, task2, task3, task4])
+	let task5 = spawn_task_maintenance(dbs.clone(), canceller.clone(), opts);
+	let task6 = spawn_task_event_queue(dbs.clone(), canceller.clone(), opts);
+	let task7 = spawn_task_sinks(dbs.clone(), canceller.clone(), opts);
+	let task8 = spawn_task_webhooks(dbs.clone(), canceller.clone(), opts);
+	Tasks(vec![task1, task2, task3, task4, task5, task6, task7, task8])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -174,6 +178,130 @@ fn spawn_task_changefeed_cleanup(
 	}))
 }
 
+fn spawn_task_maintenance(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.maintenance_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running background maintenance every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.maintenance().await {
+						error!("Error running background maintenance: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running background maintenance");
+	}))
+}
+
+fn spawn_task_event_queue(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.event_queue_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Processing queued table events every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.process_event_queue().await {
+						error!("Error processing queued table events: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Processing queued table events");
+	}))
+}
+
+fn spawn_task_sinks(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.sink_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Delivering changefeeds to sinks every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.process_sinks().await {
+						error!("Error delivering changefeeds to sinks: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Delivering changefeeds to sinks");
+	}))
+}
+
+fn spawn_task_webhooks(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.webhook_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Delivering queued webhooks every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.process_webhooks().await {
+						error!("Error delivering queued webhooks: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Delivering queued webhooks");
+	}))
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_arch = "wasm32"))]
 	use tokio::{time, time::MissedTickBehavior};
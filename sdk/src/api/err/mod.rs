@@ -1,4 +1,4 @@
-use crate::{api::Response, Value};
+use crate::{api::Response, RecordId, Value};
 use serde::Serialize;
 use std::path::PathBuf;
 use std::{convert::Infallible, io};
@@ -13,6 +13,18 @@ pub enum Error {
 	#[error("{0}")]
 	Query(String),
 
+	/// A unique index already contains the value of the record being written.
+	/// Embedded storage engines report this conflict as `Db::IndexExists`.
+	#[error("Database index `{index}` already contains {value}, with record `{record}`")]
+	IndexExists {
+		/// The name of the unique index
+		index: String,
+		/// The conflicting value, as formatted by the server
+		value: String,
+		/// The record already holding the value
+		record: RecordId,
+	},
+
 	/// There was an error processing a remote HTTP request
 	#[error("There was an error processing a remote HTTP request: {0}")]
 	Http(String),
@@ -0,0 +1,305 @@
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::engine::any::Any;
+use crate::method::Query;
+use crate::value::Action;
+use crate::value::Datetime;
+use crate::Surreal;
+use crate::Value;
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::future::IntoFuture;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use surrealdb_core::sql::{
+	statements::ShowSince, statements::ShowStatement, Statement, Table, Value as CoreValue,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
+/// The default number of changesets read from the changefeed at a time
+const DEFAULT_LIMIT: u32 = 100;
+
+/// The default interval between polls of the changefeed, once all changes have been read
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The point in a changefeed from which changes are read
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Since {
+	/// Read the changes committed at, or after, a versionstamp
+	Versionstamp(u64),
+	/// Read the changes committed at, or after, a point in time
+	Time(Datetime),
+}
+
+impl From<u64> for Since {
+	fn from(versionstamp: u64) -> Self {
+		Self::Versionstamp(versionstamp)
+	}
+}
+
+impl From<Datetime> for Since {
+	fn from(time: Datetime) -> Self {
+		Self::Time(time)
+	}
+}
+
+impl From<DateTime<Utc>> for Since {
+	fn from(time: DateTime<Utc>) -> Self {
+		Self::Time(time.into())
+	}
+}
+
+impl From<Since> for ShowSince {
+	fn from(since: Since) -> Self {
+		match since {
+			Since::Versionstamp(vs) => ShowSince::Versionstamp(vs),
+			Since::Time(time) => ShowSince::Timestamp(time.into_inner()),
+		}
+	}
+}
+
+/// A change made to a record, read from a changefeed
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ChangeEvent {
+	/// The versionstamp of the transaction which made the change
+	pub versionstamp: u64,
+	/// The action which was performed on the record
+	pub action: Action,
+	/// The record after the change, or the id of a deleted record
+	pub data: Value,
+}
+
+/// A changefeed future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changes<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) since: Since,
+	pub(super) limit: u32,
+	pub(super) interval: Duration,
+}
+
+impl<'r, C> Changes<'r, C>
+where
+	C: Connection,
+{
+	pub(super) fn new(client: Cow<'r, Surreal<C>>, table: String) -> Self {
+		Changes {
+			client,
+			table,
+			since: Since::Versionstamp(0),
+			limit: DEFAULT_LIMIT,
+			interval: DEFAULT_INTERVAL,
+		}
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Changes<'static, C> {
+		Changes {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Reads the changes committed at, or after, a versionstamp or a point in time
+	pub fn since(mut self, since: impl Into<Since>) -> Self {
+		self.since = since.into();
+		self
+	}
+
+	/// Sets the maximum number of changesets to read from the changefeed at a time
+	pub fn limit(mut self, limit: u32) -> Self {
+		self.limit = limit;
+		self
+	}
+
+	/// Sets how long to wait before polling the changefeed again, once all changes have been read
+	pub fn interval(mut self, interval: Duration) -> Self {
+		self.interval = interval;
+		self
+	}
+}
+
+impl<'r, Client> IntoFuture for Changes<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ChangeStream>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut stream = ChangeStream {
+				client: Surreal::new_from_router_waiter(
+					self.client.router.clone(),
+					self.client.waiter.clone(),
+				),
+				table: self.table,
+				since: self.since.into(),
+				limit: self.limit,
+				interval: self.interval,
+				idle: false,
+				buffer: VecDeque::new(),
+				future: None,
+			};
+			// Read the first changes, so that any errors are returned early
+			let (next, events) = stream.fetch().await?;
+			stream.advance(next, events);
+			Ok(stream)
+		})
+	}
+}
+
+/// The changes read from a changefeed, along with the versionstamp following them, if any
+type Fetch = BoxFuture<'static, Result<(Option<u64>, Vec<ChangeEvent>)>>;
+
+/// A stream of changes read from a changefeed
+///
+/// The changefeed is polled for new changes once all of the existing changes
+/// have been read, and the position in the changefeed is kept track of, so
+/// that each change is only returned once.
+#[must_use = "streams do nothing unless you poll them"]
+pub struct ChangeStream {
+	client: Surreal<Any>,
+	table: String,
+	since: ShowSince,
+	limit: u32,
+	interval: Duration,
+	idle: bool,
+	buffer: VecDeque<ChangeEvent>,
+	future: Option<Fetch>,
+}
+
+impl ChangeStream {
+	/// The versionstamp from which the next changes will be read
+	///
+	/// This can be stored and passed to [`Changes::since`] to resume reading
+	/// the changefeed later, without missing any changes.
+	pub fn cursor(&self) -> Option<u64> {
+		match self.since {
+			ShowSince::Versionstamp(vs) => {
+				// Take any buffered changes into account
+				Some(self.buffer.front().map(|v| v.versionstamp).unwrap_or(vs))
+			}
+			_ => None,
+		}
+	}
+
+	/// Reads the next changes from the changefeed
+	fn fetch(&self) -> Fetch {
+		let client = self.client.clone();
+		let mut table = Table::default();
+		table.0.clone_from(&self.table);
+		let stmt = ShowStatement::new(Some(table), self.since.clone(), Some(self.limit));
+		let interval = self.idle.then_some(self.interval);
+		Box::pin(async move {
+			// Wait before polling again if there were no changes
+			if let Some(interval) = interval {
+				sleep(interval).await;
+			}
+			let query = Query::new(
+				Cow::Owned(client),
+				vec![Statement::Show(stmt)],
+				Default::default(),
+				false,
+			);
+			let value = query.await?.take::<Value>(0)?.into_inner();
+			parse(value)
+		})
+	}
+
+	/// Moves the cursor past the changes which have been read
+	fn advance(&mut self, next: Option<u64>, events: Vec<ChangeEvent>) {
+		self.idle = next.is_none();
+		if let Some(next) = next {
+			self.since = ShowSince::Versionstamp(next);
+		}
+		self.buffer.extend(events);
+	}
+}
+
+/// Converts the result of a `SHOW CHANGES` statement into change events,
+/// along with the versionstamp following the last changeset, if any
+fn parse(value: CoreValue) -> Result<(Option<u64>, Vec<ChangeEvent>)> {
+	let CoreValue::Array(changesets) = value else {
+		return Err(Error::InternalError(format!(
+			"expected the database to return an array of changesets, received {value}"
+		))
+		.into());
+	};
+	let mut next = None;
+	let mut events = Vec::new();
+	for changeset in changesets {
+		let CoreValue::Object(mut changeset) = changeset else {
+			continue;
+		};
+		let Some(CoreValue::Number(vs)) = changeset.remove("versionstamp") else {
+			continue;
+		};
+		// Changesets include the trailing bytes of the versionstamp, which are not part of the
+		// versionstamp that changes are read from
+		let versionstamp = (vs.to_int() >> 16) as u64;
+		next = Some(versionstamp + 1);
+		let Some(CoreValue::Array(changes)) = changeset.remove("changes") else {
+			continue;
+		};
+		for change in changes {
+			let CoreValue::Object(mut change) = change else {
+				continue;
+			};
+			let (action, data) = if let Some(data) = change.remove("create") {
+				(Action::Create, data)
+			} else if let Some(data) = change.remove("update") {
+				// Changes which include the original contain the current record separately
+				(Action::Update, change.remove("current").unwrap_or(data))
+			} else if let Some(data) = change.remove("delete") {
+				(Action::Delete, data)
+			} else {
+				// Table definitions are not record changes
+				continue;
+			};
+			events.push(ChangeEvent {
+				versionstamp,
+				action,
+				data: Value::from_inner(data),
+			});
+		}
+	}
+	Ok((next, events))
+}
+
+impl futures::Stream for ChangeStream {
+	type Item = Result<ChangeEvent>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		loop {
+			// Return any changes which have already been read
+			if let Some(event) = self.buffer.pop_front() {
+				return Poll::Ready(Some(Ok(event)));
+			}
+			// Otherwise read the next changes from the changefeed
+			let mut future = self.future.take().unwrap_or_else(|| self.fetch());
+			match future.as_mut().poll(cx) {
+				Poll::Pending => {
+					self.future = Some(future);
+					return Poll::Pending;
+				}
+				Poll::Ready(Ok((next, events))) => self.advance(next, events),
+				Poll::Ready(Err(error)) => return Poll::Ready(Some(Err(error))),
+			}
+		}
+	}
+}
@@ -7,6 +7,7 @@ use crate::api::ExtraFeatures;
 use crate::api::Result;
 use crate::engine::any::Any;
 use crate::method::Live;
+use crate::method::LiveDiff;
 use crate::method::OnceLockExt;
 use crate::method::Query;
 use crate::method::Select;
@@ -36,7 +37,7 @@ use wasm_bindgen_futures::spawn_local as spawn;
 
 const ID: &str = "id";
 
-fn into_future<C, O>(this: Select<C, O, Live>) -> BoxFuture<Result<Stream<O>>>
+fn into_future<C, O, T>(this: Select<C, O, T>, diff: bool) -> BoxFuture<Result<Stream<O>>>
 where
 	C: Connection,
 {
@@ -50,8 +51,11 @@ where
 		if !router.features.contains(&ExtraFeatures::LiveQueries) {
 			return Err(Error::LiveQueriesNotSupported.into());
 		}
+		// A live query without fields sends JSON patches
 		let mut fields = Fields::default();
-		fields.0 = vec![Field::All];
+		if !diff {
+			fields.0 = vec![Field::All];
+		}
 		let mut stmt = LiveStatement::new(fields);
 		let mut table = Table::default();
 		match resource? {
@@ -126,7 +130,7 @@ where
 	type IntoFuture = BoxFuture<'r, Self::Output>;
 
 	fn into_future(self) -> Self::IntoFuture {
-		into_future(self)
+		into_future(self, false)
 	}
 }
 
@@ -139,7 +143,7 @@ where
 	type IntoFuture = BoxFuture<'r, Self::Output>;
 
 	fn into_future(self) -> Self::IntoFuture {
-		into_future(self)
+		into_future(self, false)
 	}
 }
 
@@ -152,7 +156,19 @@ where
 	type IntoFuture = BoxFuture<'r, Self::Output>;
 
 	fn into_future(self) -> Self::IntoFuture {
-		into_future(self)
+		into_future(self, false)
+	}
+}
+
+impl<'r, Client> IntoFuture for Select<'r, Client, Value, LiveDiff>
+where
+	Client: Connection,
+{
+	type Output = Result<Stream<Value>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		into_future(self, true)
 	}
 }
 
@@ -27,6 +27,7 @@ pub(crate) mod query;
 mod authenticate;
 mod begin;
 mod cancel;
+mod changes;
 mod commit;
 mod content;
 mod create;
@@ -39,6 +40,7 @@ mod insert_relation;
 mod invalidate;
 mod merge;
 mod patch;
+mod prepare;
 mod run;
 mod select;
 mod set;
@@ -61,6 +63,7 @@ pub use begin::Begin;
 pub use begin::Transaction;
 #[doc(hidden)] // Not supported yet
 pub use cancel::Cancel;
+pub use changes::{ChangeEvent, ChangeStream, Changes, Since};
 #[doc(hidden)] // Not supported yet
 pub use commit::Commit;
 pub use content::Content;
@@ -75,6 +78,7 @@ pub use invalidate::Invalidate;
 pub use live::Stream;
 pub use merge::Merge;
 pub use patch::Patch;
+pub use prepare::{Execute, Prepare, Prepared};
 pub use query::Query;
 pub use query::QueryStream;
 pub use run::IntoFn;
@@ -112,6 +116,9 @@ pub struct Model;
 /// Live query marker type
 pub struct Live;
 
+/// Live query marker type for JSON Patch notifications
+pub struct LiveDiff;
+
 /// Responses returned with statistics
 #[derive(Debug)]
 pub struct WithStats<T>(pub T);
@@ -651,6 +658,46 @@ where
 		}
 	}
 
+	/// Parses a query once, so that it can be executed repeatedly with different parameters
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Parse the query once
+	/// let statement = db.prepare("SELECT * FROM person WHERE age > $age").await?;
+	///
+	/// // Execute it as many times as needed
+	/// for age in [18, 30, 65] {
+	///     let mut result = db.execute(&statement).bind(("age", age)).await?;
+	///     let people: Vec<serde_json::Value> = result.take(0)?;
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn prepare(&self, query: impl Into<String>) -> Prepare<C> {
+		Prepare {
+			client: Cow::Borrowed(self),
+			query: query.into(),
+		}
+	}
+
+	/// Executes a query which was previously parsed with [`Surreal::prepare`]
+	pub fn execute(&self, statement: &Prepared) -> Execute<C> {
+		Execute {
+			client: Cow::Borrowed(self),
+			id: statement.id(),
+			bindings: Ok(Default::default()),
+		}
+	}
+
 	/// Selects all records in a table, or a specific record
 	///
 	/// # Examples
@@ -1264,6 +1311,38 @@ where
 		}
 	}
 
+	/// Reads the changes made to a table from its changefeed
+	///
+	/// The returned stream keeps track of its position in the changefeed, and
+	/// polls for new changes once all of the existing changes have been read.
+	/// The table must be defined with a `CHANGEFEED`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Read all of the changes to the table, and any made later
+	/// let mut stream = db.changes("person").since(0).await?;
+	///
+	/// while let Some(change) = stream.next().await {
+	///     let change = change?;
+	///     println!("{:?} {:?} at {}", change.action, change.data, change.versionstamp);
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn changes(&self, table: impl Into<String>) -> Changes<C> {
+		Changes::new(Cow::Borrowed(self), table.into())
+	}
+
 	/// Runs a function
 	///
 	/// # Examples
@@ -0,0 +1,134 @@
+use super::query::append_bindings;
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Response;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use surrealdb_core::sql::{to_value as to_core_value, Object as CoreObject, Value as CoreValue};
+use uuid::Uuid;
+
+/// A query which has been parsed by the database, and which can be executed repeatedly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prepared {
+	id: Uuid,
+}
+
+impl Prepared {
+	/// The identifier of this prepared query on the database
+	pub fn id(&self) -> Uuid {
+		self.id
+	}
+}
+
+/// A prepare future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Prepare<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) query: String,
+}
+
+impl<C> Prepare<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Prepare<'static, C> {
+		Prepare {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Prepare<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Prepared>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let value = router
+				.execute_value(Command::Prepare {
+					query: self.query,
+				})
+				.await?;
+			match value.into_inner() {
+				CoreValue::Uuid(id) => Ok(Prepared {
+					id: id.0,
+				}),
+				value => Err(Error::InternalError(format!(
+					"expected the database to return a uuid, received {value}"
+				))
+				.into()),
+			}
+		})
+	}
+}
+
+/// An execute future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Execute<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) id: Uuid,
+	pub(super) bindings: Result<CoreObject>,
+}
+
+impl<C> Execute<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Execute<'static, C> {
+		Execute {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Binds a parameter or parameters to the prepared query
+	///
+	/// Accepts the same key/value tuples and objects as [`Query::bind`](super::Query::bind).
+	pub fn bind(mut self, bindings: impl Serialize + 'static) -> Self {
+		self.bindings = self.bindings.and_then(|mut target| {
+			append_bindings(&mut target, to_core_value(bindings)?)?;
+			Ok(target)
+		});
+		self
+	}
+}
+
+impl<'r, Client> IntoFuture for Execute<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Response>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut response = router
+				.execute_query(Command::ExecutePrepared {
+					id: self.id,
+					variables: self.bindings?,
+				})
+				.await?;
+			response.client = Surreal::new_from_router_waiter(
+				self.client.router.clone(),
+				self.client.waiter.clone(),
+			);
+			Ok(response)
+		})
+	}
+}
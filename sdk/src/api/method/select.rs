@@ -5,6 +5,7 @@ use crate::api::opt::Resource;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::Live;
+use crate::method::LiveDiff;
 use crate::opt::KeyRange;
 use crate::Surreal;
 use crate::Value;
@@ -171,3 +172,44 @@ where
 		}
 	}
 }
+
+impl<'r, C, R> Select<'r, C, R, Live>
+where
+	C: Connection,
+{
+	/// Turns a live query into one sending RFC 6902 JSON patches of the changes to each record
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use futures::StreamExt;
+	/// # #[derive(Debug, serde::Deserialize)]
+	/// # struct Person;
+	/// #
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Listen to the changes made to a table
+	/// let mut stream = db.select::<Vec<Person>>("person").live().diff().await?;
+	///
+	/// // Each notification contains an array of patch operations
+	/// while let Some(notification) = stream.next().await {
+	///     println!("{:?}", notification.data);
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn diff(self) -> Select<'r, C, Value, LiveDiff> {
+		Select {
+			client: self.client,
+			resource: self.resource,
+			response_type: PhantomData,
+			query_type: PhantomData,
+		}
+	}
+}
@@ -170,6 +170,10 @@ async fn api() {
 
 	// run
 	let _: Option<User> = DB.run("foo").await.unwrap();
+
+	// prepare
+	let statement = DB.prepare("SELECT * FROM user WHERE name = $name").await.unwrap();
+	let _: QueryResponse = DB.execute(&statement).bind(("name", "John Doe")).await.unwrap();
 }
 
 fn assert_send_sync(_: impl Send + Sync) {}
@@ -43,7 +43,13 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				} => Ok(DbResponse::Other(CoreValue::None)),
 				Command::Query {
 					..
+				}
+				| Command::ExecutePrepared {
+					..
 				} => Ok(DbResponse::Query(QueryResponse::new())),
+				Command::Prepare {
+					..
+				} => Ok(DbResponse::Other(CoreValue::Uuid(Default::default()))),
 				Command::Create {
 					data,
 					..
@@ -24,6 +24,10 @@ pub struct Config {
 	pub(crate) node_membership_check_interval: Option<Duration>,
 	pub(crate) node_membership_cleanup_interval: Option<Duration>,
 	pub(crate) changefeed_gc_interval: Option<Duration>,
+	pub(crate) maintenance_interval: Option<Duration>,
+	pub(crate) event_queue_interval: Option<Duration>,
+	pub(crate) sink_interval: Option<Duration>,
+	pub(crate) webhook_interval: Option<Duration>,
 }
 
 impl Config {
@@ -127,4 +131,28 @@ impl Config {
 		self.changefeed_gc_interval = interval.into().filter(|x| !x.is_zero());
 		self
 	}
+
+	/// Set the interval at which the database should remove expired and superseded data
+	pub fn maintenance_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.maintenance_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the interval at which the database should process queued asynchronous table events
+	pub fn event_queue_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.event_queue_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the interval at which the database should deliver changefeeds to sinks
+	pub fn sink_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.sink_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
+
+	/// Set the interval at which the database should deliver queued webhooks
+	pub fn webhook_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.webhook_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
 }
@@ -9,6 +9,8 @@ mod fdb;
 mod indxdb;
 #[cfg(feature = "kv-mem")]
 mod mem;
+#[cfg(feature = "kv-opfs")]
+mod opfs;
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb;
 #[cfg(feature = "kv-surrealcs")]
@@ -124,6 +126,8 @@ pub enum EndpointKind {
 	FoundationDb,
 	#[cfg(target_arch = "wasm32")]
 	IndxDb,
+	#[cfg(target_arch = "wasm32")]
+	Opfs,
 	Memory,
 	RocksDb,
 	File,
@@ -143,6 +147,8 @@ impl From<&str> for EndpointKind {
 			"fdb" => Self::FoundationDb,
 			#[cfg(target_arch = "wasm32")]
 			"indxdb" => Self::IndxDb,
+			#[cfg(target_arch = "wasm32")]
+			"opfs" => Self::Opfs,
 			"mem" => Self::Memory,
 			"file" => Self::File,
 			"rocksdb" => Self::RocksDb,
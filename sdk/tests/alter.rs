@@ -50,6 +50,7 @@ async fn define_alter_table() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -68,6 +69,7 @@ async fn define_alter_table() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL DROP SCHEMALESS COMMENT \\'test\\' CHANGEFEED 1d PERMISSIONS FOR select, update, delete NONE, FOR create FULL' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -86,6 +88,7 @@ async fn define_alter_table() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -125,6 +128,7 @@ async fn define_alter_table_if_exists() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: {},
+			types: {},
 			users: {},
 		}",
 	);
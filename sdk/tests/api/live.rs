@@ -422,6 +422,52 @@ async fn live_select_query() {
 	drop(permit);
 }
 
+#[test_log::test(tokio::test)]
+async fn live_select_aggregate() {
+	let (permit, db) = new_db().await;
+
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap();
+	db.query(format!("CREATE {table}:1 SET status = 'open', amount = 10")).await.unwrap();
+
+	// Start listening
+	let mut orders = db
+		.query(format!(
+			"LIVE SELECT count() AS total, math::sum(amount) AS amount FROM {table} \
+			WHERE status = 'open' GROUP ALL"
+		))
+		.await
+		.unwrap()
+		.stream::<Value>(0)
+		.unwrap();
+
+	// Add a record to the group
+	db.query(format!("CREATE {table}:2 SET status = 'open', amount = 5")).await.unwrap();
+	let notification = tokio::time::timeout(LQ_TIMEOUT, orders.next()).await.unwrap().unwrap();
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(
+		notification.data.into_inner(),
+		surrealdb_core::syn::value("{ total: 2, amount: 15 }").unwrap()
+	);
+
+	// Move a record out of the group
+	db.query(format!("UPDATE {table}:1 SET status = 'closed'")).await.unwrap();
+	let notification = tokio::time::timeout(LQ_TIMEOUT, orders.next()).await.unwrap().unwrap();
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(
+		notification.data.into_inner(),
+		surrealdb_core::syn::value("{ total: 1, amount: 5 }").unwrap()
+	);
+
+	// Remove the last record from the group
+	db.query(format!("DELETE {table}:2")).await.unwrap();
+	let notification = tokio::time::timeout(LQ_TIMEOUT, orders.next()).await.unwrap().unwrap();
+	assert_eq!(notification.action, Action::Delete);
+
+	drop(permit);
+}
+
 async fn receive_all_pending_notifications<
 	S: Stream<Item = Result<Notification<I>, Error>> + Unpin,
 	I,
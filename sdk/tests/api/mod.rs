@@ -391,6 +391,28 @@ async fn query_binds() {
 	assert_eq!(record.name, "John Doe");
 }
 
+#[test_log::test(tokio::test)]
+async fn query_prepared() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let statement = db.prepare("CREATE type::thing('user', $id) SET name = $name").await.unwrap();
+	for (id, name) in [("john", "John Doe"), ("jane", "Jane Doe")] {
+		let mut response =
+			db.execute(&statement).bind(("id", id)).bind(("name", name)).await.unwrap();
+		let Some(record): Option<RecordName> = response.take(0).unwrap() else {
+			panic!("query returned no record");
+		};
+		assert_eq!(record.name, name);
+	}
+	let statement = db.prepare("SELECT VALUE name FROM user ORDER BY name").await.unwrap();
+	let mut response = db.execute(&statement).await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["Jane Doe", "John Doe"]);
+	// Preparing an invalid query returns an error
+	db.prepare("SELECT * FROM").await.unwrap_err();
+}
+
 #[test_log::test(tokio::test)]
 async fn query_with_stats() {
 	let (permit, db) = new_db().await;
@@ -1443,6 +1465,55 @@ async fn changefeed() {
 	);
 }
 
+#[test_log::test(tokio::test)]
+async fn changefeed_stream() {
+	use futures::StreamExt;
+	use surrealdb::Action;
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	// Enable change feeds
+	let response = db.query("DEFINE TABLE user CHANGEFEED 1h").await.unwrap();
+	response.check().unwrap();
+	// Create, update and delete a user
+	let sql = "
+        CREATE user:amos SET name = 'Amos';
+        UPDATE user:amos SET name = 'AMOS';
+        DELETE user:amos;
+    ";
+	let response = db.query(sql).await.unwrap();
+	response.check().unwrap();
+	// The existing changes are read in order
+	let mut stream =
+		db.changes("user").since(0).interval(Duration::from_millis(100)).await.unwrap();
+	let create = stream.next().await.unwrap().unwrap();
+	let expected = match FFLAGS.change_feed_live_queries.enabled() {
+		true => Action::Create,
+		false => Action::Update,
+	};
+	assert_eq!(create.action, expected);
+	assert_eq!(create.data.into_inner().pick(&["name".into()]), CoreValue::from("Amos"));
+	let update = stream.next().await.unwrap().unwrap();
+	assert_eq!(update.action, Action::Update);
+	assert_eq!(update.data.into_inner().pick(&["name".into()]), CoreValue::from("AMOS"));
+	assert!(update.versionstamp > create.versionstamp);
+	let delete = stream.next().await.unwrap().unwrap();
+	assert_eq!(delete.action, Action::Delete);
+	assert!(delete.versionstamp > update.versionstamp);
+	// Later changes are read by polling the changefeed
+	let response = db.query("CREATE user:jane SET name = 'Jane'").await.unwrap();
+	response.check().unwrap();
+	let jane = tokio::time::timeout(Duration::from_secs(10), stream.next())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap();
+	drop(permit);
+	assert_eq!(jane.data.into_inner().pick(&["name".into()]), CoreValue::from("Jane"));
+	assert!(jane.versionstamp > delete.versionstamp);
+	// The cursor is moved past the changes which have been read
+	assert!(stream.cursor().unwrap() > jane.versionstamp);
+}
+
 #[test_log::test(tokio::test)]
 async fn version() {
 	let (permit, db) = new_db().await;
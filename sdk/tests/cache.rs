@@ -136,3 +136,46 @@ async fn clear_transaction_cache_field() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn clear_transaction_cache_permissions() -> Result<(), Error> {
+	let sql = "
+		DEFINE ACCESS user ON DATABASE TYPE RECORD;
+		DEFINE TABLE user PERMISSIONS FULL;
+		DEFINE TABLE data PERMISSIONS FOR select WHERE $auth.admin = true OR owner = $auth.id;
+		CREATE user:1 SET admin = false;
+		CREATE data:1 SET owner = user:1;
+		CREATE data:2 SET owner = user:2;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	//
+	let sql = "
+		BEGIN;
+		SELECT VALUE id FROM data;
+		UPDATE user:1 SET admin = true;
+		SELECT VALUE id FROM data;
+		COMMIT;
+	";
+	let ses = Session::for_record("test", "test", "user", Value::parse("user:1"));
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[data:1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[data:1, data:2]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
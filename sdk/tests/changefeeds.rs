@@ -411,6 +411,44 @@ async fn table_change_feeds() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn table_change_feeds_retention() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE short CHANGEFEED 1s;
+		DEFINE TABLE long CHANGEFEED 1h;
+		CREATE short:1;
+		CREATE long:1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test-tb-cf").with_db("test-tb-cf");
+	dbs.changefeed_process_at(0).await?;
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	dbs.changefeed_process_at(1).await?;
+	// The changefeed size is shown for the table
+	let res = &mut dbs.execute("INFO FOR TABLE short", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_ne!(tmp.pick(&["changefeed".into(), "entries".into()]), Value::from(0));
+	assert_ne!(tmp.pick(&["changefeed".into(), "size".into()]), Value::from(0));
+	// Only the table with the shorter retention is trimmed
+	dbs.changefeed_process_at(10).await?;
+	let res = &mut dbs.execute("SHOW CHANGES FOR TABLE short SINCE 0", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	let res = &mut dbs.execute("SHOW CHANGES FOR TABLE long SINCE 0", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_ne!(tmp, Value::parse("[]"));
+	// The changefeed size reflects the trimmed entries
+	let res = &mut dbs.execute("INFO FOR TABLE short", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&["changefeed".into()]), Value::parse("{ entries: 0, size: 0 }"));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn changefeed_with_ts() -> Result<(), Error> {
 	let db = new_ds().await?;
@@ -4,7 +4,7 @@ mod helpers;
 use helpers::new_ds;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
-use surrealdb::sql::Value;
+use surrealdb::sql::{Number, Value};
 
 #[tokio::test]
 async fn datetimes_conversion() -> Result<(), Error> {
@@ -44,3 +44,46 @@ async fn datetimes_conversion() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn durations_arithmetic() -> Result<(), Error> {
+	let sql = r#"
+		RETURN [1h30m * 2, 3 * 1h, 1h * 1.5, 1h / 4, 90m / 1h, 100m % 30m];
+		RETURN 1h * -1;
+		RETURN 1h / 0;
+		CREATE ticket:1 SET opened = d"2024-01-01T00:00:00Z", sla = 4h, priority = 1;
+		CREATE ticket:2 SET opened = d"2024-01-01T01:00:00Z", sla = 2h, priority = 3;
+		CREATE ticket:3 SET opened = d"2024-01-01T02:00:00Z", sla = 2h, priority = 2;
+		SELECT id, opened + sla * priority AS due FROM ticket ORDER BY due;
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[3h, 3h, 1h30m, 15m, 1.5, 10m]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::ArithmeticOverflow(_))));
+	//
+	let tmp = res.remove(0).result?;
+	assert!(matches!(tmp, Value::Number(Number::Float(v)) if v.is_nan()));
+	//
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: ticket:1, due: d'2024-01-01T04:00:00Z' },
+			{ id: ticket:3, due: d'2024-01-01T06:00:00Z' },
+			{ id: ticket:2, due: d'2024-01-01T07:00:00Z' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
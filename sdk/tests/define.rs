@@ -97,6 +97,7 @@ async fn define_statement_function() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: {},
+			types: {},
 			users: {},
 		}",
 	);
@@ -129,6 +130,7 @@ async fn define_statement_table_drop() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY DROP SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -161,6 +163,7 @@ async fn define_statement_table_schemaless() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -188,6 +191,7 @@ async fn define_statement_table_schemafull() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	)?;
@@ -218,6 +222,7 @@ async fn define_statement_table_schemaful() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -261,6 +266,7 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 				test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE',
 				view: 'DEFINE TABLE view TYPE ANY SCHEMALESS AS SELECT count() FROM test GROUP ALL PERMISSIONS NONE',
 			},
+			types: {},
 			users: {},
 		}",
 	);
@@ -293,6 +299,7 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 			tables: {
 				test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE',
 			},
+			types: {},
 			users: {},
 		}",
 	);
@@ -313,6 +320,64 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_table_partition() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE metrics PARTITION BY time::floor(ts, 1d);
+		CREATE metrics:1 SET ts = d'2024-01-01T10:00:00Z', v = 1;
+		CREATE metrics:2 SET ts = d'2024-01-01T20:00:00Z', v = 2;
+		CREATE metrics:3 SET ts = d'2024-01-02T10:00:00Z', v = 3;
+		SELECT v FROM metrics WHERE time::floor(ts, 1d) = d'2024-01-01';
+		SELECT v FROM metrics WHERE time::floor(ts, 1d) = d'2024-01-01' EXPLAIN;
+		UPDATE metrics:2 SET ts = d'2024-01-02T20:00:00Z';
+		DELETE metrics WHERE time::floor(ts, 1d) = d'2024-01-02';
+		SELECT v FROM metrics;
+		DEFINE TABLE OVERWRITE metrics PARTITION BY v;
+		SELECT v FROM metrics WHERE v = 1;
+		INFO FOR DB;
+	";
+	let mut t = Test::new(sql).await?;
+	//
+	t.skip_ok(4)?;
+	t.expect_val("[{ v: 1 }, { v: 2 }]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					partition: d'2024-01-01T00:00:00Z',
+					table: 'metrics'
+				},
+				operation: 'Iterate Partition'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.skip_ok(2)?;
+	t.expect_val("[{ v: 1 }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ v: 1 }]")?;
+	t.expect_val(
+		"{
+			accesses: {},
+			analyzers: {},
+			configs: {},
+			functions: {},
+			models: {},
+			params: {},
+			tables: { metrics: 'DEFINE TABLE metrics TYPE ANY SCHEMALESS PARTITION BY v PERMISSIONS NONE' },
+			types: {},
+			users: {},
+		}",
+	)?;
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_event() -> Result<(), Error> {
 	let sql = "
@@ -667,6 +732,46 @@ async fn define_field_with_recursive_types() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_type() -> Result<(), Error> {
+	let sql = "
+		DEFINE TYPE address { street: string, city: string, geo: point };
+		DEFINE FIELD home ON person TYPE option<address>;
+		CREATE person:one SET home = { street: '1 Main Street', city: 'London', geo: (-0.1, 51.5) };
+		CREATE person:two SET home = { street: '2 Main Street', city: 'London' };
+		RETURN <address> { street: '3 Main Street', city: 'Paris', geo: (2.35, 48.85) };
+		DEFINE TYPE node { value: number, next: option<node> };
+		INFO FOR DB;
+	";
+	let mut t = Test::new(sql).await?;
+	//
+	t.skip_ok(2)?;
+	t.expect_val(
+		"[{
+			home: { street: '1 Main Street', city: 'London', geo: (-0.1, 51.5) },
+			id: person:one,
+		}]",
+	)?;
+	t.expect_error_func(|e| matches!(e, Error::FieldCheck { .. }))?;
+	t.expect_val("{ street: '3 Main Street', city: 'Paris', geo: (2.35, 48.85) }")?;
+	t.expect_error("The type 'node' can not be defined in terms of itself")?;
+	t.expect_val(
+		"{
+			accesses: {},
+			analyzers: {},
+			configs: {},
+			functions: {},
+			models: {},
+			params: {},
+			tables: { person: 'DEFINE TABLE person TYPE ANY SCHEMALESS PERMISSIONS NONE' },
+			types: { address: 'DEFINE TYPE address { city: string, geo: point, street: string }' },
+			users: {},
+		}",
+	)?;
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_field_permissions() -> Result<(), Error> {
 	// Specific permissions
@@ -1364,6 +1469,212 @@ async fn define_statement_index_concurrently() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_index_concurrently_failed() -> Result<(), Error> {
+	let sql = "
+		CREATE user:1 SET email = 'testA@surrealdb.com';
+		CREATE user:2 SET email = 'testA@surrealdb.com';
+		DEFINE INDEX test ON user FIELDS email UNIQUE CONCURRENTLY;
+		SLEEP 1s;
+		INFO FOR TABLE user;
+		SELECT * FROM user WHERE email = 'testA@surrealdb.com' EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	// The failed build is reported
+	t.expect_val(
+		"{
+			building: {
+				test: {
+					error: 'Database index `test` already contains \\'testA@surrealdb.com\\', with record `user:1`',
+					status: 'error'
+				}
+			},
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {
+				test: 'DEFINE INDEX test ON user FIELDS email UNIQUE CONCURRENTLY',
+			},
+			lives: {},
+		}",
+	)?;
+	// The incomplete index is not used
+	t.expect_val(
+		"[
+			{
+				detail: {
+					table: 'user'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_index_partial() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX active_email ON user FIELDS email WHERE active = true UNIQUE;
+		CREATE user:1 SET email = 'test@surrealdb.com', active = false;
+		CREATE user:2 SET email = 'test@surrealdb.com', active = true;
+		CREATE user:3 SET email = 'test@surrealdb.com', active = true;
+		UPDATE user:2 SET active = false;
+		CREATE user:3 SET email = 'test@surrealdb.com', active = true;
+		SELECT VALUE id FROM user WHERE email = 'test@surrealdb.com' AND active = true EXPLAIN;
+		SELECT VALUE id FROM user WHERE email = 'test@surrealdb.com' AND active = true;
+		SELECT VALUE id FROM user WHERE email = 'test@surrealdb.com' EXPLAIN;
+		INFO FOR TABLE user;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	// Uniqueness only applies to the records matching the condition
+	t.expect_error(
+		"Database index `active_email` already contains 'test@surrealdb.com', with record `user:2`",
+	)?;
+	t.skip_ok(2)?;
+	// The index is used when the condition implies the index condition
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'active_email',
+						operator: '=',
+						value: 'test@surrealdb.com'
+					},
+					table: 'user'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[user:3]")?;
+	// Otherwise the index is ignored
+	t.expect_val(
+		"[
+			{
+				detail: {
+					table: 'user'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {
+				active_email: 'DEFINE INDEX active_email ON user FIELDS email WHERE active = true UNIQUE',
+			},
+			lives: {},
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_index_collate() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX email ON user FIELDS email UNIQUE COLLATE NOCASE;
+		DEFINE INDEX name ON user FIELDS name UNIQUE COLLATE NFKC;
+		CREATE user:1 SET email = 'Tobie@SurrealDB.com', name = 'ﬁle';
+		CREATE user:2 SET email = 'tobie@surrealdb.com', name = 'tobie';
+		CREATE user:3 SET email = 'jaime@surrealdb.com', name = 'file';
+		SELECT VALUE id FROM user WHERE string::lowercase(email) = 'tobie@surrealdb.com' EXPLAIN;
+		SELECT VALUE id FROM user WHERE string::lowercase(email) = 'tobie@surrealdb.com';
+		SELECT VALUE id FROM user WHERE email = 'Tobie@SurrealDB.com' EXPLAIN;
+		SELECT VALUE id FROM user WHERE email = 'Tobie@SurrealDB.com';
+		INFO FOR TABLE user;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	// Uniqueness is checked on the normalized values
+	t.expect_error(
+		"Database index `email` already contains 'tobie@surrealdb.com', with record `user:1`",
+	)?;
+	t.expect_error("Database index `name` already contains 'file', with record `user:1`")?;
+	// Lookups are normalized as well
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'email',
+						operator: '=',
+						value: 'tobie@surrealdb.com'
+					},
+					table: 'user'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[user:1]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'email',
+						operator: '=',
+						value: 'tobie@surrealdb.com'
+					},
+					table: 'user'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[user:1]")?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {
+				email: 'DEFINE INDEX email ON user FIELDS email UNIQUE COLLATE NOCASE',
+				name: 'DEFINE INDEX name ON user FIELDS name UNIQUE COLLATE NFKC',
+			},
+			lives: {},
+		}",
+	)?;
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn define_statement_index_concurrently_building_status() -> Result<(), Error> {
 	let session = Session::owner().with_ns("test").with_db("test");
@@ -1799,6 +2110,7 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: {},
+			types: {},
 			users: {},
 		}"#,
 	)?;
@@ -2117,8 +2429,8 @@ async fn permissions_checks_define_function() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2159,8 +2471,8 @@ async fn permissions_checks_define_analyzer() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2285,8 +2597,8 @@ async fn permissions_checks_define_access_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2411,8 +2723,8 @@ async fn permissions_checks_define_user_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2453,8 +2765,8 @@ async fn permissions_checks_define_access_record() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2495,8 +2807,8 @@ async fn permissions_checks_define_param() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, tables: {  }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2534,8 +2846,8 @@ async fn permissions_checks_define_table() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, types: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2730,6 +3042,7 @@ async fn define_statement_table_permissions() -> Result<(), Error> {
 					full: 'DEFINE TABLE full TYPE ANY SCHEMALESS PERMISSIONS FULL',
 					select_full: 'DEFINE TABLE select_full TYPE ANY SCHEMALESS PERMISSIONS FOR select FULL, FOR create, update, delete NONE'
 			},
+			types: {},
 			users: {}
 		}",
 	);
@@ -2980,6 +3293,110 @@ async fn define_remove_users() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_remove_roles() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE example ON ROOT GRANT VIEW GRANT EDIT ON TABLE IN NAMESPACE test;
+		DEFINE ROLE IF NOT EXISTS example ON ROOT GRANT VIEW;
+		DEFINE ROLE OVERWRITE example ON ROOT GRANT VIEW GRANT EDIT ON TABLE IN NAMESPACE test;
+		DEFINE ROLE example ON ROOT GRANT VIEW;
+		DEFINE ROLE editor ON ROOT GRANT VIEW;
+		DEFINE USER example ON ROOT PASSWORD \"example\" ROLES example;
+		DEFINE USER other ON ROOT PASSWORD \"other\" ROLES other;
+		INFO FOR ROOT;
+		REMOVE ROLE example ON ROOT;
+		REMOVE USER example ON ROOT;
+		REMOVE ROLE IF EXISTS example ON ROOT;
+		REMOVE ROLE example ON ROOT;
+		REMOVE ROLE IF EXISTS example ON ROOT;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(1)?;
+	t.expect_val("None")?;
+	t.skip_ok(1)?;
+	t.expect_error("The root role 'example' already exists")?;
+	t.expect_error("The role 'editor' is a predefined role and can not be redefined")?;
+	t.skip_ok(1)?;
+	t.expect_error("IAM error: Invalid role 'other'")?;
+	let info = t.next_value()?;
+	assert_eq!(
+		info.pick(&[Part::from("roles"), Part::from("example")]),
+		Value::from("DEFINE ROLE example ON ROOT GRANT VIEW GRANT EDIT ON TABLE IN NAMESPACE test")
+	);
+	t.expect_error("The role 'example' can not be removed, as it is still granted by 'example'")?;
+	t.skip_ok(2)?;
+	t.expect_error("The root role 'example' does not exist")?;
+	t.expect_val("None")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_role_permissions() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE reporter ON DATABASE GRANT VIEW GRANT EDIT ON FUNCTION;
+		DEFINE ROLE manager ON DATABASE GRANT EDIT;
+		DEFINE USER reporter ON DATABASE PASSWORD 'reporter' ROLES reporter;
+		DEFINE USER manager ON DATABASE PASSWORD 'manager' ROLES manager;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	for res in res.drain(..) {
+		res.result?;
+	}
+	let denied = |res: Result<Value, Error>| match res {
+		Err(e) => e.to_string().contains("Not enough permissions to perform this action"),
+		Ok(_) => false,
+	};
+	//
+	// The reporter role allows viewing, and editing functions
+	//
+	let mut ses = Session::default().with_ns("test").with_db("test");
+	surrealdb::iam::verify::basic(
+		&dbs,
+		&mut ses,
+		"reporter",
+		"reporter",
+		Some("test"),
+		Some("test"),
+	)
+	.await?;
+	let sql = "
+		INFO FOR DB;
+		DEFINE FUNCTION fn::greet() { RETURN 'Hello' };
+		DEFINE TABLE person;
+		CREATE person:test;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	assert!(res.remove(0).result.is_ok());
+	assert!(res.remove(0).result.is_ok());
+	assert!(denied(res.remove(0).result));
+	assert!(denied(res.remove(0).result));
+	//
+	// The manager role allows editing, but not users and access methods
+	//
+	let mut ses = Session::default().with_ns("test").with_db("test");
+	surrealdb::iam::verify::basic(&dbs, &mut ses, "manager", "manager", Some("test"), Some("test"))
+		.await?;
+	let sql = "
+		DEFINE TABLE person;
+		CREATE person:test;
+		DEFINE USER intruder ON DATABASE PASSWORD 'intruder' ROLES OWNER;
+		DEFINE ROLE intruder ON DATABASE GRANT EDIT;
+		DEFINE ACCESS intruder ON DATABASE TYPE JWT ALGORITHM HS512 KEY 'secret';
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	assert!(res.remove(0).result.is_ok());
+	assert!(res.remove(0).result.is_ok());
+	assert!(denied(res.remove(0).result));
+	assert!(denied(res.remove(0).result));
+	assert!(denied(res.remove(0).result));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_table_relation() -> Result<(), Error> {
 	let sql = "
@@ -3131,6 +3548,7 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	)?;
@@ -3153,6 +3571,7 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	)?;
@@ -3175,6 +3594,7 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing | other SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	)?;
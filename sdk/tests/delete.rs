@@ -42,6 +42,40 @@ async fn delete() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn delete_return_projection() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test SET name = 'Tester', age = 42;
+		DELETE person:test RETURN { id, name, deleted: $after = NONE };
+		DELETE person:test RETURN name, age + 1 AS next;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:test,
+				name: 'Tester',
+				deleted: true
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
@@ -96,6 +96,85 @@ async fn field_definition_value_assert_failure() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_check_constraint() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD price ON product CHECK price > 0 AND price < 1000000;
+		DEFINE FIELD sale ON product VALUE $value OR price CHECK sale <= price;
+		CREATE product:one SET price = 10, sale = 2;
+		CREATE product:two SET price = 0;
+		CREATE product:three;
+		UPDATE product:one SET price = 1;
+		UPDATE product:one SET price = 100, sale = 50;
+		SELECT * FROM product;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	t.expect_error(
+		"Found 0 for field `price`, with record `product:two`, but field must satisfy the CHECK constraint: price > 0 AND price < 1000000",
+	)?;
+	t.expect_error(
+		"Found NONE for field `price`, with record `product:three`, but field must satisfy the CHECK constraint: price > 0 AND price < 1000000",
+	)?;
+	t.expect_error(
+		"Found 2 for field `sale`, with record `product:one`, but field must satisfy the CHECK constraint: sale <= price",
+	)?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: product:one, price: 100, sale: 50 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_on_delete_references() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD author ON post TYPE record<user> ON DELETE CASCADE;
+		DEFINE FIELD owner ON team TYPE record<user> ON DELETE RESTRICT;
+		DEFINE FIELD members ON team TYPE array<record<user>> ON DELETE SET NONE;
+		CREATE user:one, user:two;
+		CREATE post:one SET author = user:one;
+		CREATE post:two SET author = user:two;
+		CREATE team:one SET owner = user:two, members = [user:one, user:two];
+		DELETE user:one;
+		SELECT * FROM post;
+		SELECT * FROM team;
+		DELETE user:two;
+		SELECT * FROM post;
+		DELETE team:one;
+		DELETE user:two;
+		SELECT * FROM post;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(8)?;
+	t.expect_val("[{ author: user:two, id: post:two }]")?;
+	t.expect_val("[{ id: team:one, members: [user:two], owner: user:two }]")?;
+	t.expect_error(
+		"Cannot delete record `user:two` as it is referenced by record `team:one` through field `owner`",
+	)?;
+	t.expect_val("[{ author: user:two, id: post:two }]")?;
+	t.skip_ok(2)?;
+	t.expect_val("[]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_computed() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD full_name ON person COMPUTED string::concat(first, ' ', last);
+		DEFINE INDEX full_name ON person FIELDS full_name;
+		CREATE person:one SET first = 'Tobie', last = 'Morgan Hitchcock';
+		UPDATE person:one SET last = 'Hitchcock', full_name = 'Someone Else';
+		SELECT * FROM person WHERE full_name = 'Tobie Hitchcock';
+		SELECT * FROM person WHERE full_name = 'Tobie Morgan Hitchcock';
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	t.expect_val(
+		"[{ first: 'Tobie', full_name: 'Tobie Hitchcock', id: person:one, last: 'Hitchcock' }]",
+	)?;
+	t.expect_val("[]")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_value_assert_success() -> Result<(), Error> {
 	let sql = "
@@ -1120,3 +1199,44 @@ async fn field_definition_array_any() -> Result<(), Error> {
 	)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn field_definition_vector() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE doc SCHEMAFULL;
+		DEFINE FIELD embedding ON doc TYPE vector<f32, 3>;
+		DEFINE FIELD counts ON doc TYPE option<vector<i16, 2>>;
+		INFO FOR TABLE doc;
+		CREATE doc:1 SET embedding = [1, 2.5, 3], counts = [1, 2];
+		CREATE doc:2 SET embedding = [1, 2];
+		CREATE doc:3 SET embedding = [1, 'a', 3];
+		CREATE doc:4 SET embedding = [1, 2, 3], counts = [1, 40000];
+		SELECT VALUE vector::distance::dot(embedding, [1, 1, 1]) FROM doc;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	t.expect_val(
+		"
+{
+	events: {  },
+	fields: {
+		counts: 'DEFINE FIELD counts ON doc TYPE option<vector<i16, 2>> PERMISSIONS FULL',
+		embedding: 'DEFINE FIELD embedding ON doc TYPE vector<f32, 3> PERMISSIONS FULL'
+	},
+	indexes: {  },
+	lives: {  },
+	tables: {  }
+}
+		",
+	)?;
+	t.expect_val("[{ counts: [1, 2], embedding: [1f, 2.5f, 3f], id: doc:1 }]")?;
+	t.expect_error("Expected a vector<f32, 3> but the array had 2 items")?;
+	t.expect_error(
+		"Found 'a' for field `embedding`, with record `doc:3`, but expected a vector<f32, 3>",
+	)?;
+	t.expect_error(
+		"Found 40000 for field `counts`, with record `doc:4`, but expected a option<vector<i16, 2>>",
+	)?;
+	t.expect_val("[-6.5f]")?;
+	Ok(())
+}
@@ -203,6 +203,26 @@ async fn function_array_combine() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_array_chunk() -> Result<(), Error> {
+	let sql = r#"
+		RETURN array::chunk([0, 1, 2, 3], 2);
+		RETURN array::chunk([0, 1, 2], 2);
+		RETURN [0, 1, 2, 3, 4].chunk(3);
+		RETURN array::chunk([], 2);
+		RETURN array::chunk([0, 1, 2], 0);
+	"#;
+	let error = "Incorrect arguments for function array::chunk(). The second argument must be an integer greater than 0";
+	Test::new(sql)
+		.await?
+		.expect_val("[[0, 1], [2, 3]]")?
+		.expect_val("[[0, 1], [2]]")?
+		.expect_val("[[0, 1, 2], [3, 4]]")?
+		.expect_val("[]")?
+		.expect_error(error)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_array_clump() -> Result<(), Error> {
 	let sql = r#"
@@ -1293,6 +1313,26 @@ async fn function_array_windows() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_array_zip() -> Result<(), Error> {
+	let sql = r#"
+		RETURN array::zip([1, 2, 3], ['a', 'b', 'c']);
+		RETURN array::zip([1, 2, 3], ['a']);
+		RETURN ['x', 'y'].zip([true, false, true]);
+		RETURN array::zip([], [1, 2]);
+		RETURN array::zip([1, 2], 3);
+	"#;
+	let error = "Incorrect arguments for function array::zip(). Argument 2 was the wrong type. Expected a array but found 3";
+	Test::new(sql)
+		.await?
+		.expect_val("[[1, 'a'], [2, 'b'], [3, 'c']]")?
+		.expect_val("[[1, 'a']]")?
+		.expect_val("[['x', true], ['y', false]]")?
+		.expect_val("[]")?
+		.expect_error(error)?;
+	Ok(())
+}
+
 // --------------------------------------------------
 // bytes
 // --------------------------------------------------
@@ -1446,6 +1486,52 @@ async fn function_crypto_sha512() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_crypto_hmac_sha256() -> Result<(), Error> {
+	let sql = r#"
+		RETURN crypto::hmac::sha256('Jefe', 'what do ya want for nothing?');
+		RETURN crypto::hmac::sha256('', '');
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from("b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_crypto_ed25519_verify() -> Result<(), Error> {
+	let sql = r#"
+		LET $key = 'd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a';
+		LET $sig = 'e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b';
+		RETURN crypto::ed25519::verify($key, '', $sig);
+		RETURN crypto::ed25519::verify($key, 'tampered', $sig);
+		RETURN crypto::ed25519::verify(
+			'3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c',
+			'r',
+			'92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00'
+		);
+		RETURN crypto::ed25519::verify($key, '', 'abc');
+		RETURN crypto::ed25519::verify($key, '', 'not hex');
+		RETURN crypto::ed25519::verify(1, '', $sig);
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(2)?;
+	test.expect_val("true")?;
+	test.expect_val("false")?;
+	test.expect_val("true")?;
+	test.expect_error("Incorrect arguments for function crypto::ed25519::verify(). The signature must be a valid hex encoded string.")?;
+	test.expect_error("Incorrect arguments for function crypto::ed25519::verify(). The signature must be a valid hex encoded string.")?;
+	test.expect_error("Incorrect arguments for function crypto::ed25519::verify(). The public key must be bytes or a hex encoded string, but found 1.")?;
+	Ok(())
+}
+
 // --------------------------------------------------
 // duration
 // --------------------------------------------------
@@ -1642,6 +1728,67 @@ async fn function_duration_weeks() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_duration_round() -> Result<(), Error> {
+	let sql = r#"
+		RETURN duration::round(1h29m, 1h);
+		RETURN duration::round(1h30m, 1h);
+		RETURN duration::round(7m31s, 15s);
+		RETURN 2d11h.round(1d);
+		RETURN duration::round(1h, 0ns);
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("1h");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("2h");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("7m30s");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("2d");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_duration_clamp() -> Result<(), Error> {
+	let sql = r#"
+		RETURN duration::clamp(30s, 1m, 1h);
+		RETURN duration::clamp(30m, 1m, 1h);
+		RETURN duration::clamp(2h, 1m, 1h);
+		RETURN duration::clamp(2h, 1h, 1m);
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("1m");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("30m");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("1h");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_duration_years() -> Result<(), Error> {
 	let sql = r#"
@@ -1960,6 +2107,68 @@ async fn function_parse_geo_distance() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_geo_distance_along_path() -> Result<(), Error> {
+	let sql = r#"
+		LET $line = { type: 'LineString', coordinates: [[0, 0], [0, 1], [1, 1]] };
+		RETURN geo::distance($line) = geo::distance((0, 0), (0, 1)) + geo::distance((0, 1), (1, 1));
+		RETURN geo::distance({ type: 'MultiLineString', coordinates: [[[0, 0], [0, 1]], [[1, 1], [1, 2]]] })
+			= geo::distance((0, 0), (0, 1)) + geo::distance((1, 1), (1, 2));
+		RETURN geo::distance((0, 0));
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(1)?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(true));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(true));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::None);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_geo_buffer() -> Result<(), Error> {
+	let sql = r#"
+		LET $area = geo::buffer((0, 0), 1000);
+		RETURN $area.type;
+		RETURN [$area CONTAINS (0, 0), $area CONTAINS (0.005, 0), $area CONTAINS (0.02, 0)];
+		LET $path = geo::buffer({ type: 'LineString', coordinates: [[0, 0], [0.1, 0], [0.1, 0.1]] }, 500);
+		RETURN $path.type;
+		RETURN [$path CONTAINS (0.05, 0.002), $path CONTAINS (0.098, 0.05), $path CONTAINS (0.05, 0.05)];
+		RETURN geo::buffer({ type: 'MultiPoint', coordinates: [[0, 0], [1, 1]] }, 1000).type;
+		RETURN geo::buffer((0, 0), 0);
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(1)?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from("Polygon"));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::parse("[true, true, false]"));
+	//
+	test.skip_ok(1)?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from("Polygon"));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::parse("[true, true, false]"));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from("MultiPolygon"));
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_parse_geo_hash_encode() -> Result<(), Error> {
 	let sql = r#"
@@ -1999,6 +2208,29 @@ async fn function_parse_geo_hash_decode() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_geo_contains_and_intersects() -> Result<(), Error> {
+	let sql = r#"
+		LET $area = {
+			type: 'Polygon',
+			coordinates: [[[-0.2, 51.4], [0.0, 51.4], [0.0, 51.6], [-0.2, 51.6], [-0.2, 51.4]]]
+		};
+		RETURN geo::contains($area, (-0.118092, 51.509865));
+		RETURN geo::contains($area, (2.352222, 48.856613));
+		RETURN geo::intersects($area, { type: 'LineString', coordinates: [[-0.1, 51.5], [2.35, 48.85]] });
+		RETURN geo::intersects($area, (2.352222, 48.856613));
+		RETURN geo::contains($area, 'London');
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(1)?;
+	test.expect_val("true")?;
+	test.expect_val("false")?;
+	test.expect_val("true")?;
+	test.expect_val("false")?;
+	test.expect_error("Incorrect arguments for function geo::contains(). Argument 2 was the wrong type. Expected a geometry but found 'London'")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_geo_is_valid() -> Result<(), Error> {
 	let sql = r#"
@@ -2033,6 +2265,38 @@ async fn function_geo_is_valid() -> Result<(), Error> {
 	Ok(())
 }
 
+// --------------------------------------------------
+// graph
+// --------------------------------------------------
+
+#[tokio::test]
+async fn function_graph_shortest_path() -> Result<(), Error> {
+	let sql = r#"
+		RELATE city:a->road:1->city:b SET dist = 5;
+		RELATE city:b->road:2->city:c SET dist = 5;
+		RELATE city:a->road:3->city:c SET dist = 20;
+		RELATE city:c->road:4->city:d SET dist = 1;
+		RELATE city:a->rail:1->city:d;
+		RETURN graph::shortest_path(city:a, city:d, 'road');
+		RETURN graph::shortest_path(city:a, city:d, 'road', 'dist');
+		RETURN graph::shortest_path(city:a, city:d, 'rail');
+		RETURN graph::shortest_path(city:d, city:a, 'road');
+		RETURN graph::shortest_path(city:a, city:a, 'road');
+		RETURN graph::shortest_path(city:a, city:d, 'road', 'missing');
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(5)?;
+	test.expect_val("[city:a, road:3, city:c, road:4, city:d]")?;
+	test.expect_val("[city:a, road:1, city:b, road:2, city:c, road:4, city:d]")?;
+	test.expect_val("[city:a, rail:1, city:d]")?;
+	test.expect_val("NONE")?;
+	test.expect_val("[city:a]")?;
+	test.expect_error(
+		"Incorrect arguments for function graph::shortest_path(). The weight of edge road:1 must be a number which is not negative.",
+	)?;
+	Ok(())
+}
+
 // --------------------------------------------------
 // math
 // --------------------------------------------------
@@ -2895,6 +3159,52 @@ async fn function_object_len() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_object_merge_patch() -> Result<(), Error> {
+	let sql = r#"
+		RETURN object::merge_patch({ a: 'b', c: { d: 'e', f: 'g' } }, { a: 'z', c: { f: null } });
+		RETURN object::merge_patch({ a: 'b' }, ['c']);
+		RETURN { a: 1 }.merge_patch({ b: 2 });
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("{ a: 'z', c: { d: 'e' } }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("['c']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("{ a: 1, b: 2 }");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_object_patch() -> Result<(), Error> {
+	let sql = r#"
+		RETURN object::patch({ a: 1, b: [1, 2] }, [
+			{ op: 'add', path: '/b/-', value: 3 },
+			{ op: 'remove', path: '/a' },
+			{ op: 'replace', path: '/c', value: true },
+		]);
+		RETURN object::patch({ a: 1 }, [{ op: 'test', path: '/a', value: 2 }]);
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("{ b: [1, 2, 3], c: true }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result;
+	assert!(tmp.is_err());
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_object_values() -> Result<(), Error> {
 	let sql = r#"
@@ -4132,6 +4442,54 @@ async fn function_string_replace_with_regex() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_string_replace_regex() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::replace_regex("2024-03-01", /(\d+)-(\d+)-(\d+)/, "$3/$2/$1");
+		RETURN string::replace_regex("a1b22c333", "[0-9]+", "-");
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from("01/03/2024");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::from("a-b-c-");
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_string_matches_captures() -> Result<(), Error> {
+	let sql = r#"
+		CREATE log:1 SET line = "GET /index.html 200";
+		CREATE log:2 SET line = "POST /login 401";
+		SELECT VALUE string::matches_captures(line, /(?<method>[A-Z]+) (?<path>\S+) (?<status>\d+)/) FROM log;
+		RETURN string::matches_captures("key=value", /(\w+)=(\w+)/);
+		RETURN string::matches_captures("no match", /(\d+)/);
+	"#;
+	let mut test = Test::new(sql).await?;
+	test.skip_ok(2)?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse(
+		"[
+			{ method: 'GET', path: '/index.html', status: '200' },
+			{ method: 'POST', path: '/login', status: '401' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("['key=value', 'key', 'value']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::None);
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_string_matches() -> Result<(), Error> {
 	let sql = r#"
@@ -4735,6 +5093,90 @@ async fn function_string_similarity_jaro_winkler() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_string_similarity_levenshtein() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::similarity::levenshtein("", "");
+		RETURN string::similarity::levenshtein("kitten", "sitting");
+		RETURN string::similarity::levenshtein("abc", "xyz");
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0 - 3.0 / 7.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(0.0));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_string_similarity_ngram() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::similarity::ngram("", "");
+		RETURN string::similarity::ngram("Text", "text");
+		RETURN string::similarity::ngram("night", "nacht", 2);
+		RETURN string::similarity::ngram("abc", "xyz");
+		RETURN string::similarity::ngram("abc", "abc", 0);
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0 / 3.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(0.0));
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_string_similarity_soundex() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::similarity::soundex("Robert", "Rupert");
+		RETURN string::similarity::soundex("Robert", "Rubin");
+		RETURN string::similarity::soundex("Robert", "");
+		CREATE person:1 SET name = 'Smith';
+		CREATE person:2 SET name = 'Smyth';
+		CREATE person:3 SET name = 'Jones';
+		SELECT VALUE name FROM person WHERE string::similarity::soundex(name, 'Smith') = 1;
+		RETURN (SELECT name, string::similarity::levenshtein(name, 'Jonas') AS score FROM person ORDER BY score DESC LIMIT 1).name;
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(0.5));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::from(0.0));
+	//
+	test.skip_ok(3)?;
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::parse("['Smith', 'Smyth']"));
+	//
+	let tmp = test.next()?.result?;
+	assert_eq!(tmp, Value::parse("['Jones']"));
+	//
+	Ok(())
+}
+
 /// Test cases taken from [`strsim`](https://docs.rs/strsim/0.11.1/src/strsim/lib.rs.html#1254)
 #[tokio::test]
 async fn function_string_similarity_sorensen_dice() -> Result<(), Error> {
@@ -5099,6 +5541,62 @@ async fn function_time_format() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_time_format_timezone_and_locale() -> Result<(), Error> {
+	let sql = r#"
+		RETURN time::format(d"1987-06-22T08:30:45Z", "%Y-%m-%d %H:%M %Z", "Europe/Zurich");
+		RETURN time::format(d"1987-12-22T08:30:45Z", "%H:%M %:z", "America/New_York");
+		RETURN time::format(d"1987-06-22T08:30:45Z", "%A %e %B %Y", "Europe/Zurich", "de-CH");
+		RETURN time::format(d"1987-06-22T08:30:45Z", "%T", "Mars/Olympus_Mons");
+		RETURN time::format(d"1987-06-22T08:30:45Z", "%T", "UTC", "xx_XX");
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("'1987-06-22 10:30 CEST'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("'03:30 -05:00'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("'Montag 22 Juni 1987'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	let tmp = test.next()?.result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_time_in_timezone() -> Result<(), Error> {
+	let sql = r#"
+		RETURN time::in_timezone(d"1987-06-22T23:30:45Z", "Europe/Zurich");
+		RETURN time::day(time::in_timezone(d"1987-06-22T23:30:45Z", "Europe/Zurich"));
+		RETURN d"1987-06-22T08:30:45Z".in_timezone("Asia/Kolkata");
+	"#;
+	let mut test = Test::new(sql).await?;
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("d'1987-06-23T01:30:45Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("23");
+	assert_eq!(tmp, val);
+	//
+	let tmp = test.next()?.result?;
+	let val = Value::parse("d'1987-06-22T14:00:45Z'");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_time_group() -> Result<(), Error> {
 	let sql = r#"
@@ -6708,6 +7206,44 @@ async fn function_vector_similarity_pearson() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_vector_distance_cosine() -> Result<(), Error> {
+	test_queries(
+		r#"
+		RETURN vector::distance::cosine([1, 2, 3], [1, 2, 3]);
+		RETURN vector::distance::cosine([1, 2, 3], [-1, -2, -3]);
+		RETURN vector::distance::cosine([10, 50, 200], [400, 100, 20]);
+	"#,
+		&["0.0", "2.0", "0.8474178403755869"],
+	)
+	.await?;
+	check_test_is_error(
+		r"RETURN vector::distance::cosine([1, 2, 3], [4, 5]);",
+		&[
+			"Incorrect arguments for function vector::distance::cosine(). The two vectors must be of the same dimension.",
+		]).await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_vector_distance_dot() -> Result<(), Error> {
+	test_queries(
+		r#"
+		RETURN vector::distance::dot([1, 2, 3], [4, 5, 6]);
+		RETURN vector::distance::dot([1.5, 2], [2, -1]);
+		RETURN vector::distance::dot([1, 0], [0, 1]);
+	"#,
+		&["-32", "-1f", "0"],
+	)
+	.await?;
+	check_test_is_error(
+		r"RETURN vector::distance::dot([1, 2, 3], [4, 5]);",
+		&[
+			"Incorrect arguments for function vector::distance::dot(). The two vectors must be of the same dimension.",
+		]).await?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_vector_distance_euclidean() -> Result<(), Error> {
 	test_queries(
@@ -7037,6 +7573,139 @@ pub async fn function_http_error() -> Result<(), Error> {
 	Ok(())
 }
 
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_get_with_policy() -> Result<(), Error> {
+	use std::time::Duration;
+	use wiremock::{
+		matchers::{method, path},
+		Mock, ResponseTemplate,
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("GET"))
+		.and(path("/flaky"))
+		.respond_with(ResponseTemplate::new(503))
+		.up_to_n_times(2)
+		.expect(2)
+		.with_priority(1)
+		.mount(&server)
+		.await;
+	Mock::given(method("GET"))
+		.and(path("/flaky"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(2)
+		.with_priority(2)
+		.mount(&server)
+		.await;
+	Mock::given(method("GET"))
+		.and(path("/slow"))
+		.respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+		.mount(&server)
+		.await;
+
+	let uri = server.uri();
+	let query = format!(
+		r#"
+		RETURN http::get("{uri}/flaky", {{}}, {{ retries: 3, backoff: 1ms }});
+		RETURN http::get("{uri}/flaky", {{}}, {{ max_size: 4 }});
+		RETURN http::get("{uri}/slow", {{}}, {{ timeout: 100ms }});
+		RETURN http::get("{uri}/flaky", {{}}, {{ retries: 'three' }});
+		RETURN http::post("{uri}/flaky", {{}}, {{}}, {{ unknown: true }});
+		"#
+	);
+	let mut test = Test::new(&query).await?;
+	test.expect_val("'some text result'")?;
+	test.expect_error(
+		"There was an error processing a remote HTTP request: The response body exceeds the maximum size of 4 bytes",
+	)?;
+	test.expect_error_func(|e| matches!(e, Error::Http(_)))?;
+	test.expect_error(
+		"Incorrect arguments for function http::get(). The third argument should be an object with optional `timeout`, `retries`, `backoff` and `max_size` fields.",
+	)?;
+	test.expect_error(
+		"Incorrect arguments for function http::post(). The fourth argument should be an object with optional `timeout`, `retries`, `backoff` and `max_size` fields.",
+	)?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+pub async fn function_http_post_with_policy() -> Result<(), Error> {
+	use std::time::Duration;
+	use wiremock::{
+		matchers::{header, method, path},
+		Mock, ResponseTemplate,
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/flaky"))
+		.and(header("a-test-header", "with-a-test-value"))
+		.respond_with(ResponseTemplate::new(429))
+		.up_to_n_times(1)
+		.expect(1)
+		.with_priority(1)
+		.mount(&server)
+		.await;
+	Mock::given(method("POST"))
+		.and(path("/flaky"))
+		.and(header("a-test-header", "with-a-test-value"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(1)
+		.with_priority(2)
+		.mount(&server)
+		.await;
+	Mock::given(method("PUT"))
+		.and(path("/large"))
+		.respond_with(ResponseTemplate::new(200).set_body_string("some text result"))
+		.expect(1)
+		.mount(&server)
+		.await;
+	Mock::given(method("PATCH"))
+		.and(path("/slow"))
+		.respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+		.mount(&server)
+		.await;
+	Mock::given(method("DELETE"))
+		.and(path("/slow"))
+		.respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+		.mount(&server)
+		.await;
+
+	let uri = server.uri();
+	let query = format!(
+		r#"
+		RETURN http::post("{uri}/flaky", {{}}, {{ 'a-test-header': 'with-a-test-value' }}, {{ retries: 1, backoff: 1ms }});
+		RETURN http::put("{uri}/large", {{}}, {{}}, {{ max_size: 4 }});
+		RETURN http::patch("{uri}/slow", {{}}, {{}}, {{ timeout: 100ms }});
+		RETURN http::delete("{uri}/slow", {{}}, {{ timeout: 100ms }});
+		RETURN http::put("{uri}/large", {{}}, {{}}, {{}}, {{}});
+		RETURN http::delete("{uri}/slow", {{}}, {{}}, {{}});
+		"#
+	);
+	let mut test = Test::new(&query).await?;
+	test.expect_val("'some text result'")?;
+	test.expect_error(
+		"There was an error processing a remote HTTP request: The response body exceeds the maximum size of 4 bytes",
+	)?;
+	test.expect_error_func(|e| matches!(e, Error::Http(_)))?;
+	test.expect_error_func(|e| matches!(e, Error::Http(_)))?;
+	test.expect_error(
+		"Incorrect arguments for function http::put(). Expected 1, 2, 3, or 4 arguments.",
+	)?;
+	test.expect_error(
+		"Incorrect arguments for function http::delete(). Expected 1, 2, or 3 arguments.",
+	)?;
+
+	server.verify().await;
+
+	Ok(())
+}
+
 #[cfg(all(feature = "http", feature = "scripting"))]
 #[tokio::test]
 pub async fn function_http_get_from_script() -> Result<(), Error> {
@@ -7271,3 +7940,26 @@ async fn function_custom_typed_returns() -> Result<(), Error> {
 		.expect_error(error)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn function_custom_typed_args() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE PARAM $tier VALUE 'gold';
+		DEFINE FUNCTION fn::discount($price: number, $tier: option<string>) -> number {
+			RETURN IF $tier = 'gold' { $price - 20 } ELSE { $price };
+		};
+		RETURN fn::discount(100);
+		RETURN fn::discount(100, 'gold');
+		RETURN fn::discount('cheap');
+		RETURN fn::discount(100, 1);
+	"#;
+	Test::new(sql)
+		.await?
+		.expect_val("None")?
+		.expect_val("None")?
+		.expect_val("100")?
+		.expect_val("80")?
+		.expect_error("Incorrect arguments for function fn::discount(). Expected a value of type 'number' for argument $price, but found 'cheap'.")?
+		.expect_error("Incorrect arguments for function fn::discount(). Expected a value of type 'option<string>' for argument $tier, but found 1.")?;
+	Ok(())
+}
@@ -1,7 +1,7 @@
 mod parse;
 use parse::Parse;
 mod helpers;
-use helpers::new_ds;
+use helpers::{new_ds, Test};
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -470,3 +470,63 @@ async fn geometry_inner_access() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn geometry_spatial_index() -> Result<(), Error> {
+	let sql = "
+		CREATE city:london SET location = (-0.118092, 51.509865);
+		CREATE city:paris SET location = (2.352222, 48.856613);
+		CREATE city:greenwich SET location = (0.0, 51.4769);
+		DEFINE INDEX city_location ON city FIELDS location SPATIAL;
+		CREATE city:berlin SET location = (13.404954, 52.520008);
+		LET $area = {
+			type: 'Polygon',
+			coordinates: [[[-0.2, 51.4], [0.1, 51.4], [0.1, 51.6], [-0.2, 51.6], [-0.2, 51.4]]]
+		};
+		SELECT id FROM city WHERE location INSIDE $area ORDER BY id;
+		SELECT id FROM city WHERE geo::contains($area, location) EXPLAIN;
+		SELECT id FROM city WHERE geo::intersects(location, $area) ORDER BY id;
+		SELECT id FROM city WHERE geo::distance(location, (-0.118092, 51.509865)) < 20000 ORDER BY id;
+		SELECT id FROM city WHERE geo::distance(location, (13.4, 52.5)) <= 1000000 ORDER BY id;
+		UPDATE city:london SET location = (2.35, 48.85);
+		SELECT id FROM city WHERE location INSIDE $area ORDER BY id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(13)?;
+	t.skip_ok(6)?;
+	t.expect_val("[{ id: city:greenwich }, { id: city:london }]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'city_location',
+						operator: 'INTERSECTS',
+						value: {
+							type: 'Polygon',
+							coordinates: [[[-0.2, 51.4], [0.1, 51.4], [0.1, 51.6], [-0.2, 51.6], [-0.2, 51.4]]]
+						}
+					},
+					table: 'city'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[{ id: city:greenwich }, { id: city:london }]")?;
+	// Radius queries
+	t.expect_val("[{ id: city:greenwich }, { id: city:london }]")?;
+	t.expect_val(
+		"[{ id: city:berlin }, { id: city:greenwich }, { id: city:london }, { id: city:paris }]",
+	)?;
+	// Updated records are moved in the index
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: city:greenwich }]")?;
+	Ok(())
+}
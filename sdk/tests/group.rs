@@ -895,3 +895,110 @@ async fn select_count_range_keys_only() -> Result<(), Error> {
 	)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_statistical_aggregates_group_by() -> Result<(), Error> {
+	let sql = r#"
+		CREATE test:1 SET g = 'a', v = 1;
+		CREATE test:2 SET g = 'a', v = 4;
+		CREATE test:3 SET g = 'a', v = 3;
+		CREATE test:4 SET g = 'a', v = 2;
+		CREATE test:5 SET g = 'b', v = 10;
+		SELECT g, math::median(v) AS median, math::percentile(v, 25) AS p25, math::variance(v) AS variance, math::stddev(v) AS stddev FROM test GROUP BY g;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	//
+	t.skip_ok(5)?;
+	//
+	t.expect_val(
+		r#"[
+				{
+					g: 'a',
+					median: 2.5,
+					p25: 1.75,
+					stddev: 1.2909944487358056,
+					variance: 1.6666666666666667
+				},
+				{
+					g: 'b',
+					median: 10f,
+					p25: 10f,
+					stddev: 0f,
+					variance: 0f
+				}
+			]"#,
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_and_order_spill_to_temporary_directory() -> Result<(), Error> {
+	std::env::set_var("SURREAL_EXTERNAL_MEMORY_BUDGET", "2");
+	let sql = r#"
+		FOR $i IN 0..10 {
+			CREATE item SET v = $i, k = $i % 5;
+		};
+		SELECT k, count() AS count, math::sum(v) AS sum, math::max(v) AS max FROM item GROUP BY k;
+		SELECT VALUE v FROM item ORDER BY v DESC LIMIT 3;
+	"#;
+	let dbs = new_ds().await?.with_temporary_directory(Some(std::env::temp_dir()));
+	let mut t = Test::with_ds(dbs, sql).await?;
+	t.expect_size(3)?;
+	//
+	t.skip_ok(1)?;
+	//
+	t.expect_val(
+		r#"[
+				{ count: 2, k: 0, max: 5, sum: 5 },
+				{ count: 2, k: 1, max: 6, sum: 7 },
+				{ count: 2, k: 2, max: 7, sum: 9 },
+				{ count: 2, k: 3, max: 8, sum: 11 },
+				{ count: 2, k: 4, max: 9, sum: 13 }
+			]"#,
+	)?;
+	//
+	t.expect_val("[9, 8, 7]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_regression_and_correlation() -> Result<(), Error> {
+	let sql = r#"
+		CREATE metric SET host = 'a', cpu = 1, load = 3;
+		CREATE metric SET host = 'a', cpu = 2, load = 5;
+		CREATE metric SET host = 'a', cpu = 3, load = 7;
+		CREATE metric SET host = 'b', cpu = 1, load = 6;
+		CREATE metric SET host = 'b', cpu = 2, load = 4;
+		CREATE metric SET host = 'b', cpu = 3, load = 2;
+		SELECT
+			host,
+			math::correlation([cpu, load]) AS correlation,
+			math::covariance([cpu, load]) AS covariance,
+			math::linear_regression([cpu, load]) AS regression
+		FROM metric GROUP BY host;
+		RETURN math::linear_regression([[0, 1], [1, 3], [2, 5]]);
+		RETURN math::correlation([1, 2, 3]);
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(6)?;
+	t.expect_val(
+		"[
+			{
+				correlation: 1f,
+				covariance: 2f,
+				host: 'a',
+				regression: { intercept: 1f, slope: 2f }
+			},
+			{
+				correlation: -1f,
+				covariance: -2f,
+				host: 'b',
+				regression: { intercept: 8f, slope: -2f }
+			}
+		]",
+	)?;
+	t.expect_val("{ intercept: 1f, slope: 2f }")?;
+	t.expect_error("Incorrect arguments for function math::correlation(). The argument must be an array of [x, y] number pairs.")?;
+	Ok(())
+}
@@ -117,6 +117,31 @@ async fn idiom_graph_with_filter_should_be_flattened() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn idiom_graph_recursive_traversal() -> Result<(), Error> {
+	let sql = r#"
+		CREATE person:1, person:2, person:3, person:4;
+		RELATE person:1->knows:1->person:2;
+		RELATE person:2->knows:2->person:3;
+		RELATE person:3->knows:3->person:1;
+		RELATE person:3->knows:4->person:4;
+		person:1->knows{1..2}->person;
+		person:1->knows{2..5}->person;
+		person:1->knows{3}->person.id;
+		person:4<-knows{..10}<-person;
+		SELECT id, ->knows{1..5}->person AS reach FROM person:2;
+	"#;
+	Test::new(sql)
+		.await?
+		.skip_ok(5)?
+		.expect_val("[person:2, person:3]")?
+		.expect_val("[person:3, person:4]")?
+		.expect_val("[person:4]")?
+		.expect_val("[person:3, person:2, person:1]")?
+		.expect_val("[{ id: person:2, reach: [person:3, person:1, person:4] }]")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn idiom_optional_after_value_should_pass_through() -> Result<(), Error> {
 	let sql = r#"
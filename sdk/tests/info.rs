@@ -2,10 +2,14 @@ mod helpers;
 use helpers::*;
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use regex::Regex;
 use surrealdb::dbs::Session;
+use surrealdb::err::Error;
 use surrealdb::iam::Role;
+use surrealdb::sql::Value;
 
 #[tokio::test]
 async fn info_for_root() {
@@ -290,8 +294,8 @@ async fn permissions_checks_info_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"],
     ];
 
 	let test_cases = [
@@ -554,7 +558,7 @@ async fn access_info_redacted() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: { access: "DEFINE ACCESS access ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"#.to_string();
+			r#"{ accesses: { access: "DEFINE ACCESS access ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, analyzers: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, types: {  }, users: {  } }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -627,7 +631,7 @@ async fn access_info_redacted_structure() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: [{ base: 'DATABASE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'RECORD' }, name: 'access' }], analyzers: [], configs: [], functions: [], models: [], params: [], tables: [], users: [] }"#.to_string();
+			r#"{ accesses: [{ base: 'DATABASE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'RECORD' }, name: 'access' }], analyzers: [], configs: [], functions: [], models: [], params: [], tables: [], types: [], users: [] }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -652,10 +656,52 @@ async fn function_info_structure() {
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 	let out_expected =
-		r#"{ accesses: [], analyzers: [], configs: [], functions: [{ args: [['name', 'string']], block: "{ RETURN 'Hello, ' + $name + '!'; }", name: 'example', permissions: true, returns: 'string' }], models: [], params: [], tables: [], users: [] }"#.to_string();
+		r#"{ accesses: [], analyzers: [], configs: [], functions: [{ args: [['name', 'string']], block: "{ RETURN 'Hello, ' + $name + '!'; }", name: 'example', permissions: true, returns: 'string' }], models: [], params: [], tables: [], types: [], users: [] }"#.to_string();
 	let out_str = out.unwrap().to_string();
 	assert_eq!(
 		out_str, out_expected,
 		"Output '{out_str}' doesn't match expected output '{out_expected}'",
 	);
 }
+
+#[tokio::test]
+async fn info_for_queries_and_kill_query() -> Result<(), Error> {
+	let dbs = Arc::new(new_ds().await?);
+	let ses = Session::owner().with_ns("ns").with_db("db");
+	// Start a long running query in the background
+	let task = {
+		let dbs = dbs.clone();
+		let ses = ses.clone();
+		tokio::spawn(async move {
+			dbs.execute("SLEEP 500ms; CREATE person:one;", &ses, None).await.unwrap()
+		})
+	};
+	// Wait until both queries are listed as running
+	let id = loop {
+		let mut res = dbs.execute("INFO FOR QUERIES", &ses, None).await?;
+		let Value::Array(list) = res.remove(0).result? else {
+			panic!("Expected an array of running queries");
+		};
+		if list.len() == 2 {
+			assert_eq!(list[0].pick(&["ns".into()]), Value::from("ns"));
+			break list[0].pick(&["id".into()]);
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	};
+	// Kill the long running query
+	let sql = format!("KILL QUERY {id}");
+	let mut res = dbs.execute(&sql, &ses, None).await?;
+	res.remove(0).result?;
+	// The remaining statements should have been cancelled
+	let res = task.await.unwrap();
+	assert_eq!(res.len(), 2);
+	for res in res {
+		assert!(matches!(res.result, Err(Error::QueryCancelled)), "{:?}", res.result);
+	}
+	// The killed query should no longer be running
+	let mut res = dbs.execute(&sql, &ses, None).await?;
+	assert!(matches!(res.remove(0).result, Err(Error::KillStatement { .. })));
+	let mut res = dbs.execute("SELECT * FROM person", &ses, None).await?;
+	assert_eq!(res.remove(0).result?, Value::from(Vec::<Value>::new()));
+	Ok(())
+}
@@ -260,6 +260,37 @@ async fn insert_statement_duplicate_key_update() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn insert_statement_duplicate_key_update_concurrent() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX email ON TABLE user COLUMNS email UNIQUE;
+		INSERT INTO user [
+			{ email: 'tobie@surrealdb.com', visits: 1 },
+			{ email: 'tobie@surrealdb.com', visits: 1 },
+			{ email: 'tobie@surrealdb.com', visits: 1 },
+			{ email: 'tobie@surrealdb.com', visits: 1 },
+			{ email: 'tobie@surrealdb.com', visits: 1 },
+		] ON DUPLICATE KEY UPDATE visits += 1 PARALLEL;
+		SELECT email, visits FROM user;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ email: 'tobie@surrealdb.com', visits: 5 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
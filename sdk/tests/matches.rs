@@ -750,3 +750,115 @@ async fn select_where_matches_analyser_with_mapper() -> Result<(), Error> {
 	t.skip_ok(2)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_matches_analyser_with_cjk_tokenizers() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER bigram TOKENIZERS blank,class,ngram(2) FILTERS lowercase,stopwords('the');
+		DEFINE ANALYZER words TOKENIZERS blank,class,dictionary('../tests/data/dictionary-zh.txt') FILTERS stopwords('的','是');
+		CREATE t:1 SET text = '我们是北京大学的学生';
+		CREATE t:2 SET text = '北京的大学很多';
+		DEFINE INDEX bigram_idx ON TABLE t COLUMNS text SEARCH ANALYZER bigram BM25;
+		SELECT id FROM t WHERE text @@ '北京大学';
+		SELECT id FROM t WHERE text @@ '大学' ORDER BY id;
+		RETURN search::analyze('words', '我们是北京大学的学生');";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.skip_ok(5)?;
+	t.expect_val("[{ id: t:1 }]")?;
+	t.expect_val("[{ id: t:1 }, { id: t:2 }]")?;
+	t.expect_val("['我们', '北京大学', '学生']")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_using_index_and_fragments() -> Result<(), Error> {
+	let sql = r"
+		CREATE blog:1 SET content = 'The quick brown fox jumps over the lazy dog. A long digression about nothing in particular follows here. Finally the fox rests.';
+		CREATE blog:2 SET content = ['A fox.', 'Nothing to see here.'];
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_content ON blog FIELDS content SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT id, search::fragments('<b>', '</b>', 1, 30) AS fragments FROM blog WHERE content @1@ 'fox' ORDER BY id;
+		SELECT id, search::fragments('<b>', '</b>', 1) AS fragments FROM blog WHERE content @1@ 'lazy dog';
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(4)?;
+	t.expect_val(
+		"[
+			{
+				id: blog:1,
+				fragments: ['quick brown <b>fox</b> jumps over', 'Finally the <b>fox</b> rests.']
+			},
+			{
+				id: blog:2,
+				fragments: ['A <b>fox</b>.']
+			}
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{
+				id: blog:1,
+				fragments: ['The quick brown fox jumps over the <b>lazy</b> <b>dog</b>. A long digression about nothing in']
+			}
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_phrase_and_proximity() -> Result<(), Error> {
+	let sql = r#"
+		CREATE blog:1 SET content = 'The quick brown fox jumps over the lazy dog';
+		CREATE blog:2 SET content = 'The brown quick fox sleeps';
+		CREATE blog:3 SET content = ['The lazy cat', 'A quick dog'];
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_content ON blog FIELDS content SEARCH ANALYZER simple BM25;
+		SELECT VALUE id FROM blog WHERE content @@ 'quick brown' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE content @@ '"quick brown"' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE content @@ '"Lazy Dog"' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE content @@ 'fox NEAR/1 jumps' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE content @@ 'quick NEAR/2 fox' ORDER BY id;
+		SELECT VALUE id FROM blog WHERE content @@ 'quick NEAR/1 fox' ORDER BY id;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(11)?;
+	t.skip_ok(5)?;
+	t.expect_val("[blog:1, blog:2]")?;
+	// The terms of a phrase must follow each other
+	t.expect_val("[blog:1]")?;
+	// Within the same string
+	t.expect_val("[blog:1]")?;
+	// The terms must be within the given distance
+	t.expect_val("[blog:1]")?;
+	t.expect_val("[blog:1, blog:2]")?;
+	t.expect_val("[blog:2]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_with_facets() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX product_title ON product FIELDS title SEARCH ANALYZER simple BM25;
+		DEFINE INDEX product_category ON product FIELDS category SEARCH ANALYZER simple BM25;
+		CREATE product:1 SET title = 'Red running shoes', category = 'Sport', price = 80;
+		CREATE product:2 SET title = 'Blue running shoes', category = 'Sport', price = 90;
+		CREATE product:3 SET title = 'Leather shoes', category = 'Formal', price = 120;
+		CREATE product:4 SET title = 'Running shorts', category = 'Sport', price = 30;
+		SELECT search::facets('category') AS categories FROM product WHERE title @@ 'shoes' GROUP ALL;
+		SELECT search::facets('category') AS categories FROM product WHERE price < 100 GROUP ALL;
+		SELECT id, search::facets('category') AS categories FROM product WHERE title @@ 'shorts';
+		SELECT search::facets('price') FROM product:1;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(11)?;
+	t.skip_ok(7)?;
+	t.expect_val("[{ categories: { formal: 1, sport: 2 } }]")?;
+	t.expect_val("[{ categories: { sport: 3 } }]")?;
+	// Without grouping, the terms of each record are returned
+	t.expect_val("[{ id: product:4, categories: ['sport'] }]")?;
+	t.expect_error("Incorrect arguments for function search::facets(). There is no full-text index on the field 'price'.")?;
+	Ok(())
+}
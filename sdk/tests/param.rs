@@ -33,6 +33,7 @@ async fn define_global_param() -> Result<(), Error> {
 			models: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
 			tables: {},
+			types: {},
 			users: {},
 		}",
 	);
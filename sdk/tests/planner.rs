@@ -2614,6 +2614,46 @@ async fn select_with_non_boolean_expression() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_range_with_order_and_limit_pushdown() -> Result<(), Error> {
+	//
+	let sql = "
+		DEFINE INDEX time ON TABLE session COLUMNS time;
+		CREATE session:1 SET time = d'2024-07-01T01:00:00Z';
+		CREATE session:2 SET time = d'2024-06-30T23:00:00Z';
+		CREATE session:3 SET time = d'2024-07-01T02:00:00Z';
+		CREATE session:4 SET time = d'2024-06-30T23:30:00Z';
+		CREATE session:5 SET time = d'2024-07-01T03:00:00Z';
+		EXPLAIN SELECT * FROM session WHERE time > d'2024-06-30T23:00:00Z' ORDER BY time LIMIT 2 START 1;
+		SELECT * FROM session WHERE time > d'2024-06-30T23:00:00Z' ORDER BY time LIMIT 2 START 1;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(6)?;
+	// The range scan is already in the index order
+	let tmp = t.next_value()?;
+	let Value::Array(items) = tmp else {
+		panic!("expected an array");
+	};
+	let pushdown = items.last().unwrap();
+	assert_eq!(pushdown.pick(&["operation".into()]), Value::from("Pushdown"));
+	assert_eq!(pushdown.pick(&["detail".into(), "limit".into()]), Value::from(true));
+	assert_eq!(pushdown.pick(&["detail".into(), "order".into()]), Value::from(true));
+	//
+	t.expect_val(
+		"[
+			{
+				id: session:1,
+				time: d'2024-07-01T01:00:00Z'
+			},
+			{
+				id: session:3,
+				time: d'2024-07-01T02:00:00Z'
+			}
+		]",
+	)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_from_standard_index_ascending() -> Result<(), Error> {
 	//
@@ -3064,8 +3104,8 @@ async fn select_composite_index(unique: bool) -> Result<(), Error> {
 				detail: {
 					plan: {
 						index: 't_idx',
-						operator: '=',
-						value: false
+						operator: 'prefix',
+						value: [false, 2]
 					},
 					table: 't'
 				},
@@ -3100,6 +3140,103 @@ async fn select_composite_unique_index() -> Result<(), Error> {
 	select_composite_index(true).await
 }
 
+async fn select_composite_index_prefix(unique: bool) -> Result<(), Error> {
+	//
+	let sql = format!(
+		"
+		DEFINE INDEX t_idx ON TABLE t COLUMNS a, b, c {};
+		CREATE t:1 SET a = 1, b = 'x', c = 1;
+		CREATE t:2 SET a = 1, b = 'y', c = 2;
+		CREATE t:3 SET a = 2, b = 'x', c = 3;
+		CREATE t:4 SET a = 3, b = 'x', c = 4;
+		SELECT VALUE id FROM t WHERE a = 1 AND b = 'x' EXPLAIN;
+		SELECT VALUE id FROM t WHERE a = 1 AND b = 'x';
+		SELECT VALUE id FROM t WHERE c = 1 AND b = 'x' AND a = 1.0;
+		SELECT VALUE id FROM t WHERE a = 1 AND c = 2;
+		SELECT VALUE id FROM t WHERE a >= 2 EXPLAIN;
+		SELECT VALUE id FROM t WHERE a >= 2;
+		SELECT VALUE id FROM t WHERE a > 1 AND a < 3;
+		SELECT VALUE id FROM t WHERE a IN [1, 3];
+	",
+		if unique {
+			"UNIQUE"
+		} else {
+			""
+		}
+	);
+	let mut t = Test::new(&sql).await?;
+	//
+	t.expect_size(13)?;
+	t.skip_ok(5)?;
+	//
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 't_idx',
+						operator: 'prefix',
+						value: [1, 'x']
+					},
+					table: 't'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[t:1]")?;
+	t.expect_val("[t:1]")?;
+	t.expect_val("[t:2]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						from: {
+							inclusive: true,
+							value: 2
+						},
+						index: 't_idx',
+						to: {
+							inclusive: false,
+							value: NONE
+						}
+					},
+					table: 't'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[t:3, t:4]")?;
+	t.expect_val("[t:3]")?;
+	t.expect_val("[t:1, t:2, t:4]")?;
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_composite_standard_index_prefix() -> Result<(), Error> {
+	select_composite_index_prefix(false).await
+}
+
+#[tokio::test]
+async fn select_composite_unique_index_prefix() -> Result<(), Error> {
+	select_composite_index_prefix(true).await
+}
+
 #[tokio::test]
 async fn select_where_index_boolean_behaviour() -> Result<(), Error> {
 	let sql = r"
@@ -3227,3 +3364,256 @@ async fn select_memory_ordered_collector() -> Result<(), Error> {
 	// but well, my apologies if that even happen ¯\_(ツ)_/¯
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_with_index_hint_must_exist() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX idx_email ON person FIELDS email;
+		CREATE person:tobie SET email = 'tobie@surrealdb.com';
+		CREATE company:surrealdb SET email = 'info@surrealdb.com';
+		SELECT VALUE id FROM person, company WITH INDEX idx_email WHERE email = 'tobie@surrealdb.com';
+		SELECT VALUE id FROM person WITH INDEX idx_mail WHERE email = 'tobie@surrealdb.com';
+		SELECT VALUE id FROM company WITH INDEX idx_email WHERE email = 'info@surrealdb.com';
+		SELECT VALUE id FROM person WITH NOINDEX WHERE email = 'tobie@surrealdb.com';
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	t.expect_val("[person:tobie]")?;
+	t.expect_error("The index 'idx_mail' does not exist")?;
+	t.expect_error("The index 'idx_email' does not exist")?;
+	t.expect_val("[person:tobie]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_record_ids_from_in_list() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET age = 20;
+		CREATE person:2 SET age = 30;
+		CREATE person:3 SET age = 40;
+		LET $ids = [person:3, person:1, other:1, person:4, person:1];
+		SELECT VALUE id FROM person WHERE id IN $ids;
+		SELECT VALUE id FROM person WHERE id IN $ids AND age > 30;
+		SELECT VALUE id FROM person WHERE $ids CONTAINS id;
+		SELECT VALUE id FROM person WHERE id IN $ids EXPLAIN;
+		SELECT VALUE id FROM person WITH NOINDEX WHERE id IN $ids;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	t.expect_val("[person:1, person:3]")?;
+	t.expect_val("[person:3]")?;
+	t.expect_val("[person:1, person:3]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					count: 3,
+					table: 'person'
+				},
+				operation: 'Iterate Records'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[person:1, person:3]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_function_index() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX email ON user COLUMNS string::lowercase(email) UNIQUE;
+		CREATE user:1 SET email = 'Tobie@SurrealDB.com';
+		CREATE user:2 SET email = 'tobie@surrealdb.com';
+		CREATE user:3 SET email = 'Jaime@SurrealDB.com';
+		SELECT VALUE id FROM user WHERE string::lowercase(email) = 'tobie@surrealdb.com' EXPLAIN;
+		SELECT VALUE id FROM user WHERE string::lowercase(email) = 'tobie@surrealdb.com';
+		SELECT VALUE id FROM user WHERE email = 'jaime@surrealdb.com';
+		INFO FOR TABLE user;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(2)?;
+	t.expect_error(
+		"Database index `email` already contains 'tobie@surrealdb.com', with record `user:1`",
+	)?;
+	t.skip_ok(1)?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'email',
+						operator: '=',
+						value: 'tobie@surrealdb.com'
+					},
+					table: 'user'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[user:1]")?;
+	t.expect_val("[]")?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			indexes: {
+				email: 'DEFINE INDEX email ON user FIELDS string::lowercase(email) UNIQUE'
+			},
+			lives: {},
+			tables: {}
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_table_statistics() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX age ON person FIELDS age;
+		DEFINE INDEX status ON person FIELDS status;
+		INSERT INTO person [
+			{ id: 1, age: 1, status: 'active' },
+			{ id: 2, age: 2, status: 'active' },
+			{ id: 3, age: 3, status: 'active' },
+			{ id: 4, age: 4, status: 'active' },
+			{ id: 5, age: 5, status: 'active' },
+			{ id: 6, age: 6, status: 'active' },
+			{ id: 7, age: 7, status: 'active' },
+			{ id: 8, age: 8, status: 'active' },
+			{ id: 9, age: 9, status: 'active' },
+			{ id: 10, age: 10, status: 'inactive' }
+		];
+		SELECT VALUE id FROM person WHERE age > 0 AND status = 'inactive' EXPLAIN;
+		ANALYZE TABLE person;
+		SELECT VALUE id FROM person WHERE age > 0 AND status = 'inactive' EXPLAIN;
+		SELECT VALUE id FROM person WHERE age > 0 AND status = 'inactive';
+		SELECT VALUE id FROM person WHERE age > 0 EXPLAIN;
+		SELECT VALUE id FROM person WHERE age = 3 EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	// Without statistics the range is used
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						from: {
+							inclusive: false,
+							value: 0
+						},
+						index: 'age',
+						to: {
+							inclusive: false,
+							value: NONE
+						}
+					},
+					table: 'person'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val(
+		"{
+			count: 10,
+			indexes: {
+				age: {
+					distinct: 10,
+					entries: 10
+				},
+				status: {
+					distinct: 2,
+					entries: 10
+				}
+			}
+		}",
+	)?;
+	// With statistics the most selective index is used
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'status',
+						operator: '=',
+						value: 'inactive'
+					},
+					table: 'person'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[person:10]")?;
+	// A range matching every record is cheaper as a table scan
+	t.expect_val(
+		"[
+			{
+				detail: {
+					table: 'person'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'The table statistics estimate that a table scan is cheaper'
+				},
+				operation: 'Fallback'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'age',
+						operator: '=',
+						value: 3
+					},
+					table: 'person'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
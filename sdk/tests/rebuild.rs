@@ -83,3 +83,42 @@ async fn rebuild_index_statement() -> Result<(), Error> {
 	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
 	Ok(())
 }
+
+#[tokio::test]
+async fn rebuild_index_concurrently() -> Result<(), Error> {
+	let sql = "
+		CREATE book:1 SET isbn = '978-1803234694';
+		CREATE book:2 SET isbn = '978-1492052593';
+		DEFINE INDEX uniq_isbn ON book FIELDS isbn UNIQUE;
+		REBUILD INDEX uniq_isbn ON book CONCURRENTLY;
+		SLEEP 1s;
+		INFO FOR INDEX uniq_isbn ON book;
+		INFO FOR TABLE book;
+		SELECT VALUE id FROM book WHERE isbn = '978-1492052593';
+		CREATE book:3 SET isbn = '978-1492052593';
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	t.expect_val(
+		"{
+			building: { status: 'built' }
+		}",
+	)?;
+	// The definition is unchanged
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			indexes: {
+				uniq_isbn: 'DEFINE INDEX uniq_isbn ON book FIELDS isbn UNIQUE'
+			},
+			lives: {},
+			tables: {}
+		}",
+	)?;
+	t.expect_val("[book:2]")?;
+	t.expect_error(
+		"Database index `uniq_isbn` already contains '978-1492052593', with record `book:2`",
+	)?;
+	Ok(())
+}
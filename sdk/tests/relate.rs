@@ -290,6 +290,7 @@ async fn relate_enforced() -> Result<(), Error> {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
 		edge: 'DEFINE TABLE edge TYPE RELATION ENFORCED SCHEMALESS PERMISSIONS NONE'
 	},
+	types: {},
 	users: {}
 	}",
 	);
@@ -1228,6 +1228,93 @@ async fn select_destructure() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_count_from_maintained_row_count() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one, person:two, person:three;
+		DELETE person:two;
+		SELECT count() FROM person GROUP ALL;
+		SELECT count() AS total FROM person GROUP ALL;
+		BEGIN;
+		CREATE person:four;
+		SELECT count() FROM person GROUP ALL;
+		COMMIT;
+		SELECT count() FROM person GROUP ALL;
+		REMOVE TABLE person;
+		CREATE person:five;
+		SELECT count() FROM person GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	for _ in 0..2 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 2 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ total: 2 }]");
+	assert_eq!(tmp, val);
+	res.remove(0).result?;
+	// The uncommitted changes are counted within the transaction
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 3 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 3 }]");
+	assert_eq!(tmp, val);
+	//
+	for _ in 0..2 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_by_collate_locale() -> Result<(), Error> {
+	let sql = "
+		CREATE item:1 SET name = 'Zebra';
+		CREATE item:2 SET name = 'Äpfel';
+		CREATE item:3 SET name = 'Bär';
+		SELECT VALUE name FROM item ORDER BY name COLLATE 'de';
+		SELECT VALUE name FROM item ORDER BY name COLLATE 'sv';
+		SELECT VALUE name FROM item ORDER BY name COLLATE 'de' DESC;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Äpfel', 'Bär', 'Zebra']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Bär', 'Zebra', 'Äpfel']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Zebra', 'Bär', 'Äpfel']");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_field_from_graph_no_flattening() -> Result<(), Error> {
 	let sql = "
@@ -1429,3 +1516,223 @@ async fn select_order_by_rand_large() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_explain_statement() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		EXPLAIN SELECT * FROM person WHERE name = 'Tobie' LIMIT 1;
+		EXPLAIN FULL SELECT * FROM person WHERE name = 'Tobie' ORDER BY name;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	res.remove(0).result?;
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					estimate: 1,
+					table: 'person',
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			},
+			{
+				detail: {
+					condition: false,
+					limit: true,
+				},
+				operation: 'Pushdown'
+			},
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let Value::Array(items) = tmp else {
+		panic!("expected an array");
+	};
+	let ops: Vec<String> =
+		items.iter().map(|v| v.pick(&["operation".into()]).as_raw_string()).collect();
+	assert_eq!(ops, vec!["Iterate Table", "Collector", "Pushdown", "Stage", "Stage", "Fetch"]);
+	let stages: Vec<(String, Value)> = items[3..5]
+		.iter()
+		.map(|v| {
+			(
+				v.pick(&["detail".into(), "stage".into()]).as_raw_string(),
+				v.pick(&["detail".into(), "rows".into()]),
+			)
+		})
+		.collect();
+	assert_eq!(
+		stages,
+		vec![("Iterate".to_owned(), Value::from(1)), ("Order".to_owned(), Value::from(1))]
+	);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_functions() -> Result<(), Error> {
+	let sql = "
+		CREATE sale:1 SET customer = 'a', amount = 10, time = 1;
+		CREATE sale:2 SET customer = 'a', amount = 20, time = 2;
+		CREATE sale:3 SET customer = 'b', amount = 5, time = 1;
+		CREATE sale:4 SET customer = 'a', amount = 20, time = 3;
+		SELECT
+			id,
+			row_number() OVER (PARTITION BY customer ORDER BY time) AS num,
+			rank() OVER (PARTITION BY customer ORDER BY amount) AS rank,
+			lag(amount, 1, 0) OVER (PARTITION BY customer ORDER BY time) AS prev,
+			math::sum(amount) OVER (PARTITION BY customer ORDER BY time) AS total,
+			count() OVER (PARTITION BY customer) AS sales
+		FROM sale ORDER BY id;
+		SELECT VALUE lead(id) OVER (ORDER BY time DESC, id) FROM sale;
+		SELECT customer, row_number() OVER () AS num FROM sale GROUP BY customer;
+		SELECT row_number() OVER () + 1 AS num FROM sale;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: sale:1, num: 1, rank: 1, prev: 0, total: 10, sales: 3 },
+			{ id: sale:2, num: 2, rank: 2, prev: 10, total: 30, sales: 3 },
+			{ id: sale:3, num: 1, rank: 1, prev: 0, total: 5, sales: 1 },
+			{ id: sale:4, num: 3, rank: 2, prev: 20, total: 50, sales: 3 },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[sale:3, sale:1, NONE, sale:2]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidFunction { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidFunction { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_running_aggregates() -> Result<(), Error> {
+	let sql = "
+		CREATE sale:1 SET amount = 10, time = 1;
+		CREATE sale:2 SET amount = 30, time = 2;
+		CREATE sale:3 SET amount = 20, time = 2;
+		CREATE sale:4 SET amount = 5, time = 3;
+		SELECT
+			id,
+			math::sum(amount) OVER (ORDER BY time) AS sum,
+			math::mean(amount) OVER (ORDER BY time) AS mean,
+			math::min(amount) OVER (ORDER BY time) AS min,
+			math::max(amount) OVER (ORDER BY time) AS max,
+			count(amount > 15) OVER (ORDER BY time) AS large,
+			math::median(amount) OVER (ORDER BY time) AS median
+		FROM sale ORDER BY id;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: sale:1, sum: 10, mean: 10f, min: 10, max: 10, large: 0, median: 10f },
+			{ id: sale:2, sum: 60, mean: 20f, min: 10, max: 30, large: 2, median: 20f },
+			{ id: sale:3, sum: 60, mean: 20f, min: 10, max: 30, large: 2, median: 20f },
+			{ id: sale:4, sum: 65, mean: 16.25f, min: 5, max: 30, large: 2, median: 15f },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_optimised_subqueries_and_record_filters() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', age = 20;
+		CREATE person:2 SET name = 'Jaime', age = 15;
+		CREATE person:3 SET name = 'Jamie', age = 30;
+		RELATE person:1->likes:a->person:2;
+		RELATE person:1->likes:b->person:3;
+		SELECT name FROM (SELECT * FROM person WHERE age > 18) WHERE name != 'Tobie';
+		SELECT * FROM (SELECT * FROM person WHERE age > 18) EXPLAIN;
+		SELECT *, name FROM person:2;
+		SELECT VALUE out FROM person:1->likes WHERE id != likes:a;
+		SELECT VALUE id FROM person:1, person:2, person:3 WHERE id INSIDE [person:2, person:3] AND age > 18;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	for _ in 0..5 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'Jamie' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					table: 'person'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 15, id: person:2, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:3]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -261,6 +261,7 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 			models: {},
 			params: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
+			types: {},
 			users: {},
 		}",
 	);
@@ -161,6 +161,74 @@ async fn define_foreign_table() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_foreign_table_group_by_alias() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE daily AS
+			SELECT
+				count() AS total,
+				time::floor(at, 1d) AS day,
+				math::max(value) AS max
+			FROM event
+			GROUP BY day
+		;
+		CREATE event:1 SET at = d'2024-01-01T10:00:00Z', value = 1;
+		CREATE event:2 SET at = d'2024-01-01T12:00:00Z', value = 5;
+		CREATE event:3 SET at = d'2024-01-02T12:00:00Z', value = 3;
+		SELECT * FROM daily;
+		DELETE event:2;
+		SELECT * FROM daily;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 4)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				day: d'2024-01-01T00:00:00Z',
+				id: daily:[d'2024-01-01T00:00:00Z'],
+				max: 5,
+				total: 2
+			},
+			{
+				day: d'2024-01-02T00:00:00Z',
+				id: daily:[d'2024-01-02T00:00:00Z'],
+				max: 3,
+				total: 1
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				day: d'2024-01-01T00:00:00Z',
+				id: daily:[d'2024-01-01T00:00:00Z'],
+				max: 1,
+				total: 1
+			},
+			{
+				day: d'2024-01-02T00:00:00Z',
+				id: daily:[d'2024-01-02T00:00:00Z'],
+				max: 3,
+				total: 1
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_foreign_table_no_doubles() -> Result<(), Error> {
 	// From: https://github.com/surrealdb/surrealdb/issues/3556
@@ -0,0 +1,8 @@
+#![cfg(all(feature = "kv-mem", feature = "kv-testsuite"))]
+
+use surrealdb_core::kvs::testsuite::{self, WriteConflicts};
+
+#[tokio::test]
+async fn memory_conformance() {
+	testsuite::run(|| "memory".to_string(), WriteConflicts::LastWriteWins).await;
+}
@@ -1,6 +1,6 @@
 mod helpers;
 mod parse;
-use helpers::Test;
+use helpers::{new_ds, Test};
 use surrealdb::err::Error;
 
 #[tokio::test]
@@ -31,3 +31,27 @@ async fn statement_timeouts() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn statement_row_limits() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..5|;
+		SELECT * FROM person LIMIT 3;
+		SELECT count() FROM person WHERE id < person:4 GROUP ALL;
+		SELECT * FROM person;
+		SELECT count() FROM person GROUP ALL;
+		DELETE person;
+	";
+	let error = "The query was aborted because it processed more than the maximum of 3 rows";
+	let ds = new_ds().await?.with_query_row_limit(Some(3));
+	Test::with_ds(ds, sql)
+		.await?
+		.expect_val("[{ id: person:1 }, { id: person:2 }, { id: person:3 }, { id: person:4 }, { id: person:5 }]")?
+		.expect_val("[{ id: person:1 }, { id: person:2 }, { id: person:3 }]")?
+		.expect_val("[{ count: 3 }]")?
+		.expect_error(error)?
+		// The count of a table is read from its maintained row count, without scanning rows
+		.expect_val("[{ count: 5 }]")?
+		.expect_val("[]")?;
+	Ok(())
+}
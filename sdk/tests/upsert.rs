@@ -3,6 +3,7 @@ use parse::Parse;
 mod helpers;
 use crate::helpers::Test;
 use helpers::new_ds;
+use std::sync::Arc;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
@@ -1006,6 +1007,38 @@ async fn check_permissions_auth_disabled() {
 	}
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn upsert_concurrent_updates() -> Result<(), Error> {
+	let dbs = Arc::new(new_ds().await?);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	// Increment the same record from many concurrent transactions
+	let tasks: Vec<_> = (0..20)
+		.map(|_| {
+			let dbs = dbs.clone();
+			let ses = ses.clone();
+			tokio::spawn(async move {
+				let sql = "UPSERT counter:one SET visits += 1";
+				dbs.execute(sql, &ses, None).await.unwrap().remove(0).result
+			})
+		})
+		.collect();
+	// Conflicting transactions fail instead of losing updates
+	let mut count = 0;
+	for task in tasks {
+		match task.await.unwrap() {
+			Ok(_) => count += 1,
+			Err(e) => assert!(e.is_retryable(), "{e:?}"),
+		}
+	}
+	assert!(count > 0);
+	let res = &mut dbs.execute("SELECT VALUE visits FROM counter:one", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(&format!("[{count}]"));
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn upsert_none_removes_field() -> Result<(), Error> {
 	let sql = "
@@ -564,3 +564,82 @@ async fn check_hnsw_persistence() -> Result<(), Error> {
 	)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_hnsw_order_by_distance_limit() -> Result<(), Error> {
+	let sql = r"
+		CREATE pts:1 SET point = [1,2,3,4], flag = true;
+		CREATE pts:2 SET point = [4,5,6,7], flag = false;
+		CREATE pts:3 SET point = [8,9,10,11], flag = true;
+		DEFINE INDEX hnsw_pts ON pts FIELDS point HNSW DIMENSION 4 DIST EUCLIDEAN TYPE F32 EFC 500 M 12;
+		LET $pt = [2,3,4,5];
+		SELECT id, vector::distance::euclidean(point, $pt) AS dist FROM pts ORDER BY dist LIMIT 2;
+		SELECT id, vector::distance::euclidean(point, $pt) AS dist FROM pts ORDER BY dist LIMIT 2 EXPLAIN;
+		SELECT id, vector::distance::euclidean(point, $pt) AS dist FROM pts WHERE flag = true ORDER BY dist LIMIT 1 START 1;
+		SELECT id, vector::distance::manhattan(point, $pt) AS dist FROM pts ORDER BY dist LIMIT 2 EXPLAIN;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(5)?;
+	// The nearest neighbours are returned using the HNSW index
+	t.expect_val(
+		"[
+			{
+				id: pts:1,
+				dist: 2f
+			},
+			{
+				id: pts:2,
+				dist: 4f
+			}
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'hnsw_pts',
+						operator: '<|2,500|>',
+						value: [2,3,4,5]
+					},
+					table: 'pts',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	// The condition filters the neighbours, and START is included in the search
+	t.expect_val(
+		"[
+			{
+				id: pts:3,
+				dist: 12f
+			}
+		]",
+	)?;
+	// A different distance does not use the index
+	t.expect_val(
+		"[
+			{
+				detail: {
+					table: 'pts',
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
@@ -69,6 +69,31 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_CHANGEFEED_GC_INTERVAL", long = "changefeed-gc-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "10s")]
 	changefeed_gc_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove expired and superseded data",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_MAINTENANCE_INTERVAL", long = "maintenance-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	maintenance_interval: Duration,
+	#[arg(
+		help = "The interval at which to process queued asynchronous table events",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_EVENT_QUEUE_INTERVAL", long = "event-queue-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	event_queue_interval: Duration,
+	#[arg(
+		help = "The interval at which to deliver changefeeds to sinks",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SINK_INTERVAL", long = "sink-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	sink_interval: Duration,
+	#[arg(help = "The interval at which to deliver queued webhooks", help_heading = "Database")]
+	#[arg(env = "SURREAL_WEBHOOK_INTERVAL", long = "webhook-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	webhook_interval: Duration,
 	//
 	// Authentication
 	//
@@ -167,6 +192,10 @@ pub async fn init(
 		node_membership_check_interval,
 		node_membership_cleanup_interval,
 		changefeed_gc_interval,
+		maintenance_interval,
+		event_queue_interval,
+		sink_interval,
+		webhook_interval,
 		no_banner,
 		no_identification_headers,
 		..
@@ -196,7 +225,11 @@ pub async fn init(
 		.with_node_membership_refresh_interval(node_membership_refresh_interval)
 		.with_node_membership_check_interval(node_membership_check_interval)
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
-		.with_changefeed_gc_interval(changefeed_gc_interval);
+		.with_changefeed_gc_interval(changefeed_gc_interval)
+		.with_maintenance_interval(maintenance_interval)
+		.with_event_queue_interval(event_queue_interval)
+		.with_sink_interval(sink_interval)
+		.with_webhook_interval(webhook_interval);
 	// Configure the config
 	let config = Config {
 		bind: listen_addresses.first().cloned().unwrap(),
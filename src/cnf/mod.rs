@@ -71,6 +71,22 @@ pub static WEBSOCKET_MAX_MESSAGE_SIZE: LazyLock<usize> =
 pub static WEBSOCKET_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_CONCURRENT_REQUESTS", usize, 24);
 
+/// How many LIVE queries can be active on each WebSocket (defaults to 1000, 0 is unlimited)
+pub static WEBSOCKET_MAX_LIVE_QUERIES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_LIVE_QUERIES", usize, 1000);
+
+/// How many LIVE queries can be active for each user across all WebSockets (defaults to 0, which is unlimited)
+pub static WEBSOCKET_MAX_USER_LIVE_QUERIES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_USER_LIVE_QUERIES", usize, 0);
+
+/// How many notifications are sent to each WebSocket per second (defaults to 0, which is unlimited)
+pub static WEBSOCKET_MAX_NOTIFICATION_RATE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_NOTIFICATION_RATE", u32, 0);
+
+/// How many notifications can be waiting to be sent to each WebSocket (defaults to 10000)
+pub static WEBSOCKET_MAX_PENDING_NOTIFICATIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_PENDING_NOTIFICATIONS", usize, 10_000);
+
 /// What is the runtime thread memory stack size (defaults to 10MiB)
 pub static RUNTIME_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse_or_else!("SURREAL_RUNTIME_STACK_SIZE", usize, |_| {
@@ -25,6 +25,9 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TRANSACTION_TIMEOUT", long)]
 	#[arg(value_parser = super::cli::validator::duration)]
 	transaction_timeout: Option<Duration>,
+	#[arg(help = "The maximum number of rows that any single SELECT statement can process")]
+	#[arg(env = "SURREAL_QUERY_ROW_LIMIT", long)]
+	query_row_limit: Option<u64>,
 	#[arg(help = "Whether to allow unauthenticated access", help_heading = "Authentication")]
 	#[arg(env = "SURREAL_UNAUTHENTICATED", long = "unauthenticated")]
 	#[arg(default_value_t = false)]
@@ -82,6 +85,7 @@ Specifically denied network targets prevail over any other allowed outbound netw
 Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For example:
  - 'surrealdb.com', '127.0.0.1' or 'fd00::1' -> Match outbound connections to these hosts on any port
  - 'surrealdb.com:80', '127.0.0.1:80' or 'fd00::1:80' -> Match outbound connections to these hosts on port 80
+ - '*.surrealdb.com' or '*.surrealdb.com:443' -> Match outbound connections to any subdomain of this domain
  - '10.0.0.0/8' or 'fd00::/8' -> Match outbound connections to any host in these networks
 "#
 	)]
@@ -149,6 +153,7 @@ Specifically allowed network targets prevail over a general denial of outbound n
 Targets must be in the form of <host>[:<port>], <ipv4|ipv6>[/<mask>]. For example:
  - 'surrealdb.com', '127.0.0.1' or 'fd00::1' -> Match outbound connections to these hosts on any port
  - 'surrealdb.com:80', '127.0.0.1:80' or 'fd00::1:80' -> Match outbound connections to these hosts on port 80
+ - '*.surrealdb.com' or '*.surrealdb.com:443' -> Match outbound connections to any subdomain of this domain
  - '10.0.0.0/8' or 'fd00::/8' -> Match outbound connections to any host in these networks
 "#
 	)]
@@ -382,6 +387,7 @@ pub async fn init(
 		strict_mode,
 		query_timeout,
 		transaction_timeout,
+		query_row_limit,
 		unauthenticated,
 		capabilities,
 		temporary_directory,
@@ -400,6 +406,10 @@ pub async fn init(
 	if let Some(v) = transaction_timeout {
 		debug!("Maximum transaction processing timeout is {v:?}");
 	}
+	// Log specified query row limit
+	if let Some(v) = query_row_limit {
+		debug!("Maximum number of rows processed by a query is {v}");
+	}
 	// Log whether authentication is disabled
 	if unauthenticated {
 		warn!("❌🔒 IMPORTANT: Authentication is disabled. This is not recommended for production use. 🔒❌");
@@ -419,6 +429,7 @@ pub async fn init(
 		.with_strict_mode(strict_mode)
 		.with_query_timeout(query_timeout)
 		.with_transaction_timeout(transaction_timeout)
+		.with_query_row_limit(query_row_limit)
 		.with_auth_enabled(!unauthenticated)
 		.with_temporary_directory(temporary_directory)
 		.with_capabilities(capabilities);
@@ -10,6 +10,8 @@ pub enum ContentType {
 	ApplicationCbor,
 	ApplicationPack,
 	ApplicationOctetStream,
+	ApplicationJsonPatch,
+	ApplicationMergePatch,
 	Surrealdb,
 }
 
@@ -21,6 +23,8 @@ impl std::fmt::Display for ContentType {
 			ContentType::ApplicationCbor => write!(f, "application/cbor"),
 			ContentType::ApplicationPack => write!(f, "application/pack"),
 			ContentType::ApplicationOctetStream => write!(f, "application/octet-stream"),
+			ContentType::ApplicationJsonPatch => write!(f, "application/json-patch+json"),
+			ContentType::ApplicationMergePatch => write!(f, "application/merge-patch+json"),
 			ContentType::Surrealdb => write!(f, "application/surrealdb"),
 		}
 	}
@@ -45,6 +49,8 @@ impl Header for ContentType {
 			"application/cbor" => Ok(ContentType::ApplicationCbor),
 			"application/pack" => Ok(ContentType::ApplicationPack),
 			"application/octet-stream" => Ok(ContentType::ApplicationOctetStream),
+			"application/json-patch+json" => Ok(ContentType::ApplicationJsonPatch),
+			"application/merge-patch+json" => Ok(ContentType::ApplicationMergePatch),
 			"application/surrealdb" => Ok(ContentType::Surrealdb),
 			// TODO: Support more (all?) mime-types
 			_ => Err(headers::Error::invalid()),
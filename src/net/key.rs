@@ -214,6 +214,7 @@ async fn modify_all(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	content_type: Option<TypedHeader<ContentType>>,
 	Path(table): Path<String>,
 	Query(params): Query<Params>,
 	body: Bytes,
@@ -232,8 +233,14 @@ async fn modify_all(
 	// Parse the request body as JSON
 	match surrealdb::sql::value(data) {
 		Ok(data) => {
-			// Specify the request statement
-			let sql = "UPDATE type::table($table) MERGE $data";
+			// Specify the request statement for the patch format
+			let sql = match content_type.as_deref() {
+				Some(ContentType::ApplicationJsonPatch) => "UPDATE type::table($table) PATCH $data",
+				Some(ContentType::ApplicationMergePatch) => {
+					"UPDATE type::table($table) REPLACE object::merge_patch($this, $data)"
+				}
+				_ => "UPDATE type::table($table) MERGE $data",
+			};
 			// Specify the request variables
 			let vars = map! {
 				String::from("table") => Value::from(table),
@@ -469,6 +476,7 @@ async fn modify_one(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	content_type: Option<TypedHeader<ContentType>>,
 	Query(params): Query<Params>,
 	Path((table, id)): Path<(String, String)>,
 	body: Bytes,
@@ -492,8 +500,16 @@ async fn modify_one(
 	// Parse the request body as JSON
 	match surrealdb::sql::value(data) {
 		Ok(data) => {
-			// Specify the request statement
-			let sql = "UPSERT type::thing($table, $id) MERGE $data";
+			// Specify the request statement for the patch format
+			let sql = match content_type.as_deref() {
+				Some(ContentType::ApplicationJsonPatch) => {
+					"UPSERT type::thing($table, $id) PATCH $data"
+				}
+				Some(ContentType::ApplicationMergePatch) => {
+					"UPSERT type::thing($table, $id) REPLACE object::merge_patch($this, $data)"
+				}
+				_ => "UPSERT type::thing($table, $id) MERGE $data",
+			};
 			// Specify the request variables
 			let vars = map! {
 				String::from("table") => Value::from(table),
@@ -1,12 +1,15 @@
 use crate::cnf::{
-	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_PING_FREQUENCY,
+	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_MAX_LIVE_QUERIES,
+	WEBSOCKET_MAX_NOTIFICATION_RATE, WEBSOCKET_MAX_USER_LIVE_QUERIES, WEBSOCKET_PING_FREQUENCY,
 };
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
-use crate::rpc::response::{failure, IntoRpcResponse};
+use crate::rpc::notifier::Notifier;
+use crate::rpc::response::{failure, success, IntoRpcResponse};
+use crate::rpc::LiveQuery;
 use crate::rpc::CONN_CLOSED_ERR;
 use crate::telemetry;
-use crate::telemetry::metrics::ws::RequestContext;
+use crate::telemetry::metrics::ws::{NotificationContext, RequestContext};
 use crate::telemetry::traces::rpc::span_for_request;
 use axum::extract::ws::{Message, WebSocket};
 use futures_util::stream::{SplitSink, SplitStream};
@@ -15,6 +18,7 @@ use opentelemetry::trace::FutureExt;
 use opentelemetry::Context as TelemetryContext;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 use surrealdb::channel::{self, Receiver, Sender};
 use surrealdb::dbs::Session;
 #[cfg(surrealdb_unstable)]
@@ -24,6 +28,7 @@ use surrealdb::rpc::format::Format;
 use surrealdb::rpc::method::Method;
 use surrealdb::rpc::Data;
 use surrealdb::rpc::RpcContext;
+use surrealdb::rpc::RpcError;
 use surrealdb::sql::Array;
 use surrealdb::sql::Value;
 use tokio::sync::{RwLock, Semaphore};
@@ -44,6 +49,7 @@ pub struct Connection {
 	pub(crate) canceller: CancellationToken,
 	pub(crate) channels: (Sender<Message>, Receiver<Message>),
 	pub(crate) state: Arc<RpcState>,
+	pub(crate) notifier: Arc<Notifier>,
 	pub(crate) datastore: Arc<Datastore>,
 	#[cfg(surrealdb_unstable)]
 	pub(crate) gql_schema: SchemaCache<Pessimistic>,
@@ -70,6 +76,7 @@ impl Connection {
 			canceller: CancellationToken::new(),
 			channels: channel::bounded(*WEBSOCKET_MAX_CONCURRENT_REQUESTS),
 			state,
+			notifier: Arc::new(Notifier::default()),
 			#[cfg(surrealdb_unstable)]
 			gql_schema: SchemaCache::new(datastore.clone()),
 			datastore,
@@ -108,6 +115,7 @@ impl Connection {
 		tasks.spawn(Self::ping(rpc.clone(), internal_sender.clone()));
 		tasks.spawn(Self::read(rpc.clone(), receiver, internal_sender.clone()));
 		tasks.spawn(Self::write(rpc.clone(), sender, internal_receiver.clone()));
+		tasks.spawn(Self::notify(rpc.clone(), internal_sender.clone()));
 
 		// Wait until all tasks finish
 		while let Some(res) = tasks.join_next().await {
@@ -126,7 +134,7 @@ impl Connection {
 		// Remove all live queries
 		let mut gc = Vec::new();
 		state.live_queries.write().await.retain(|key, value| {
-			if value == &id {
+			if value.ws == id {
 				trace!("Removing live query: {}", key);
 				gc.push(*key);
 				return false;
@@ -172,6 +180,55 @@ impl Connection {
 		}
 	}
 
+	/// Send LIVE query notifications to the client
+	async fn notify(rpc: Arc<RwLock<Connection>>, internal_sender: Sender<Message>) {
+		// Get the notifications for this WebSocket
+		let notifier = rpc.read().await.notifier.clone();
+		// Get the WebSocket output format
+		let format = rpc.read().await.format;
+		// Clone the WebSocket cancellation token
+		let canceller = rpc.read().await.canceller.clone();
+		// Create the interval ticker, if notifications are rate limited
+		let mut interval = match *WEBSOCKET_MAX_NOTIFICATION_RATE {
+			0 => None,
+			rate => {
+				let mut interval = tokio::time::interval(Duration::from_secs(1) / rate);
+				interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+				Some(interval)
+			}
+		};
+		// Loop, and listen for notifications to send
+		loop {
+			// Wait until another notification can be sent
+			if let Some(interval) = interval.as_mut() {
+				tokio::select! {
+					//
+					biased;
+					// Check if this has shutdown
+					_ = canceller.cancelled() => break,
+					// Wait for the rate limit
+					_ = interval.tick() => {},
+				}
+			}
+			tokio::select! {
+				//
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Wait for the next notification
+				notification = notifier.next() => {
+					// Add metrics
+					let cx = TelemetryContext::new();
+					let not_ctx = NotificationContext::default()
+						.with_live_id(notification.id.to_string());
+					let cx = Arc::new(cx.with_value(not_ctx));
+					// Send the notification to the client
+					success(None, notification).send(cx, format, &internal_sender).await
+				},
+			}
+		}
+	}
+
 	/// Write messages to the client
 	async fn write(
 		rpc: Arc<RwLock<Connection>>,
@@ -373,6 +430,12 @@ impl Connection {
 			false => rpc.read().await.execute_immut(method, params).await.map_err(Into::into),
 		}
 	}
+
+	/// The user which is authenticated on this WebSocket, if any
+	fn user(&self) -> Option<String> {
+		let au = &self.session.au;
+		(!au.is_anon()).then(|| format!("{}{}", au.level(), au.id()))
+	}
 }
 
 impl RpcContext for Connection {
@@ -403,14 +466,37 @@ impl RpcContext for Connection {
 	const LQ_SUPPORT: bool = true;
 
 	async fn handle_live(&self, lqid: &Uuid) {
-		self.state.live_queries.write().await.insert(*lqid, self.id);
+		let live = LiveQuery {
+			ws: self.id,
+			user: self.user(),
+		};
+		self.state.live_queries.write().await.insert(*lqid, live);
 		trace!("Registered live query {} on websocket {}", lqid, self.id);
 	}
 
 	async fn handle_kill(&self, lqid: &Uuid) {
 		if let Some(id) = self.state.live_queries.write().await.remove(lqid) {
-			trace!("Unregistered live query {} on websocket {}", lqid, id);
+			trace!("Unregistered live query {} on websocket {}", lqid, id.ws);
+		}
+	}
+
+	async fn check_live_quota(&self, count: usize) -> Result<(), RpcError> {
+		let user = self.user();
+		let lives = self.state.live_queries.read().await;
+		// Check the number of live queries on this WebSocket
+		let max = *WEBSOCKET_MAX_LIVE_QUERIES;
+		if max > 0 && lives.values().filter(|v| v.ws == self.id).count() + count > max {
+			return Err(RpcError::LqLimitExceeded(max));
+		}
+		// Check the number of live queries for this user
+		let max = *WEBSOCKET_MAX_USER_LIVE_QUERIES;
+		if max > 0
+			&& user.is_some()
+			&& lives.values().filter(|v| v.user == user).count() + count > max
+		{
+			return Err(RpcError::LqLimitExceeded(max));
 		}
+		Ok(())
 	}
 
 	#[cfg(surrealdb_unstable)]
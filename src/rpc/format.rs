@@ -27,7 +27,9 @@ impl From<&ContentType> for Format {
 	fn from(value: &ContentType) -> Self {
 		match value {
 			ContentType::TextPlain => Format::None,
-			ContentType::ApplicationJson => Format::Json,
+			ContentType::ApplicationJson
+			| ContentType::ApplicationJsonPatch
+			| ContentType::ApplicationMergePatch => Format::Json,
 			ContentType::ApplicationCbor => Format::Cbor,
 			ContentType::ApplicationPack => Format::Msgpack,
 			ContentType::ApplicationOctetStream => Format::Unsupported,
@@ -1,13 +1,11 @@
 pub mod connection;
 pub mod failure;
 pub mod format;
+pub mod notifier;
 pub mod post_context;
 pub mod response;
 
 use crate::rpc::connection::Connection;
-use crate::rpc::response::success;
-use crate::telemetry::metrics::ws::NotificationContext;
-use opentelemetry::Context as TelemetryContext;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -21,8 +19,16 @@ static CONN_CLOSED_ERR: &str = "Connection closed normally";
 type WebSocket = Arc<RwLock<Connection>>;
 /// Mapping of WebSocket ID to WebSocket
 type WebSockets = RwLock<HashMap<Uuid, WebSocket>>;
-/// Mapping of LIVE Query ID to WebSocket ID
-type LiveQueries = RwLock<HashMap<Uuid, Uuid>>;
+/// Mapping of LIVE Query ID to the LIVE Query
+type LiveQueries = RwLock<HashMap<Uuid, LiveQuery>>;
+
+/// A LIVE Query which was initiated on a WebSocket
+pub struct LiveQuery {
+	/// The WebSocket which initiated the LIVE query
+	pub ws: Uuid,
+	/// The user which initiated the LIVE query
+	pub user: Option<String>,
+}
 
 pub struct RpcState {
 	/// Stores the currently connected WebSockets
@@ -60,7 +66,7 @@ pub(crate) async fn notifications(
 					// Find which WebSocket the notification belongs to
 					let found_ws = {
 						// We remove the lock asap
-						state.live_queries.read().await.get(&notification.id).cloned()
+						state.live_queries.read().await.get(&notification.id).map(|v| v.ws)
 					};
 					if let Some(id) = found_ws {
 						// Check to see if the WebSocket exists
@@ -70,19 +76,8 @@ pub(crate) async fn notifications(
 							state.web_sockets.read().await.get(&id).cloned()
 						};
 						if let Some(rpc) = maybe_ws {
-							// Serialize the message to send
-							let message = success(None, notification);
-							// Add metrics
-							let cx = TelemetryContext::new();
-							let not_ctx = NotificationContext::default()
-								  .with_live_id(id.to_string());
-							let cx = Arc::new(cx.with_value(not_ctx));
-							// Get the WebSocket output format
-							let format = rpc.read().await.format;
-							// get the WebSocket sending channel
-							let sender = rpc.read().await.channels.0.clone();
-							// Send the notification to the client
-							message.send(cx, format, &sender).await
+							// Queue the notification for the WebSocket
+							rpc.read().await.notifier.push(notification);
 						}
 					}
 				},
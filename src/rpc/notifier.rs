@@ -0,0 +1,156 @@
+//! Delivery of LIVE query notifications to a WebSocket.
+//!
+//! Notifications for each WebSocket are queued, and are sent to the client
+//! by a dedicated task for that WebSocket, so that a slow client can never
+//! hold up the delivery of notifications to any other client. The rate at
+//! which notifications are sent to each client can also be limited.
+//!
+//! While a notification is waiting to be sent, any later notification for
+//! the same LIVE query and record is coalesced with it, so that the client
+//! receives the latest state of the record, instead of every intermediate
+//! change. Notifications containing JSON Patch changes, which are sent for
+//! `LIVE SELECT DIFF` queries, are never coalesced, as each of the patches
+//! needs to be applied in order.
+use crate::cnf::WEBSOCKET_MAX_PENDING_NOTIFICATIONS;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use surrealdb::dbs::{Action, Notification};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+/// The notifications waiting to be sent to a WebSocket
+#[derive(Default)]
+pub(crate) struct Notifier {
+	/// The pending notifications
+	pending: Mutex<Pending>,
+	/// Wakes the task which sends the notifications
+	ready: Notify,
+}
+
+#[derive(Default)]
+struct Pending {
+	/// The pending notifications, in the order they are sent
+	queue: BTreeMap<u64, Notification>,
+	/// The position of the pending notification for each LIVE query and record
+	latest: HashMap<(Uuid, String), u64>,
+	/// The position of the next notification
+	next: u64,
+}
+
+impl Notifier {
+	/// Queue a notification to be sent to the WebSocket
+	pub(crate) fn push(&self, notification: Notification) {
+		let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+		// Get the live query and record for this notification
+		let key = (notification.id.0, notification.record.to_string());
+		// Check if there is a pending notification to coalesce with
+		if let Some(pos) = pending.latest.get(&key).copied() {
+			if let Some(prev) = pending.queue.get_mut(&pos) {
+				if !prev.result.is_array() && !notification.result.is_array() {
+					// The record was created and deleted before being sent
+					if let (Action::Create, Action::Delete) = (&prev.action, &notification.action) {
+						pending.queue.remove(&pos);
+						pending.latest.remove(&key);
+						return;
+					}
+					// Merge the changes into the pending notification
+					prev.action = match (&prev.action, notification.action) {
+						(Action::Create, Action::Update) => Action::Create,
+						(Action::Delete, Action::Create) => Action::Update,
+						(_, action) => action,
+					};
+					prev.result = notification.result;
+					prev.resume = prev.resume.or(notification.resume);
+					return;
+				}
+			}
+		}
+		// Check if there is space for another notification
+		if pending.queue.len() >= *WEBSOCKET_MAX_PENDING_NOTIFICATIONS {
+			warn!("Dropping a notification for live query {}, as the client is too slow", key.0);
+			return;
+		}
+		// Queue the notification
+		let pos = pending.next;
+		pending.next += 1;
+		pending.queue.insert(pos, notification);
+		pending.latest.insert(key, pos);
+		// Wake the sending task
+		self.ready.notify_one();
+	}
+
+	/// Wait for the next notification to send to the WebSocket
+	pub(crate) async fn next(&self) -> Notification {
+		loop {
+			if let Some(notification) = self.pop() {
+				return notification;
+			}
+			self.ready.notified().await;
+		}
+	}
+
+	/// Take the next notification to send, if there is one
+	fn pop(&self) -> Option<Notification> {
+		let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+		let (pos, notification) = pending.queue.pop_first()?;
+		let key = (notification.id.0, notification.record.to_string());
+		if pending.latest.get(&key) == Some(&pos) {
+			pending.latest.remove(&key);
+		}
+		Some(notification)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Notifier;
+	use surrealdb::dbs::{Action, Notification};
+	use surrealdb::sql::{Thing, Uuid, Value};
+
+	fn notification(id: Uuid, action: Action, record: &str, result: Value) -> Notification {
+		Notification::new(id, action, Value::Thing(Thing::from(("person", record))), result)
+	}
+
+	#[test]
+	fn notifications_are_coalesced() {
+		let id = Uuid::new_v4();
+		let notifier = Notifier::default();
+		notifier.push(notification(id, Action::Create, "one", Value::from(1)));
+		notifier.push(notification(id, Action::Create, "two", Value::from(1)));
+		notifier.push(notification(id, Action::Update, "one", Value::from(2)));
+		notifier.push(notification(id, Action::Update, "one", Value::from(3)));
+		// The updates are merged into the pending creation
+		let first = notifier.pop().unwrap();
+		assert_eq!(first.action, Action::Create);
+		assert_eq!(first.record, Value::Thing(Thing::from(("person", "one"))));
+		assert_eq!(first.result, Value::from(3));
+		let second = notifier.pop().unwrap();
+		assert_eq!(second.record, Value::Thing(Thing::from(("person", "two"))));
+		assert!(notifier.pop().is_none());
+		// Notifications after sending are not coalesced
+		notifier.push(notification(id, Action::Update, "one", Value::from(4)));
+		notifier.push(notification(id, Action::Delete, "two", Value::None));
+		assert_eq!(notifier.pop().unwrap().result, Value::from(4));
+		assert_eq!(notifier.pop().unwrap().action, Action::Delete);
+	}
+
+	#[test]
+	fn created_and_deleted_records_are_dropped() {
+		let id = Uuid::new_v4();
+		let notifier = Notifier::default();
+		notifier.push(notification(id, Action::Create, "one", Value::from(1)));
+		notifier.push(notification(id, Action::Delete, "one", Value::from(1)));
+		assert!(notifier.pop().is_none());
+	}
+
+	#[test]
+	fn patches_are_not_coalesced() {
+		let id = Uuid::new_v4();
+		let notifier = Notifier::default();
+		notifier.push(notification(id, Action::Update, "one", Value::parse("[{ op: 'add' }]")));
+		notifier.push(notification(id, Action::Update, "one", Value::parse("[{ op: 'remove' }]")));
+		assert_eq!(notifier.pop().unwrap().result, Value::parse("[{ op: 'add' }]"));
+		assert_eq!(notifier.pop().unwrap().result, Value::parse("[{ op: 'remove' }]"));
+		assert!(notifier.pop().is_none());
+	}
+}
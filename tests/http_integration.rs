@@ -1652,6 +1652,47 @@ mod http_integration {
 			);
 		}
 
+		// Modify one record with a JSON Patch
+		{
+			let res = client
+				.patch(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "application/json-patch+json")
+				.body(r#"[{"op": "replace", "path": "/name", "value": "json_patch"}]"#)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			// Verify the record has the new data
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(
+				body[0]["result"].as_array().unwrap()[0]["name"],
+				"json_patch",
+				"body: {body}"
+			);
+		}
+
+		// Modify one record with a JSON Merge Patch
+		{
+			let res = client
+				.patch(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "application/merge-patch+json")
+				.body(r#"{"name": null, "merged": true}"#)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			// Verify the record has been patched
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			let record = &body[0]["result"].as_array().unwrap()[0];
+			assert!(record["name"].is_null(), "body: {body}");
+			assert_eq!(record["merged"], true, "body: {body}");
+			assert_eq!(record["default"], "content", "body: {body}");
+		}
+
 		// Modify one record without authentication
 		{
 			// Try to modify the record